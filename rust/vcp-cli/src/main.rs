@@ -8,35 +8,180 @@
 //! vcp-cli parse-token family.safe.guide@1.2.0
 //! vcp-cli parse-csm1 N5+F+E
 //! vcp-cli encode-csm1 '{"persona":"Nanny","adherence_level":5,...}'
-//! vcp-cli hash <content-file>
-//! vcp-cli verify <manifest.json> <content-file>
+//! vcp-cli csm1 build --persona nanny --level 5 --scopes family,education --namespace ELEM
+//! vcp-cli validate-context '⏰🌅|📍🏡‖🧠focused:4' --strict
+//! vcp-cli context set --cognitive focused:4 --location home
+//! vcp-cli context merge '⏰🌅|📍🏡' '📍🏢‖🧠focused:4'
+//! vcp-cli hash <content-file> --report
+//! vcp-cli verify <manifest.json> <content-file> --max-size 1048576
+//! vcp-cli verify-url https://registry.example.org/b/manifest.json https://registry.example.org/b/content --trust trust.json
+//! vcp-cli pack <manifest.json> <content-file> --attach logo.png --out bundle.vcpb
+//! vcp-cli unpack bundle.vcpb --out ./bundle
+//! vcp-cli init bundle ./my-bundle --issuer example.org
+//! vcp-cli watch ./bundles
+//! vcp-cli expiry ./bundles --trust base.json --trust production.json --within 30
+//! vcp-cli scrub <failing-token.txt> > safe-to-share.txt
+//! vcp-cli explain 'N4+F+E:ACME@1.2.0'
+//! vcp-cli lint token.txt --fix
+//! vcp-cli lint-content constitution.md --strict
+//! vcp-cli diff constitution-v1.md constitution-v2.md
+//! vcp-cli capabilities --json
+//! vcp-cli conformance ./conformance
+//! vcp-cli decay-sim --dimension urgency --half-life 600 --duration 3600 --step 300
+//! vcp-cli keygen --out issuer.pem
+//! vcp-cli key inspect issuer.pem
+//! vcp-cli trust fingerprint trust.json --entity creed-space
+//! vcp-cli completions bash > /etc/bash_completion.d/vcp-cli
 //! ```
+//!
+//! ## Output
+//!
+//! Every command takes `--format text|json` (`--json` is short for
+//! `--format json`). Text output is for people and may change between
+//! releases; the JSON shapes below are stable. Fields marked `?` are
+//! omitted when not set.
+//!
+//! | Command | JSON on stdout |
+//! |---------|----------------|
+//! | `parse-token` | `{token, domain, approach, role, depth, canonical, full}` |
+//! | `parse-csm1` | `{code, encoded, active, maximum}` |
+//! | `parse-csm1-token` | the token object (text output is JSON too) |
+//! | `encode-csm1` | `{encoded}` |
+//! | `csm1 build` | `{code, encoded}` |
+//! | `parse-context` | the context object (text output is JSON too) |
+//! | `validate-context` | `[{severity, code, path, message}]` |
+//! | `context set`, `context merge` | `{wire, format, warnings}` |
+//! | `hash` | `{hash, changes?: [{kind, count, lines}]}` (`changes` with `--report`) |
+//! | `verify` | `{valid, code, error_code, message}` |
+//! | `verify-url` | `{valid, code, error_code, degraded, valid_until?}` |
+//! | `pack` | `{path, bytes, attachments}` |
+//! | `unpack` | `{path, attachments: [{name, bytes}], extracted_to?}` |
+//! | `init bundle` | `{path, files}` |
+//! | `watch` | one line per result: `{bundle, status, detail?}`, then `{bundles, failed}` after the first pass |
+//! | `expiry` | `{bundles: [{bundle, advisories: [{kind, ...}], error?}], needs_renewal, expired}` |
+//! | `scrub` | `{kind, output, expected_error?, actual_error?, reproduced}` |
+//! | `explain` | `{kind, entries: [{field, value, meaning}], warnings}` |
+//! | `lint` | `[{severity, code, location, message, fixable}]` |
+//! | `lint-content` | `[{severity, rule, line?, message}]` |
+//! | `diff` | the changelog object |
+//! | `conformance` | the conformance report |
+//! | `decay-sim` | `[{t, intensity, state}]` (text output is CSV with that header) |
+//! | `capabilities` | the capabilities object |
+//! | `keygen` | `{public_key, fingerprint, path?, secret?}` |
+//! | `key inspect` | `{type, public_key, fingerprint}` |
+//! | `trust fingerprint` | `[{entity, type, key_id, fingerprint, short, words, emoji}]` |
+//!
+//! `completions` always prints the script.
+//!
+//! Constitution files read by `hash`, `verify`, `pack` and `watch` may be
+//! UTF-8 with a BOM or UTF-16 (LE or BE, with or without a BOM); they are
+//! converted to UTF-8 before hashing, with a warning on stderr. `hash` and
+//! `verify` read content in chunks, so files of any size hash in constant
+//! memory; `verify` then fails with `size_exceeded` if the decoded text is
+//! over `--max-size` (the orchestrator's 256 KB limit by default).
+//!
+//! `--quiet` (`-q`) drops warnings and notes on stderr, and the text report
+//! of the checks below; JSON output, results and errors are still printed.
+//!
+//! ## Exit status
+//!
+//! | Status | Meaning |
+//! |--------|---------|
+//! | 0 | success |
+//! | 1 | any other error |
+//! | 2 | a check failed (`verify`, `verify-url`, `validate-context`, `lint`, `lint-content`, `conformance`, an unreadable bundle in `expiry`) |
+//! | 3 | the input could not be parsed (`VCP-E-1xxx`, malformed JSON) |
+//! | 4 | a file or URL could not be read or written |
+//! | 5 | `verify` or `verify-url` failed because the bundle is revoked |
+//! | 6 | `verify` or `verify-url` failed with `expired` or `not_yet_valid`; `expiry` found an expired bundle |
+//! | 7 | invalid command line |
+//!
+//! In JSON mode an error is written to
+//! stderr as one line, `{"error": {code, error_code?, message, span?, expected?}}`,
+//! where `code` is the `VcpError` variant name (or `CliError`), `error_code`
+//! its stable `VCP-E-NNNN` identifier and `span` is the `{offset, length}`
+//! byte range of the input at fault. Text mode prints the identifier in the
+//! heading, `error[VCP-E-1005]: ...`.
 
+use std::collections::BTreeSet;
+use std::fmt;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_json::{json, Value};
 
-use vcp_core::context::FullContext;
-use vcp_core::csm1::{Csm1Code, Csm1Token};
-use vcp_core::identity::VcpToken;
-use vcp_core::transport;
+use vcp_core::budget::BudgetEstimator;
+use vcp_core::composer::Constitution;
+use vcp_core::conformance::{self, VectorStatus};
+use vcp_core::content_lint::{self, ContentLintConfig};
+use vcp_core::context::{FullContext, WireFormat};
+use vcp_core::context_schema::{ContextSchema, ValidationIssue};
+use vcp_core::csm1::{Csm1Code, Csm1Token, Persona, Scope};
+use vcp_core::diff::{self, ConstitutionDiff};
+use vcp_core::error::VerificationCode;
+use vcp_core::explain;
+use vcp_core::extensions::personal::{
+    self as decay, DecayCurve, PersonalDimension as DecayDimensionKind, PersonalSignal,
+};
+use vcp_core::identity::{AliasMap, VcpToken};
+use vcp_core::ids::{IdGenerator, UuidV7Generator};
+use vcp_core::keys::{self, EncryptedKey, KeyFormat, KeyPair};
+use vcp_core::lint;
+use vcp_core::orchestrator::{self, FreshnessAdvisory, FreshnessPolicy, Orchestrator};
+use vcp_core::personal::{PersonalDimension, PersonalDimensionKind};
+use vcp_core::scrub::Scrubber;
+use vcp_core::situational::SituationalDimension;
+use vcp_core::transport::{
+    self, BundleArchive, ContentHasher, ReadSummary, TextEncoding, VerificationResult,
+};
+use vcp_core::trust::{MergeStrategy, TrustAnchor, TrustConfig};
+use vcp_core::VcpError;
 
 #[derive(Parser)]
 #[command(name = "vcp-cli")]
 #[command(about = "Value Context Protocol SDK command-line tools")]
 #[command(version)]
 struct Cli {
+    /// Output format; see the crate docs for the JSON shapes.
+    #[arg(long, global = true, value_enum, default_value_t)]
+    format: OutputFormat,
+    /// Short for --format json.
+    #[arg(long, global = true)]
+    json: bool,
+    /// Print only results and errors: no warnings or notes, and no text
+    /// report from checks, whose outcome is the exit status.
+    #[arg(long, short, global = true)]
+    quiet: bool,
     #[command(subcommand)]
     command: Commands,
 }
 
+/// How results and errors are printed.
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Parse a VCP/I identity token and display its components.
     ParseToken {
         /// Token string (e.g. "family.safe.guide@1.2.0").
         token: String,
+        /// JSON file mapping aliases to tokens (e.g. '{"fam.guide":
+        /// "family.safe.guide@1.2.0"}'); the token may then be an alias.
+        #[arg(long)]
+        aliases: Option<String>,
     },
 
     /// Parse a CSM-1 compact code and display its components.
@@ -58,16 +203,45 @@ enum Commands {
         json: String,
     },
 
+    /// CSM-1 code utilities.
+    Csm1 {
+        #[command(subcommand)]
+        action: Csm1Command,
+    },
+
     /// Parse a context wire-format string.
     ParseContext {
         /// Wire-format string.
         wire: String,
     },
 
+    /// Check a context for unknown categories, bad intensities and
+    /// conflicting signals.
+    ///
+    /// Exits with status 2 if any error-level issue is found.
+    ValidateContext {
+        /// Wire-format string or context JSON, or "-" for stdin.
+        #[arg(default_value = "-")]
+        input: String,
+        /// Treat unknown categories as errors.
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Build, update or combine contexts and print the wire string.
+    Context {
+        #[command(subcommand)]
+        action: ContextCommand,
+    },
+
     /// Compute SHA-256 content hash of a file.
     Hash {
         /// Path to the content file.
         path: String,
+        /// Also list what canonicalization changed (line endings,
+        /// trailing whitespace, ...), by line.
+        #[arg(long)]
+        report: bool,
     },
 
     /// Verify a bundle (manifest + content).
@@ -76,33 +250,833 @@ enum Commands {
         manifest: String,
         /// Path to the content file.
         content: String,
+        /// Largest content accepted, in UTF-8 bytes after decoding
+        /// (the orchestrator's limit by default).
+        #[arg(long, default_value_t = orchestrator::MAX_CONTENT_SIZE)]
+        max_size: usize,
+    },
+
+    /// Fetch a registry-hosted bundle and run the full verification
+    /// pipeline on it.
+    ///
+    /// URLs must be http or https on a standard port and must not point
+    /// at a private address; redirects are not followed. Downloads stop
+    /// at the orchestrator's size limits.
+    VerifyUrl {
+        /// URL of the manifest JSON.
+        manifest_url: String,
+        /// URL of the content.
+        content_url: String,
+        /// Trust config JSON. Repeat to layer overlays; later files win
+        /// on duplicate key ids.
+        #[arg(long, required = true)]
+        trust: Vec<String>,
+    },
+
+    /// Pack a manifest, its content and attachments into one .vcpb file.
+    ///
+    /// Every member is checked against the manifest first; attachments
+    /// must be listed in its `attachments` section.
+    Pack {
+        /// Path to the manifest JSON file.
+        manifest: String,
+        /// Path to the content file.
+        content: String,
+        /// Attachment file, stored under its file name (repeatable).
+        #[arg(long = "attach")]
+        attachments: Vec<String>,
+        /// Output path (conventionally ending in .vcpb).
+        #[arg(long)]
+        out: String,
+    },
+
+    /// Verify a .vcpb archive and optionally extract it.
+    Unpack {
+        /// Path to the archive, or "-" for stdin.
+        archive: String,
+        /// Extract manifest.json, content and attachments/ into this
+        /// directory; without it the archive is only verified and listed.
+        #[arg(long)]
+        out: Option<String>,
+    },
+
+    /// Scaffold new VCP artifacts.
+    Init {
+        #[command(subcommand)]
+        action: InitCommand,
+    },
+
+    /// Verify every bundle under a directory, then re-verify each one as
+    /// its files change.
+    ///
+    /// Bundles are .vcpb archives and directories holding manifest.json
+    /// and content (the layout `unpack --out` writes). Runs until
+    /// interrupted.
+    Watch {
+        /// Directory to watch (searched recursively).
+        dir: String,
+    },
+
+    /// Report bundles under a directory that need renewing.
+    ///
+    /// Checks each manifest's expiry and, with --trust, the issuer and
+    /// auditor anchors it relies on. Exits with status 2 if a bundle or
+    /// anchor has already expired or a manifest cannot be read.
+    Expiry {
+        /// Directory of bundles (searched recursively, as for `watch`).
+        dir: String,
+        /// Trust config JSON whose anchors are checked as well. Repeat
+        /// to layer overlays; later files win on duplicate key ids.
+        #[arg(long)]
+        trust: Vec<String>,
+        /// Warn about anything expiring within this many days.
+        #[arg(long, default_value_t = 30)]
+        within: u32,
+    },
+
+    /// Anonymize a token or context for attaching to a bug report.
+    ///
+    /// Profile IDs, namespaces, private markers and custom categories are
+    /// replaced with stable pseudonyms; the scrubbed output is written to
+    /// stdout and re-parsed to confirm it fails the same way.
+    Scrub {
+        /// Path to the artifact, or "-" for stdin.
+        #[arg(default_value = "-")]
+        path: String,
+        /// Salt for pseudonym derivation (same salt, same pseudonyms).
+        #[arg(long, default_value = "")]
+        salt: String,
+    },
+
+    /// Explain any VCP artifact field by field.
+    ///
+    /// Detects whether the input is a CSM-1 code or token, a VCP/I token,
+    /// a context wire string or a manifest, and prints each field with its
+    /// meaning and any warnings.
+    Explain {
+        /// The artifact itself, a path to a file containing it, or "-" for stdin.
+        input: String,
+    },
+
+    /// Check a CSM-1 token or manifest against best practices.
+    ///
+    /// Reports warnings and errors without rejecting the artifact. Exits
+    /// with status 2 if any error-level issue is found.
+    Lint {
+        /// Path to the token or manifest, or "-" for stdin.
+        #[arg(default_value = "-")]
+        path: String,
+        /// Rewrite the file with mechanical issues (whitespace, list
+        /// order) fixed; with stdin, print the fixed token to stdout.
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Check constitution text for structure and style problems.
+    ///
+    /// Flags broken numbering, ambiguous modal verbs ("should"), overlong
+    /// and duplicate rules, and a missing scope section. Exits with status
+    /// 2 if any error-level issue is found.
+    LintContent {
+        /// Path to the constitution, or "-" for stdin.
+        #[arg(default_value = "-")]
+        path: String,
+        /// Rule config JSON file (rule levels, word limit, word lists).
+        #[arg(long)]
+        config: Option<String>,
+        /// Treat every enabled rule as an error.
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Show which rules changed between two constitution versions.
+    ///
+    /// Each file is either constitution text (one rule per line or list
+    /// item) or JSON with `id`, `rules` and optional `priority`.
+    Diff {
+        /// The old version, or "-" for stdin.
+        old: String,
+        /// The new version.
+        new: String,
+    },
+
+    /// Run the shared cross-SDK conformance vectors in a directory.
+    ///
+    /// Exits with status 2 if any vector fails.
+    Conformance {
+        /// Directory of JSON fixtures (searched recursively).
+        dir: String,
+    },
+
+    /// Simulate how a personal signal decays, for tuning decay configs.
+    ///
+    /// Starts from the dimension's default config, or --config, with any
+    /// overrides applied, and samples intensity and lifecycle state from
+    /// declaration to --duration.
+    DecaySim(Box<DecaySimArgs>),
+
+    /// Show supported spec versions, algorithms, hook types, composition
+    /// modes and enabled features.
+    Capabilities,
+
+    /// Generate an Ed25519 signing key.
+    ///
+    /// The public key and fingerprint are printed for the trust config.
+    Keygen {
+        /// Write the secret key here instead of stdout.
+        #[arg(long)]
+        out: Option<String>,
+        /// Secret key format: pem, base64 or raw (raw requires --out).
+        #[arg(long, default_value = "pem")]
+        key_format: String,
+        /// Encrypt the key with the passphrase in --passphrase-env.
+        #[arg(long)]
+        encrypt: bool,
+        /// Environment variable holding the passphrase.
+        #[arg(long, default_value = "VCP_KEY_PASSPHRASE")]
+        passphrase_env: String,
+    },
+
+    /// Key file utilities.
+    Key {
+        #[command(subcommand)]
+        action: KeyCommand,
+    },
+
+    /// Trust config utilities.
+    Trust {
+        #[command(subcommand)]
+        action: TrustCommand,
+    },
+
+    /// Print a shell completion script to stdout.
+    Completions {
+        /// Shell to generate for.
+        shell: Shell,
+    },
+}
+
+#[derive(Subcommand)]
+enum Csm1Command {
+    /// Build a CSM-1 code and explain it.
+    ///
+    /// With no flags on a terminal, prompts for each part. Otherwise
+    /// --persona and --level are required.
+    Build {
+        /// Persona name or letter (nanny, sentinel, godparent, ambassador,
+        /// muse, mediator, custom).
+        #[arg(long)]
+        persona: Option<String>,
+        /// Adherence level 0-5.
+        #[arg(long)]
+        level: Option<u8>,
+        /// Comma-separated scope names or letters (e.g. family,education).
+        #[arg(long)]
+        scopes: Option<String>,
+        /// Namespace, starting with a letter (e.g. ELEM).
+        #[arg(long)]
+        namespace: Option<String>,
+        /// Version as MAJOR.MINOR.PATCH.
+        #[arg(long)]
+        version: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContextCommand {
+    /// Set dimensions on a context (empty unless --from is given).
+    Set {
+        /// Context to update: a wire string, or "-" for stdin.
+        #[arg(long)]
+        from: Option<String>,
+        #[command(flatten)]
+        fields: Box<ContextFields>,
+        /// Print the ASCII-safe `ctx1;` form.
+        #[arg(long)]
+        ascii: bool,
+    },
+
+    /// Overlay contexts left to right; later dimensions win.
+    Merge {
+        /// Wire strings to merge.
+        #[arg(required = true, num_args = 2..)]
+        wires: Vec<String>,
+        /// Print the ASCII-safe `ctx1;` form.
+        #[arg(long)]
+        ascii: bool,
+    },
+}
+
+/// Dimension flags for `context set`.
+///
+/// Personal dimensions take `value:intensity` (optionally `[extended]`).
+/// Situational dimensions take comma-separated tags, by name (`home`) or
+/// emoji.
+#[derive(Args)]
+struct ContextFields {
+    /// Cognitive state, e.g. focused:4.
+    #[arg(long)]
+    cognitive: Option<String>,
+    /// Emotional tone, e.g. calm:3.
+    #[arg(long)]
+    emotional: Option<String>,
+    /// Energy level, e.g. rested:2.
+    #[arg(long)]
+    energy: Option<String>,
+    /// Perceived urgency, e.g. pressured:4.
+    #[arg(long)]
+    urgency: Option<String>,
+    /// Body signals, e.g. discomfort:2[headache].
+    #[arg(long)]
+    body: Option<String>,
+    /// Time tags, e.g. morning,weekday.
+    #[arg(long)]
+    time: Option<String>,
+    /// Space tags, e.g. home.
+    #[arg(long, visible_alias = "location")]
+    space: Option<String>,
+    /// Company tags, e.g. children.
+    #[arg(long)]
+    company: Option<String>,
+    /// Culture tags, e.g. casual.
+    #[arg(long)]
+    culture: Option<String>,
+    /// Occasion tags, e.g. business.
+    #[arg(long)]
+    occasion: Option<String>,
+    /// Environment tags, e.g. quiet.
+    #[arg(long)]
+    environment: Option<String>,
+    /// Agency tags, e.g. peer.
+    #[arg(long)]
+    agency: Option<String>,
+    /// Constraint tags, e.g. legal.
+    #[arg(long)]
+    constraints: Option<String>,
+    /// System context tags, e.g. offline.
+    #[arg(long)]
+    system_context: Option<String>,
+    /// Embodiment tags, e.g. navigating.
+    #[arg(long)]
+    embodiment: Option<String>,
+    /// Proximity tags, e.g. same_room.
+    #[arg(long)]
+    proximity: Option<String>,
+    /// Relationship as tie:function, e.g. colleague:professional.
+    #[arg(long)]
+    relationship: Option<String>,
+    /// Formality tags, e.g. professional.
+    #[arg(long)]
+    formality: Option<String>,
+}
+
+impl ContextFields {
+    fn apply(&self, ctx: &mut FullContext) -> Result<(), String> {
+        let personal = [
+            (PersonalDimensionKind::CognitiveState, &self.cognitive),
+            (PersonalDimensionKind::EmotionalTone, &self.emotional),
+            (PersonalDimensionKind::EnergyLevel, &self.energy),
+            (PersonalDimensionKind::PerceivedUrgency, &self.urgency),
+            (PersonalDimensionKind::BodySignals, &self.body),
+        ];
+        for (kind, raw) in personal {
+            if let Some(raw) = raw {
+                let dim = PersonalDimension::from_wire(raw).map_err(|e| format!("{kind}: {e}"))?;
+                ctx.personal.set(kind, dim);
+            }
+        }
+
+        let situational = [
+            (SituationalDimension::Time, &self.time),
+            (SituationalDimension::Space, &self.space),
+            (SituationalDimension::Company, &self.company),
+            (SituationalDimension::Culture, &self.culture),
+            (SituationalDimension::Occasion, &self.occasion),
+            (SituationalDimension::Environment, &self.environment),
+            (SituationalDimension::Agency, &self.agency),
+            (SituationalDimension::Constraints, &self.constraints),
+            (SituationalDimension::SystemContext, &self.system_context),
+            (SituationalDimension::Embodiment, &self.embodiment),
+            (SituationalDimension::Proximity, &self.proximity),
+            (SituationalDimension::Relationship, &self.relationship),
+            (SituationalDimension::Formality, &self.formality),
+        ];
+        for (dim, raw) in situational {
+            if let Some(raw) = raw {
+                let tags = raw
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(|t| resolve_tag(dim, t))
+                    .collect::<Result<Vec<_>, _>>()?;
+                ctx.situational.set(dim, tags);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Map a tag name to its emoji; emoji and free-form values pass through.
+fn resolve_tag(dim: SituationalDimension, tag: &str) -> Result<String, String> {
+    if let Some(emoji) = dim.tag_emoji(tag) {
+        return Ok(emoji.to_string());
+    }
+    if dim.is_free_form() || !tag.is_ascii() {
+        return Ok(tag.to_string());
+    }
+    let names: Vec<&str> = dim.vocabulary().iter().map(|(_, name)| *name).collect();
+    Err(format!(
+        "unknown {dim} tag '{tag}' (expected one of: {})",
+        names.join(", ")
+    ))
+}
+
+/// Flags for `decay-sim`. Times are in seconds.
+#[derive(Args)]
+struct DecaySimArgs {
+    /// Dimension whose default decay config is the starting point.
+    #[arg(long, value_enum, default_value_t = DecayDimension::Urgency)]
+    dimension: DecayDimension,
+    /// Decay config JSON file, used instead of the dimension default.
+    #[arg(long)]
+    config: Option<String>,
+    /// Override the exponential half-life.
+    #[arg(long)]
+    half_life: Option<f64>,
+    /// Decay linearly to baseline over this long instead.
+    #[arg(long, conflicts_with = "half_life")]
+    linear: Option<f64>,
+    /// Override the baseline intensity.
+    #[arg(long)]
+    baseline: Option<u8>,
+    /// Override the fraction of declared intensity that marks staleness.
+    #[arg(long)]
+    stale_threshold: Option<f64>,
+    /// Override how long a signal stays active before decaying.
+    #[arg(long)]
+    fresh_window: Option<f64>,
+    /// Declared intensity, 1-5.
+    #[arg(long, default_value_t = 5)]
+    intensity: u8,
+    /// How long to simulate.
+    #[arg(long, default_value_t = 3600.0)]
+    duration: f64,
+    /// Time between samples.
+    #[arg(long, default_value_t = 60.0)]
+    step: f64,
+}
+
+/// Personal dimensions for `decay-sim`, named as in `context set`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DecayDimension {
+    Cognitive,
+    Emotional,
+    Energy,
+    Urgency,
+    Body,
+}
+
+#[derive(Subcommand)]
+enum InitCommand {
+    /// Create a bundle directory: constitution.md, a manifest with the
+    /// hash, budget and timestamps filled in, a trust config stub and a
+    /// README of next steps.
+    ///
+    /// Fields only the author can supply (keys, signatures, the safety
+    /// attestation) are left as `TODO`. Existing files are not
+    /// overwritten without --force.
+    Bundle {
+        /// Directory to create (may already exist).
+        dir: String,
+        /// Bundle URI, `creed://<issuer>/<path>`; defaults to the
+        /// directory name under --issuer.
+        #[arg(long)]
+        id: Option<String>,
+        /// Issuer identifier (domain-style or DID).
+        #[arg(long, default_value = "example.org")]
+        issuer: String,
+        /// Days until the manifest and trust anchor expire.
+        #[arg(long, default_value_t = 90)]
+        days: u32,
+        /// Overwrite files that already exist.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeyCommand {
+    /// Show the public key and fingerprint of a secret, public or
+    /// encrypted key file.
+    Inspect {
+        /// Path to the key file, or "-" for stdin.
+        #[arg(default_value = "-")]
+        path: String,
+        /// Environment variable holding the passphrase for encrypted keys.
+        #[arg(long, default_value = "VCP_KEY_PASSPHRASE")]
+        passphrase_env: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrustCommand {
+    /// Print fingerprints of the anchors in a trust config, with short,
+    /// word and emoji forms for checking keys with the issuer out of band.
+    Fingerprint {
+        /// Path to the trust config JSON, or "-" for stdin.
+        #[arg(default_value = "-")]
+        path: String,
+        /// Only show anchors for this issuer or auditor.
+        #[arg(long)]
+        entity: Option<String>,
+        /// Only show the anchor with this key ID.
+        #[arg(long)]
+        key_id: Option<String>,
     },
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let cli = Cli::try_parse().unwrap_or_else(|e| {
+        // clap would exit with 2, which here means a failed check.
+        let _ = e.print();
+        process::exit(if e.use_stderr() {
+            Exit::Usage.code()
+        } else {
+            0
+        });
+    });
+    let json = cli.json || cli.format == OutputFormat::Json;
+    QUIET.store(cli.quiet, Ordering::Relaxed);
 
     let result = match cli.command {
-        Commands::ParseToken { token } => cmd_parse_token(&token),
-        Commands::ParseCsm1 { code } => cmd_parse_csm1(&code),
+        Commands::ParseToken { token, aliases } => {
+            cmd_parse_token(&token, aliases.as_deref(), json)
+        }
+        Commands::ParseCsm1 { code } => cmd_parse_csm1(&code, json),
         Commands::ParseCsm1Token { path } => cmd_parse_csm1_token(&path),
-        Commands::EncodeCsm1 { json } => cmd_encode_csm1(&json),
+        Commands::EncodeCsm1 { json: input } => cmd_encode_csm1(&input, json),
+        Commands::Csm1 {
+            action:
+                Csm1Command::Build {
+                    persona,
+                    level,
+                    scopes,
+                    namespace,
+                    version,
+                },
+        } => cmd_csm1_build(
+            Csm1Parts {
+                persona,
+                level,
+                scopes,
+                namespace,
+                version,
+            },
+            json,
+        ),
         Commands::ParseContext { wire } => cmd_parse_context(&wire),
-        Commands::Hash { path } => cmd_hash(&path),
-        Commands::Verify { manifest, content } => cmd_verify(&manifest, &content),
+        Commands::ValidateContext { input, strict } => cmd_validate_context(&input, strict, json),
+        Commands::Context {
+            action:
+                ContextCommand::Set {
+                    from,
+                    fields,
+                    ascii,
+                },
+        } => cmd_context_set(from.as_deref(), &fields, ascii, json),
+        Commands::Context {
+            action: ContextCommand::Merge { wires, ascii },
+        } => cmd_context_merge(&wires, ascii, json),
+        Commands::Hash { path, report } => cmd_hash(&path, report, json),
+        Commands::Verify {
+            manifest,
+            content,
+            max_size,
+        } => cmd_verify(&manifest, &content, max_size, json),
+        Commands::VerifyUrl {
+            manifest_url,
+            content_url,
+            trust,
+        } => cmd_verify_url(&manifest_url, &content_url, &trust, json),
+        Commands::Pack {
+            manifest,
+            content,
+            attachments,
+            out,
+        } => cmd_pack(&manifest, &content, &attachments, &out, json),
+        Commands::Unpack { archive, out } => cmd_unpack(&archive, out.as_deref(), json),
+        Commands::Init {
+            action:
+                InitCommand::Bundle {
+                    dir,
+                    id,
+                    issuer,
+                    days,
+                    force,
+                },
+        } => cmd_init_bundle(&dir, id.as_deref(), &issuer, days, force, json),
+        Commands::Watch { dir } => cmd_watch(&dir, json),
+        Commands::Expiry { dir, trust, within } => cmd_expiry(&dir, &trust, within, json),
+        Commands::Scrub { path, salt } => cmd_scrub(&path, &salt, json),
+        Commands::Explain { input } => cmd_explain(&input, json),
+        Commands::Lint { path, fix } => cmd_lint(&path, fix, json),
+        Commands::LintContent {
+            path,
+            config,
+            strict,
+        } => cmd_lint_content(&path, config.as_deref(), strict, json),
+        Commands::Diff { old, new } => cmd_diff(&old, &new, json),
+        Commands::Conformance { dir } => cmd_conformance(&dir, json),
+        Commands::DecaySim(args) => cmd_decay_sim(&args, json),
+        Commands::Capabilities => cmd_capabilities(json),
+        Commands::Keygen {
+            out,
+            key_format,
+            encrypt,
+            passphrase_env,
+        } => cmd_keygen(out.as_deref(), &key_format, encrypt, &passphrase_env, json),
+        Commands::Key {
+            action:
+                KeyCommand::Inspect {
+                    path,
+                    passphrase_env,
+                },
+        } => cmd_key_inspect(&path, &passphrase_env, json),
+        Commands::Trust {
+            action:
+                TrustCommand::Fingerprint {
+                    path,
+                    entity,
+                    key_id,
+                },
+        } => cmd_trust_fingerprint(&path, entity.as_deref(), key_id.as_deref(), json),
+        Commands::Completions { shell } => {
+            clap_complete::generate(
+                shell,
+                &mut Cli::command(),
+                "vcp-cli",
+                &mut std::io::stdout(),
+            );
+            Ok(())
+        }
     };
 
     if let Err(e) = result {
-        eprintln!("error: {e}");
-        process::exit(1);
+        if json {
+            eprintln!("{}", e.to_json());
+        } else {
+            eprintln!("{}: {}", e.heading(), e.to_text());
+        }
+        process::exit(e.exit().code());
+    }
+}
+
+// ── Errors and output ───────────────────────────────────────
+
+/// Process exit status, one per class of failure. The values are part of
+/// the CLI's stable interface.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Exit {
+    /// Any failure not classed below.
+    Error = 1,
+    /// A check ran and did not pass.
+    Failed = 2,
+    /// The input could not be parsed.
+    Parse = 3,
+    /// A file could not be read or written.
+    Io = 4,
+    /// Verification failed because the bundle is revoked.
+    Revoked = 5,
+    /// Verification failed because the bundle is expired or not yet valid.
+    Expired = 6,
+    /// The command line was invalid.
+    Usage = 7,
+}
+
+impl Exit {
+    fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// The status for a core error: codes `VCP-E-1xxx` and malformed JSON
+    /// are parse errors.
+    fn for_error(error: &VcpError) -> Self {
+        match error.error_code().number() {
+            1000..=1999 | 3001 => Self::Parse,
+            3002 => Self::Io,
+            _ => Self::Error,
+        }
+    }
+
+    /// The status for a failed verification.
+    fn for_verification(code: VerificationCode) -> Self {
+        match code {
+            VerificationCode::Revoked => Self::Revoked,
+            VerificationCode::Expired | VerificationCode::NotYetValid => Self::Expired,
+            _ => Self::Failed,
+        }
     }
 }
 
-fn cmd_parse_token(raw: &str) -> Result<(), String> {
-    let token = VcpToken::parse(raw).map_err(|e| e.to_string())?;
-    let json = serde_json::to_string_pretty(&token).map_err(|e| e.to_string())?;
-    println!("{json}");
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Whether `--quiet` was given.
+fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Print a warning on stderr unless `--quiet`.
+fn warn(message: impl fmt::Display) {
+    if !quiet() {
+        eprintln!("warning: {message}");
+    }
+}
+
+/// Why a command failed.
+enum CliError {
+    /// A plain message.
+    Message(String),
+    /// An error from the core library.
+    Vcp(VcpError),
+    /// A parse error, with the input it was found in.
+    Parse { error: VcpError, input: String },
+}
+
+impl CliError {
+    fn parse(error: VcpError, input: &str) -> Self {
+        Self::Parse {
+            error,
+            input: input.to_string(),
+        }
+    }
+
+    /// A failure to `action` (read, write, ...) `path`.
+    fn io(action: &str, path: impl fmt::Display, error: &std::io::Error) -> Self {
+        Self::Vcp(VcpError::IoError(format!(
+            "cannot {action} {path}: {error}"
+        )))
+    }
+
+    fn exit(&self) -> Exit {
+        match self {
+            Self::Message(_) => Exit::Error,
+            Self::Vcp(error) | Self::Parse { error, .. } => Exit::for_error(error),
+        }
+    }
+
+    /// `error`, tagged with the stable error code when there is one.
+    fn heading(&self) -> String {
+        match self {
+            Self::Message(_) => "error".to_string(),
+            Self::Vcp(error) | Self::Parse { error, .. } => {
+                format!("error[{}]", error.error_code())
+            }
+        }
+    }
+
+    /// The message, with the offending part of the input underlined for
+    /// parse errors. `main` supplies the leading [`heading`](Self::heading).
+    fn to_text(&self) -> String {
+        match self {
+            Self::Message(message) => message.clone(),
+            Self::Vcp(error) => error.to_string(),
+            Self::Parse { error, input } => {
+                let out = error.render(input);
+                out.strip_prefix("error: ")
+                    .unwrap_or(&out)
+                    .trim_end()
+                    .to_string()
+            }
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let error = match self {
+            Self::Message(message) => json!({"code": "CliError", "message": message}),
+            Self::Vcp(error) | Self::Parse { error, .. } => {
+                let mut out = json!({
+                    "code": error.code(),
+                    "error_code": error.error_code().to_string(),
+                    "message": error.to_string(),
+                });
+                if let Some(span) = error.span() {
+                    out["span"] = json!({"offset": span.offset, "length": span.len});
+                }
+                if !error.expected().is_empty() {
+                    out["expected"] = json!(error.expected());
+                }
+                out
+            }
+        };
+        json!({ "error": error })
+    }
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        Self::Message(message)
+    }
+}
+
+impl From<&str> for CliError {
+    fn from(message: &str) -> Self {
+        Self::Message(message.to_string())
+    }
+}
+
+impl From<VcpError> for CliError {
+    fn from(error: VcpError) -> Self {
+        Self::Vcp(error)
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Vcp(error.into())
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Vcp(error.into())
+    }
+}
+
+/// Print `value` as pretty JSON on stdout.
+fn print_json(value: &impl Serialize) -> Result<(), CliError> {
+    let out = serde_json::to_string_pretty(value)?;
+    println!("{out}");
+    Ok(())
+}
+
+fn cmd_parse_token(raw: &str, aliases: Option<&str>, json: bool) -> Result<(), CliError> {
+    let token = match aliases {
+        Some(path) => AliasMap::from_json(&read_input(path)?)?.resolve(raw),
+        None => VcpToken::parse(raw),
+    }
+    .map_err(|e| CliError::parse(e, raw))?;
+    // Set when `raw` named an alias rather than the token itself.
+    let alias = (token.full() != raw).then_some(raw);
+    if json {
+        let mut out = json!({
+            "token": token,
+            "domain": token.domain(),
+            "approach": token.approach(),
+            "role": token.role(),
+            "depth": token.depth(),
+            "canonical": token.canonical(),
+            "full": token.full(),
+        });
+        if let Some(alias) = alias {
+            out["alias"] = json!(alias);
+        }
+        return print_json(&out);
+    }
+    print_json(&token)?;
     println!();
+    if let Some(alias) = alias {
+        println!("alias:     {alias}");
+    }
     println!("domain:    {}", token.domain());
     println!("approach:  {}", token.approach());
     println!("role:      {}", token.role());
@@ -118,17 +1092,33 @@ fn cmd_parse_token(raw: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn cmd_parse_csm1(raw: &str) -> Result<(), String> {
-    let code = Csm1Code::parse(raw).map_err(|e| e.to_string())?;
-    let json = serde_json::to_string_pretty(&code).map_err(|e| e.to_string())?;
-    println!("{json}");
+fn cmd_parse_csm1(raw: &str, json: bool) -> Result<(), CliError> {
+    let code = Csm1Code::parse(raw).map_err(|e| CliError::parse(e, raw))?;
+    if json {
+        return print_json(&json!({
+            "code": code,
+            "encoded": code.encode(),
+            "active": code.is_active(),
+            "maximum": code.is_maximum(),
+        }));
+    }
+    print_json(&code)?;
     println!();
+    print_csm1_summary(&code);
+    Ok(())
+}
+
+fn print_csm1_summary(code: &Csm1Code) {
     println!(
         "persona:   {} ({})",
         code.persona,
         code.persona.description()
     );
-    println!("level:     {}", code.adherence_level);
+    println!(
+        "level:     {} ({})",
+        code.adherence_level,
+        code.level_description()
+    );
     if !code.scopes.is_empty() {
         let scope_strs: Vec<String> = code
             .scopes
@@ -146,61 +1136,1377 @@ fn cmd_parse_csm1(raw: &str) -> Result<(), String> {
     println!("active:    {}", code.is_active());
     println!("maximum:   {}", code.is_maximum());
     println!("encoded:   {}", code.encode());
+}
+
+/// Raw `csm1 build` inputs, from flags or prompts.
+struct Csm1Parts {
+    persona: Option<String>,
+    level: Option<u8>,
+    scopes: Option<String>,
+    namespace: Option<String>,
+    version: Option<String>,
+}
+
+fn cmd_csm1_build(parts: Csm1Parts, json: bool) -> Result<(), CliError> {
+    use std::io::IsTerminal;
+
+    let no_flags = parts.persona.is_none()
+        && parts.level.is_none()
+        && parts.scopes.is_none()
+        && parts.namespace.is_none()
+        && parts.version.is_none();
+    let code = if no_flags && std::io::stdin().is_terminal() {
+        prompt_csm1()?
+    } else {
+        let persona = parts.persona.ok_or("missing --persona")?;
+        let level = parts.level.ok_or("missing --level")?;
+        build_csm1(
+            &persona,
+            level,
+            parts.scopes.as_deref().unwrap_or(""),
+            parts.namespace.as_deref().unwrap_or(""),
+            parts.version.as_deref().unwrap_or(""),
+        )?
+    };
+
+    if json {
+        return print_json(&json!({"code": code, "encoded": code.encode()}));
+    }
+    println!("{}", code.encode());
+    println!();
+    print_csm1_summary(&code);
     Ok(())
 }
 
-fn cmd_parse_csm1_token(path: &str) -> Result<(), String> {
-    let input = if path == "-" {
+/// Assemble a code from its parts; empty strings mean "not set".
+fn build_csm1(
+    persona: &str,
+    level: u8,
+    scopes: &str,
+    namespace: &str,
+    version: &str,
+) -> Result<Csm1Code, String> {
+    let persona = Persona::from_name(persona.trim()).map_err(|e| e.to_string())?;
+    let scopes = scopes
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(Scope::from_name)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    let namespace = non_empty(namespace).map(str::to_uppercase);
+    if let Some(ns) = &namespace {
+        let starts_with_letter = ns.starts_with(|c: char| c.is_ascii_alphabetic());
+        if !starts_with_letter || !ns.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!(
+                "invalid namespace: {ns} (letters, digits and _, starting with a letter)"
+            ));
+        }
+    }
+    let code = Csm1Code {
+        persona,
+        adherence_level: 0,
+        scopes,
+        namespace,
+        version: non_empty(version).map(String::from),
+    }
+    .with_level(level)
+    .map_err(|e| e.to_string())?;
+
+    // The encoded form must parse back to the same code, which catches
+    // namespaces and versions the grammar cannot carry.
+    match Csm1Code::parse(&code.encode()) {
+        Ok(parsed) if parsed == code => Ok(code),
+        Ok(_) => Err(format!("{} does not round-trip", code.encode())),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn non_empty(s: &str) -> Option<&str> {
+    Some(s.trim()).filter(|s| !s.is_empty())
+}
+
+fn prompt_csm1() -> Result<Csm1Code, String> {
+    eprintln!("Personas:");
+    for p in Persona::all() {
+        eprintln!("  {:<11} {}  {}", p.name(), p.code(), p.description());
+    }
+    let persona = prompt("persona", |s| {
+        Persona::from_name(s.trim()).map_err(|e| e.to_string())
+    })?;
+    let level = prompt("adherence level 0-5 [3]", |s| match s.trim() {
+        "" => Ok(3),
+        n => n
+            .parse::<u8>()
+            .ok()
+            .filter(|&n| n <= 5)
+            .ok_or_else(|| format!("'{n}' is not a level from 0 to 5")),
+    })?;
+
+    eprintln!("Scopes:");
+    for scope in Scope::all() {
+        eprintln!(
+            "  {:<14} {}  {}",
+            scope.name(),
+            scope.code(),
+            scope.description()
+        );
+    }
+    let scopes = prompt("scopes, comma-separated [all]", |s| {
+        build_csm1(persona.name(), level, s, "", "").map(|_| s.to_string())
+    })?;
+    let namespace = prompt("namespace [none]", |s| {
+        build_csm1(persona.name(), level, &scopes, s, "").map(|_| s.to_string())
+    })?;
+    prompt("version [none]", |s| {
+        build_csm1(persona.name(), level, &scopes, &namespace, s)
+    })
+}
+
+/// Ask on stderr until `parse` accepts a line from stdin.
+fn prompt<T>(label: &str, parse: impl Fn(&str) -> Result<T, String>) -> Result<T, String> {
+    use std::io::{BufRead, Write};
+
+    let stdin = std::io::stdin();
+    loop {
+        eprint!("{label}: ");
+        std::io::stderr().flush().map_err(|e| e.to_string())?;
+        let mut line = String::new();
+        if stdin
+            .lock()
+            .read_line(&mut line)
+            .map_err(|e| e.to_string())?
+            == 0
+        {
+            return Err(format!("no answer for {label}"));
+        }
+        match parse(line.trim_end_matches(['\r', '\n'])) {
+            Ok(value) => return Ok(value),
+            Err(e) => eprintln!("  {e}"),
+        }
+    }
+}
+
+/// Read a constitution, converting UTF-16 or BOM-prefixed UTF-8 to plain
+/// UTF-8 with a warning, so the hash is that of the canonical text.
+fn read_content(path: &str) -> Result<String, CliError> {
+    let bytes = if path == "-" {
         use std::io::Read;
-        let mut buf = String::new();
-        std::io::stdin()
-            .read_to_string(&mut buf)
-            .map_err(|e| e.to_string())?;
+        let mut buf = Vec::new();
+        std::io::stdin().read_to_end(&mut buf)?;
         buf
     } else {
-        fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}"))?
+        fs::read(path).map_err(|e| CliError::io("read", path, &e))?
     };
+    let decoded = transport::decode_content(&bytes).map_err(|e| format!("{path}: {e}"))?;
+    if decoded.encoding != TextEncoding::Utf8 {
+        warn(format_args!(
+            "{path} is {}; hashing it as UTF-8",
+            decoded.encoding
+        ));
+    }
+    Ok(decoded.text)
+}
 
-    let token = Csm1Token::parse(&input).map_err(|e| e.to_string())?;
-    let json = serde_json::to_string_pretty(&token).map_err(|e| e.to_string())?;
-    println!("{json}");
-    Ok(())
+/// Feed a constitution through a [`ContentHasher`] in chunks, so files of
+/// any size hash in constant memory. Encodings are handled as in
+/// [`read_content`].
+fn hash_content_stream(path: &str) -> Result<(ContentHasher, ReadSummary), CliError> {
+    let mut hasher = ContentHasher::new();
+    let summary = if path == "-" {
+        hasher.update_reader(std::io::stdin().lock())
+    } else {
+        let file = fs::File::open(path).map_err(|e| CliError::io("read", path, &e))?;
+        hasher.update_reader(file)
+    }
+    .map_err(|e| format!("{path}: {e}"))?;
+    if summary.encoding != TextEncoding::Utf8 {
+        warn(format_args!(
+            "{path} is {}; hashing it as UTF-8",
+            summary.encoding
+        ));
+    }
+    Ok((hasher, summary))
 }
 
-fn cmd_encode_csm1(json: &str) -> Result<(), String> {
-    let code: Csm1Code = serde_json::from_str(json).map_err(|e| e.to_string())?;
+fn read_input(path: &str) -> Result<String, CliError> {
+    if path == "-" {
+        use std::io::Read;
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf)?;
+        Ok(buf)
+    } else {
+        fs::read_to_string(path).map_err(|e| CliError::io("read", path, &e))
+    }
+}
+
+fn cmd_parse_csm1_token(path: &str) -> Result<(), CliError> {
+    let input = read_input(path)?;
+    let token = Csm1Token::parse(&input).map_err(|e| CliError::parse(e, &input))?;
+    print_json(&token)
+}
+
+fn cmd_encode_csm1(input: &str, json: bool) -> Result<(), CliError> {
+    let code: Csm1Code = serde_json::from_str(input)?;
+    if json {
+        return print_json(&json!({"encoded": code.encode()}));
+    }
     println!("{}", code.encode());
     Ok(())
 }
 
-fn cmd_parse_context(wire: &str) -> Result<(), String> {
-    let ctx = FullContext::from_wire(wire).map_err(|e| e.to_string())?;
-    let json = serde_json::to_string_pretty(&ctx).map_err(|e| e.to_string())?;
-    println!("{json}");
+fn cmd_parse_context(wire: &str) -> Result<(), CliError> {
+    let ctx = FullContext::from_wire(wire).map_err(|e| CliError::parse(e, wire))?;
+    print_json(&ctx)
+}
+
+fn cmd_validate_context(input: &str, strict: bool, json: bool) -> Result<(), CliError> {
+    let raw = if input == "-" {
+        read_input("-")?
+    } else {
+        input.to_string()
+    };
+    let raw = raw.trim();
+    let ctx = if raw.starts_with('{') {
+        serde_json::from_str::<FullContext>(raw)?
+    } else {
+        FullContext::from_wire(raw).map_err(|e| CliError::parse(e, raw))?
+    };
+
+    let mut schema = ContextSchema::default();
+    if strict {
+        schema = schema.strict();
+    }
+    let issues = ctx.validate(&schema);
+
+    if json {
+        print_json(&issues)?;
+    } else if !quiet() {
+        if issues.is_empty() {
+            println!("OK: no issues");
+        }
+        for issue in &issues {
+            println!("{issue}");
+        }
+    }
+
+    if issues.iter().any(ValidationIssue::is_error) {
+        process::exit(Exit::Failed.code());
+    }
     Ok(())
 }
 
-fn cmd_hash(path: &str) -> Result<(), String> {
-    let content = fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}"))?;
-    let hash = transport::compute_content_hash(&content).map_err(|e| e.to_string())?;
+fn cmd_context_set(
+    from: Option<&str>,
+    fields: &ContextFields,
+    ascii: bool,
+    json: bool,
+) -> Result<(), CliError> {
+    let mut ctx = match from {
+        Some("-") => FullContext::from_wire(read_input("-")?.trim()),
+        Some(wire) => FullContext::from_wire(wire),
+        None => Ok(FullContext::default()),
+    }?;
+    fields.apply(&mut ctx)?;
+    print_context_wire(&ctx, ascii, json)
+}
+
+fn cmd_context_merge(wires: &[String], ascii: bool, json: bool) -> Result<(), CliError> {
+    let mut ctx = FullContext::default();
+    for wire in wires {
+        let next = FullContext::from_wire(wire).map_err(|e| format!("{wire}: {e}"))?;
+        ctx.merge(&next);
+    }
+    print_context_wire(&ctx, ascii, json)
+}
+
+fn print_context_wire(ctx: &FullContext, ascii: bool, json: bool) -> Result<(), CliError> {
+    let multi_tag = SituationalDimension::all()
+        .iter()
+        .any(|&dim| ctx.situational.get(dim).is_some_and(|tags| tags.len() > 1));
+    let mut warnings = Vec::new();
+    if multi_tag && !ascii {
+        warnings.push("the emoji wire format reads several tags in one dimension back as one; use --ascii to keep them apart");
+    }
+    let format = if ascii {
+        WireFormat::Ascii
+    } else {
+        WireFormat::Emoji
+    };
+    let wire = ctx.to_wire_as(format);
+    if json {
+        return print_json(&json!({
+            "wire": wire,
+            "format": if ascii { "ascii" } else { "emoji" },
+            "warnings": warnings,
+        }));
+    }
+    for warning in warnings {
+        warn(warning);
+    }
+    println!("{wire}");
+    Ok(())
+}
+
+fn cmd_hash(path: &str, report: bool, json: bool) -> Result<(), CliError> {
+    if !report {
+        let (hasher, _) = hash_content_stream(path)?;
+        let hash = hasher.finalize()?;
+        if json {
+            return print_json(&json!({ "hash": hash }));
+        }
+        println!("{hash}");
+        return Ok(());
+    }
+
+    // The report lists changes by line, so it needs the whole text.
+    let content = read_content(path)?;
+    let hash = transport::compute_content_hash(&content)?;
+    let canonical = transport::canonicalize_with_report(&content)?;
+    if json {
+        return print_json(&json!({ "hash": hash, "changes": canonical.changes }));
+    }
     println!("{hash}");
+    if canonical.is_unchanged() {
+        println!("already canonical; hashed as is");
+    } else {
+        println!("hashed after canonicalizing:");
+        for change in &canonical.changes {
+            println!("  - {change}");
+        }
+    }
+    Ok(())
+}
+
+fn cmd_verify(
+    manifest_path: &str,
+    content_path: &str,
+    max_size: usize,
+    json: bool,
+) -> Result<(), CliError> {
+    let manifest_json =
+        fs::read_to_string(manifest_path).map_err(|e| CliError::io("read", manifest_path, &e))?;
+    let (hasher, summary) = hash_content_stream(content_path)?;
+
+    // Hash everything first so an oversized file still reports its size,
+    // then fail the way the orchestrator would rather than erroring.
+    let result = if summary.text_len > max_size as u64 {
+        VerificationResult::fail(
+            VerificationCode::SizeExceeded,
+            format!(
+                "content is {} bytes; the limit is {max_size} (raise it with --max-size)",
+                summary.text_len
+            ),
+        )
+    } else {
+        transport::verify_bundle_streaming(&manifest_json, hasher)?
+    };
+
+    if json {
+        print_json(&json!({
+            "valid": result.is_valid(),
+            "code": result.code,
+            "error_code": result.code.error_code().to_string(),
+            "message": result.message,
+        }))?;
+    } else if !quiet() {
+        if result.is_valid() {
+            println!("VALID: {}", result.message);
+        } else {
+            println!(
+                "FAILED [{} {}]: {}",
+                result.code.error_code(),
+                result.code,
+                result.message
+            );
+        }
+    }
+
+    if !result.is_valid() {
+        process::exit(Exit::for_verification(result.code).code());
+    }
+    Ok(())
+}
+
+fn cmd_verify_url(
+    manifest_url: &str,
+    content_url: &str,
+    trust: &[String],
+    json: bool,
+) -> Result<(), CliError> {
+    let trust = TrustConfig::load_layered(trust, MergeStrategy::OtherWins)?;
+    let orchestrator = Orchestrator::new(trust);
+    let outcome = orchestrator.fetch_and_verify(
+        manifest_url,
+        content_url,
+        &orchestrator.verification_context(),
+    )?;
+
+    if json {
+        let mut out = json!({
+            "valid": outcome.is_valid(),
+            "code": outcome.code,
+            "error_code": outcome.code.error_code().to_string(),
+            "degraded": outcome.degraded,
+        });
+        if let Some(valid_until) = outcome.valid_until {
+            out["valid_until"] = json!(valid_until);
+        }
+        print_json(&out)?;
+    } else if !quiet() {
+        if outcome.is_valid() {
+            match outcome.valid_until {
+                Some(until) => println!("VALID: {manifest_url} (until {until})"),
+                None => println!("VALID: {manifest_url}"),
+            }
+        } else {
+            println!(
+                "FAILED [{} {}]: {manifest_url}",
+                outcome.code.error_code(),
+                outcome.code
+            );
+        }
+    }
+
+    if !outcome.is_valid() {
+        process::exit(Exit::for_verification(outcome.code).code());
+    }
+    Ok(())
+}
+
+fn cmd_pack(
+    manifest_path: &str,
+    content_path: &str,
+    attachments: &[String],
+    out: &str,
+    json: bool,
+) -> Result<(), CliError> {
+    let mut archive = BundleArchive::new(read_input(manifest_path)?, read_content(content_path)?);
+    for path in attachments {
+        let name = Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| format!("{path}: not a file name"))?;
+        let bytes = fs::read(path).map_err(|e| CliError::io("read", path, &e))?;
+        archive = archive.with_attachment(name, bytes);
+    }
+    let bytes = archive.pack()?;
+    fs::write(out, &bytes).map_err(|e| CliError::io("write", out, &e))?;
+    if json {
+        return print_json(&json!({
+            "path": out,
+            "bytes": bytes.len(),
+            "attachments": archive.attachments.len(),
+        }));
+    }
+    println!(
+        "packed {out} ({} bytes, {} attachments)",
+        bytes.len(),
+        archive.attachments.len()
+    );
+    Ok(())
+}
+
+fn cmd_unpack(path: &str, out: Option<&str>, json: bool) -> Result<(), CliError> {
+    let bytes = if path == "-" {
+        use std::io::Read;
+        let mut buf = Vec::new();
+        std::io::stdin().read_to_end(&mut buf)?;
+        buf
+    } else {
+        fs::read(path).map_err(|e| CliError::io("read", path, &e))?
+    };
+    let archive = BundleArchive::unpack(&bytes).map_err(|e| format!("{path}: {e}"))?;
+
+    if !json {
+        println!("VALID: {path}");
+        println!("  manifest.json");
+        println!("  content");
+        for (name, data) in &archive.attachments {
+            println!("  attachments/{name} ({} bytes)", data.len());
+        }
+    }
+
+    if let Some(dir) = out {
+        let dir = Path::new(dir);
+        let attachments_dir = dir.join("attachments");
+        let write = |path: &Path, data: &[u8]| {
+            fs::write(path, data).map_err(|e| CliError::io("write", path.display(), &e))
+        };
+        fs::create_dir_all(if archive.attachments.is_empty() {
+            dir
+        } else {
+            &attachments_dir
+        })
+        .map_err(|e| CliError::io("create", dir.display(), &e))?;
+        write(&dir.join("manifest.json"), archive.manifest_json.as_bytes())?;
+        write(&dir.join("content"), archive.content.as_bytes())?;
+        for (name, data) in &archive.attachments {
+            write(&attachments_dir.join(name), data)?;
+        }
+    }
+
+    if json {
+        let attachments: Vec<Value> = archive
+            .attachments
+            .iter()
+            .map(|(name, data)| json!({"name": name, "bytes": data.len()}))
+            .collect();
+        let mut report = json!({"path": path, "attachments": attachments});
+        if let Some(dir) = out {
+            report["extracted_to"] = json!(dir);
+        }
+        print_json(&report)?;
+    }
+    Ok(())
+}
+
+const CONSTITUTION_TEMPLATE: &str = "\
+# Constitution
+
+One rule per list item. Keep each rule short and testable.
+
+## Principles
+
+- Be honest about uncertainty.
+- Respect the user's stated boundaries.
+
+## Boundaries
+
+- Do not share personal information about third parties.
+";
+
+/// Scaffold a bundle directory, refusing to clobber existing files
+/// unless `force` is set.
+fn cmd_init_bundle(
+    dir: &str,
+    id: Option<&str>,
+    issuer: &str,
+    days: u32,
+    force: bool,
+    json: bool,
+) -> Result<(), CliError> {
+    let root = Path::new(dir);
+    let name = root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .filter(|n| !n.is_empty())
+        .unwrap_or("bundle")
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' | '.' | '_' | '-' => c,
+            'A'..='Z' => c.to_ascii_lowercase(),
+            _ => '-',
+        })
+        .collect::<String>();
+    let bundle_id = id.map_or_else(|| format!("creed://{issuer}/{name}"), str::to_string);
+    let key_id = format!("{}-key-1", issuer.replace(['.', ':'], "-"));
+
+    let now = chrono::Utc::now();
+    let exp = now + chrono::Duration::days(i64::from(days));
+    let (now, exp) = (
+        now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        exp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    );
+    let content_hash = transport::compute_content_hash(CONSTITUTION_TEMPLATE)?;
+    let token_count =
+        BudgetEstimator::default().estimate(Some("cl100k_base"), CONSTITUTION_TEMPLATE);
+
+    let manifest = json!({
+        "vcp_version": "1.0",
+        "bundle": {
+            "id": bundle_id,
+            "version": "0.1.0",
+            "content_hash": content_hash,
+            "content_format": "text/markdown",
+        },
+        "issuer": {
+            "id": issuer,
+            "public_key": "ed25519:TODO",
+            "key_id": key_id,
+        },
+        "timestamps": {
+            "iat": now,
+            "nbf": now,
+            "exp": exp,
+            "jti": UuidV7Generator.next_id(),
+        },
+        "budget": {
+            "token_count": token_count,
+            "tokenizer": "cl100k_base",
+        },
+        "safety_attestation": {
+            "auditor": "TODO",
+            "auditor_key_id": "TODO",
+            "reviewed_at": now,
+            "attestation_type": "content-safe",
+            "signature": "base64:TODO",
+        },
+        "signature": {
+            "algorithm": "ed25519",
+            "value": "base64:TODO",
+            "signed_fields": [
+                "vcp_version", "bundle", "issuer", "timestamps", "budget", "safety_attestation",
+            ],
+        },
+    });
+    let trust = json!({
+        "trust_anchors": {
+            issuer: {
+                "type": "issuer",
+                "keys": [{
+                    "id": key_id,
+                    "algorithm": "ed25519",
+                    "public_key": "TODO",
+                    "state": "active",
+                    "valid_from": now,
+                    "valid_until": exp,
+                }],
+            },
+        },
+    });
+    let readme = format!(
+        "# {bundle_id}
+
+Scaffolded by `vcp-cli init bundle`. Fields marked `TODO` need real values
+before the bundle will verify.
+
+1. Write the rules in `constitution.md`.
+2. Run `vcp-cli hash constitution.md` and put the result in
+   `bundle.content_hash` in `manifest.json`. Update `budget.token_count`
+   and `bundle.version` to match.
+3. Run `vcp-cli keygen --out issuer.pem` and put the printed public key in
+   `issuer.public_key` (as `ed25519:<key>`) and in the `{key_id}` entry of
+   `trust.json`. Keep `issuer.pem` out of version control.
+4. Have your safety auditor fill in `safety_attestation`, then sign the
+   manifest and replace `signature.value`.
+5. Check it: `vcp-cli lint manifest.json`, then
+   `vcp-cli verify manifest.json constitution.md`.
+6. Ship it: `vcp-cli pack manifest.json constitution.md --out {name}.vcpb`.
+
+The manifest and trust anchor expire on {exp}; `vcp-cli expiry` warns
+before then.
+"
+    );
+
+    let files = [
+        ("constitution.md", CONSTITUTION_TEMPLATE.to_string()),
+        ("manifest.json", pretty_json(&manifest)?),
+        ("trust.json", pretty_json(&trust)?),
+        ("README.md", readme),
+    ];
+    if !force {
+        let existing: Vec<&str> = files
+            .iter()
+            .map(|(file, _)| *file)
+            .filter(|file| root.join(file).exists())
+            .collect();
+        if !existing.is_empty() {
+            return Err(format!(
+                "{dir} already has {}; use --force to overwrite",
+                existing.join(", ")
+            )
+            .into());
+        }
+    }
+    fs::create_dir_all(root).map_err(|e| CliError::io("create", dir, &e))?;
+    for (file, contents) in &files {
+        let path = root.join(file);
+        fs::write(&path, contents).map_err(|e| CliError::io("write", path.display(), &e))?;
+    }
+
+    let names: Vec<&str> = files.iter().map(|(file, _)| *file).collect();
+    if json {
+        return print_json(&json!({"path": dir, "files": names}));
+    }
+    println!("Created bundle in {dir}");
+    for file in names {
+        println!("  {file}");
+    }
+    println!("Next steps are in {}", root.join("README.md").display());
+    Ok(())
+}
+
+/// JSON with a trailing newline, for files people will edit.
+fn pretty_json(value: &Value) -> Result<String, CliError> {
+    let mut out = serde_json::to_string_pretty(value)?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// How long to wait for more file events before re-verifying; editors
+/// and `pack` write a file in several steps.
+const WATCH_SETTLE: Duration = Duration::from_millis(200);
+
+/// The bundle a changed file belongs to, if any.
+fn watched_bundle(path: &Path) -> Option<PathBuf> {
+    if path.extension().is_some_and(|ext| ext == "vcpb") {
+        return Some(path.to_path_buf());
+    }
+    match path.file_name()?.to_str()? {
+        "manifest.json" | "content" => path.parent().map(Path::to_path_buf),
+        _ => None,
+    }
+}
+
+fn find_bundles(dir: &Path, found: &mut BTreeSet<PathBuf>) -> Result<(), CliError> {
+    let entries = fs::read_dir(dir).map_err(|e| CliError::io("read", dir.display(), &e))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_bundles(&path, found)?;
+        } else if let Some(bundle) = watched_bundle(&path) {
+            found.insert(bundle);
+        }
+    }
+    Ok(())
+}
+
+/// Verify one bundle, returning a one-line summary either way.
+fn check_bundle(bundle: &Path) -> Result<String, String> {
+    if bundle.is_dir() {
+        let read = |name: &str| {
+            let path = bundle.join(name);
+            fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {e}", path.display()))
+        };
+        let content =
+            read_content(&bundle.join("content").display().to_string()).map_err(|e| e.to_text())?;
+        let result = transport::verify_bundle(&read("manifest.json")?, &content)
+            .map_err(|e| e.to_string())?;
+        if result.is_valid() {
+            Ok(result.message)
+        } else {
+            Err(format!("[{}] {}", result.code, result.message))
+        }
+    } else {
+        let bytes = fs::read(bundle).map_err(|e| format!("cannot read: {e}"))?;
+        let archive = BundleArchive::unpack(&bytes).map_err(|e| e.to_string())?;
+        Ok(format!("{} attachment(s)", archive.attachments.len()))
+    }
+}
+
+/// Print the outcome for `bundle`; returns `false` if it failed.
+fn report_bundle(bundle: &Path, json: bool) -> bool {
+    let (status, detail) = if bundle.exists() {
+        match check_bundle(bundle) {
+            Ok(detail) => ("valid", Some(detail)),
+            Err(e) => ("failed", Some(e)),
+        }
+    } else {
+        ("removed", None)
+    };
+    if json {
+        // One compact object per line, so results can be streamed.
+        let mut line = json!({"bundle": bundle.display().to_string(), "status": status});
+        if let Some(detail) = &detail {
+            line["detail"] = json!(detail);
+        }
+        println!("{line}");
+    } else {
+        let bundle = bundle.display();
+        match (status, detail) {
+            ("valid", _) if quiet() => {}
+            ("valid", Some(detail)) => println!("VALID: {bundle} ({detail})"),
+            (_, Some(e)) => println!("FAILED: {bundle}: {e}"),
+            (_, None) => println!("REMOVED: {bundle}"),
+        }
+    }
+    status != "failed"
+}
+
+fn cmd_watch(dir: &str, json: bool) -> Result<(), CliError> {
+    let root = Path::new(dir);
+    let mut bundles = BTreeSet::new();
+    find_bundles(root, &mut bundles)?;
+    let failed = bundles.iter().filter(|b| !report_bundle(b, json)).count();
+    if json {
+        println!("{}", json!({"bundles": bundles.len(), "failed": failed}));
+    } else {
+        println!("{} bundle(s), {failed} failed", bundles.len());
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .map_err(|e| format!("cannot watch {dir}: {e}"))?;
+    if !quiet() {
+        eprintln!("watching {dir} (Ctrl-C to stop)");
+    }
+
+    while let Ok(event) = rx.recv() {
+        let mut touched = BTreeSet::new();
+        let mut collect = |event: notify::Result<notify::Event>| match event {
+            // Reads, including our own during verification, change nothing.
+            Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+            Ok(event) => touched.extend(event.paths.iter().filter_map(|p| watched_bundle(p))),
+            Err(e) => eprintln!("watch error: {e}"),
+        };
+        collect(event);
+        while let Ok(event) = rx.recv_timeout(WATCH_SETTLE) {
+            collect(event);
+        }
+        for bundle in &touched {
+            report_bundle(bundle, json);
+        }
+    }
+    Ok(())
+}
+
+/// The manifest JSON of a bundle directory or `.vcpb` archive.
+fn bundle_manifest(bundle: &Path) -> Result<String, String> {
+    if bundle.is_dir() {
+        let path = bundle.join("manifest.json");
+        fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {e}", path.display()))
+    } else {
+        let bytes = fs::read(bundle).map_err(|e| format!("cannot read: {e}"))?;
+        let archive = BundleArchive::unpack(&bytes).map_err(|e| e.to_string())?;
+        Ok(archive.manifest_json)
+    }
+}
+
+fn cmd_expiry(dir: &str, trust: &[String], within: u32, json: bool) -> Result<(), CliError> {
+    let trust = if trust.is_empty() {
+        TrustConfig::new()
+    } else {
+        TrustConfig::load_layered(trust, MergeStrategy::OtherWins)?
+    };
+    let window = chrono::Duration::days(i64::from(within));
+    let orchestrator = Orchestrator::new(trust).with_freshness_policy(FreshnessPolicy {
+        manifest_warning: window,
+        anchor_warning: window,
+        ..FreshnessPolicy::default()
+    });
+
+    let mut bundles = BTreeSet::new();
+    find_bundles(Path::new(dir), &mut bundles)?;
+    let (mut needs_renewal, mut expired, mut unreadable) = (0, 0, 0);
+    let report_text = !json && !quiet();
+    let mut report = Vec::new();
+    for bundle in &bundles {
+        let name = bundle.display().to_string();
+        let advisories = bundle_manifest(bundle).and_then(|manifest| {
+            orchestrator
+                .check_freshness(&manifest)
+                .map_err(|e| e.to_string())
+        });
+        match &advisories {
+            Ok(advisories) if advisories.is_empty() => {
+                if report_text {
+                    println!("OK: {name}");
+                }
+            }
+            Ok(advisories) => {
+                needs_renewal += 1;
+                let is_expired = advisories.iter().any(FreshnessAdvisory::is_expired);
+                if is_expired {
+                    expired += 1;
+                }
+                if report_text {
+                    let status = if is_expired { "EXPIRED" } else { "RENEW" };
+                    println!("{status}: {name}");
+                    for advisory in advisories {
+                        println!("  {advisory}");
+                    }
+                }
+            }
+            Err(e) => {
+                expired += 1;
+                unreadable += 1;
+                if report_text {
+                    println!("FAILED: {name}: {e}");
+                }
+            }
+        }
+        report.push(match advisories {
+            Ok(advisories) => json!({"bundle": name, "advisories": advisories}),
+            Err(e) => json!({"bundle": name, "advisories": [], "error": e}),
+        });
+    }
+
+    if json {
+        print_json(&json!({
+            "bundles": report,
+            "needs_renewal": needs_renewal,
+            "expired": expired,
+        }))?;
+    } else if report_text {
+        println!(
+            "{} bundle(s), {needs_renewal} need renewal, {expired} expired or unreadable",
+            bundles.len()
+        );
+    }
+
+    if expired > unreadable {
+        process::exit(Exit::Expired.code());
+    } else if unreadable > 0 {
+        process::exit(Exit::Failed.code());
+    }
+    Ok(())
+}
+
+fn cmd_conformance(dir: &str, json: bool) -> Result<(), CliError> {
+    let report = conformance::run_dir(Path::new(dir)).map_err(|e| format!("{dir}: {e}"))?;
+
+    if json {
+        print_json(&report)?;
+    } else if !quiet() {
+        for suite in &report.suites {
+            println!(
+                "{:<45} {:>3} passed {:>3} failed {:>3} skipped",
+                suite.suite,
+                suite.passed(),
+                suite.failed(),
+                suite.skipped()
+            );
+            for result in suite.failures() {
+                match &result.status {
+                    VectorStatus::Failed { mismatches } => {
+                        for m in mismatches {
+                            println!(
+                                "  FAIL {} {}: expected {}, got {}",
+                                result.id, m.field, m.expected, m.actual
+                            );
+                        }
+                    }
+                    VectorStatus::Errored { message } => {
+                        println!("  FAIL {}: {message}", result.id);
+                    }
+                    VectorStatus::Passed | VectorStatus::Skipped { .. } => {}
+                }
+            }
+        }
+        println!();
+        println!(
+            "{} passed, {} failed, {} skipped",
+            report.passed(),
+            report.failed(),
+            report.skipped()
+        );
+    }
+
+    if !report.is_conformant() {
+        process::exit(Exit::Failed.code());
+    }
+    Ok(())
+}
+
+fn cmd_decay_sim(args: &DecaySimArgs, json: bool) -> Result<(), CliError> {
+    let mut config = match &args.config {
+        Some(path) => serde_json::from_str(&read_input(path)?)
+            .map_err(|e| format!("invalid decay config {path}: {e}"))?,
+        None => decay::default_decay_config(match args.dimension {
+            DecayDimension::Cognitive => DecayDimensionKind::CognitiveState,
+            DecayDimension::Emotional => DecayDimensionKind::EmotionalTone,
+            DecayDimension::Energy => DecayDimensionKind::EnergyLevel,
+            DecayDimension::Urgency => DecayDimensionKind::PerceivedUrgency,
+            DecayDimension::Body => DecayDimensionKind::BodySignals,
+        }),
+    };
+    if let Some(half_life) = args.half_life {
+        config.curve = DecayCurve::Exponential;
+        config.half_life_seconds = half_life;
+    }
+    if let Some(full_decay) = args.linear {
+        config.curve = DecayCurve::Linear;
+        config.full_decay_seconds = Some(full_decay);
+    }
+    if let Some(baseline) = args.baseline {
+        config.baseline = baseline;
+    }
+    if let Some(threshold) = args.stale_threshold {
+        config.stale_threshold = threshold;
+    }
+    if let Some(window) = args.fresh_window {
+        config.fresh_window_seconds = window;
+    }
+
+    let seconds = |name: &str, value: f64| {
+        Duration::try_from_secs_f64(value)
+            .map_err(|_| format!("--{name} must be a non-negative number of seconds"))
+    };
+    let series = decay::simulate(
+        &PersonalSignal::new("simulated", args.intensity),
+        &config,
+        seconds("duration", args.duration)?,
+        seconds("step", args.step)?,
+    );
+
+    if json {
+        return print_json(&series);
+    }
+    println!("t,intensity,state");
+    for sample in &series {
+        println!("{},{},{:?}", sample.t, sample.intensity, sample.state);
+    }
+    Ok(())
+}
+
+fn cmd_capabilities(json: bool) -> Result<(), CliError> {
+    let caps = vcp_core::capabilities();
+    if json {
+        return print_json(&caps);
+    }
+
+    let join = |items: Vec<String>| {
+        if items.is_empty() {
+            "(none)".to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    println!("sdk:          {}", caps.sdk_version);
+    for (name, version) in &caps.spec_versions {
+        println!("spec:         {name} {version}");
+    }
+    println!("hash:         {}", join(caps.hash_algorithms.clone()));
+    println!("signature:    {}", join(caps.signature_algorithms.clone()));
+    println!(
+        "hooks:        {}",
+        join(caps.hook_types.iter().map(ToString::to_string).collect())
+    );
+    println!(
+        "composition:  {}",
+        join(
+            caps.composition_modes
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        )
+    );
+    println!("features:     {}", join(caps.features.clone()));
     Ok(())
 }
 
-fn cmd_verify(manifest_path: &str, content_path: &str) -> Result<(), String> {
-    let manifest_json = fs::read_to_string(manifest_path)
-        .map_err(|e| format!("cannot read {manifest_path}: {e}"))?;
-    let content =
-        fs::read_to_string(content_path).map_err(|e| format!("cannot read {content_path}: {e}"))?;
+fn passphrase(var: &str) -> Result<String, String> {
+    std::env::var(var).map_err(|_| format!("set {var} to the key passphrase"))
+}
 
-    let result = transport::verify_bundle(&manifest_json, &content).map_err(|e| e.to_string())?;
+fn cmd_keygen(
+    out: Option<&str>,
+    format: &str,
+    encrypt: bool,
+    passphrase_env: &str,
+    json: bool,
+) -> Result<(), CliError> {
+    let format: KeyFormat = format
+        .parse()
+        .map_err(|e: vcp_core::VcpError| e.to_string())?;
+    let key = KeyPair::generate();
 
-    if result.is_valid() {
-        println!("VALID: {}", result.message);
+    let secret = if encrypt {
+        let passphrase = passphrase(passphrase_env)?;
+        let file = EncryptedKey::encrypt(&key, &passphrase)?;
+        let mut json = file.to_json()?;
+        json.push('\n');
+        json.into_bytes()
     } else {
-        println!("FAILED [{}]: {}", result.code, result.message);
-        process::exit(2);
+        let mut secret = key.export(format)?;
+        if format == KeyFormat::Base64 {
+            secret.push(b'\n');
+        }
+        secret
+    };
+
+    let mut report = json!({
+        "public_key": key.public_key_base64(),
+        "fingerprint": key.fingerprint(),
+    });
+    if let Some(path) = out {
+        write_secret(path, &secret).map_err(|e| CliError::io("write", path, &e))?;
+        if json {
+            report["path"] = json!(path);
+            return print_json(&report);
+        }
+        println!("public_key:  {}", key.public_key_base64());
+        println!("fingerprint: {}", key.fingerprint());
+    } else {
+        if format == KeyFormat::Raw && !encrypt {
+            return Err("raw keys are binary; use --out".into());
+        }
+        if json {
+            report["secret"] = json!(String::from_utf8_lossy(&secret).trim_end());
+            return print_json(&report);
+        }
+        use std::io::Write;
+        std::io::stdout().write_all(&secret)?;
+        if !quiet() {
+            eprintln!("public_key:  {}", key.public_key_base64());
+            eprintln!("fingerprint: {}", key.fingerprint());
+        }
+    }
+    Ok(())
+}
+
+/// Create a key file readable only by the owner.
+fn write_secret(path: &str, data: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
     }
+    options.open(path)?.write_all(data)
+}
 
+fn cmd_key_inspect(path: &str, passphrase_env: &str, json: bool) -> Result<(), CliError> {
+    let data = if path == "-" {
+        use std::io::Read;
+        let mut buf = Vec::new();
+        std::io::stdin().read_to_end(&mut buf)?;
+        buf
+    } else {
+        fs::read(path).map_err(|e| CliError::io("read", path, &e))?
+    };
+
+    let encrypted = std::str::from_utf8(&data)
+        .ok()
+        .and_then(|text| EncryptedKey::from_json(text).ok());
+    let (kind, public) = if let Some(file) = encrypted {
+        match std::env::var(passphrase_env) {
+            Ok(passphrase) => {
+                let key = file.decrypt(&passphrase)?;
+                ("encrypted secret key (passphrase ok)", key.public_bytes())
+            }
+            Err(_) => {
+                let public = keys::import_public_key(file.public_key.as_bytes())?;
+                ("encrypted secret key (not decrypted)", public)
+            }
+        }
+    } else if let Ok(key) = KeyPair::import(&data) {
+        ("secret key", key.public_bytes())
+    } else {
+        let public = keys::import_public_key(&data)?;
+        ("public key", public)
+    };
+
+    let public_key = vcp_core::TrustAnchor::encode_public_key(&public);
+    let fingerprint = keys::public_key_fingerprint(&public);
+    if json {
+        return print_json(&json!({
+            "type": kind,
+            "public_key": public_key,
+            "fingerprint": fingerprint,
+        }));
+    }
+    println!("type:        {kind}");
+    println!("public_key:  {public_key}");
+    println!("fingerprint: {fingerprint}");
+    Ok(())
+}
+
+fn cmd_trust_fingerprint(
+    path: &str,
+    entity: Option<&str>,
+    key_id: Option<&str>,
+    json: bool,
+) -> Result<(), CliError> {
+    let input = read_input(path)?;
+    let config = TrustConfig::from_json(&input).map_err(|e| CliError::parse(e, &input))?;
+    let mut anchors: Vec<&TrustAnchor> = config
+        .issuers
+        .values()
+        .chain(config.auditors.values())
+        .chain(config.organizations.values())
+        .flatten()
+        .filter(|a| entity.is_none_or(|e| a.id == e))
+        .filter(|a| key_id.is_none_or(|k| a.key_id == k))
+        .collect();
+    anchors.sort_by(|a, b| (&a.id, &a.key_id).cmp(&(&b.id, &b.key_id)));
+    if anchors.is_empty() {
+        return Err("no matching anchors".into());
+    }
+
+    let mut rows = Vec::new();
+    for anchor in anchors {
+        let fingerprint = anchor.fingerprint().ok_or_else(|| {
+            format!(
+                "{} key '{}': public key is not valid base64",
+                anchor.id, anchor.key_id
+            )
+        })?;
+        rows.push(json!({
+            "entity": anchor.id,
+            "type": anchor.anchor_type,
+            "key_id": anchor.key_id,
+            "fingerprint": fingerprint.to_string(),
+            "short": fingerprint.short(),
+            "words": fingerprint.words(),
+            "emoji": fingerprint.emoji(),
+        }));
+    }
+    if json {
+        return print_json(&rows);
+    }
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{} ({}) {}",
+            row["entity"].as_str().unwrap_or_default(),
+            row["type"].as_str().unwrap_or_default(),
+            row["key_id"].as_str().unwrap_or_default()
+        );
+        for field in ["fingerprint", "short", "words", "emoji"] {
+            println!("  {field:<12} {}", row[field].as_str().unwrap_or_default());
+        }
+    }
+    Ok(())
+}
+
+fn cmd_scrub(path: &str, salt: &str, json: bool) -> Result<(), CliError> {
+    let input = read_input(path)?;
+    let report = Scrubber::new(salt).scrub(&input);
+    if json {
+        return print_json(&report);
+    }
+    print!("{}", report.output);
+    if quiet() {
+        return Ok(());
+    }
+
+    match (&report.expected_error, &report.actual_error) {
+        (None, None) => eprintln!("scrubbed {:?}: parses cleanly", report.kind),
+        (Some(_), Some(actual)) if report.reproduces_error() => {
+            eprintln!(
+                "scrubbed {:?}: reproduces parse error: {actual}",
+                report.kind
+            );
+        }
+        (expected, actual) => {
+            eprintln!(
+                "warning: scrubbed {:?} does not reproduce the original outcome",
+                report.kind
+            );
+            eprintln!("  original: {}", expected.as_deref().unwrap_or("ok"));
+            eprintln!("  scrubbed: {}", actual.as_deref().unwrap_or("ok"));
+        }
+    }
+    Ok(())
+}
+
+fn cmd_explain(input: &str, json: bool) -> Result<(), CliError> {
+    let raw = if input == "-" || Path::new(input).is_file() {
+        read_input(input)?
+    } else {
+        input.to_string()
+    };
+    let explanation = explain::explain(&raw)?;
+    if json {
+        print_json(&explanation)?;
+    } else {
+        print!("{explanation}");
+    }
+    Ok(())
+}
+
+fn cmd_lint(path: &str, fix: bool, json: bool) -> Result<(), CliError> {
+    let mut raw = read_input(path)?;
+    if fix {
+        let fixed = lint::fix(&raw);
+        if path == "-" {
+            print!("{fixed}");
+        } else if fixed != raw {
+            fs::write(path, &fixed).map_err(|e| CliError::io("write", path, &e))?;
+            if !quiet() {
+                eprintln!("fixed {path}");
+            }
+        }
+        raw = fixed;
+    }
+
+    let report = lint::lint(&raw)?;
+    let out = if json {
+        serde_json::to_string_pretty(&report.issues)? + "\n"
+    } else {
+        report.to_string()
+    };
+    // With `--fix` on stdin, stdout carries the fixed token.
+    if json || !quiet() {
+        if fix && path == "-" {
+            eprint!("{out}");
+        } else {
+            print!("{out}");
+        }
+    }
+
+    if report.has_errors() {
+        process::exit(Exit::Failed.code());
+    }
+    Ok(())
+}
+
+fn cmd_lint_content(
+    path: &str,
+    config: Option<&str>,
+    strict: bool,
+    json: bool,
+) -> Result<(), CliError> {
+    let mut config = match config {
+        Some(config_path) => ContentLintConfig::from_json(&read_input(config_path)?)
+            .map_err(|e| format!("invalid lint config {config_path}: {e}"))?,
+        None => ContentLintConfig::default(),
+    };
+    if strict {
+        config = config.strict();
+    }
+
+    let report = content_lint::lint_content(&read_input(path)?, &config);
+    if json {
+        print_json(&report.issues)?;
+    } else if !quiet() {
+        print!("{report}");
+    }
+
+    if report.has_errors() {
+        process::exit(Exit::Failed.code());
+    }
+    Ok(())
+}
+
+/// A constitution given as JSON (`{"id", "rules", "priority"}`), or `None`
+/// if `raw` is plain text.
+fn parse_constitution_json(raw: &str) -> Result<Option<Constitution>, String> {
+    let Ok(serde_json::Value::Object(doc)) = serde_json::from_str(raw) else {
+        return Ok(None);
+    };
+    let id = doc.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+    let rules = doc
+        .get("rules")
+        .and_then(|v| v.as_array())
+        .ok_or("constitution JSON needs a \"rules\" array")?
+        .iter()
+        .map(|r| {
+            r.as_str()
+                .map(str::to_string)
+                .ok_or("rules must be strings")
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let priority = doc
+        .get("priority")
+        .and_then(serde_json::Value::as_i64)
+        .and_then(|p| i32::try_from(p).ok())
+        .unwrap_or(0);
+    Ok(Some(Constitution::new(id, rules, priority)))
+}
+
+fn cmd_diff(old: &str, new: &str, json: bool) -> Result<(), CliError> {
+    let (old_raw, new_raw) = (read_input(old)?, read_input(new)?);
+    let changelog: ConstitutionDiff = match (
+        parse_constitution_json(&old_raw)?,
+        parse_constitution_json(&new_raw)?,
+    ) {
+        (Some(a), Some(b)) => diff::diff_constitutions(&a, &b),
+        (None, None) => diff::diff_text(&old_raw, &new_raw),
+        _ => return Err("cannot diff a JSON constitution against a text one".into()),
+    };
+    if json {
+        print_json(&changelog)?;
+    } else {
+        print!("{changelog}");
+    }
     Ok(())
 }
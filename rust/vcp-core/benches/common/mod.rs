@@ -0,0 +1,12 @@
+//! Shared helpers for the benchmark suite.
+
+/// Print the documented baseline for each benchmark in a group.
+///
+/// Criterion compares against the last saved run on the same machine;
+/// these figures are the reference numbers from `benches/README.md`, printed
+/// so a regression is visible even on a fresh checkout.
+pub fn print_baselines(group: &str, baselines: &[(&str, &str)]) {
+    for (name, time) in baselines {
+        println!("baseline {group}/{name}: {time}");
+    }
+}
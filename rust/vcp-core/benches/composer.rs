@@ -0,0 +1,113 @@
+//! Composition of large constitutions.
+//!
+//! Run with `cargo bench -p vcp-core --features bench --bench composer`.
+
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+use vcp_core::composer::{Composer, CompositionMode, Constitution};
+use vcp_core::composer_session::ComposerSession;
+
+/// Two constitutions with `total` rules between them. Every tenth rule in
+/// the overlay contradicts one in the base, so conflict handling is
+/// exercised as well as the pairwise scan.
+fn constitutions(total: usize) -> Vec<Constitution> {
+    let half = total / 2;
+    let base: Vec<String> = (0..half)
+        .map(|i| format!("always cite source {i} when discussing topic {}", i % 40))
+        .collect();
+    let overlay: Vec<String> = (0..half)
+        .map(|i| {
+            if i % 10 == 0 {
+                format!("never cite source {i} when discussing topic {}", i % 40)
+            } else {
+                format!(
+                    "prefer plain language for audience {i} in region {}",
+                    i % 25
+                )
+            }
+        })
+        .collect();
+    vec![
+        Constitution::new("base", base, 10),
+        Constitution::new("overlay", overlay, 5),
+    ]
+}
+
+fn bench_compose(c: &mut Criterion) {
+    common::print_baselines(
+        "compose_1k_rules",
+        &[("base", "56 ms"), ("override", "49 ms")],
+    );
+
+    let composer = Composer::new();
+    let input = constitutions(1000);
+
+    let mut group = c.benchmark_group("compose_1k_rules");
+    group.sample_size(10);
+    for mode in [CompositionMode::Base, CompositionMode::Override] {
+        group.bench_with_input(BenchmarkId::from_parameter(mode), &input, |b, input| {
+            b.iter(|| black_box(composer.compose(black_box(input), mode).unwrap()));
+        });
+    }
+    group.finish();
+}
+
+/// Adding or removing a small constitution next to 10k merged rules, against
+/// recomposing everything.
+fn bench_incremental(c: &mut Criterion) {
+    common::print_baselines(
+        "compose_10k_rules",
+        &[
+            ("full", "3.6 s"),
+            ("session_build", "49 ms"),
+            ("session_add_100", "0.43 ms"),
+        ],
+    );
+
+    let mut input = constitutions(10_000);
+    let patch = Constitution::new(
+        "patch",
+        (0..100)
+            .map(|i| format!("never cite source {i} when discussing topic {}", i % 40))
+            .collect(),
+        20,
+    );
+    let mode = CompositionMode::Override;
+
+    let mut session = ComposerSession::new(mode);
+    for constitution in &input {
+        session.add_constitution(constitution.clone()).unwrap();
+    }
+
+    let mut group = c.benchmark_group("compose_10k_rules");
+    group.sample_size(10);
+    group.bench_function("session_build", |b| {
+        b.iter(|| {
+            let mut session = ComposerSession::new(mode);
+            for constitution in black_box(&input) {
+                session.add_constitution(constitution.clone()).unwrap();
+            }
+            session
+        });
+    });
+    group.bench_function("session_add_100", |b| {
+        b.iter_batched(
+            || session.clone(),
+            |mut session| {
+                session.add_constitution(black_box(patch.clone())).unwrap();
+                session
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    input.push(patch);
+    group.bench_function("full", |b| {
+        b.iter(|| black_box(Composer::new().compose(black_box(&input), mode).unwrap()));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_compose, bench_incremental);
+criterion_main!(benches);
@@ -0,0 +1,87 @@
+//! CSM-1 compact codes and 8-line tokens: parse and encode.
+//!
+//! Run with `cargo bench -p vcp-core --features bench --bench csm1`.
+
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use vcp_core::csm1::{Csm1Code, Csm1CodeRef, Csm1Token};
+
+const CODES: [&str; 6] = [
+    "N5",
+    "N5+F+E",
+    "z3+p:sec",
+    "M2@1.0.0",
+    "D4+W+L+P:ORG7@2.1.0",
+    "g1+f+w+e+h+i+l+p+s+a+v+g:ACMECORP@10.20.30",
+];
+
+const TOKEN: &str = "VCP:1.0:user-001\n\
+                     C:family.safe.guide@1.2.0\n\
+                     P:N:5\n\
+                     G:learn:beginner:visual\n\
+                     X:no_violence,no_profanity\n\
+                     F:verbose\n\
+                     S:private_a,private_b\n\
+                     R:\u{1F9E0}focused:3|\u{1F50B}rested:4|\u{1FA7A}pain:2[headache]";
+
+fn bench_code(c: &mut Criterion) {
+    common::print_baselines(
+        "csm1_code",
+        &[
+            ("parse_owned", "1.5 µs"),
+            ("parse_borrowed", "0.80 µs"),
+            ("encode", "1.4 µs"),
+        ],
+    );
+
+    let mut group = c.benchmark_group("csm1_code");
+    group.throughput(Throughput::Elements(CODES.len() as u64));
+
+    group.bench_function("parse_owned", |b| {
+        b.iter(|| {
+            for raw in CODES {
+                black_box(Csm1Code::parse(black_box(raw)).unwrap());
+            }
+        });
+    });
+
+    group.bench_function("parse_borrowed", |b| {
+        b.iter(|| {
+            for raw in CODES {
+                black_box(Csm1CodeRef::parse(black_box(raw)).unwrap());
+            }
+        });
+    });
+
+    let parsed: Vec<Csm1Code> = CODES.iter().map(|c| Csm1Code::parse(c).unwrap()).collect();
+    group.bench_function("encode", |b| {
+        b.iter(|| {
+            for code in &parsed {
+                black_box(black_box(code).encode());
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_token(c: &mut Criterion) {
+    common::print_baselines("csm1_token", &[("parse", "2.1 µs"), ("encode", "2.2 µs")]);
+
+    let mut group = c.benchmark_group("csm1_token");
+    group.bench_function("parse", |b| {
+        b.iter(|| black_box(Csm1Token::parse(black_box(TOKEN)).unwrap()));
+    });
+
+    let token = Csm1Token::parse(TOKEN).unwrap();
+    group.bench_function("encode", |b| {
+        b.iter(|| black_box(black_box(&token).encode()));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_code, bench_token);
+criterion_main!(benches);
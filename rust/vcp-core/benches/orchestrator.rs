@@ -0,0 +1,94 @@
+//! Full orchestrator verification pipeline.
+//!
+//! Run with `cargo bench -p vcp-core --features bench --bench orchestrator`.
+
+mod common;
+
+use chrono::{Duration, Utc};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use vcp_core::error::VerificationCode;
+use vcp_core::orchestrator::{Orchestrator, VerificationContext};
+use vcp_core::transport::compute_content_hash;
+use vcp_core::trust::{AnchorState, AnchorType, TrustAnchor, TrustConfig};
+
+fn anchor(id: &str, key_id: &str, anchor_type: AnchorType) -> TrustAnchor {
+    TrustAnchor {
+        id: id.into(),
+        key_id: key_id.into(),
+        algorithm: "ed25519".into(),
+        public_key: "base64:AAAA".into(),
+        anchor_type,
+        valid_from: Utc::now() - Duration::days(1),
+        valid_until: Utc::now() + Duration::days(365),
+        state: AnchorState::Active,
+    }
+}
+
+fn trust_config() -> TrustConfig {
+    let mut config = TrustConfig::new();
+    config.add_issuer(
+        "bench-issuer",
+        anchor("bench-issuer", "key-01", AnchorType::Issuer),
+    );
+    config.add_auditor(
+        "bench-auditor",
+        anchor("bench-auditor", "aud-key-01", AnchorType::Auditor),
+    );
+    config
+}
+
+fn manifest(content: &str) -> String {
+    let now = Utc::now();
+    serde_json::json!({
+        "vcp_version": "2.0",
+        "bundle": {
+            "id": "bench-bundle",
+            "version": "1.0.0",
+            "content_hash": compute_content_hash(content).unwrap(),
+        },
+        "issuer": {"id": "bench-issuer", "key_id": "key-01"},
+        "safety_attestation": {
+            "auditor": "bench-auditor",
+            "auditor_key_id": "aud-key-01",
+            "attestation_type": "injection-safe",
+            "signature": "base64:fake-sig",
+        },
+        "timestamps": {
+            "iat": now.to_rfc3339(),
+            "nbf": (now - Duration::hours(1)).to_rfc3339(),
+            "exp": (now + Duration::days(30)).to_rfc3339(),
+            "jti": "018bcfe5-6800-7daf-b1dc-2dec89025cc1",
+        },
+        "budget": {"token_count": 1000, "tokenizer": "cl100k_base", "max_context_share": 0.25},
+    })
+    .to_string()
+}
+
+fn bench_pipeline(c: &mut Criterion) {
+    common::print_baselines("orchestrator", &[("verify", "265 µs")]);
+
+    let trust = trust_config();
+    let ctx = VerificationContext::new(trust.clone());
+    let content = "Always respond with care and cite sources where possible.\n".repeat(64);
+    let manifest = manifest(&content);
+
+    // A fresh orchestrator per iteration keeps the replay cache from
+    // short-circuiting the pipeline on the second run.
+    let orch = Orchestrator::new(trust.clone());
+    assert_eq!(
+        orch.verify(&manifest, &content, &ctx),
+        VerificationCode::Valid
+    );
+
+    c.bench_function("orchestrator/verify", |b| {
+        b.iter_batched(
+            || Orchestrator::new(trust.clone()),
+            |orch| black_box(orch.verify(black_box(&manifest), &content, &ctx)),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+criterion_group!(benches, bench_pipeline);
+criterion_main!(benches);
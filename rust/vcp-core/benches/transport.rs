@@ -0,0 +1,97 @@
+//! Content canonicalization/hashing and manifest signing.
+//!
+//! Run with `cargo bench -p vcp-core --features bench --bench transport`.
+
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ed25519_dalek::SigningKey;
+
+use vcp_core::transport::{
+    canonicalize_content, canonicalize_manifest, compute_content_hash, sign_manifest,
+    verify_manifest_signature,
+};
+
+/// Constitution-like text of roughly `bytes` bytes, with CRLFs and
+/// trailing whitespace so canonicalization has work to do.
+fn sample_content(bytes: usize) -> String {
+    let line = "Always respond with care and cite sources where possible.   \r\n";
+    line.repeat(bytes / line.len() + 1)
+}
+
+fn sample_manifest() -> serde_json::Value {
+    serde_json::json!({
+        "vcp_version": "2.0",
+        "bundle": {
+            "id": "bench-bundle",
+            "version": "1.0.0",
+            "content_hash": compute_content_hash(&sample_content(4096)).unwrap(),
+        },
+        "issuer": {"id": "bench-issuer", "key_id": "key-01"},
+        "timestamps": {
+            "iat": "2026-01-01T00:00:00Z",
+            "nbf": "2026-01-01T00:00:00Z",
+            "exp": "2027-01-01T00:00:00Z",
+            "jti": "018bcfe5-6800-7daf-b1dc-2dec89025cc1",
+        },
+        "budget": {"token_count": 1000, "tokenizer": "cl100k_base", "max_context_share": 0.25},
+    })
+}
+
+fn bench_content(c: &mut Criterion) {
+    common::print_baselines(
+        "content",
+        &[
+            ("canonicalize/1024", "47 µs"),
+            ("canonicalize/65536", "2.2 ms"),
+            ("hash/1024", "38 µs"),
+            ("hash/65536", "2.3 ms"),
+        ],
+    );
+
+    let mut group = c.benchmark_group("content");
+    for size in [1024, 65536] {
+        let content = sample_content(size);
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::new("canonicalize", size), &content, |b, s| {
+            b.iter(|| black_box(canonicalize_content(black_box(s)).unwrap()));
+        });
+        group.bench_with_input(BenchmarkId::new("hash", size), &content, |b, s| {
+            b.iter(|| black_box(compute_content_hash(black_box(s)).unwrap()));
+        });
+    }
+    group.finish();
+}
+
+fn bench_manifest(c: &mut Criterion) {
+    common::print_baselines(
+        "manifest",
+        &[
+            ("canonicalize", "2.8 µs"),
+            ("sign", "72 µs"),
+            ("verify", "61 µs"),
+        ],
+    );
+
+    let manifest = sample_manifest();
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let public = key.verifying_key().to_bytes();
+    let signature = sign_manifest(&manifest, &key.to_bytes()).unwrap();
+
+    let mut group = c.benchmark_group("manifest");
+    group.bench_function("canonicalize", |b| {
+        b.iter(|| black_box(canonicalize_manifest(black_box(&manifest)).unwrap()));
+    });
+    group.bench_function("sign", |b| {
+        b.iter(|| black_box(sign_manifest(black_box(&manifest), &key.to_bytes()).unwrap()));
+    });
+    group.bench_function("verify", |b| {
+        b.iter(|| {
+            assert!(verify_manifest_signature(black_box(&manifest), &public, &signature).unwrap());
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_content, bench_manifest);
+criterion_main!(benches);
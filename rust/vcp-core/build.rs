@@ -0,0 +1,23 @@
+//! Build script for vcp-core.
+//!
+//! Only does work when the `proto` feature is enabled: compiles
+//! `proto/vcp/v1/vcp.proto` with prost-build using a vendored `protoc`,
+//! so no system protobuf toolchain is required.
+
+fn main() {
+    #[cfg(feature = "proto")]
+    compile_protos();
+}
+
+#[cfg(feature = "proto")]
+fn compile_protos() {
+    const PROTO: &str = "proto/vcp/v1/vcp.proto";
+    println!("cargo:rerun-if-changed={PROTO}");
+
+    let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available");
+    let mut config = prost_build::Config::new();
+    config.protoc_executable(protoc);
+    config
+        .compile_protos(&[PROTO], &["proto"])
+        .expect("failed to compile vcp.proto");
+}
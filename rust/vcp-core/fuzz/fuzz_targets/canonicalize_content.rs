@@ -0,0 +1,29 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcp_core::transport::{canonicalize_content, ContentCanonicalizer};
+
+fuzz_target!(|input: (&[u8], u8)| {
+    let (bytes, split) = input;
+    let one_shot = std::str::from_utf8(bytes).ok().map(canonicalize_content);
+
+    // Feeding the same bytes in two chunks, split anywhere (even inside a
+    // UTF-8 sequence), must give the same result as the one-shot path.
+    let at = usize::from(split) % (bytes.len() + 1);
+    let mut streaming = ContentCanonicalizer::new(Vec::new());
+    let streamed = streaming
+        .update_bytes(&bytes[..at])
+        .and_then(|()| streaming.update_bytes(&bytes[at..]))
+        .and_then(|()| streaming.finish());
+
+    match (one_shot, streamed) {
+        (Some(Ok(a)), Ok(b)) => {
+            assert_eq!(a, b);
+            // Canonical form is a fixed point.
+            let again = canonicalize_content(std::str::from_utf8(&a).unwrap()).unwrap();
+            assert_eq!(again, a);
+        }
+        (Some(Ok(_)), Err(e)) => panic!("streaming rejected valid input: {e}"),
+        (Some(Err(_)) | None, _) => {}
+    }
+});
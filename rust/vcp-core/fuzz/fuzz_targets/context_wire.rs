@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcp_core::context::FullContext;
+
+fuzz_target!(|raw: &str| {
+    if let Ok(ctx) = FullContext::from_wire(raw) {
+        // Encoding a parsed context must not panic and must parse again.
+        let _ = FullContext::from_wire(&ctx.to_wire());
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcp_core::revocation::Crl;
+
+fuzz_target!(|raw: &str| {
+    if let Ok(crl) = Crl::from_json(raw) {
+        let _ = crl.find("fuzz-jti");
+    }
+});
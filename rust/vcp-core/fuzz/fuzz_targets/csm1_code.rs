@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcp_core::csm1::Csm1Code;
+
+fuzz_target!(|raw: &str| {
+    let owned = Csm1Code::parse(raw);
+
+    // The borrowed parser must agree with the owned one, errors included.
+    let borrowed = Csm1Code::parse_borrowed(raw).map(|c| c.to_code());
+    assert_eq!(owned, borrowed);
+
+    if let Ok(code) = owned {
+        let reparsed = Csm1Code::parse(&code.encode()).expect("encoded code must parse");
+        assert_eq!(reparsed, code);
+    }
+});
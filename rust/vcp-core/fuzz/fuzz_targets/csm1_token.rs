@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcp_core::csm1::Csm1Token;
+
+fuzz_target!(|raw: &str| {
+    let _ = Csm1Token::parse(raw);
+});
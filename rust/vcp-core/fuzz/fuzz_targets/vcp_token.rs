@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcp_core::identity::VcpToken;
+
+fuzz_target!(|raw: &str| {
+    if let Ok(token) = VcpToken::parse(raw) {
+        let reparsed = VcpToken::parse(&token.full()).expect("full form must parse");
+        assert_eq!(reparsed, token);
+    }
+});
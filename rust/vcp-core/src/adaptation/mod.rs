@@ -0,0 +1,13 @@
+//! VCP/A — the adaptation layer.
+//!
+//! The adaptation layer sits on top of identity (VCP/I), transport
+//! (VCP/T) and semantics (VCP/S). A client sends the constitution token
+//! it wants applied together with its current context; the adapter
+//! answers with the constitution it selected, how it was composed, and
+//! any directives the model should follow.
+//!
+//! | Module | Purpose |
+//! |--------|---------|
+//! | [`protocol`] | Request/response envelopes exchanged with an adapter |
+
+pub mod protocol;
@@ -0,0 +1,556 @@
+//! VCP/A request and response envelopes.
+//!
+//! An [`AdaptationRequest`] carries a VCP/I token, an optional context
+//! wire string and the client's capability flags. The adapter replies
+//! with an [`AdaptationResponse`] naming the selected constitution, the
+//! composition that produced it and a list of [`Directive`]s.
+//!
+//! Both envelopes serialize to canonical JSON (sorted keys, no
+//! whitespace) so they can be hashed or signed with the same tooling as
+//! manifests.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::adaptation::protocol::{AdaptationRequest, AdaptationResponse, SelectedConstitution};
+//!
+//! let request = AdaptationRequest::new("req-1", "family.safe.guide@1.2.0")
+//!     .with_context("⏰🌅|📍🏡")
+//!     .with_capability("personal", true);
+//! request.validate().unwrap();
+//!
+//! let json = request.to_canonical_json().unwrap();
+//! let parsed = AdaptationRequest::from_json(&json).unwrap();
+//! assert_eq!(parsed, request);
+//!
+//! let response = AdaptationResponse::ok(
+//!     "req-1",
+//!     SelectedConstitution::new("family.safe.guide", "1.2.0"),
+//! );
+//! assert!(response.validate().is_ok());
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::composer::CompositionResult;
+use crate::context::FullContext;
+use crate::error::{VcpError, VcpResult};
+use crate::identity::{SemVer, VcpToken};
+
+/// Version of the adaptation envelope format produced by this crate.
+pub const PROTOCOL_VERSION: &str = "1.0.0";
+
+/// Maximum length of a request ID.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+// ── Request ──────────────────────────────────────────────────
+
+/// A request asking an adapter to select and compose a constitution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdaptationRequest {
+    /// Envelope format version (`X.Y.Z`).
+    pub version: String,
+    /// Caller-chosen correlation ID, echoed in the response.
+    pub request_id: String,
+    /// VCP/I token of the requested constitution.
+    pub token: String,
+    /// Context in full wire format (situational ‖ personal).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// Client capability flags (e.g. `"personal": true`).
+    #[serde(default)]
+    pub capabilities: BTreeMap<String, bool>,
+}
+
+impl AdaptationRequest {
+    /// Create a request for `token` with no context or capabilities.
+    pub fn new(request_id: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            version: PROTOCOL_VERSION.to_string(),
+            request_id: request_id.into(),
+            token: token.into(),
+            context: None,
+            capabilities: BTreeMap::new(),
+        }
+    }
+
+    /// Attach a context wire string.
+    #[must_use]
+    pub fn with_context(mut self, wire: impl Into<String>) -> Self {
+        self.context = Some(wire.into());
+        self
+    }
+
+    /// Add a capability flag.
+    #[must_use]
+    pub fn with_capability(mut self, name: impl Into<String>, enabled: bool) -> Self {
+        self.capabilities.insert(name.into(), enabled);
+        self
+    }
+
+    /// Parse the requested token.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`VcpToken::parse`] error if the token is malformed.
+    pub fn parsed_token(&self) -> VcpResult<VcpToken> {
+        VcpToken::parse(&self.token)
+    }
+
+    /// Parse the attached context, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`FullContext::from_wire`] error if the wire string
+    /// is malformed.
+    pub fn parsed_context(&self) -> VcpResult<Option<FullContext>> {
+        self.context
+            .as_deref()
+            .map(FullContext::from_wire)
+            .transpose()
+    }
+
+    /// Returns `true` if the client advertised `name` as enabled.
+    pub fn supports(&self, name: &str) -> bool {
+        self.capabilities.get(name).copied().unwrap_or(false)
+    }
+
+    /// Check that every field is well formed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for a bad version, request ID or
+    /// capability name, and the underlying parse error for a malformed
+    /// token or context.
+    pub fn validate(&self) -> VcpResult<()> {
+        validate_envelope(&self.version, &self.request_id)?;
+        self.parsed_token()?;
+        self.parsed_context()?;
+        if self.capabilities.keys().any(String::is_empty) {
+            return Err(VcpError::ParseError(
+                "capability names must not be empty".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Serialize to canonical JSON (sorted keys, no whitespace).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_canonical_json(&self) -> VcpResult<String> {
+        canonical_json(self)
+    }
+
+    /// Deserialize and validate a request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] for invalid JSON, or any error
+    /// from [`AdaptationRequest::validate`].
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        let request: Self = serde_json::from_str(json)?;
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+// ── Response ─────────────────────────────────────────────────
+
+/// Outcome of an adaptation request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdaptationStatus {
+    /// A constitution was selected.
+    Ok,
+    /// The adapter refused the request (policy, unknown token, ...).
+    Rejected,
+    /// The adapter failed while processing the request.
+    Error,
+}
+
+/// The constitution an adapter selected for a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectedConstitution {
+    /// Constitution ID (usually the canonical VCP/I token).
+    pub id: String,
+    /// Constitution version (`X.Y.Z`).
+    pub version: String,
+    /// `sha256:<hex>` hash of the constitution content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+impl SelectedConstitution {
+    /// Create a selection without a content hash.
+    pub fn new(id: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            version: version.into(),
+            content_hash: None,
+        }
+    }
+
+    /// Attach the content hash.
+    #[must_use]
+    pub fn with_content_hash(mut self, hash: impl Into<String>) -> Self {
+        self.content_hash = Some(hash.into());
+        self
+    }
+}
+
+/// An instruction from the adapter to the model runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Directive {
+    /// Directive key (e.g. `"tone"`, `"verbosity"`).
+    pub key: String,
+    /// Directive value.
+    pub value: String,
+    /// Why the directive was issued (e.g. the context dimension that
+    /// triggered it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Directive {
+    /// Create a directive without a reason.
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            reason: None,
+        }
+    }
+
+    /// Attach a reason.
+    #[must_use]
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+/// An adapter's reply to an [`AdaptationRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdaptationResponse {
+    /// Envelope format version (`X.Y.Z`).
+    pub version: String,
+    /// Correlation ID copied from the request.
+    pub request_id: String,
+    /// Outcome of the request.
+    pub status: AdaptationStatus,
+    /// Selected constitution (required when `status` is `ok`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constitution: Option<SelectedConstitution>,
+    /// Composition that produced the selected constitution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub composition: Option<CompositionResult>,
+    /// Directives for the model runtime.
+    #[serde(default)]
+    pub directives: Vec<Directive>,
+    /// Human-readable reason (required when `status` is not `ok`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AdaptationResponse {
+    /// Create a successful response.
+    pub fn ok(request_id: impl Into<String>, constitution: SelectedConstitution) -> Self {
+        Self {
+            version: PROTOCOL_VERSION.to_string(),
+            request_id: request_id.into(),
+            status: AdaptationStatus::Ok,
+            constitution: Some(constitution),
+            composition: None,
+            directives: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Create a rejection.
+    pub fn rejected(request_id: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::failure(request_id, AdaptationStatus::Rejected, reason)
+    }
+
+    /// Create an error response.
+    pub fn error(request_id: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::failure(request_id, AdaptationStatus::Error, reason)
+    }
+
+    fn failure(
+        request_id: impl Into<String>,
+        status: AdaptationStatus,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            version: PROTOCOL_VERSION.to_string(),
+            request_id: request_id.into(),
+            status,
+            constitution: None,
+            composition: None,
+            directives: Vec::new(),
+            error: Some(reason.into()),
+        }
+    }
+
+    /// Attach the composition result.
+    #[must_use]
+    pub fn with_composition(mut self, composition: CompositionResult) -> Self {
+        self.composition = Some(composition);
+        self
+    }
+
+    /// Append a directive.
+    #[must_use]
+    pub fn with_directive(mut self, directive: Directive) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Returns `true` if a constitution was selected.
+    pub fn is_ok(&self) -> bool {
+        self.status == AdaptationStatus::Ok
+    }
+
+    /// Check that the response is internally consistent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the version or request ID is
+    /// malformed, an `ok` response has no constitution, a failure has no
+    /// error message, the content hash is not `sha256:<64 hex>`, or a
+    /// directive has an empty key.
+    pub fn validate(&self) -> VcpResult<()> {
+        validate_envelope(&self.version, &self.request_id)?;
+
+        match (self.status, &self.constitution) {
+            (AdaptationStatus::Ok, None) => {
+                return Err(VcpError::ParseError(
+                    "ok response must name a constitution".into(),
+                ));
+            }
+            (AdaptationStatus::Ok, Some(c)) => validate_selection(c)?,
+            (_, _) => {
+                if self.error.as_deref().is_none_or(str::is_empty) {
+                    return Err(VcpError::ParseError(format!(
+                        "{} response must carry an error message",
+                        status_label(self.status)
+                    )));
+                }
+            }
+        }
+
+        if self.directives.iter().any(|d| d.key.is_empty()) {
+            return Err(VcpError::ParseError(
+                "directive keys must not be empty".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Serialize to canonical JSON (sorted keys, no whitespace).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_canonical_json(&self) -> VcpResult<String> {
+        canonical_json(self)
+    }
+
+    /// Deserialize and validate a response.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] for invalid JSON, or any error
+    /// from [`AdaptationResponse::validate`].
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        let response: Self = serde_json::from_str(json)?;
+        response.validate()?;
+        Ok(response)
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────
+
+fn status_label(status: AdaptationStatus) -> &'static str {
+    match status {
+        AdaptationStatus::Ok => "ok",
+        AdaptationStatus::Rejected => "rejected",
+        AdaptationStatus::Error => "error",
+    }
+}
+
+fn validate_envelope(version: &str, request_id: &str) -> VcpResult<()> {
+    SemVer::parse(version)?;
+    if request_id.is_empty() || request_id.len() > MAX_REQUEST_ID_LEN {
+        return Err(VcpError::ParseError(format!(
+            "request_id must be 1-{MAX_REQUEST_ID_LEN} bytes"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_selection(selection: &SelectedConstitution) -> VcpResult<()> {
+    if selection.id.is_empty() {
+        return Err(VcpError::ParseError("constitution id is empty".into()));
+    }
+    SemVer::parse(&selection.version)?;
+    if let Some(hash) = &selection.content_hash {
+        let hex = hash.strip_prefix("sha256:").unwrap_or_default();
+        if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(VcpError::ParseError(format!(
+                "content_hash must be sha256:<64 hex>, got: {hash}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Serialize through [`serde_json::Value`] so object keys come out sorted.
+fn canonical_json<T: Serialize>(value: &T) -> VcpResult<String> {
+    let value = serde_json::to_value(value)?;
+    Ok(serde_json::to_string(&value)?)
+}
+
+// ── Tests ────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composer::{Composer, CompositionMode, Constitution};
+    use crate::transport::compute_content_hash;
+
+    fn sample_request() -> AdaptationRequest {
+        AdaptationRequest::new("req-42", "family.safe.guide@1.2.0")
+            .with_context("\u{23F0}\u{1F305}|\u{1F4CD}\u{1F3E1}")
+            .with_capability("personal", true)
+            .with_capability("streaming", false)
+    }
+
+    #[test]
+    fn request_roundtrip() {
+        let request = sample_request();
+        request.validate().unwrap();
+
+        let json = request.to_canonical_json().unwrap();
+        let parsed = AdaptationRequest::from_json(&json).unwrap();
+        assert_eq!(parsed, request);
+        assert!(parsed.supports("personal"));
+        assert!(!parsed.supports("streaming"));
+        assert!(!parsed.supports("unknown"));
+    }
+
+    #[test]
+    fn request_canonical_json_sorts_keys() {
+        let json = AdaptationRequest::new("r", "family.safe.guide")
+            .to_canonical_json()
+            .unwrap();
+        assert_eq!(
+            json,
+            r#"{"capabilities":{},"request_id":"r","token":"family.safe.guide","version":"1.0.0"}"#
+        );
+    }
+
+    #[test]
+    fn request_parses_token_and_context() {
+        let request = sample_request();
+        assert_eq!(request.parsed_token().unwrap().domain(), "family");
+        let ctx = request.parsed_context().unwrap().unwrap();
+        assert!(ctx.situational.has_any());
+    }
+
+    #[test]
+    fn request_rejects_bad_token() {
+        let request = AdaptationRequest::new("r", "not-a-token");
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn request_rejects_bad_envelope() {
+        let mut request = sample_request();
+        request.version = "1.0".into();
+        assert!(request.validate().is_err());
+
+        let mut request = sample_request();
+        request.request_id = String::new();
+        assert!(request.validate().is_err());
+
+        let request = sample_request().with_capability("", true);
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn request_from_json_rejects_missing_token() {
+        let err = AdaptationRequest::from_json(r#"{"version":"1.0.0","request_id":"r"}"#);
+        assert!(matches!(err, Err(VcpError::JsonError(_))));
+    }
+
+    #[test]
+    fn response_ok_roundtrip_with_composition() {
+        let composition = Composer::new()
+            .compose(
+                &[
+                    Constitution::new("base", vec!["Always be honest.".into()], 0),
+                    Constitution::new("ext", vec!["Respect privacy.".into()], 1),
+                ],
+                CompositionMode::Extend,
+            )
+            .unwrap();
+        let hash = compute_content_hash("Always be honest.").unwrap();
+
+        let response = AdaptationResponse::ok(
+            "req-42",
+            SelectedConstitution::new("family.safe.guide", "1.2.0").with_content_hash(hash),
+        )
+        .with_composition(composition)
+        .with_directive(Directive::new("tone", "gentle").with_reason("company=children"));
+
+        response.validate().unwrap();
+        let json = response.to_canonical_json().unwrap();
+        assert!(json.contains(r#""mode_used":"extend""#));
+        assert!(json.contains(r#""status":"ok""#));
+
+        let parsed = AdaptationResponse::from_json(&json).unwrap();
+        assert_eq!(parsed, response);
+        assert!(parsed.is_ok());
+    }
+
+    #[test]
+    fn response_ok_requires_constitution() {
+        let mut response =
+            AdaptationResponse::ok("r", SelectedConstitution::new("family.safe.guide", "1.0.0"));
+        response.constitution = None;
+        assert!(response.validate().is_err());
+    }
+
+    #[test]
+    fn response_rejects_bad_hash() {
+        let response = AdaptationResponse::ok(
+            "r",
+            SelectedConstitution::new("family.safe.guide", "1.0.0").with_content_hash("sha256:xyz"),
+        );
+        assert!(response.validate().is_err());
+    }
+
+    #[test]
+    fn response_failure_requires_message() {
+        let response = AdaptationResponse::rejected("r", "unknown token");
+        response.validate().unwrap();
+        assert!(!response.is_ok());
+        assert_eq!(response.status, AdaptationStatus::Rejected);
+
+        let response = AdaptationResponse::error("r", "");
+        assert!(response.validate().is_err());
+    }
+
+    #[test]
+    fn response_rejects_empty_directive_key() {
+        let response =
+            AdaptationResponse::ok("r", SelectedConstitution::new("family.safe.guide", "1.0.0"))
+                .with_directive(Directive::new("", "x"));
+        assert!(response.validate().is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Token count estimation for manifest budgets.
+//!
+//! A manifest's `budget.token_count` is declared by its issuer. A
+//! [`BudgetEstimator`] lets the orchestrator count the content itself and
+//! reject bundles whose declared count is off by more than a tolerance,
+//! so an understated count cannot slip a large constitution past the
+//! context-share limit.
+//!
+//! Tokenizers plug in through the [`Tokenizer`] trait. The built-in ones
+//! are heuristics that need no vocabulary files:
+//!
+//! | Tokenizer | Estimate |
+//! |-----------|----------|
+//! | [`CharTokenizer`] | One token per 4 characters |
+//! | [`WordTokenizer`] | Four tokens per 3 words |
+//! | [`HeuristicTokenizer`] | The larger of the two (default) |
+//!
+//! An exact BPE tokenizer such as `tiktoken-rs` can be registered under the
+//! name manifests use (e.g. `cl100k_base`) by implementing [`Tokenizer`]
+//! for a thin wrapper.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::budget::{BudgetEstimator, WordTokenizer};
+//!
+//! let estimator = BudgetEstimator::default()
+//!     .with_tokenizer("words", WordTokenizer)
+//!     .with_tolerance(0.2);
+//!
+//! let content = "Be kind. Be honest. Be brief.";
+//! assert_eq!(estimator.estimate(Some("words"), content), 8);
+//! assert!(estimator.check(8, Some("words"), content).is_ok());
+//! assert!(estimator.check(2, Some("words"), content).is_err());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+/// Tolerance used by [`BudgetEstimator::default`]: heuristics are rough,
+/// so a declared count may differ from the estimate by half.
+pub const DEFAULT_TOLERANCE: f64 = 0.5;
+
+/// Counts the tokens in a piece of text.
+pub trait Tokenizer: Send + Sync {
+    /// Short name used in reports (e.g. `"heuristic"`).
+    fn name(&self) -> &str;
+
+    /// Number of tokens `text` encodes to.
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+impl<T: Tokenizer + ?Sized> Tokenizer for Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        (**self).count_tokens(text)
+    }
+}
+
+impl<T: Tokenizer + ?Sized> Tokenizer for Box<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        (**self).count_tokens(text)
+    }
+}
+
+// ── Heuristics ───────────────────────────────────────────────
+
+/// Estimates one token per four characters, the usual rule of thumb for
+/// English under BPE vocabularies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CharTokenizer;
+
+impl Tokenizer for CharTokenizer {
+    fn name(&self) -> &'static str {
+        "chars"
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+}
+
+/// Estimates four tokens per three whitespace-separated words.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WordTokenizer;
+
+impl Tokenizer for WordTokenizer {
+    fn name(&self) -> &'static str {
+        "words"
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        (text.split_whitespace().count() * 4).div_ceil(3)
+    }
+}
+
+/// The larger of the [`CharTokenizer`] and [`WordTokenizer`] estimates.
+///
+/// Character counts undershoot on short-word text and word counts
+/// undershoot on long unbroken strings; taking the maximum keeps the
+/// estimate conservative for both.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicTokenizer;
+
+impl Tokenizer for HeuristicTokenizer {
+    fn name(&self) -> &'static str {
+        "heuristic"
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        CharTokenizer
+            .count_tokens(text)
+            .max(WordTokenizer.count_tokens(text))
+    }
+}
+
+// ── Estimator ────────────────────────────────────────────────
+
+/// A declared token count outside the tolerance of the estimate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetMismatch {
+    pub declared: u64,
+    pub estimated: u64,
+    /// [`Tokenizer::name`] of the tokenizer that produced `estimated`.
+    pub tokenizer: String,
+    pub tolerance: f64,
+}
+
+impl fmt::Display for BudgetMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "declared token_count {} is not within {:.0}% of the {} estimate {}",
+            self.declared,
+            self.tolerance * 100.0,
+            self.tokenizer,
+            self.estimated
+        )
+    }
+}
+
+impl std::error::Error for BudgetMismatch {}
+
+/// Picks a tokenizer by the manifest's `budget.tokenizer` name and
+/// compares its count with the declared one.
+#[derive(Clone)]
+pub struct BudgetEstimator {
+    fallback: Arc<dyn Tokenizer>,
+    by_name: HashMap<String, Arc<dyn Tokenizer>>,
+    tolerance: f64,
+}
+
+impl fmt::Debug for BudgetEstimator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&str> = self.by_name.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        f.debug_struct("BudgetEstimator")
+            .field("fallback", &self.fallback.name())
+            .field("by_name", &names)
+            .field("tolerance", &self.tolerance)
+            .finish()
+    }
+}
+
+impl Default for BudgetEstimator {
+    /// [`HeuristicTokenizer`] for every name, with [`DEFAULT_TOLERANCE`].
+    fn default() -> Self {
+        Self::new(HeuristicTokenizer)
+    }
+}
+
+impl BudgetEstimator {
+    /// Use `fallback` for any tokenizer name without a registered one.
+    pub fn new(fallback: impl Tokenizer + 'static) -> Self {
+        Self {
+            fallback: Arc::new(fallback),
+            by_name: HashMap::new(),
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Count with `tokenizer` when a manifest declares `name`.
+    #[must_use]
+    pub fn with_tokenizer(mut self, name: &str, tokenizer: impl Tokenizer + 'static) -> Self {
+        self.by_name.insert(name.to_string(), Arc::new(tokenizer));
+        self
+    }
+
+    /// Accept declared counts within `tolerance` (a fraction of the
+    /// estimate) either side. Negative values are treated as zero.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance.max(0.0);
+        self
+    }
+
+    /// The configured tolerance.
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    fn tokenizer(&self, name: Option<&str>) -> &dyn Tokenizer {
+        name.and_then(|n| self.by_name.get(n))
+            .unwrap_or(&self.fallback)
+            .as_ref()
+    }
+
+    /// Estimate the tokens in `content` with the tokenizer for `name`.
+    pub fn estimate(&self, name: Option<&str>, content: &str) -> u64 {
+        u64::try_from(self.tokenizer(name).count_tokens(content)).unwrap_or(u64::MAX)
+    }
+
+    /// Check a declared count against the estimate, returning the estimate.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BudgetMismatch`] when `declared` is outside
+    /// `estimate * (1 ± tolerance)`.
+    pub fn check(
+        &self,
+        declared: u64,
+        name: Option<&str>,
+        content: &str,
+    ) -> Result<u64, BudgetMismatch> {
+        let tokenizer = self.tokenizer(name);
+        let estimated = u64::try_from(tokenizer.count_tokens(content)).unwrap_or(u64::MAX);
+        #[allow(clippy::cast_precision_loss)]
+        let (declared_f, estimated_f) = (declared as f64, estimated as f64);
+        if (declared_f - estimated_f).abs() <= estimated_f * self.tolerance {
+            Ok(estimated)
+        } else {
+            Err(BudgetMismatch {
+                declared,
+                estimated,
+                tokenizer: tokenizer.name().to_string(),
+                tolerance: self.tolerance,
+            })
+        }
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heuristics_count() {
+        assert_eq!(CharTokenizer.count_tokens(""), 0);
+        assert_eq!(CharTokenizer.count_tokens("abcde"), 2);
+        assert_eq!(WordTokenizer.count_tokens("one two three"), 4);
+        // Many short words: the word estimate wins.
+        assert_eq!(HeuristicTokenizer.count_tokens("a b c d e f"), 8);
+        // One long word: the character estimate wins.
+        assert_eq!(HeuristicTokenizer.count_tokens(&"x".repeat(40)), 10);
+    }
+
+    #[test]
+    fn registered_tokenizer_selected_by_name() {
+        struct Fixed;
+        impl Tokenizer for Fixed {
+            fn name(&self) -> &'static str {
+                "fixed"
+            }
+            fn count_tokens(&self, _: &str) -> usize {
+                100
+            }
+        }
+
+        let estimator = BudgetEstimator::default().with_tokenizer("cl100k_base", Fixed);
+        assert_eq!(estimator.estimate(Some("cl100k_base"), "hi"), 100);
+        assert_eq!(estimator.estimate(Some("o200k_base"), "hi"), 2);
+        assert_eq!(estimator.estimate(None, "hi"), 2);
+    }
+
+    #[test]
+    fn check_applies_tolerance_both_ways() {
+        let estimator = BudgetEstimator::new(CharTokenizer).with_tolerance(0.25);
+        let content = "x".repeat(400); // 100 tokens
+        assert_eq!(estimator.check(100, None, &content), Ok(100));
+        assert!(estimator.check(75, None, &content).is_ok());
+        assert!(estimator.check(125, None, &content).is_ok());
+
+        let low = estimator.check(74, None, &content).unwrap_err();
+        assert_eq!(low.estimated, 100);
+        assert_eq!(low.tokenizer, "chars");
+        assert_eq!(
+            low.to_string(),
+            "declared token_count 74 is not within 25% of the chars estimate 100"
+        );
+        assert!(estimator.check(126, None, &content).is_err());
+    }
+}
@@ -0,0 +1,737 @@
+//! Local store of hash-checked bundles.
+//!
+//! [`BundleStore`] keeps bundles on disk, by default under `~/.cache/vcp/`,
+//! keyed by token and version, so an agent can reuse a constitution
+//! without downloading it again. A bundle is stored only once its content
+//! matches the manifest's `content_hash`, and the hash is checked again on
+//! every read: an entry altered on disk is removed and reported as
+//! [`VcpError::HashMismatch`], and one that can no longer be parsed is
+//! removed and reads as a miss.
+//!
+//! The store does not check signatures or trust: it has no
+//! [`TrustConfig`](crate::trust::TrustConfig). Run a bundle through
+//! [`Orchestrator::verify`](crate::orchestrator::Orchestrator::verify)
+//! before trusting what [`BundleStore::get`] returns.
+//!
+//! With a size limit, storing a bundle evicts the least recently used
+//! entries until the store fits.
+//! [`get_or_insert_with`](BundleStore::get_or_insert_with) refetches a
+//! stored bundle as its [`ResolutionPolicy`] directs, and in offline mode
+//! never calls its fetch function, so a miss is an error rather than a
+//! download.
+//!
+//! | Path | Holds |
+//! |------|-------|
+//! | `<root>/<token>/<version>/manifest.json` | The manifest, as stored |
+//! | `<root>/<token>/<version>/content` | The content, as stored |
+//! | `<root>/<token>/<version>/entry.json` | A [`CacheEntry`] |
+//!
+//! `<token>` is the canonical token, followed by `~<namespace>` for a
+//! namespaced one.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::cache::BundleStore;
+//! use vcp_core::identity::VcpToken;
+//! use vcp_core::transport::compute_content_hash;
+//!
+//! let dir = std::env::temp_dir().join(format!("vcp-cache-doc-{}", std::process::id()));
+//! let store = BundleStore::open(&dir)?.with_max_size(1 << 20);
+//!
+//! let content = "Be kind.";
+//! let manifest = format!(
+//!     r#"{{"bundle": {{"content_hash": "{}"}}}}"#,
+//!     compute_content_hash(content)?
+//! );
+//! let token = VcpToken::parse("family.safe.guide@1.2.0")?;
+//! store.put(&token, &manifest, content)?;
+//!
+//! let cached = store.get(&token)?.expect("just stored");
+//! assert_eq!(cached.content, content);
+//! # std::fs::remove_dir_all(&dir)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{default_clock, Clock};
+use crate::error::{VcpError, VcpResult};
+use crate::identity::{SemVer, VcpToken};
+use crate::resolution::{Plan, ResolutionPolicy};
+use crate::transport::{compute_content_hash, Manifest};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const CONTENT_FILE: &str = "content";
+const ENTRY_FILE: &str = "entry.json";
+
+// ── Entries ─────────────────────────────────────────────────
+
+/// Bookkeeping for one stored bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// The token, with the version the bundle is stored under.
+    pub token: VcpToken,
+    /// Bytes on disk for the manifest and content.
+    pub size: u64,
+    /// When the bundle was stored.
+    pub stored_at: DateTime<Utc>,
+    /// When the bundle was last stored or read. Eviction removes the
+    /// oldest first.
+    pub last_used: DateTime<Utc>,
+}
+
+/// A bundle read back from a [`BundleStore`], its hash checked again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedBundle {
+    /// The token, with the version the bundle is stored under.
+    pub token: VcpToken,
+    pub manifest_json: String,
+    pub content: String,
+    /// When the bundle was stored.
+    pub stored_at: DateTime<Utc>,
+}
+
+// ── Store ───────────────────────────────────────────────────
+
+/// An on-disk store of bundles whose content hash has been checked.
+///
+/// Signatures are not checked; see the [module docs](self).
+#[derive(Debug)]
+pub struct BundleStore {
+    root: PathBuf,
+    max_size: Option<u64>,
+    offline: bool,
+    policy: ResolutionPolicy,
+    clock: Arc<dyn Clock>,
+}
+
+impl BundleStore {
+    /// Open the store at `root`, creating the directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::IoError`] if the directory cannot be created.
+    pub fn open(root: impl Into<PathBuf>) -> VcpResult<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        Ok(Self {
+            root,
+            max_size: None,
+            offline: false,
+            policy: ResolutionPolicy::prefer_cache(),
+            clock: default_clock(),
+        })
+    }
+
+    /// Open the store in [`default_dir`](Self::default_dir).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::IoError`] if there is no default directory or
+    /// it cannot be created.
+    pub fn open_default() -> VcpResult<Self> {
+        let root = Self::default_dir().ok_or_else(|| {
+            VcpError::IoError("no cache directory: neither XDG_CACHE_HOME nor HOME is set".into())
+        })?;
+        Self::open(root)
+    }
+
+    /// `$XDG_CACHE_HOME/vcp`, or `~/.cache/vcp`.
+    pub fn default_dir() -> Option<PathBuf> {
+        let non_empty = |name| std::env::var_os(name).filter(|v| !v.is_empty());
+        non_empty("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".cache")))
+            .map(|cache| cache.join("vcp"))
+    }
+
+    /// Keep the manifests and content of all entries within `bytes`,
+    /// evicting the least recently used.
+    #[must_use]
+    pub fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Serve only what is already stored; see
+    /// [`get_or_insert_with`](Self::get_or_insert_with).
+    #[must_use]
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Decide when a stored bundle is refetched; by default
+    /// [`ResolutionPolicy::prefer_cache`], which never refetches.
+    #[must_use]
+    pub fn with_resolution_policy(mut self, policy: ResolutionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Stamp entries using `clock` rather than the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The directory the store lives in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Whether the store is in offline mode.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Store a bundle under `token`, replacing any entry for the same
+    /// version, then evict down to the size limit.
+    ///
+    /// Only the content hash is checked; the manifest's signature is not.
+    ///
+    /// The version is the token's, or else the manifest's
+    /// `bundle.version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::HashMismatch`] if `content` does not match the
+    /// manifest's `content_hash`, [`VcpError::ParseError`] if the manifest
+    /// is invalid, neither it nor the token has a version or the two
+    /// disagree, or if the bundle alone is over the size limit, and
+    /// [`VcpError::IoError`] if writing fails.
+    pub fn put(
+        &self,
+        token: &VcpToken,
+        manifest_json: &str,
+        content: &str,
+    ) -> VcpResult<CacheEntry> {
+        let manifest = Manifest::from_json(manifest_json)?;
+        let actual = compute_content_hash(content)?;
+        if actual != manifest.bundle.content_hash {
+            return Err(VcpError::HashMismatch {
+                expected: manifest.bundle.content_hash,
+                actual,
+            });
+        }
+
+        let manifest_version = manifest
+            .bundle
+            .version
+            .as_deref()
+            .map(SemVer::parse)
+            .transpose()?;
+        let version = match (token.version.clone(), manifest_version) {
+            (Some(ours), Some(theirs)) if ours != theirs => {
+                return Err(VcpError::ParseError(format!(
+                    "token version {ours} does not match manifest version {theirs}"
+                )));
+            }
+            (Some(version), _) | (None, Some(version)) => version,
+            (None, None) => {
+                return Err(VcpError::ParseError(format!(
+                    "cannot store {}: neither the token nor the manifest has a version",
+                    token.full()
+                )));
+            }
+        };
+
+        let size = (manifest_json.len() + content.len()) as u64;
+        if let Some(max) = self.max_size.filter(|&max| size > max) {
+            return Err(VcpError::ParseError(format!(
+                "bundle is {size} bytes; the store holds at most {max}"
+            )));
+        }
+
+        let token = token.with_version(version);
+        let dir = self.entry_dir(&token)?;
+        fs::create_dir_all(&dir)?;
+        write_atomic(&dir.join(MANIFEST_FILE), manifest_json.as_bytes())?;
+        write_atomic(&dir.join(CONTENT_FILE), content.as_bytes())?;
+        let now = self.clock.now();
+        let entry = CacheEntry {
+            token,
+            size,
+            stored_at: now,
+            last_used: now,
+        };
+        write_atomic(&dir.join(ENTRY_FILE), &serde_json::to_vec(&entry)?)?;
+
+        self.evict(&dir)?;
+        Ok(entry)
+    }
+
+    /// Read the bundle for `token`, checking its hash again. A token
+    /// without a version reads the highest version stored.
+    ///
+    /// An entry whose files are missing or unreadable (not UTF-8, or a
+    /// manifest or `entry.json` that does not parse) is dropped and reads
+    /// as `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::HashMismatch`] (after removing the entry) if
+    /// the stored content no longer matches its manifest, and
+    /// [`VcpError::IoError`] if the store cannot be read.
+    pub fn get(&self, token: &VcpToken) -> VcpResult<Option<CachedBundle>> {
+        let token = match &token.version {
+            Some(_) => token.clone(),
+            None => match self.latest_version(token)? {
+                Some(version) => token.with_version(version),
+                None => return Ok(None),
+            },
+        };
+        let dir = self.entry_dir(&token)?;
+        let read = |name| match fs::read_to_string(dir.join(name)) {
+            Ok(text) => Ok(Some(text)),
+            Err(e) if matches!(e.kind(), ErrorKind::NotFound | ErrorKind::InvalidData) => Ok(None),
+            Err(e) => Err(VcpError::from(e)),
+        };
+        let (Some(entry), Some(manifest_json), Some(content)) =
+            (read(ENTRY_FILE)?, read(MANIFEST_FILE)?, read(CONTENT_FILE)?)
+        else {
+            remove_entry_dir(&dir)?;
+            return Ok(None);
+        };
+        let (Ok(mut entry), Ok(manifest)) = (
+            serde_json::from_str::<CacheEntry>(&entry),
+            Manifest::from_json(&manifest_json),
+        ) else {
+            remove_entry_dir(&dir)?;
+            return Ok(None);
+        };
+
+        let expected = manifest.bundle.content_hash;
+        let actual = compute_content_hash(&content)?;
+        if actual != expected {
+            remove_entry_dir(&dir)?;
+            return Err(VcpError::HashMismatch { expected, actual });
+        }
+
+        entry.last_used = self.clock.now();
+        write_atomic(&dir.join(ENTRY_FILE), &serde_json::to_vec(&entry)?)?;
+        Ok(Some(CachedBundle {
+            token,
+            manifest_json,
+            content,
+            stored_at: entry.stored_at,
+        }))
+    }
+
+    /// Read the bundle for `token`, or call `fetch` for its
+    /// `(manifest_json, content)` and store that.
+    ///
+    /// Whether a stored bundle is served as is, refetched, or kept as the
+    /// fallback for a failed fetch is up to the store's
+    /// [`ResolutionPolicy`], measured from when the bundle was stored.
+    /// In offline mode `fetch` is never called and is treated as failing.
+    ///
+    /// # Errors
+    ///
+    /// When no usable bundle is stored: in offline mode a
+    /// [`VcpError::IoError`], otherwise the error from `fetch`. Also as
+    /// for [`get`](Self::get) and [`put`](Self::put).
+    pub fn get_or_insert_with(
+        &self,
+        token: &VcpToken,
+        fetch: impl FnOnce() -> VcpResult<(String, String)>,
+    ) -> VcpResult<CachedBundle> {
+        let cached = self.get(token)?;
+        let age = cached.as_ref().map(|c| {
+            (self.clock.now() - c.stored_at)
+                .to_std()
+                .unwrap_or_default()
+        });
+        let plan = self.policy.plan(age);
+        let fallback = match (plan, cached) {
+            (Plan::UseCached, Some(cached)) => return Ok(cached),
+            (Plan::Fetch { fallback: true }, cached) => cached,
+            _ => None,
+        };
+        if self.offline {
+            return fallback.ok_or_else(|| {
+                VcpError::IoError(format!(
+                    "{} is not usably cached and the store is offline",
+                    token.full()
+                ))
+            });
+        }
+        let (manifest_json, content) = match (fetch(), fallback) {
+            (Ok(fetched), _) => fetched,
+            (Err(_), Some(cached)) => return Ok(cached),
+            (Err(e), None) => return Err(e),
+        };
+        let entry = self.put(token, &manifest_json, &content)?;
+        Ok(CachedBundle {
+            token: entry.token,
+            manifest_json,
+            content,
+            stored_at: entry.stored_at,
+        })
+    }
+
+    /// Remove the entry for `token` (which must have a version). Returns
+    /// whether there was one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for a token without a version and
+    /// [`VcpError::IoError`] if removal fails.
+    pub fn remove(&self, token: &VcpToken) -> VcpResult<bool> {
+        if token.version.is_none() {
+            return Err(VcpError::ParseError(format!(
+                "cannot remove {}: no version",
+                token.full()
+            )));
+        }
+        let dir = self.entry_dir(token)?;
+        let existed = dir.is_dir();
+        remove_entry_dir(&dir)?;
+        Ok(existed)
+    }
+
+    /// Every entry in the store, least recently used first. Directories
+    /// without a readable `entry.json` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::IoError`] if the store cannot be listed.
+    pub fn entries(&self) -> VcpResult<Vec<CacheEntry>> {
+        Ok(self
+            .entry_dirs()?
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect())
+    }
+
+    /// Bytes used by every entry's manifest and content.
+    ///
+    /// # Errors
+    ///
+    /// As for [`entries`](Self::entries).
+    pub fn total_size(&self) -> VcpResult<u64> {
+        Ok(self.entries()?.iter().map(|e| e.size).sum())
+    }
+
+    /// Entries with their directories, least recently used first.
+    fn entry_dirs(&self) -> VcpResult<Vec<(PathBuf, CacheEntry)>> {
+        let mut found = Vec::new();
+        for token_dir in subdirs(&self.root)? {
+            for dir in subdirs(&token_dir)? {
+                let entry = fs::read(dir.join(ENTRY_FILE))
+                    .ok()
+                    .and_then(|bytes| serde_json::from_slice::<CacheEntry>(&bytes).ok());
+                if let Some(entry) = entry {
+                    found.push((dir, entry));
+                }
+            }
+        }
+        found.sort_by_key(|(_, entry)| entry.last_used);
+        Ok(found)
+    }
+
+    /// Remove least recently used entries, other than `keep`, until the
+    /// store is within its size limit.
+    fn evict(&self, keep: &Path) -> VcpResult<()> {
+        let Some(max) = self.max_size else {
+            return Ok(());
+        };
+        let entries = self.entry_dirs()?;
+        let mut total: u64 = entries.iter().map(|(_, e)| e.size).sum();
+        for (dir, entry) in entries {
+            if total <= max {
+                break;
+            }
+            if dir != keep {
+                remove_entry_dir(&dir)?;
+                total -= entry.size;
+            }
+        }
+        Ok(())
+    }
+
+    /// The highest version stored for `token`.
+    fn latest_version(&self, token: &VcpToken) -> VcpResult<Option<SemVer>> {
+        let token_dir = self.token_dir(token)?;
+        Ok(subdirs(&token_dir)?
+            .iter()
+            .filter_map(|dir| SemVer::parse(dir.file_name()?.to_str()?).ok())
+            .max_by_key(|v| (v.major, v.minor, v.patch)))
+    }
+
+    fn token_dir(&self, token: &VcpToken) -> VcpResult<PathBuf> {
+        // Re-parse so a hand-built token cannot name a path outside the root.
+        let canonical = VcpToken::parse(&token.canonical())?.canonical();
+        Ok(self.root.join(match &token.namespace {
+            Some(namespace) if namespace.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                format!("{canonical}~{namespace}")
+            }
+            Some(namespace) => {
+                return Err(VcpError::MalformedToken(format!(
+                    "invalid namespace: {namespace}"
+                )))
+            }
+            None => canonical,
+        }))
+    }
+
+    fn entry_dir(&self, token: &VcpToken) -> VcpResult<PathBuf> {
+        let version = token
+            .version
+            .as_ref()
+            .ok_or_else(|| VcpError::ParseError(format!("{} has no version", token.full())))?;
+        Ok(self.token_dir(token)?.join(version.to_string()))
+    }
+}
+
+/// Write via a temporary file, so a reader never sees half a file.
+fn write_atomic(path: &Path, data: &[u8]) -> VcpResult<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Remove an entry directory, and its token directory if now empty.
+fn remove_entry_dir(dir: &Path) -> VcpResult<()> {
+    match fs::remove_dir_all(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    if let Some(token_dir) = dir.parent() {
+        // Fails while other versions remain, which is fine.
+        let _ = fs::remove_dir(token_dir);
+    }
+    Ok(())
+}
+
+/// The subdirectories of `dir`; none if it does not exist.
+fn subdirs(dir: &Path) -> VcpResult<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut dirs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::cell::Cell;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("vcp-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn manifest(content: &str) -> String {
+        format!(
+            r#"{{"bundle": {{"content_hash": "{}"}}}}"#,
+            compute_content_hash(content).unwrap()
+        )
+    }
+
+    fn token(raw: &str) -> VcpToken {
+        VcpToken::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn put_then_get_round_trips_and_unversioned_reads_latest() {
+        let dir = scratch("round-trip");
+        let store = BundleStore::open(&dir).unwrap();
+        store
+            .put(&token("family.safe.guide@1.2.0"), &manifest("old"), "old")
+            .unwrap();
+        store
+            .put(&token("family.safe.guide@1.10.0"), &manifest("new"), "new")
+            .unwrap();
+
+        let cached = store
+            .get(&token("family.safe.guide@1.2.0"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.content, "old");
+        let latest = store.get(&token("family.safe.guide")).unwrap().unwrap();
+        assert_eq!(latest.content, "new");
+        assert_eq!(latest.token.full(), "family.safe.guide@1.10.0");
+        assert!(store.get(&token("family.safe.other")).unwrap().is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn put_rejects_mismatched_hash_and_missing_version() {
+        let dir = scratch("reject");
+        let store = BundleStore::open(&dir).unwrap();
+        let err = store
+            .put(&token("family.safe.guide@1.0.0"), &manifest("a"), "b")
+            .unwrap_err();
+        assert!(matches!(err, VcpError::HashMismatch { .. }));
+        let err = store
+            .put(&token("family.safe.guide"), &manifest("a"), "a")
+            .unwrap_err();
+        assert!(err.to_string().contains("version"));
+
+        // The manifest's version serves when the token has none.
+        let versioned = r#"{"bundle": {"version": "2.0.0", "content_hash": "HASH"}}"#
+            .replace("HASH", &compute_content_hash("a").unwrap());
+        let entry = store
+            .put(&token("family.safe.guide"), &versioned, "a")
+            .unwrap();
+        assert_eq!(entry.token.full(), "family.safe.guide@2.0.0");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn tampered_entry_is_removed_on_read() {
+        let dir = scratch("tamper");
+        let store = BundleStore::open(&dir).unwrap();
+        let key = token("family.safe.guide@1.0.0:ACME");
+        store.put(&key, &manifest("Be kind."), "Be kind.").unwrap();
+        let content = dir.join("family.safe.guide~ACME/1.0.0/content");
+        fs::write(&content, "Be unkind.").unwrap();
+
+        let err = store.get(&key).unwrap_err();
+        assert!(matches!(err, VcpError::HashMismatch { .. }));
+        assert!(store.get(&key).unwrap().is_none());
+        assert!(store.entries().unwrap().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_entry_reads_as_missing() {
+        let dir = scratch("corrupt");
+        let store = BundleStore::open(&dir).unwrap();
+        let key = token("family.safe.guide@1.0.0");
+
+        store.put(&key, &manifest("Be kind."), "Be kind.").unwrap();
+        let entry = dir.join("family.safe.guide/1.0.0/entry.json");
+        let bytes = fs::read(&entry).unwrap();
+        fs::write(&entry, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(store.get(&key).unwrap().is_none());
+        assert!(!entry.parent().unwrap().exists());
+
+        store.put(&key, &manifest("Be kind."), "Be kind.").unwrap();
+        fs::write(dir.join("family.safe.guide/1.0.0/manifest.json"), "{").unwrap();
+        assert!(store.get(&key).unwrap().is_none());
+
+        // Stored again, the bundle reads normally.
+        store.put(&key, &manifest("Be kind."), "Be kind.").unwrap();
+        assert_eq!(store.get(&key).unwrap().unwrap().content, "Be kind.");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn eviction_removes_least_recently_used() {
+        let dir = scratch("evict");
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let body = "x".repeat(100);
+        let size = (manifest(&body).len() + body.len()) as u64;
+        let store = BundleStore::open(&dir)
+            .unwrap()
+            .with_max_size(size * 2)
+            .with_clock(clock.clone());
+
+        for raw in ["a.b.c@1.0.0", "a.b.d@1.0.0"] {
+            store.put(&token(raw), &manifest(&body), &body).unwrap();
+            clock.advance(chrono::Duration::seconds(1));
+        }
+        // Reading the first makes the second the least recently used.
+        store.get(&token("a.b.c@1.0.0")).unwrap().unwrap();
+        clock.advance(chrono::Duration::seconds(1));
+        store
+            .put(&token("a.b.e@1.0.0"), &manifest(&body), &body)
+            .unwrap();
+
+        let kept: Vec<String> = store
+            .entries()
+            .unwrap()
+            .iter()
+            .map(|e| e.token.full())
+            .collect();
+        assert_eq!(kept, ["a.b.c@1.0.0", "a.b.e@1.0.0"]);
+        assert_eq!(store.total_size().unwrap(), size * 2);
+
+        let big = "x".repeat(usize::try_from(size * 2).unwrap());
+        assert!(store
+            .put(&token("a.b.f@1.0.0"), &manifest(&big), &big)
+            .is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn offline_store_never_fetches() {
+        let dir = scratch("offline");
+        let fetches = Cell::new(0);
+        let fetch = || {
+            fetches.set(fetches.get() + 1);
+            Ok((manifest("Be kind."), "Be kind.".to_string()))
+        };
+        let key = token("family.safe.guide@1.0.0");
+
+        let offline = BundleStore::open(&dir).unwrap().with_offline(true);
+        assert!(offline.get_or_insert_with(&key, fetch).is_err());
+        assert_eq!(fetches.get(), 0);
+
+        let online = BundleStore::open(&dir).unwrap();
+        online.get_or_insert_with(&key, fetch).unwrap();
+        online.get_or_insert_with(&key, fetch).unwrap();
+        assert_eq!(fetches.get(), 1);
+
+        let cached = offline.get_or_insert_with(&key, fetch).unwrap();
+        assert_eq!(cached.content, "Be kind.");
+        assert_eq!(fetches.get(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn stale_bundle_is_refetched_with_bounded_fallback() {
+        let dir = scratch("stale");
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let policy = ResolutionPolicy::require_fresh(std::time::Duration::from_hours(1))
+            .with_max_stale(std::time::Duration::from_hours(24));
+        let store = BundleStore::open(&dir)
+            .unwrap()
+            .with_resolution_policy(policy)
+            .with_clock(clock.clone());
+        let key = token("family.safe.guide@1.0.0");
+        let unreachable = || Err(VcpError::IoError("registry unreachable".into()));
+
+        store.put(&key, &manifest("Be kind."), "Be kind.").unwrap();
+        clock.advance(chrono::Duration::hours(2));
+        let fetched = store
+            .get_or_insert_with(&key, || {
+                Ok((manifest("Be kinder."), "Be kinder.".to_string()))
+            })
+            .unwrap();
+        assert_eq!(fetched.content, "Be kinder.");
+        assert_eq!(fetched.stored_at, clock.now());
+
+        clock.advance(chrono::Duration::hours(2));
+        let stale = store.get_or_insert_with(&key, unreachable).unwrap();
+        assert_eq!(stale.content, "Be kinder.");
+
+        clock.advance(chrono::Duration::days(1));
+        let err = store.get_or_insert_with(&key, unreachable).unwrap_err();
+        assert!(err.to_string().contains("registry unreachable"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,193 @@
+//! Machine-readable description of what this build supports.
+//!
+//! Peers exchange [`Capabilities`] (or the flattened
+//! [`Capabilities::flags`] inside a [`VcpHello`](crate::negotiation::VcpHello))
+//! to agree on algorithms and modes up front, rather than inferring
+//! them from a crate or protocol version number.
+//!
+//! | Field | Contents |
+//! |-------|----------|
+//! | `spec_versions` | Wire/spec name to the version this build reads and writes |
+//! | `hash_algorithms` | Content hash prefixes (`sha256`) |
+//! | `signature_algorithms` | Manifest signature schemes (`ed25519`) |
+//! | `hook_types` | [`HookType`] interception points |
+//! | `composition_modes` | [`CompositionMode`] values accepted by the composer |
+//! | `features` | Cargo features compiled into `vcp-core` |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::{capabilities, CompositionMode, HookType};
+//!
+//! let caps = capabilities();
+//! assert!(caps.hash_algorithms.contains(&"sha256".to_string()));
+//! assert!(caps.hook_types.contains(&HookType::OnConflict));
+//! assert!(caps.composition_modes.contains(&CompositionMode::Strict));
+//! assert!(caps.flags().contains_key("signature:ed25519"));
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::adaptation::protocol::PROTOCOL_VERSION;
+use crate::composer::CompositionMode;
+use crate::events::EVENT_VERSION;
+use crate::hooks::HookType;
+use crate::orchestrator::SNAPSHOT_VERSION;
+use crate::transport::ARCHIVE_VERSION;
+
+/// Cargo features that change what `vcp-core` can do at runtime.
+const KNOWN_FEATURES: [(&str, bool); 10] = [
+    ("http", cfg!(feature = "http")),
+    ("keystore", cfg!(feature = "keystore")),
+    ("mcp", cfg!(feature = "mcp")),
+    ("parallel", cfg!(feature = "parallel")),
+    ("proto", cfg!(feature = "proto")),
+    ("seal", cfg!(feature = "seal")),
+    ("tracing", cfg!(feature = "tracing")),
+    ("wasm-clock", cfg!(feature = "wasm-clock")),
+    ("wasm-hooks", cfg!(feature = "wasm-hooks")),
+    ("watch", cfg!(feature = "watch")),
+];
+
+/// Supported specs, algorithms and modes for this build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// `vcp-core` crate version.
+    pub sdk_version: String,
+    /// Spec or wire format name to the version implemented.
+    pub spec_versions: BTreeMap<String, String>,
+    /// Content hash algorithms, as they appear before `:` in a hash.
+    pub hash_algorithms: Vec<String>,
+    /// Manifest signature algorithms.
+    pub signature_algorithms: Vec<String>,
+    /// Hook interception points the executor dispatches.
+    pub hook_types: Vec<HookType>,
+    /// Constitution composition modes.
+    pub composition_modes: Vec<CompositionMode>,
+    /// Enabled cargo features.
+    pub features: Vec<String>,
+}
+
+impl Capabilities {
+    /// Whether the named cargo feature was compiled in.
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.iter().any(|f| f == name)
+    }
+
+    /// Flatten into `category:value` flags for [`VcpHello`](crate::negotiation::VcpHello)
+    /// capability maps, e.g. `hash:sha256` or `hook:on_conflict`.
+    ///
+    /// Spec versions are emitted as `spec:<name>@<version>`. Only supported
+    /// entries appear, since [`negotiate`](crate::negotiation::negotiate)
+    /// treats any listed key as available.
+    pub fn flags(&self) -> HashMap<String, bool> {
+        let mut flags = HashMap::new();
+        for (name, version) in &self.spec_versions {
+            flags.insert(format!("spec:{name}@{version}"), true);
+        }
+        for alg in &self.hash_algorithms {
+            flags.insert(format!("hash:{alg}"), true);
+        }
+        for alg in &self.signature_algorithms {
+            flags.insert(format!("signature:{alg}"), true);
+        }
+        for hook in &self.hook_types {
+            flags.insert(format!("hook:{hook}"), true);
+        }
+        for mode in &self.composition_modes {
+            flags.insert(format!("compose:{mode}"), true);
+        }
+        for name in &self.features {
+            flags.insert(format!("feature:{name}"), true);
+        }
+        flags
+    }
+}
+
+/// Describe the capabilities of this build.
+pub fn capabilities() -> Capabilities {
+    let spec_versions = [
+        ("vcp", "2.0.0".to_string()),
+        ("csm1", "1.1".to_string()),
+        ("context", "3.2".to_string()),
+        ("adaptation", PROTOCOL_VERSION.to_string()),
+        ("events", EVENT_VERSION.to_string()),
+        ("orchestrator_snapshot", SNAPSHOT_VERSION.to_string()),
+        ("bundle_archive", ARCHIVE_VERSION.to_string()),
+    ]
+    .into_iter()
+    .map(|(name, version)| (name.to_string(), version))
+    .collect();
+
+    Capabilities {
+        sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+        spec_versions,
+        hash_algorithms: vec!["sha256".to_string()],
+        signature_algorithms: vec!["ed25519".to_string()],
+        hook_types: HookType::ALL.to_vec(),
+        composition_modes: CompositionMode::ALL.to_vec(),
+        features: KNOWN_FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| (*name).to_string())
+            .collect(),
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::negotiation::{negotiate, VcpHello};
+
+    #[test]
+    fn lists_every_hook_and_mode() {
+        let caps = capabilities();
+        assert_eq!(caps.hook_types.len(), 6);
+        assert_eq!(caps.composition_modes.len(), 4);
+        assert_eq!(caps.sdk_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(caps.spec_versions["adaptation"], PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn features_track_cfg() {
+        let caps = capabilities();
+        assert_eq!(caps.has_feature("mcp"), cfg!(feature = "mcp"));
+        assert_eq!(caps.has_feature("proto"), cfg!(feature = "proto"));
+        assert_eq!(
+            caps.flags().contains_key("feature:proto"),
+            cfg!(feature = "proto")
+        );
+    }
+
+    #[test]
+    fn json_shape_is_stable() {
+        let json = serde_json::to_value(capabilities()).unwrap();
+        assert_eq!(json["hash_algorithms"], serde_json::json!(["sha256"]));
+        assert_eq!(json["hook_types"][0], "pre_inject");
+        assert_eq!(json["composition_modes"][3], "strict");
+        assert_eq!(json["spec_versions"]["csm1"], "1.1");
+
+        let back: Capabilities = serde_json::from_value(json).unwrap();
+        assert_eq!(back, capabilities());
+    }
+
+    #[test]
+    fn flags_feed_negotiation() {
+        let caps = capabilities();
+        let server: HashMap<String, String> = caps
+            .flags()
+            .into_iter()
+            .map(|(k, v)| (k, v.to_string()))
+            .collect();
+        let hello = VcpHello::v2_0()
+            .with_extension("hash:sha256", "*")
+            .with_extension("hash:blake3", "*");
+        let ack = negotiate(&hello, &server);
+        assert!(ack.accepted_extensions.contains_key("hash:sha256"));
+        assert!(ack.rejected_extensions.contains_key("hash:blake3"));
+    }
+}
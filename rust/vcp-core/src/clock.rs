@@ -0,0 +1,171 @@
+//! Time sources for expiry, validity and decay checks.
+//!
+//! Components that compare against the current time take a [`Clock`]
+//! rather than reading the system clock, so tests and simulations can pin
+//! or move time deterministically.
+//!
+//! | Clock | Time |
+//! |-------|------|
+//! | [`SystemClock`] | The host clock (default) |
+//! | [`FixedClock`] | A constant instant |
+//! | [`MockClock`] | Settable and advanceable, shared across clones of its `Arc` |
+//!
+//! `SystemTime::now()` and `Instant::now()` panic on
+//! `wasm32-unknown-unknown`, so nothing in the crate calls them directly:
+//! wall-clock reads go through a [`Clock`], and elapsed-time measurements
+//! (hook timings) through [`Stopwatch`]. With the `wasm-clock` feature,
+//! [`SystemClock`] reads the browser's `Date.now()`, which is enough for
+//! the full [`Orchestrator`](crate::orchestrator::Orchestrator), its
+//! replay cache and the temporal checks to run in a browser.
+//!
+//! # Examples
+//!
+//! ```
+//! use chrono::{Duration, TimeZone, Utc};
+//! use vcp_core::clock::{Clock, MockClock};
+//!
+//! let clock = MockClock::new(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap());
+//! clock.advance(Duration::days(30));
+//! assert_eq!(clock.now(), Utc.with_ymd_and_hms(2026, 1, 31, 0, 0, 0).unwrap());
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::Instant;
+use std::time::{Duration as StdDuration, SystemTime};
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Source of the current time.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// The current instant.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// The current instant as a [`SystemTime`].
+    fn system_time(&self) -> SystemTime {
+        self.now().into()
+    }
+}
+
+impl<T: Clock + ?Sized> Clock for Arc<T> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+impl<T: Clock + ?Sized> Clock for Box<T> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// The clock used when none is supplied.
+pub fn default_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+// ── System ───────────────────────────────────────────────────
+
+/// The host clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+// ── Fixed ────────────────────────────────────────────────────
+
+/// A clock stopped at one instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+// ── Mock ─────────────────────────────────────────────────────
+
+/// A clock tests move by hand.
+///
+/// Share it as an `Arc<MockClock>` between the component under test and
+/// the test body; [`set`](Self::set) and [`advance`](Self::advance) are
+/// seen by every holder.
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Start at `now`.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Jump to `now`, forwards or backwards.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) = now;
+    }
+
+    /// Move forwards by `by` (backwards if negative).
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(PoisonError::into_inner);
+        *now += by;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// ── Stopwatch ────────────────────────────────────────────────
+
+/// Measures elapsed time for metrics and tracing.
+///
+/// Monotonic (`Instant`) on native targets. On `wasm32-unknown-unknown`,
+/// where `Instant` is unavailable, it reads [`SystemClock`] instead, so
+/// it has the browser clock's millisecond resolution there.
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    start: Instant,
+}
+
+impl Stopwatch {
+    /// Start timing now.
+    pub fn start() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+
+    /// Time since [`start`](Self::start).
+    pub fn elapsed(&self) -> StdDuration {
+        self.start.elapsed()
+    }
+}
+
+/// Stand-in for `Instant` backed by the wall clock.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Debug, Clone, Copy)]
+struct Instant(DateTime<Utc>);
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl Instant {
+    fn now() -> Self {
+        Self(SystemClock.now())
+    }
+
+    /// Zero if the clock went backwards.
+    fn elapsed(self) -> StdDuration {
+        (SystemClock.now() - self.0).to_std().unwrap_or_default()
+    }
+}
@@ -12,6 +12,52 @@
 //! | [`CompositionMode::Override`] | Later constitutions replace conflicting earlier rules |
 //! | [`CompositionMode::Strict`] | No conflicts or duplicates allowed |
 //!
+//! # Conflict hooks
+//!
+//! [`Composer::with_conflict_hooks`] routes every detected conflict through
+//! the `on_conflict` hook chain first. A hook can settle it by returning a
+//! [`ConflictResolution`] through `HookAction::Modify`, or escalate it by
+//! aborting, which fails the composition in any mode. A `Modify` value
+//! that is not a valid resolution escalates the same way.
+//!
+//! # Scoped composition
+//!
+//! Constitutions, or single rules, can be tagged with CSM-1 [`Scope`]s.
+//! [`Composer::compose_for_scope`] merges only the rules relevant to the
+//! active scope, so unrelated domains neither bloat the prompt nor
+//! conflict with each other. Untagged rules, and rules tagged
+//! [`Scope::General`], apply in every scope.
+//!
+//! # Conditional rules
+//!
+//! A rule can carry a [`Condition`] on the context, attached with
+//! [`Constitution::with_rule_condition`]. Composition ignores conditions;
+//! they ride along on each [`MergedRule`], and
+//! [`CompositionResult::filter_for_context`] keeps only the rules active
+//! in a given [`FullContext`] before rendering.
+//!
+//! # Explaining conflicts
+//!
+//! [`Conflict::explain`] shows why two rules were judged to conflict: the
+//! opposing keywords and the shared topic words, with character offsets
+//! into each rule. [`CompositionError::report`] collects these for every
+//! conflict and renders them as Markdown or JSON.
+//!
+//! # Markdown constitutions
+//!
+//! [`Constitution::from_markdown`] turns a Markdown document into rules:
+//!
+//! | Markdown | Becomes |
+//! |----------|---------|
+//! | `-`, `*`, `+`, `1.`, `1)` list items, nested or not | One rule each |
+//! | Unmarked lines directly under a list item | Part of that rule |
+//! | `#` headings | The section of the rules below them, e.g. `Safety > Privacy` |
+//! | Prose paragraphs | Ignored, unless the document has no list items; then one rule each |
+//! | Code fences, block quotes, tables, HTML comments, front matter, rules (`---`) | Ignored |
+//!
+//! Sections are kept in [`Constitution::sections`]; a merged rule's
+//! `source_id` and `original_index` lead back to its section.
+//!
 //! # Examples
 //!
 //! ```
@@ -23,15 +69,27 @@
 //! let composer = Composer::new();
 //! let result = composer.compose(&[c1, c2], CompositionMode::Extend).unwrap();
 //! assert_eq!(result.merged_rules.len(), 2);
+//! assert_eq!(result.merged_rules[1].source_id, "ext");
+//! assert_eq!(result.rule_texts(), ["Always be honest.", "Respect privacy."]);
 //! ```
 
 use std::collections::{HashMap, HashSet};
-use std::fmt;
+use std::fmt::{self, Write as _};
+
+use serde::{Deserialize, Serialize};
+
+use crate::condition::Condition;
+use crate::context::FullContext;
+use crate::csm1::Scope;
+use crate::diff::strip_list_marker;
+use crate::error::VcpResult;
+use crate::hooks::{ChainState, HookAction, HookExecutor, HookInput, HookType};
 
 // ── Composition mode ─────────────────────────────────────────
 
 /// Composition modes for multi-constitution scenarios.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum CompositionMode {
     /// First constitution is immutable base; later ones can only add
     /// non-conflicting rules.
@@ -44,6 +102,16 @@ pub enum CompositionMode {
     Strict,
 }
 
+impl CompositionMode {
+    /// Every composition mode, in declaration order.
+    pub const ALL: [CompositionMode; 4] = [
+        CompositionMode::Base,
+        CompositionMode::Extend,
+        CompositionMode::Override,
+        CompositionMode::Strict,
+    ];
+}
+
 impl fmt::Display for CompositionMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -58,7 +126,7 @@ impl fmt::Display for CompositionMode {
 // ── Conflict ─────────────────────────────────────────────────
 
 /// A detected conflict between two constitution rules.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Conflict {
     /// The new rule that triggered the conflict.
     pub rule_a: String,
@@ -84,13 +152,304 @@ impl fmt::Display for Conflict {
     }
 }
 
+// ── Conflict explanations ────────────────────────────────────
+
+/// A highlighted part of a rule, by character (not byte) offset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Highlight {
+    /// The highlighted text as it appears in the rule.
+    pub text: String,
+    /// Offset of the first character.
+    pub start: usize,
+    /// Offset one past the last character.
+    pub end: usize,
+}
+
+/// What in one rule of a conflict triggered the heuristics.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleEvidence {
+    /// The conflict keyword found in this rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<Highlight>,
+    /// Occurrences of the shared topic words.
+    pub topic: Vec<Highlight>,
+}
+
+/// Why two rules were judged to conflict, from [`Conflict::explain`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictExplanation {
+    /// Significant words both rules contain, sorted.
+    pub topic_words: Vec<String>,
+    /// Evidence in the incoming rule (`rule_a`).
+    pub rule_a: RuleEvidence,
+    /// Evidence in the existing rule (`rule_b`).
+    pub rule_b: RuleEvidence,
+}
+
+impl ConflictExplanation {
+    /// The opposing keywords, lowercase, if the conflict came from a
+    /// keyword pair (duplicates have none).
+    #[must_use]
+    pub fn keyword_pair(&self) -> Option<(String, String)> {
+        let a = self.rule_a.keyword.as_ref()?;
+        let b = self.rule_b.keyword.as_ref()?;
+        Some((a.text.to_lowercase(), b.text.to_lowercase()))
+    }
+}
+
+impl Conflict {
+    /// Explain which keywords and topic words made the rules conflict.
+    ///
+    /// Uses the same keyword table and topic heuristic as
+    /// [`Composer::rules_conflict`].
+    #[must_use]
+    pub fn explain(&self) -> ConflictExplanation {
+        let (a_lower, b_lower) = (self.rule_a.to_lowercase(), self.rule_b.to_lowercase());
+        let shared: HashSet<&str> = &topic_words(&a_lower) & &topic_words(&b_lower);
+        let mut topic_words: Vec<String> = shared.iter().map(|w| (*w).to_string()).collect();
+        topic_words.sort();
+
+        let mut rule_a = RuleEvidence {
+            keyword: None,
+            topic: topic_highlights(&self.rule_a, &shared),
+        };
+        let mut rule_b = RuleEvidence {
+            keyword: None,
+            topic: topic_highlights(&self.rule_b, &shared),
+        };
+        'pairs: for (keyword, opposites) in CONFLICT_KEYWORDS {
+            let Some(found_a) = find_keyword(&self.rule_a, keyword) else {
+                continue;
+            };
+            for opposite in *opposites {
+                if let Some(found_b) = find_keyword(&self.rule_b, opposite) {
+                    rule_a.keyword = Some(found_a);
+                    rule_b.keyword = Some(found_b);
+                    break 'pairs;
+                }
+            }
+        }
+
+        ConflictExplanation {
+            topic_words,
+            rule_a,
+            rule_b,
+        }
+    }
+}
+
+/// The first case-insensitive occurrence of an ASCII `keyword` in `rule`.
+fn find_keyword(rule: &str, keyword: &str) -> Option<Highlight> {
+    // ASCII lowercasing keeps byte offsets aligned with `rule`.
+    let start = rule.to_ascii_lowercase().find(keyword)?;
+    Some(highlight(rule, start, start + keyword.len()))
+}
+
+/// Whitespace-separated words of `rule` that are in `shared` once
+/// lowercased, as [`topic_words`] compares them.
+fn topic_highlights(rule: &str, shared: &HashSet<&str>) -> Vec<Highlight> {
+    rule.split_whitespace()
+        .filter(|word| shared.contains(word.to_lowercase().as_str()))
+        .map(|word| {
+            let start = word.as_ptr() as usize - rule.as_ptr() as usize;
+            highlight(rule, start, start + word.len())
+        })
+        .collect()
+}
+
+fn highlight(rule: &str, start: usize, end: usize) -> Highlight {
+    Highlight {
+        text: rule[start..end].to_string(),
+        start: rule[..start].chars().count(),
+        end: rule[..end].chars().count(),
+    }
+}
+
+/// `rule` with the evidence in bold.
+fn emphasize(rule: &str, evidence: &RuleEvidence) -> String {
+    let mut marks: Vec<(usize, usize)> = evidence
+        .topic
+        .iter()
+        .chain(&evidence.keyword)
+        .map(|h| (h.start, h.end))
+        .collect();
+    marks.sort_unstable();
+    let chars: Vec<char> = rule.chars().collect();
+    let mut out = String::new();
+    let mut cursor = 0;
+    for (start, end) in marks {
+        // Skip highlights overlapping one already written.
+        if start < cursor {
+            continue;
+        }
+        out.extend(&chars[cursor..start]);
+        out.push_str("**");
+        out.extend(&chars[start..end]);
+        out.push_str("**");
+        cursor = end;
+    }
+    out.extend(&chars[cursor..]);
+    out
+}
+
+// ── Merged rule ──────────────────────────────────────────────
+
+/// Where a rule came from: its text, constitution and position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleOrigin {
+    /// The rule text.
+    pub text: String,
+    /// ID of the constitution that contributed the rule.
+    pub source_id: String,
+    /// Index of the rule within that constitution's `rules`.
+    pub original_index: usize,
+}
+
+/// A rule in a [`CompositionResult`], with its provenance.
+///
+/// Compares equal to a `&str` holding the same text, so code that only
+/// cares about rule text can keep treating `merged_rules` as strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "MergedRuleRepr")]
+pub struct MergedRule {
+    /// The rule text.
+    pub text: String,
+    /// ID of the constitution that contributed the rule.
+    pub source_id: String,
+    /// Index of the rule within that constitution's `rules`.
+    pub original_index: usize,
+    /// Earlier rules this one replaced ([`CompositionMode::Override`] only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overridden: Option<Vec<RuleOrigin>>,
+    /// When the rule is active; `None` for always.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Condition>,
+}
+
+impl MergedRule {
+    pub(crate) fn new(text: &str, source_id: &str, original_index: usize) -> Self {
+        Self {
+            text: text.to_string(),
+            source_id: source_id.to_string(),
+            original_index,
+            overridden: None,
+            condition: None,
+        }
+    }
+
+    /// Rule `index` of `constitution`, with its condition.
+    pub(crate) fn of(constitution: &Constitution, index: usize) -> Self {
+        Self {
+            condition: constitution.condition(index).cloned(),
+            ..Self::new(&constitution.rules[index], &constitution.id, index)
+        }
+    }
+
+    /// Whether the rule is active in `context`.
+    #[must_use]
+    pub fn applies_in(&self, context: &FullContext) -> bool {
+        self.condition
+            .as_ref()
+            .is_none_or(|condition| condition.evaluate(context))
+    }
+
+    /// The rule text.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// This rule's origin, without override information.
+    #[must_use]
+    pub fn origin(&self) -> RuleOrigin {
+        RuleOrigin {
+            text: self.text.clone(),
+            source_id: self.source_id.clone(),
+            original_index: self.original_index,
+        }
+    }
+}
+
+impl fmt::Display for MergedRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl AsRef<str> for MergedRule {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl PartialEq<str> for MergedRule {
+    fn eq(&self, other: &str) -> bool {
+        self.text == other
+    }
+}
+
+impl PartialEq<&str> for MergedRule {
+    fn eq(&self, other: &&str) -> bool {
+        self.text == *other
+    }
+}
+
+impl PartialEq<String> for MergedRule {
+    fn eq(&self, other: &String) -> bool {
+        &self.text == other
+    }
+}
+
+/// Results serialized before provenance tracking carry plain strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MergedRuleRepr {
+    Text(String),
+    Full {
+        text: String,
+        source_id: String,
+        original_index: usize,
+        #[serde(default)]
+        overridden: Option<Vec<RuleOrigin>>,
+        #[serde(default)]
+        condition: Option<Condition>,
+    },
+}
+
+impl From<MergedRuleRepr> for MergedRule {
+    fn from(repr: MergedRuleRepr) -> Self {
+        match repr {
+            MergedRuleRepr::Text(text) => Self {
+                text,
+                source_id: String::new(),
+                original_index: 0,
+                overridden: None,
+                condition: None,
+            },
+            MergedRuleRepr::Full {
+                text,
+                source_id,
+                original_index,
+                overridden,
+                condition,
+            } => Self {
+                text,
+                source_id,
+                original_index,
+                overridden,
+                condition,
+            },
+        }
+    }
+}
+
 // ── Composition result ───────────────────────────────────────
 
 /// Result of composing multiple constitutions.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CompositionResult {
-    /// The merged set of rules after composition.
-    pub merged_rules: Vec<String>,
+    /// The merged set of rules after composition, with provenance.
+    pub merged_rules: Vec<MergedRule>,
     /// Conflicts that were detected (and possibly resolved).
     pub conflicts: Vec<Conflict>,
     /// Non-fatal warnings generated during composition.
@@ -99,6 +458,43 @@ pub struct CompositionResult {
     pub mode_used: CompositionMode,
 }
 
+impl CompositionResult {
+    /// The merged rule texts, in order.
+    #[must_use]
+    pub fn rule_texts(&self) -> Vec<&str> {
+        self.merged_rules.iter().map(MergedRule::as_str).collect()
+    }
+
+    /// Consume the result, keeping only the merged rule texts.
+    #[must_use]
+    pub fn into_rule_texts(self) -> Vec<String> {
+        self.merged_rules.into_iter().map(|r| r.text).collect()
+    }
+
+    /// The result with only the rules active in `context`.
+    ///
+    /// Run before rendering the merged rules into a prompt.
+    #[must_use]
+    pub fn filter_for_context(&self, context: &FullContext) -> Self {
+        Self {
+            merged_rules: self
+                .merged_rules
+                .iter()
+                .filter(|rule| rule.applies_in(context))
+                .cloned()
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Merged rules contributed by the constitution `source_id`.
+    pub fn rules_from<'a>(&'a self, source_id: &'a str) -> impl Iterator<Item = &'a MergedRule> {
+        self.merged_rules
+            .iter()
+            .filter(move |r| r.source_id == source_id)
+    }
+}
+
 // ── Constitution ─────────────────────────────────────────────
 
 /// A minimal constitution representation for composition.
@@ -110,6 +506,16 @@ pub struct Constitution {
     pub rules: Vec<String>,
     /// Priority level. Higher values take precedence.
     pub priority: i32,
+    /// The heading path each rule appeared under, parallel to `rules`.
+    /// Empty for constitutions not built from Markdown.
+    pub sections: Vec<Option<String>>,
+    /// Scopes the whole constitution applies to; empty for all scopes.
+    pub scopes: Vec<Scope>,
+    /// Per-rule scopes, parallel to `rules`, overriding `scopes` where
+    /// not empty.
+    pub rule_scopes: Vec<Vec<Scope>>,
+    /// Per-rule applicability conditions, parallel to `rules`.
+    pub conditions: Vec<Option<Condition>>,
 }
 
 impl Constitution {
@@ -129,10 +535,168 @@ impl Constitution {
             id,
             rules,
             priority,
+            sections: Vec::new(),
+            scopes: Vec::new(),
+            rule_scopes: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Restrict the whole constitution to `scopes`.
+    #[must_use]
+    pub fn with_scopes(mut self, scopes: impl IntoIterator<Item = Scope>) -> Self {
+        self.scopes = scopes.into_iter().collect();
+        self
+    }
+
+    /// Restrict rule `index` to `scopes`, regardless of the constitution's
+    /// own scopes.
+    #[must_use]
+    pub fn with_rule_scopes(
+        mut self,
+        index: usize,
+        scopes: impl IntoIterator<Item = Scope>,
+    ) -> Self {
+        if self.rule_scopes.len() <= index {
+            self.rule_scopes.resize(index + 1, Vec::new());
+        }
+        self.rule_scopes[index] = scopes.into_iter().collect();
+        self
+    }
+
+    /// Make rule `index` active only when `condition` holds.
+    #[must_use]
+    pub fn with_rule_condition(mut self, index: usize, condition: Condition) -> Self {
+        if self.conditions.len() <= index {
+            self.conditions.resize(index + 1, None);
         }
+        self.conditions[index] = Some(condition);
+        self
+    }
+
+    /// The condition on rule `index`, if any.
+    #[must_use]
+    pub fn condition(&self, index: usize) -> Option<&Condition> {
+        self.conditions.get(index)?.as_ref()
+    }
+
+    /// Whether rule `index` applies in `scope`.
+    #[must_use]
+    pub fn applies_to(&self, index: usize, scope: Scope) -> bool {
+        let scopes = match self.rule_scopes.get(index) {
+            Some(tags) if !tags.is_empty() => tags,
+            _ => &self.scopes,
+        };
+        scopes.is_empty() || scopes.contains(&scope) || scopes.contains(&Scope::General)
+    }
+
+    /// Extract rules from a Markdown document, with priority 0.
+    ///
+    /// See the [module docs](self#markdown-constitutions) for which
+    /// elements become rules. Each rule's section is its heading path,
+    /// outermost first, joined with `" > "`.
+    #[must_use]
+    pub fn from_markdown(id: impl Into<String>, text: &str) -> Self {
+        // (rule, section) pairs for list items and for prose paragraphs.
+        let mut items: Vec<(String, Option<String>)> = Vec::new();
+        let mut paragraphs: Vec<(String, Option<String>)> = Vec::new();
+        let mut headings: Vec<(usize, String)> = Vec::new();
+        // The block unmarked lines continue: `Some(true)` for the last
+        // item, `Some(false)` for the last paragraph.
+        let mut open: Option<bool> = None;
+        let mut in_fence = false;
+        let mut in_comment = false;
+
+        let mut lines = text.lines().peekable();
+        if lines.peek().is_some_and(|l| l.trim() == "---") {
+            lines.next();
+            lines.by_ref().find(|l| l.trim() == "---");
+        }
+        for line in lines {
+            let line = line.trim();
+            if line.starts_with("```") || line.starts_with("~~~") {
+                in_fence = !in_fence;
+                open = None;
+                continue;
+            }
+            if in_comment || line.starts_with("<!--") {
+                in_comment = !line.contains("-->");
+                continue;
+            }
+            if in_fence || line.is_empty() || is_thematic_break(line) {
+                open = None;
+                continue;
+            }
+            if line.starts_with('>') || line.starts_with('|') {
+                open = None;
+                continue;
+            }
+            if let Some(title) = line.strip_prefix('#') {
+                let level = 1 + title.bytes().take_while(|&b| b == b'#').count();
+                let title = title.trim_start_matches('#').trim();
+                headings.retain(|(l, _)| *l < level);
+                if !title.is_empty() {
+                    headings.push((level, title.to_string()));
+                }
+                open = None;
+                continue;
+            }
+
+            let section = (!headings.is_empty()).then(|| {
+                headings
+                    .iter()
+                    .map(|(_, t)| t.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" > ")
+            });
+            let rule = strip_list_marker(line);
+            if rule.len() != line.len() {
+                items.push((rule.to_string(), section));
+                open = Some(true);
+                continue;
+            }
+            let target = match open {
+                Some(true) => items.last_mut(),
+                Some(false) => paragraphs.last_mut(),
+                None => None,
+            };
+            if let Some((text, _)) = target {
+                text.push(' ');
+                text.push_str(line);
+            } else {
+                paragraphs.push((line.to_string(), section));
+                open = Some(false);
+            }
+        }
+
+        let (rules, sections) = if items.is_empty() { paragraphs } else { items }
+            .into_iter()
+            .filter(|(rule, _)| !rule.is_empty())
+            .unzip();
+        Self {
+            id: id.into(),
+            rules,
+            priority: 0,
+            sections,
+            scopes: Vec::new(),
+            rule_scopes: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+
+    /// The section rule `index` appeared under, if known.
+    #[must_use]
+    pub fn section(&self, index: usize) -> Option<&str> {
+        self.sections.get(index)?.as_deref()
     }
 }
 
+/// `---`, `***` or `___`, optionally spaced.
+fn is_thematic_break(line: &str) -> bool {
+    let marks: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && ['-', '*', '_'].iter().any(|m| marks.iter().all(|c| c == m))
+}
+
 // ── Composition error ────────────────────────────────────────
 
 /// Error returned when composition has unresolvable conflicts.
@@ -154,33 +718,177 @@ impl fmt::Display for CompositionError {
 
 impl std::error::Error for CompositionError {}
 
+impl CompositionError {
+    /// Explain every conflict, for authors fixing their constitutions.
+    #[must_use]
+    pub fn report(&self) -> ConflictReport {
+        ConflictReport {
+            conflicts: self
+                .conflicts
+                .iter()
+                .map(|conflict| ExplainedConflict {
+                    conflict: conflict.clone(),
+                    explanation: conflict.explain(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A conflict and its explanation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplainedConflict {
+    /// The conflict as detected.
+    #[serde(flatten)]
+    pub conflict: Conflict,
+    /// Why it was detected.
+    pub explanation: ConflictExplanation,
+}
+
+/// Explanations for all conflicts of a [`CompositionError`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictReport {
+    /// One entry per conflict, in detection order.
+    pub conflicts: Vec<ExplainedConflict>,
+}
+
+impl ConflictReport {
+    /// Render as a Markdown document, with evidence in bold.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Composition conflicts\n\n{} unresolvable conflict(s).\n",
+            self.conflicts.len()
+        );
+        for (i, entry) in self.conflicts.iter().enumerate() {
+            let (conflict, explanation) = (&entry.conflict, &entry.explanation);
+            let _ = write!(
+                out,
+                "\n## {}. {}\n\n- Incoming (`{}`): {}\n- Existing (`{}`): {}\n",
+                i + 1,
+                conflict.conflict_type,
+                conflict.source_a,
+                emphasize(&conflict.rule_a, &explanation.rule_a),
+                conflict.source_b,
+                emphasize(&conflict.rule_b, &explanation.rule_b),
+            );
+            if let Some((a, b)) = explanation.keyword_pair() {
+                let _ = writeln!(out, "- Opposing keywords: `{a}` / `{b}`");
+            }
+            if !explanation.topic_words.is_empty() {
+                let words: Vec<String> = explanation
+                    .topic_words
+                    .iter()
+                    .map(|w| format!("`{w}`"))
+                    .collect();
+                let _ = writeln!(out, "- Shared topic words: {}", words.join(", "));
+            }
+        }
+        out
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`](crate::VcpError::JsonError) if
+    /// serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
 // ── Conflict keywords ────────────────────────────────────────
 
 /// Keywords that indicate potential conflicts between rules.
 ///
-/// Maps each keyword to its set of opposing keywords.
-fn conflict_keywords() -> HashMap<&'static str, Vec<&'static str>> {
-    let mut m = HashMap::new();
-    m.insert("always", vec!["never"]);
-    m.insert("never", vec!["always"]);
-    m.insert("must", vec!["must not", "should not", "never"]);
-    m.insert("must not", vec!["must", "always"]);
-    m.insert("allow", vec!["forbid", "prohibit", "deny"]);
-    m.insert("forbid", vec!["allow", "permit"]);
-    m.insert("prohibit", vec!["allow", "permit"]);
-    m.insert("require", vec!["forbid", "prohibit"]);
-    m
-}
+/// Pairs each keyword with its opposing keywords.
+pub(crate) const CONFLICT_KEYWORDS: &[(&str, &[&str])] = &[
+    ("always", &["never"]),
+    ("never", &["always"]),
+    ("must", &["must not", "should not", "never"]),
+    ("must not", &["must", "always"]),
+    ("allow", &["forbid", "prohibit", "deny"]),
+    ("forbid", &["allow", "permit"]),
+    ("prohibit", &["allow", "permit"]),
+    ("require", &["forbid", "prohibit"]),
+];
 
 /// Common words excluded from topic-overlap heuristics.
-fn common_words() -> HashSet<&'static str> {
-    [
-        "the", "a", "an", "is", "are", "be", "to", "of", "and", "or", "in", "on", "at", "for",
-        "with", "by", "from", "as", "it", "this", "that", "these", "those", "you", "we", "they",
-        "i",
-    ]
-    .into_iter()
-    .collect()
+const COMMON_WORDS: &[&str] = &[
+    "the", "a", "an", "is", "are", "be", "to", "of", "and", "or", "in", "on", "at", "for", "with",
+    "by", "from", "as", "it", "this", "that", "these", "those", "you", "we", "they", "i",
+];
+
+/// Significant words of an already-lowercased rule, as compared by
+/// [`Composer::same_topic`].
+pub(crate) fn topic_words(rule: &str) -> HashSet<&str> {
+    rule.split_whitespace()
+        .filter(|w| !COMMON_WORDS.contains(w))
+        .collect()
+}
+
+// ── Conflict hooks ───────────────────────────────────────────
+
+/// How an `on_conflict` hook settled a conflict.
+///
+/// Hooks return it as `HookAction::Modify` with a `resolution` tag, e.g.
+/// `{"resolution": "keep_existing"}` or
+/// `{"resolution": "rewrite", "rule": "Ask before sharing personal data."}`.
+/// Aborting the chain escalates the conflict into a [`CompositionError`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "resolution", rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// Keep the rule already merged and drop the incoming one.
+    KeepExisting,
+    /// Replace the merged rule with the incoming one.
+    KeepIncoming,
+    /// Accept the tension and keep both rules.
+    KeepBoth,
+    /// Keep the merged rule and add this text in place of the incoming one.
+    Rewrite {
+        /// Replacement text, attributed to the incoming rule's constitution.
+        rule: String,
+    },
+}
+
+impl fmt::Display for ConflictResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConflictResolution::KeepExisting => f.write_str("keep_existing"),
+            ConflictResolution::KeepIncoming => f.write_str("keep_incoming"),
+            ConflictResolution::KeepBoth => f.write_str("keep_both"),
+            ConflictResolution::Rewrite { .. } => f.write_str("rewrite"),
+        }
+    }
+}
+
+/// Executor and session used to run `on_conflict` chains.
+#[derive(Clone, Copy)]
+struct ConflictHooks<'a> {
+    executor: &'a HookExecutor<'a>,
+    session_id: &'a str,
+}
+
+/// Apply `resolution` to `merged`, where `existing` is the index of the
+/// conflicting merged rule. Returns whether the incoming rule was kept.
+fn apply_resolution(
+    resolution: &ConflictResolution,
+    merged: &mut Vec<MergedRule>,
+    existing: usize,
+    mut incoming: MergedRule,
+) -> bool {
+    match resolution {
+        ConflictResolution::KeepExisting => return false,
+        ConflictResolution::KeepIncoming => {
+            let replaced = merged.remove(existing);
+            incoming.overridden = Some(vec![replaced.origin()]);
+        }
+        ConflictResolution::KeepBoth => {}
+        ConflictResolution::Rewrite { rule } => incoming.text.clone_from(rule),
+    }
+    merged.push(incoming);
+    true
 }
 
 // ── Composer ─────────────────────────────────────────────────
@@ -188,14 +896,41 @@ fn common_words() -> HashSet<&'static str> {
 /// Composition engine for merging multiple constitutions.
 ///
 /// Provides four composition modes and uses keyword-based heuristics
-/// to detect semantic conflicts between rules.
-pub struct Composer;
+/// to detect semantic conflicts between rules. With
+/// [`with_conflict_hooks`](Self::with_conflict_hooks), each detected
+/// conflict is first offered to the `on_conflict` hook chain.
+#[derive(Clone, Copy)]
+pub struct Composer<'a> {
+    hooks: Option<ConflictHooks<'a>>,
+}
 
-impl Composer {
+impl<'a> Composer<'a> {
     /// Create a new composer instance.
     #[must_use]
     pub fn new() -> Self {
-        Composer
+        Composer { hooks: None }
+    }
+
+    /// Run the [`HookType::OnConflict`] chain for `session_id` whenever a
+    /// conflict is detected.
+    ///
+    /// The hook event carries the mode and both rules. A chain that ends
+    /// with a [`ConflictResolution`] in its modified context settles the
+    /// conflict, which is then recorded in
+    /// [`CompositionResult::conflicts`] with that resolution. A chain that
+    /// aborts fails the composition. Otherwise the mode's usual handling
+    /// applies. Rewritten rules are not re-checked for conflicts.
+    #[must_use]
+    pub fn with_conflict_hooks(
+        mut self,
+        executor: &'a HookExecutor<'a>,
+        session_id: &'a str,
+    ) -> Self {
+        self.hooks = Some(ConflictHooks {
+            executor,
+            session_id,
+        });
+        self
     }
 
     /// Compose constitutions according to the specified mode.
@@ -203,7 +938,22 @@ impl Composer {
     /// # Errors
     ///
     /// Returns [`CompositionError`] if the chosen mode does not allow
-    /// the conflicts that were detected.
+    /// the conflicts that were detected, or if an `on_conflict` hook
+    /// aborted or returned something other than a [`ConflictResolution`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vcp.compose",
+            level = "debug",
+            skip_all,
+            fields(
+                %mode,
+                constitutions = constitutions.len(),
+                rules = tracing::field::Empty,
+                conflicts = tracing::field::Empty,
+            ),
+        )
+    )]
     pub fn compose(
         &self,
         constitutions: &[Constitution],
@@ -218,62 +968,209 @@ impl Composer {
             });
         }
 
-        match mode {
-            CompositionMode::Base => Ok(self.compose_base(constitutions)),
+        let result = match mode {
+            CompositionMode::Base => self.compose_base(constitutions),
             CompositionMode::Extend => self.compose_extend(constitutions),
-            CompositionMode::Override => Ok(self.compose_override(constitutions)),
+            CompositionMode::Override => self.compose_override(constitutions),
             CompositionMode::Strict => self.compose_strict(constitutions),
+        };
+        #[cfg(feature = "tracing")]
+        if let Ok(composed) = &result {
+            let span = tracing::Span::current();
+            span.record("rules", composed.merged_rules.len());
+            span.record("conflicts", composed.conflicts.len());
         }
+        result
+    }
+
+    /// Compose only the rules that apply in `scope`.
+    ///
+    /// Rules outside the scope are left out before conflict detection, so
+    /// they cannot conflict with anything. Merged rules keep their
+    /// `original_index` into the constitutions passed in.
+    ///
+    /// # Errors
+    ///
+    /// As [`compose`](Self::compose).
+    pub fn compose_for_scope(
+        &self,
+        constitutions: &[Constitution],
+        mode: CompositionMode,
+        scope: Scope,
+    ) -> Result<CompositionResult, CompositionError> {
+        // Indices of the kept rules, per constitution.
+        let kept: Vec<Vec<usize>> = constitutions
+            .iter()
+            .map(|c| {
+                (0..c.rules.len())
+                    .filter(|&i| c.applies_to(i, scope))
+                    .collect()
+            })
+            .collect();
+        let filtered: Vec<Constitution> = constitutions
+            .iter()
+            .zip(&kept)
+            .map(|(c, indices)| Constitution {
+                id: c.id.clone(),
+                rules: indices.iter().map(|&i| c.rules[i].clone()).collect(),
+                priority: c.priority,
+                sections: indices
+                    .iter()
+                    .filter_map(|&i| c.sections.get(i).cloned())
+                    .collect(),
+                scopes: c.scopes.clone(),
+                rule_scopes: Vec::new(),
+                conditions: indices
+                    .iter()
+                    .filter_map(|&i| c.conditions.get(i).cloned())
+                    .collect(),
+            })
+            .collect();
+
+        let mut result = self.compose(&filtered, mode)?;
+        let original = |source_id: &str, index: usize| {
+            constitutions
+                .iter()
+                .position(|c| c.id == source_id)
+                .and_then(|c| kept[c].get(index).copied())
+                .unwrap_or(index)
+        };
+        for rule in &mut result.merged_rules {
+            rule.original_index = original(&rule.source_id, rule.original_index);
+            for origin in rule.overridden.iter_mut().flatten() {
+                origin.original_index = original(&origin.source_id, origin.original_index);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Offer `conflict` to the `on_conflict` chain.
+    ///
+    /// Returns `Ok(None)` when no hooks are attached or none of them
+    /// settled the conflict, and escalates the conflict when a hook
+    /// aborts or modifies it with something that is not a
+    /// [`ConflictResolution`].
+    fn consult(
+        &self,
+        conflict: &Conflict,
+        mode: CompositionMode,
+    ) -> Result<Option<ConflictResolution>, CompositionError> {
+        let Some(hooks) = self.hooks else {
+            return Ok(None);
+        };
+
+        let input = HookInput {
+            context: serde_json::Value::Null,
+            constitution: serde_json::Value::Null,
+            event: serde_json::json!({
+                "mode": mode,
+                "conflict": conflict,
+            }),
+            session_id: hooks.session_id.to_string(),
+            chain_state: ChainState::new(),
+        };
+        let chain = hooks
+            .executor
+            .execute(HookType::OnConflict, hooks.session_id, input);
+
+        if !chain.completed {
+            let mut escalated = conflict.clone();
+            escalated.resolution = Some(format!(
+                "escalated by {}: {}",
+                chain.aborted_by.unwrap_or_default(),
+                chain.abort_reason.unwrap_or_default()
+            ));
+            return Err(CompositionError {
+                conflicts: vec![escalated],
+            });
+        }
+
+        let Some(value) = chain.modified_context else {
+            return Ok(None);
+        };
+        serde_json::from_value(value).map(Some).map_err(|e| {
+            let hook = chain
+                .results
+                .iter()
+                .rev()
+                .find(|(_, result)| matches!(result.action, HookAction::Modify(_)))
+                .map(|(name, _)| name.as_str())
+                .unwrap_or_default();
+            let mut escalated = conflict.clone();
+            escalated.resolution = Some(format!("malformed resolution from {hook}: {e}"));
+            CompositionError {
+                conflicts: vec![escalated],
+            }
+        })
     }
 
     /// BASE mode: first constitution is immutable.
     ///
     /// Later constitutions can only add non-conflicting rules.
-    /// Conflicts are recorded but the base rules always win.
-    fn compose_base(&self, constitutions: &[Constitution]) -> CompositionResult {
+    /// Conflicts are recorded but the base rules always win, unless an
+    /// `on_conflict` hook decides otherwise.
+    fn compose_base(
+        &self,
+        constitutions: &[Constitution],
+    ) -> Result<CompositionResult, CompositionError> {
         let base = &constitutions[0];
-        let mut merged = base.rules.clone();
+        let mut merged: Vec<MergedRule> = (0..base.rules.len())
+            .map(|i| MergedRule::of(base, i))
+            .collect();
         let mut conflicts = Vec::new();
 
         for constitution in &constitutions[1..] {
-            for rule in &constitution.rules {
-                if let Some(conflict) =
+            for (index, rule) in constitution.rules.iter().enumerate() {
+                if let Some((existing, mut conflict)) =
                     self.detect_conflict(rule, &constitution.id, &merged, &base.id)
                 {
+                    if let Some(resolution) = self.consult(&conflict, CompositionMode::Base)? {
+                        let incoming = MergedRule::of(constitution, index);
+                        apply_resolution(&resolution, &mut merged, existing, incoming);
+                        conflict.resolution = Some(resolution.to_string());
+                    }
                     conflicts.push(conflict);
                 } else {
-                    merged.push(rule.clone());
+                    merged.push(MergedRule::of(constitution, index));
                 }
             }
         }
 
-        CompositionResult {
+        Ok(CompositionResult {
             merged_rules: merged,
             conflicts,
             warnings: Vec::new(),
             mode_used: CompositionMode::Base,
-        }
+        })
     }
 
-    /// EXTEND mode: all rules merged, conflicts are errors.
+    /// EXTEND mode: all rules merged, unresolved conflicts are errors.
     fn compose_extend(
         &self,
         constitutions: &[Constitution],
     ) -> Result<CompositionResult, CompositionError> {
-        let mut merged: Vec<String> = Vec::new();
+        let mut merged: Vec<MergedRule> = Vec::new();
         let mut conflicts: Vec<Conflict> = Vec::new();
+        let mut resolved: Vec<Conflict> = Vec::new();
         let mut sources: HashMap<String, String> = HashMap::new();
 
         for constitution in constitutions {
-            for rule in &constitution.rules {
+            for (index, rule) in constitution.rules.iter().enumerate() {
                 let existing_source = sources.get(rule).map_or("unknown", String::as_str);
 
-                if let Some(conflict) =
+                if let Some((existing, mut conflict)) =
                     self.detect_conflict(rule, &constitution.id, &merged, existing_source)
                 {
-                    conflicts.push(conflict);
+                    if let Some(resolution) = self.consult(&conflict, CompositionMode::Extend)? {
+                        let incoming = MergedRule::of(constitution, index);
+                        apply_resolution(&resolution, &mut merged, existing, incoming);
+                        conflict.resolution = Some(resolution.to_string());
+                        resolved.push(conflict);
+                    } else {
+                        conflicts.push(conflict);
+                    }
                 } else {
-                    merged.push(rule.clone());
+                    merged.push(MergedRule::of(constitution, index));
                     sources.insert(rule.clone(), constitution.id.clone());
                 }
             }
@@ -285,69 +1182,99 @@ impl Composer {
 
         Ok(CompositionResult {
             merged_rules: merged,
-            conflicts: Vec::new(),
+            conflicts: resolved,
             warnings: Vec::new(),
             mode_used: CompositionMode::Extend,
         })
     }
 
-    /// OVERRIDE mode: later constitutions win conflicts.
-    fn compose_override(&self, constitutions: &[Constitution]) -> CompositionResult {
-        let mut merged: Vec<String> = Vec::new();
+    /// OVERRIDE mode: later constitutions win conflicts, unless an
+    /// `on_conflict` hook picks differently.
+    fn compose_override(
+        &self,
+        constitutions: &[Constitution],
+    ) -> Result<CompositionResult, CompositionError> {
+        let mut merged: Vec<MergedRule> = Vec::new();
+        let mut conflicts: Vec<Conflict> = Vec::new();
         let mut warnings: Vec<String> = Vec::new();
 
         for constitution in constitutions {
-            for rule in &constitution.rules {
+            for (index, rule) in constitution.rules.iter().enumerate() {
+                let mut entry = MergedRule::of(constitution, index);
+                let mut keep_entry = true;
+                let mut removed: Vec<usize> = Vec::new();
+
                 // Find conflicting rules in current merged set.
-                let conflicting_indices: Vec<usize> = merged
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(i, existing)| {
-                        if self.rules_conflict(existing, rule) {
-                            Some(i)
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
+                for (i, existing) in merged.iter().enumerate() {
+                    if !self.rules_conflict(&existing.text, rule) {
+                        continue;
+                    }
+                    let mut conflict = Conflict {
+                        rule_a: rule.clone(),
+                        source_a: constitution.id.clone(),
+                        rule_b: existing.text.clone(),
+                        source_b: existing.source_id.clone(),
+                        conflict_type: self.determine_conflict_type(rule, &existing.text),
+                        resolution: None,
+                    };
+                    let resolution = self.consult(&conflict, CompositionMode::Override)?;
+                    match &resolution {
+                        None | Some(ConflictResolution::KeepIncoming) => removed.push(i),
+                        Some(ConflictResolution::KeepExisting) => keep_entry = false,
+                        Some(ConflictResolution::KeepBoth) => {}
+                        Some(ConflictResolution::Rewrite { rule }) => entry.text.clone_from(rule),
+                    }
+                    if let Some(resolution) = resolution {
+                        conflict.resolution = Some(resolution.to_string());
+                        conflicts.push(conflict);
+                    }
+                }
+
+                if !keep_entry {
+                    continue;
+                }
 
                 // Record warnings for overridden rules.
-                for &i in &conflicting_indices {
+                for &i in &removed {
                     warnings.push(format!(
                         "Rule '{}' ({}) overrides '{}'",
-                        rule, constitution.id, merged[i]
+                        rule, constitution.id, merged[i].text
                     ));
                 }
+                if !removed.is_empty() {
+                    entry.overridden = Some(removed.iter().map(|&i| merged[i].origin()).collect());
+                }
 
                 // Remove conflicting rules in reverse order to preserve indices.
-                for &i in conflicting_indices.iter().rev() {
+                for &i in removed.iter().rev() {
                     merged.remove(i);
                 }
 
-                merged.push(rule.clone());
+                merged.push(entry);
             }
         }
 
-        CompositionResult {
+        Ok(CompositionResult {
             merged_rules: merged,
-            conflicts: Vec::new(),
+            conflicts,
             warnings,
             mode_used: CompositionMode::Override,
-        }
+        })
     }
 
-    /// STRICT mode: no conflicts and no duplicates allowed.
+    /// STRICT mode: no duplicates and no unresolved conflicts allowed.
     fn compose_strict(
         &self,
         constitutions: &[Constitution],
     ) -> Result<CompositionResult, CompositionError> {
-        let mut merged: Vec<String> = Vec::new();
+        let mut merged: Vec<MergedRule> = Vec::new();
         let mut conflicts: Vec<Conflict> = Vec::new();
+        let mut resolved: Vec<Conflict> = Vec::new();
         let mut seen_rules: HashSet<String> = HashSet::new();
         let mut sources: HashMap<String, String> = HashMap::new();
 
         for constitution in constitutions {
-            for rule in &constitution.rules {
+            for (index, rule) in constitution.rules.iter().enumerate() {
                 let normalized = rule.to_lowercase();
 
                 // Check for exact duplicates.
@@ -367,14 +1294,24 @@ impl Composer {
                 }
 
                 // Check for semantic conflicts.
-                if let Some(conflict) =
+                if let Some((existing, mut conflict)) =
                     self.detect_conflict(rule, &constitution.id, &merged, "earlier")
                 {
-                    conflicts.push(conflict);
+                    if let Some(resolution) = self.consult(&conflict, CompositionMode::Strict)? {
+                        let incoming = MergedRule::of(constitution, index);
+                        if apply_resolution(&resolution, &mut merged, existing, incoming) {
+                            seen_rules.insert(normalized.clone());
+                            sources.insert(normalized, constitution.id.clone());
+                        }
+                        conflict.resolution = Some(resolution.to_string());
+                        resolved.push(conflict);
+                    } else {
+                        conflicts.push(conflict);
+                    }
                     continue;
                 }
 
-                merged.push(rule.clone());
+                merged.push(MergedRule::of(constitution, index));
                 seen_rules.insert(normalized.clone());
                 sources.insert(normalized, constitution.id.clone());
             }
@@ -386,30 +1323,34 @@ impl Composer {
 
         Ok(CompositionResult {
             merged_rules: merged,
-            conflicts: Vec::new(),
+            conflicts: resolved,
             warnings: Vec::new(),
             mode_used: CompositionMode::Strict,
         })
     }
 
-    /// Detect whether a rule conflicts with any rule in the existing set.
+    /// Detect whether a rule conflicts with any rule in the existing set,
+    /// returning the index of the first conflicting rule with the conflict.
     fn detect_conflict(
         &self,
         rule: &str,
         source: &str,
-        existing: &[String],
+        existing: &[MergedRule],
         existing_source: &str,
-    ) -> Option<Conflict> {
-        for existing_rule in existing {
+    ) -> Option<(usize, Conflict)> {
+        for (i, existing_rule) in existing.iter().map(MergedRule::as_str).enumerate() {
             if self.rules_conflict(rule, existing_rule) {
-                return Some(Conflict {
-                    rule_a: rule.to_string(),
-                    source_a: source.to_string(),
-                    rule_b: existing_rule.clone(),
-                    source_b: existing_source.to_string(),
-                    conflict_type: self.determine_conflict_type(rule, existing_rule),
-                    resolution: None,
-                });
+                return Some((
+                    i,
+                    Conflict {
+                        rule_a: rule.to_string(),
+                        source_a: source.to_string(),
+                        rule_b: existing_rule.to_string(),
+                        source_b: existing_source.to_string(),
+                        conflict_type: self.determine_conflict_type(rule, existing_rule),
+                        resolution: None,
+                    },
+                ));
             }
         }
         None
@@ -425,11 +1366,9 @@ impl Composer {
         let a_lower = rule_a.to_lowercase();
         let b_lower = rule_b.to_lowercase();
 
-        let keywords = conflict_keywords();
-
-        for (keyword, opposites) in &keywords {
+        for (keyword, opposites) in CONFLICT_KEYWORDS {
             if a_lower.contains(keyword) {
-                for opposite in opposites {
+                for opposite in *opposites {
                     if b_lower.contains(opposite) && self.same_topic(&a_lower, &b_lower) {
                         return true;
                     }
@@ -446,18 +1385,8 @@ impl Composer {
     /// returns `true` if there are at least 2 words in common.
     #[must_use]
     pub fn same_topic(&self, rule_a: &str, rule_b: &str) -> bool {
-        let stop_words = common_words();
-
-        let words_a: HashSet<&str> = rule_a
-            .split_whitespace()
-            .filter(|w| !stop_words.contains(w))
-            .collect();
-
-        let words_b: HashSet<&str> = rule_b
-            .split_whitespace()
-            .filter(|w| !stop_words.contains(w))
-            .collect();
-
+        let words_a = topic_words(rule_a);
+        let words_b = topic_words(rule_b);
         let overlap: usize = words_a.intersection(&words_b).count();
         overlap >= 2
     }
@@ -496,7 +1425,7 @@ impl Composer {
     }
 }
 
-impl Default for Composer {
+impl Default for Composer<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -512,6 +1441,9 @@ fn a_lower_has_must_without_not(s: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hooks::{Hook, HookHandler, HookRegistry, HookResult, HookScope};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     // ── Empty / single constitution ──────────────────────────
 
@@ -618,6 +1550,245 @@ mod tests {
         assert!(!result.warnings.is_empty());
     }
 
+    #[test]
+    fn override_mode_records_overridden_rule() {
+        let c1 = Constitution::new(
+            "old",
+            vec![
+                "Be brief.".into(),
+                "Always collect user tracking data.".into(),
+            ],
+            0,
+        );
+        let c2 = Constitution::new("new", vec!["Never collect user tracking data.".into()], 1);
+
+        let result = Composer::new()
+            .compose(&[c1, c2], CompositionMode::Override)
+            .unwrap();
+
+        assert!(result.merged_rules[0].overridden.is_none());
+        let winner = &result.merged_rules[1];
+        assert_eq!(winner.source_id, "new");
+        assert_eq!(winner.original_index, 0);
+        assert_eq!(
+            winner.overridden.as_deref(),
+            Some(
+                &[RuleOrigin {
+                    text: "Always collect user tracking data.".into(),
+                    source_id: "old".into(),
+                    original_index: 1,
+                }][..]
+            )
+        );
+    }
+
+    // ── Provenance ───────────────────────────────────────────
+
+    #[test]
+    fn merged_rules_carry_source_and_index() {
+        let c1 = Constitution::new("a", vec!["Be kind.".into(), "Be honest.".into()], 0);
+        let c2 = Constitution::new("b", vec!["Respect privacy.".into()], 1);
+
+        let result = Composer::new()
+            .compose(&[c1, c2], CompositionMode::Extend)
+            .unwrap();
+
+        let origins: Vec<_> = result
+            .merged_rules
+            .iter()
+            .map(|r| (r.source_id.as_str(), r.original_index))
+            .collect();
+        assert_eq!(origins, [("a", 0), ("a", 1), ("b", 0)]);
+        assert_eq!(result.rules_from("b").count(), 1);
+        assert_eq!(
+            result.clone().into_rule_texts(),
+            ["Be kind.", "Be honest.", "Respect privacy."]
+        );
+    }
+
+    #[test]
+    fn result_deserializes_plain_string_rules() {
+        let json =
+            r#"{"merged_rules":["Be kind."],"conflicts":[],"warnings":[],"mode_used":"extend"}"#;
+        let result: CompositionResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.rule_texts(), ["Be kind."]);
+        assert!(result.merged_rules[0].source_id.is_empty());
+
+        let roundtrip: CompositionResult =
+            serde_json::from_str(&serde_json::to_string(&result).unwrap()).unwrap();
+        assert_eq!(roundtrip, result);
+    }
+
+    // ── Conflict hooks ───────────────────────────────────────
+
+    /// Answers every conflict with a fixed action and records the events.
+    struct Resolver {
+        action: HookAction,
+        seen: Arc<Mutex<Vec<serde_json::Value>>>,
+    }
+
+    impl HookHandler for Resolver {
+        fn execute(&self, input: &HookInput) -> HookResult {
+            self.seen.lock().unwrap().push(input.event.clone());
+            HookResult {
+                action: self.action.clone(),
+                annotations: HashMap::new(),
+                state_updates: ChainState::new(),
+                duration: Duration::ZERO,
+            }
+        }
+    }
+
+    fn resolver_registry(action: HookAction) -> (HookRegistry, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = HookRegistry::new();
+        registry
+            .register(
+                Hook {
+                    name: "resolver".into(),
+                    hook_type: HookType::OnConflict,
+                    priority: 50,
+                    handler: Box::new(Resolver {
+                        action,
+                        seen: Arc::clone(&seen),
+                    }),
+                    timeout: Duration::from_secs(1),
+                    enabled: true,
+                    description: String::new(),
+                },
+                HookScope::Deployment,
+                None,
+            )
+            .unwrap();
+        (registry, seen)
+    }
+
+    fn tracking_pair() -> [Constitution; 2] {
+        [
+            Constitution::new("a", vec!["Always collect user tracking data.".into()], 0),
+            Constitution::new("b", vec!["Never collect user tracking data.".into()], 1),
+        ]
+    }
+
+    #[test]
+    fn conflict_hook_receives_both_rules() {
+        let (registry, seen) = resolver_registry(HookAction::Continue);
+        let executor = HookExecutor::new(&registry);
+        let composer = Composer::new().with_conflict_hooks(&executor, "sess-1");
+
+        // Continue leaves the mode's own handling in place.
+        assert!(composer
+            .compose(&tracking_pair(), CompositionMode::Extend)
+            .is_err());
+
+        let events = seen.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["mode"], "extend");
+        assert_eq!(
+            events[0]["conflict"]["rule_a"],
+            "Never collect user tracking data."
+        );
+        assert_eq!(
+            events[0]["conflict"]["rule_b"],
+            "Always collect user tracking data."
+        );
+    }
+
+    #[test]
+    fn conflict_hook_picks_winner() {
+        let action = HookAction::Modify(serde_json::json!({"resolution": "keep_incoming"}));
+        let (registry, _) = resolver_registry(action);
+        let executor = HookExecutor::new(&registry);
+
+        let result = Composer::new()
+            .with_conflict_hooks(&executor, "sess-1")
+            .compose(&tracking_pair(), CompositionMode::Extend)
+            .unwrap();
+
+        assert_eq!(
+            result.merged_rules,
+            vec!["Never collect user tracking data."]
+        );
+        assert_eq!(
+            result.merged_rules[0].overridden.as_ref().unwrap()[0].source_id,
+            "a"
+        );
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(
+            result.conflicts[0].resolution.as_deref(),
+            Some("keep_incoming")
+        );
+    }
+
+    #[test]
+    fn conflict_hook_rewrites_rule() {
+        let action = HookAction::Modify(serde_json::json!({
+            "resolution": "rewrite",
+            "rule": "Collect tracking data only with consent."
+        }));
+        let (registry, _) = resolver_registry(action);
+        let executor = HookExecutor::new(&registry);
+
+        let result = Composer::new()
+            .with_conflict_hooks(&executor, "sess-1")
+            .compose(&tracking_pair(), CompositionMode::Override)
+            .unwrap();
+
+        assert_eq!(
+            result.merged_rules,
+            vec![
+                "Always collect user tracking data.",
+                "Collect tracking data only with consent."
+            ]
+        );
+        assert_eq!(result.merged_rules[1].source_id, "b");
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn conflict_hook_abort_escalates() {
+        let action = HookAction::Abort {
+            reason: "needs review".into(),
+        };
+        let (registry, _) = resolver_registry(action);
+        let executor = HookExecutor::new(&registry);
+
+        let err = Composer::new()
+            .with_conflict_hooks(&executor, "sess-1")
+            .compose(&tracking_pair(), CompositionMode::Base)
+            .unwrap_err();
+
+        assert_eq!(err.conflicts.len(), 1);
+        assert_eq!(
+            err.conflicts[0].resolution.as_deref(),
+            Some("escalated by resolver: needs review")
+        );
+    }
+
+    #[test]
+    fn malformed_conflict_resolution_escalates() {
+        for value in [
+            serde_json::json!({"resolution": "keep_neither"}),
+            serde_json::json!({"resolution": "rewrite"}),
+            serde_json::json!("keep_incoming please"),
+        ] {
+            let (registry, _) = resolver_registry(HookAction::Modify(value.clone()));
+            let executor = HookExecutor::new(&registry);
+
+            let err = Composer::new()
+                .with_conflict_hooks(&executor, "sess-1")
+                .compose(&tracking_pair(), CompositionMode::Override)
+                .unwrap_err();
+
+            assert_eq!(err.conflicts.len(), 1, "{value}");
+            let resolution = err.conflicts[0].resolution.as_deref().unwrap();
+            assert!(
+                resolution.starts_with("malformed resolution from resolver:"),
+                "{resolution}"
+            );
+        }
+    }
+
     // ── STRICT mode ──────────────────────────────────────────
 
     #[test]
@@ -788,7 +1959,7 @@ mod tests {
             "test",
             vec![
                 "  Rule one.  ".into(),
-                "".into(),
+                String::new(),
                 "   ".into(),
                 "Rule two.".into(),
             ],
@@ -796,4 +1967,225 @@ mod tests {
         );
         assert_eq!(c.rules, vec!["Rule one.", "Rule two."]);
     }
+
+    #[test]
+    fn conflict_explanation_locates_evidence() {
+        let conflict = Conflict {
+            rule_a: "Never share the user's personal data.".into(),
+            source_a: "ext".into(),
+            rule_b: "Always share Personal data with partners.".into(),
+            source_b: "base".into(),
+            conflict_type: "contradiction".into(),
+            resolution: None,
+        };
+        let explanation = conflict.explain();
+        assert_eq!(
+            explanation.keyword_pair(),
+            Some(("never".into(), "always".into()))
+        );
+        assert_eq!(explanation.topic_words, ["personal", "share"]);
+        let keyword = explanation.rule_b.keyword.as_ref().unwrap();
+        assert_eq!(
+            (keyword.text.as_str(), keyword.start, keyword.end),
+            ("Always", 0, 6)
+        );
+        let topic: Vec<(&str, usize)> = explanation
+            .rule_a
+            .topic
+            .iter()
+            .map(|h| (h.text.as_str(), h.start))
+            .collect();
+        assert_eq!(topic, [("share", 6), ("personal", 23)]);
+
+        // Offsets count characters, not bytes.
+        let accented = Conflict {
+            rule_a: "Évitez: never log user names.".into(),
+            rule_b: "Always log user names.".into(),
+            ..conflict.clone()
+        };
+        let keyword = accented.explain().rule_a.keyword.unwrap();
+        assert_eq!((keyword.start, keyword.end), (8, 13));
+
+        let duplicate = Conflict {
+            rule_b: conflict.rule_a.clone(),
+            conflict_type: "duplicate".into(),
+            ..conflict
+        };
+        let explanation = duplicate.explain();
+        assert!(explanation.keyword_pair().is_none());
+        assert_eq!(explanation.topic_words.len(), 5);
+    }
+
+    #[test]
+    fn composition_error_report() {
+        let c1 = Constitution::new("base", vec!["Always share personal data.".into()], 0);
+        let c2 = Constitution::new("ext", vec!["Never share personal data.".into()], 1);
+        let err = Composer::new()
+            .compose(&[c1, c2], CompositionMode::Extend)
+            .unwrap_err();
+        let report = err.report();
+        assert_eq!(
+            report.to_markdown(),
+            "# Composition conflicts\n\n1 unresolvable conflict(s).\n\n\
+             ## 1. contradiction\n\n\
+             - Incoming (`ext`): **Never** **share** **personal** **data.**\n\
+             - Existing (`unknown`): **Always** **share** **personal** **data.**\n\
+             - Opposing keywords: `never` / `always`\n\
+             - Shared topic words: `data.`, `personal`, `share`\n"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        let entry = &json["conflicts"][0];
+        assert_eq!(entry["source_a"], "ext");
+        assert_eq!(entry["explanation"]["rule_a"]["keyword"]["text"], "Never");
+        let back: ConflictReport = serde_json::from_value(json).unwrap();
+        assert_eq!(back, report);
+    }
+
+    #[test]
+    fn compose_for_scope_filters_rules() {
+        let health = Constitution::new(
+            "health",
+            vec![
+                "Always share medical data with doctors.".into(),
+                "Be respectful.".into(),
+            ],
+            0,
+        )
+        .with_scopes([Scope::Healthcare])
+        .with_rule_scopes(1, [Scope::General]);
+        let family = Constitution::new(
+            "family",
+            vec![
+                "Keep answers short.".into(),
+                "Never share medical data with doctors.".into(),
+            ],
+            1,
+        )
+        .with_rule_scopes(1, [Scope::Privacy]);
+        assert!(family.applies_to(0, Scope::Work));
+        assert!(!family.applies_to(1, Scope::Healthcare));
+
+        let composer = Composer::new();
+        let constitutions = [health, family];
+        assert!(composer
+            .compose(&constitutions, CompositionMode::Extend)
+            .is_err());
+
+        let result = composer
+            .compose_for_scope(&constitutions, CompositionMode::Extend, Scope::Healthcare)
+            .unwrap();
+        assert_eq!(
+            result.rule_texts(),
+            [
+                "Always share medical data with doctors.",
+                "Be respectful.",
+                "Keep answers short."
+            ]
+        );
+
+        let result = composer
+            .compose_for_scope(&constitutions, CompositionMode::Override, Scope::Privacy)
+            .unwrap();
+        assert_eq!(
+            result.rule_texts(),
+            [
+                "Be respectful.",
+                "Keep answers short.",
+                "Never share medical data with doctors."
+            ]
+        );
+        // Indices point into the unfiltered constitutions.
+        assert_eq!(result.merged_rules[0].original_index, 1);
+        assert_eq!(result.merged_rules[2].original_index, 1);
+        assert_eq!(result.merged_rules[2].source_id, "family");
+    }
+
+    #[test]
+    fn conditional_rules_filter_by_context() {
+        let school = Condition::parse("situational.space == school").unwrap();
+        let c = Constitution::new(
+            "tutor",
+            vec![
+                "Be encouraging.".into(),
+                "Use age-appropriate language.".into(),
+                "Keep answers to one sentence.".into(),
+            ],
+            0,
+        )
+        .with_rule_condition(1, school.clone())
+        .with_rule_condition(2, "personal.urgency >= 4".parse().unwrap());
+        assert_eq!(c.condition(1), Some(&school));
+        assert!(c.condition(0).is_none());
+
+        let result = Composer::new()
+            .compose(std::slice::from_ref(&c), CompositionMode::Extend)
+            .unwrap();
+        assert_eq!(result.merged_rules[1].condition.as_ref(), Some(&school));
+
+        let at_school = FullContext::from_wire("📍🏫").unwrap();
+        assert_eq!(
+            result.filter_for_context(&at_school).rule_texts(),
+            ["Be encouraging.", "Use age-appropriate language."]
+        );
+        let rushed = FullContext::from_wire("📍🏡‖⚡critical:5").unwrap();
+        assert_eq!(
+            result.filter_for_context(&rushed).rule_texts(),
+            ["Be encouraging.", "Keep answers to one sentence."]
+        );
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json["merged_rules"][1]["condition"],
+            "situational.space == school"
+        );
+        assert!(json["merged_rules"][0].get("condition").is_none());
+        let back: CompositionResult = serde_json::from_value(json).unwrap();
+        assert_eq!(back, result);
+
+        let scoped = Composer::new()
+            .compose_for_scope(&[c], CompositionMode::Extend, Scope::Education)
+            .unwrap();
+        assert_eq!(scoped.merged_rules[1].condition.as_ref(), Some(&school));
+    }
+
+    #[test]
+    fn constitution_from_markdown() {
+        let text = "---\ntitle: Tutor\n---\n# Tutor\nThis document governs the tutor.\n\n## Safety\n- Never share personal data.\n- Always cite sources\n  when quoting.\n  1. Prefer primary sources.\n\n> A quote.\n\n```\n- not a rule\n```\n<!-- - hidden\n-->\n---\n## Style\n1) Be brief.\n| a | b |\n# Appendix\n* Be kind.\n";
+        let c = Constitution::from_markdown("tutor", text);
+        assert_eq!(
+            c.rules,
+            [
+                "Never share personal data.",
+                "Always cite sources when quoting.",
+                "Prefer primary sources.",
+                "Be brief.",
+                "Be kind.",
+            ]
+        );
+        assert_eq!(c.section(0), Some("Tutor > Safety"));
+        assert_eq!(c.section(2), Some("Tutor > Safety"));
+        assert_eq!(c.section(3), Some("Tutor > Style"));
+        assert_eq!(c.section(4), Some("Appendix"));
+        assert_eq!(c.section(5), None);
+        assert_eq!(c.priority, 0);
+
+        let composer = Composer::new();
+        let base = Constitution::new("base", vec!["Always share personal data.".into()], 0);
+        let result = composer
+            .compose(&[base, c], CompositionMode::Override)
+            .unwrap();
+        let winner = result.rules_from("tutor").next().unwrap();
+        assert_eq!(winner, "Never share personal data.");
+    }
+
+    #[test]
+    fn constitution_from_markdown_prose_only() {
+        let c = Constitution::from_markdown("p", "Be honest and\nkind.\n\nRespect privacy.\n");
+        assert_eq!(c.rules, ["Be honest and kind.", "Respect privacy."]);
+        assert_eq!(c.sections, [None, None]);
+        assert!(Constitution::new("n", vec!["x".into()], 0)
+            .section(0)
+            .is_none());
+    }
 }
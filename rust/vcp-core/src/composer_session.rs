@@ -0,0 +1,510 @@
+//! Incremental constitution composition.
+//!
+//! [`Composer::compose`] compares every incoming rule with every merged
+//! rule, and starts over whenever the input changes. A [`ComposerSession`]
+//! keeps the merged rules together with an inverted index from conflict
+//! keywords (`always`, `must not`, `forbid`, ...) to the rules containing
+//! them. Two rules can only conflict when one holds a keyword and the other
+//! its opposite, so an incoming rule is only compared with the rules the
+//! index returns for it, and then only if they share enough topic words.
+//!
+//! At every point the session holds what [`Composer::compose`] would return
+//! for the constitutions added so far, in the order they were added.
+//!
+//! | Operation | Cost |
+//! |-----------|------|
+//! | [`add_constitution`](ComposerSession::add_constitution) | Its rules × rules holding an opposing keyword |
+//! | [`remove_constitution`](ComposerSession::remove_constitution) | Replays the remaining constitutions through the index |
+//! | [`result`](ComposerSession::result) | Copies the merged rules |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::composer::{CompositionMode, Constitution};
+//! use vcp_core::composer_session::ComposerSession;
+//!
+//! let mut session = ComposerSession::new(CompositionMode::Extend);
+//! session
+//!     .add_constitution(Constitution::new("base", vec!["Always be honest.".into()], 0))
+//!     .unwrap();
+//! session
+//!     .add_constitution(Constitution::new("ext", vec!["Respect privacy.".into()], 1))
+//!     .unwrap();
+//! assert_eq!(session.rule_count(), 2);
+//!
+//! session.remove_constitution("base");
+//! assert_eq!(session.result().rule_texts(), ["Respect privacy."]);
+//! ```
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::composer::{
+    topic_words, Composer, CompositionError, CompositionMode, CompositionResult, Conflict,
+    Constitution, MergedRule, CONFLICT_KEYWORDS,
+};
+
+/// Topic words two rules must share to conflict (see [`Composer::same_topic`]).
+const MIN_SHARED_WORDS: usize = 2;
+
+// ── Merged state ────────────────────────────────────────────
+
+/// A merged rule with its lowercased text and topic words.
+#[derive(Debug, Clone)]
+struct Entry {
+    rule: MergedRule,
+    lower: String,
+    words: HashSet<String>,
+}
+
+impl Entry {
+    fn shares_topic(&self, words: &HashSet<&str>) -> bool {
+        words.iter().filter(|w| self.words.contains(**w)).count() >= MIN_SHARED_WORDS
+    }
+}
+
+/// Keywords of `lower` (as the first rule) whose opposites a conflicting
+/// rule must hold.
+fn opposing_terms(lower: &str) -> impl Iterator<Item = &'static str> + '_ {
+    CONFLICT_KEYWORDS
+        .iter()
+        .filter(move |(keyword, _)| lower.contains(keyword))
+        .flat_map(|(_, opposites)| opposites.iter().copied())
+}
+
+/// Keywords a rule must hold to conflict with `lower` (as the second rule).
+fn provoking_terms(lower: &str) -> impl Iterator<Item = &'static str> + '_ {
+    CONFLICT_KEYWORDS
+        .iter()
+        .filter(move |(_, opposites)| opposites.iter().any(|o| lower.contains(o)))
+        .map(|(keyword, _)| *keyword)
+}
+
+/// Every keyword and opposite that [`CONFLICT_KEYWORDS`] mentions.
+fn indexed_terms() -> impl Iterator<Item = &'static str> {
+    CONFLICT_KEYWORDS
+        .iter()
+        .flat_map(|(keyword, opposites)| std::iter::once(*keyword).chain(opposites.iter().copied()))
+}
+
+/// Changes made while adding a constitution, for rolling back a rejected
+/// one. Only the modes that never remove merged rules can reject.
+#[derive(Debug, Default)]
+struct Undo {
+    first_slot: usize,
+    sources: Vec<(String, Option<String>)>,
+    seen: Vec<(String, Option<String>)>,
+}
+
+// ── ComposerSession ─────────────────────────────────────────
+
+/// Merged state for a growing or shrinking set of constitutions.
+///
+/// Conflict hooks are not consulted; use [`Composer::with_conflict_hooks`]
+/// for a one-off composition that needs them.
+#[derive(Debug, Clone)]
+pub struct ComposerSession {
+    mode: CompositionMode,
+    constitutions: Vec<Constitution>,
+    /// Merged rules in order; `None` marks a rule removed by an override.
+    slots: Vec<Option<Entry>>,
+    /// Conflict keyword -> slots of the rules containing it.
+    index: HashMap<&'static str, BTreeSet<usize>>,
+    /// Rule text -> source, as tracked by [`CompositionMode::Extend`].
+    sources: HashMap<String, String>,
+    /// Lowercased rule -> source, as tracked by [`CompositionMode::Strict`].
+    seen: HashMap<String, String>,
+    conflicts: Vec<Conflict>,
+    warnings: Vec<String>,
+}
+
+impl ComposerSession {
+    /// Create an empty session composing in `mode`.
+    #[must_use]
+    pub fn new(mode: CompositionMode) -> Self {
+        Self {
+            mode,
+            constitutions: Vec::new(),
+            slots: Vec::new(),
+            index: HashMap::new(),
+            sources: HashMap::new(),
+            seen: HashMap::new(),
+            conflicts: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// The composition mode.
+    pub fn mode(&self) -> CompositionMode {
+        self.mode
+    }
+
+    /// The constitutions currently composed, in the order they were added.
+    pub fn constitutions(&self) -> &[Constitution] {
+        &self.constitutions
+    }
+
+    /// Number of merged rules.
+    pub fn rule_count(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+
+    /// Merge `constitution` after the ones already added.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CompositionError`] with the constitution's conflicts when
+    /// the mode rejects them ([`CompositionMode::Extend`] and
+    /// [`CompositionMode::Strict`]). The session is left as it was.
+    pub fn add_constitution(&mut self, constitution: Constitution) -> Result<(), CompositionError> {
+        let composer = Composer::new();
+        match self.mode {
+            CompositionMode::Base if self.constitutions.is_empty() => {
+                for index in 0..constitution.rules.len() {
+                    self.push(MergedRule::of(&constitution, index));
+                }
+            }
+            CompositionMode::Base => {
+                let base_id = self.constitutions[0].id.clone();
+                for (index, rule) in constitution.rules.iter().enumerate() {
+                    match self.first_conflict(&composer, rule, &constitution.id, &base_id) {
+                        Some(conflict) => self.conflicts.push(conflict),
+                        None => self.push(MergedRule::of(&constitution, index)),
+                    }
+                }
+            }
+            CompositionMode::Override => self.add_override(&composer, &constitution),
+            CompositionMode::Extend | CompositionMode::Strict => {
+                let mut undo = Undo {
+                    first_slot: self.slots.len(),
+                    ..Undo::default()
+                };
+                let conflicts = self.add_checked(&composer, &constitution, &mut undo);
+                if !conflicts.is_empty() {
+                    self.rollback(undo);
+                    return Err(CompositionError { conflicts });
+                }
+            }
+        }
+        self.constitutions.push(constitution);
+        Ok(())
+    }
+
+    /// Remove the constitution with `id`, returning it.
+    ///
+    /// Rules it overrode or blocked are restored by replaying the
+    /// remaining constitutions.
+    pub fn remove_constitution(&mut self, id: &str) -> Option<Constitution> {
+        let position = self.constitutions.iter().position(|c| c.id == id)?;
+        let mut remaining = std::mem::take(&mut self.constitutions);
+        let removed = remaining.remove(position);
+
+        *self = Self::new(self.mode);
+        for constitution in remaining {
+            // Dropping a constitution only removes candidates for conflicts,
+            // so every one that was accepted before is accepted again.
+            let accepted = self.add_constitution(constitution).is_ok();
+            debug_assert!(accepted, "replayed constitution was rejected");
+        }
+        Some(removed)
+    }
+
+    /// The composition result for the current constitutions.
+    pub fn result(&self) -> CompositionResult {
+        CompositionResult {
+            merged_rules: self
+                .slots
+                .iter()
+                .flatten()
+                .map(|e| e.rule.clone())
+                .collect(),
+            conflicts: self.conflicts.clone(),
+            warnings: self.warnings.clone(),
+            mode_used: self.mode,
+        }
+    }
+
+    // ── Mode handling ───────────────────────────────────────
+
+    /// EXTEND and STRICT: collect conflicts, merging the rules that have
+    /// none so later rules in the same constitution are checked against
+    /// them.
+    fn add_checked(
+        &mut self,
+        composer: &Composer<'_>,
+        constitution: &Constitution,
+        undo: &mut Undo,
+    ) -> Vec<Conflict> {
+        let strict = self.mode == CompositionMode::Strict;
+        let mut conflicts = Vec::new();
+
+        for (index, rule) in constitution.rules.iter().enumerate() {
+            let normalized = rule.to_lowercase();
+
+            if strict {
+                if let Some(source) = self.seen.get(&normalized) {
+                    conflicts.push(Conflict {
+                        rule_a: rule.clone(),
+                        source_a: constitution.id.clone(),
+                        rule_b: rule.clone(),
+                        source_b: source.clone(),
+                        conflict_type: "duplicate".to_string(),
+                        resolution: None,
+                    });
+                    continue;
+                }
+            }
+
+            let existing_source = if strict {
+                "earlier".to_string()
+            } else {
+                self.sources
+                    .get(rule)
+                    .cloned()
+                    .unwrap_or_else(|| "unknown".to_string())
+            };
+            if let Some(conflict) =
+                self.first_conflict(composer, rule, &constitution.id, &existing_source)
+            {
+                conflicts.push(conflict);
+                continue;
+            }
+
+            self.push(MergedRule::of(constitution, index));
+            if strict {
+                let previous = self
+                    .seen
+                    .insert(normalized.clone(), constitution.id.clone());
+                undo.seen.push((normalized, previous));
+            } else {
+                let previous = self.sources.insert(rule.clone(), constitution.id.clone());
+                undo.sources.push((rule.clone(), previous));
+            }
+        }
+        conflicts
+    }
+
+    /// OVERRIDE: incoming rules replace every merged rule they conflict with.
+    fn add_override(&mut self, composer: &Composer<'_>, constitution: &Constitution) {
+        for (index, rule) in constitution.rules.iter().enumerate() {
+            let lower = rule.to_lowercase();
+            let overridden: Vec<usize> = self
+                .candidates(&lower, provoking_terms(&lower))
+                .into_iter()
+                .filter(|&slot| {
+                    let existing = &self.entry(slot).rule.text;
+                    composer.rules_conflict(existing, rule)
+                })
+                .collect();
+
+            let mut entry = MergedRule::of(constitution, index);
+            if !overridden.is_empty() {
+                let mut origins = Vec::with_capacity(overridden.len());
+                for &slot in &overridden {
+                    let replaced = self.remove_slot(slot);
+                    self.warnings.push(format!(
+                        "Rule '{}' ({}) overrides '{}'",
+                        rule, constitution.id, replaced.text
+                    ));
+                    origins.push(replaced.origin());
+                }
+                entry.overridden = Some(origins);
+            }
+            self.push(entry);
+        }
+    }
+
+    // ── Index ───────────────────────────────────────────────
+
+    /// The first merged rule (in merge order) that `rule` conflicts with.
+    fn first_conflict(
+        &self,
+        composer: &Composer<'_>,
+        rule: &str,
+        source: &str,
+        existing_source: &str,
+    ) -> Option<Conflict> {
+        let lower = rule.to_lowercase();
+        self.candidates(&lower, opposing_terms(&lower))
+            .into_iter()
+            .map(|slot| self.entry(slot).rule.text.as_str())
+            .find(|existing| composer.rules_conflict(rule, existing))
+            .map(|existing| Conflict {
+                rule_a: rule.to_string(),
+                source_a: source.to_string(),
+                rule_b: existing.to_string(),
+                source_b: existing_source.to_string(),
+                conflict_type: composer.determine_conflict_type(rule, existing),
+                resolution: None,
+            })
+    }
+
+    /// Live slots holding one of `terms` and sharing enough topic words
+    /// with `lower`, in merge order.
+    fn candidates(&self, lower: &str, terms: impl Iterator<Item = &'static str>) -> Vec<usize> {
+        let words = topic_words(lower);
+        let mut slots = BTreeSet::new();
+        for term in terms {
+            slots.extend(self.index.get(term).into_iter().flatten().copied());
+        }
+        slots
+            .into_iter()
+            .filter(|&slot| self.entry(slot).shares_topic(&words))
+            .collect()
+    }
+
+    fn entry(&self, slot: usize) -> &Entry {
+        self.slots[slot]
+            .as_ref()
+            .expect("index only holds live slots")
+    }
+
+    fn push(&mut self, rule: MergedRule) {
+        let slot = self.slots.len();
+        let lower = rule.text.to_lowercase();
+        for term in indexed_terms().filter(|t| lower.contains(t)) {
+            self.index.entry(term).or_default().insert(slot);
+        }
+        let words = topic_words(&lower).into_iter().map(String::from).collect();
+        self.slots.push(Some(Entry { rule, lower, words }));
+    }
+
+    fn remove_slot(&mut self, slot: usize) -> MergedRule {
+        let entry = self.slots[slot].take().expect("slot is live");
+        for term in indexed_terms().filter(|t| entry.lower.contains(t)) {
+            if let Some(slots) = self.index.get_mut(term) {
+                slots.remove(&slot);
+            }
+        }
+        entry.rule
+    }
+
+    fn rollback(&mut self, undo: Undo) {
+        for slot in (undo.first_slot..self.slots.len()).rev() {
+            self.remove_slot(slot);
+        }
+        self.slots.truncate(undo.first_slot);
+        for (key, previous) in undo.sources.into_iter().rev() {
+            restore(&mut self.sources, key, previous);
+        }
+        for (key, previous) in undo.seen.into_iter().rev() {
+            restore(&mut self.seen, key, previous);
+        }
+    }
+}
+
+fn restore(map: &mut HashMap<String, String>, key: String, previous: Option<String>) {
+    match previous {
+        Some(value) => map.insert(key, value),
+        None => map.remove(&key),
+    };
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Constitution> {
+        vec![
+            Constitution::new(
+                "a",
+                vec![
+                    "Always collect user tracking data.".into(),
+                    "Be kind to animals.".into(),
+                    "Must log user activity.".into(),
+                ],
+                0,
+            ),
+            Constitution::new(
+                "b",
+                vec![
+                    "Never collect user tracking data.".into(),
+                    "Respect human dignity.".into(),
+                ],
+                1,
+            ),
+            Constitution::new(
+                "c",
+                vec![
+                    "Must not log user activity.".into(),
+                    "be kind to animals.".into(),
+                ],
+                2,
+            ),
+        ]
+    }
+
+    fn session_with(mode: CompositionMode, constitutions: &[Constitution]) -> ComposerSession {
+        let mut session = ComposerSession::new(mode);
+        for c in constitutions {
+            let _ = session.add_constitution(c.clone());
+        }
+        session
+    }
+
+    #[test]
+    fn matches_full_composition_in_lenient_modes() {
+        for mode in [CompositionMode::Base, CompositionMode::Override] {
+            let expected = Composer::new().compose(&sample(), mode).unwrap();
+            assert_eq!(session_with(mode, &sample()).result(), expected, "{mode}");
+        }
+    }
+
+    #[test]
+    fn rejected_constitution_leaves_session_unchanged() {
+        for mode in [CompositionMode::Extend, CompositionMode::Strict] {
+            let mut session = ComposerSession::new(mode);
+            let all = sample();
+            session.add_constitution(all[0].clone()).unwrap();
+            let before = session.result();
+
+            let err = session.add_constitution(all[1].clone()).unwrap_err();
+            let full = Composer::new().compose(&all[..2], mode).unwrap_err();
+            assert_eq!(err.conflicts, full.conflicts, "{mode}");
+            assert_eq!(session.result(), before);
+            assert_eq!(session.constitutions().len(), 1);
+        }
+    }
+
+    #[test]
+    fn removal_restores_overridden_rules() {
+        let mut session = session_with(CompositionMode::Override, &sample());
+        assert!(session.remove_constitution("b").is_some());
+        assert!(session.remove_constitution("missing").is_none());
+
+        let remaining = [sample()[0].clone(), sample()[2].clone()];
+        let expected = Composer::new()
+            .compose(&remaining, CompositionMode::Override)
+            .unwrap();
+        assert_eq!(session.result(), expected);
+        assert!(session
+            .result()
+            .rule_texts()
+            .contains(&"Always collect user tracking data."));
+    }
+
+    #[test]
+    fn matches_full_composition_on_larger_input() {
+        let base: Vec<String> = (0..200)
+            .map(|i| format!("always cite source {i} when discussing topic {}", i % 7))
+            .collect();
+        let overlay: Vec<String> = (0..200)
+            .map(|i| {
+                if i % 10 == 0 {
+                    format!("never cite source {i} when discussing topic {}", i % 7)
+                } else {
+                    format!("prefer plain language for audience {i} in region {}", i % 5)
+                }
+            })
+            .collect();
+        let input = [
+            Constitution::new("base", base, 0),
+            Constitution::new("overlay", overlay, 1),
+        ];
+        for mode in [CompositionMode::Base, CompositionMode::Override] {
+            let expected = Composer::new().compose(&input, mode).unwrap();
+            assert_eq!(session_with(mode, &input).result(), expected, "{mode}");
+        }
+    }
+}
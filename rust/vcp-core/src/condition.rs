@@ -0,0 +1,593 @@
+//! Applicability conditions: when a rule is active, given the context.
+//!
+//! A [`Condition`] is a small boolean expression over a [`FullContext`].
+//! Attach one to a constitution rule with
+//! [`Constitution::with_rule_condition`](crate::composer::Constitution::with_rule_condition);
+//! it travels onto the [`MergedRule`](crate::composer::MergedRule), and
+//! [`CompositionResult::filter_for_context`](crate::composer::CompositionResult::filter_for_context)
+//! drops inactive rules before the result is rendered.
+//!
+//! | Expression | True when |
+//! |------------|-----------|
+//! | `situational.space == school` | A `space` tag is `school`, by name or emoji |
+//! | `personal.urgency >= 4` | Urgency is set with intensity 4 or more (`==`, `!=`, `<`, `<=`, `>`, `>=`) |
+//! | `personal.cognitive == focused` | The cognitive state's value is `focused` |
+//! | `personal.body` | The dimension is set (situational too) |
+//! | `a and b`, `a or b`, `not a`, `( ... )` | As usual; `not` binds tightest, then `and` |
+//!
+//! Situational fields use the dimension labels (`system_context`), with
+//! `location` accepted for `space`. Personal fields take the labels
+//! (`perceived_urgency`) or the short names (`urgency`). Values containing
+//! spaces or operators are double-quoted. A comparison on an unset
+//! dimension is false, except `!=`, which is its negation.
+//!
+//! Conditions serialize as their expression string.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::condition::Condition;
+//! use vcp_core::context::FullContext;
+//!
+//! let condition: Condition = "situational.space == school or personal.urgency >= 4"
+//!     .parse()
+//!     .unwrap();
+//! assert!(condition.evaluate(&FullContext::from_wire("📍🏫").unwrap()));
+//! assert!(condition.evaluate(&FullContext::from_wire("‖⚡pressured:4").unwrap()));
+//! assert!(!condition.evaluate(&FullContext::from_wire("📍🏡‖⚡pressured:2").unwrap()));
+//! assert_eq!(
+//!     condition.to_string(),
+//!     "situational.space == school or personal.perceived_urgency >= 4"
+//! );
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::FullContext;
+use crate::error::{VcpError, VcpResult};
+use crate::personal::PersonalDimensionKind;
+use crate::situational::SituationalDimension;
+
+// ── Expression ──────────────────────────────────────────────
+
+/// A context dimension a condition reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    /// Tags of a situational dimension.
+    Situational(SituationalDimension),
+    /// Value and intensity of a personal dimension.
+    Personal(PersonalDimensionKind),
+}
+
+/// A comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    fn holds(self, left: u8, right: u8) -> bool {
+        match self {
+            Self::Eq => left == right,
+            Self::Ne => left != right,
+            Self::Lt => left < right,
+            Self::Le => left <= right,
+            Self::Gt => left > right,
+            Self::Ge => left >= right,
+        }
+    }
+}
+
+/// The right-hand side of a comparison.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Value {
+    /// A situational tag or personal value.
+    Tag(String),
+    /// A personal intensity, 1-5.
+    Intensity(u8),
+}
+
+/// A boolean expression over a [`FullContext`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Condition {
+    /// The dimension is set.
+    Present(Field),
+    /// The dimension compared with a value.
+    Compare {
+        field: Field,
+        op: CompareOp,
+        value: Value,
+    },
+    Not(Box<Condition>),
+    /// Every condition holds.
+    All(Vec<Condition>),
+    /// At least one condition holds.
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    /// Parse an expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for malformed expressions
+    /// (including unterminated quotes and nesting deeper than
+    /// [`MAX_CONDITION_DEPTH`]), unknown fields, ordering comparisons on
+    /// anything but a personal intensity, and intensities outside 1-5.
+    pub fn parse(s: &str) -> VcpResult<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            pos: 0,
+            depth: 0,
+        };
+        if parser.tokens.is_empty() {
+            return Err(VcpError::ParseError("empty condition".into()));
+        }
+        let condition = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(condition),
+            Some(token) => Err(VcpError::ParseError(format!(
+                "unexpected {token} in condition: {s}"
+            ))),
+        }
+    }
+
+    /// Whether the condition holds in `context`.
+    pub fn evaluate(&self, context: &FullContext) -> bool {
+        match self {
+            Self::Present(Field::Situational(dim)) => context
+                .situational
+                .get(*dim)
+                .is_some_and(|tags| !tags.is_empty()),
+            Self::Present(Field::Personal(kind)) => context.personal.get(*kind).is_some(),
+            Self::Compare { field, op, value } => {
+                let equal = match (field, value) {
+                    (Field::Situational(dim), Value::Tag(tag)) => {
+                        context.situational.get(*dim).is_some_and(|tags| {
+                            tags.iter()
+                                .any(|t| t == tag || dim.tag_name(t) == Some(tag.as_str()))
+                        })
+                    }
+                    (Field::Personal(kind), Value::Tag(tag)) => context
+                        .personal
+                        .get(*kind)
+                        .is_some_and(|dim| dim.value == *tag),
+                    (Field::Personal(kind), Value::Intensity(n)) => {
+                        return match context.personal.get(*kind) {
+                            Some(dim) => op.holds(dim.intensity, *n),
+                            None => *op == CompareOp::Ne,
+                        };
+                    }
+                    // Rejected by the parser.
+                    (Field::Situational(_), Value::Intensity(_)) => false,
+                };
+                equal == (*op == CompareOp::Eq)
+            }
+            Self::Not(inner) => !inner.evaluate(context),
+            Self::All(all) => all.iter().all(|c| c.evaluate(context)),
+            Self::Any(any) => any.iter().any(|c| c.evaluate(context)),
+        }
+    }
+}
+
+// ── Display ─────────────────────────────────────────────────
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Situational(dim) => write!(f, "situational.{dim}"),
+            Self::Personal(kind) => write!(f, "personal.{kind}"),
+        }
+    }
+}
+
+impl fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        })
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Intensity(n) => write!(f, "{n}"),
+            Self::Tag(tag) if is_bare_word(tag) => f.write_str(tag),
+            Self::Tag(tag) => write!(f, "\"{tag}\""),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Parenthesize children that bind more loosely than their parent.
+        let child = |f: &mut fmt::Formatter<'_>, c: &Condition, loose: fn(&Condition) -> bool| {
+            if loose(c) {
+                write!(f, "({c})")
+            } else {
+                write!(f, "{c}")
+            }
+        };
+        let join = |f: &mut fmt::Formatter<'_>, list: &[Condition], sep: &str| {
+            for (i, c) in list.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(sep)?;
+                }
+                child(f, c, |c| matches!(c, Self::Any(_)))?;
+            }
+            Ok(())
+        };
+        match self {
+            Self::Present(field) => write!(f, "{field}"),
+            Self::Compare { field, op, value } => write!(f, "{field} {op} {value}"),
+            Self::Not(inner) => {
+                f.write_str("not ")?;
+                child(f, inner, |c| matches!(c, Self::All(_) | Self::Any(_)))
+            }
+            Self::All(all) => join(f, all, " and "),
+            Self::Any(any) => {
+                for (i, c) in any.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" or ")?;
+                    }
+                    write!(f, "{c}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = VcpError;
+
+    fn from_str(s: &str) -> VcpResult<Self> {
+        Self::parse(s)
+    }
+}
+
+impl From<Condition> for String {
+    fn from(condition: Condition) -> Self {
+        condition.to_string()
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = VcpError;
+
+    fn try_from(s: String) -> VcpResult<Self> {
+        Self::parse(&s)
+    }
+}
+
+// ── Parsing ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Op(CompareOp),
+    Word(String),
+    Quoted(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => f.write_str("`(`"),
+            Self::Close => f.write_str("`)`"),
+            Self::And => f.write_str("`and`"),
+            Self::Or => f.write_str("`or`"),
+            Self::Not => f.write_str("`not`"),
+            Self::Op(op) => write!(f, "`{op}`"),
+            Self::Word(w) => write!(f, "`{w}`"),
+            Self::Quoted(w) => write!(f, "\"{w}\""),
+        }
+    }
+}
+
+/// Deepest nesting of parentheses and `not` a condition may use.
+pub const MAX_CONDITION_DEPTH: usize = 32;
+
+const SPECIAL: &[char] = &['(', ')', '=', '!', '<', '>', '"', '&', '|'];
+
+fn is_bare_word(s: &str) -> bool {
+    !s.is_empty()
+        && !s.chars().any(|c| c.is_whitespace() || SPECIAL.contains(&c))
+        && !["and", "or", "not"].contains(&s.to_ascii_lowercase().as_str())
+}
+
+fn tokenize(s: &str) -> VcpResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut next_is = |expected: char| chars.next_if_eq(&expected).is_some();
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '=' if next_is('=') => Token::Op(CompareOp::Eq),
+            '!' if next_is('=') => Token::Op(CompareOp::Ne),
+            '!' => Token::Not,
+            '<' if next_is('=') => Token::Op(CompareOp::Le),
+            '<' => Token::Op(CompareOp::Lt),
+            '>' if next_is('=') => Token::Op(CompareOp::Ge),
+            '>' => Token::Op(CompareOp::Gt),
+            '&' if next_is('&') => Token::And,
+            '|' if next_is('|') => Token::Or,
+            '"' => {
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => quoted.push(c),
+                        None => {
+                            return Err(VcpError::ParseError(format!(
+                                "unterminated quote in condition: {s}"
+                            )))
+                        }
+                    }
+                }
+                Token::Quoted(quoted)
+            }
+            c if SPECIAL.contains(&c) => {
+                return Err(VcpError::ParseError(format!(
+                    "unexpected `{c}` in condition: {s}"
+                )))
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !SPECIAL.contains(c)) {
+                    word.push(c);
+                }
+                match word.to_ascii_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Word(word),
+                }
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Parentheses and `not`s enclosing the current position.
+    depth: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.tokens.get(self.pos) == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> VcpResult<Condition> {
+        let mut any = vec![self.and()?];
+        while self.eat(&Token::Or) {
+            any.push(self.and()?);
+        }
+        Ok(if any.len() == 1 {
+            any.remove(0)
+        } else {
+            Condition::Any(any)
+        })
+    }
+
+    fn and(&mut self) -> VcpResult<Condition> {
+        let mut all = vec![self.unary()?];
+        while self.eat(&Token::And) {
+            all.push(self.unary()?);
+        }
+        Ok(if all.len() == 1 {
+            all.remove(0)
+        } else {
+            Condition::All(all)
+        })
+    }
+
+    /// Enter a `not` or parenthesis, failing past [`MAX_CONDITION_DEPTH`].
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> VcpResult<T>) -> VcpResult<T> {
+        if self.depth == MAX_CONDITION_DEPTH {
+            return Err(VcpError::ParseError(format!(
+                "condition nests deeper than {MAX_CONDITION_DEPTH}"
+            )));
+        }
+        self.depth += 1;
+        let parsed = parse(self);
+        self.depth -= 1;
+        parsed
+    }
+
+    fn unary(&mut self) -> VcpResult<Condition> {
+        match self.next() {
+            Some(Token::Not) => Ok(Condition::Not(Box::new(self.nested(Self::unary)?))),
+            Some(Token::Open) => {
+                let inner = self.nested(Self::or)?;
+                if self.eat(&Token::Close) {
+                    Ok(inner)
+                } else {
+                    Err(VcpError::ParseError("unclosed `(` in condition".into()))
+                }
+            }
+            Some(Token::Word(path)) => self.comparison(parse_field(&path)?),
+            Some(token) => Err(VcpError::ParseError(format!(
+                "expected a field, found {token}"
+            ))),
+            None => Err(VcpError::ParseError(
+                "condition ends where a field was expected".into(),
+            )),
+        }
+    }
+
+    fn comparison(&mut self, field: Field) -> VcpResult<Condition> {
+        let Some(Token::Op(op)) = self.tokens.get(self.pos).cloned() else {
+            return Ok(Condition::Present(field));
+        };
+        self.pos += 1;
+        let value = match (self.next(), field) {
+            (Some(Token::Word(word)), Field::Personal(_)) if word.parse::<u8>().is_ok() => {
+                let n: u8 = word.parse().unwrap_or_default();
+                if !(1..=5).contains(&n) {
+                    return Err(VcpError::ParseError(format!(
+                        "intensity must be 1-5, got: {n}"
+                    )));
+                }
+                Value::Intensity(n)
+            }
+            (Some(Token::Word(word) | Token::Quoted(word)), _) => Value::Tag(word),
+            (Some(token), _) => {
+                return Err(VcpError::ParseError(format!(
+                    "expected a value after `{op}`, found {token}"
+                )))
+            }
+            (None, _) => return Err(VcpError::ParseError(format!("condition ends after `{op}`"))),
+        };
+        if !matches!(op, CompareOp::Eq | CompareOp::Ne) && !matches!(value, Value::Intensity(_)) {
+            return Err(VcpError::ParseError(format!(
+                "`{op}` compares personal intensities only: {field} {op} {value}"
+            )));
+        }
+        Ok(Condition::Compare { field, op, value })
+    }
+}
+
+fn parse_field(path: &str) -> VcpResult<Field> {
+    let unknown = || VcpError::ParseError(format!("unknown condition field: {path}"));
+    match path.split_once('.') {
+        Some(("situational", "location")) => Ok(Field::Situational(SituationalDimension::Space)),
+        Some(("situational", label)) => SituationalDimension::from_label(label)
+            .map(Field::Situational)
+            .ok_or_else(unknown),
+        Some(("personal", name)) => {
+            let kind = match name {
+                "cognitive" => Some(PersonalDimensionKind::CognitiveState),
+                "emotional" => Some(PersonalDimensionKind::EmotionalTone),
+                "energy" => Some(PersonalDimensionKind::EnergyLevel),
+                "urgency" => Some(PersonalDimensionKind::PerceivedUrgency),
+                "body" => Some(PersonalDimensionKind::BodySignals),
+                label => PersonalDimensionKind::from_label(label),
+            };
+            kind.map(Field::Personal).ok_or_else(unknown)
+        }
+        _ => Err(unknown()),
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holds(condition: &str, wire: &str) -> bool {
+        Condition::parse(condition)
+            .unwrap()
+            .evaluate(&FullContext::from_wire(wire).unwrap())
+    }
+
+    #[test]
+    fn evaluates_comparisons() {
+        assert!(holds("situational.location == school", "📍🏫"));
+        assert!(holds("situational.space == \"🏫\"", "📍🏫"));
+        assert!(!holds("situational.space == school", ""));
+        assert!(holds("situational.space != school", ""));
+        assert!(holds("personal.cognitive == focused", "‖🧠focused:2"));
+        assert!(holds("personal.cognitive_state <= 2", "‖🧠focused:2"));
+        assert!(!holds("personal.urgency > 1", ""));
+        assert!(holds("personal.urgency != 3", ""));
+        assert!(holds("personal.body", "‖🩺pain:4"));
+        assert!(!holds("situational.time", "📍🏫"));
+    }
+
+    #[test]
+    fn combinators_and_precedence() {
+        let c = "not situational.space == home and (personal.urgency >= 4 || personal.energy)";
+        assert!(holds(c, "📍🏫‖⚡critical:5"));
+        assert!(!holds(c, "📍🏡‖⚡critical:5"));
+        assert!(!holds(c, "📍🏫‖⚡pressured:3"));
+
+        let condition = Condition::parse(c).unwrap();
+        assert_eq!(
+            condition.to_string(),
+            "not situational.space == home and (personal.perceived_urgency >= 4 or personal.energy_level)"
+        );
+        assert_eq!(Condition::parse(&condition.to_string()).unwrap(), condition);
+
+        let json = serde_json::to_value(&condition).unwrap();
+        assert!(json.is_string());
+        assert_eq!(
+            serde_json::from_value::<Condition>(json).unwrap(),
+            condition
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for bad in [
+            "",
+            "situational.mood == calm",
+            "personal.urgency >= 9",
+            "situational.space > 2",
+            "personal.cognitive < focused",
+            "(personal.body",
+            "personal.body ==",
+            "personal.body and",
+            "personal.body personal.energy",
+            "situational.space = school",
+            "situational.space == \"school",
+            "situational.space == \"",
+        ] {
+            assert!(Condition::parse(bad).is_err(), "{bad}");
+        }
+        let err = Condition::parse("situational.space == \"school").unwrap_err();
+        assert!(err.to_string().contains("unterminated quote"), "{err}");
+    }
+
+    #[test]
+    fn nesting_depth_is_capped() {
+        let nested =
+            |depth: usize| format!("{}personal.body{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(Condition::parse(&nested(MAX_CONDITION_DEPTH)).is_ok());
+        let err = Condition::parse(&nested(MAX_CONDITION_DEPTH + 1)).unwrap_err();
+        assert!(err.to_string().contains("nests deeper"), "{err}");
+
+        let negated = format!("{}personal.body", "not ".repeat(MAX_CONDITION_DEPTH + 1));
+        assert!(Condition::parse(&negated).is_err());
+
+        // Far too deep to recurse through: still an error, not a stack overflow.
+        assert!(Condition::parse(&nested(100_000)).is_err());
+        assert!(Condition::parse(&"!".repeat(100_000)).is_err());
+    }
+}
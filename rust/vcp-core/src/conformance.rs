@@ -0,0 +1,932 @@
+//! Runner for the shared cross-SDK conformance vectors.
+//!
+//! The repository's `conformance/` directory holds language-agnostic JSON
+//! fixtures that every SDK runs to prove it agrees with the others
+//! byte for byte. Each fixture file names its `suite` and lists vectors
+//! with an input and an `expected` object:
+//!
+//! ```json
+//! {
+//!   "suite": "identity/token_parsing",
+//!   "version": "1.0.0",
+//!   "vectors": [
+//!     { "id": "token-001", "input": "family.safe.guide",
+//!       "expected": { "valid": true, "domain": "family", "depth": 3 } }
+//!   ]
+//! }
+//! ```
+//!
+//! For every suite it recognises, the runner computes the same fields
+//! from this crate and compares only the keys present in `expected`
+//! (`note` is informational). Keys the runner cannot compute are listed
+//! as unchecked rather than failed, and a vector with nothing checkable
+//! is skipped. A vector whose inputs this crate rejects outright counts
+//! as a failure.
+//!
+//! | Suite | Checks |
+//! |-------|--------|
+//! | `identity/token_parsing` | Parse tree, parent, URI, patterns, hierarchy |
+//! | `semantics/csm1_parsing` | Persona, adherence, scopes, namespace, version |
+//! | `semantics/csm1_encoding` | Nano/micro encodings and re-parse |
+//! | `transport/content_canonicalization` | Canonical bytes and rejections |
+//! | `transport/content_hashing` | Hashes, equality, verification |
+//! | `transport/manifest_canonicalization` | Canonical JSON and signature exclusion |
+//! | `transport/signature_verification` | Seeded Ed25519 sign/verify procedures |
+//! | `adaptation/context_encoding*` | Wire encoding, flags and round-trips |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::conformance::{run_suite, Suite};
+//!
+//! let suite: Suite = serde_json::from_str(r#"{
+//!     "suite": "transport/content_hashing",
+//!     "vectors": [{
+//!         "id": "hash-001",
+//!         "input": "Hello, World!\n",
+//!         "expected": {
+//!             "hash": "sha256:c98c24b677eff44860afea6f493bbaec5bb1c4cbb209c6fc2bbb47f66ff2ad31"
+//!         }
+//!     }]
+//! }"#).unwrap();
+//!
+//! let report = run_suite(&suite);
+//! assert_eq!(report.passed(), 1);
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::context::{FullContext, WIRE_SEPARATOR};
+use crate::csm1::Csm1Code;
+use crate::error::{VcpError, VcpResult};
+use crate::identity::VcpToken;
+use crate::transport::{
+    canonicalize_content, canonicalize_manifest, compute_content_hash, sign_manifest,
+    verify_content_hash, verify_manifest_signature,
+};
+
+// ── Fixtures ────────────────────────────────────────────────
+
+/// One conformance fixture file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Suite {
+    /// Suite name, `<layer>/<topic>`.
+    pub suite: String,
+    /// Fixture version.
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    pub vectors: Vec<Vector>,
+    /// File the suite was loaded from, if any.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// A single test vector.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vector {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    /// Expected outputs; only these keys are compared.
+    #[serde(default)]
+    pub expected: Map<String, Value>,
+    /// Inputs (`input`, `input_a`, `procedure`, `seed_byte`, ...).
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+impl Vector {
+    fn field(&self, name: &str) -> Option<&Value> {
+        self.fields.get(name)
+    }
+
+    fn str_field(&self, name: &str) -> Option<&str> {
+        self.field(name).and_then(Value::as_str)
+    }
+
+    fn expected_str(&self, name: &str) -> Option<&str> {
+        self.expected.get(name).and_then(Value::as_str)
+    }
+}
+
+/// Load a single fixture file.
+///
+/// # Errors
+///
+/// Returns [`VcpError::IoError`] if the file cannot be read, or
+/// [`VcpError::JsonError`] if it is not a suite fixture.
+pub fn load_suite(path: &Path) -> VcpResult<Suite> {
+    let text = fs::read_to_string(path)?;
+    let mut suite: Suite = serde_json::from_str(&text)?;
+    suite.path = Some(path.to_path_buf());
+    Ok(suite)
+}
+
+/// Load every suite fixture under `dir`, recursively, in path order.
+///
+/// JSON files that are not suite fixtures (no top-level `suite` and
+/// `vectors`) are ignored, since other fixture formats share the tree.
+///
+/// # Errors
+///
+/// Returns [`VcpError::IoError`] if the directory cannot be walked, or
+/// [`VcpError::JsonError`] if a file is not valid JSON or a suite
+/// fixture is malformed.
+pub fn load_dir(dir: &Path) -> VcpResult<Vec<Suite>> {
+    let mut files = Vec::new();
+    collect_json_files(dir, &mut files)?;
+    files.sort();
+
+    let mut suites = Vec::new();
+    for file in files {
+        let value: Value = serde_json::from_str(&fs::read_to_string(&file)?)?;
+        if value.get("suite").is_none() || value.get("vectors").is_none() {
+            continue;
+        }
+        let mut suite: Suite = serde_json::from_value(value)?;
+        suite.path = Some(file);
+        suites.push(suite);
+    }
+    Ok(suites)
+}
+
+fn collect_json_files(dir: &Path, out: &mut Vec<PathBuf>) -> VcpResult<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_json_files(&path, out)?;
+        } else if path.extension().is_some_and(|e| e == "json") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+// ── Results ─────────────────────────────────────────────────
+
+/// A field whose computed value differs from the fixture.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mismatch {
+    pub field: String,
+    pub expected: Value,
+    pub actual: Value,
+}
+
+/// Outcome of a single vector.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum VectorStatus {
+    /// Every checked field matched.
+    Passed,
+    /// At least one checked field differed.
+    Failed { mismatches: Vec<Mismatch> },
+    /// The vector's inputs could not be read (missing or rejected).
+    Errored { message: String },
+    /// Nothing in `expected` could be checked.
+    Skipped { reason: String },
+}
+
+/// Result for one vector.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VectorResult {
+    pub id: String,
+    #[serde(flatten)]
+    pub status: VectorStatus,
+    /// Expected keys this runner does not compute.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unchecked: Vec<String>,
+}
+
+/// Results for one suite.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SuiteReport {
+    pub suite: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    pub results: Vec<VectorResult>,
+}
+
+impl SuiteReport {
+    fn count(&self, f: impl Fn(&VectorStatus) -> bool) -> usize {
+        self.results.iter().filter(|r| f(&r.status)).count()
+    }
+
+    pub fn passed(&self) -> usize {
+        self.count(|s| matches!(s, VectorStatus::Passed))
+    }
+
+    /// Failed plus errored vectors.
+    pub fn failed(&self) -> usize {
+        self.count(|s| {
+            matches!(
+                s,
+                VectorStatus::Failed { .. } | VectorStatus::Errored { .. }
+            )
+        })
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.count(|s| matches!(s, VectorStatus::Skipped { .. }))
+    }
+
+    /// Failed and errored vectors.
+    pub fn failures(&self) -> impl Iterator<Item = &VectorResult> {
+        self.results.iter().filter(|r| {
+            matches!(
+                r.status,
+                VectorStatus::Failed { .. } | VectorStatus::Errored { .. }
+            )
+        })
+    }
+}
+
+/// Results for a whole vectors directory.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConformanceReport {
+    pub suites: Vec<SuiteReport>,
+}
+
+impl ConformanceReport {
+    pub fn passed(&self) -> usize {
+        self.suites.iter().map(SuiteReport::passed).sum()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.suites.iter().map(SuiteReport::failed).sum()
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.suites.iter().map(SuiteReport::skipped).sum()
+    }
+
+    /// `true` if no vector failed.
+    pub fn is_conformant(&self) -> bool {
+        self.failed() == 0
+    }
+
+    /// `suite:id` for every failed vector.
+    pub fn failed_ids(&self) -> Vec<String> {
+        self.suites
+            .iter()
+            .flat_map(|s| s.failures().map(move |r| format!("{}:{}", s.suite, r.id)))
+            .collect()
+    }
+}
+
+// ── Running ─────────────────────────────────────────────────
+
+/// Load and run every suite under `dir`.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be loaded; see [`load_dir`].
+pub fn run_dir(dir: &Path) -> VcpResult<ConformanceReport> {
+    let suites = load_dir(dir)?;
+    Ok(ConformanceReport {
+        suites: suites.iter().map(run_suite).collect(),
+    })
+}
+
+/// Run every vector in a suite.
+pub fn run_suite(suite: &Suite) -> SuiteReport {
+    let observe: Option<fn(&Vector) -> Observed> = match suite.suite.as_str() {
+        "identity/token_parsing" => Some(observe_token),
+        "semantics/csm1_parsing" => Some(observe_csm1_parse),
+        "semantics/csm1_encoding" => Some(observe_csm1_encode),
+        "transport/content_canonicalization" => Some(observe_content_canonical),
+        "transport/content_hashing" => Some(observe_content_hash),
+        "transport/manifest_canonicalization" => Some(observe_manifest_canonical),
+        "transport/signature_verification" => Some(observe_signature),
+        "adaptation/context_encoding" | "adaptation/context_encoding_extended" => {
+            Some(observe_context)
+        }
+        _ => None,
+    };
+
+    let results = suite
+        .vectors
+        .iter()
+        .map(|v| match observe {
+            Some(observe) => match observe(v) {
+                Ok(actual) => compare(v, &actual),
+                Err(message) => VectorResult {
+                    id: v.id.clone(),
+                    status: VectorStatus::Errored { message },
+                    unchecked: Vec::new(),
+                },
+            },
+            None => VectorResult {
+                id: v.id.clone(),
+                status: VectorStatus::Skipped {
+                    reason: format!("no runner for suite {}", suite.suite),
+                },
+                unchecked: Vec::new(),
+            },
+        })
+        .collect();
+
+    SuiteReport {
+        suite: suite.suite.clone(),
+        path: suite.path.clone(),
+        results,
+    }
+}
+
+/// Fields computed for a vector, or why its inputs could not be used.
+type Observed = Result<Map<String, Value>, String>;
+
+fn compare(vector: &Vector, actual: &Map<String, Value>) -> VectorResult {
+    let mut mismatches = Vec::new();
+    let mut unchecked = Vec::new();
+    let mut checked = 0;
+
+    for (key, expected) in &vector.expected {
+        if key == "note" {
+            continue;
+        }
+        match actual.get(key) {
+            Some(value) => {
+                checked += 1;
+                if value != expected {
+                    mismatches.push(Mismatch {
+                        field: key.clone(),
+                        expected: expected.clone(),
+                        actual: value.clone(),
+                    });
+                }
+            }
+            None => unchecked.push(key.clone()),
+        }
+    }
+
+    let status = if !mismatches.is_empty() {
+        VectorStatus::Failed { mismatches }
+    } else if checked == 0 {
+        VectorStatus::Skipped {
+            reason: "no checkable expectations".into(),
+        }
+    } else {
+        VectorStatus::Passed
+    };
+
+    VectorResult {
+        id: vector.id.clone(),
+        status,
+        unchecked,
+    }
+}
+
+/// Substring expectations (`wire_contains` and friends) report the
+/// needle when the check holds and the whole haystack when it does not.
+fn needle_check(haystack: &str, needle: &str, holds: bool) -> Value {
+    if holds {
+        Value::from(needle)
+    } else {
+        Value::from(haystack)
+    }
+}
+
+// ── Identity ────────────────────────────────────────────────
+
+fn observe_token(v: &Vector) -> Observed {
+    let mut out = Map::new();
+
+    if let (Some(a), Some(d)) = (v.str_field("ancestor"), v.str_field("descendant")) {
+        let a = VcpToken::parse(a).map_err(|e| format!("ancestor rejected: {e}"))?;
+        let d = VcpToken::parse(d).map_err(|e| format!("descendant rejected: {e}"))?;
+        out.insert("is_ancestor".into(), a.is_ancestor_of(&d).into());
+        out.insert("is_descendant".into(), d.is_descendant_of(&a).into());
+        out.insert("reverse_is_ancestor".into(), d.is_ancestor_of(&a).into());
+        return Ok(out);
+    }
+
+    let input = v.str_field("input").ok_or("missing input")?;
+    let Ok(token) = VcpToken::parse(input) else {
+        out.insert("valid".into(), false.into());
+        return Ok(out);
+    };
+
+    out.insert("valid".into(), true.into());
+    out.insert("domain".into(), token.domain().into());
+    out.insert("approach".into(), token.approach().into());
+    out.insert("role".into(), token.role().into());
+    out.insert("path".into(), json!(token.path()));
+    out.insert("depth".into(), token.depth().into());
+    out.insert("namespace".into(), json!(token.namespace));
+    out.insert(
+        "version".into(),
+        json!(token.version.as_ref().map(ToString::to_string)),
+    );
+    if let Some(ver) = &token.version {
+        out.insert("version_major".into(), ver.major.into());
+        out.insert("version_minor".into(), ver.minor.into());
+        out.insert("version_patch".into(), ver.patch.into());
+    }
+    out.insert("canonical_display".into(), token.full().into());
+    let parent = token.parent();
+    out.insert("has_parent".into(), parent.is_some().into());
+    out.insert(
+        "parent_canonical".into(),
+        json!(parent.map(|p| p.canonical())),
+    );
+    out.insert(
+        "uri_with_registry_creed_space".into(),
+        token.to_uri("creed.space").into(),
+    );
+    if let Some(pattern) = v.str_field("pattern") {
+        out.insert("matches".into(), token.matches_pattern(pattern).into());
+    }
+    Ok(out)
+}
+
+// ── Semantics ───────────────────────────────────────────────
+
+fn observe_csm1_parse(v: &Vector) -> Observed {
+    let mut out = Map::new();
+    let input = v.str_field("input").ok_or("missing input")?;
+    let Ok(code) = Csm1Code::parse(input) else {
+        out.insert("valid".into(), false.into());
+        return Ok(out);
+    };
+
+    out.insert("valid".into(), true.into());
+    out.insert("persona".into(), code.persona.to_string().into());
+    out.insert(
+        "persona_name".into(),
+        format!("{:?}", code.persona).to_lowercase().into(),
+    );
+    out.insert("adherence".into(), code.adherence_level.into());
+    out.insert(
+        "scopes".into(),
+        json!(code
+            .scopes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()),
+    );
+    out.insert("namespace".into(), json!(code.namespace));
+    out.insert("version".into(), json!(code.version));
+    Ok(out)
+}
+
+fn observe_csm1_encode(v: &Vector) -> Observed {
+    // Nano form drops namespace and version; micro form keeps them.
+    fn nano(code: &Csm1Code) -> String {
+        Csm1Code {
+            namespace: None,
+            version: None,
+            ..code.clone()
+        }
+        .encode()
+    }
+
+    let mut out = Map::new();
+    let input = v.str_field("input").ok_or("missing input")?;
+    let Ok(code) = Csm1Code::parse(input) else {
+        out.insert("parse_succeeds".into(), false.into());
+        return Ok(out);
+    };
+
+    let nano_form = nano(&code);
+    out.insert("parse_succeeds".into(), true.into());
+    out.insert("to_micro".into(), code.encode().into());
+    if let Ok(reparsed) = Csm1Code::parse(&nano_form) {
+        out.insert("reparse_to_nano".into(), nano(&reparsed).into());
+    }
+    out.insert("to_nano".into(), nano_form.into());
+    Ok(out)
+}
+
+// ── Transport ───────────────────────────────────────────────
+
+fn observe_content_canonical(v: &Vector) -> Observed {
+    let mut out = Map::new();
+    let input = v.str_field("input").ok_or("missing input")?;
+    match canonicalize_content(input) {
+        Ok(bytes) => {
+            out.insert("valid".into(), true.into());
+            out.insert(
+                "canonical".into(),
+                String::from_utf8_lossy(&bytes).into_owned().into(),
+            );
+        }
+        Err(_) => {
+            out.insert("valid".into(), false.into());
+        }
+    }
+    Ok(out)
+}
+
+fn observe_content_hash(v: &Vector) -> Observed {
+    let mut out = Map::new();
+
+    if let (Some(a), Some(b)) = (v.str_field("input_a"), v.str_field("input_b")) {
+        let ha = compute_content_hash(a).map_err(|e| format!("input_a rejected: {e}"))?;
+        let hb = compute_content_hash(b).map_err(|e| format!("input_b rejected: {e}"))?;
+        out.insert("hashes_equal".into(), (ha == hb).into());
+        return Ok(out);
+    }
+
+    let input = v.str_field("input").ok_or("missing input")?;
+    let hash = compute_content_hash(input).map_err(|e| format!("input rejected: {e}"))?;
+
+    let claimed = match v.str_field("claimed_hash") {
+        Some(claimed) => Some(claimed.to_string()),
+        None if v.str_field("procedure") == Some("compute_hash_then_verify") => Some(hash.clone()),
+        None => None,
+    };
+    if let Some(claimed) = claimed {
+        if let Ok(ok) = verify_content_hash(input, &claimed) {
+            out.insert("verification_result".into(), ok.into());
+        }
+    }
+
+    if let Some((prefix, hex)) = hash.split_once(':') {
+        out.insert("hash_prefix".into(), format!("{prefix}:").into());
+        out.insert("hash_hex_length".into(), hex.len().into());
+    }
+    out.insert("hash".into(), hash.into());
+    Ok(out)
+}
+
+fn observe_manifest_canonical(v: &Vector) -> Observed {
+    let mut out = Map::new();
+    let input = v.field("input").ok_or("missing input")?;
+    let bytes = canonicalize_manifest(input).map_err(|e| format!("input rejected: {e}"))?;
+    let canonical = String::from_utf8_lossy(&bytes).into_owned();
+
+    let reparsed: Value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    let has_signature = reparsed.get("signature").is_some();
+    if input.get("signature").is_some() {
+        out.insert("signature_excluded".into(), (!has_signature).into());
+    }
+    out.insert("canonical_contains_signature".into(), has_signature.into());
+    out.insert(
+        "canonical_contains_bundle".into(),
+        reparsed.get("bundle").is_some().into(),
+    );
+    out.insert("canonical_json".into(), canonical.into());
+    Ok(out)
+}
+
+fn seeded_key(byte: u64) -> Option<SigningKey> {
+    u8::try_from(byte)
+        .ok()
+        .map(|b| SigningKey::from_bytes(&[b; 32]))
+}
+
+#[allow(clippy::too_many_lines)]
+fn observe_signature(v: &Vector) -> Observed {
+    let mut out = Map::new();
+    let procedure = v.str_field("procedure").ok_or("missing procedure")?;
+    let seed = v
+        .field("seed_byte")
+        .and_then(Value::as_u64)
+        .and_then(seeded_key);
+    let manifest = v.field("manifest").cloned().unwrap_or(Value::Null);
+
+    let sign = |key: &SigningKey, m: &Value| sign_manifest(m, &key.to_bytes());
+    let verify = |key: &SigningKey, m: &Value, sig: &str| {
+        verify_manifest_signature(m, &key.verifying_key().to_bytes(), sig).unwrap_or(false)
+    };
+
+    match procedure {
+        "generate_keypair_from_seed" => {
+            if let Some(key) = seed {
+                let sig = sign(&key, &manifest);
+                out.insert("sign_succeeds".into(), sig.is_ok().into());
+                if let Ok(sig) = sig {
+                    out.insert(
+                        "verify_with_correct_key".into(),
+                        verify(&key, &manifest, &sig).into(),
+                    );
+                    out.insert("signature".into(), sig.into());
+                }
+            }
+        }
+        "sign_with_seed_verify_with_different_seed" => {
+            let signer = v
+                .field("sign_seed_byte")
+                .and_then(Value::as_u64)
+                .and_then(seeded_key);
+            let verifier = v
+                .field("verify_seed_byte")
+                .and_then(Value::as_u64)
+                .and_then(seeded_key);
+            if let (Some(signer), Some(verifier)) = (signer, verifier) {
+                let sig = sign(&signer, &manifest);
+                out.insert("sign_succeeds".into(), sig.is_ok().into());
+                if let Ok(sig) = sig {
+                    out.insert(
+                        "verify_with_wrong_key".into(),
+                        verify(&verifier, &manifest, &sig).into(),
+                    );
+                }
+            }
+        }
+        "sign_original_verify_tampered" => {
+            if let (Some(key), Some(original), Some(tampered)) = (
+                seed,
+                v.field("original_manifest"),
+                v.field("tampered_manifest"),
+            ) {
+                let sig = sign(&key, original);
+                out.insert("sign_succeeds".into(), sig.is_ok().into());
+                if let Ok(sig) = sig {
+                    out.insert(
+                        "verify_tampered".into(),
+                        verify(&key, tampered, &sig).into(),
+                    );
+                }
+            }
+        }
+        "sign_without_sig_verify_with_sig" => {
+            if let (Some(key), Some(signing), Some(verifying)) = (
+                seed,
+                v.field("manifest_for_signing"),
+                v.field("manifest_for_verification"),
+            ) {
+                let sig = sign(&key, signing);
+                out.insert("sign_succeeds".into(), sig.is_ok().into());
+                if let Ok(sig) = sig {
+                    out.insert(
+                        "verify_with_sig_field_present".into(),
+                        verify(&key, verifying, &sig).into(),
+                    );
+                }
+            }
+        }
+        "sign_and_verify_with_base64_prefix" => {
+            if let Some(key) = seed {
+                let sig = sign(&key, &manifest);
+                out.insert("sign_succeeds".into(), sig.is_ok().into());
+                if let Ok(sig) = sig {
+                    out.insert(
+                        "raw_sig_verifies".into(),
+                        verify(&key, &manifest, &sig).into(),
+                    );
+                    out.insert(
+                        "prefixed_sig_verifies".into(),
+                        verify(&key, &manifest, &format!("base64:{sig}")).into(),
+                    );
+                }
+            }
+        }
+        "sign_twice_compare" => {
+            if let Some(key) = seed {
+                if let (Ok(a), Ok(b)) = (sign(&key, &manifest), sign(&key, &manifest)) {
+                    out.insert("signatures_equal".into(), (a == b).into());
+                }
+            }
+        }
+        "sign_with_short_key" => {
+            let len = v.field("key_bytes").and_then(Value::as_u64).unwrap_or(0);
+            let key = vec![0u8; usize::try_from(len).unwrap_or(0)];
+            let result = sign_manifest(&manifest, &key);
+            out.insert("error".into(), result.is_err().into());
+            if let (Err(e), Some(needle)) = (result, v.expected_str("error_message_contains")) {
+                let msg = e.to_string();
+                out.insert(
+                    "error_message_contains".into(),
+                    needle_check(&msg, needle, msg.contains(needle)),
+                );
+            }
+        }
+        "verify_with_short_key" => {
+            let len = v.field("key_bytes").and_then(Value::as_u64).unwrap_or(0);
+            let key = vec![0u8; usize::try_from(len).unwrap_or(0)];
+            let sig = v.str_field("signature").unwrap_or_default();
+            out.insert(
+                "error".into(),
+                verify_manifest_signature(&manifest, &key, sig)
+                    .is_err()
+                    .into(),
+            );
+        }
+        "verify_with_invalid_base64" => {
+            if let Some(key) = seed {
+                let sig = v.str_field("signature").unwrap_or_default();
+                let result =
+                    verify_manifest_signature(&manifest, &key.verifying_key().to_bytes(), sig);
+                out.insert("error".into(), result.is_err().into());
+            }
+        }
+        _ => {}
+    }
+    Ok(out)
+}
+
+// ── Adaptation ──────────────────────────────────────────────
+
+fn observe_context(v: &Vector) -> Observed {
+    let mut out = Map::new();
+    let ctx = if let Some(wire) = v.str_field("wire_input") {
+        FullContext::from_wire(wire)
+    } else if let Some(input) = v.field("input") {
+        serde_json::from_value::<FullContext>(input.clone()).map_err(VcpError::from)
+    } else {
+        return Err("missing input".into());
+    };
+    let ctx = ctx.map_err(|e| format!("input rejected: {e}"))?;
+
+    let wire = ctx.to_wire();
+    let has_separator = wire.contains(WIRE_SEPARATOR);
+    out.insert("has_any".into(), ctx.has_any().into());
+    out.insert("has_situational".into(), ctx.situational.has_any().into());
+    out.insert("has_personal".into(), ctx.personal.has_any().into());
+    out.insert("has_vep_0004".into(), ctx.situational.has_vep_0004().into());
+    out.insert(
+        "conformance_level".into(),
+        ctx.conformance_level().label().into(),
+    );
+    out.insert("separator_u2016_present".into(), has_separator.into());
+    out.insert("wire_contains_separator_u2016".into(), has_separator.into());
+    out.insert(
+        "personal_cognitive_value".into(),
+        json!(ctx.personal.cognitive.as_ref().map(|d| &d.value)),
+    );
+    if let Some(needle) = v.expected_str("wire_contains") {
+        out.insert(
+            "wire_contains".into(),
+            needle_check(&wire, needle, wire.contains(needle)),
+        );
+    }
+    if let Some(needle) = v.expected_str("wire_does_not_contain") {
+        out.insert(
+            "wire_does_not_contain".into(),
+            needle_check(&wire, needle, !wire.contains(needle)),
+        );
+    }
+    if let Some(needle) = v.expected_str("wire_starts_with") {
+        out.insert(
+            "wire_starts_with".into(),
+            needle_check(&wire, needle, wire.starts_with(needle)),
+        );
+    }
+
+    if let Ok(back) = FullContext::from_wire(&wire) {
+        let sit = (&back.situational, &ctx.situational);
+        out.insert(
+            "roundtrip_preserves_time".into(),
+            (sit.0.time == sit.1.time).into(),
+        );
+        out.insert(
+            "roundtrip_preserves_space".into(),
+            (sit.0.space == sit.1.space).into(),
+        );
+        out.insert(
+            "roundtrip_preserves_relationship_compound".into(),
+            (sit.0.relationship == sit.1.relationship).into(),
+        );
+        out.insert(
+            "roundtrip_cognitive_value".into(),
+            json!(back.personal.cognitive.as_ref().map(|d| &d.value)),
+        );
+        out.insert(
+            "roundtrip_emotional_value".into(),
+            json!(back.personal.emotional.as_ref().map(|d| &d.value)),
+        );
+    }
+    if let Ok(json) = serde_json::to_value(&ctx) {
+        let back = serde_json::from_value::<FullContext>(json).ok();
+        out.insert(
+            "json_roundtrip".into(),
+            (back.as_ref() == Some(&ctx)).into(),
+        );
+    }
+    out.insert("wire".into(), wire.into());
+    Ok(out)
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suite(name: &str, vectors: Value) -> Suite {
+        let mut raw = json!({ "suite": name });
+        raw["vectors"] = vectors;
+        serde_json::from_value(raw).unwrap()
+    }
+
+    #[test]
+    fn token_vectors_pass_and_fail() {
+        let s = suite(
+            "identity/token_parsing",
+            json!([
+                { "id": "ok", "input": "family.safe.guide@1.2.0",
+                  "expected": { "valid": true, "domain": "family", "version_major": 1,
+                                "note": "ignored" } },
+                { "id": "bad", "input": "family.safe.guide",
+                  "expected": { "valid": true, "depth": 4 } },
+                { "id": "err", "input": "one.two",
+                  "expected": { "valid": false, "error": "too_few_segments" } },
+            ]),
+        );
+        let report = run_suite(&s);
+        assert_eq!((report.passed(), report.failed()), (2, 1));
+
+        let bad = &report.results[1];
+        assert_eq!(
+            bad.status,
+            VectorStatus::Failed {
+                mismatches: vec![Mismatch {
+                    field: "depth".into(),
+                    expected: json!(4),
+                    actual: json!(3),
+                }]
+            }
+        );
+        assert_eq!(report.results[2].unchecked, vec!["error".to_string()]);
+    }
+
+    #[test]
+    fn unknown_suites_and_keys_are_skipped() {
+        let s = suite(
+            "semantics/composition",
+            json!([{ "id": "c", "expected": { "result": "ok" } }]),
+        );
+        assert_eq!(run_suite(&s).skipped(), 1);
+
+        let s = suite(
+            "transport/content_hashing",
+            json!([{ "id": "h", "input": "x", "expected": { "mystery": 1 } }]),
+        );
+        let report = run_suite(&s);
+        assert_eq!(report.skipped(), 1);
+        assert_eq!(report.results[0].unchecked, vec!["mystery".to_string()]);
+    }
+
+    #[test]
+    fn signature_procedures_run() {
+        let s = suite(
+            "transport/signature_verification",
+            json!([
+                { "id": "s1", "procedure": "generate_keypair_from_seed", "seed_byte": 1,
+                  "manifest": { "bundle": { "id": "t" } },
+                  "expected": { "sign_succeeds": true, "verify_with_correct_key": true } },
+                { "id": "s2", "procedure": "sign_with_short_key", "key_bytes": 16,
+                  "manifest": { "bundle": { "id": "t" } },
+                  "expected": { "error": true, "error_message_contains": "32 bytes" } },
+            ]),
+        );
+        let report = run_suite(&s);
+        assert_eq!(report.passed(), 2, "{report:?}");
+    }
+
+    #[test]
+    fn pinned_signature_is_checked() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let manifest = json!({ "bundle": { "id": "golden" } });
+        let sig = sign_manifest(&manifest, &key.to_bytes()).unwrap();
+
+        let s = suite(
+            "transport/signature_verification",
+            json!([{ "id": "g", "procedure": "generate_keypair_from_seed", "seed_byte": 7,
+                     "manifest": manifest, "expected": { "signature": sig } }]),
+        );
+        assert_eq!(run_suite(&s).passed(), 1);
+    }
+
+    #[test]
+    fn context_needles_report_the_wire() {
+        let s = suite(
+            "adaptation/context_encoding",
+            json!([{ "id": "c", "input": { "situational": { "time": ["\u{1F305}"] }, "personal": {} },
+                     "expected": { "wire_contains": "\u{23F0}", "wire_starts_with": "|" } }]),
+        );
+        let report = run_suite(&s);
+        let VectorStatus::Failed { mismatches } = &report.results[0].status else {
+            panic!("expected failure: {report:?}");
+        };
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].field, "wire_starts_with");
+        assert_eq!(mismatches[0].actual, json!("\u{23F0}\u{1F305}"));
+    }
+
+    #[test]
+    fn load_dir_ignores_other_formats() {
+        let dir = std::env::temp_dir().join(format!("vcp-conformance-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(
+            dir.join("nested/a.json"),
+            r#"{"suite":"transport/content_hashing","vectors":[]}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("b.json"), r#"{"test_cases":[]}"#).unwrap();
+        fs::write(dir.join("c.txt"), "not json").unwrap();
+
+        let suites = load_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(suites.len(), 1);
+        assert!(suites[0].path.as_ref().unwrap().ends_with("nested/a.json"));
+    }
+}
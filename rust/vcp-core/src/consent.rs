@@ -0,0 +1,355 @@
+//! Consent metadata for shared contexts.
+//!
+//! A [`Consent`] records what the user agreed to when sharing a context:
+//! the purposes it may be used for, when the agreement lapses, which
+//! dimensions it covers and where to check whether it was withdrawn. It
+//! rides on [`FullContext::consent`] and in an extension segment of
+//! either wire format:
+//!
+//! | Format | Consent segment |
+//! |--------|-----------------|
+//! | Emoji | `📜purposes=support,expires=2026-11-01T00:00:00Z,situational=time;⏰🌅` |
+//! | ASCII | `ctx1;consent=purposes=support,expires=...,situational=time;time=morning` |
+//!
+//! [`FullContext::from_wire`] reads the segment but does not enforce it.
+//! A consumer that must honour consent parses with
+//! [`FullContext::from_wire_strict`], declaring its purpose; parsing then
+//! fails with [`VcpError::ConsentError`] unless the consent allows that
+//! purpose, has not expired and covers every dimension present.
+//! Withdrawal is not checked here: the SDK makes no network calls, so
+//! resolve [`Consent::revocation`] yourself.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::consent::Consent;
+//! use vcp_core::context::FullContext;
+//!
+//! let mut ctx = FullContext::from_wire("⏰🌅|📍🏡‖🧠focused:4").unwrap();
+//! ctx.consent = Some(Consent::new(&["support"]).covering(&ctx));
+//! let wire = ctx.to_wire();
+//!
+//! assert!(FullContext::from_wire_strict(&wire, "support").is_ok());
+//! let err = FullContext::from_wire_strict(&wire, "advertising").unwrap_err();
+//! assert_eq!(err.code(), "ConsentError");
+//! ```
+
+use std::fmt::Write as _;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::context::{ascii_escape, ascii_unescape, FullContext};
+use crate::error::{VcpError, VcpResult};
+use crate::personal::PersonalDimensionKind;
+use crate::situational::SituationalDimension;
+
+/// What a user agreed to when sharing a context.
+///
+/// The dimension lists are the scope of the agreement: a context carrying
+/// a dimension outside them fails [`check`](Self::check).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Consent {
+    /// Purposes the context may be used for (e.g. `"support"`).
+    pub purposes: Vec<String>,
+    /// When the consent lapses; `None` for no expiry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Situational dimensions the consent covers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub situational: Vec<SituationalDimension>,
+    /// Personal dimensions the consent covers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub personal: Vec<PersonalDimensionKind>,
+    /// Where to check whether the consent was withdrawn (a URI or receipt
+    /// identifier).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revocation: Option<String>,
+}
+
+impl Consent {
+    /// Consent for `purposes`, covering no dimensions yet.
+    pub fn new(purposes: &[&str]) -> Self {
+        Self {
+            purposes: purposes.iter().map(ToString::to_string).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the expiry.
+    #[must_use]
+    pub fn with_expiry(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Also cover a situational dimension.
+    #[must_use]
+    pub fn with_situational(mut self, dim: SituationalDimension) -> Self {
+        if !self.situational.contains(&dim) {
+            self.situational.push(dim);
+        }
+        self
+    }
+
+    /// Also cover a personal dimension.
+    #[must_use]
+    pub fn with_personal(mut self, kind: PersonalDimensionKind) -> Self {
+        if !self.personal.contains(&kind) {
+            self.personal.push(kind);
+        }
+        self
+    }
+
+    /// Also cover every dimension `ctx` carries.
+    #[must_use]
+    pub fn covering(mut self, ctx: &FullContext) -> Self {
+        for &dim in SituationalDimension::all() {
+            if ctx.situational.get(dim).is_some() {
+                self = self.with_situational(dim);
+            }
+        }
+        for &kind in PersonalDimensionKind::all() {
+            if ctx.personal.get(kind).is_some() {
+                self = self.with_personal(kind);
+            }
+        }
+        self
+    }
+
+    /// Set the revocation reference.
+    #[must_use]
+    pub fn with_revocation(mut self, reference: impl Into<String>) -> Self {
+        self.revocation = Some(reference.into());
+        self
+    }
+
+    /// `true` if the consent allows use for `purpose`.
+    pub fn allows_purpose(&self, purpose: &str) -> bool {
+        self.purposes.iter().any(|p| p == purpose)
+    }
+
+    /// `true` if the consent has lapsed at `now`.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|exp| now >= exp)
+    }
+
+    /// Check that `ctx` may be used for `purpose` now.
+    ///
+    /// # Errors
+    ///
+    /// See [`check_at`](Self::check_at).
+    pub fn check(&self, ctx: &FullContext, purpose: &str) -> VcpResult<()> {
+        self.check_at(ctx, purpose, Utc::now())
+    }
+
+    /// Check that `ctx` may be used for `purpose` at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ConsentError`] if `purpose` is not allowed, the
+    /// consent has expired, or `ctx` carries a dimension it does not cover.
+    pub fn check_at(&self, ctx: &FullContext, purpose: &str, now: DateTime<Utc>) -> VcpResult<()> {
+        if !self.allows_purpose(purpose) {
+            return Err(VcpError::ConsentError(format!(
+                "purpose '{purpose}' is not consented to (allowed: {})",
+                self.purposes.join(", ")
+            )));
+        }
+        if let Some(exp) = self.expires_at.filter(|_| self.is_expired_at(now)) {
+            return Err(VcpError::ConsentError(format!(
+                "consent expired at {}",
+                exp.to_rfc3339_opts(SecondsFormat::Secs, true)
+            )));
+        }
+        let uncovered: Vec<String> = SituationalDimension::all()
+            .iter()
+            .filter(|dim| ctx.situational.get(**dim).is_some() && !self.situational.contains(dim))
+            .map(ToString::to_string)
+            .chain(
+                PersonalDimensionKind::all()
+                    .iter()
+                    .filter(|kind| {
+                        ctx.personal.get(**kind).is_some() && !self.personal.contains(kind)
+                    })
+                    .map(ToString::to_string),
+            )
+            .collect();
+        if !uncovered.is_empty() {
+            return Err(VcpError::ConsentError(format!(
+                "consent does not cover {}",
+                uncovered.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    // ── Wire segment ────────────────────────────────────────
+
+    /// Encode as the ASCII-safe body of a wire extension segment:
+    /// `key=value` fields joined by `,`, list items joined by `+`.
+    pub(crate) fn to_segment(&self) -> String {
+        let list = |items: Vec<String>| {
+            items
+                .iter()
+                .map(|item| ascii_escape(item, &[]))
+                .collect::<Vec<_>>()
+                .join("+")
+        };
+        let mut out = format!("purposes={}", list(self.purposes.clone()));
+        if let Some(exp) = self.expires_at {
+            let _ = write!(
+                out,
+                ",expires={}",
+                exp.to_rfc3339_opts(SecondsFormat::Secs, true)
+            );
+        }
+        if !self.situational.is_empty() {
+            let labels = self.situational.iter().map(ToString::to_string).collect();
+            let _ = write!(out, ",situational={}", list(labels));
+        }
+        if !self.personal.is_empty() {
+            let labels = self.personal.iter().map(ToString::to_string).collect();
+            let _ = write!(out, ",personal={}", list(labels));
+        }
+        if let Some(reference) = &self.revocation {
+            let _ = write!(out, ",revocation={}", ascii_escape(reference, &[':', '/']));
+        }
+        out
+    }
+
+    /// Parse the body written by [`to_segment`](Self::to_segment).
+    pub(crate) fn from_segment(segment: &str) -> VcpResult<Self> {
+        let mut consent = Self::default();
+        for field in segment.split(',') {
+            let (key, value) = field.split_once('=').ok_or_else(|| {
+                VcpError::ParseError(format!("expected <field>=<value> in consent, got: {field}"))
+            })?;
+            let items = || -> VcpResult<Vec<String>> {
+                value
+                    .split('+')
+                    .filter(|item| !item.is_empty())
+                    .map(ascii_unescape)
+                    .collect()
+            };
+            match key {
+                "purposes" => consent.purposes = items()?,
+                "expires" => {
+                    let exp = DateTime::parse_from_rfc3339(value).map_err(|e| {
+                        VcpError::ParseError(format!("invalid consent expiry '{value}': {e}"))
+                    })?;
+                    consent.expires_at = Some(exp.with_timezone(&Utc));
+                }
+                "situational" => {
+                    for label in items()? {
+                        let dim = SituationalDimension::from_label(&label).ok_or_else(|| {
+                            VcpError::ParseError(format!("unknown situational dimension: {label}"))
+                        })?;
+                        consent = consent.with_situational(dim);
+                    }
+                }
+                "personal" => {
+                    for label in items()? {
+                        let kind = PersonalDimensionKind::from_label(&label).ok_or_else(|| {
+                            VcpError::ParseError(format!("unknown personal dimension: {label}"))
+                        })?;
+                        consent = consent.with_personal(kind);
+                    }
+                }
+                "revocation" => consent.revocation = Some(ascii_unescape(value)?),
+                _ => {
+                    return Err(VcpError::ParseError(format!(
+                        "unknown consent field: {key}"
+                    )))
+                }
+            }
+        }
+        if consent.purposes.is_empty() {
+            return Err(VcpError::ParseError(
+                "consent names no purposes".to_string(),
+            ));
+        }
+        Ok(consent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WireFormat;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()
+    }
+
+    fn shared() -> FullContext {
+        let mut ctx = FullContext::from_wire("⏰🌅|📍🏡‖🧠focused:4|💭calm:3").unwrap();
+        ctx.consent = Some(
+            Consent::new(&["support", "analytics+research"])
+                .covering(&ctx)
+                .with_expiry(Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap())
+                .with_revocation("https://consent.example.com/r/42"),
+        );
+        ctx
+    }
+
+    #[test]
+    fn consent_round_trips_in_both_wire_formats() {
+        let ctx = shared();
+        assert_eq!(
+            ctx.to_wire(),
+            "📜purposes=support+analytics%2Bresearch,expires=2026-11-01T00:00:00Z,\
+             situational=time+space,personal=cognitive_state+emotional_tone,\
+             revocation=https://consent.example.com/r/42;⏰🌅|📍🏡‖🧠focused:4|💭calm:3"
+        );
+        for format in [WireFormat::Emoji, WireFormat::Ascii] {
+            let wire = ctx.to_wire_as(format);
+            assert_eq!(FullContext::from_wire(&wire).unwrap(), ctx, "{wire}");
+        }
+
+        let redacted = ctx.redacted(&crate::privacy::RedactionProfile::minimal());
+        let wire = redacted.to_ascii_wire();
+        assert!(
+            wire.starts_with("ctx1;redacted=minimal;consent=purposes="),
+            "{wire}"
+        );
+        assert_eq!(FullContext::from_wire(&wire).unwrap(), redacted);
+        assert_eq!(
+            FullContext::from_wire(&redacted.to_wire()).unwrap(),
+            redacted
+        );
+
+        assert!(FullContext::from_wire("📜expires=2026-11-01T00:00:00Z;⏰🌅").is_err());
+        assert!(FullContext::from_wire("📜purposes=support,color=red;⏰🌅").is_err());
+    }
+
+    #[test]
+    fn strict_parsing_enforces_purpose_expiry_and_scope() {
+        let wire = shared().to_wire();
+        let parse = |purpose: &str, at: DateTime<Utc>| {
+            FullContext::from_wire_strict_at(&wire, purpose, at).map_err(|e| e.to_string())
+        };
+
+        assert!(parse("support", now()).is_ok());
+        assert!(parse("analytics+research", now()).is_ok());
+        assert!(parse("advertising", now())
+            .unwrap_err()
+            .contains("'advertising'"));
+        let late = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+        assert!(parse("support", late).unwrap_err().contains("expired"));
+
+        // Dimensions added after consent was given are not covered.
+        let mut ctx = shared();
+        ctx.situational.company = Some(vec!["👶".into()]);
+        let err = FullContext::from_wire_strict_at(&ctx.to_wire(), "support", now()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "consent error: consent does not cover company"
+        );
+
+        let bare = FullContext::from_wire_strict_at("⏰🌅", "support", now()).unwrap_err();
+        assert_eq!(bare.code(), "ConsentError");
+        assert!(FullContext::from_wire("⏰🌅").is_ok());
+    }
+}
@@ -0,0 +1,532 @@
+//! Structural and style checks for constitution text.
+//!
+//! [`Orchestrator::scan_for_injection`](crate::orchestrator::Orchestrator::scan_for_injection)
+//! looks for hostile content; [`lint_content`] looks for rules that are
+//! hard to apply consistently. Rules are taken line by line as in
+//! [`diff::extract_rules`](crate::diff::extract_rules): each list item or
+//! paragraph line outside headings and code fences.
+//!
+//! | Rule | Default | Finds |
+//! |------|---------|-------|
+//! | `numbering` | warning | Numbered lists that skip or repeat a number, or mix `1.` and `1)` |
+//! | `ambiguous_modal` | warning | `should`, `could`, `might`, ... where `must` or `may` is meant |
+//! | `overlong_rule` | warning | Rules longer than [`ContentLintConfig::max_rule_words`] |
+//! | `missing_scope` | warning | No heading naming the scope the constitution applies to |
+//! | `duplicate_rule` | error | A rule repeated, ignoring case, punctuation and list markers |
+//!
+//! Each rule can be raised to an error or turned off, and the word lists
+//! replaced, through a [`ContentLintConfig`], which loads from JSON:
+//!
+//! ```json
+//! { "rules": { "ambiguous_modal": "error", "missing_scope": "off" }, "max_rule_words": 30 }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::content_lint::{lint_content, ContentLintConfig, ContentRule};
+//!
+//! let text = "# Rules\n1. You should cite sources.\n3. Never invent quotes.\n";
+//! let report = lint_content(text, &ContentLintConfig::default());
+//! let rules: Vec<ContentRule> = report.issues.iter().map(|i| i.rule).collect();
+//! assert_eq!(
+//!     rules,
+//!     [ContentRule::MissingScope, ContentRule::AmbiguousModal, ContentRule::Numbering]
+//! );
+//! assert!(!report.has_errors());
+//! assert!(lint_content(text, &ContentLintConfig::default().strict()).has_errors());
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::context_schema::Severity;
+use crate::error::VcpResult;
+
+// ── Rules ───────────────────────────────────────────────────
+
+/// One check [`lint_content`] can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentRule {
+    /// Numbered list items out of sequence or in mixed styles.
+    Numbering,
+    /// A modal verb that leaves it unclear whether a rule is binding.
+    AmbiguousModal,
+    /// A rule too long to apply at a glance.
+    OverlongRule,
+    /// No section saying where the constitution applies.
+    MissingScope,
+    /// The same rule stated twice.
+    DuplicateRule,
+}
+
+impl ContentRule {
+    /// Every rule, in the order findings are reported within a line.
+    pub const ALL: [Self; 5] = [
+        Self::Numbering,
+        Self::AmbiguousModal,
+        Self::OverlongRule,
+        Self::MissingScope,
+        Self::DuplicateRule,
+    ];
+
+    /// The level used when a config does not set one.
+    pub fn default_level(self) -> RuleLevel {
+        match self {
+            Self::DuplicateRule => RuleLevel::Error,
+            _ => RuleLevel::Warning,
+        }
+    }
+}
+
+impl fmt::Display for ContentRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Numbering => "numbering",
+            Self::AmbiguousModal => "ambiguous_modal",
+            Self::OverlongRule => "overlong_rule",
+            Self::MissingScope => "missing_scope",
+            Self::DuplicateRule => "duplicate_rule",
+        })
+    }
+}
+
+/// Whether a [`ContentRule`] runs, and how serious its findings are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleLevel {
+    /// The rule does not run.
+    Off,
+    /// Findings are warnings.
+    Warning,
+    /// Findings are errors.
+    Error,
+}
+
+impl RuleLevel {
+    fn severity(self) -> Option<Severity> {
+        match self {
+            Self::Off => None,
+            Self::Warning => Some(Severity::Warning),
+            Self::Error => Some(Severity::Error),
+        }
+    }
+}
+
+// ── Config ──────────────────────────────────────────────────
+
+/// Which rules run, at what level, and their thresholds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentLintConfig {
+    /// Levels overriding [`ContentRule::default_level`].
+    pub rules: BTreeMap<ContentRule, RuleLevel>,
+    /// Rules with more words than this are overlong.
+    pub max_rule_words: usize,
+    /// Words flagged by `ambiguous_modal`, lowercase.
+    pub ambiguous_words: Vec<String>,
+    /// A heading containing any of these (case-insensitive) is a scope
+    /// section.
+    pub scope_headings: Vec<String>,
+}
+
+impl Default for ContentLintConfig {
+    fn default() -> Self {
+        let strings = |words: &[&str]| words.iter().map(|w| (*w).to_string()).collect();
+        Self {
+            rules: BTreeMap::new(),
+            max_rule_words: 40,
+            ambiguous_words: strings(&[
+                "should",
+                "could",
+                "might",
+                "ought",
+                "ideally",
+                "preferably",
+                "generally",
+            ]),
+            scope_headings: strings(&["scope", "applies to", "applicability"]),
+        }
+    }
+}
+
+impl ContentLintConfig {
+    /// Raise every rule that is not off to an error.
+    #[must_use]
+    pub fn strict(mut self) -> Self {
+        for rule in ContentRule::ALL {
+            if self.level(rule) != RuleLevel::Off {
+                self.rules.insert(rule, RuleLevel::Error);
+            }
+        }
+        self
+    }
+
+    /// Set the level of `rule`.
+    #[must_use]
+    pub fn with_level(mut self, rule: ContentRule, level: RuleLevel) -> Self {
+        self.rules.insert(rule, level);
+        self
+    }
+
+    /// Set the overlong-rule threshold.
+    #[must_use]
+    pub fn with_max_rule_words(mut self, words: usize) -> Self {
+        self.max_rule_words = words;
+        self
+    }
+
+    /// The level `rule` runs at.
+    pub fn level(&self, rule: ContentRule) -> RuleLevel {
+        self.rules
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+
+    /// Load from JSON; omitted fields keep their defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`](crate::VcpError::JsonError) if the
+    /// JSON does not describe a config.
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`](crate::VcpError::JsonError) if
+    /// serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+// ── Issues ──────────────────────────────────────────────────
+
+/// One finding from [`lint_content`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentIssue {
+    /// How serious the issue is, from the config.
+    pub severity: Severity,
+    /// The rule that found it.
+    pub rule: ContentRule,
+    /// 1-based line number, or `None` for whole-document findings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl ContentIssue {
+    /// Returns `true` for [`Severity::Error`].
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ContentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}] ", self.severity, self.rule)?;
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "document: {}", self.message),
+        }
+    }
+}
+
+/// Everything [`lint_content`] found.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentLintReport {
+    /// Whole-document findings first, then by line.
+    pub issues: Vec<ContentIssue>,
+}
+
+impl ContentLintReport {
+    /// Whether any issue is an error.
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(ContentIssue::is_error)
+    }
+}
+
+impl fmt::Display for ContentLintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return writeln!(f, "Constitution: no issues");
+        }
+        for issue in &self.issues {
+            writeln!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+// ── Linting ─────────────────────────────────────────────────
+
+/// A numbered list item's number and delimiter (`.` or `)`).
+type Number = (u64, char);
+
+/// Check constitution text against `config`.
+pub fn lint_content(text: &str, config: &ContentLintConfig) -> ContentLintReport {
+    let mut linter = Linter {
+        config,
+        issues: Vec::new(),
+    };
+    let mut in_fence = false;
+    let mut has_scope = false;
+    let mut numbers: Vec<(usize, Number)> = Vec::new();
+    let mut delimiter: Option<(usize, char)> = None;
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || line.is_empty() {
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim().to_lowercase();
+            has_scope |= config
+                .scope_headings
+                .iter()
+                .any(|s| heading.contains(&s.to_lowercase()));
+            linter.check_sequence(&numbers);
+            numbers.clear();
+            continue;
+        }
+
+        let (number, rule) = split_marker(line);
+        if let Some((n, delim)) = number {
+            match delimiter {
+                Some((first_line, first)) if first != delim => linter.push(
+                    ContentRule::Numbering,
+                    Some(line_no),
+                    format!(
+                        "`{n}{delim}` differs from the `{first}` style used on line {first_line}"
+                    ),
+                ),
+                Some(_) => {}
+                None => delimiter = Some((line_no, delim)),
+            }
+            numbers.push((line_no, (n, delim)));
+        }
+
+        let words = words(rule);
+        for word in &words {
+            if config.ambiguous_words.iter().any(|w| w == word) {
+                linter.push(
+                    ContentRule::AmbiguousModal,
+                    Some(line_no),
+                    format!(
+                        "`{word}` is ambiguous; use `must` for an obligation or `may` for a permission"
+                    ),
+                );
+            }
+        }
+        if words.len() > config.max_rule_words {
+            linter.push(
+                ContentRule::OverlongRule,
+                Some(line_no),
+                format!(
+                    "{} words (limit {}); split it into separate rules",
+                    words.len(),
+                    config.max_rule_words
+                ),
+            );
+        }
+        if !words.is_empty() {
+            let key = words.join(" ");
+            if let Some(first) = seen.get(&key) {
+                linter.push(
+                    ContentRule::DuplicateRule,
+                    Some(line_no),
+                    format!("repeats the rule on line {first}"),
+                );
+            } else {
+                seen.insert(key, line_no);
+            }
+        }
+    }
+    linter.check_sequence(&numbers);
+
+    if !has_scope {
+        linter.push(
+            ContentRule::MissingScope,
+            None,
+            format!(
+                "no heading naming the scope (one of: {})",
+                config.scope_headings.join(", ")
+            ),
+        );
+    }
+
+    let mut issues = linter.issues;
+    issues.sort_by_key(|issue| issue.line);
+    ContentLintReport { issues }
+}
+
+struct Linter<'a> {
+    config: &'a ContentLintConfig,
+    issues: Vec<ContentIssue>,
+}
+
+impl Linter<'_> {
+    fn push(&mut self, rule: ContentRule, line: Option<usize>, message: String) {
+        if let Some(severity) = self.config.level(rule).severity() {
+            self.issues.push(ContentIssue {
+                severity,
+                rule,
+                line,
+                message,
+            });
+        }
+    }
+
+    /// Numbers within one section must count up by one. A list numbered
+    /// `1.` throughout is Markdown's auto-numbering and passes.
+    fn check_sequence(&mut self, numbers: &[(usize, Number)]) {
+        if numbers.iter().all(|(_, (n, _))| *n == 1) {
+            return;
+        }
+        for pair in numbers.windows(2) {
+            let (_, (prev, _)) = pair[0];
+            let (line, (n, delim)) = pair[1];
+            if n != prev + 1 {
+                self.push(
+                    ContentRule::Numbering,
+                    Some(line),
+                    format!("`{n}{delim}` follows {prev}; expected {}", prev + 1),
+                );
+            }
+        }
+    }
+}
+
+/// Split a list marker off `line`, returning the item number if the
+/// marker is numbered.
+fn split_marker(line: &str) -> (Option<Number>, &str) {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return (None, rest.trim_start());
+    }
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        for delim in ['.', ')'] {
+            if let Some(rest) = rest.strip_prefix(delim) {
+                if rest.starts_with(' ') {
+                    if let Ok(n) = line[..digits].parse() {
+                        return (Some((n, delim)), rest.trim_start());
+                    }
+                }
+            }
+        }
+    }
+    (None, line)
+}
+
+/// Lowercase words with punctuation removed.
+fn words(rule: &str) -> Vec<String> {
+    rule.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(report: &ContentLintReport) -> Vec<(ContentRule, Option<usize>)> {
+        report.issues.iter().map(|i| (i.rule, i.line)).collect()
+    }
+
+    #[test]
+    fn clean_constitution_passes() {
+        let text = "# Scope\nApplies to the tutoring assistant.\n\n# Rules\n1. Cite sources.\n2. Never invent quotes.\n\n```\nshould should should\n```\n";
+        let report = lint_content(text, &ContentLintConfig::default());
+        assert!(report.issues.is_empty(), "{report}");
+        assert_eq!(report.to_string(), "Constitution: no issues\n");
+    }
+
+    #[test]
+    fn numbering_gaps_repeats_and_styles() {
+        let text = "## Scope\n1. a\n2. b\n2. c\n5) d\n# Next\n1. e\n1. f\n";
+        let report = lint_content(text, &ContentLintConfig::default());
+        assert_eq!(
+            rules(&report),
+            [
+                (ContentRule::Numbering, Some(4)),
+                (ContentRule::Numbering, Some(5)),
+                (ContentRule::Numbering, Some(5)),
+            ]
+        );
+        assert!(report.issues[0].message.contains("expected 3"));
+        assert!(report.issues[1].message.contains("line 2"));
+    }
+
+    #[test]
+    fn modals_length_and_duplicates() {
+        let text = "# Scope\n- You should be kind.\n- Always respond in English, even if the user writes in another language, unless asked.\n* you SHOULD be kind!\n";
+        let config = ContentLintConfig::default().with_max_rule_words(10);
+        let report = lint_content(text, &config);
+        assert_eq!(
+            rules(&report),
+            [
+                (ContentRule::AmbiguousModal, Some(2)),
+                (ContentRule::OverlongRule, Some(3)),
+                (ContentRule::AmbiguousModal, Some(4)),
+                (ContentRule::DuplicateRule, Some(4)),
+            ]
+        );
+        assert!(report.has_errors());
+        assert_eq!(
+            report.issues[3].to_string(),
+            "error [duplicate_rule] line 4: repeats the rule on line 2"
+        );
+    }
+
+    #[test]
+    fn levels_are_configurable() {
+        let text = "- You might help.\n- You might help.\n";
+        let config = ContentLintConfig::from_json(
+            r#"{"rules": {"missing_scope": "off", "duplicate_rule": "warning", "ambiguous_modal": "error"}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.max_rule_words, 40);
+        let report = lint_content(text, &config);
+        assert_eq!(
+            report
+                .issues
+                .iter()
+                .map(|i| (i.rule, i.severity))
+                .collect::<Vec<_>>(),
+            [
+                (ContentRule::AmbiguousModal, Severity::Error),
+                (ContentRule::AmbiguousModal, Severity::Error),
+                (ContentRule::DuplicateRule, Severity::Warning),
+            ]
+        );
+
+        let strict = config.strict();
+        assert_eq!(strict.level(ContentRule::MissingScope), RuleLevel::Off);
+        assert_eq!(strict.level(ContentRule::Numbering), RuleLevel::Error);
+        let back = ContentLintConfig::from_json(&strict.to_json().unwrap()).unwrap();
+        assert_eq!(back, strict);
+    }
+}
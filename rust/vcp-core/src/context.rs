@@ -12,6 +12,48 @@
 //! \u{23F0}\u{1F305}|\u{1F4CD}\u{1F3E1}\u{2016}\u{1F9E0}focused:4|\u{1F4AD}calm:3
 //! ```
 //!
+//! ## ASCII-safe encoding (`ctx1`)
+//!
+//! Emoji do not survive every log pipeline, so [`FullContext::to_ascii_wire`]
+//! writes a versioned, plain-ASCII alternative:
+//!
+//! ```text
+//! ctx1;time=morning;space=home||cognitive_state=focused:4;emotional_tone=calm:3
+//! ```
+//!
+//! Vocabulary tags are written by name; anything else is percent-encoded.
+//! [`FullContext::from_wire`] recognises the `ctx<N>;` header and accepts
+//! either form. Emoji remains the default output.
+//!
+//! ## Redaction flag
+//!
+//! A context filtered through a [`RedactionProfile`](crate::privacy::RedactionProfile)
+//! names the applied profiles up front, so receivers can tell withheld
+//! dimensions from unknown ones:
+//!
+//! ```text
+//! \u{1F512}share-with-third-party;\u{23F0}\u{1F305}\u{2016}\u{1F9E0}focused:4
+//! ctx1;redacted=share-with-third-party;time=morning||cognitive_state=focused:4
+//! ```
+//!
+//! Redactions and [`Consent`] are extension segments: each opens with a
+//! marker (`\u{1F512}`, `\u{1F4DC}`) or key (`redacted=`, `consent=`) and
+//! precedes the body.
+//!
+//! ## Sealed contexts
+//!
+//! With the `seal` feature, [`seal`] encrypts a context to a recipient's
+//! X25519 public key so it can cross untrusted relays, and [`open`]
+//! recovers it with the matching secret:
+//!
+//! ```text
+//! vcs1.<base64url(ephemeral public key || XChaCha20-Poly1305 ciphertext)>
+//! ```
+//!
+//! Each envelope uses a fresh ephemeral key, so the sender needs no key
+//! of its own and envelopes are unlinkable. Sealing hides the context but
+//! does not authenticate the sender.
+//!
 //! ## Conformance levels (VCP v3.2)
 //!
 //! | level          | shape                                                 |
@@ -20,10 +62,16 @@
 //! | VCP-Standard   | Minimal + any personal-state dim                      |
 //! | VCP-Extended   | Standard (or Minimal) + any VEP-0004 dim (pos 10-13)  |
 
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::error::VcpResult;
+use crate::consent::Consent;
+use crate::context_schema::{ContextSchema, ValidationIssue};
+use crate::error::{Span, VcpError, VcpResult};
 use crate::personal::PersonalState;
+use crate::privacy::{is_profile_name, RedactionProfile};
 use crate::situational::SituationalContext;
 
 /// VCP v3.2 conformance classification for a [`FullContext`].
@@ -61,6 +109,36 @@ impl ConformanceLevel {
 /// full context wire format.
 pub const WIRE_SEPARATOR: char = '\u{2016}'; // double vertical line
 
+/// Version written in the header of the ASCII wire format (`ctx1;`).
+pub const ASCII_WIRE_VERSION: u32 = 1;
+
+/// Separator between the situational and personal halves of the ASCII
+/// wire format.
+pub const ASCII_WIRE_SEPARATOR: &str = "||";
+
+/// Marker opening the redaction header of the emoji wire format
+/// (`\u{1F512}name,name;`).
+pub const REDACTION_MARKER: char = '\u{1F512}'; // locked padlock
+
+/// Marker opening the consent segment of the emoji wire format.
+pub const CONSENT_MARKER: char = '\u{1F4DC}'; // scroll
+
+/// Key of the redaction segment in the ASCII wire format.
+const ASCII_REDACTION_KEY: &str = "redacted=";
+
+/// Key of the consent segment in the ASCII wire format.
+const ASCII_CONSENT_KEY: &str = "consent=";
+
+/// Output encoding for [`FullContext::to_wire_as`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WireFormat {
+    /// Emoji symbols with a `‖` separator (the v3.2 default).
+    #[default]
+    Emoji,
+    /// Versioned plain-ASCII form, e.g. `ctx1;time=morning`.
+    Ascii,
+}
+
 /// Full VCP context combining situational and personal state (VCP v3.2).
 ///
 /// Situational carries 13 dimensions (9 core + 4 VEP-0004).
@@ -71,6 +149,13 @@ pub struct FullContext {
     pub situational: SituationalContext,
     /// Personal state (5 dimensions).
     pub personal: PersonalState,
+    /// Names of the redaction profiles applied, in order. Empty for an
+    /// unfiltered context.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redactions: Vec<String>,
+    /// What the user agreed to when sharing this context, if recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consent: Option<Consent>,
 }
 
 impl FullContext {
@@ -79,6 +164,8 @@ impl FullContext {
         Self {
             situational,
             personal,
+            redactions: Vec::new(),
+            consent: None,
         }
     }
 
@@ -106,56 +193,199 @@ impl FullContext {
     ///
     /// If only one half has data, the separator is still included
     /// to make the format unambiguous. If neither has data,
-    /// returns an empty string.
+    /// returns an empty string. Applied redactions and consent are
+    /// written as `\u{1F512}name,name;` and `\u{1F4DC}<consent>;` prefixes.
     pub fn to_wire(&self) -> String {
         let sit = self.situational.to_wire();
         let per = self.personal.to_wire();
 
-        if sit.is_empty() && per.is_empty() {
-            return String::new();
+        let body = if per.is_empty() {
+            sit
+        } else if sit.is_empty() {
+            format!("{WIRE_SEPARATOR}{per}")
+        } else {
+            format!("{sit}{WIRE_SEPARATOR}{per}")
+        };
+
+        let mut out = String::new();
+        if !self.redactions.is_empty() {
+            let _ = write!(out, "{REDACTION_MARKER}{};", self.redactions.join(","));
+        }
+        if let Some(consent) = &self.consent {
+            let _ = write!(out, "{CONSENT_MARKER}{};", consent.to_segment());
         }
+        out.push_str(&body);
+        out
+    }
 
-        if per.is_empty() {
-            return sit;
+    /// Overlay `other` on this context, dimension by dimension.
+    ///
+    /// Dimensions set in `other` win; the rest are kept. Redactions from
+    /// both sides are kept; `other`'s consent, if any, replaces this one.
+    pub fn merge(&mut self, other: &FullContext) {
+        self.situational.merge(&other.situational);
+        self.personal.merge(&other.personal);
+        if other.consent.is_some() {
+            self.consent.clone_from(&other.consent);
+        }
+        for name in &other.redactions {
+            if !self.redactions.contains(name) {
+                self.redactions.push(name.clone());
+            }
         }
+    }
+
+    /// A copy with `profile`'s dimensions stripped and the profile
+    /// recorded in [`redactions`](Self::redactions).
+    #[must_use]
+    pub fn redacted(&self, profile: &RedactionProfile) -> Self {
+        let mut out = self.clone();
+        profile.apply(&mut out);
+        out
+    }
+
+    /// Check this context against `schema`, returning every issue found.
+    ///
+    /// An empty result means the context is sensible under the schema;
+    /// warnings alone do not make it unusable.
+    pub fn validate(&self, schema: &ContextSchema) -> Vec<ValidationIssue> {
+        schema.check(self)
+    }
 
-        if sit.is_empty() {
-            return format!("{WIRE_SEPARATOR}{per}");
+    /// Encode in the requested [`WireFormat`].
+    pub fn to_wire_as(&self, format: WireFormat) -> String {
+        match format {
+            WireFormat::Emoji => self.to_wire(),
+            WireFormat::Ascii => self.to_ascii_wire(),
         }
+    }
 
-        format!("{sit}{WIRE_SEPARATOR}{per}")
+    /// Encode to the ASCII-safe `ctx1` wire format.
+    ///
+    /// The header is always present, so an empty context encodes as
+    /// `ctx1;`. Applied redactions and consent follow it as
+    /// `redacted=name,name` and `consent=<consent>` segments. The `||`
+    /// separator is only written when there is personal state.
+    pub fn to_ascii_wire(&self) -> String {
+        let sit = self.situational.to_ascii_wire();
+        let per = self.personal.to_ascii_wire();
+        let mut segments = Vec::new();
+        if !self.redactions.is_empty() {
+            segments.push(format!(
+                "{ASCII_REDACTION_KEY}{}",
+                self.redactions.join(",")
+            ));
+        }
+        if let Some(consent) = &self.consent {
+            segments.push(format!("{ASCII_CONSENT_KEY}{}", consent.to_segment()));
+        }
+        if !sit.is_empty() {
+            segments.push(sit);
+        }
+        let mut out = format!("ctx{ASCII_WIRE_VERSION};{}", segments.join(";"));
+        if !per.is_empty() {
+            out.push_str(ASCII_WIRE_SEPARATOR);
+            out.push_str(&per);
+        }
+        out
     }
 
-    /// Parse from the full wire format.
+    /// Parse from either wire format.
+    ///
+    /// A leading `ctx<N>;` header selects the ASCII form; anything else is
+    /// read as emoji.
     ///
     /// # Errors
     ///
     /// Returns [`VcpError::ParseError`] if the situational or personal
-    /// portion of the wire format is malformed.
+    /// portion of the wire format is malformed, if the ASCII header
+    /// names an unsupported version, or if an extension segment is
+    /// malformed. Consent is read but not enforced; see
+    /// [`from_wire_strict`](Self::from_wire_strict).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.parse.context", level = "trace", skip_all, fields(bytes = wire.len()), err(Display))
+    )]
     pub fn from_wire(wire: &str) -> VcpResult<Self> {
         if wire.is_empty() {
             return Ok(Self::default());
         }
 
-        if let Some(sep_idx) = wire.find(WIRE_SEPARATOR) {
-            let sit_part = &wire[..sep_idx];
-            let per_part = &wire[sep_idx + WIRE_SEPARATOR.len_utf8()..];
+        if let Some((version, body)) = split_ascii_header(wire) {
+            if version != ASCII_WIRE_VERSION {
+                let header = &wire[..wire.len() - body.len()];
+                return Err(VcpError::ParseError(format!(
+                    "unsupported context wire version: ctx{version}"
+                ))
+                .at(Span::of(wire, header))
+                .expecting(&[&format!("ctx{ASCII_WIRE_VERSION};")]));
+            }
+            let (ext, body) = split_ascii_extensions(wire, body)?;
+            let (sit_part, per_part) = body
+                .split_once(ASCII_WIRE_SEPARATOR)
+                .unwrap_or((body, &body[body.len()..]));
+            return Ok(Self {
+                situational: SituationalContext::from_ascii_wire(sit_part)
+                    .map_err(|e| e.at(Span::of(wire, sit_part)))?,
+                personal: PersonalState::from_ascii_wire(per_part)
+                    .map_err(|e| e.at(Span::of(wire, per_part)))?,
+                redactions: ext.redactions,
+                consent: ext.consent,
+            });
+        }
+
+        let (ext, body) = split_emoji_extensions(wire)?;
+        let mut ctx = Self {
+            redactions: ext.redactions,
+            consent: ext.consent,
+            ..Self::default()
+        };
+        if body.is_empty() {
+            return Ok(ctx);
+        }
 
-            let situational = SituationalContext::from_wire(sit_part)?;
-            let personal = PersonalState::from_wire(per_part)?;
+        if let Some(sep_idx) = body.find(WIRE_SEPARATOR) {
+            let sit_part = &body[..sep_idx];
+            let per_part = &body[sep_idx + WIRE_SEPARATOR.len_utf8()..];
 
-            Ok(Self {
-                situational,
-                personal,
-            })
+            ctx.situational = SituationalContext::from_wire(sit_part)
+                .map_err(|e| e.at(Span::of(wire, sit_part)))?;
+            ctx.personal =
+                PersonalState::from_wire(per_part).map_err(|e| e.at(Span::of(wire, per_part)))?;
         } else {
             // No separator -- treat the entire string as situational only.
-            let situational = SituationalContext::from_wire(wire)?;
-            Ok(Self {
-                situational,
-                personal: PersonalState::default(),
-            })
+            ctx.situational =
+                SituationalContext::from_wire(body).map_err(|e| e.at(Span::of(wire, body)))?;
         }
+        Ok(ctx)
+    }
+
+    /// Parse from either wire format on behalf of a consumer using the
+    /// context for `purpose`, requiring consent that allows it.
+    ///
+    /// # Errors
+    ///
+    /// See [`from_wire_strict_at`](Self::from_wire_strict_at).
+    pub fn from_wire_strict(wire: &str, purpose: &str) -> VcpResult<Self> {
+        Self::from_wire_strict_at(wire, purpose, Utc::now())
+    }
+
+    /// [`from_wire_strict`](Self::from_wire_strict) at a fixed time.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`from_wire`](Self::from_wire), or
+    /// [`VcpError::ConsentError`] if the context carries no consent or
+    /// [`Consent::check_at`] rejects it.
+    pub fn from_wire_strict_at(wire: &str, purpose: &str, now: DateTime<Utc>) -> VcpResult<Self> {
+        let ctx = Self::from_wire(wire)?;
+        let Some(consent) = &ctx.consent else {
+            return Err(VcpError::ConsentError(
+                "context carries no consent".to_string(),
+            ));
+        };
+        consent.check_at(&ctx, purpose, now)?;
+        Ok(ctx)
     }
 }
 
@@ -165,6 +395,371 @@ impl std::fmt::Display for FullContext {
     }
 }
 
+/// Detect which format `wire` is in.
+pub fn detect_wire_format(wire: &str) -> WireFormat {
+    if split_ascii_header(wire).is_some() {
+        WireFormat::Ascii
+    } else {
+        WireFormat::Emoji
+    }
+}
+
+/// Split `ctx<N>;` off the front of `wire`, returning the version and body.
+fn split_ascii_header(wire: &str) -> Option<(u32, &str)> {
+    let rest = wire.strip_prefix("ctx")?;
+    let (version, body) = rest.split_once(';')?;
+    if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((version.parse().ok()?, body))
+}
+
+// ── Extension segments ──────────────────────────────────────
+
+/// Extension segments read ahead of the context body.
+#[derive(Default)]
+struct Extensions {
+    redactions: Vec<String>,
+    consent: Option<Consent>,
+}
+
+/// Split `\u{1F512}...;` and `\u{1F4DC}...;` segments off the front of an
+/// emoji wire.
+fn split_emoji_extensions(wire: &str) -> VcpResult<(Extensions, &str)> {
+    let mut ext = Extensions::default();
+    let mut rest = wire;
+    while let Some(marker) = rest
+        .chars()
+        .next()
+        .filter(|c| [REDACTION_MARKER, CONSENT_MARKER].contains(c))
+    {
+        let Some((value, body)) = rest[marker.len_utf8()..].split_once(';') else {
+            return Err(VcpError::ParseError(
+                "extension segment is missing its ';' terminator".into(),
+            )
+            .at(Span::of(wire, rest))
+            .expecting(&[";"]));
+        };
+        if marker == REDACTION_MARKER {
+            ext.redactions = parse_redactions(wire, value)?;
+        } else {
+            ext.consent = Some(parse_consent(wire, value)?);
+        }
+        rest = body;
+    }
+    Ok((ext, rest))
+}
+
+/// Split leading `redacted=...` and `consent=...` segments off an ASCII
+/// wire body.
+fn split_ascii_extensions<'a>(wire: &str, body: &'a str) -> VcpResult<(Extensions, &'a str)> {
+    let mut ext = Extensions::default();
+    let mut rest = body;
+    loop {
+        let (is_redaction, after) = if let Some(after) = rest.strip_prefix(ASCII_REDACTION_KEY) {
+            (true, after)
+        } else if let Some(after) = rest.strip_prefix(ASCII_CONSENT_KEY) {
+            (false, after)
+        } else {
+            break;
+        };
+        let end = after.find([';', '|']).unwrap_or(after.len());
+        let value = &after[..end];
+        if is_redaction {
+            ext.redactions = parse_redactions(wire, value)?;
+        } else {
+            ext.consent = Some(parse_consent(wire, value)?);
+        }
+        rest = after[end..].strip_prefix(';').unwrap_or(&after[end..]);
+    }
+    Ok((ext, rest))
+}
+
+fn parse_consent(wire: &str, segment: &str) -> VcpResult<Consent> {
+    Consent::from_segment(segment).map_err(|e| e.at(Span::of(wire, segment)))
+}
+
+fn parse_redactions(wire: &str, names: &str) -> VcpResult<Vec<String>> {
+    names
+        .split(',')
+        .map(|name| {
+            if is_profile_name(name) {
+                Ok(name.to_string())
+            } else {
+                Err(
+                    VcpError::ParseError(format!("invalid redaction profile name '{name}'"))
+                        .at(Span::of(wire, name))
+                        .expecting(&["[a-z0-9-]+"]),
+                )
+            }
+        })
+        .collect()
+}
+
+// ── Sealed contexts ─────────────────────────────────────────
+
+#[cfg(feature = "seal")]
+pub use sealed::{import_seal_public_key, open, seal, SealKeyPair, SEALED_PREFIX};
+
+#[cfg(feature = "seal")]
+mod sealed {
+    use std::fmt;
+
+    use base64::engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64_URL};
+    use base64::Engine as _;
+    use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+    use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+    use curve25519_dalek::montgomery::MontgomeryPoint;
+    use sha2::{Digest, Sha256};
+
+    use super::FullContext;
+    use crate::error::{VcpError, VcpResult};
+
+    /// Prefix of a sealed context envelope.
+    pub const SEALED_PREFIX: &str = "vcs1.";
+
+    const KEY_LABEL: &[u8] = b"vcp-seal-v1 key";
+    const NONCE_LABEL: &[u8] = b"vcp-seal-v1 nonce";
+
+    /// An X25519 key pair for receiving sealed contexts.
+    #[derive(Clone)]
+    pub struct SealKeyPair {
+        secret: [u8; 32],
+    }
+
+    impl SealKeyPair {
+        /// Generate a new key pair from the system RNG.
+        pub fn generate() -> Self {
+            Self::from_secret(rand::random())
+        }
+
+        /// Rebuild from a 32-byte X25519 secret.
+        pub fn from_secret(secret: [u8; 32]) -> Self {
+            Self { secret }
+        }
+
+        /// Rebuild from a secret in `base64:` text (prefix optional).
+        ///
+        /// # Errors
+        ///
+        /// Returns [`VcpError::SignatureError`] unless `text` is 32 bytes
+        /// of base64.
+        pub fn import(text: &str) -> VcpResult<Self> {
+            decode_key(text).map(Self::from_secret)
+        }
+
+        /// The secret as `base64:` text.
+        pub fn secret_key_base64(&self) -> String {
+            format!("base64:{}", BASE64.encode(self.secret))
+        }
+
+        /// The 32-byte secret; keep it private.
+        pub fn secret_bytes(&self) -> [u8; 32] {
+            self.secret
+        }
+
+        /// The 32-byte public key to hand to senders.
+        pub fn public_bytes(&self) -> [u8; 32] {
+            MontgomeryPoint::mul_base_clamped(self.secret).to_bytes()
+        }
+
+        /// The public key as `base64:` text.
+        pub fn public_key_base64(&self) -> String {
+            format!("base64:{}", BASE64.encode(self.public_bytes()))
+        }
+    }
+
+    impl fmt::Debug for SealKeyPair {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("SealKeyPair")
+                .field("public_key", &self.public_key_base64())
+                .finish_non_exhaustive()
+        }
+    }
+
+    /// Read an X25519 public key from `base64:` text (prefix optional).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] unless `text` is 32 bytes of
+    /// base64.
+    pub fn import_seal_public_key(text: &str) -> VcpResult<[u8; 32]> {
+        decode_key(text)
+    }
+
+    fn decode_key(text: &str) -> VcpResult<[u8; 32]> {
+        let text = text.trim();
+        let bytes = BASE64
+            .decode(text.strip_prefix("base64:").unwrap_or(text))
+            .map_err(|e| VcpError::SignatureError(format!("invalid base64 key: {e}")))?;
+        bytes.as_slice().try_into().map_err(|_| {
+            VcpError::SignatureError(format!(
+                "X25519 key must be exactly 32 bytes, got {}",
+                bytes.len()
+            ))
+        })
+    }
+
+    /// Encrypt `context` so only the holder of `recipient_public`'s secret
+    /// can read it. Returns a [`SEALED_PREFIX`] envelope.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] if `recipient_public` is a
+    /// low-order point or encryption fails.
+    pub fn seal(context: &FullContext, recipient_public: &[u8; 32]) -> VcpResult<String> {
+        let ephemeral = SealKeyPair::generate();
+        let ephemeral_public = ephemeral.public_bytes();
+        let (cipher, nonce) = envelope_cipher(
+            &ephemeral.secret,
+            recipient_public,
+            &ephemeral_public,
+            recipient_public,
+        )?;
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: context.to_wire().as_bytes(),
+                    aad: SEALED_PREFIX.as_bytes(),
+                },
+            )
+            .map_err(|_| VcpError::SignatureError("context encryption failed".into()))?;
+
+        let mut body = ephemeral_public.to_vec();
+        body.extend_from_slice(&ciphertext);
+        Ok(format!("{SEALED_PREFIX}{}", BASE64_URL.encode(body)))
+    }
+
+    /// Decrypt an envelope made by [`seal`] with the recipient's key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if `envelope` is not a sealed
+    /// context or the decrypted wire is malformed, and
+    /// [`VcpError::SignatureError`] if it was sealed to another key or
+    /// tampered with.
+    pub fn open(envelope: &str, recipient: &SealKeyPair) -> VcpResult<FullContext> {
+        let encoded = envelope.trim().strip_prefix(SEALED_PREFIX).ok_or_else(|| {
+            VcpError::ParseError(format!("sealed context must start with '{SEALED_PREFIX}'"))
+        })?;
+        let body = BASE64_URL
+            .decode(encoded)
+            .map_err(|e| VcpError::ParseError(format!("invalid sealed context: {e}")))?;
+        if body.len() < 32 {
+            return Err(VcpError::ParseError("sealed context is truncated".into()));
+        }
+        let (ephemeral_public, ciphertext) = body.split_at(32);
+        let ephemeral_public: [u8; 32] = ephemeral_public
+            .try_into()
+            .map_err(|_| VcpError::ParseError("sealed context is truncated".into()))?;
+
+        let (cipher, nonce) = envelope_cipher(
+            &recipient.secret,
+            &ephemeral_public,
+            &ephemeral_public,
+            &recipient.public_bytes(),
+        )?;
+        let plaintext = cipher
+            .decrypt(
+                &nonce,
+                Payload {
+                    msg: ciphertext,
+                    aad: SEALED_PREFIX.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                VcpError::SignatureError(
+                    "sealed context was not sealed to this key or was tampered with".into(),
+                )
+            })?;
+        let wire = String::from_utf8(plaintext)
+            .map_err(|_| VcpError::ParseError("sealed context is not UTF-8".into()))?;
+        FullContext::from_wire(&wire)
+    }
+
+    /// The AEAD and nonce for an envelope between `ephemeral_public` and
+    /// `recipient_public`, from either side's secret and the other's
+    /// public key.
+    ///
+    /// Key and nonce are SHA-256 of a label, the X25519 shared secret (key
+    /// only) and both public keys. The ephemeral key is fresh per
+    /// envelope, so the deterministic nonce never repeats under a key.
+    fn envelope_cipher(
+        secret: &[u8; 32],
+        peer_public: &[u8; 32],
+        ephemeral_public: &[u8; 32],
+        recipient_public: &[u8; 32],
+    ) -> VcpResult<(XChaCha20Poly1305, XNonce)> {
+        let shared = MontgomeryPoint(*peer_public)
+            .mul_clamped(*secret)
+            .to_bytes();
+        if shared == [0u8; 32] {
+            return Err(VcpError::SignatureError(
+                "X25519 public key is a low-order point".into(),
+            ));
+        }
+        let key = Sha256::new()
+            .chain_update(KEY_LABEL)
+            .chain_update(shared)
+            .chain_update(ephemeral_public)
+            .chain_update(recipient_public)
+            .finalize();
+        let nonce = Sha256::new()
+            .chain_update(NONCE_LABEL)
+            .chain_update(ephemeral_public)
+            .chain_update(recipient_public)
+            .finalize();
+        Ok((
+            XChaCha20Poly1305::new(Key::from_slice(&key)),
+            *XNonce::from_slice(&nonce[..24]),
+        ))
+    }
+}
+
+// ── ASCII escaping ──────────────────────────────────────────
+
+/// Percent-encode every byte outside `[A-Za-z0-9_.-]` and `extra`.
+pub(crate) fn ascii_escape(value: &str, extra: &[char]) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') || extra.contains(&c) {
+            out.push(c);
+        } else {
+            let mut buf = [0u8; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                let _ = write!(out, "%{b:02X}");
+            }
+        }
+    }
+    out
+}
+
+/// Reverse [`ascii_escape`].
+pub(crate) fn ascii_unescape(value: &str) -> VcpResult<String> {
+    if !value.contains('%') {
+        return Ok(value.to_string());
+    }
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| VcpError::ParseError(format!("bad percent escape in: {value}")))?;
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out)
+        .map_err(|_| VcpError::ParseError(format!("percent escape is not UTF-8: {value}")))
+}
+
 // ── Tests ───────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -309,4 +904,131 @@ mod tests {
         ctx.personal.cognitive = Some(PersonalDimension::new("focused", 4).unwrap());
         assert_eq!(ctx.conformance_level(), ConformanceLevel::Extended);
     }
+
+    #[test]
+    fn ascii_wire_round_trip() {
+        let mut ctx = FullContext::default();
+        ctx.situational.time = Some(vec!["\u{1F305}".into()]);
+        ctx.situational.company = Some(vec!["\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}".into()]);
+        ctx.personal.cognitive = Some(PersonalDimension::new("focused", 4).unwrap());
+        ctx.personal.body =
+            Some(PersonalDimension::with_extended("pain", 3, "lower back").unwrap());
+
+        let wire = ctx.to_ascii_wire();
+        assert_eq!(
+            wire,
+            "ctx1;time=morning;company=family||cognitive_state=focused:4;body_signals=pain:3[lower%20back]"
+        );
+        assert!(wire.is_ascii());
+        assert_eq!(FullContext::from_wire(&wire).unwrap(), ctx);
+    }
+
+    #[test]
+    fn ascii_wire_escapes_free_form_tags() {
+        let mut ctx = FullContext::default();
+        ctx.situational.relationship = Some(vec!["mentor;peer".into(), "caf\u{e9}".into()]);
+        ctx.situational.space = Some(vec!["\u{1F3D5}".into()]);
+
+        let wire = ctx.to_wire_as(WireFormat::Ascii);
+        assert_eq!(
+            wire,
+            "ctx1;space=%F0%9F%8F%95;relationship=mentor%3Bpeer,caf%C3%A9"
+        );
+        assert_eq!(FullContext::from_wire(&wire).unwrap(), ctx);
+    }
+
+    #[test]
+    fn ascii_wire_detection() {
+        assert_eq!(detect_wire_format("ctx1;time=night"), WireFormat::Ascii);
+        assert_eq!(detect_wire_format("\u{23F0}\u{1F319}"), WireFormat::Emoji);
+        assert_eq!(FullContext::default().to_ascii_wire(), "ctx1;");
+        assert!(!FullContext::from_wire("ctx1;").unwrap().has_any());
+
+        let personal_only = FullContext::from_wire("ctx1;||energy_level=rested:2").unwrap();
+        assert_eq!(personal_only.personal.energy.unwrap().value, "rested");
+    }
+
+    #[test]
+    fn ascii_wire_rejects_bad_input() {
+        assert!(FullContext::from_wire("ctx2;time=night").is_err());
+        assert!(FullContext::from_wire("ctx1;weather=rain").is_err());
+        assert!(FullContext::from_wire("ctx1;time").is_err());
+        assert!(FullContext::from_wire("ctx1;time=%ZZ").is_err());
+        assert!(FullContext::from_wire("ctx1;||cognitive_state=focused:9").is_err());
+    }
+
+    #[test]
+    fn wire_errors_point_into_the_input() {
+        let span = |wire| FullContext::from_wire(wire).unwrap_err().span();
+        assert_eq!(span("ctx2;time=night"), Some(Span::new(0, 5)));
+        assert_eq!(span("ctx1;weather=rain"), Some(Span::new(5, 7)));
+        assert_eq!(
+            span("ctx1;time=morning||cognitive_state=focused:9"),
+            Some(Span::new(35, 9))
+        );
+        // "⏰" is three bytes, "🌅" four, "|" one; the unknown symbol follows.
+        assert_eq!(span("\u{23F0}\u{1F305}|\u{1F47E}x"), Some(Span::new(8, 5)));
+
+        let wire = "ctx1;time=morning||cognitive_state=focused:9";
+        let err = FullContext::from_wire(wire).unwrap_err();
+        assert_eq!(err.without_span(), &VcpError::InvalidIntensity(9));
+        assert!(err
+            .render(wire)
+            .ends_with("^^^^^^^^^ expected <value>:<intensity 1-5>\n"));
+    }
+
+    #[test]
+    fn emoji_remains_default() {
+        let mut ctx = FullContext::default();
+        ctx.situational.time = Some(vec!["\u{1F305}".into()]);
+        assert_eq!(ctx.to_wire_as(WireFormat::default()), ctx.to_wire());
+        assert_eq!(ctx.to_string(), "\u{23F0}\u{1F305}");
+    }
+
+    #[test]
+    fn merge_overlays_set_dimensions() {
+        let mut base =
+            FullContext::from_wire("ctx1;time=morning;space=home||emotional_tone=calm:3").unwrap();
+        let update =
+            FullContext::from_wire("ctx1;space=office||cognitive_state=focused:4").unwrap();
+        base.merge(&update);
+        assert_eq!(
+            base.to_ascii_wire(),
+            "ctx1;time=morning;space=office||cognitive_state=focused:4;emotional_tone=calm:3"
+        );
+    }
+
+    #[cfg(feature = "seal")]
+    #[test]
+    fn sealed_contexts_open_only_for_the_recipient() {
+        let recipient = SealKeyPair::from_secret([7u8; 32]);
+        let ctx = FullContext::from_wire("🔒minimal;⏰🌅|📍🏡‖🧠focused:4|🩺pain:2").unwrap();
+
+        let envelope = seal(&ctx, &recipient.public_bytes()).unwrap();
+        assert!(envelope.starts_with(SEALED_PREFIX));
+        assert!(!envelope.contains("focused"));
+        assert_ne!(envelope, seal(&ctx, &recipient.public_bytes()).unwrap());
+        assert_eq!(open(&envelope, &recipient).unwrap(), ctx);
+
+        let other = SealKeyPair::from_secret([8u8; 32]);
+        assert_eq!(
+            open(&envelope, &other).unwrap_err().code(),
+            "SignatureError"
+        );
+
+        let mut tampered = envelope.clone().into_bytes();
+        let last = tampered.len() - 2;
+        tampered[last] = if tampered[last] == b'A' { b'B' } else { b'A' };
+        assert!(open(&String::from_utf8(tampered).unwrap(), &recipient).is_err());
+
+        assert!(open("vcs1.AAAA", &recipient).is_err());
+        assert!(open("⏰🌅", &recipient).is_err());
+        assert!(seal(&ctx, &[0u8; 32]).is_err());
+        assert!(!format!("{recipient:?}").contains("secret"));
+
+        let restored = SealKeyPair::import(&recipient.secret_key_base64()).unwrap();
+        let public = import_seal_public_key(&restored.public_key_base64()).unwrap();
+        assert_eq!(public, recipient.public_bytes());
+        assert!(import_seal_public_key("base64:AAAA").is_err());
+    }
 }
@@ -0,0 +1,539 @@
+//! Semantic validation of a [`FullContext`].
+//!
+//! Parsing only checks that a context is well-formed. A [`ContextSchema`]
+//! describes what a *sensible* context looks like: which categories each
+//! dimension accepts, and which signals cannot hold at the same time.
+//! [`FullContext::validate`] returns every problem as a [`ValidationIssue`]
+//! rather than stopping at the first one.
+//!
+//! | Check | Code | Default severity |
+//! |-------|------|------------------|
+//! | Personal intensity outside 1-5 | `invalid_intensity` | error |
+//! | Relationship tag not `{tie}:{function}` | `malformed_tag` | error |
+//! | Tag or value outside the schema | `unknown_category` | warning (error when strict) |
+//! | Dimension present with no tags | `empty_dimension` | warning |
+//! | Same tag twice in one dimension | `duplicate_tag` | warning |
+//! | Two signals from an exclusive group | `conflicting_signals` | warning |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::context::FullContext;
+//! use vcp_core::context_schema::{ContextSchema, IssueCode};
+//!
+//! // Morning and night at once, and an unknown cognitive state.
+//! let ctx = FullContext::from_wire("ctx1;time=morning,night||cognitive_state=sleepy:2").unwrap();
+//! let issues = ctx.validate(&ContextSchema::default());
+//!
+//! assert!(issues.iter().any(|i| i.code == IssueCode::ConflictingSignals));
+//! assert!(issues.iter().any(|i| i.path == "personal.cognitive_state.value"));
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::FullContext;
+use crate::personal::PersonalDimensionKind;
+use crate::situational::SituationalDimension;
+
+// ── Issues ──────────────────────────────────────────────────
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Suspicious but usable.
+    Warning,
+    /// The context should not be acted on as-is.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Machine-readable kind of a [`ValidationIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueCode {
+    /// Personal intensity outside 1-5.
+    InvalidIntensity,
+    /// Tag or value not allowed by the schema.
+    UnknownCategory,
+    /// Tag does not have the shape its dimension requires.
+    MalformedTag,
+    /// Dimension is present but has no tags.
+    EmptyDimension,
+    /// The same tag appears twice in one dimension.
+    DuplicateTag,
+    /// Signals that the schema marks as mutually exclusive.
+    ConflictingSignals,
+}
+
+impl fmt::Display for IssueCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidIntensity => "invalid_intensity",
+            Self::UnknownCategory => "unknown_category",
+            Self::MalformedTag => "malformed_tag",
+            Self::EmptyDimension => "empty_dimension",
+            Self::DuplicateTag => "duplicate_tag",
+            Self::ConflictingSignals => "conflicting_signals",
+        })
+    }
+}
+
+/// One problem found by [`FullContext::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// How serious the issue is.
+    pub severity: Severity,
+    /// What kind of issue this is.
+    pub code: IssueCode,
+    /// Dotted location, e.g. `situational.time[1]` or
+    /// `personal.energy_level.intensity`.
+    pub path: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl ValidationIssue {
+    /// Returns `true` for [`Severity::Error`].
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}: {}",
+            self.severity, self.code, self.path, self.message
+        )
+    }
+}
+
+// ── Signals ─────────────────────────────────────────────────
+
+/// A single observable value, used to describe conflicts.
+///
+/// Situational tags may be given as the emoji or the vocabulary name
+/// (`morning`); both match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// A tag on a situational dimension.
+    Situational(SituationalDimension, String),
+    /// A value on a personal dimension.
+    Personal(PersonalDimensionKind, String),
+}
+
+impl Signal {
+    /// Situational tag signal.
+    pub fn situational(dim: SituationalDimension, tag: impl Into<String>) -> Self {
+        Self::Situational(dim, tag.into())
+    }
+
+    /// Personal value signal.
+    pub fn personal(kind: PersonalDimensionKind, value: impl Into<String>) -> Self {
+        Self::Personal(kind, value.into())
+    }
+
+    fn is_present(&self, ctx: &FullContext) -> bool {
+        match self {
+            Self::Situational(dim, want) => ctx
+                .situational
+                .get(*dim)
+                .is_some_and(|tags| tags.iter().any(|tag| tag_matches(*dim, tag, want))),
+            Self::Personal(kind, want) => ctx.personal.get(*kind).is_some_and(|d| &d.value == want),
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Situational(dim, tag) => {
+                let name = dim.tag_name(tag).unwrap_or(tag);
+                write!(f, "{dim}={name}")
+            }
+            Self::Personal(kind, value) => write!(f, "{kind}={value}"),
+        }
+    }
+}
+
+/// Compare ignoring emoji variation selectors, and accept vocabulary names.
+fn tag_matches(dim: SituationalDimension, tag: &str, want: &str) -> bool {
+    let strip = |s: &str| s.replace('\u{FE0F}', "");
+    strip(tag) == strip(want) || dim.tag_name(tag) == Some(want)
+}
+
+// ── Schema ──────────────────────────────────────────────────
+
+/// Allowed categories and exclusive signal groups for context validation.
+///
+/// [`ContextSchema::default`] accepts the standard vocabulary of every
+/// dimension and flags the obvious contradictions (morning and night,
+/// online and offline, an emergency while unhurried, ...).
+#[derive(Debug, Clone)]
+pub struct ContextSchema {
+    situational: HashMap<SituationalDimension, HashSet<String>>,
+    personal: HashMap<PersonalDimensionKind, HashSet<String>>,
+    exclusive: Vec<Vec<Signal>>,
+    unknown_severity: Severity,
+}
+
+impl Default for ContextSchema {
+    fn default() -> Self {
+        use PersonalDimensionKind as P;
+        use SituationalDimension as S;
+
+        let situational = S::all()
+            .iter()
+            .map(|&dim| {
+                let tags = dim
+                    .vocabulary()
+                    .iter()
+                    .map(|(e, _)| strip_vs16(e))
+                    .collect();
+                (dim, tags)
+            })
+            .collect();
+        let personal = P::all()
+            .iter()
+            .map(|&kind| {
+                let values = kind
+                    .valid_values()
+                    .iter()
+                    .map(|v| (*v).to_string())
+                    .collect();
+                (kind, values)
+            })
+            .collect();
+
+        let names = |dim: S, names: &[&str]| -> Vec<Signal> {
+            names.iter().map(|n| Signal::situational(dim, *n)).collect()
+        };
+
+        Self {
+            situational,
+            personal,
+            exclusive: vec![
+                names(S::Time, &["morning", "midday", "evening", "night"]),
+                names(S::Time, &["weekday", "weekend"]),
+                names(S::Environment, &["hot", "cold"]),
+                names(S::Environment, &["quiet", "noisy"]),
+                names(S::SystemContext, &["online", "offline"]),
+                names(S::Embodiment, &["stationary", "navigating"]),
+                vec![
+                    Signal::situational(S::Occasion, "emergency"),
+                    Signal::personal(P::PerceivedUrgency, "unhurried"),
+                ],
+                vec![
+                    Signal::situational(S::Embodiment, "emergency_stop"),
+                    Signal::personal(P::PerceivedUrgency, "unhurried"),
+                ],
+            ],
+            unknown_severity: Severity::Warning,
+        }
+    }
+}
+
+impl ContextSchema {
+    /// A schema that allows nothing and has no conflict rules.
+    pub fn empty() -> Self {
+        Self {
+            situational: HashMap::new(),
+            personal: HashMap::new(),
+            exclusive: Vec::new(),
+            unknown_severity: Severity::Warning,
+        }
+    }
+
+    /// Report unknown categories as errors instead of warnings.
+    #[must_use]
+    pub fn strict(mut self) -> Self {
+        self.unknown_severity = Severity::Error;
+        self
+    }
+
+    /// Accept an extra situational tag (emoji or custom string).
+    #[must_use]
+    pub fn with_tag(mut self, dim: SituationalDimension, tag: impl AsRef<str>) -> Self {
+        let tag = dim.tag_emoji(tag.as_ref()).unwrap_or(tag.as_ref());
+        self.situational
+            .entry(dim)
+            .or_default()
+            .insert(strip_vs16(tag));
+        self
+    }
+
+    /// Accept an extra personal value.
+    #[must_use]
+    pub fn with_value(mut self, kind: PersonalDimensionKind, value: impl Into<String>) -> Self {
+        self.personal.entry(kind).or_default().insert(value.into());
+        self
+    }
+
+    /// Add a group of signals of which at most one may be present.
+    #[must_use]
+    pub fn with_exclusive(mut self, signals: impl IntoIterator<Item = Signal>) -> Self {
+        self.exclusive.push(signals.into_iter().collect());
+        self
+    }
+
+    fn allows_tag(&self, dim: SituationalDimension, tag: &str) -> bool {
+        self.situational
+            .get(&dim)
+            .is_some_and(|tags| tags.contains(&strip_vs16(tag)))
+    }
+
+    fn allows_value(&self, kind: PersonalDimensionKind, value: &str) -> bool {
+        self.personal
+            .get(&kind)
+            .is_some_and(|values| values.contains(value))
+    }
+
+    /// Check `ctx` against this schema. See [`FullContext::validate`].
+    pub fn check(&self, ctx: &FullContext) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        self.check_situational(ctx, &mut issues);
+        self.check_personal(ctx, &mut issues);
+        self.check_conflicts(ctx, &mut issues);
+        issues
+    }
+
+    fn check_situational(&self, ctx: &FullContext, issues: &mut Vec<ValidationIssue>) {
+        for &dim in SituationalDimension::all() {
+            let Some(tags) = ctx.situational.get(dim) else {
+                continue;
+            };
+            let base = format!("situational.{dim}");
+            if tags.is_empty() {
+                issues.push(issue(
+                    Severity::Warning,
+                    IssueCode::EmptyDimension,
+                    base.clone(),
+                    format!("{dim} is present but has no tags"),
+                ));
+            }
+            let mut seen = HashSet::new();
+            for (i, tag) in tags.iter().enumerate() {
+                let path = format!("{base}[{i}]");
+                if !seen.insert(strip_vs16(tag)) {
+                    issues.push(issue(
+                        Severity::Warning,
+                        IssueCode::DuplicateTag,
+                        path.clone(),
+                        format!("{tag} appears more than once"),
+                    ));
+                }
+                if dim.is_free_form() {
+                    if !is_relationship_tag(tag) {
+                        issues.push(issue(
+                            Severity::Error,
+                            IssueCode::MalformedTag,
+                            path,
+                            format!("expected {{tie}}:{{function}}, got {tag:?}"),
+                        ));
+                    }
+                } else if !self.allows_tag(dim, tag) {
+                    issues.push(issue(
+                        self.unknown_severity,
+                        IssueCode::UnknownCategory,
+                        path,
+                        format!("{tag} is not a known {dim} tag"),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_personal(&self, ctx: &FullContext, issues: &mut Vec<ValidationIssue>) {
+        for &kind in PersonalDimensionKind::all() {
+            let Some(dim) = ctx.personal.get(kind) else {
+                continue;
+            };
+            let base = format!("personal.{kind}");
+            if !(1..=5).contains(&dim.intensity) {
+                issues.push(issue(
+                    Severity::Error,
+                    IssueCode::InvalidIntensity,
+                    format!("{base}.intensity"),
+                    format!("intensity must be 1-5, got {}", dim.intensity),
+                ));
+            }
+            if !self.allows_value(kind, &dim.value) {
+                issues.push(issue(
+                    self.unknown_severity,
+                    IssueCode::UnknownCategory,
+                    format!("{base}.value"),
+                    format!("{:?} is not a known {kind} value", dim.value),
+                ));
+            }
+            if dim.extended.as_deref().is_some_and(|e| e.trim().is_empty()) {
+                issues.push(issue(
+                    Severity::Warning,
+                    IssueCode::MalformedTag,
+                    format!("{base}.extended"),
+                    "extended qualifier is empty".to_string(),
+                ));
+            }
+        }
+    }
+
+    fn check_conflicts(&self, ctx: &FullContext, issues: &mut Vec<ValidationIssue>) {
+        for group in &self.exclusive {
+            let present: Vec<&Signal> = group.iter().filter(|s| s.is_present(ctx)).collect();
+            if present.len() < 2 {
+                continue;
+            }
+            let listed: Vec<String> = present.iter().map(ToString::to_string).collect();
+            let path = match present[0] {
+                Signal::Situational(dim, _) => format!("situational.{dim}"),
+                Signal::Personal(kind, _) => format!("personal.{kind}"),
+            };
+            issues.push(issue(
+                Severity::Warning,
+                IssueCode::ConflictingSignals,
+                path,
+                format!("conflicting signals: {}", listed.join(", ")),
+            ));
+        }
+    }
+}
+
+fn issue(severity: Severity, code: IssueCode, path: String, message: String) -> ValidationIssue {
+    ValidationIssue {
+        severity,
+        code,
+        path,
+        message,
+    }
+}
+
+fn strip_vs16(s: &str) -> String {
+    s.replace('\u{FE0F}', "")
+}
+
+/// `{tie}:{function}` with both halves non-empty.
+fn is_relationship_tag(tag: &str) -> bool {
+    tag.split_once(':').is_some_and(|(tie, function)| {
+        !tie.is_empty() && !function.is_empty() && !function.contains(':')
+    })
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::personal::PersonalDimension;
+
+    fn codes(issues: &[ValidationIssue]) -> Vec<IssueCode> {
+        issues.iter().map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn standard_context_is_clean() {
+        let ctx = FullContext::from_wire(
+            "\u{23F0}\u{1F305}|\u{1F4CD}\u{1F3E1}|\u{1F321}\u{FE0F}\u{2600}\u{FE0F}|\u{1FAA2}colleague:professional\u{2016}\u{1F9E0}focused:4|\u{1F4AD}calm:3",
+        )
+        .unwrap();
+        assert_eq!(ctx.validate(&ContextSchema::default()), vec![]);
+    }
+
+    #[test]
+    fn reports_bad_intensity_and_unknown_values() {
+        let mut ctx = FullContext::default();
+        ctx.personal.energy = Some(PersonalDimension {
+            value: "caffeinated".into(),
+            intensity: 0,
+            extended: Some(String::new()),
+            ..PersonalDimension::new("caffeinated", 1).unwrap()
+        });
+        ctx.situational.space = Some(vec!["\u{1F3D5}".into()]);
+
+        let issues = ctx.validate(&ContextSchema::default());
+        assert_eq!(
+            codes(&issues),
+            vec![
+                IssueCode::UnknownCategory,
+                IssueCode::InvalidIntensity,
+                IssueCode::UnknownCategory,
+                IssueCode::MalformedTag,
+            ]
+        );
+        assert_eq!(issues[1].path, "personal.energy_level.intensity");
+        assert_eq!(issues[0].path, "situational.space[0]");
+        assert_eq!(issues.iter().filter(|i| i.is_error()).count(), 1);
+
+        let strict = ctx.validate(&ContextSchema::default().strict());
+        assert_eq!(strict.iter().filter(|i| i.is_error()).count(), 3);
+    }
+
+    #[test]
+    fn schema_extensions_accept_custom_categories() {
+        let mut ctx = FullContext::default();
+        ctx.situational.space = Some(vec!["\u{1F3D5}".into()]);
+        ctx.personal.energy = Some(PersonalDimension::new("caffeinated", 4).unwrap());
+
+        let schema = ContextSchema::default()
+            .with_tag(SituationalDimension::Space, "\u{1F3D5}")
+            .with_value(PersonalDimensionKind::EnergyLevel, "caffeinated");
+        assert!(ctx.validate(&schema).is_empty());
+    }
+
+    #[test]
+    fn detects_conflicts_across_halves() {
+        let mut ctx = FullContext::default();
+        ctx.situational.occasion = Some(vec!["\u{1F6A8}".into()]);
+        ctx.personal.urgency = Some(PersonalDimension::new("unhurried", 2).unwrap());
+
+        let issues = ctx.validate(&ContextSchema::default());
+        assert_eq!(codes(&issues), vec![IssueCode::ConflictingSignals]);
+        assert!(issues[0]
+            .message
+            .contains("occasion=emergency, perceived_urgency=unhurried"));
+
+        let custom = ContextSchema::default().with_exclusive([
+            Signal::personal(PersonalDimensionKind::EnergyLevel, "rested"),
+            Signal::personal(PersonalDimensionKind::CognitiveState, "foggy"),
+        ]);
+        ctx.personal.energy = Some(PersonalDimension::new("rested", 4).unwrap());
+        ctx.personal.cognitive = Some(PersonalDimension::new("foggy", 3).unwrap());
+        assert_eq!(ctx.validate(&custom).len(), 2);
+    }
+
+    #[test]
+    fn structural_checks() {
+        let mut ctx = FullContext::default();
+        ctx.situational.company = Some(vec![]);
+        ctx.situational.time = Some(vec!["\u{1F305}".into(), "\u{1F305}".into()]);
+        ctx.situational.relationship = Some(vec!["mentor".into(), "friend:support".into()]);
+
+        let issues = ctx.validate(&ContextSchema::default());
+        assert_eq!(
+            codes(&issues),
+            vec![
+                IssueCode::DuplicateTag,
+                IssueCode::EmptyDimension,
+                IssueCode::MalformedTag,
+            ]
+        );
+        assert_eq!(issues[2].path, "situational.relationship[0]");
+        assert_eq!(
+            issues[0].to_string(),
+            format!(
+                "warning [duplicate_tag] situational.time[1]: \u{1F305} appears more than once"
+            )
+        );
+    }
+}
@@ -27,7 +27,10 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-use crate::error::{VcpError, VcpResult};
+use crate::context_schema::ValidationIssue;
+use crate::error::{Span, VcpError, VcpResult};
+use crate::goal::{ExperienceLevel, GoalField, GoalKind, GoalTaxonomy, InteractionStyle};
+use crate::identity::SemVer;
 use crate::personal::PersonalState;
 
 // ── Persona ─────────────────────────────────────────────────
@@ -97,6 +100,52 @@ impl Persona {
         }
     }
 
+    /// Lowercase name (e.g. `nanny`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Nanny => "nanny",
+            Self::Sentinel => "sentinel",
+            Self::Godparent => "godparent",
+            Self::Ambassador => "ambassador",
+            Self::Muse => "muse",
+            Self::Mediator => "mediator",
+            Self::Custom => "custom",
+        }
+    }
+
+    /// Parse a persona name (`nanny`) or code letter (`N`), ignoring case.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::InvalidPersona`] for an unknown code letter, or
+    /// [`VcpError::ParseError`] for an unknown name.
+    pub fn from_name(s: &str) -> VcpResult<Self> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Self::from_char(c);
+        }
+        Self::all()
+            .iter()
+            .copied()
+            .find(|p| p.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| VcpError::ParseError(format!("unknown persona: {s}")))
+    }
+
+    /// Rank in the restrictiveness order used by [`Csm1Code::merge`]:
+    /// Muse (0) < Ambassador < Godparent < Custom < Mediator < Sentinel
+    /// < Nanny (6).
+    pub fn restrictiveness(self) -> u8 {
+        match self {
+            Self::Muse => 0,
+            Self::Ambassador => 1,
+            Self::Godparent => 2,
+            Self::Custom => 3,
+            Self::Mediator => 4,
+            Self::Sentinel => 5,
+            Self::Nanny => 6,
+        }
+    }
+
     /// All persona variants.
     pub fn all() -> &'static [Persona] {
         &[
@@ -120,7 +169,7 @@ impl fmt::Display for Persona {
 // ── Scope ───────────────────────────────────────────────────
 
 /// Eleven context scopes for constitutional application.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Scope {
     Family,
     Work,
@@ -192,6 +241,58 @@ impl Scope {
             Self::General => "General purpose",
         }
     }
+
+    /// Lowercase name (e.g. `education`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Family => "family",
+            Self::Work => "work",
+            Self::Education => "education",
+            Self::Healthcare => "healthcare",
+            Self::Finance => "finance",
+            Self::Legal => "legal",
+            Self::Privacy => "privacy",
+            Self::Safety => "safety",
+            Self::Accessibility => "accessibility",
+            Self::Environment => "environment",
+            Self::General => "general",
+        }
+    }
+
+    /// Parse a scope name (`education`) or code letter (`E`), ignoring case.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::InvalidScope`] for an unknown code letter, or
+    /// [`VcpError::ParseError`] for an unknown name.
+    pub fn from_name(s: &str) -> VcpResult<Self> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Self::from_char(c);
+        }
+        Self::all()
+            .iter()
+            .copied()
+            .find(|scope| scope.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| VcpError::ParseError(format!("unknown scope: {s}")))
+    }
+
+    /// All scope variants.
+    pub fn all() -> &'static [Scope] {
+        &[
+            Self::Family,
+            Self::Work,
+            Self::Education,
+            Self::Healthcare,
+            Self::Finance,
+            Self::Legal,
+            Self::Privacy,
+            Self::Safety,
+            Self::Accessibility,
+            Self::Environment,
+            Self::General,
+        ]
+    }
 }
 
 impl fmt::Display for Scope {
@@ -234,7 +335,15 @@ impl Csm1Code {
     /// assert_eq!(code.adherence_level, 5);
     /// assert_eq!(code.scopes, vec![Scope::Family, Scope::Education]);
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.parse.csm1_code", level = "trace", skip_all, fields(code = raw), err(Display))
+    )]
     pub fn parse(raw: &str) -> VcpResult<Self> {
+        Self::parse_unlocated(raw).map_err(|e| locate_code_error(raw, e))
+    }
+
+    fn parse_unlocated(raw: &str) -> VcpResult<Self> {
         if raw.is_empty() {
             return Err(VcpError::ParseError("CSM1 code cannot be empty".into()));
         }
@@ -393,6 +502,152 @@ impl Csm1Code {
     pub fn is_maximum(&self) -> bool {
         self.adherence_level == 5
     }
+
+    /// Whether an agent holding this code can hand off to one holding
+    /// `other`: same namespace and major version, overlapping scopes, and
+    /// no behavior one side allows while the other forbids.
+    ///
+    /// See [`persona::check_compatibility`](crate::persona::check_compatibility)
+    /// for the reasons behind a `false`.
+    pub fn is_compatible_with(&self, other: &Csm1Code) -> bool {
+        crate::persona::check_compatibility(self, other).is_empty()
+    }
+
+    /// What the adherence level means, in a few words.
+    pub fn level_description(&self) -> &'static str {
+        match self.adherence_level {
+            0 => "Disabled",
+            1 => "Minimal constraints",
+            2 => "Light guidance",
+            3 => "Moderate guidance",
+            4 => "Strong guidance",
+            _ => "Maximum safety",
+        }
+    }
+}
+
+// ── Code Arithmetic ─────────────────────────────────────────
+
+/// Numeric ordering key for a `major.minor.patch` version, with the
+/// string itself as a tie-break so unparseable versions still order
+/// deterministically.
+fn version_key(version: &str) -> (Vec<u32>, &str) {
+    let parts = version
+        .split('.')
+        .map(|p| p.parse::<u32>().unwrap_or(0))
+        .collect();
+    (parts, version)
+}
+
+/// `scopes` deduplicated, in [`Scope::all`] order.
+fn canonical_scopes(scopes: impl IntoIterator<Item = Scope>) -> Vec<Scope> {
+    let set: Vec<Scope> = scopes.into_iter().collect();
+    Scope::all()
+        .iter()
+        .copied()
+        .filter(|s| set.contains(s))
+        .collect()
+}
+
+impl Csm1Code {
+    /// Combine two codes so the result is at least as restrictive as both.
+    ///
+    /// Each component is joined in its own lattice:
+    ///
+    /// | Component | Join |
+    /// |-----------|------|
+    /// | persona | higher [`Persona::restrictiveness`] |
+    /// | adherence level | maximum |
+    /// | scopes | union, where an empty list (all contexts) absorbs any other; result in [`Scope::all`] order |
+    /// | namespace | the one present; two different namespaces are an error |
+    /// | version | the higher `major.minor.patch` |
+    ///
+    /// Because every component is a join, `merge` is commutative,
+    /// associative and idempotent on codes whose scopes are already in
+    /// canonical order (as every merge result is). Errors are symmetric
+    /// too: a namespace conflict fails in any grouping.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if both codes carry a namespace and
+    /// they differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vcp_core::csm1::Csm1Code;
+    ///
+    /// let user = Csm1Code::parse("M2+F").unwrap();
+    /// let policy = Csm1Code::parse("Z4+W+F").unwrap();
+    /// assert_eq!(user.merge(&policy).unwrap().encode(), "Z4+F+W");
+    /// ```
+    pub fn merge(&self, other: &Csm1Code) -> VcpResult<Self> {
+        let persona = if other.persona.restrictiveness() > self.persona.restrictiveness() {
+            other.persona
+        } else {
+            self.persona
+        };
+
+        let scopes = if self.scopes.is_empty() || other.scopes.is_empty() {
+            Vec::new()
+        } else {
+            canonical_scopes(self.scopes.iter().chain(&other.scopes).copied())
+        };
+
+        let namespace = match (&self.namespace, &other.namespace) {
+            (Some(a), Some(b)) if a != b => {
+                let (first, second) = if a < b { (a, b) } else { (b, a) };
+                return Err(VcpError::ParseError(format!(
+                    "cannot merge namespaces {first} and {second}"
+                )));
+            }
+            (a, b) => a.clone().or_else(|| b.clone()),
+        };
+
+        let version = match (&self.version, &other.version) {
+            (Some(a), Some(b)) => Some(
+                if version_key(b) > version_key(a) {
+                    b
+                } else {
+                    a
+                }
+                .clone(),
+            ),
+            (a, b) => a.clone().or_else(|| b.clone()),
+        };
+
+        Ok(Csm1Code {
+            persona,
+            adherence_level: self.adherence_level.max(other.adherence_level),
+            scopes,
+            namespace,
+            version,
+        })
+    }
+
+    /// Narrow this code to the contexts in `scopes`.
+    ///
+    /// The result applies where both this code and `scopes` apply, with
+    /// scopes in [`Scope::all`] order. An unscoped code takes `scopes`
+    /// as-is; an empty `scopes` restricts nothing. Returns `None`
+    /// when the two share no scope, since an empty scope list would mean
+    /// "everywhere" rather than "nowhere".
+    ///
+    /// `restrict_to` is idempotent, and restricting by `a` then `b` equals
+    /// restricting by `b` then `a`.
+    pub fn restrict_to(&self, scopes: &[Scope]) -> Option<Self> {
+        let narrowed = match (self.scopes.is_empty(), scopes.is_empty()) {
+            (_, true) => canonical_scopes(self.scopes.iter().copied()),
+            (true, false) => canonical_scopes(scopes.iter().copied()),
+            (false, false) => {
+                canonical_scopes(self.scopes.iter().copied().filter(|s| scopes.contains(s)))
+            }
+        };
+        if narrowed.is_empty() && !(self.scopes.is_empty() && scopes.is_empty()) {
+            return None;
+        }
+        Some(self.with_scopes(narrowed))
+    }
 }
 
 impl fmt::Display for Csm1Code {
@@ -401,6 +656,264 @@ impl fmt::Display for Csm1Code {
     }
 }
 
+// ── Borrowed Compact Code ───────────────────────────────────
+
+/// Zero-allocation view of a CSM-1 compact code.
+///
+/// Accepts exactly the inputs [`Csm1Code::parse`] accepts, but keeps the
+/// scope list, namespace and version as slices of the input instead of
+/// upper-casing into owned strings. The namespace is therefore exactly as
+/// written; use [`Csm1CodeRef::to_code`] for the normalised owned form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Csm1CodeRef<'a> {
+    pub persona: Persona,
+    /// Adherence level 0-5 (0 = disabled, 5 = maximum).
+    pub adherence_level: u8,
+    /// Raw scope section, e.g. `+F+E`. Validated at parse time.
+    scopes: &'a str,
+    /// Namespace as written in the input (not upper-cased).
+    pub namespace: Option<&'a str>,
+    pub version: Option<&'a str>,
+}
+
+impl<'a> Csm1CodeRef<'a> {
+    /// Parse a compact CSM-1 code without allocating on success.
+    ///
+    /// # Errors
+    ///
+    /// Same conditions and messages as [`Csm1Code::parse`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vcp_core::csm1::{Csm1CodeRef, Scope};
+    ///
+    /// let code = Csm1CodeRef::parse("z3+p:sec@1.0.0").unwrap();
+    /// assert_eq!(code.namespace, Some("sec"));
+    /// assert_eq!(code.scopes().collect::<Vec<_>>(), vec![Scope::Privacy]);
+    /// assert_eq!(code.to_string(), "Z3+P:SEC@1.0.0");
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.parse.csm1_code", level = "trace", skip_all, fields(code = raw), err(Display))
+    )]
+    pub fn parse(raw: &'a str) -> VcpResult<Self> {
+        Self::parse_unlocated(raw).map_err(|e| locate_code_error(raw, e))
+    }
+
+    fn parse_unlocated(raw: &'a str) -> VcpResult<Self> {
+        if raw.is_empty() {
+            return Err(VcpError::ParseError("CSM1 code cannot be empty".into()));
+        }
+
+        // Mirror `Csm1Code::parse`, which reads persona and level from the
+        // upper-cased string: a character whose upper case expands (e.g.
+        // U+1E9A) supplies both.
+        let mut chars = raw.chars();
+        let first = chars.next().unwrap_or_default();
+        let mut first_upper = first.to_uppercase();
+        let persona_char = first_upper.next().unwrap_or(first);
+        let Some(level_char) = first_upper.next().or_else(|| chars.next()) else {
+            return Err(VcpError::ParseError(format!("CSM1 code too short: {raw}")));
+        };
+
+        let persona = Persona::from_char(persona_char)?;
+        let level = level_char.to_digit(10).and_then(|d| u8::try_from(d).ok());
+        let adherence_level = level
+            .filter(|&d| d <= 5)
+            .ok_or(VcpError::InvalidAdherence(level.unwrap_or(255)))?;
+
+        let remaining = &raw[first.len_utf8() + level_char.len_utf8()..];
+
+        let (before_version, version) = match remaining.find('@') {
+            Some(at_idx) => {
+                let v = &remaining[at_idx + 1..];
+                let mut parts = v.split('.');
+                let valid = (0..3).all(|_| parts.next().is_some_and(|p| p.parse::<u32>().is_ok()))
+                    && parts.next().is_none();
+                if !valid {
+                    return Err(VcpError::ParseError(format!(
+                        "invalid version: {}",
+                        v.to_uppercase()
+                    )));
+                }
+                (&remaining[..at_idx], Some(v))
+            }
+            None => (remaining, None),
+        };
+
+        let (scopes, namespace) = match before_version.find(':') {
+            Some(colon_idx) => {
+                let n = &before_version[colon_idx + 1..];
+                let starts_upper = n
+                    .chars()
+                    .next()
+                    .and_then(|c| c.to_uppercase().next())
+                    .is_some_and(|c| c.is_ascii_uppercase());
+                if !starts_upper {
+                    return Err(VcpError::ParseError(format!(
+                        "invalid namespace: {}",
+                        n.to_uppercase()
+                    )));
+                }
+                (&before_version[..colon_idx], Some(n))
+            }
+            None => (before_version, None),
+        };
+
+        for scope_str in scopes.split('+') {
+            if scope_str.is_empty() {
+                continue;
+            }
+            if scope_str.len() != 1 {
+                return Err(VcpError::ParseError(format!(
+                    "invalid scope token: {}",
+                    scope_str.to_uppercase()
+                )));
+            }
+            Scope::from_char(scope_str.as_bytes()[0].to_ascii_uppercase().into())?;
+        }
+
+        Ok(Self {
+            persona,
+            adherence_level,
+            scopes,
+            namespace,
+            version,
+        })
+    }
+
+    /// Iterate the scopes in written order.
+    pub fn scopes(&self) -> impl Iterator<Item = Scope> + 'a {
+        self.scopes
+            .split('+')
+            .filter_map(|s| s.chars().next())
+            .filter_map(|c| Scope::from_char(c).ok())
+    }
+
+    /// Whether this code applies to a given scope (empty = all scopes).
+    pub fn applies_to(&self, scope: Scope) -> bool {
+        let mut scopes = self.scopes().peekable();
+        scopes.peek().is_none() || scopes.any(|s| s == scope)
+    }
+
+    /// Convert to the owned, normalised form.
+    pub fn to_code(&self) -> Csm1Code {
+        Csm1Code::from(*self)
+    }
+}
+
+impl From<Csm1CodeRef<'_>> for Csm1Code {
+    fn from(code: Csm1CodeRef<'_>) -> Self {
+        Csm1Code {
+            persona: code.persona,
+            adherence_level: code.adherence_level,
+            scopes: code.scopes().collect(),
+            namespace: code.namespace.map(str::to_uppercase),
+            version: code.version.map(str::to_string),
+        }
+    }
+}
+
+impl fmt::Display for Csm1CodeRef<'_> {
+    /// Writes the same string as [`Csm1Code::encode`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.persona.code(), self.adherence_level)?;
+        for scope in self.scopes() {
+            write!(f, "+{}", scope.code())?;
+        }
+        if let Some(ns) = self.namespace {
+            f.write_str(":")?;
+            for c in ns.chars().flat_map(char::to_uppercase) {
+                write!(f, "{c}")?;
+            }
+        }
+        if let Some(v) = self.version {
+            write!(f, "@{v}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Csm1Code {
+    /// Parse into a borrowed [`Csm1CodeRef`] without allocating.
+    ///
+    /// # Errors
+    ///
+    /// See [`Csm1CodeRef::parse`].
+    pub fn parse_borrowed(raw: &str) -> VcpResult<Csm1CodeRef<'_>> {
+        Csm1CodeRef::parse(raw)
+    }
+}
+
+// ── Error spans ─────────────────────────────────────────────
+
+const PERSONA_EXPECTED: &str = "persona letter (N Z G A M D C)";
+const LEVEL_EXPECTED: &str = "adherence level 0-5";
+const SCOPE_EXPECTED: &str = "scope letter (F W E H I L P S A V G)";
+const NAMESPACE_EXPECTED: &str = "namespace starting with A-Z";
+const VERSION_EXPECTED: &str = "version MAJOR.MINOR.PATCH";
+
+/// Pin a compact-code parse error to the component of `raw` it came from.
+///
+/// Both parsers report errors by kind alone; the location is recovered
+/// from the layout of `raw`, so owned and borrowed parsing stay in step.
+fn locate_code_error(raw: &str, err: VcpError) -> VcpError {
+    let mut chars = raw.char_indices();
+    let Some((_, first)) = chars.next() else {
+        return err.at(Span::new(0, 0)).expecting(&[PERSONA_EXPECTED]);
+    };
+    let persona = Span::new(0, first.len_utf8());
+    // A first character whose upper case expands supplies the level too.
+    let level = if first.to_uppercase().nth(1).is_some() {
+        persona
+    } else {
+        match chars.next() {
+            Some((i, c)) => Span::new(i, c.len_utf8()),
+            None => {
+                return err.at(Span::new(raw.len(), 0)).expecting(&[LEVEL_EXPECTED]);
+            }
+        }
+    };
+
+    let remaining = &raw[level.end()..];
+    let (before_version, version) = match remaining.find('@') {
+        Some(i) => (&remaining[..i], &remaining[i + 1..]),
+        None => (remaining, &raw[raw.len()..]),
+    };
+    let (scopes, namespace) = match before_version.find(':') {
+        Some(i) => (&before_version[..i], &before_version[i + 1..]),
+        None => (before_version, &before_version[before_version.len()..]),
+    };
+    let bad_scope = || {
+        scopes
+            .split('+')
+            .find(|s| {
+                !s.is_empty()
+                    && (s.len() != 1
+                        || Scope::from_char(s.as_bytes()[0].to_ascii_uppercase().into()).is_err())
+            })
+            .unwrap_or(scopes)
+    };
+
+    let (span, expected) = match &err {
+        VcpError::InvalidPersona(_) => (persona, PERSONA_EXPECTED),
+        VcpError::InvalidAdherence(_) => (level, LEVEL_EXPECTED),
+        VcpError::InvalidScope(_) => (Span::of(raw, bad_scope()), SCOPE_EXPECTED),
+        VcpError::ParseError(msg) if msg.starts_with("invalid scope token") => {
+            (Span::of(raw, bad_scope()), SCOPE_EXPECTED)
+        }
+        VcpError::ParseError(msg) if msg.starts_with("invalid namespace") => {
+            (Span::of(raw, namespace), NAMESPACE_EXPECTED)
+        }
+        VcpError::ParseError(msg) if msg.starts_with("invalid version") => {
+            (Span::of(raw, version), VERSION_EXPECTED)
+        }
+        _ => return err,
+    };
+    err.at(span).expecting(&[expected])
+}
+
 // ── CSM-1 8-line Token ──────────────────────────────────────
 
 /// Reference to a constitution with version.
@@ -418,6 +931,46 @@ pub struct GoalContext {
     pub style: String,
 }
 
+impl GoalContext {
+    /// Check the three fields against `taxonomy`, returning every value
+    /// it does not know. Empty fields (partial goals) are not reported.
+    pub fn validate(&self, taxonomy: &GoalTaxonomy) -> Vec<ValidationIssue> {
+        taxonomy.check(self)
+    }
+
+    /// A copy with each field in canonical form under the built-in
+    /// [`GoalTaxonomy`]: lowercased, `_`-separated, synonyms replaced.
+    #[must_use]
+    pub fn normalize(&self) -> Self {
+        self.normalize_with(&GoalTaxonomy::default())
+    }
+
+    /// As [`normalize`](Self::normalize), under `taxonomy`.
+    #[must_use]
+    pub fn normalize_with(&self, taxonomy: &GoalTaxonomy) -> Self {
+        Self {
+            goal: taxonomy.normalize_term(GoalField::Goal, &self.goal),
+            experience: taxonomy.normalize_term(GoalField::Experience, &self.experience),
+            style: taxonomy.normalize_term(GoalField::Style, &self.style),
+        }
+    }
+
+    /// The goal as a typed term.
+    pub fn goal_kind(&self) -> GoalKind {
+        GoalKind::from(self.goal.as_str())
+    }
+
+    /// The experience as a typed term.
+    pub fn experience_level(&self) -> ExperienceLevel {
+        ExperienceLevel::from(self.experience.as_str())
+    }
+
+    /// The style as a typed term.
+    pub fn interaction_style(&self) -> InteractionStyle {
+        InteractionStyle::from(self.style.as_str())
+    }
+}
+
 /// Constraint flag for line 5 of the 8-line token.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ConstraintFlag(pub String);
@@ -434,6 +987,11 @@ pub struct ConstraintFlag(pub String);
 /// S:internal-marker
 /// R:focused:4|calm:3
 /// ```
+///
+/// Lines after these, from newer protocol versions, are kept in
+/// [`extensions`](Self::extensions) and written back by
+/// [`encode`](Self::encode), so a proxy on an older SDK passes them
+/// through intact.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Csm1Token {
     /// Protocol version (e.g. "1.0").
@@ -458,9 +1016,18 @@ pub struct Csm1Token {
     /// Personal state (line 8, optional v1.1).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub personal_state: Option<PersonalState>,
+    /// Unrecognised `<key>:<value>` lines after line 7 (and line 8, when
+    /// present), in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<(String, String)>,
 }
 
 impl Csm1Token {
+    /// A validating [`Csm1TokenBuilder`].
+    pub fn builder() -> Csm1TokenBuilder {
+        Csm1TokenBuilder::default()
+    }
+
     /// Parse an 8-line CSM-1 token string.
     ///
     /// # Errors
@@ -470,6 +1037,10 @@ impl Csm1Token {
     /// adherence, goal, constraint, flag, or personal-state fields are
     /// malformed.
     #[allow(clippy::too_many_lines)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.parse.csm1_token", level = "trace", skip_all, fields(bytes = raw.len()), err(Display))
+    )]
     pub fn parse(raw: &str) -> VcpResult<Self> {
         let token_lines: Vec<&str> = raw.lines().collect();
 
@@ -477,49 +1048,66 @@ impl Csm1Token {
             return Err(VcpError::ParseError(format!(
                 "CSM1 token requires at least 7 lines, got {}",
                 token_lines.len()
-            )));
+            ))
+            .at(Span::new(raw.len(), 0)));
         }
 
         // Line 1: VCP:<version>:<profile-id>
-        let vcp_header = Self::strip_and_validate(token_lines[0], "VCP:")?;
+        let vcp_header = Self::strip_and_validate(raw, token_lines[0], "VCP:")?;
         let (version, profile_id) = vcp_header.split_once(':').ok_or_else(|| {
             VcpError::ParseError(format!(
                 "line 1 missing profile-id separator: {}",
                 token_lines[0]
             ))
+            .at(Span::of(raw, vcp_header))
+            .expecting(&["<version>:<profile-id>"])
         })?;
 
         // Line 2: C:<constitution>@<version>
-        let const_line = Self::strip_and_validate(token_lines[1], "C:")?;
+        let const_line = Self::strip_and_validate(raw, token_lines[1], "C:")?;
         let (const_id, const_ver) = const_line.split_once('@').ok_or_else(|| {
             VcpError::ParseError(format!(
                 "line 2 missing version separator: {}",
                 token_lines[1]
             ))
+            .at(Span::of(raw, const_line))
+            .expecting(&["<constitution>@<version>"])
         })?;
 
         // Line 3: P:<persona>:<adherence>
-        let persona_line = Self::strip_and_validate(token_lines[2], "P:")?;
+        let persona_line = Self::strip_and_validate(raw, token_lines[2], "P:")?;
         let (persona_str, adherence_str) = persona_line.split_once(':').ok_or_else(|| {
             VcpError::ParseError(format!(
                 "line 3 missing adherence separator: {}",
                 token_lines[2]
             ))
+            .at(Span::of(raw, persona_line))
+            .expecting(&["<persona>:<adherence>"])
         })?;
+        let at_persona = |e: VcpError| {
+            e.at(Span::of(raw, persona_str))
+                .expecting(&[PERSONA_EXPECTED])
+        };
         let persona_char = persona_str
             .chars()
             .next()
-            .ok_or_else(|| VcpError::ParseError("empty persona in line 3".into()))?;
-        let persona = Persona::from_char(persona_char)?;
-        let adherence: u8 = adherence_str
-            .parse()
-            .map_err(|_| VcpError::ParseError(format!("invalid adherence: {adherence_str}")))?;
+            .ok_or_else(|| at_persona(VcpError::ParseError("empty persona in line 3".into())))?;
+        let persona = Persona::from_char(persona_char).map_err(at_persona)?;
+        let at_adherence = |e: VcpError| {
+            e.at(Span::of(raw, adherence_str))
+                .expecting(&["adherence level 1-5"])
+        };
+        let adherence: u8 = adherence_str.parse().map_err(|_| {
+            at_adherence(VcpError::ParseError(format!(
+                "invalid adherence: {adherence_str}"
+            )))
+        })?;
         if !(1..=5).contains(&adherence) {
-            return Err(VcpError::InvalidAdherence(adherence));
+            return Err(at_adherence(VcpError::InvalidAdherence(adherence)));
         }
 
         // Line 4: G:<goal>:<experience>:<style>
-        let goal_line = Self::strip_and_validate(token_lines[3], "G:")?;
+        let goal_line = Self::strip_and_validate(raw, token_lines[3], "G:")?;
         let goal = if goal_line.is_empty() {
             None
         } else {
@@ -541,7 +1129,7 @@ impl Csm1Token {
         };
 
         // Line 5: X:<constraints>
-        let constraint_line = Self::strip_and_validate(token_lines[4], "X:")?;
+        let constraint_line = Self::strip_and_validate(raw, token_lines[4], "X:")?;
         let constraints = if constraint_line.is_empty() {
             Vec::new()
         } else {
@@ -552,7 +1140,7 @@ impl Csm1Token {
         };
 
         // Line 6: F:<flags>
-        let flags_line = Self::strip_and_validate(token_lines[5], "F:")?;
+        let flags_line = Self::strip_and_validate(raw, token_lines[5], "F:")?;
         let flags = if flags_line.is_empty() {
             Vec::new()
         } else {
@@ -563,7 +1151,7 @@ impl Csm1Token {
         };
 
         // Line 7: S:<private-markers>
-        let markers_line = Self::strip_and_validate(token_lines[6], "S:")?;
+        let markers_line = Self::strip_and_validate(raw, token_lines[6], "S:")?;
         let private_markers = if markers_line.is_empty() {
             Vec::new()
         } else {
@@ -574,17 +1162,37 @@ impl Csm1Token {
         };
 
         // Line 8 (optional): R:<personal-state>
-        let personal_state = if token_lines.len() > 7 {
-            let state_line = Self::strip_and_validate(token_lines[7], "R:")?;
-            if state_line.is_empty() {
-                None
-            } else {
-                Some(PersonalState::from_wire(state_line)?)
+        let mut rest = token_lines[7..].iter();
+        let personal_state = match token_lines.get(7).and_then(|l| l.strip_prefix("R:")) {
+            Some(state_line) => {
+                rest.next();
+                if state_line.is_empty() {
+                    None
+                } else {
+                    Some(
+                        PersonalState::from_wire(state_line)
+                            .map_err(|e| e.at(Span::of(raw, state_line)))?,
+                    )
+                }
             }
-        } else {
-            None
+            None => None,
         };
 
+        // Anything after: <key>:<value> lines from newer versions.
+        let extensions = rest
+            .map(|line| {
+                let (key, value) = line
+                    .split_once(':')
+                    .filter(|(key, _)| is_extension_key(key))
+                    .ok_or_else(|| {
+                        VcpError::ParseError(format!("unexpected line in CSM1 token: {line}"))
+                            .at(Span::of(raw, line))
+                            .expecting(&["<key>:<value> extension line"])
+                    })?;
+                Ok((key.to_string(), value.to_string()))
+            })
+            .collect::<VcpResult<Vec<_>>>()?;
+
         Ok(Csm1Token {
             version: version.to_string(),
             profile_id: profile_id.to_string(),
@@ -599,10 +1207,12 @@ impl Csm1Token {
             flags,
             private_markers,
             personal_state,
+            extensions,
         })
     }
 
-    /// Encode to 8-line (or 7-line) string.
+    /// Encode to 8-line (or 7-line) string, followed by any extension
+    /// lines.
     pub fn encode(&self) -> String {
         let mut lines = Vec::with_capacity(8);
 
@@ -640,15 +1250,21 @@ impl Csm1Token {
             lines.push(format!("R:{}", ps.to_wire()));
         }
 
+        for (key, value) in &self.extensions {
+            lines.push(format!("{key}:{value}"));
+        }
+
         lines.join("\n")
     }
 
     /// Helper: strip a required prefix from a line.
-    fn strip_and_validate<'a>(line: &'a str, prefix: &str) -> VcpResult<&'a str> {
+    fn strip_and_validate<'a>(raw: &str, line: &'a str, prefix: &str) -> VcpResult<&'a str> {
         line.strip_prefix(prefix).ok_or_else(|| {
             VcpError::ParseError(format!(
                 "expected line to start with '{prefix}', got: {line}"
             ))
+            .at(Span::of(raw, line))
+            .expecting(&[&format!("line starting with '{prefix}'")])
         })
     }
 }
@@ -659,6 +1275,253 @@ impl fmt::Display for Csm1Token {
     }
 }
 
+// ── Token builder ───────────────────────────────────────────
+
+/// Builds a [`Csm1Token`] that encodes and parses back unchanged.
+///
+/// Each setter checks its input against the token grammar as it is
+/// called; the first failure is kept and returned by
+/// [`build`](Self::build), which also requires a profile ID, constitution
+/// and persona. Anything else unset defaults to version `1.0`, adherence
+/// 3, no goal, no personal state and empty lists.
+///
+/// ```
+/// use vcp_core::csm1::{Csm1Token, Persona};
+///
+/// let token = Csm1Token::builder()
+///     .profile_id("profile-123")
+///     .constitution("family-safe", "1.2.0")
+///     .persona(Persona::Nanny)
+///     .adherence(5)
+///     .goal("protect", "guided", "gentle")
+///     .constraint("no-profanity")
+///     .build()
+///     .unwrap();
+/// assert_eq!(Csm1Token::parse(&token.encode()).unwrap(), token);
+///
+/// assert!(Csm1Token::builder().adherence(0).build().is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct Csm1TokenBuilder {
+    version: Option<String>,
+    profile_id: Option<String>,
+    constitution: Option<ConstitutionRef>,
+    persona: Option<Persona>,
+    adherence: Option<u8>,
+    goal: Option<GoalContext>,
+    constraints: Vec<ConstraintFlag>,
+    flags: Vec<String>,
+    private_markers: Vec<String>,
+    personal_state: Option<PersonalState>,
+    extensions: Vec<(String, String)>,
+    error: Option<VcpError>,
+}
+
+impl Csm1TokenBuilder {
+    /// Default protocol version.
+    pub const DEFAULT_VERSION: &'static str = "1.0";
+    /// Default adherence level.
+    pub const DEFAULT_ADHERENCE: u8 = 3;
+
+    /// Protocol version, `MAJOR.MINOR`.
+    pub fn version(mut self, version: &str) -> Self {
+        let valid = version
+            .split_once('.')
+            .is_some_and(|(major, minor)| is_number(major) && is_number(minor));
+        if valid {
+            self.version = Some(version.to_string());
+        } else {
+            self.fail(malformed("version", version, "expected MAJOR.MINOR"));
+        }
+        self
+    }
+
+    /// Profile identifier (line 1).
+    pub fn profile_id(mut self, profile_id: &str) -> Self {
+        if let Err(e) = check_field("profile ID", profile_id, &[]) {
+            self.fail(e);
+        } else {
+            self.profile_id = Some(profile_id.to_string());
+        }
+        self
+    }
+
+    /// Constitution ID and `X.Y.Z` version (line 2).
+    pub fn constitution(mut self, id: &str, version: &str) -> Self {
+        let checked = check_field("constitution ID", id, &['@']).and_then(|()| {
+            SemVer::parse(version).map_err(|_| {
+                malformed(
+                    "constitution version",
+                    version,
+                    "expected MAJOR.MINOR.PATCH",
+                )
+            })
+        });
+        match checked {
+            Ok(semver) => {
+                self.constitution = Some(ConstitutionRef {
+                    id: id.to_string(),
+                    version: semver.to_string(),
+                });
+            }
+            Err(e) => self.fail(e),
+        }
+        self
+    }
+
+    /// Persona (line 3).
+    pub fn persona(mut self, persona: Persona) -> Self {
+        self.persona = Some(persona);
+        self
+    }
+
+    /// Adherence level 1-5 (line 3).
+    pub fn adherence(mut self, adherence: u8) -> Self {
+        if (1..=5).contains(&adherence) {
+            self.adherence = Some(adherence);
+        } else {
+            self.fail(VcpError::InvalidAdherence(adherence));
+        }
+        self
+    }
+
+    /// Goal, experience and style (line 4). Any may be empty.
+    pub fn goal(mut self, goal: &str, experience: &str, style: &str) -> Self {
+        let checked = [("goal", goal), ("experience", experience), ("style", style)]
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .try_for_each(|(what, value)| check_field(what, value, &[':']));
+        match checked {
+            Ok(()) => {
+                self.goal = Some(GoalContext {
+                    goal: goal.to_string(),
+                    experience: experience.to_string(),
+                    style: style.to_string(),
+                });
+            }
+            Err(e) => self.fail(e),
+        }
+        self
+    }
+
+    /// Add a constraint flag (line 5).
+    pub fn constraint(mut self, constraint: &str) -> Self {
+        match check_field("constraint", constraint, &[',']) {
+            Ok(()) => self
+                .constraints
+                .push(ConstraintFlag(constraint.to_string())),
+            Err(e) => self.fail(e),
+        }
+        self
+    }
+
+    /// Add a feature flag (line 6).
+    pub fn flag(mut self, flag: &str) -> Self {
+        match check_field("flag", flag, &[',']) {
+            Ok(()) => self.flags.push(flag.to_string()),
+            Err(e) => self.fail(e),
+        }
+        self
+    }
+
+    /// Add a private marker (line 7).
+    pub fn private_marker(mut self, marker: &str) -> Self {
+        match check_field("private marker", marker, &[',']) {
+            Ok(()) => self.private_markers.push(marker.to_string()),
+            Err(e) => self.fail(e),
+        }
+        self
+    }
+
+    /// Personal state (line 8). An empty state is left off.
+    pub fn personal_state(mut self, state: PersonalState) -> Self {
+        self.personal_state = state.has_any().then_some(state);
+        self
+    }
+
+    /// Add an extension line, `<key>:<value>`, after the standard lines.
+    /// Keys are letters, digits and `-`, and may not reuse a standard
+    /// line's key.
+    pub fn extension(mut self, key: &str, value: &str) -> Self {
+        if !is_extension_key(key) {
+            self.fail(malformed("extension key", key, "expected a new line key"));
+        } else if value.contains(['\n', '\r']) {
+            self.fail(malformed("extension value", value, "contains a line break"));
+        } else {
+            self.extensions.push((key.to_string(), value.to_string()));
+        }
+        self
+    }
+
+    /// The token.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from a setter: [`VcpError::InvalidAdherence`]
+    /// for adherence outside 1-5, otherwise [`VcpError::MalformedToken`].
+    /// Returns [`VcpError::MalformedToken`] if the profile ID, constitution
+    /// or persona was never set.
+    pub fn build(self) -> VcpResult<Csm1Token> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let missing = |what: &str| VcpError::MalformedToken(format!("CSM-1 token requires {what}"));
+        Ok(Csm1Token {
+            version: self
+                .version
+                .unwrap_or_else(|| Self::DEFAULT_VERSION.to_string()),
+            profile_id: self.profile_id.ok_or_else(|| missing("a profile ID"))?,
+            constitution: self.constitution.ok_or_else(|| missing("a constitution"))?,
+            persona: self.persona.ok_or_else(|| missing("a persona"))?,
+            adherence: self.adherence.unwrap_or(Self::DEFAULT_ADHERENCE),
+            goal: self.goal,
+            constraints: self.constraints,
+            flags: self.flags,
+            private_markers: self.private_markers,
+            personal_state: self.personal_state,
+            extensions: self.extensions,
+        })
+    }
+
+    fn fail(&mut self, error: VcpError) {
+        self.error.get_or_insert(error);
+    }
+}
+
+/// Keys of the standard lines, which cannot be reused by extensions.
+const STANDARD_LINE_KEYS: &[&str] = &["VCP", "C", "P", "G", "X", "F", "S", "R"];
+
+/// `true` for a key an extension line may use.
+fn is_extension_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        && !STANDARD_LINE_KEYS.contains(&key)
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn malformed(what: &str, value: &str, why: &str) -> VcpError {
+    VcpError::MalformedToken(format!("invalid {what} '{value}': {why}"))
+}
+
+/// A token field must be non-empty, free of whitespace and control
+/// characters, and free of the separators in `reserved`.
+fn check_field(what: &str, value: &str, reserved: &[char]) -> VcpResult<()> {
+    if value.is_empty() {
+        return Err(VcpError::MalformedToken(format!("{what} cannot be empty")));
+    }
+    if let Some(c) = value
+        .chars()
+        .find(|c| c.is_whitespace() || c.is_control() || reserved.contains(c))
+    {
+        return Err(malformed(what, value, &format!("contains {c:?}")));
+    }
+    Ok(())
+}
+
 // ── Tests ───────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -728,6 +1591,21 @@ mod tests {
         assert!(Scope::from_char('X').is_err());
     }
 
+    #[test]
+    fn names_round_trip() {
+        for p in Persona::all() {
+            assert_eq!(Persona::from_name(p.name()).unwrap(), *p);
+            assert_eq!(Persona::from_name(&p.code().to_string()).unwrap(), *p);
+        }
+        for scope in Scope::all() {
+            assert_eq!(Scope::from_name(scope.name()).unwrap(), *scope);
+            assert_eq!(Scope::from_name(&scope.code().to_string()).unwrap(), *scope);
+        }
+        assert_eq!(Scope::from_name("Education").unwrap(), Scope::Education);
+        assert!(Persona::from_name("nanni").is_err());
+        assert!(Scope::from_name("").is_err());
+    }
+
     // ── Compact Code Parsing ────────────────────────────
 
     #[test]
@@ -909,6 +1787,102 @@ mod tests {
         assert!(Csm1Code::parse("N5").unwrap().is_maximum());
     }
 
+    // ── Borrowed Compact Code ───────────────────────────
+
+    #[test]
+    fn merge_takes_most_restrictive() {
+        let user = Csm1Code::parse("M2+F@1.2.0").unwrap();
+        let policy = Csm1Code::parse("G4+W+F:ORG@1.10.0").unwrap();
+        let merged = user.merge(&policy).unwrap();
+        assert_eq!(merged.encode(), "G4+F+W:ORG@1.10.0");
+        assert_eq!(policy.merge(&user).unwrap(), merged);
+
+        // Unscoped means everywhere, which absorbs any scope list.
+        let everywhere = Csm1Code::parse("N1").unwrap();
+        assert_eq!(everywhere.merge(&user).unwrap().encode(), "N2@1.2.0");
+    }
+
+    #[test]
+    fn merge_rejects_namespace_conflict() {
+        let a = Csm1Code::parse("Z3:SEC").unwrap();
+        let b = Csm1Code::parse("Z3:OPS").unwrap();
+        assert!(matches!(a.merge(&b), Err(VcpError::ParseError(_))));
+    }
+
+    #[test]
+    fn restrict_to_intersects_scopes() {
+        let code = Csm1Code::parse("N5+W+F+E").unwrap();
+        let narrowed = code
+            .restrict_to(&[Scope::Education, Scope::Family])
+            .unwrap();
+        assert_eq!(narrowed.encode(), "N5+F+E");
+        assert_eq!(code.restrict_to(&[Scope::Legal]), None);
+        assert_eq!(code.restrict_to(&[]).unwrap().encode(), "N5+F+W+E");
+
+        let unscoped = Csm1Code::parse("A3").unwrap();
+        assert_eq!(
+            unscoped.restrict_to(&[Scope::Work]).unwrap().encode(),
+            "A3+W"
+        );
+        assert_eq!(unscoped.restrict_to(&[]).unwrap(), unscoped);
+    }
+
+    #[test]
+    fn borrowed_matches_owned() {
+        let inputs = [
+            "N5",
+            "n5+f+e",
+            "Z3+P:SEC",
+            "z3+p:sec@1.0.0",
+            "M2@1.0.0",
+            "C0+G+V:ACME1",
+            "N5+",
+            "N5++F",
+            "",
+            "N",
+            "X5",
+            "N6",
+            "NX",
+            "N5+Q",
+            "N5+FF",
+            "N5:",
+            "N5:1A",
+            "N5@1.0",
+            "N5@1.0.x",
+            "N5@1.0.0.0",
+            "N5:ns@a.b.c",
+            "\u{e9}5",
+            "N5:\u{df}x",
+            "\u{1e9a}",
+            "\u{1e9a}1VCP:ZZ0@Z",
+        ];
+        for raw in inputs {
+            let owned = Csm1Code::parse(raw);
+            let borrowed = Csm1Code::parse_borrowed(raw).map(|c| c.to_code());
+            assert_eq!(owned, borrowed, "input {raw:?}");
+        }
+    }
+
+    #[test]
+    fn borrowed_keeps_input_slices() {
+        let raw = String::from("d4+w+l:org7@2.1.0");
+        let code = Csm1CodeRef::parse(&raw).unwrap();
+        assert_eq!(code.persona, Persona::Mediator);
+        assert_eq!(code.adherence_level, 4);
+        assert_eq!(code.namespace, Some("org7"));
+        assert_eq!(code.version, Some("2.1.0"));
+        assert!(code.applies_to(Scope::Legal));
+        assert!(!code.applies_to(Scope::Family));
+        assert_eq!(code.to_string(), Csm1Code::parse(&raw).unwrap().encode());
+    }
+
+    #[test]
+    fn borrowed_empty_scope_section_applies_everywhere() {
+        let code = Csm1CodeRef::parse("N5+").unwrap();
+        assert!(code.applies_to(Scope::Finance));
+        assert_eq!(code.to_string(), "N5");
+    }
+
     // ── 8-line Token ────────────────────────────────────
 
     const SAMPLE_TOKEN_7: &str = "\
@@ -996,4 +1970,152 @@ R:\u{1F9E0}focused:4|\u{1F4AD}calm:3";
         let bad = SAMPLE_TOKEN_7.replace("P:N:5", "P:N:9");
         assert!(Csm1Token::parse(&bad).is_err());
     }
+
+    #[test]
+    fn code_errors_point_at_component() {
+        for (raw, offset, len, expected) in [
+            ("", 0, 0, PERSONA_EXPECTED),
+            ("N", 1, 0, LEVEL_EXPECTED),
+            ("Q5", 0, 1, PERSONA_EXPECTED),
+            ("N9+F", 1, 1, LEVEL_EXPECTED),
+            ("N5+F+X", 5, 1, SCOPE_EXPECTED),
+            ("N5+FE", 3, 2, SCOPE_EXPECTED),
+            ("N5+F:9X", 5, 2, NAMESPACE_EXPECTED),
+            ("N5:ORG@1.x", 7, 3, VERSION_EXPECTED),
+        ] {
+            let err = Csm1Code::parse(raw).unwrap_err();
+            assert_eq!(err.span(), Some(Span::new(offset, len)), "{raw:?}");
+            assert_eq!(err.expected(), [expected], "{raw:?}");
+            assert_eq!(Csm1CodeRef::parse(raw).unwrap_err(), err, "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn token_errors_point_into_the_line() {
+        let bad = SAMPLE_TOKEN_7.replace("P:N:5", "P:N:9");
+        let err = Csm1Token::parse(&bad).unwrap_err();
+        assert_eq!(err.without_span(), &VcpError::InvalidAdherence(9));
+        let at = bad.find("P:N:9").unwrap() + 4;
+        assert_eq!(err.span(), Some(Span::new(at, 1)));
+        assert!(err
+            .render(&bad)
+            .contains("3 | P:N:9\n  |     ^ expected adherence level 1-5"));
+
+        let bad = SAMPLE_TOKEN_7.replace("G:", "Q:");
+        let err = Csm1Token::parse(&bad).unwrap_err();
+        assert_eq!(err.expected(), ["line starting with 'G:'"]);
+        assert_eq!(err.span().unwrap().offset, bad.find("Q:").unwrap());
+    }
+
+    #[test]
+    fn unknown_trailing_lines_round_trip() {
+        let newer = format!("{SAMPLE_TOKEN_8}\nT:tz=Europe/Berlin\nQ2:a:b:c");
+        let token = Csm1Token::parse(&newer).unwrap();
+        assert!(token.personal_state.is_some());
+        assert_eq!(
+            token.extensions,
+            [
+                ("T".to_string(), "tz=Europe/Berlin".to_string()),
+                ("Q2".to_string(), "a:b:c".to_string()),
+            ]
+        );
+        assert_eq!(token.encode(), newer);
+
+        // Without personal state, extensions follow line 7 directly.
+        let newer = format!("{SAMPLE_TOKEN_7}\nT:tz=UTC");
+        let token = Csm1Token::parse(&newer).unwrap();
+        assert!(token.personal_state.is_none());
+        assert_eq!(token.extensions.len(), 1);
+        assert_eq!(token.encode(), newer);
+
+        for bad in ["no separator", "R:\u{1F9E0}focused:4", ":empty-key"] {
+            let raw = format!("{SAMPLE_TOKEN_8}\n{bad}");
+            let err = Csm1Token::parse(&raw).unwrap_err();
+            assert_eq!(
+                err.span().unwrap().offset,
+                SAMPLE_TOKEN_8.len() + 1,
+                "{bad}"
+            );
+        }
+    }
+
+    // ── Token builder ───────────────────────────────────
+
+    fn minimal_builder() -> Csm1TokenBuilder {
+        Csm1Token::builder()
+            .profile_id("profile-123")
+            .constitution("family-safe", "1.2.0")
+            .persona(Persona::Nanny)
+    }
+
+    #[test]
+    fn builder_fills_defaults_and_round_trips() {
+        let token = minimal_builder().build().unwrap();
+        assert_eq!(token.version, "1.0");
+        assert_eq!(token.adherence, 3);
+        assert!(token.goal.is_none() && token.personal_state.is_none());
+        assert_eq!(Csm1Token::parse(&token.encode()).unwrap(), token);
+
+        let full = minimal_builder()
+            .version("1.1")
+            .adherence(5)
+            .goal("learn", "", "visual")
+            .constraint("no-profanity")
+            .flag("coppa")
+            .flag("gdpr")
+            .private_marker("internal")
+            .personal_state(PersonalState::from_wire("\u{1F9E0}focused:4").unwrap())
+            .extension("T", "tz=UTC")
+            .build()
+            .unwrap();
+        assert_eq!(full.flags, ["coppa", "gdpr"]);
+        assert_eq!(Csm1Token::parse(&full.encode()).unwrap(), full);
+    }
+
+    #[test]
+    fn builder_rejects_what_would_not_round_trip() {
+        let err = |builder: Csm1TokenBuilder| builder.build().unwrap_err();
+
+        assert_eq!(
+            err(minimal_builder().adherence(0)),
+            VcpError::InvalidAdherence(0)
+        );
+        assert!(matches!(
+            err(minimal_builder().profile_id("")),
+            VcpError::MalformedToken(_)
+        ));
+        for version in ["1.2", "v1.2.0", "1.2.x"] {
+            assert!(
+                minimal_builder()
+                    .constitution("family-safe", version)
+                    .build()
+                    .is_err(),
+                "{version}"
+            );
+        }
+        assert!(minimal_builder()
+            .constitution("a@b", "1.0.0")
+            .build()
+            .is_err());
+        assert!(minimal_builder()
+            .goal("learn:guitar", "", "")
+            .build()
+            .is_err());
+        assert!(minimal_builder().flag("a,b").build().is_err());
+        assert!(minimal_builder()
+            .private_marker("two words")
+            .build()
+            .is_err());
+        assert!(minimal_builder().version("one").build().is_err());
+        assert!(minimal_builder().extension("R", "x").build().is_err());
+        assert!(minimal_builder().extension("T", "a\nb").build().is_err());
+
+        // The first bad setter wins, even if a later one is fine.
+        assert_eq!(
+            err(minimal_builder().adherence(9).adherence(4).profile_id("")),
+            VcpError::InvalidAdherence(9)
+        );
+        let missing = err(Csm1Token::builder().profile_id("p").persona(Persona::Muse));
+        assert!(missing.to_string().contains("constitution"), "{missing}");
+    }
 }
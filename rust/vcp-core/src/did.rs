@@ -0,0 +1,619 @@
+//! DID-based issuer and auditor identities.
+//!
+//! A manifest's `issuer.id` (or an attestation's `auditor`) may be a
+//! [DID](https://www.w3.org/TR/did-core/) instead of a domain-style name.
+//! [`DidResolver`] turns the DID into a [`DidDocument`] listing its Ed25519
+//! keys, and [`DidResolver::trust_issuer`] / [`DidResolver::trust_auditor`]
+//! add those keys to a [`TrustConfig`] under the DID, where the
+//! orchestrator finds them like any other anchor.
+//!
+//! | Method | Resolution |
+//! |--------|------------|
+//! | `did:key` | Offline: the identifier is the multibase-encoded key |
+//! | `did:web` | `did.json` over HTTPS through a [`DidFetcher`], after [`validate_uri`] |
+//!
+//! Key IDs in a DID document are DID URLs (`did:web:creed.space#key-1`).
+//! Anchors store only the fragment (`key-1`); lookups through
+//! [`TrustConfig`] accept the fragment, `#key-1` or the full DID URL.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::did::{did_key, DidResolver};
+//! use vcp_core::keys::KeyPair;
+//! use vcp_core::trust::TrustConfig;
+//!
+//! let key = KeyPair::from_seed(&[7u8; 32]);
+//! let did = did_key(&key.public_bytes());
+//! assert!(did.starts_with("did:key:z6Mk"));
+//!
+//! let mut trust = TrustConfig::new();
+//! DidResolver::new().trust_issuer(&mut trust, &did).unwrap();
+//!
+//! let fragment = did.strip_prefix("did:key:").unwrap();
+//! let anchor = trust.get_issuer_key(&did, Some(&format!("{did}#{fragment}"))).unwrap();
+//! assert_eq!(anchor.public_key_bytes().unwrap(), key.public_bytes());
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL;
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::error::{VcpError, VcpResult};
+use crate::revocation::validate_uri;
+use crate::trust::{AnchorState, AnchorType, TrustAnchor, TrustConfig};
+
+/// Multicodec prefix for an Ed25519 public key (`0xed` as a varint).
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+/// Bitcoin base58 alphabet, used by multibase `z` (base58btc).
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Returns `true` if `id` is a DID (`did:<method>:<id>`).
+pub fn is_did(id: &str) -> bool {
+    id.strip_prefix("did:")
+        .and_then(|rest| rest.split_once(':'))
+        .is_some_and(|(method, specific)| !method.is_empty() && !specific.is_empty())
+}
+
+/// Reduce a key ID to the fragment stored in DID-derived anchors.
+///
+/// `did:web:creed.space#key-1` and `#key-1` both become `key-1` when
+/// `did` is `did:web:creed.space`. Other key IDs are returned unchanged.
+pub fn key_fragment<'a>(did: &str, key_id: &'a str) -> &'a str {
+    key_id
+        .strip_prefix(did)
+        .and_then(|rest| rest.strip_prefix('#'))
+        .or_else(|| key_id.strip_prefix('#'))
+        .unwrap_or(key_id)
+}
+
+// ── did:key ─────────────────────────────────────────────────
+
+/// Encode an Ed25519 public key as a `did:key` identifier.
+pub fn did_key(public_key: &[u8; 32]) -> String {
+    format!("did:key:{}", multibase_ed25519(public_key))
+}
+
+/// Decode the Ed25519 public key inside a `did:key` identifier.
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if `did` is not a `did:key`, is not
+/// base58btc-encoded, or does not hold an Ed25519 key.
+pub fn did_key_public_key(did: &str) -> VcpResult<[u8; 32]> {
+    let encoded = did
+        .strip_prefix("did:key:")
+        .ok_or_else(|| VcpError::ParseError(format!("not a did:key identifier: {did}")))?;
+    parse_multibase_ed25519(encoded)
+        .map_err(|e| VcpError::ParseError(format!("invalid did:key {did}: {e}")))
+}
+
+fn multibase_ed25519(public_key: &[u8; 32]) -> String {
+    let mut bytes = ED25519_MULTICODEC.to_vec();
+    bytes.extend_from_slice(public_key);
+    format!("z{}", base58_encode(&bytes))
+}
+
+fn parse_multibase_ed25519(encoded: &str) -> Result<[u8; 32], String> {
+    let base58 = encoded
+        .strip_prefix('z')
+        .ok_or("only base58btc ('z') multibase is supported")?;
+    let bytes = base58_decode(base58)?;
+    let key = bytes
+        .strip_prefix(&ED25519_MULTICODEC)
+        .ok_or("not an Ed25519 public key")?;
+    key.try_into()
+        .map_err(|_| format!("Ed25519 key must be 32 bytes, got {}", key.len()))
+}
+
+// ── did:web ─────────────────────────────────────────────────
+
+/// The HTTPS URL of a `did:web` identifier's DID document.
+///
+/// `did:web:creed.space` maps to `https://creed.space/.well-known/did.json`
+/// and `did:web:creed.space:issuers:safety` to
+/// `https://creed.space/issuers/safety/did.json`. The URL passes
+/// [`validate_uri`], so private and loopback hosts are refused.
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] for a malformed `did:web`, or the
+/// [`validate_uri`] error for an unsafe host.
+pub fn did_web_url(did: &str) -> VcpResult<String> {
+    let specific = did
+        .strip_prefix("did:web:")
+        .ok_or_else(|| VcpError::ParseError(format!("not a did:web identifier: {did}")))?;
+    let mut segments = specific.split(':');
+    let host = segments.next().unwrap_or_default().replace("%3A", ":");
+    let path: Vec<&str> = segments.collect();
+    let valid = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':'))
+    };
+    if !valid(&host) || !path.iter().all(|s| valid(s)) {
+        return Err(VcpError::ParseError(format!(
+            "invalid did:web identifier: {did}"
+        )));
+    }
+
+    let url = if path.is_empty() {
+        format!("https://{host}/.well-known/did.json")
+    } else {
+        format!("https://{host}/{}/did.json", path.join("/"))
+    };
+    validate_uri(&url)?;
+    Ok(url)
+}
+
+/// Fetches a DID document over HTTPS.
+///
+/// The SDK has no HTTP client, so `did:web` resolution goes through this
+/// trait. URLs are validated before `fetch` is called. Closures of type
+/// `Fn(&str) -> VcpResult<String>` implement it.
+pub trait DidFetcher: Send + Sync {
+    /// Return the body of a GET request to `url`.
+    ///
+    /// # Errors
+    ///
+    /// Any error is passed through from [`DidResolver::resolve`].
+    fn fetch(&self, url: &str) -> VcpResult<String>;
+}
+
+impl<F> DidFetcher for F
+where
+    F: Fn(&str) -> VcpResult<String> + Send + Sync,
+{
+    fn fetch(&self, url: &str) -> VcpResult<String> {
+        self(url)
+    }
+}
+
+// ── DID documents ───────────────────────────────────────────
+
+/// An Ed25519 verification method from a DID document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DidKey {
+    /// The method's fragment (`key-1` for `did:web:creed.space#key-1`).
+    pub id: String,
+    /// The raw public key.
+    pub public_key: [u8; 32],
+}
+
+/// The parts of a DID document VCP uses: the subject and its signing keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DidDocument {
+    /// The DID the document describes.
+    pub id: String,
+    /// Ed25519 keys usable for assertions, in document order.
+    pub keys: Vec<DidKey>,
+}
+
+impl DidDocument {
+    /// Parse a DID document from JSON.
+    ///
+    /// # Errors
+    ///
+    /// See [`from_value`](Self::from_value).
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| VcpError::JsonError(format!("invalid DID document: {e}")))?;
+        Self::from_value(&value)
+    }
+
+    /// Extract the Ed25519 keys from a parsed DID document.
+    ///
+    /// When `assertionMethod` is present only the methods it lists (by
+    /// reference or embedded) are used; otherwise every entry of
+    /// `verificationMethod` is. Methods are read from
+    /// `publicKeyMultibase` (`Ed25519VerificationKey2020`, `Multikey`),
+    /// `publicKeyBase58` (`Ed25519VerificationKey2018`) or an `OKP`
+    /// `publicKeyJwk`. Other key types are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if `id` is missing or not a DID, a
+    /// key is malformed, or no Ed25519 key remains.
+    pub fn from_value(value: &Value) -> VcpResult<Self> {
+        let id = value
+            .get("id")
+            .and_then(Value::as_str)
+            .filter(|id| is_did(id))
+            .ok_or_else(|| VcpError::ParseError("DID document has no DID 'id'".into()))?;
+        let methods: Vec<&Value> = value
+            .get("verificationMethod")
+            .and_then(Value::as_array)
+            .map(|m| m.iter().collect())
+            .unwrap_or_default();
+
+        let selected: Vec<&Value> = match value.get("assertionMethod").and_then(Value::as_array) {
+            Some(assertion) => assertion
+                .iter()
+                .filter_map(|entry| match entry {
+                    Value::String(reference) => methods
+                        .iter()
+                        .copied()
+                        .find(|m| method_fragment(id, m) == Some(key_fragment(id, reference))),
+                    other => Some(other),
+                })
+                .collect(),
+            None => methods,
+        };
+
+        let mut keys = Vec::new();
+        for method in selected {
+            let Some(fragment) = method_fragment(id, method) else {
+                continue;
+            };
+            if let Some(public_key) = method_public_key(method)
+                .map_err(|e| VcpError::ParseError(format!("DID key {id}#{fragment}: {e}")))?
+            {
+                keys.push(DidKey {
+                    id: fragment.to_string(),
+                    public_key,
+                });
+            }
+        }
+        if keys.is_empty() {
+            return Err(VcpError::ParseError(format!(
+                "DID document for {id} has no Ed25519 assertion keys"
+            )));
+        }
+        Ok(Self {
+            id: id.to_string(),
+            keys,
+        })
+    }
+
+    /// The document for a `did:key`, derived without any network access.
+    ///
+    /// # Errors
+    ///
+    /// See [`did_key_public_key`].
+    pub fn from_did_key(did: &str) -> VcpResult<Self> {
+        let public_key = did_key_public_key(did)?;
+        Ok(Self {
+            id: did.to_string(),
+            keys: vec![DidKey {
+                id: multibase_ed25519(&public_key),
+                public_key,
+            }],
+        })
+    }
+
+    /// One active anchor per key, named by the DID and the key fragment.
+    ///
+    /// DID documents carry no validity window, so anchors are valid from
+    /// the Unix epoch to the end of year 9999; re-resolve to pick up
+    /// rotated or removed keys.
+    pub fn trust_anchors(&self, anchor_type: AnchorType) -> Vec<TrustAnchor> {
+        let valid_until =
+            DateTime::<Utc>::from_timestamp(253_402_300_799, 0).unwrap_or(DateTime::<Utc>::MAX_UTC);
+        self.keys
+            .iter()
+            .map(|key| TrustAnchor {
+                id: self.id.clone(),
+                key_id: key.id.clone(),
+                algorithm: "ed25519".into(),
+                public_key: TrustAnchor::encode_public_key(&key.public_key),
+                anchor_type,
+                valid_from: DateTime::UNIX_EPOCH,
+                valid_until,
+                state: AnchorState::Active,
+            })
+            .collect()
+    }
+}
+
+/// The fragment of a verification method's `id`, if it belongs to `did`.
+fn method_fragment<'a>(did: &str, method: &'a Value) -> Option<&'a str> {
+    let id = method.get("id").and_then(Value::as_str)?;
+    let fragment = key_fragment(did, id);
+    (fragment != id || !id.contains(':')).then_some(fragment)
+}
+
+/// The Ed25519 key of a verification method, or `None` for other key types.
+fn method_public_key(method: &Value) -> Result<Option<[u8; 32]>, String> {
+    if let Some(multibase) = method.get("publicKeyMultibase").and_then(Value::as_str) {
+        return parse_multibase_ed25519(multibase).map(Some);
+    }
+    let raw = if let Some(base58) = method.get("publicKeyBase58").and_then(Value::as_str) {
+        base58_decode(base58)?
+    } else if let Some(jwk) = method.get("publicKeyJwk") {
+        let field = |name: &str| jwk.get(name).and_then(Value::as_str);
+        if field("kty") != Some("OKP") || field("crv") != Some("Ed25519") {
+            return Ok(None);
+        }
+        let x = field("x").ok_or("JWK has no 'x'")?;
+        BASE64_URL
+            .decode(x)
+            .map_err(|e| format!("invalid JWK 'x': {e}"))?
+    } else {
+        return Ok(None);
+    };
+    raw.as_slice()
+        .try_into()
+        .map(Some)
+        .map_err(|_| format!("Ed25519 key must be 32 bytes, got {}", raw.len()))
+}
+
+// ── Resolver ────────────────────────────────────────────────
+
+/// Resolves DIDs to documents and populates trust configurations.
+///
+/// `did:key` always resolves offline. `did:web` needs a fetcher set with
+/// [`with_fetcher`](Self::with_fetcher); without one it is refused.
+#[derive(Clone, Default)]
+pub struct DidResolver {
+    fetcher: Option<Arc<dyn DidFetcher>>,
+}
+
+impl fmt::Debug for DidResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DidResolver")
+            .field("fetcher", &self.fetcher.is_some())
+            .finish()
+    }
+}
+
+impl DidResolver {
+    /// A resolver for `did:key` only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `did:web` through `fetcher`.
+    #[must_use]
+    pub fn with_fetcher(mut self, fetcher: impl DidFetcher + 'static) -> Self {
+        self.fetcher = Some(Arc::new(fetcher));
+        self
+    }
+
+    /// Resolve a DID to its document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for unsupported methods, malformed
+    /// DIDs or documents, or a `did:web` document whose `id` is not the
+    /// requested DID. `did:web` without a fetcher, an unsafe URL and fetch
+    /// failures are reported as well.
+    pub fn resolve(&self, did: &str) -> VcpResult<DidDocument> {
+        if did.starts_with("did:key:") {
+            return DidDocument::from_did_key(did);
+        }
+        if !did.starts_with("did:web:") {
+            return Err(VcpError::ParseError(format!(
+                "unsupported DID method (expected did:key or did:web): {did}"
+            )));
+        }
+
+        let url = did_web_url(did)?;
+        let fetcher = self.fetcher.as_ref().ok_or_else(|| {
+            VcpError::ParseError(format!(
+                "cannot resolve {did}: no did:web fetcher configured"
+            ))
+        })?;
+        let document = DidDocument::from_json(&fetcher.fetch(&url)?)?;
+        if document.id != did {
+            return Err(VcpError::ParseError(format!(
+                "DID document at {url} is for {}, not {did}",
+                document.id
+            )));
+        }
+        Ok(document)
+    }
+
+    /// Resolve `did` and trust its keys as an issuer. Returns the number
+    /// of anchors added.
+    ///
+    /// # Errors
+    ///
+    /// See [`resolve`](Self::resolve).
+    pub fn trust_issuer(&self, config: &mut TrustConfig, did: &str) -> VcpResult<usize> {
+        let anchors = self.resolve(did)?.trust_anchors(AnchorType::Issuer);
+        let added = anchors.len();
+        for anchor in anchors {
+            config.add_issuer(did, anchor);
+        }
+        Ok(added)
+    }
+
+    /// Resolve `did` and trust its keys as an auditor. Returns the number
+    /// of anchors added.
+    ///
+    /// # Errors
+    ///
+    /// See [`resolve`](Self::resolve).
+    pub fn trust_auditor(&self, config: &mut TrustConfig, did: &str) -> VcpResult<usize> {
+        let anchors = self.resolve(did)?.trust_anchors(AnchorType::Auditor);
+        let added = anchors.len();
+        for anchor in anchors {
+            config.add_auditor(did, anchor);
+        }
+        Ok(added)
+    }
+}
+
+// ── Base58 ──────────────────────────────────────────────────
+
+fn base58_encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    // Little-endian base-58 digits.
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in &bytes[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = u8::try_from(carry % 58).expect("remainder below 58");
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push(u8::try_from(carry % 58).expect("remainder below 58"));
+            carry /= 58;
+        }
+    }
+    std::iter::repeat_n('1', zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|&d| char::from(BASE58_ALPHABET[usize::from(d)])),
+        )
+        .collect()
+}
+
+fn base58_decode(text: &str) -> Result<Vec<u8>, String> {
+    let zeros = text.bytes().take_while(|&b| b == b'1').count();
+    // Little-endian bytes.
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in text.bytes().skip(zeros) {
+        let value = BASE58_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| format!("invalid base58 character '{}'", char::from(c)))?;
+        let mut carry = u32::try_from(value).expect("index below 58");
+        for byte in &mut bytes {
+            carry += u32::from(*byte) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    let mut out = vec![0u8; zeros];
+    out.extend(bytes.iter().rev());
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::KeyPair;
+
+    fn web_document(did: &str, key: &KeyPair) -> String {
+        serde_json::json!({
+            "@context": ["https://www.w3.org/ns/did/v1"],
+            "id": did,
+            "verificationMethod": [
+                {
+                    "id": format!("{did}#key-1"),
+                    "type": "Ed25519VerificationKey2020",
+                    "controller": did,
+                    "publicKeyMultibase": multibase_ed25519(&key.public_bytes()),
+                },
+                {
+                    "id": "#key-2",
+                    "type": "JsonWebKey2020",
+                    "controller": did,
+                    "publicKeyJwk": {
+                        "kty": "OKP",
+                        "crv": "Ed25519",
+                        "x": BASE64_URL.encode([2u8; 32]),
+                    },
+                },
+                {
+                    "id": "#p256",
+                    "type": "JsonWebKey2020",
+                    "publicKeyJwk": {"kty": "EC", "crv": "P-256", "x": "AA", "y": "AA"},
+                },
+            ],
+            "assertionMethod": ["#key-1", format!("{did}#p256")],
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn did_key_matches_published_vector() {
+        // From the did:key method specification test vectors.
+        let did = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+        let key = did_key_public_key(did).unwrap();
+        assert_eq!(did_key(&key), did);
+
+        let doc = DidResolver::new().resolve(did).unwrap();
+        assert_eq!(doc.keys.len(), 1);
+        assert_eq!(doc.keys[0].id, did.strip_prefix("did:key:").unwrap());
+
+        for bad in ["did:key:", "did:key:6Mk", "did:key:z0OIl", "did:web:x"] {
+            assert!(did_key_public_key(bad).is_err(), "{bad}");
+        }
+        assert_eq!(base58_decode("1112").unwrap(), [0, 0, 0, 1]);
+        assert_eq!(base58_encode(&[0, 0, 0, 1]), "1112");
+    }
+
+    #[test]
+    fn did_web_urls_follow_the_method_spec() {
+        assert_eq!(
+            did_web_url("did:web:creed.space").unwrap(),
+            "https://creed.space/.well-known/did.json"
+        );
+        assert_eq!(
+            did_web_url("did:web:creed.space:issuers:safety").unwrap(),
+            "https://creed.space/issuers/safety/did.json"
+        );
+        assert_eq!(
+            did_web_url("did:web:creed.space%3A443").unwrap(),
+            "https://creed.space:443/.well-known/did.json"
+        );
+        for unsafe_did in [
+            "did:web:localhost",
+            "did:web:127.0.0.1",
+            "did:web:10.0.0.1",
+            "did:web:creed.space%3A8443",
+            "did:web:creed.space/../x",
+            "did:web:creed.space::x",
+        ] {
+            assert!(did_web_url(unsafe_did).is_err(), "{unsafe_did}");
+        }
+    }
+
+    #[test]
+    fn resolves_did_web_into_trust_anchors() {
+        let did = "did:web:creed.space";
+        let key = KeyPair::from_seed(&[3u8; 32]);
+        let body = web_document(did, &key);
+        let resolver = DidResolver::new().with_fetcher(move |url: &str| {
+            assert_eq!(url, "https://creed.space/.well-known/did.json");
+            Ok(body.clone())
+        });
+
+        let mut trust = TrustConfig::new();
+        // Only key-1 is an assertion key; the P-256 method is skipped.
+        assert_eq!(resolver.trust_issuer(&mut trust, did).unwrap(), 1);
+        for key_id in ["key-1", "#key-1", "did:web:creed.space#key-1"] {
+            let anchor = trust.get_issuer_key(did, Some(key_id)).unwrap();
+            assert_eq!(anchor.public_key_bytes().unwrap(), key.public_bytes());
+        }
+        assert!(trust.get_issuer_key(did, Some("key-2")).is_none());
+
+        // Without assertionMethod every Ed25519 method is used.
+        let mut doc: Value = serde_json::from_str(&web_document(did, &key)).unwrap();
+        doc.as_object_mut().unwrap().remove("assertionMethod");
+        let parsed = DidDocument::from_value(&doc).unwrap();
+        let ids: Vec<&str> = parsed.keys.iter().map(|k| k.id.as_str()).collect();
+        assert_eq!(ids, ["key-1", "key-2"]);
+    }
+
+    #[test]
+    fn did_web_requires_a_fetcher_and_a_matching_document() {
+        let did = "did:web:creed.space";
+        assert!(DidResolver::new().resolve(did).is_err());
+
+        let key = KeyPair::from_seed(&[3u8; 32]);
+        let other = web_document("did:web:evil.example", &key);
+        let resolver = DidResolver::new().with_fetcher(move |_: &str| Ok(other.clone()));
+        let err = resolver.resolve(did).unwrap_err();
+        assert!(err.to_string().contains("not did:web:creed.space"));
+
+        let called = DidResolver::new()
+            .with_fetcher(|url: &str| -> VcpResult<String> { panic!("fetched unsafe URL {url}") });
+        assert!(called.resolve("did:web:192.168.1.1").is_err());
+        assert!(called.resolve("did:example:123").is_err());
+    }
+}
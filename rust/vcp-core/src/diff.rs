@@ -0,0 +1,484 @@
+//! Rule-level diffs between two versions of a constitution.
+//!
+//! Before trusting a bundle update, a reviewer wants to know which rules
+//! it drops, which it rewrites and which it adds. [`diff_constitutions`]
+//! compares two [`Constitution`]s rule by rule; [`diff_text`] does the
+//! same for raw constitution text, taking each list item or paragraph
+//! line as a rule (see [`extract_rules`]).
+//!
+//! Reordering alone is not a change. A rule rewritten rather than
+//! replaced is reported once as [`ChangeKind::Modified`] when the two
+//! versions share at least half their words.
+//!
+//! | Change | Severity |
+//! |--------|----------|
+//! | Rule removed | [`Major`](Severity::Major) |
+//! | Rule modified, obligation words changed (`must`, `never`, `may`, ...) | [`Major`](Severity::Major) |
+//! | Rule modified otherwise, rule added, priority changed | [`Minor`](Severity::Minor) |
+//! | Only case, punctuation or spacing changed | [`Cosmetic`](Severity::Cosmetic) |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::diff::{diff_text, ChangeKind, Severity};
+//!
+//! let old = "- Never share personal data.\n- Be kind.\n";
+//! let new = "- Be kind and patient.\n- Cite sources.\n";
+//! let diff = diff_text(old, new);
+//!
+//! assert_eq!(diff.count(ChangeKind::Removed), 1);
+//! assert_eq!(diff.count(ChangeKind::Modified), 1);
+//! assert_eq!(diff.count(ChangeKind::Added), 1);
+//! assert_eq!(diff.severity(), Some(Severity::Major));
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::composer::Constitution;
+
+/// Rewrites sharing fewer words than this are reported as a removal
+/// plus an addition.
+const MODIFIED_SIMILARITY: f64 = 0.5;
+
+/// Words whose presence decides how binding a rule is.
+const OBLIGATION_WORDS: &[&str] = &[
+    "must",
+    "never",
+    "always",
+    "shall",
+    "should",
+    "may",
+    "can",
+    "cannot",
+    "not",
+    "no",
+    "only",
+    "required",
+    "forbidden",
+    "prohibited",
+];
+
+// ── Changes ─────────────────────────────────────────────────
+
+/// How much a change matters to a reviewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Wording only; the rule says the same thing.
+    Cosmetic,
+    /// New or reworded guidance.
+    Minor,
+    /// A rule was dropped or its obligation changed.
+    Major,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cosmetic => "cosmetic",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        })
+    }
+}
+
+/// What happened to a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Removed,
+    Modified,
+    Added,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Removed => "removed",
+            Self::Modified => "modified",
+            Self::Added => "added",
+        })
+    }
+}
+
+/// One changed rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleChange {
+    pub kind: ChangeKind,
+    pub severity: Severity,
+    /// Position and text in the old version (`None` for additions).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<(usize, String)>,
+    /// Position and text in the new version (`None` for removals).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<(usize, String)>,
+}
+
+/// Everything that changed between two constitution versions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstitutionDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_id: Option<String>,
+    /// `(old, new)` priority, when it changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<(i32, i32)>,
+    /// Removals, then modifications, then additions, each in rule order.
+    pub changes: Vec<RuleChange>,
+}
+
+impl ConstitutionDiff {
+    /// `true` if the versions have the same rules and priority.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.priority.is_none()
+    }
+
+    /// The most severe change, or `None` if nothing changed.
+    pub fn severity(&self) -> Option<Severity> {
+        let priority = self.priority.map(|_| Severity::Minor);
+        self.changes
+            .iter()
+            .map(|c| c.severity)
+            .chain(priority)
+            .max()
+    }
+
+    /// Number of changes of `kind`.
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+impl fmt::Display for ConstitutionDiff {
+    /// A changelog grouped by change kind.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Some(old), Some(new)) = (&self.old_id, &self.new_id) {
+            writeln!(f, "{old} -> {new}")?;
+        }
+        let Some(severity) = self.severity() else {
+            return writeln!(f, "no changes");
+        };
+        writeln!(
+            f,
+            "{} removed, {} modified, {} added ({severity})",
+            self.count(ChangeKind::Removed),
+            self.count(ChangeKind::Modified),
+            self.count(ChangeKind::Added)
+        )?;
+        if let Some((old, new)) = self.priority {
+            writeln!(f, "priority: {old} -> {new}")?;
+        }
+        let mut current = None;
+        for change in &self.changes {
+            if current != Some(change.kind) {
+                writeln!(f, "{}:", change.kind)?;
+                current = Some(change.kind);
+            }
+            match (&change.old, &change.new) {
+                (Some((_, old)), Some((_, new))) => {
+                    writeln!(f, "  - [{}] {old}", change.severity)?;
+                    writeln!(f, "    -> {new}")?;
+                }
+                (Some((_, text)), None) | (None, Some((_, text))) => {
+                    writeln!(f, "  - [{}] {text}", change.severity)?;
+                }
+                (None, None) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+// ── Diffing ─────────────────────────────────────────────────
+
+/// Compare two constitutions rule by rule.
+pub fn diff_constitutions(old: &Constitution, new: &Constitution) -> ConstitutionDiff {
+    ConstitutionDiff {
+        old_id: Some(old.id.clone()),
+        new_id: Some(new.id.clone()),
+        priority: (old.priority != new.priority).then_some((old.priority, new.priority)),
+        changes: diff_rules(&old.rules, &new.rules),
+    }
+}
+
+/// Compare two constitution texts, using [`extract_rules`] on each.
+pub fn diff_text(old: &str, new: &str) -> ConstitutionDiff {
+    ConstitutionDiff {
+        changes: diff_rules(&extract_rules(old), &extract_rules(new)),
+        ..ConstitutionDiff::default()
+    }
+}
+
+/// Split constitution text into rules.
+///
+/// Each non-blank line is a rule, with list markers (`-`, `*`, `+`, `1.`,
+/// `1)`) removed. Markdown headings and fenced code blocks are skipped.
+pub fn extract_rules(text: &str) -> Vec<String> {
+    let mut rules = Vec::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let rule = strip_list_marker(line);
+        if !rule.is_empty() {
+            rules.push(rule.to_string());
+        }
+    }
+    rules
+}
+
+pub(crate) fn strip_list_marker(line: &str) -> &str {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return rest.trim_start();
+    }
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return rest.trim_start();
+        }
+    }
+    line
+}
+
+/// Lowercase words with punctuation removed.
+fn words(rule: &str) -> Vec<String> {
+    rule.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let w = w.to_lowercase();
+            // Contractions count as their negation.
+            match w.as_str() {
+                "don't" | "doesn't" | "won't" | "isn't" | "aren't" => "not".to_string(),
+                "can't" => "cannot".to_string(),
+                _ => w,
+            }
+        })
+        .collect()
+}
+
+fn similarity(a: &HashSet<&str>, b: &HashSet<&str>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let ratio = a.intersection(b).count() as f64 / union as f64;
+    ratio
+}
+
+fn modified_severity(old: &[String], new: &[String]) -> Severity {
+    if old == new {
+        return Severity::Cosmetic;
+    }
+    let obligations = |ws: &[String]| -> HashSet<String> {
+        ws.iter()
+            .filter(|w| OBLIGATION_WORDS.contains(&w.as_str()))
+            .cloned()
+            .collect()
+    };
+    if obligations(old) == obligations(new) {
+        Severity::Minor
+    } else {
+        Severity::Major
+    }
+}
+
+fn diff_rules(old: &[String], new: &[String]) -> Vec<RuleChange> {
+    // Rules present verbatim in both versions are unchanged, wherever
+    // they sit.
+    let mut new_left: Vec<Option<&String>> = new.iter().map(Some).collect();
+    let mut old_left: Vec<usize> = Vec::new();
+    for (i, rule) in old.iter().enumerate() {
+        match new_left.iter().position(|n| *n == Some(rule)) {
+            Some(j) => new_left[j] = None,
+            None => old_left.push(i),
+        }
+    }
+    let new_left: Vec<usize> = new_left
+        .iter()
+        .enumerate()
+        .filter_map(|(j, n)| n.map(|_| j))
+        .collect();
+
+    let old_words: Vec<Vec<String>> = old_left.iter().map(|&i| words(&old[i])).collect();
+    let new_words: Vec<Vec<String>> = new_left.iter().map(|&j| words(&new[j])).collect();
+
+    // Pair the most similar remaining rules first.
+    let mut candidates = Vec::new();
+    for (a, ow) in old_words.iter().enumerate() {
+        let os: HashSet<&str> = ow.iter().map(String::as_str).collect();
+        for (b, nw) in new_words.iter().enumerate() {
+            let ns: HashSet<&str> = nw.iter().map(String::as_str).collect();
+            let score = similarity(&os, &ns);
+            if score >= MODIFIED_SIMILARITY {
+                candidates.push((score, a, b));
+            }
+        }
+    }
+    candidates.sort_by(|x, y| y.0.total_cmp(&x.0).then(x.1.cmp(&y.1)).then(x.2.cmp(&y.2)));
+
+    let mut old_paired = vec![false; old_left.len()];
+    let mut new_paired = vec![false; new_left.len()];
+    let mut changes = Vec::new();
+    for (_, a, b) in candidates {
+        if old_paired[a] || new_paired[b] {
+            continue;
+        }
+        old_paired[a] = true;
+        new_paired[b] = true;
+        let (i, j) = (old_left[a], new_left[b]);
+        changes.push(RuleChange {
+            kind: ChangeKind::Modified,
+            severity: modified_severity(&old_words[a], &new_words[b]),
+            old: Some((i, old[i].clone())),
+            new: Some((j, new[j].clone())),
+        });
+    }
+    for (a, &i) in old_left.iter().enumerate() {
+        if !old_paired[a] {
+            changes.push(RuleChange {
+                kind: ChangeKind::Removed,
+                severity: Severity::Major,
+                old: Some((i, old[i].clone())),
+                new: None,
+            });
+        }
+    }
+    for (b, &j) in new_left.iter().enumerate() {
+        if !new_paired[b] {
+            changes.push(RuleChange {
+                kind: ChangeKind::Added,
+                severity: Severity::Minor,
+                old: None,
+                new: Some((j, new[j].clone())),
+            });
+        }
+    }
+
+    changes.sort_by_key(|c| {
+        let position = c.new.as_ref().or(c.old.as_ref()).map_or(0, |(i, _)| *i);
+        (c.kind, position)
+    });
+    changes
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn constitution(id: &str, rules: &[&str], priority: i32) -> Constitution {
+        Constitution::new(
+            id,
+            rules.iter().map(ToString::to_string).collect(),
+            priority,
+        )
+    }
+
+    #[test]
+    fn identical_and_reordered_rules_are_unchanged() {
+        let old = constitution("c@1", &["Be kind.", "Be honest."], 1);
+        let new = constitution("c@2", &["Be honest.", "Be kind."], 1);
+        let diff = diff_constitutions(&old, &new);
+        assert!(diff.is_empty());
+        assert_eq!(diff.severity(), None);
+        assert_eq!(diff.to_string(), "c@1 -> c@2\nno changes\n");
+    }
+
+    #[test]
+    fn classifies_each_change() {
+        let old = constitution(
+            "c@1",
+            &[
+                "Never share personal data with third parties.",
+                "Be kind to everyone.",
+                "Always cite your sources when asked.",
+                "Avoid medical advice.",
+            ],
+            1,
+        );
+        let new = constitution(
+            "c@2",
+            &[
+                "be kind to everyone",
+                "You may share personal data with third parties.",
+                "Always cite your sources when you are asked.",
+                "Answer in the user's language.",
+            ],
+            2,
+        );
+        let diff = diff_constitutions(&old, &new);
+        let summary: Vec<(ChangeKind, Severity)> =
+            diff.changes.iter().map(|c| (c.kind, c.severity)).collect();
+        assert_eq!(
+            summary,
+            [
+                (ChangeKind::Removed, Severity::Major),
+                (ChangeKind::Modified, Severity::Cosmetic),
+                (ChangeKind::Modified, Severity::Major),
+                (ChangeKind::Modified, Severity::Minor),
+                (ChangeKind::Added, Severity::Minor),
+            ]
+        );
+        assert_eq!(diff.priority, Some((1, 2)));
+        assert_eq!(diff.severity(), Some(Severity::Major));
+        assert_eq!(
+            diff.changes[0].old,
+            Some((3, "Avoid medical advice.".to_string()))
+        );
+    }
+
+    #[test]
+    fn contractions_count_as_negation() {
+        let diff = diff_text("- Do share logs.\n", "- Don't share logs.\n");
+        assert_eq!(diff.changes[0].kind, ChangeKind::Modified);
+        assert_eq!(diff.changes[0].severity, Severity::Major);
+    }
+
+    #[test]
+    fn extract_rules_from_markdown() {
+        let text = "# Safety\n\n- Be kind.\n* Be honest.\n2. Be brief.\n10) Be clear.\n\n```\n- not a rule\n```\nPlain line.\n";
+        assert_eq!(
+            extract_rules(text),
+            [
+                "Be kind.",
+                "Be honest.",
+                "Be brief.",
+                "Be clear.",
+                "Plain line."
+            ]
+        );
+    }
+
+    #[test]
+    fn changelog_text() {
+        let diff = diff_text(
+            "- Never lie.\n- Be kind to everyone.\n",
+            "- Be kind to everyone you meet.\n- Be brief.\n",
+        );
+        assert_eq!(
+            diff.to_string(),
+            "1 removed, 1 modified, 1 added (major)\n\
+             removed:\n  - [major] Never lie.\n\
+             modified:\n  - [minor] Be kind to everyone.\n    -> Be kind to everyone you meet.\n\
+             added:\n  - [minor] Be brief.\n"
+        );
+    }
+}
@@ -2,8 +2,47 @@
 //!
 //! All fallible operations in `vcp-core` return [`VcpError`] through
 //! the standard [`Result`] alias [`VcpResult`].
+//!
+//! The CSM-1, identity and context parsers wrap their errors in
+//! [`VcpError::Spanned`], recording the byte range of the input at fault
+//! and what would have been accepted there. [`VcpError::render`] draws
+//! the offending line with the range underlined:
+//!
+//! ```
+//! use vcp_core::csm1::Csm1Code;
+//!
+//! let err = Csm1Code::parse("N5+X").unwrap_err();
+//! assert_eq!(err.span().map(|s| s.offset), Some(3));
+//! assert!(err
+//!     .render("N5+X")
+//!     .ends_with("1 | N5+X\n  |    ^ expected scope letter (F W E H I L P S A V G)\n"));
+//! ```
+//!
+//! Every [`VcpError`] variant and [`VerificationCode`] also has an
+//! [`ErrorCode`], a numbered identifier shared by all VCP SDKs so logs
+//! and alerts can be matched across languages. Numbers are never reused
+//! or reassigned.
+//!
+//! | Range | Area |
+//! |-------|------|
+//! | `VCP-E-1xxx` | Parsing and token structure |
+//! | `VCP-E-2xxx` | Integrity: hashes, signatures, revocation |
+//! | `VCP-E-3xxx` | Serialization and I/O |
+//! | `VCP-E-4xxx` | Runtime: hooks, sessions, consent |
+//! | `VCP-E-5xxx` | Bundle verification results (`5000` + [`VerificationCode`] discriminant) |
+//!
+//! ```
+//! use vcp_core::error::{ErrorCode, VerificationCode};
+//! use vcp_core::csm1::Csm1Code;
+//!
+//! let err = Csm1Code::parse("N5+X").unwrap_err();
+//! assert_eq!(err.error_code().to_string(), "VCP-E-1005");
+//! let hash_mismatch: ErrorCode = "VCP-E-5007".parse().unwrap();
+//! assert_eq!(VerificationCode::HashMismatch.error_code(), hash_mismatch);
+//! ```
 
 use std::fmt;
+use std::str::FromStr;
 
 /// Convenience alias used throughout the crate.
 pub type VcpResult<T> = Result<T, VcpError>;
@@ -54,6 +93,237 @@ pub enum VcpError {
     /// A revocation check error.
     #[error("revocation error: {0}")]
     RevocationError(String),
+
+    /// An unknown, expired or terminated session.
+    #[error("session error: {0}")]
+    SessionError(String),
+
+    /// A context shared without consent for the requested use.
+    #[error("consent error: {0}")]
+    ConsentError(String),
+
+    /// An I/O error while reading input or writing output.
+    #[error("io error: {0}")]
+    IoError(String),
+
+    /// A parse error pinned to a region of the input. Displays as the
+    /// wrapped error.
+    #[error("{}", .0.error)]
+    Spanned(Box<Located>),
+}
+
+// ── Spans ───────────────────────────────────────────────────
+
+/// A byte range of parser input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub offset: usize,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(offset: usize, len: usize) -> Self {
+        Self { offset, len }
+    }
+
+    /// The span `part` occupies in `source`. `part` must be a subslice of
+    /// `source`; otherwise the span is empty at the end of `source`.
+    pub fn of(source: &str, part: &str) -> Self {
+        let start = (part.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+        if start <= source.len() && start + part.len() <= source.len() {
+            Self::new(start, part.len())
+        } else {
+            Self::new(source.len(), 0)
+        }
+    }
+
+    /// One past the last byte.
+    pub fn end(&self) -> usize {
+        self.offset + self.len
+    }
+}
+
+/// An error with the input region it concerns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located {
+    pub error: VcpError,
+    pub span: Span,
+    /// What the parser would have accepted at `span`, if it knows.
+    pub expected: Vec<String>,
+}
+
+impl VcpError {
+    /// The variant name (e.g. `"InvalidPersona"`), stable for callers that
+    /// branch on the kind of error rather than its message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            VcpError::ParseError(_) => "ParseError",
+            VcpError::InvalidPersona(_) => "InvalidPersona",
+            VcpError::InvalidAdherence(_) => "InvalidAdherence",
+            VcpError::InvalidIntensity(_) => "InvalidIntensity",
+            VcpError::InvalidScope(_) => "InvalidScope",
+            VcpError::MalformedToken(_) => "MalformedToken",
+            VcpError::HashMismatch { .. } => "HashMismatch",
+            VcpError::SignatureError(_) => "SignatureError",
+            VcpError::JsonError(_) => "JsonError",
+            VcpError::HookError(_) => "HookError",
+            VcpError::RevocationError(_) => "RevocationError",
+            VcpError::SessionError(_) => "SessionError",
+            VcpError::ConsentError(_) => "ConsentError",
+            VcpError::IoError(_) => "IoError",
+            VcpError::Spanned(located) => located.error.code(),
+        }
+    }
+
+    /// The stable cross-SDK identifier for this kind of error. A spanned
+    /// error has the code of the error it wraps.
+    pub fn error_code(&self) -> ErrorCode {
+        ErrorCode(match self {
+            VcpError::ParseError(_) => 1001,
+            VcpError::InvalidPersona(_) => 1002,
+            VcpError::InvalidAdherence(_) => 1003,
+            VcpError::InvalidIntensity(_) => 1004,
+            VcpError::InvalidScope(_) => 1005,
+            VcpError::MalformedToken(_) => 1006,
+            VcpError::HashMismatch { .. } => 2001,
+            VcpError::SignatureError(_) => 2002,
+            VcpError::RevocationError(_) => 2003,
+            VcpError::JsonError(_) => 3001,
+            VcpError::IoError(_) => 3002,
+            VcpError::HookError(_) => 4001,
+            VcpError::SessionError(_) => 4002,
+            VcpError::ConsentError(_) => 4003,
+            VcpError::Spanned(located) => return located.error.error_code(),
+        })
+    }
+
+    /// Pin this error to `span` of the input.
+    ///
+    /// An error that already has a span came from parsing a part of the
+    /// input; its span is taken as relative to `span` and shifted.
+    #[must_use]
+    pub fn at(self, span: Span) -> Self {
+        match self {
+            VcpError::Spanned(mut located) => {
+                located.span.offset += span.offset;
+                VcpError::Spanned(located)
+            }
+            error => VcpError::Spanned(Box::new(Located {
+                error,
+                span,
+                expected: Vec::new(),
+            })),
+        }
+    }
+
+    /// Record what the parser would have accepted. Has no effect on an
+    /// error without a span, or one whose expectations are already set.
+    #[must_use]
+    pub fn expecting(mut self, expected: &[&str]) -> Self {
+        if let VcpError::Spanned(located) = &mut self {
+            if located.expected.is_empty() {
+                located.expected = expected.iter().map(|e| (*e).to_string()).collect();
+            }
+        }
+        self
+    }
+
+    /// The input region this error concerns, if the parser recorded one.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            VcpError::Spanned(located) => Some(located.span),
+            _ => None,
+        }
+    }
+
+    /// What the parser would have accepted at [`span`](Self::span).
+    pub fn expected(&self) -> &[String] {
+        match self {
+            VcpError::Spanned(located) => &located.expected,
+            _ => &[],
+        }
+    }
+
+    /// The error without its span, for matching on the kind.
+    pub fn without_span(&self) -> &VcpError {
+        match self {
+            VcpError::Spanned(located) => located.error.without_span(),
+            error => error,
+        }
+    }
+
+    /// Render the error for a terminal: the message, then the line of
+    /// `source` holding the span with the span underlined and the
+    /// expected set beside it. `source` must be the parsed input.
+    pub fn render(&self, source: &str) -> String {
+        let Some(span) = self.span() else {
+            return format!("error: {self}\n");
+        };
+        let floor = |mut i: usize| {
+            i = i.min(source.len());
+            while !source.is_char_boundary(i) {
+                i -= 1;
+            }
+            i
+        };
+        let start = floor(span.offset);
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i);
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let end = floor(span.end()).clamp(start, line_start + line.len());
+
+        let line_no = source[..line_start].matches('\n').count() + 1;
+        let column = source[line_start..start].chars().count();
+        let width = source[start..end].chars().count().max(1);
+        let gutter = " ".repeat(line_no.to_string().len());
+
+        let expected = match self.expected() {
+            [] => String::new(),
+            [one] => format!(" expected {one}"),
+            many => format!(" expected one of: {}", many.join(", ")),
+        };
+        format!(
+            "error: {self}\n{gutter}--> {line_no}:{}\n{gutter} |\n{line_no} | {line}\n{gutter} | {}{}{expected}\n",
+            column + 1,
+            " ".repeat(column),
+            "^".repeat(width)
+        )
+    }
+}
+
+// ── Stable codes ────────────────────────────────────────────
+
+/// A stable error identifier, written `VCP-E-NNNN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ErrorCode(u16);
+
+impl ErrorCode {
+    /// The numeric part, e.g. `1001` for `VCP-E-1001`.
+    pub fn number(self) -> u16 {
+        self.0
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VCP-E-{:04}", self.0)
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = VcpError;
+
+    /// Parse `VCP-E-NNNN`. Any four-digit number is accepted, so codes
+    /// added by newer SDKs still parse.
+    fn from_str(s: &str) -> VcpResult<Self> {
+        s.strip_prefix("VCP-E-")
+            .filter(|n| n.len() == 4 && n.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|n| n.parse().ok())
+            .map(Self)
+            .ok_or_else(|| VcpError::ParseError(format!("expected VCP-E-NNNN, got: {s}")))
+    }
 }
 
 impl From<serde_json::Error> for VcpError {
@@ -62,6 +332,12 @@ impl From<serde_json::Error> for VcpError {
     }
 }
 
+impl From<std::io::Error> for VcpError {
+    fn from(err: std::io::Error) -> Self {
+        VcpError::IoError(err.to_string())
+    }
+}
+
 /// Validation result codes mirroring the Python `VerificationResult` enum.
 ///
 /// These are returned by bundle verification routines to indicate
@@ -89,11 +365,37 @@ pub enum VerificationCode {
 }
 
 impl VerificationCode {
+    /// Every code, in discriminant order.
+    pub const ALL: [VerificationCode; 17] = [
+        VerificationCode::Valid,
+        VerificationCode::SizeExceeded,
+        VerificationCode::InvalidSchema,
+        VerificationCode::UntrustedIssuer,
+        VerificationCode::InvalidSignature,
+        VerificationCode::UntrustedAuditor,
+        VerificationCode::InvalidAttestation,
+        VerificationCode::HashMismatch,
+        VerificationCode::NotYetValid,
+        VerificationCode::Expired,
+        VerificationCode::FutureTimestamp,
+        VerificationCode::ReplayDetected,
+        VerificationCode::TokenMismatch,
+        VerificationCode::BudgetExceeded,
+        VerificationCode::ScopeMismatch,
+        VerificationCode::Revoked,
+        VerificationCode::FetchFailed,
+    ];
+
     /// Returns `true` when the code represents a successful verification.
     pub fn is_valid(self) -> bool {
         matches!(self, VerificationCode::Valid)
     }
 
+    /// The stable cross-SDK identifier, `VCP-E-5000` plus the discriminant.
+    pub fn error_code(self) -> ErrorCode {
+        ErrorCode(5000 + self as u16)
+    }
+
     /// Broad classification of the failure reason.
     pub fn category(self) -> &'static str {
         match self {
@@ -157,6 +459,109 @@ mod tests {
         assert_eq!(VerificationCode::BudgetExceeded.category(), "configuration");
     }
 
+    #[test]
+    fn vcp_error_codes() {
+        assert_eq!(VcpError::InvalidPersona('X').code(), "InvalidPersona");
+        assert_eq!(
+            VcpError::HashMismatch {
+                expected: String::new(),
+                actual: String::new(),
+            }
+            .code(),
+            "HashMismatch"
+        );
+    }
+
+    #[test]
+    fn error_codes_are_stable_and_unique() {
+        let errors = [
+            VcpError::ParseError(String::new()),
+            VcpError::InvalidPersona('X'),
+            VcpError::InvalidAdherence(9),
+            VcpError::InvalidIntensity(9),
+            VcpError::InvalidScope('X'),
+            VcpError::MalformedToken(String::new()),
+            VcpError::HashMismatch {
+                expected: String::new(),
+                actual: String::new(),
+            },
+            VcpError::SignatureError(String::new()),
+            VcpError::JsonError(String::new()),
+            VcpError::HookError(String::new()),
+            VcpError::RevocationError(String::new()),
+            VcpError::SessionError(String::new()),
+            VcpError::ConsentError(String::new()),
+            VcpError::IoError(String::new()),
+        ];
+        let mut codes: Vec<ErrorCode> = errors.iter().map(VcpError::error_code).collect();
+        codes.extend(VerificationCode::ALL.map(VerificationCode::error_code));
+        let unique: std::collections::BTreeSet<_> = codes.iter().collect();
+        assert_eq!(unique.len(), codes.len());
+
+        assert_eq!(errors[0].error_code().to_string(), "VCP-E-1001");
+        assert_eq!(errors[13].error_code().number(), 3002);
+        assert_eq!(
+            errors[4].clone().at(Span::new(1, 1)).error_code(),
+            errors[4].error_code()
+        );
+        assert_eq!(
+            VerificationCode::Valid.error_code().to_string(),
+            "VCP-E-5000"
+        );
+        assert_eq!(VerificationCode::FetchFailed.error_code().number(), 5016);
+
+        for code in codes {
+            assert_eq!(code.to_string().parse::<ErrorCode>().unwrap(), code);
+        }
+        for bad in [
+            "VCP-E-12",
+            "VCP-E-12345",
+            "vcp-e-1001",
+            "VCP-E-10a1",
+            "1001",
+        ] {
+            assert!(bad.parse::<ErrorCode>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn spans_nest_and_unwrap() {
+        let inner = VcpError::InvalidIntensity(9).at(Span::new(2, 1));
+        let outer = inner.at(Span::new(10, 5)).expecting(&["1-5"]);
+        assert_eq!(outer.span(), Some(Span::new(12, 1)));
+        assert_eq!(outer.expected(), ["1-5"]);
+        assert_eq!(outer.code(), "InvalidIntensity");
+        assert_eq!(outer.without_span(), &VcpError::InvalidIntensity(9));
+        assert_eq!(outer.to_string(), "invalid intensity: 9 (must be 1-5)");
+        assert_eq!(VcpError::IoError("x".into()).expecting(&["y"]).span(), None);
+
+        let source = "abcdef";
+        assert_eq!(Span::of(source, &source[2..4]), Span::new(2, 2));
+        assert_eq!(Span::of(source, "elsewhere"), Span::new(6, 0));
+    }
+
+    #[test]
+    fn render_underlines_the_right_line() {
+        let source = "first\nsecond line\nthird";
+        let err = VcpError::ParseError("bad word".into()).at(Span::new(13, 4));
+        assert_eq!(
+            err.render(source),
+            "error: parse error: bad word\n --> 2:8\n  |\n2 | second line\n  |        ^^^^\n"
+        );
+        assert_eq!(
+            VcpError::ParseError("plain".into()).render(source),
+            "error: parse error: plain\n"
+        );
+        // An empty span at the end still gets one caret.
+        let eof = VcpError::ParseError("eof".into())
+            .at(Span::new(3, 0))
+            .expecting(&["a", "b"]);
+        assert_eq!(
+            eof.render("abc"),
+            "error: parse error: eof\n --> 1:4\n  |\n1 | abc\n  |    ^ expected one of: a, b\n"
+        );
+    }
+
     #[test]
     fn vcp_error_display() {
         let e = VcpError::InvalidPersona('X');
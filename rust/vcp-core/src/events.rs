@@ -0,0 +1,481 @@
+//! Versioned event envelope for streaming VCP activity.
+//!
+//! Every event shares one envelope — `type`, `version`, `tenant`,
+//! `payload`, `occurred_at` — so consumers on an event bus (Kafka,
+//! NATS, ...) can route on `type` and decode `payload` without
+//! per-producer formats.
+//!
+//! | `type` | Payload |
+//! |--------|---------|
+//! | `verification` | [`VerificationEvent`] — outcome of a bundle verification |
+//! | `revocation` | [`RevocationEvent`] — a bundle was found revoked |
+//! | `context_transition` | [`ContextTransitionEvent`] — a session's context changed |
+//! | `hook_abort` | [`HookAbortEvent`] — a hook chain was aborted |
+//!
+//! [`event_schema`] returns the JSON Schema for the envelope.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::error::VerificationCode;
+//! use vcp_core::events::{EventBody, VcpEvent, VerificationEvent};
+//!
+//! let event = VcpEvent::now(EventBody::Verification(VerificationEvent::new(
+//!     VerificationCode::Expired,
+//!     "bundle expired",
+//! )))
+//! .with_tenant("acme");
+//!
+//! assert_eq!(event.event_type(), "verification");
+//! let json = event.to_json().unwrap();
+//! assert_eq!(VcpEvent::from_json(&json).unwrap(), event);
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::context::FullContext;
+use crate::error::{VcpError, VcpResult, VerificationCode};
+use crate::hooks::{ChainResult, HookType};
+use crate::situational::SituationalDimension;
+use crate::transport::VerificationResult;
+
+/// Envelope version produced by this crate.
+pub const EVENT_VERSION: &str = "1.0";
+
+/// All `type` discriminators, in schema order.
+pub const EVENT_TYPES: &[&str] = &[
+    "verification",
+    "revocation",
+    "context_transition",
+    "hook_abort",
+];
+
+// ── Envelope ─────────────────────────────────────────────────
+
+/// A single VCP event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VcpEvent {
+    /// Envelope version (`EVENT_VERSION` for events built here).
+    pub version: String,
+    /// Tenant the event belongs to, for multi-tenant pipelines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+    /// When the underlying action happened.
+    pub occurred_at: DateTime<Utc>,
+    /// Event type and payload (`type` + `payload` on the wire).
+    #[serde(flatten)]
+    pub body: EventBody,
+}
+
+/// Event type and payload.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum EventBody {
+    /// Outcome of a bundle verification.
+    Verification(VerificationEvent),
+    /// A bundle was found revoked.
+    Revocation(RevocationEvent),
+    /// A session's context changed.
+    ContextTransition(ContextTransitionEvent),
+    /// A hook chain was aborted.
+    HookAbort(HookAbortEvent),
+}
+
+impl EventBody {
+    /// The `type` discriminator for this body.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            EventBody::Verification(_) => "verification",
+            EventBody::Revocation(_) => "revocation",
+            EventBody::ContextTransition(_) => "context_transition",
+            EventBody::HookAbort(_) => "hook_abort",
+        }
+    }
+}
+
+impl VcpEvent {
+    /// Create an event that occurred at `occurred_at`.
+    pub fn new(body: EventBody, occurred_at: DateTime<Utc>) -> Self {
+        Self {
+            version: EVENT_VERSION.to_string(),
+            tenant: None,
+            occurred_at,
+            body,
+        }
+    }
+
+    /// Create an event stamped with the current time.
+    pub fn now(body: EventBody) -> Self {
+        Self::new(body, Utc::now())
+    }
+
+    /// Attach a tenant identifier.
+    #[must_use]
+    pub fn with_tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// The `type` discriminator.
+    pub fn event_type(&self) -> &'static str {
+        self.body.event_type()
+    }
+
+    /// Serialize to a single-line JSON string, suitable as a message value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserialize an event, rejecting unsupported major versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] for malformed JSON or an unknown
+    /// `type`, and [`VcpError::ParseError`] if the major version differs
+    /// from [`EVENT_VERSION`].
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        let event: Self = serde_json::from_str(json)?;
+        let major = |v: &str| v.split('.').next().map(str::to_owned);
+        if major(&event.version) != major(EVENT_VERSION) {
+            return Err(VcpError::ParseError(format!(
+                "unsupported event version: {}",
+                event.version
+            )));
+        }
+        Ok(event)
+    }
+}
+
+// ── Payloads ─────────────────────────────────────────────────
+
+/// Outcome of a bundle verification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationEvent {
+    /// Verification outcome.
+    pub code: VerificationCode,
+    /// Human-readable detail.
+    pub message: String,
+    /// Bundle URI, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    /// Bundle instance ID (`timestamps.jti`), if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+}
+
+impl VerificationEvent {
+    /// Create a verification event.
+    pub fn new(code: VerificationCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            bundle_id: None,
+            jti: None,
+        }
+    }
+
+    /// Attach the bundle URI.
+    #[must_use]
+    pub fn with_bundle_id(mut self, bundle_id: impl Into<String>) -> Self {
+        self.bundle_id = Some(bundle_id.into());
+        self
+    }
+
+    /// Attach the bundle instance ID.
+    #[must_use]
+    pub fn with_jti(mut self, jti: impl Into<String>) -> Self {
+        self.jti = Some(jti.into());
+        self
+    }
+}
+
+impl From<&VerificationResult> for VerificationEvent {
+    fn from(result: &VerificationResult) -> Self {
+        Self::new(result.code, result.message.clone())
+    }
+}
+
+/// A bundle was found revoked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationEvent {
+    /// Bundle instance ID that was revoked.
+    pub jti: String,
+    /// Revocation reason, if published.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// ISO 8601 revocation time, if published.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<String>,
+}
+
+/// A session's context changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextTransitionEvent {
+    /// Session the context belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Previous context in wire format.
+    pub from: String,
+    /// New context in wire format.
+    pub to: String,
+    /// Names of the dimensions that changed (e.g. `"time"`, `"energy"`).
+    pub changed: Vec<String>,
+}
+
+impl ContextTransitionEvent {
+    /// Build a transition event by diffing two contexts.
+    pub fn between(session_id: Option<String>, from: &FullContext, to: &FullContext) -> Self {
+        let mut changed: Vec<String> = SituationalDimension::all()
+            .iter()
+            .filter(|d| from.situational.get(**d) != to.situational.get(**d))
+            .map(ToString::to_string)
+            .collect();
+
+        let (a, b) = (&from.personal, &to.personal);
+        for (name, differs) in [
+            ("cognitive", a.cognitive != b.cognitive),
+            ("emotional", a.emotional != b.emotional),
+            ("energy", a.energy != b.energy),
+            ("urgency", a.urgency != b.urgency),
+            ("body", a.body != b.body),
+        ] {
+            if differs {
+                changed.push(name.to_string());
+            }
+        }
+
+        Self {
+            session_id,
+            from: from.to_wire(),
+            to: to.to_wire(),
+            changed,
+        }
+    }
+}
+
+/// A hook chain was aborted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookAbortEvent {
+    /// Hook point whose chain was aborted.
+    pub hook_type: HookType,
+    /// Name of the aborting hook.
+    pub hook_name: String,
+    /// Abort reason given by the hook.
+    pub reason: String,
+    /// Session the chain ran for, if session-scoped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Annotations of the hooks that ran, keyed `hook_name.key`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, serde_json::Value>,
+}
+
+impl HookAbortEvent {
+    /// Build an abort event from a chain result, or `None` if the chain
+    /// was not aborted.
+    pub fn from_chain(
+        hook_type: HookType,
+        session_id: Option<&str>,
+        chain: &ChainResult,
+    ) -> Option<Self> {
+        let hook_name = chain.aborted_by.clone()?;
+        Some(Self {
+            hook_type,
+            hook_name,
+            reason: chain.abort_reason.clone().unwrap_or_default(),
+            session_id: session_id.map(String::from),
+            annotations: chain.annotations.clone(),
+        })
+    }
+}
+
+// ── Schema export ────────────────────────────────────────────
+
+/// JSON Schema (draft 2020-12) describing [`VcpEvent`].
+pub fn event_schema() -> serde_json::Value {
+    let string = serde_json::json!({ "type": "string" });
+    let codes: Vec<String> = VerificationCode::ALL
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let variant = |ty: &str, payload: serde_json::Value| {
+        serde_json::json!({
+            "properties": { "type": { "const": ty }, "payload": payload }
+        })
+    };
+
+    serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": "https://vcp.creed.space/schema/vcp-event/v1.json",
+        "title": "VCP Event Envelope v1",
+        "type": "object",
+        "required": ["type", "version", "payload", "occurred_at"],
+        "properties": {
+            "type": { "enum": EVENT_TYPES },
+            "version": { "type": "string", "pattern": "^1\\.[0-9]+$" },
+            "tenant": string,
+            "occurred_at": { "type": "string", "format": "date-time" },
+            "payload": { "type": "object" }
+        },
+        "oneOf": [
+            variant("verification", serde_json::json!({
+                "type": "object",
+                "required": ["code", "message"],
+                "properties": {
+                    "code": { "enum": codes },
+                    "message": string,
+                    "bundle_id": string,
+                    "jti": string
+                }
+            })),
+            variant("revocation", serde_json::json!({
+                "type": "object",
+                "required": ["jti"],
+                "properties": { "jti": string, "reason": string, "revoked_at": string }
+            })),
+            variant("context_transition", serde_json::json!({
+                "type": "object",
+                "required": ["from", "to", "changed"],
+                "properties": {
+                    "session_id": string,
+                    "from": string,
+                    "to": string,
+                    "changed": { "type": "array", "items": string }
+                }
+            })),
+            variant("hook_abort", serde_json::json!({
+                "type": "object",
+                "required": ["hook_type", "hook_name", "reason"],
+                "properties": {
+                    "hook_type": { "enum": [
+                        "pre_inject", "post_select", "on_transition",
+                        "on_conflict", "on_violation", "periodic"
+                    ] },
+                    "hook_name": string,
+                    "reason": string,
+                    "session_id": string,
+                    "annotations": { "type": "object" }
+                }
+            }))
+        ]
+    })
+}
+
+// ── Tests ────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::ChainState;
+    use chrono::TimeZone;
+
+    fn fixed_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn envelope_wire_shape() {
+        let event = VcpEvent::new(
+            EventBody::Revocation(RevocationEvent {
+                jti: "abc".into(),
+                reason: Some("key compromise".into()),
+                revoked_at: None,
+            }),
+            fixed_time(),
+        )
+        .with_tenant("acme");
+
+        let value: serde_json::Value = serde_json::from_str(&event.to_json().unwrap()).unwrap();
+        assert_eq!(value["type"], "revocation");
+        assert_eq!(value["version"], "1.0");
+        assert_eq!(value["tenant"], "acme");
+        assert_eq!(value["occurred_at"], "2026-03-01T12:00:00Z");
+        assert_eq!(value["payload"]["jti"], "abc");
+    }
+
+    #[test]
+    fn verification_roundtrip() {
+        let result = VerificationResult::fail(VerificationCode::HashMismatch, "hash mismatch");
+        let event = VcpEvent::new(
+            EventBody::Verification(VerificationEvent::from(&result).with_jti("j-1")),
+            fixed_time(),
+        );
+        let json = event.to_json().unwrap();
+        assert!(json.contains(r#""code":"hash_mismatch""#));
+        assert_eq!(VcpEvent::from_json(&json).unwrap(), event);
+    }
+
+    #[test]
+    fn context_transition_lists_changed_dims() {
+        let from = FullContext::from_wire("\u{23F0}\u{1F305}|\u{1F4CD}\u{1F3E1}").unwrap();
+        let to = FullContext::from_wire(
+            "\u{23F0}\u{1F319}|\u{1F4CD}\u{1F3E1}\u{2016}\u{1F9E0}focused:4",
+        )
+        .unwrap();
+        let event = ContextTransitionEvent::between(Some("s1".into()), &from, &to);
+        assert_eq!(event.changed, vec!["time", "cognitive"]);
+        assert_eq!(event.from, from.to_wire());
+    }
+
+    #[test]
+    fn hook_abort_from_chain() {
+        let chain = ChainResult {
+            completed: false,
+            aborted_by: Some("guard".into()),
+            abort_reason: Some("blocked".into()),
+            modified_context: None,
+            modified_constitution: None,
+            results: Vec::new(),
+            annotations: BTreeMap::from([("guard.rule".into(), serde_json::json!("no-pii"))]),
+            chain_state: ChainState::new(),
+        };
+        let event = HookAbortEvent::from_chain(HookType::PreInject, Some("s1"), &chain).unwrap();
+        assert_eq!(event.hook_name, "guard");
+
+        let json = VcpEvent::new(EventBody::HookAbort(event), fixed_time())
+            .to_json()
+            .unwrap();
+        assert!(json.contains(r#""hook_type":"pre_inject""#));
+        assert!(json.contains(r#""annotations":{"guard.rule":"no-pii"}"#));
+
+        let completed = ChainResult {
+            completed: true,
+            aborted_by: None,
+            ..chain
+        };
+        assert!(HookAbortEvent::from_chain(HookType::PreInject, None, &completed).is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_type_and_version() {
+        let bad_type =
+            r#"{"type":"nope","version":"1.0","occurred_at":"2026-03-01T12:00:00Z","payload":{}}"#;
+        assert!(VcpEvent::from_json(bad_type).is_err());
+
+        let bad_version = r#"{"type":"revocation","version":"2.0","occurred_at":"2026-03-01T12:00:00Z","payload":{"jti":"a"}}"#;
+        assert!(matches!(
+            VcpEvent::from_json(bad_version),
+            Err(VcpError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn schema_covers_every_type() {
+        let schema = event_schema();
+        let variants = schema["oneOf"].as_array().unwrap();
+        assert_eq!(variants.len(), EVENT_TYPES.len());
+        for (variant, ty) in variants.iter().zip(EVENT_TYPES) {
+            assert_eq!(variant["properties"]["type"]["const"], *ty);
+        }
+        let codes = &variants[0]["properties"]["payload"]["properties"]["code"]["enum"];
+        assert_eq!(codes.as_array().unwrap().len(), 17);
+        assert_eq!(codes[16], VerificationCode::FetchFailed.to_string());
+    }
+}
@@ -0,0 +1,761 @@
+//! Human-readable breakdowns of VCP artifacts.
+//!
+//! Support threads usually start with someone pasting an opaque string:
+//! `N4+F+E:ACME@1.2.0`, a seven-line CSM-1 token, a row of emoji. [`explain`]
+//! detects what the input is, parses it, and lists every field with its
+//! decoded value and what it means, plus warnings for things that parse
+//! but are probably not what the author intended.
+//!
+//! | Input | Detected by |
+//! |-------|-------------|
+//! | Manifest | Leading `{` with manifest sections (`bundle`, `issuer`, ...) |
+//! | Context (JSON) | Leading `{` with `situational` / `personal` |
+//! | CSM-1 token | `VCP:` header or several lines |
+//! | Context wire | `‖` separator, `ctx1;` header, or a leading emoji |
+//! | VCP/I token | Dotted first segment (`family.safe.guide`) |
+//! | CSM-1 code | Anything else (`N5+F+E`) |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::explain::{explain, InputKind};
+//!
+//! let explanation = explain("N0+F").unwrap();
+//! assert_eq!(explanation.kind, InputKind::Csm1Code);
+//! assert_eq!(explanation.get("persona"), Some("N (nanny)"));
+//! assert!(explanation.warnings[0].contains("disabled"));
+//! ```
+
+use std::fmt::{self, Write as _};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::context::{detect_wire_format, FullContext, WireFormat};
+use crate::context_schema::ContextSchema;
+use crate::csm1::{Csm1Code, Csm1Token, Persona};
+use crate::error::{VcpError, VcpResult};
+use crate::identity::VcpToken;
+use crate::personal::{PersonalDimension, PersonalDimensionKind};
+use crate::scrub::ArtifactKind;
+use crate::situational::SituationalDimension;
+
+/// Values longer than this are shortened in manifest breakdowns.
+const MAX_VALUE_CHARS: usize = 64;
+
+// ── Input kinds ─────────────────────────────────────────────
+
+/// The kind of artifact being explained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputKind {
+    /// VCP/I identity token.
+    IdentityToken,
+    /// CSM-1 compact code.
+    Csm1Code,
+    /// CSM-1 7- or 8-line token.
+    Csm1Token,
+    /// Context, as a wire string or JSON.
+    Context,
+    /// Bundle manifest (JSON).
+    Manifest,
+}
+
+impl InputKind {
+    /// Guess the kind of `raw` without parsing it.
+    pub fn detect(raw: &str) -> Self {
+        let trimmed = raw.trim();
+        if trimmed.starts_with('{') {
+            let is_context = serde_json::from_str::<Value>(trimmed)
+                .is_ok_and(|v| v.get("situational").is_some() || v.get("personal").is_some());
+            return if is_context {
+                Self::Context
+            } else {
+                Self::Manifest
+            };
+        }
+        if detect_wire_format(trimmed) == WireFormat::Ascii {
+            return Self::Context;
+        }
+        ArtifactKind::detect(trimmed).into()
+    }
+
+    /// Human-readable name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::IdentityToken => "VCP/I identity token",
+            Self::Csm1Code => "CSM-1 compact code",
+            Self::Csm1Token => "CSM-1 token",
+            Self::Context => "Context",
+            Self::Manifest => "Bundle manifest",
+        }
+    }
+}
+
+impl From<ArtifactKind> for InputKind {
+    fn from(kind: ArtifactKind) -> Self {
+        match kind {
+            ArtifactKind::IdentityToken => Self::IdentityToken,
+            ArtifactKind::Csm1Code => Self::Csm1Code,
+            ArtifactKind::Csm1Token => Self::Csm1Token,
+            ArtifactKind::Context => Self::Context,
+        }
+    }
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+// ── Explanation ─────────────────────────────────────────────
+
+/// One decoded field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Field name or dotted path (`timestamps.exp`).
+    pub field: String,
+    /// The decoded value.
+    pub value: String,
+    /// What the field means.
+    pub meaning: String,
+}
+
+/// Annotated breakdown of one artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Explanation {
+    /// Detected kind.
+    pub kind: InputKind,
+    /// Fields in the order they appear in the artifact.
+    pub entries: Vec<Entry>,
+    /// Things that parse but look wrong.
+    pub warnings: Vec<String>,
+}
+
+impl Explanation {
+    fn new(kind: InputKind) -> Self {
+        Self {
+            kind,
+            entries: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        field: impl Into<String>,
+        value: impl Into<String>,
+        meaning: impl Into<String>,
+    ) {
+        self.entries.push(Entry {
+            field: field.into(),
+            value: value.into(),
+            meaning: meaning.into(),
+        });
+    }
+
+    fn warn(&mut self, warning: impl Into<String>) {
+        self.warnings.push(warning.into());
+    }
+
+    /// Value of the first entry named `field`.
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.field == field)
+            .map(|e| e.value.as_str())
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.kind)?;
+        let field_width = self
+            .entries
+            .iter()
+            .map(|e| e.field.chars().count())
+            .max()
+            .unwrap_or(0);
+        let value_width = self
+            .entries
+            .iter()
+            .map(|e| e.value.chars().count())
+            .max()
+            .unwrap_or(0);
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "  {:field_width$}  {:value_width$}  {}",
+                entry.field, entry.value, entry.meaning
+            )?;
+        }
+        if !self.warnings.is_empty() {
+            writeln!(f, "warnings:")?;
+            for warning in &self.warnings {
+                writeln!(f, "  - {warning}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ── Entry points ────────────────────────────────────────────
+
+/// Detect the kind of `raw` and explain it.
+///
+/// # Errors
+///
+/// Returns the parser's error if `raw` does not parse as the detected kind.
+pub fn explain(raw: &str) -> VcpResult<Explanation> {
+    explain_as(raw, InputKind::detect(raw))
+}
+
+/// Explain `raw` as a specific kind, skipping detection.
+///
+/// # Errors
+///
+/// Returns the parser's error if `raw` does not parse as `kind`, or
+/// [`VcpError::ParseError`] if a manifest or JSON context is not valid JSON.
+pub fn explain_as(raw: &str, kind: InputKind) -> VcpResult<Explanation> {
+    let trimmed = raw.trim();
+    match kind {
+        InputKind::IdentityToken => Ok(explain_identity(&VcpToken::parse(trimmed)?)),
+        InputKind::Csm1Code => Ok(explain_csm1_code(&Csm1Code::parse(trimmed)?)),
+        InputKind::Csm1Token => Ok(explain_csm1_token(&Csm1Token::parse(trimmed)?)),
+        InputKind::Context => {
+            let ctx = if trimmed.starts_with('{') {
+                serde_json::from_str(trimmed)
+                    .map_err(|e| VcpError::ParseError(format!("invalid context JSON: {e}")))?
+            } else {
+                FullContext::from_wire(trimmed)?
+            };
+            Ok(explain_context(&ctx))
+        }
+        InputKind::Manifest => {
+            let manifest: Value = serde_json::from_str(trimmed)
+                .map_err(|e| VcpError::ParseError(format!("invalid manifest JSON: {e}")))?;
+            explain_manifest(&manifest, Utc::now())
+        }
+    }
+}
+
+// ── VCP/I ───────────────────────────────────────────────────
+
+fn explain_identity(token: &VcpToken) -> Explanation {
+    let mut out = Explanation::new(InputKind::IdentityToken);
+    out.push("domain", token.domain(), "Top-level category");
+    if !token.path().is_empty() {
+        out.push(
+            "path",
+            token.path().join("."),
+            "Sub-categories between domain and approach",
+        );
+    }
+    out.push("approach", token.approach(), "Approach or method");
+    out.push("role", token.role(), "Role or function");
+    if let Some(version) = &token.version {
+        out.push("version", version.to_string(), "Pinned bundle version");
+    } else {
+        out.warn("no @version: resolves to whatever version the registry considers latest");
+    }
+    if let Some(ns) = &token.namespace {
+        out.push("namespace", ns.as_str(), "Organisation namespace");
+    }
+    out
+}
+
+// ── CSM-1 ───────────────────────────────────────────────────
+
+fn persona_value(persona: Persona) -> String {
+    format!("{} ({})", persona.code(), persona.name())
+}
+
+fn explain_csm1_code(code: &Csm1Code) -> Explanation {
+    let mut out = Explanation::new(InputKind::Csm1Code);
+    out.push(
+        "persona",
+        persona_value(code.persona),
+        code.persona.description(),
+    );
+    out.push(
+        "level",
+        code.adherence_level.to_string(),
+        code.level_description(),
+    );
+    for scope in &code.scopes {
+        out.push(
+            "scope",
+            format!("{} ({})", scope.code(), scope.name()),
+            scope.description(),
+        );
+    }
+    if code.scopes.is_empty() {
+        out.push("scope", "-", "No scopes: applies everywhere");
+    }
+    if let Some(ns) = &code.namespace {
+        out.push(
+            "namespace",
+            ns.as_str(),
+            "Organisation namespace for custom extensions",
+        );
+    }
+    if let Some(version) = &code.version {
+        out.push("version", version.as_str(), "Persona definition version");
+    }
+
+    if !code.is_active() {
+        out.warn("level 0 means the persona is disabled");
+    }
+    if code.persona == Persona::Custom && code.namespace.is_none() {
+        out.warn("custom persona without a :NAMESPACE cannot be resolved to a definition");
+    }
+    out
+}
+
+fn explain_csm1_token(token: &Csm1Token) -> Explanation {
+    let mut out = Explanation::new(InputKind::Csm1Token);
+    out.push(
+        "version",
+        token.version.as_str(),
+        "CSM-1 token format version",
+    );
+    out.push(
+        "profile",
+        token.profile_id.as_str(),
+        "Profile the token was issued for",
+    );
+    out.push(
+        "constitution",
+        format!("{}@{}", token.constitution.id, token.constitution.version),
+        "Constitution the model should follow",
+    );
+    out.push(
+        "persona",
+        persona_value(token.persona),
+        token.persona.description(),
+    );
+    out.push(
+        "adherence",
+        token.adherence.to_string(),
+        "How strictly the persona is applied (1-5)",
+    );
+    if let Some(goal) = &token.goal {
+        out.push(
+            "goal",
+            goal.goal.as_str(),
+            "What the user is trying to achieve",
+        );
+        out.push(
+            "experience",
+            goal.experience.as_str(),
+            "The user's experience level",
+        );
+        out.push("style", goal.style.as_str(), "Preferred interaction style");
+    }
+    let constraints: Vec<&str> = token.constraints.iter().map(|c| c.0.as_str()).collect();
+    out.push(
+        "constraints",
+        list_or_dash(&constraints),
+        "Active constraint flags",
+    );
+    out.push(
+        "flags",
+        list_or_dash(&token.flags),
+        "Regulatory and feature flags",
+    );
+    out.push(
+        "private",
+        format!("{} marker(s)", token.private_markers.len()),
+        "Private markers; shared only as opaque flags",
+    );
+    if let Some(state) = &token.personal_state {
+        for kind in PersonalDimensionKind::all() {
+            if let Some(dim) = state.get(*kind) {
+                push_personal(&mut out, *kind, dim);
+            }
+        }
+    }
+
+    if !token.private_markers.is_empty() {
+        out.warn("token carries private markers; scrub it before sharing");
+    }
+    if token.version != "1.0" {
+        out.warn(format!("unrecognised token version {}", token.version));
+    }
+    out
+}
+
+fn list_or_dash<S: AsRef<str>>(items: &[S]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+// ── Context ─────────────────────────────────────────────────
+
+fn situational_meaning(dim: SituationalDimension) -> &'static str {
+    match dim {
+        SituationalDimension::Time => "When the interaction happens",
+        SituationalDimension::Space => "Where the user is",
+        SituationalDimension::Company => "Who else is present",
+        SituationalDimension::Culture => "Cultural register to respect",
+        SituationalDimension::Occasion => "What kind of occasion this is",
+        SituationalDimension::Environment => "Physical conditions",
+        SituationalDimension::Agency => "The user's authority or role",
+        SituationalDimension::Constraints => "Limits on the interaction",
+        SituationalDimension::SystemContext => "State of the surrounding system",
+        SituationalDimension::Embodiment => "Physical form of the agent",
+        SituationalDimension::Proximity => "Distance between agent and user",
+        SituationalDimension::Relationship => "Relationship between agent and user",
+        SituationalDimension::Formality => "Expected formality",
+    }
+}
+
+fn personal_meaning(kind: PersonalDimensionKind) -> &'static str {
+    match kind {
+        PersonalDimensionKind::CognitiveState => "How the user is thinking",
+        PersonalDimensionKind::EmotionalTone => "How the user is feeling",
+        PersonalDimensionKind::EnergyLevel => "How much energy the user has",
+        PersonalDimensionKind::PerceivedUrgency => "How pressed for time the user feels",
+        PersonalDimensionKind::BodySignals => "Physical wellbeing",
+    }
+}
+
+fn push_personal(out: &mut Explanation, kind: PersonalDimensionKind, dim: &PersonalDimension) {
+    let mut value = format!("{} (intensity {})", dim.value, dim.intensity);
+    if let Some(extended) = &dim.extended {
+        value = format!("{}:{extended} (intensity {})", dim.value, dim.intensity);
+    }
+    let metadata: Vec<String> = [
+        dim.source.map(|source| format!("{source:?}")),
+        dim.declared_at
+            .map(|at| format!("declared {}", at.to_rfc3339())),
+        dim.confidence.map(|c| format!("confidence {c}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !metadata.is_empty() {
+        value = format!("{value}; {}", metadata.join(", "));
+    }
+    out.push(kind.to_string(), value, personal_meaning(kind));
+}
+
+fn explain_context(ctx: &FullContext) -> Explanation {
+    let mut out = Explanation::new(InputKind::Context);
+    for dim in SituationalDimension::all() {
+        let Some(tags) = ctx.situational.get(*dim) else {
+            continue;
+        };
+        let names: Vec<String> = tags
+            .iter()
+            .map(|tag| match dim.tag_name(tag) {
+                Some(name) => format!("{tag} {name}"),
+                None => tag.clone(),
+            })
+            .collect();
+        out.push(dim.to_string(), names.join(", "), situational_meaning(*dim));
+    }
+    for kind in PersonalDimensionKind::all() {
+        if let Some(dim) = ctx.personal.get(*kind) {
+            push_personal(&mut out, *kind, dim);
+        }
+    }
+
+    if !ctx.redactions.is_empty() {
+        out.push(
+            "redacted",
+            ctx.redactions.join(", "),
+            "Privacy profiles applied before sharing; their dimensions were withheld",
+        );
+    }
+    if let Some(consent) = &ctx.consent {
+        let mut value = format!("for {}", consent.purposes.join(", "));
+        if let Some(exp) = consent.expires_at {
+            let _ = write!(value, " until {}", exp.format("%Y-%m-%d %H:%M UTC"));
+        }
+        out.push(
+            "consent",
+            value,
+            "Purposes the user agreed to; strict consumers reject any other use",
+        );
+    }
+
+    if !ctx.has_any() {
+        out.warn("context is empty");
+    }
+    out.push(
+        "conformance",
+        ctx.conformance_level().label(),
+        "Highest conformance level this context needs",
+    );
+    for issue in ctx.validate(&ContextSchema::default()) {
+        out.warn(issue.to_string());
+    }
+    out
+}
+
+// ── Manifest ────────────────────────────────────────────────
+
+/// Known manifest fields: dotted path, meaning, and whether the schema
+/// requires it.
+const MANIFEST_FIELDS: &[(&str, &str, bool)] = &[
+    ("vcp_version", "Protocol version the manifest targets", true),
+    ("token_type", "Kind of token in the bundle", false),
+    ("bundle.id", "Bundle URI", true),
+    ("bundle.version", "Bundle version", true),
+    (
+        "bundle.content_hash",
+        "SHA-256 of the canonical content",
+        true,
+    ),
+    ("bundle.content_format", "Content MIME type", false),
+    ("issuer.id", "Who published the bundle", true),
+    ("issuer.public_key", "Issuer Ed25519 public key", true),
+    (
+        "issuer.key_id",
+        "Issuer key, matched against trust anchors",
+        true,
+    ),
+    ("timestamps.iat", "Issued at", true),
+    ("timestamps.nbf", "Not valid before", true),
+    ("timestamps.exp", "Expires at", true),
+    (
+        "timestamps.jti",
+        "Unique manifest ID, used for replay detection",
+        true,
+    ),
+    ("budget.token_count", "Tokens the content occupies", true),
+    ("budget.tokenizer", "Tokenizer used for the count", true),
+    (
+        "budget.max_context_share",
+        "Largest share of the context window allowed",
+        false,
+    ),
+    (
+        "scope.model_families",
+        "Model families the bundle may be used with",
+        false,
+    ),
+    (
+        "scope.purposes",
+        "Purposes the bundle may be used for",
+        false,
+    ),
+    ("scope.regions", "Regions the bundle may be used in", false),
+    (
+        "composition.layer",
+        "Composition layer (0 platform .. 4 session)",
+        false,
+    ),
+    (
+        "composition.mode",
+        "How the bundle combines with lower layers",
+        false,
+    ),
+    ("revocation.check_uri", "Live revocation endpoint", false),
+    ("revocation.crl_uri", "Revocation list", false),
+    (
+        "safety_attestation.auditor",
+        "Auditor that reviewed the content",
+        true,
+    ),
+    (
+        "safety_attestation.auditor_key_id",
+        "Auditor key, matched against trust anchors",
+        true,
+    ),
+    (
+        "safety_attestation.reviewed_at",
+        "When the review happened",
+        true,
+    ),
+    (
+        "safety_attestation.attestation_type",
+        "What the review covered",
+        true,
+    ),
+    ("safety_attestation.signature", "Auditor signature", true),
+    ("metadata.title", "Human-readable title", false),
+    (
+        "metadata.persona",
+        "Persona the bundle is written for",
+        false,
+    ),
+    ("metadata.csm1", "CSM-1 code summarising the bundle", false),
+    ("signature.algorithm", "Signature algorithm", true),
+    ("signature.value", "Issuer signature", true),
+    (
+        "signature.signed_fields",
+        "Fields covered by the signature",
+        true,
+    ),
+];
+
+fn lookup<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    path.split('.').try_fold(value, |v, key| v.get(key))
+}
+
+fn display_value(value: &Value) -> String {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(|v| v.as_str().map_or_else(|| v.to_string(), str::to_string))
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    };
+    if text.chars().count() > MAX_VALUE_CHARS {
+        let head: String = text.chars().take(MAX_VALUE_CHARS - 3).collect();
+        format!("{head}...")
+    } else {
+        text
+    }
+}
+
+fn parse_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.as_str()?)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn explain_manifest(manifest: &Value, now: DateTime<Utc>) -> VcpResult<Explanation> {
+    if !manifest.is_object() {
+        return Err(VcpError::ParseError(
+            "manifest must be a JSON object".into(),
+        ));
+    }
+    let mut out = Explanation::new(InputKind::Manifest);
+    for (path, meaning, required) in MANIFEST_FIELDS {
+        match lookup(manifest, path) {
+            Some(value) => out.push(*path, display_value(value), *meaning),
+            None if *required => out.warn(format!("missing required field {path}")),
+            None => {}
+        }
+    }
+
+    let timestamp = |field: &str| lookup(manifest, field).and_then(parse_timestamp);
+    if let Some(exp) = timestamp("timestamps.exp") {
+        if exp <= now {
+            out.warn(format!("manifest expired at {}", exp.to_rfc3339()));
+        }
+    }
+    if let Some(nbf) = timestamp("timestamps.nbf") {
+        if nbf > now {
+            out.warn(format!("manifest is not valid until {}", nbf.to_rfc3339()));
+        }
+    }
+    for field in ["timestamps.iat", "timestamps.nbf", "timestamps.exp"] {
+        if lookup(manifest, field).is_some() && timestamp(field).is_none() {
+            out.warn(format!("{field} is not an RFC 3339 timestamp"));
+        }
+    }
+    if let Some(code) = lookup(manifest, "metadata.csm1").and_then(Value::as_str) {
+        if let Err(e) = Csm1Code::parse(code) {
+            out.warn(format!("metadata.csm1 does not parse: {e}"));
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> Value {
+        serde_json::json!({
+            "vcp_version": "1.0",
+            "bundle": {"id": "creed://acme/family", "version": "1.0.0", "content_hash": "sha256:ab"},
+            "issuer": {"id": "acme.example", "public_key": "AAAA", "key_id": "k1"},
+            "timestamps": {
+                "iat": "2026-01-01T00:00:00Z",
+                "nbf": "2026-01-01T00:00:00Z",
+                "exp": "2026-06-01T00:00:00Z",
+                "jti": "1f1e8c1a-0000-4000-8000-000000000000"
+            },
+            "budget": {"token_count": 120, "tokenizer": "cl100k_base"},
+            "metadata": {"csm1": "Q9"},
+            "signature": {"algorithm": "ed25519", "value": "sig", "signed_fields": ["bundle", "issuer"]}
+        })
+    }
+
+    #[test]
+    fn detects_each_kind() {
+        assert_eq!(
+            InputKind::detect("family.safe.guide@1.2.0"),
+            InputKind::IdentityToken
+        );
+        assert_eq!(InputKind::detect("N5+F+E"), InputKind::Csm1Code);
+        assert_eq!(
+            InputKind::detect("VCP:1.0:p\nC:c@1.0\nP:N:5\nG:\nX:\nF:\nS:"),
+            InputKind::Csm1Token
+        );
+        assert_eq!(InputKind::detect("\u{23F0}\u{1F305}"), InputKind::Context);
+        assert_eq!(InputKind::detect("ctx1;time=morning"), InputKind::Context);
+        assert_eq!(
+            InputKind::detect(r#"{"situational": {}}"#),
+            InputKind::Context
+        );
+        assert_eq!(InputKind::detect(r#"{"bundle": {}}"#), InputKind::Manifest);
+    }
+
+    #[test]
+    fn explains_csm1_code_fields() {
+        let out = explain("C3+W:ACME@1.0.0").unwrap();
+        assert_eq!(out.get("persona"), Some("C (custom)"));
+        assert_eq!(out.get("level"), Some("3"));
+        assert_eq!(out.get("scope"), Some("W (work)"));
+        assert_eq!(out.get("namespace"), Some("ACME"));
+        assert!(out.warnings.is_empty());
+
+        let bare = explain("C3").unwrap();
+        assert!(bare.warnings[0].contains("NAMESPACE"));
+    }
+
+    #[test]
+    fn explains_context_with_tag_names() {
+        let out =
+            explain("\u{23F0}\u{1F305}|\u{1F4CD}\u{1F3E1}\u{2016}\u{1F9E0}focused:4").unwrap();
+        assert_eq!(out.get("time"), Some("\u{1F305} morning"));
+        assert_eq!(out.get("space"), Some("\u{1F3E1} home"));
+        assert_eq!(out.get("cognitive_state"), Some("focused (intensity 4)"));
+    }
+
+    #[test]
+    fn warns_on_private_markers() {
+        let out = explain("VCP:1.0:p\nC:family.safe@1.0\nP:N:5\nG:\nX:\nF:\nS:marker").unwrap();
+        assert_eq!(out.get("private"), Some("1 marker(s)"));
+        assert!(out.warnings.iter().any(|w| w.contains("private markers")));
+    }
+
+    #[test]
+    fn manifest_warnings() {
+        let now = DateTime::parse_from_rfc3339("2026-07-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let out = explain_manifest(&manifest(), now).unwrap();
+        assert_eq!(out.get("bundle.id"), Some("creed://acme/family"));
+        assert_eq!(out.get("signature.signed_fields"), Some("bundle, issuer"));
+        assert!(out
+            .warnings
+            .iter()
+            .any(|w| w.contains("safety_attestation.auditor")));
+        assert!(out.warnings.iter().any(|w| w.contains("expired")));
+        assert!(out.warnings.iter().any(|w| w.contains("metadata.csm1")));
+    }
+
+    #[test]
+    fn parse_errors_propagate() {
+        assert!(explain("family.").is_err());
+        assert!(explain("{not json").is_err());
+    }
+}
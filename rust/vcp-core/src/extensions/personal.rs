@@ -5,7 +5,9 @@
 //! exponential/linear/step decay over time.
 
 use std::fmt;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+
+use crate::clock::Clock;
 
 // ── Enums ──────────────────────────────────────────────────────────────────
 
@@ -41,6 +43,31 @@ pub enum SignalSource {
     Decayed,
 }
 
+impl SignalSource {
+    /// One-letter code used in the personal wire format (`~d`).
+    pub fn code(self) -> char {
+        match self {
+            Self::Declared => 'd',
+            Self::Inferred => 'i',
+            Self::InferredLocal => 'l',
+            Self::Preset => 'p',
+            Self::Decayed => 'x',
+        }
+    }
+
+    /// Parse a code produced by [`code`](Self::code).
+    pub fn from_code(code: char) -> Option<Self> {
+        match code {
+            'd' => Some(Self::Declared),
+            'i' => Some(Self::Inferred),
+            'l' => Some(Self::InferredLocal),
+            'p' => Some(Self::Preset),
+            'x' => Some(Self::Decayed),
+            _ => None,
+        }
+    }
+}
+
 /// Lifecycle state for a personal dimension signal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum LifecycleState {
@@ -113,6 +140,24 @@ impl PersonalSignal {
         self.declared_at = Some(at);
         self
     }
+
+    /// Intensity after decay up to `clock`'s current time. A signal with
+    /// no declaration time has not decayed.
+    pub fn decayed_intensity(&self, config: &DecayConfig, clock: &dyn Clock) -> u8 {
+        match self.declared_at {
+            Some(at) => compute_decayed_intensity(self.intensity, at, config, clock.system_time()),
+            None => self.intensity,
+        }
+    }
+
+    /// Lifecycle state at `clock`'s current time. A signal with no
+    /// declaration time is [`LifecycleState::Active`].
+    pub fn lifecycle_state(&self, config: &DecayConfig, clock: &dyn Clock) -> LifecycleState {
+        match self.declared_at {
+            Some(at) => compute_lifecycle_state(self.intensity, at, config, clock.system_time()),
+            None => LifecycleState::Active,
+        }
+    }
 }
 
 /// Personal state context (5 dimensions).
@@ -134,6 +179,61 @@ impl PersonalContext {
             || self.perceived_urgency.is_some()
             || self.body_signals.is_some()
     }
+
+    /// The signal for `dim`, if set.
+    pub fn signal(&self, dim: PersonalDimension) -> Option<&PersonalSignal> {
+        match dim {
+            PersonalDimension::CognitiveState => self.cognitive_state.as_ref(),
+            PersonalDimension::EmotionalTone => self.emotional_tone.as_ref(),
+            PersonalDimension::EnergyLevel => self.energy_level.as_ref(),
+            PersonalDimension::PerceivedUrgency => self.perceived_urgency.as_ref(),
+            PersonalDimension::BodySignals => self.body_signals.as_ref(),
+        }
+    }
+
+    /// Mutable access to the signal for `dim`, if set.
+    pub fn signal_mut(&mut self, dim: PersonalDimension) -> Option<&mut PersonalSignal> {
+        match dim {
+            PersonalDimension::CognitiveState => self.cognitive_state.as_mut(),
+            PersonalDimension::EmotionalTone => self.emotional_tone.as_mut(),
+            PersonalDimension::EnergyLevel => self.energy_level.as_mut(),
+            PersonalDimension::PerceivedUrgency => self.perceived_urgency.as_mut(),
+            PersonalDimension::BodySignals => self.body_signals.as_mut(),
+        }
+    }
+
+    /// Record that the user engaged with `dim` at `now`, under the
+    /// dimension's [default decay config](default_decay_config).
+    ///
+    /// See [`record_engagement_with`](Self::record_engagement_with).
+    pub fn record_engagement(&mut self, dim: PersonalDimension, now: SystemTime) -> bool {
+        self.record_engagement_with(dim, &default_decay_config(dim), now)
+    }
+
+    /// Record that the user engaged with `dim` at `now`.
+    ///
+    /// If `config.reset_on_engagement` is set, the signal's decay timer
+    /// restarts: `declared_at` moves to `now`, so it decays from its
+    /// declared intensity again. Returns `true` if the timer was reset.
+    /// Nothing happens for an unset signal, one with no `declared_at`
+    /// (it is not decaying), or a `now` earlier than `declared_at`.
+    pub fn record_engagement_with(
+        &mut self,
+        dim: PersonalDimension,
+        config: &DecayConfig,
+        now: SystemTime,
+    ) -> bool {
+        if !config.reset_on_engagement {
+            return false;
+        }
+        match self.signal_mut(dim) {
+            Some(signal) if signal.declared_at.is_some_and(|at| at < now) => {
+                signal.declared_at = Some(now);
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 /// A discrete intensity step for step-curve decay.
@@ -351,12 +451,61 @@ pub fn compute_lifecycle_state(
     }
 }
 
+// ── Simulation ─────────────────────────────────────────────────────────────
+
+/// One point of a [`simulate`] time series.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DecaySample {
+    /// Seconds since the signal was declared.
+    pub t: f64,
+    /// Effective intensity at `t`.
+    pub intensity: u8,
+    /// Lifecycle state at `t`.
+    pub state: LifecycleState,
+}
+
+/// Sample `signal`'s decay under `config` every `step` from declaration
+/// until `duration` has elapsed, both ends included.
+///
+/// Use this to tune half-lives and thresholds before shipping a config.
+/// Only the elapsed time matters: the signal's `declared_at`, if any, is
+/// ignored. A zero `step` yields the single sample at `t = 0`.
+pub fn simulate(
+    signal: &PersonalSignal,
+    config: &DecayConfig,
+    duration: Duration,
+    step: Duration,
+) -> Vec<DecaySample> {
+    let declared = SystemTime::UNIX_EPOCH;
+    let sample = |elapsed: Duration| {
+        let now = declared + elapsed;
+        DecaySample {
+            t: elapsed.as_secs_f64(),
+            intensity: compute_decayed_intensity(signal.intensity, declared, config, now),
+            state: compute_lifecycle_state(signal.intensity, declared, config, now),
+        }
+    };
+
+    let mut samples = vec![sample(Duration::ZERO)];
+    if step.is_zero() {
+        return samples;
+    }
+    let mut elapsed = step;
+    while elapsed < duration {
+        samples.push(sample(elapsed));
+        elapsed += step;
+    }
+    if duration > Duration::ZERO {
+        samples.push(sample(duration));
+    }
+    samples
+}
+
 // ── Tests ──────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
 
     fn time_plus_secs(base: SystemTime, secs: f64) -> SystemTime {
         base + Duration::from_secs_f64(secs)
@@ -525,6 +674,103 @@ mod tests {
         assert_eq!(state, LifecycleState::Active);
     }
 
+    #[test]
+    fn test_signal_decays_against_injected_clock() {
+        use crate::clock::MockClock;
+        use chrono::{TimeZone, Utc};
+
+        let declared = Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap();
+        let clock = MockClock::new(declared);
+        let config = DecayConfig::exponential(900.0);
+        let signal = PersonalSignal::new("urgent", 5).with_declared_at(declared.into());
+
+        assert_eq!(signal.decayed_intensity(&config, &clock), 5);
+        clock.advance(chrono::Duration::seconds(900));
+        assert_eq!(signal.decayed_intensity(&config, &clock), 3);
+        assert_eq!(
+            signal.lifecycle_state(&config, &clock),
+            LifecycleState::Decaying
+        );
+        clock.advance(chrono::Duration::hours(4));
+        assert_eq!(
+            signal.lifecycle_state(&config, &clock),
+            LifecycleState::Expired
+        );
+
+        let undeclared = PersonalSignal::new("urgent", 5);
+        assert_eq!(undeclared.decayed_intensity(&config, &clock), 5);
+        assert_eq!(
+            undeclared.lifecycle_state(&config, &clock),
+            LifecycleState::Active
+        );
+    }
+
+    #[test]
+    fn test_simulate_samples_both_ends() {
+        let signal = PersonalSignal::new("urgent", 5);
+        let config = DecayConfig::exponential(900.0);
+        let series = simulate(
+            &signal,
+            &config,
+            Duration::from_secs(2000),
+            Duration::from_mins(15),
+        );
+
+        let times: Vec<f64> = series.iter().map(|s| s.t).collect();
+        assert_eq!(times.len(), 4);
+        assert!((times[3] - 2000.0).abs() < f64::EPSILON);
+        assert_eq!(series[0].intensity, 5);
+        assert_eq!(series[0].state, LifecycleState::Set);
+        assert_eq!(series[1].intensity, 3);
+        assert_eq!(series[1].state, LifecycleState::Decaying);
+        assert!(series.windows(2).all(|w| w[0].intensity >= w[1].intensity));
+
+        let single = simulate(&signal, &config, Duration::from_mins(1), Duration::ZERO);
+        assert_eq!(single.len(), 1);
+    }
+
+    #[test]
+    fn test_engagement_resets_only_configured_dimensions() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_hours(1000);
+        let mut ctx = PersonalContext {
+            cognitive_state: Some(PersonalSignal::new("focused", 5).with_declared_at(t0)),
+            perceived_urgency: Some(PersonalSignal::new("urgent", 5).with_declared_at(t0)),
+            ..Default::default()
+        };
+        let intensity_at = |ctx: &PersonalContext, dim, secs| {
+            let signal = ctx.signal(dim).unwrap();
+            compute_decayed_intensity(
+                signal.intensity,
+                signal.declared_at.unwrap(),
+                &default_decay_config(dim),
+                time_plus_secs(t0, secs),
+            )
+        };
+        let cognitive = PersonalDimension::CognitiveState;
+        let urgency = PersonalDimension::PerceivedUrgency;
+
+        // One cognitive half-life in, both have decayed.
+        assert_eq!(intensity_at(&ctx, cognitive, 720.0), 3);
+        assert!(ctx.record_engagement(cognitive, time_plus_secs(t0, 720.0)));
+        assert!(!ctx.record_engagement(urgency, time_plus_secs(t0, 720.0)));
+        assert_eq!(intensity_at(&ctx, cognitive, 720.0), 5);
+        assert_eq!(intensity_at(&ctx, urgency, 900.0), 3);
+
+        // Decay resumes from the engagement, and engaging again resets it again.
+        assert_eq!(intensity_at(&ctx, cognitive, 1440.0), 3);
+        assert!(ctx.record_engagement(cognitive, time_plus_secs(t0, 1800.0)));
+        assert_eq!(intensity_at(&ctx, cognitive, 1800.0), 5);
+        assert_eq!(intensity_at(&ctx, urgency, 1800.0), 2);
+
+        // Stale engagements and unset or timerless signals are ignored.
+        assert!(!ctx.record_engagement(cognitive, time_plus_secs(t0, 60.0)));
+        assert!(!ctx.record_engagement(PersonalDimension::EnergyLevel, t0));
+        ctx.emotional_tone = Some(PersonalSignal::new("calm", 3));
+        let always_reset = DecayConfig::exponential(60.0).with_reset_on_engagement(true);
+        assert!(!ctx.record_engagement_with(PersonalDimension::EmotionalTone, &always_reset, t0));
+        assert!(ctx.emotional_tone.as_ref().unwrap().declared_at.is_none());
+    }
+
     #[test]
     fn test_default_decay_configs() {
         let urg = default_decay_config(PersonalDimension::PerceivedUrgency);
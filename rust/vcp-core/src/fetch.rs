@@ -0,0 +1,279 @@
+//! Fetching registry-hosted bundles over HTTP(S) (feature `http`).
+//!
+//! [`fetch_limited`] downloads one resource after the SSRF checks of
+//! [`validate_uri`], and a second check that the host name does not
+//! resolve to a private or reserved address. That check runs inside the
+//! HTTP client's resolver, so the connection goes to exactly the
+//! addresses that were checked: a DNS server that answers with a public
+//! address for the check and a private one for the connection (DNS
+//! rebinding) gets only one lookup. Redirects are not followed, since
+//! their target would bypass both checks, and the body is read only up to
+//! a size limit, so an oversized resource is never held in full.
+//!
+//! [`Orchestrator::fetch_and_verify`](crate::orchestrator::Orchestrator::fetch_and_verify)
+//! fetches a manifest and its content this way and runs the full
+//! verification pipeline on them.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use vcp_core::orchestrator::{Orchestrator, VerificationContext};
+//! use vcp_core::trust::TrustConfig;
+//!
+//! let trust = TrustConfig::from_json(&std::fs::read_to_string("trust.json")?)?;
+//! let orch = Orchestrator::new(trust.clone());
+//! let outcome = orch.fetch_and_verify(
+//!     "https://registry.example.org/bundles/family-safe/manifest.json",
+//!     "https://registry.example.org/bundles/family-safe/content",
+//!     &VerificationContext::new(trust),
+//! )?;
+//! println!("{}", outcome.code);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fmt;
+use std::io::{self, Read};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
+
+use ureq::config::Config;
+use ureq::http::Uri;
+use ureq::unversioned::resolver::{ResolvedSocketAddrs, Resolver};
+use ureq::unversioned::transport::{DefaultConnector, NextTimeout};
+
+use crate::error::{VcpError, VcpResult};
+use crate::revocation::{is_private_ip, validate_uri};
+
+/// Longest a download may take, from connecting to the last body byte.
+pub const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The outcome of a size-limited download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The whole body, no longer than the limit.
+    Body(Vec<u8>),
+    /// The body was longer than the limit; reading stopped there.
+    TooLarge,
+}
+
+/// Download `url`, reading at most `limit` bytes of the body.
+///
+/// The URL must pass [`validate_uri`] and its host must not resolve to a
+/// private address. Only a `2xx` response is accepted; a redirect is
+/// reported as an error rather than followed.
+///
+/// # Errors
+///
+/// Returns the error from [`validate_uri`] for an unsafe URL, and
+/// [`VcpError::IoError`] when the host resolves to a private address or
+/// the request fails.
+pub fn fetch_limited(url: &str, limit: usize) -> VcpResult<Fetched> {
+    validate_uri(url)?;
+    let agent = public_agent(PublicOnlyResolver::system(), FETCH_TIMEOUT);
+    get_limited(&agent, url, limit)
+}
+
+/// An agent that connects only through `resolver` and never follows
+/// redirects.
+fn public_agent(resolver: PublicOnlyResolver, timeout: Duration) -> ureq::Agent {
+    let config = ureq::Agent::config_builder()
+        .max_redirects(0)
+        .timeout_global(Some(timeout))
+        .build();
+    ureq::Agent::with_parts(config, DefaultConnector::default(), resolver)
+}
+
+fn get_limited(agent: &ureq::Agent, url: &str, limit: usize) -> VcpResult<Fetched> {
+    let response = agent
+        .get(url)
+        .call()
+        .map_err(|e| VcpError::IoError(format!("cannot fetch {url}: {e}")))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(VcpError::IoError(format!(
+            "cannot fetch {url}: HTTP {status} (redirects are not followed)"
+        )));
+    }
+    read_limited(response.into_body().into_reader(), limit)
+        .map_err(|e| VcpError::IoError(format!("cannot fetch {url}: {e}")))
+}
+
+/// Read `reader` to the end, or until it has given more than `limit`
+/// bytes.
+fn read_limited(reader: impl Read, limit: usize) -> std::io::Result<Fetched> {
+    let mut body = Vec::new();
+    let cap = u64::try_from(limit).unwrap_or(u64::MAX).saturating_add(1);
+    reader.take(cap).read_to_end(&mut body)?;
+    Ok(if body.len() > limit {
+        Fetched::TooLarge
+    } else {
+        Fetched::Body(body)
+    })
+}
+
+// ── Resolution ──────────────────────────────────────────────
+
+/// How many addresses a ureq resolver may return.
+const MAX_RESOLVED: usize = 16;
+
+/// Looks a host and port up; the system resolver outside tests.
+type Lookup = dyn Fn(&str, u16) -> io::Result<Vec<SocketAddr>> + Send + Sync;
+
+/// A resolver that fails when any address for the host is private or
+/// reserved, and otherwise hands the connector the addresses it checked.
+/// [`validate_uri`] only sees literal IPs.
+struct PublicOnlyResolver {
+    lookup: Box<Lookup>,
+}
+
+impl PublicOnlyResolver {
+    fn system() -> Self {
+        Self::with_lookup(|host, port| (host, port).to_socket_addrs().map(Iterator::collect))
+    }
+
+    fn with_lookup(
+        lookup: impl Fn(&str, u16) -> io::Result<Vec<SocketAddr>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            lookup: Box::new(lookup),
+        }
+    }
+}
+
+impl fmt::Debug for PublicOnlyResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublicOnlyResolver").finish_non_exhaustive()
+    }
+}
+
+impl Resolver for PublicOnlyResolver {
+    fn resolve(
+        &self,
+        uri: &Uri,
+        _config: &Config,
+        _timeout: NextTimeout,
+    ) -> Result<ResolvedSocketAddrs, ureq::Error> {
+        let host = uri.host().ok_or(ureq::Error::HostNotFound)?;
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let port = uri
+            .port_u16()
+            .unwrap_or(if uri.scheme_str() == Some("https") {
+                443
+            } else {
+                80
+            });
+        let addrs = (self.lookup)(host, port)?;
+        if let Some(private) = addrs.iter().find(|addr| is_private_ip(addr.ip())) {
+            return Err(ureq::Error::Io(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "{host} resolves to private/reserved address {}",
+                    private.ip()
+                ),
+            )));
+        }
+        let mut resolved = self.empty();
+        for addr in addrs.into_iter().take(MAX_RESOLVED) {
+            resolved.push(addr);
+        }
+        if resolved.is_empty() {
+            return Err(ureq::Error::HostNotFound);
+        }
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn read_limited_keeps_body_at_limit() {
+        let body = read_limited(&b"0123456789"[..], 10).unwrap();
+        assert_eq!(body, Fetched::Body(b"0123456789".to_vec()));
+    }
+
+    #[test]
+    fn read_limited_stops_past_limit() {
+        let mut reader = std::io::repeat(b'x');
+        assert_eq!(read_limited(&mut reader, 1024).unwrap(), Fetched::TooLarge);
+    }
+
+    #[test]
+    fn unsafe_urls_are_rejected_before_fetching() {
+        for url in [
+            "file:///etc/passwd",
+            "http://127.0.0.1/manifest.json",
+            "http://[::1]/manifest.json",
+            "https://localhost/manifest.json",
+            "https://registry.example.org:8443/manifest.json",
+        ] {
+            assert!(fetch_limited(url, 1024).is_err(), "{url}");
+        }
+    }
+
+    /// A loopback server that answers every request with `body`, and the
+    /// number of connections it has accepted.
+    fn local_server(body: &'static str) -> (u16, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&accepted);
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = stream.read(&mut [0u8; 1024]);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+        (port, accepted)
+    }
+
+    #[test]
+    fn rebinding_cannot_redirect_the_connection() {
+        let (port, accepted) = local_server("secret");
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&lookups);
+        // Public for the first lookup, loopback for any after it.
+        let resolver = PublicOnlyResolver::with_lookup(move |_, port| {
+            let ip = if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                "192.0.2.1"
+            } else {
+                "127.0.0.1"
+            };
+            Ok(vec![SocketAddr::new(ip.parse().unwrap(), port)])
+        });
+        let agent = public_agent(resolver, Duration::from_secs(2));
+
+        let result = get_limited(&agent, &format!("http://rebind.example:{port}/"), 1024);
+        assert!(result.is_err());
+        assert_eq!(lookups.load(Ordering::SeqCst), 1);
+        assert_eq!(accepted.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn any_private_address_rejects_the_host() {
+        let (port, accepted) = local_server("secret");
+        let resolver = PublicOnlyResolver::with_lookup(|_, port| {
+            Ok(vec![
+                SocketAddr::new("192.0.2.1".parse().unwrap(), port),
+                SocketAddr::new("127.0.0.1".parse().unwrap(), port),
+            ])
+        });
+        let agent = public_agent(resolver, Duration::from_secs(2));
+
+        let err = get_limited(&agent, &format!("http://mixed.example:{port}/"), 1024)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("private/reserved address 127.0.0.1"), "{err}");
+        assert_eq!(accepted.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,573 @@
+//! Goal taxonomy for CSM-1 line 4 (`G:<goal>:<experience>:<style>`).
+//!
+//! The three fields are free-form on the wire, so `G:lern:begginer:gentel`
+//! parses without complaint. A [`GoalTaxonomy`] lists the terms each field
+//! expects and common synonyms for them; [`GoalContext::validate`] reports
+//! anything else and [`GoalContext::normalize`] rewrites synonyms to their
+//! canonical term.
+//!
+//! | Field | Built-in terms |
+//! |-------|----------------|
+//! | goal ([`GoalKind`]) | protect, learn, create, advise, support, inform, explore, decide |
+//! | experience ([`ExperienceLevel`]) | beginner, intermediate, advanced, expert, guided, professional |
+//! | style ([`InteractionStyle`]) | gentle, formal, casual, visual, concise, detailed, socratic, playful |
+//!
+//! Values starting with the taxonomy's custom prefix (`x_` by default)
+//! are deliberate extensions and always pass. Unknown values are warnings
+//! under [`ValidationLevel::Lenient`] and errors under
+//! [`ValidationLevel::Strict`].
+//!
+//! A taxonomy can be loaded from JSON. Fields given in the file replace
+//! the built-in vocabulary for that field; omitted ones keep it:
+//!
+//! ```json
+//! {
+//!   "level": "strict",
+//!   "style": { "values": ["gentle", "formal"], "synonyms": { "kind": "gentle" } }
+//! }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::csm1::GoalContext;
+//! use vcp_core::goal::{ExperienceLevel, GoalTaxonomy};
+//!
+//! let goal = GoalContext {
+//!     goal: "Study".into(),
+//!     experience: "novice".into(),
+//!     style: "x_haiku".into(),
+//! };
+//! assert_eq!(goal.validate(&GoalTaxonomy::default()).len(), 2);
+//!
+//! let goal = goal.normalize();
+//! assert_eq!((goal.goal.as_str(), goal.experience.as_str()), ("learn", "beginner"));
+//! assert_eq!(goal.experience_level(), ExperienceLevel::Beginner);
+//! assert!(goal.validate(&GoalTaxonomy::default().strict()).is_empty());
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::context_schema::{IssueCode, Severity, ValidationIssue};
+use crate::csm1::GoalContext;
+use crate::error::VcpResult;
+
+// ── Typed terms ─────────────────────────────────────────────
+
+/// What the user is trying to do (line 4, first field).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GoalKind {
+    Protect,
+    Learn,
+    Create,
+    Advise,
+    Support,
+    Inform,
+    Explore,
+    Decide,
+    /// Any other value, kept verbatim.
+    Custom(String),
+}
+
+impl GoalKind {
+    /// The built-in terms, in table order.
+    pub const KNOWN: &'static [&'static str] = &[
+        "protect", "learn", "create", "advise", "support", "inform", "explore", "decide",
+    ];
+
+    /// The wire term.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Protect => "protect",
+            Self::Learn => "learn",
+            Self::Create => "create",
+            Self::Advise => "advise",
+            Self::Support => "support",
+            Self::Inform => "inform",
+            Self::Explore => "explore",
+            Self::Decide => "decide",
+            Self::Custom(s) => s,
+        }
+    }
+}
+
+impl From<&str> for GoalKind {
+    fn from(s: &str) -> Self {
+        match s {
+            "protect" => Self::Protect,
+            "learn" => Self::Learn,
+            "create" => Self::Create,
+            "advise" => Self::Advise,
+            "support" => Self::Support,
+            "inform" => Self::Inform,
+            "explore" => Self::Explore,
+            "decide" => Self::Decide,
+            other => Self::Custom(other.to_string()),
+        }
+    }
+}
+
+/// How familiar the user is with the subject (line 4, second field).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExperienceLevel {
+    Beginner,
+    Intermediate,
+    Advanced,
+    Expert,
+    Guided,
+    Professional,
+    /// Any other value, kept verbatim.
+    Custom(String),
+}
+
+impl ExperienceLevel {
+    /// The built-in terms, in table order.
+    pub const KNOWN: &'static [&'static str] = &[
+        "beginner",
+        "intermediate",
+        "advanced",
+        "expert",
+        "guided",
+        "professional",
+    ];
+
+    /// The wire term.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Beginner => "beginner",
+            Self::Intermediate => "intermediate",
+            Self::Advanced => "advanced",
+            Self::Expert => "expert",
+            Self::Guided => "guided",
+            Self::Professional => "professional",
+            Self::Custom(s) => s,
+        }
+    }
+}
+
+impl From<&str> for ExperienceLevel {
+    fn from(s: &str) -> Self {
+        match s {
+            "beginner" => Self::Beginner,
+            "intermediate" => Self::Intermediate,
+            "advanced" => Self::Advanced,
+            "expert" => Self::Expert,
+            "guided" => Self::Guided,
+            "professional" => Self::Professional,
+            other => Self::Custom(other.to_string()),
+        }
+    }
+}
+
+/// How the user wants to be addressed (line 4, third field).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InteractionStyle {
+    Gentle,
+    Formal,
+    Casual,
+    Visual,
+    Concise,
+    Detailed,
+    Socratic,
+    Playful,
+    /// Any other value, kept verbatim.
+    Custom(String),
+}
+
+impl InteractionStyle {
+    /// The built-in terms, in table order.
+    pub const KNOWN: &'static [&'static str] = &[
+        "gentle", "formal", "casual", "visual", "concise", "detailed", "socratic", "playful",
+    ];
+
+    /// The wire term.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Gentle => "gentle",
+            Self::Formal => "formal",
+            Self::Casual => "casual",
+            Self::Visual => "visual",
+            Self::Concise => "concise",
+            Self::Detailed => "detailed",
+            Self::Socratic => "socratic",
+            Self::Playful => "playful",
+            Self::Custom(s) => s,
+        }
+    }
+}
+
+impl From<&str> for InteractionStyle {
+    fn from(s: &str) -> Self {
+        match s {
+            "gentle" => Self::Gentle,
+            "formal" => Self::Formal,
+            "casual" => Self::Casual,
+            "visual" => Self::Visual,
+            "concise" => Self::Concise,
+            "detailed" => Self::Detailed,
+            "socratic" => Self::Socratic,
+            "playful" => Self::Playful,
+            other => Self::Custom(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for GoalKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for ExperienceLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for InteractionStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ── Taxonomy ────────────────────────────────────────────────
+
+/// One of the three line-4 fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalField {
+    Goal,
+    Experience,
+    Style,
+}
+
+impl GoalField {
+    /// All three, in wire order.
+    pub fn all() -> &'static [Self] {
+        &[Self::Goal, Self::Experience, Self::Style]
+    }
+}
+
+impl fmt::Display for GoalField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Goal => "goal",
+            Self::Experience => "experience",
+            Self::Style => "style",
+        })
+    }
+}
+
+/// How [`GoalContext::validate`] treats values outside the taxonomy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationLevel {
+    /// Report them as warnings.
+    #[default]
+    Lenient,
+    /// Report them as errors.
+    Strict,
+}
+
+/// Accepted terms and synonyms for one line-4 field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FieldTaxonomy {
+    /// Canonical terms.
+    pub values: BTreeSet<String>,
+    /// Synonym to canonical term.
+    pub synonyms: BTreeMap<String, String>,
+}
+
+impl FieldTaxonomy {
+    fn builtin(values: &[&str], synonyms: &[(&str, &str)]) -> Self {
+        Self {
+            values: values.iter().map(|v| (*v).to_string()).collect(),
+            synonyms: synonyms
+                .iter()
+                .map(|(from, to)| ((*from).to_string(), (*to).to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// Expected line-4 terms, with synonyms and a validation level.
+///
+/// [`GoalTaxonomy::default`] is the built-in vocabulary from the
+/// [module docs](self), validated leniently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GoalTaxonomy {
+    /// Severity of values outside the taxonomy.
+    pub level: ValidationLevel,
+    /// Values starting with this are custom extensions and always pass.
+    pub custom_prefix: String,
+    /// Terms for the goal field.
+    pub goal: FieldTaxonomy,
+    /// Terms for the experience field.
+    pub experience: FieldTaxonomy,
+    /// Terms for the style field.
+    pub style: FieldTaxonomy,
+}
+
+impl Default for GoalTaxonomy {
+    fn default() -> Self {
+        Self {
+            level: ValidationLevel::Lenient,
+            custom_prefix: "x_".to_string(),
+            goal: FieldTaxonomy::builtin(
+                GoalKind::KNOWN,
+                &[
+                    ("safeguard", "protect"),
+                    ("protection", "protect"),
+                    ("study", "learn"),
+                    ("learning", "learn"),
+                    ("build", "create"),
+                    ("make", "create"),
+                    ("advice", "advise"),
+                    ("counsel", "advise"),
+                    ("help", "support"),
+                    ("assist", "support"),
+                    ("explain", "inform"),
+                    ("discover", "explore"),
+                    ("choose", "decide"),
+                ],
+            ),
+            experience: FieldTaxonomy::builtin(
+                ExperienceLevel::KNOWN,
+                &[
+                    ("novice", "beginner"),
+                    ("newbie", "beginner"),
+                    ("new", "beginner"),
+                    ("mid", "intermediate"),
+                    ("experienced", "advanced"),
+                    ("specialist", "expert"),
+                    ("pro", "professional"),
+                    ("assisted", "guided"),
+                    ("supervised", "guided"),
+                ],
+            ),
+            style: FieldTaxonomy::builtin(
+                InteractionStyle::KNOWN,
+                &[
+                    ("soft", "gentle"),
+                    ("kind", "gentle"),
+                    ("polite", "formal"),
+                    ("informal", "casual"),
+                    ("relaxed", "casual"),
+                    ("brief", "concise"),
+                    ("terse", "concise"),
+                    ("thorough", "detailed"),
+                    ("verbose", "detailed"),
+                    ("graphical", "visual"),
+                    ("fun", "playful"),
+                    ("questioning", "socratic"),
+                ],
+            ),
+        }
+    }
+}
+
+impl GoalTaxonomy {
+    /// Report unknown values as errors instead of warnings.
+    #[must_use]
+    pub fn strict(mut self) -> Self {
+        self.level = ValidationLevel::Strict;
+        self
+    }
+
+    /// Accept an extra canonical term for `field`.
+    #[must_use]
+    pub fn with_value(mut self, field: GoalField, value: impl Into<String>) -> Self {
+        self.field_mut(field).values.insert(value.into());
+        self
+    }
+
+    /// Map `synonym` to `canonical` for `field`.
+    #[must_use]
+    pub fn with_synonym(
+        mut self,
+        field: GoalField,
+        synonym: impl Into<String>,
+        canonical: impl Into<String>,
+    ) -> Self {
+        self.field_mut(field)
+            .synonyms
+            .insert(synonym.into(), canonical.into());
+        self
+    }
+
+    /// The terms for `field`.
+    pub fn field(&self, field: GoalField) -> &FieldTaxonomy {
+        match field {
+            GoalField::Goal => &self.goal,
+            GoalField::Experience => &self.experience,
+            GoalField::Style => &self.style,
+        }
+    }
+
+    fn field_mut(&mut self, field: GoalField) -> &mut FieldTaxonomy {
+        match field {
+            GoalField::Goal => &mut self.goal,
+            GoalField::Experience => &mut self.experience,
+            GoalField::Style => &mut self.style,
+        }
+    }
+
+    /// Canonical form of `value` for `field`: trimmed, lowercased, with
+    /// spaces and `-` as `_`, and a synonym replaced by its term. Custom
+    /// values are only trimmed.
+    pub fn normalize_term(&self, field: GoalField, value: &str) -> String {
+        let value = value.trim();
+        if self.is_custom(value) {
+            return value.to_string();
+        }
+        let cleaned = value.to_lowercase().replace([' ', '-'], "_");
+        match self.field(field).synonyms.get(&cleaned) {
+            Some(canonical) => canonical.clone(),
+            None => cleaned,
+        }
+    }
+
+    /// Check `goal` against this taxonomy. See [`GoalContext::validate`].
+    pub fn check(&self, goal: &GoalContext) -> Vec<ValidationIssue> {
+        let severity = match self.level {
+            ValidationLevel::Lenient => Severity::Warning,
+            ValidationLevel::Strict => Severity::Error,
+        };
+        let mut issues = Vec::new();
+        for &field in GoalField::all() {
+            let value = goal_field(goal, field);
+            if value.is_empty() || self.is_custom(value) {
+                continue;
+            }
+            let terms = self.field(field);
+            if terms.values.contains(value) {
+                continue;
+            }
+            let normalized = self.normalize_term(field, value);
+            let message = if terms.values.contains(&normalized) {
+                format!("'{value}' is not canonical; normalize to '{normalized}'")
+            } else {
+                format!(
+                    "unknown {field} '{value}' (prefix custom values with '{}')",
+                    self.custom_prefix
+                )
+            };
+            issues.push(ValidationIssue {
+                severity,
+                code: IssueCode::UnknownCategory,
+                path: format!("goal.{field}"),
+                message,
+            });
+        }
+        issues
+    }
+
+    /// Parse a taxonomy file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`](crate::VcpError::JsonError) if the
+    /// JSON is invalid.
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`](crate::VcpError::JsonError) if
+    /// serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    fn is_custom(&self, value: &str) -> bool {
+        !self.custom_prefix.is_empty() && value.starts_with(&self.custom_prefix)
+    }
+}
+
+fn goal_field(goal: &GoalContext, field: GoalField) -> &str {
+    match field {
+        GoalField::Goal => &goal.goal,
+        GoalField::Experience => &goal.experience,
+        GoalField::Style => &goal.style,
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(goal: &str, experience: &str, style: &str) -> GoalContext {
+        GoalContext {
+            goal: goal.into(),
+            experience: experience.into(),
+            style: style.into(),
+        }
+    }
+
+    #[test]
+    fn levels_set_severity_and_custom_prefix_escapes() {
+        let typo = goal("lern", "beginner", "x_haiku");
+        let lenient = typo.validate(&GoalTaxonomy::default());
+        assert_eq!(lenient.len(), 1);
+        assert_eq!(lenient[0].severity, Severity::Warning);
+        assert_eq!(lenient[0].path, "goal.goal");
+
+        let strict = typo.validate(&GoalTaxonomy::default().strict());
+        assert!(strict[0].is_error());
+
+        // Partial goals leave fields empty; that is not an issue.
+        assert!(goal("protect", "", "")
+            .validate(&GoalTaxonomy::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn normalize_maps_synonyms_and_spelling_variants() {
+        let normalized = goal(" Safeguard ", "Newbie", "Kind").normalize();
+        assert_eq!(normalized, goal("protect", "beginner", "gentle"));
+        assert_eq!(normalized.goal_kind(), GoalKind::Protect);
+        assert_eq!(normalized.interaction_style(), InteractionStyle::Gentle);
+
+        let custom = goal("learn-guitar", "x_Grade 3", "visual").normalize();
+        assert_eq!(custom, goal("learn_guitar", "x_Grade 3", "visual"));
+        assert_eq!(custom.goal_kind(), GoalKind::Custom("learn_guitar".into()));
+    }
+
+    #[test]
+    fn taxonomy_json_replaces_named_fields_only() {
+        let taxonomy = GoalTaxonomy::from_json(
+            r#"{"level": "strict", "style": {"values": ["sung"], "synonyms": {"musical": "sung"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(taxonomy.level, ValidationLevel::Strict);
+        assert_eq!(taxonomy.goal, GoalTaxonomy::default().goal);
+
+        let g = goal("learn", "beginner", "musical");
+        assert_eq!(g.normalize_with(&taxonomy).style, "sung");
+        let issues = goal("learn", "beginner", "gentle").validate(&taxonomy);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "goal.style");
+
+        let extended = GoalTaxonomy::default()
+            .with_value(GoalField::Goal, "heal")
+            .with_synonym(GoalField::Goal, "recover", "heal");
+        assert_eq!(
+            GoalTaxonomy::from_json(&extended.to_json().unwrap()).unwrap(),
+            extended
+        );
+        assert!(goal("recover", "", "")
+            .normalize_with(&extended)
+            .validate(&extended)
+            .is_empty());
+    }
+}
@@ -0,0 +1,497 @@
+//! HTTP header encoding profile for VCP artifacts.
+//!
+//! Gateways propagate VCP over existing HTTP hops in three headers:
+//!
+//! | Header | Artifact | Text form |
+//! |--------|----------|-----------|
+//! | `VCP-Code` | [`Csm1Code`] | `"N5+F+E"` |
+//! | `VCP-Token` | [`VcpToken`] | `"family.safe.guide@1.2.0"` |
+//! | `VCP-Context` | [`FullContext`] | `"ctx1;time=morning"` (the ASCII wire) |
+//!
+//! Each value is an RFC 8941 structured-field item. With
+//! [`HeaderEncoding::String`] (the default) it is an sf-string. With
+//! [`HeaderEncoding::Binary`] it is an sf-binary holding the base64 of the
+//! UTF-8 text, and the context uses the emoji wire, which is more compact
+//! once base64-encoded. Decoding accepts either form regardless of the
+//! codec's encoding, so peers can choose independently.
+//!
+//! Decoding is lenient in the ways real proxies require: obsolete line
+//! folding (CRLF followed by whitespace) is unfolded, surrounding
+//! whitespace is trimmed, whitespace inside base64 is skipped, padding is
+//! optional, and item parameters (`;key=value`) are ignored. Values
+//! longer than the codec's limit (by default [`DEFAULT_MAX_HEADER_LEN`]
+//! bytes) are rejected in both directions.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::context::FullContext;
+//! use vcp_core::csm1::Csm1Code;
+//! use vcp_core::headers::{HeaderCodec, HeaderEncoding, VcpHeaders};
+//!
+//! let codec = HeaderCodec::new();
+//! let code = Csm1Code::parse("N5+F+E").unwrap();
+//! assert_eq!(codec.encode_code(&code).unwrap(), r#""N5+F+E""#);
+//!
+//! let ctx = FullContext::from_wire("⏰🌅|📍🏡").unwrap();
+//! let binary = HeaderCodec::new().with_encoding(HeaderEncoding::Binary);
+//! let value = binary.encode_context(&ctx).unwrap();
+//! assert!(value.starts_with(':'));
+//! assert_eq!(codec.decode_context(&value).unwrap(), ctx);
+//!
+//! let headers = VcpHeaders::from_pairs(&codec, [("vcp-code", "\"N5+F+E\"")]).unwrap();
+//! assert_eq!(headers.code, Some(code));
+//! ```
+
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD as BASE64};
+use base64::engine::DecodePaddingMode;
+use base64::Engine as _;
+
+use crate::context::FullContext;
+use crate::csm1::Csm1Code;
+use crate::error::{VcpError, VcpResult};
+use crate::identity::VcpToken;
+
+/// Header carrying a CSM-1 compact code.
+pub const CODE_HEADER: &str = "VCP-Code";
+
+/// Header carrying a VCP/I identity token.
+pub const TOKEN_HEADER: &str = "VCP-Token";
+
+/// Header carrying a context wire string.
+pub const CONTEXT_HEADER: &str = "VCP-Context";
+
+/// Longest header value, in bytes, a [`HeaderCodec`] accepts by default.
+pub const DEFAULT_MAX_HEADER_LEN: usize = 4096;
+
+/// Base64 decoder that accepts values with or without padding.
+const BASE64_LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+// ── Codec ───────────────────────────────────────────────────
+
+/// How a [`HeaderCodec`] writes values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HeaderEncoding {
+    /// An sf-string: `"N5+F+E"`. The context uses the ASCII wire.
+    #[default]
+    String,
+    /// An sf-binary: `:TjUrRitF:`. The context uses the emoji wire.
+    Binary,
+}
+
+/// Encodes VCP artifacts to header values and decodes them back.
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderCodec {
+    encoding: HeaderEncoding,
+    max_len: usize,
+}
+
+impl Default for HeaderCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeaderCodec {
+    /// A codec writing sf-strings, limited to [`DEFAULT_MAX_HEADER_LEN`].
+    pub fn new() -> Self {
+        Self {
+            encoding: HeaderEncoding::String,
+            max_len: DEFAULT_MAX_HEADER_LEN,
+        }
+    }
+
+    /// Write values with `encoding`. Decoding accepts both encodings.
+    #[must_use]
+    pub fn with_encoding(mut self, encoding: HeaderEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Reject values longer than `max_len` bytes.
+    #[must_use]
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Encode a `VCP-Code` value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the value exceeds the size limit.
+    pub fn encode_code(&self, code: &Csm1Code) -> VcpResult<String> {
+        self.encode(CODE_HEADER, &code.encode())
+    }
+
+    /// Decode a `VCP-Code` value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the value is too long or not an
+    /// sf-string or sf-binary, or the error from [`Csm1Code::parse`].
+    pub fn decode_code(&self, value: &str) -> VcpResult<Csm1Code> {
+        Csm1Code::parse(&self.decode(CODE_HEADER, value)?)
+    }
+
+    /// Encode a `VCP-Token` value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the value exceeds the size
+    /// limit, or cannot be an sf-string (a non-ASCII namespace).
+    pub fn encode_token(&self, token: &VcpToken) -> VcpResult<String> {
+        self.encode(TOKEN_HEADER, &token.full())
+    }
+
+    /// Decode a `VCP-Token` value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the value is too long or not an
+    /// sf-string or sf-binary, or the error from [`VcpToken::parse`].
+    pub fn decode_token(&self, value: &str) -> VcpResult<VcpToken> {
+        VcpToken::parse(&self.decode(TOKEN_HEADER, value)?)
+    }
+
+    /// Encode a `VCP-Context` value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the value exceeds the size limit.
+    pub fn encode_context(&self, context: &FullContext) -> VcpResult<String> {
+        let wire = match self.encoding {
+            HeaderEncoding::String => context.to_ascii_wire(),
+            HeaderEncoding::Binary => context.to_wire(),
+        };
+        self.encode(CONTEXT_HEADER, &wire)
+    }
+
+    /// Decode a `VCP-Context` value holding either wire format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the value is too long or not an
+    /// sf-string or sf-binary, or the error from [`FullContext::from_wire`].
+    pub fn decode_context(&self, value: &str) -> VcpResult<FullContext> {
+        FullContext::from_wire(&self.decode(CONTEXT_HEADER, value)?)
+    }
+
+    fn encode(&self, header: &str, text: &str) -> VcpResult<String> {
+        let value = match self.encoding {
+            HeaderEncoding::String => sf_string(header, text)?,
+            HeaderEncoding::Binary => format!(":{}:", BASE64.encode(text)),
+        };
+        self.check_len(header, &value)?;
+        Ok(value)
+    }
+
+    fn decode(&self, header: &str, value: &str) -> VcpResult<String> {
+        self.check_len(header, value)?;
+        parse_item(header, &unfold(value))
+    }
+
+    fn check_len(&self, header: &str, value: &str) -> VcpResult<()> {
+        if value.len() > self.max_len {
+            return Err(VcpError::ParseError(format!(
+                "{header} is {} bytes, over the {}-byte limit",
+                value.len(),
+                self.max_len
+            )));
+        }
+        Ok(())
+    }
+}
+
+// ── Header sets ─────────────────────────────────────────────
+
+/// The VCP headers of one request or response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VcpHeaders {
+    /// `VCP-Code`, if present.
+    pub code: Option<Csm1Code>,
+    /// `VCP-Token`, if present.
+    pub token: Option<VcpToken>,
+    /// `VCP-Context`, if present.
+    pub context: Option<FullContext>,
+}
+
+impl VcpHeaders {
+    /// Encode the headers that are set, as `(name, value)` pairs in
+    /// code, token, context order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first encoding error.
+    pub fn to_pairs(&self, codec: &HeaderCodec) -> VcpResult<Vec<(&'static str, String)>> {
+        let mut pairs = Vec::new();
+        if let Some(code) = &self.code {
+            pairs.push((CODE_HEADER, codec.encode_code(code)?));
+        }
+        if let Some(token) = &self.token {
+            pairs.push((TOKEN_HEADER, codec.encode_token(token)?));
+        }
+        if let Some(context) = &self.context {
+            pairs.push((CONTEXT_HEADER, codec.encode_context(context)?));
+        }
+        Ok(pairs)
+    }
+
+    /// Decode the VCP headers among `pairs`, matching names without
+    /// regard to case and ignoring other headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if a VCP header appears more than
+    /// once, or the first decoding error.
+    pub fn from_pairs<'a, I>(codec: &HeaderCodec, pairs: I) -> VcpResult<Self>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        fn set<T>(slot: &mut Option<T>, header: &str, value: T) -> VcpResult<()> {
+            if slot.replace(value).is_some() {
+                return Err(VcpError::ParseError(format!(
+                    "{header} appears more than once"
+                )));
+            }
+            Ok(())
+        }
+
+        let mut headers = Self::default();
+        for (name, value) in pairs {
+            if name.eq_ignore_ascii_case(CODE_HEADER) {
+                set(&mut headers.code, CODE_HEADER, codec.decode_code(value)?)?;
+            } else if name.eq_ignore_ascii_case(TOKEN_HEADER) {
+                set(&mut headers.token, TOKEN_HEADER, codec.decode_token(value)?)?;
+            } else if name.eq_ignore_ascii_case(CONTEXT_HEADER) {
+                set(
+                    &mut headers.context,
+                    CONTEXT_HEADER,
+                    codec.decode_context(value)?,
+                )?;
+            }
+        }
+        Ok(headers)
+    }
+}
+
+// ── Structured fields ───────────────────────────────────────
+
+/// Serialize `text` as an RFC 8941 sf-string.
+fn sf_string(header: &str, text: &str) -> VcpResult<String> {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            _ => {
+                return Err(VcpError::ParseError(format!(
+                    "{header} cannot carry {c:?} in an sf-string; use binary encoding"
+                )))
+            }
+        }
+    }
+    out.push('"');
+    Ok(out)
+}
+
+/// Replace obsolete line folding with a single space and trim.
+fn unfold(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\r' || c == '\n' {
+            while chars
+                .next_if(|&n| matches!(n, '\r' | '\n' | ' ' | '\t'))
+                .is_some()
+            {}
+            out.push(' ');
+        } else {
+            out.push(c);
+        }
+    }
+    out.trim_matches([' ', '\t']).to_string()
+}
+
+/// Parse an sf-string or sf-binary item, ignoring any parameters.
+fn parse_item(header: &str, value: &str) -> VcpResult<String> {
+    let invalid = |why: &str| VcpError::ParseError(format!("{header} {why}"));
+
+    let (text, rest) = if let Some(body) = value.strip_prefix('"') {
+        let mut text = String::new();
+        let mut chars = body.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, c @ ('"' | '\\'))) => text.push(c),
+                    _ => return Err(invalid("has an invalid escape")),
+                },
+                Some((i, '"')) => break i + 1,
+                Some((_, c @ ' '..='~')) => text.push(c),
+                Some(_) => return Err(invalid("has a non-printable character")),
+                None => return Err(invalid("has an unterminated string")),
+            }
+        };
+        (text, &body[end..])
+    } else if let Some(body) = value.strip_prefix(':') {
+        let end = body
+            .find(':')
+            .ok_or_else(|| invalid("has an unterminated byte sequence"))?;
+        let encoded: String = body[..end]
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect();
+        let bytes = BASE64_LENIENT
+            .decode(encoded)
+            .map_err(|e| invalid(&format!("has invalid base64: {e}")))?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid("is not UTF-8"))?;
+        (text, &body[end + 1..])
+    } else {
+        return Err(invalid("must be an sf-string or sf-binary"));
+    };
+
+    let rest = rest.trim_start_matches([' ', '\t']);
+    if !rest.is_empty() && !rest.starts_with(';') {
+        return Err(invalid("has trailing data after the item"));
+    }
+    Ok(text)
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_encoding_round_trips_every_header() {
+        let codec = HeaderCodec::new();
+        let code = Csm1Code::parse("N5+F+E").unwrap();
+        let token = VcpToken::parse("family.safe.guide@1.2.0").unwrap();
+        let ctx = FullContext::from_wire("⏰🌅|📍🏡").unwrap();
+
+        assert_eq!(
+            codec.encode_token(&token).unwrap(),
+            r#""family.safe.guide@1.2.0""#
+        );
+        let value = codec.encode_context(&ctx).unwrap();
+        assert!(value.starts_with("\"ctx1;"));
+
+        assert_eq!(
+            codec
+                .decode_code(&codec.encode_code(&code).unwrap())
+                .unwrap(),
+            code
+        );
+        assert_eq!(
+            codec
+                .decode_token(&codec.encode_token(&token).unwrap())
+                .unwrap(),
+            token
+        );
+        assert_eq!(codec.decode_context(&value).unwrap(), ctx);
+    }
+
+    #[test]
+    fn binary_encoding_round_trips_and_decodes_either_way() {
+        let binary = HeaderCodec::new().with_encoding(HeaderEncoding::Binary);
+        let code = Csm1Code::parse("N5+F+E").unwrap();
+        let value = binary.encode_code(&code).unwrap();
+        assert_eq!(value, ":TjUrRitF:");
+        assert_eq!(HeaderCodec::new().decode_code(&value).unwrap(), code);
+        assert_eq!(binary.decode_code(r#""N5+F+E""#).unwrap(), code);
+
+        let ctx = FullContext::from_wire("⏰🌅|📍🏡").unwrap();
+        assert_eq!(
+            binary
+                .decode_context(&binary.encode_context(&ctx).unwrap())
+                .unwrap(),
+            ctx
+        );
+    }
+
+    #[test]
+    fn decoding_tolerates_folding_padding_and_parameters() {
+        let codec = HeaderCodec::new();
+        let code = Csm1Code::parse("N5+F+E").unwrap();
+        assert_eq!(codec.decode_code("  \"N5+F+E\"\t").unwrap(), code);
+        assert_eq!(codec.decode_code(":TjUr\r\n RitF:").unwrap(), code);
+        assert_eq!(codec.decode_code(":TjUrRitF:;v=1").unwrap(), code);
+
+        // "N5" is "TjU=" padded.
+        assert_eq!(
+            codec.decode_code(":TjU:").unwrap(),
+            Csm1Code::parse("N5").unwrap()
+        );
+        assert_eq!(
+            codec.decode_code(":TjU=:").unwrap(),
+            Csm1Code::parse("N5").unwrap()
+        );
+
+        assert_eq!(parse_item("X", r#""a\"b\\c""#).unwrap(), r#"a"b\c"#);
+    }
+
+    #[test]
+    fn malformed_values_are_rejected() {
+        let codec = HeaderCodec::new();
+        for value in [
+            "N5+F+E",
+            "\"N5+F+E",
+            ":TjUrRitF",
+            ":!!!:",
+            "\"N5\" extra",
+            "\"N5\\x\"",
+        ] {
+            assert!(
+                matches!(codec.decode_code(value), Err(VcpError::ParseError(_))),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn size_limits_apply_both_ways() {
+        let codec = HeaderCodec::new().with_max_len(8);
+        let code = Csm1Code::parse("N5+F+E").unwrap();
+        assert_eq!(codec.encode_code(&code).unwrap(), r#""N5+F+E""#);
+
+        let binary = codec.with_encoding(HeaderEncoding::Binary);
+        let err = binary.encode_code(&code).unwrap_err();
+        assert!(err.to_string().contains("VCP-Code is 10 bytes"));
+        assert!(codec.decode_code(":TjUrRitF:").is_err());
+    }
+
+    #[test]
+    fn header_sets_match_names_case_insensitively() {
+        let codec = HeaderCodec::new();
+        let headers = VcpHeaders {
+            code: Some(Csm1Code::parse("A3+W").unwrap()),
+            token: Some(VcpToken::parse("work.strict.review").unwrap()),
+            context: None,
+        };
+        let pairs = headers.to_pairs(&codec).unwrap();
+        assert_eq!(pairs[0].0, CODE_HEADER);
+        assert_eq!(pairs.len(), 2);
+
+        let lower: Vec<(String, String)> = pairs
+            .iter()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.clone()))
+            .chain([("accept".to_string(), "*/*".to_string())])
+            .collect();
+        let decoded =
+            VcpHeaders::from_pairs(&codec, lower.iter().map(|(n, v)| (n.as_str(), v.as_str())))
+                .unwrap();
+        assert_eq!(decoded, headers);
+
+        let duplicate = [("VCP-Code", "\"N5\""), ("vcp-code", "\"A3\"")];
+        assert!(VcpHeaders::from_pairs(&codec, duplicate).is_err());
+    }
+}
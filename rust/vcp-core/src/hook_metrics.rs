@@ -0,0 +1,463 @@
+//! Per-hook metrics for [`HookExecutor`](crate::hooks::HookExecutor).
+//!
+//! Metrics are opt-in: create a [`HookMetrics`] recorder, attach it with
+//! [`HookExecutor::with_metrics`](crate::hooks::HookExecutor::with_metrics),
+//! and read a [`ChainMetrics`] snapshot whenever needed. The recorder is
+//! `Sync`, so one instance can be shared by executors on many threads.
+//!
+//! [`ChainMetrics::to_prometheus`] renders the snapshot in the Prometheus
+//! text exposition format:
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `vcp_hook_invocations_total` | counter | `hook_type`, `hook` |
+//! | `vcp_hook_aborts_total` | counter | `hook_type`, `hook` |
+//! | `vcp_hook_modifies_total` | counter | `hook_type`, `hook` |
+//! | `vcp_hook_panics_total` | counter | `hook_type`, `hook` |
+//! | `vcp_hook_duration_seconds` | summary (`_sum`, `_count`) | `hook_type`, `hook` |
+//! | `vcp_hook_duration_seconds_max` | gauge | `hook_type`, `hook` |
+//! | `vcp_chain_executions_total` | counter | `hook_type` |
+//! | `vcp_chain_aborts_total` | counter | `hook_type` |
+//! | `vcp_chain_duration_seconds` | summary (`_sum`, `_count`) | `hook_type` |
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use std::time::Duration;
+//! use vcp_core::hook_metrics::HookMetrics;
+//! use vcp_core::hooks::*;
+//!
+//! struct Noop;
+//! impl HookHandler for Noop {
+//!     fn execute(&self, _input: &HookInput) -> HookResult {
+//!         HookResult { action: HookAction::Continue, annotations: HashMap::new(), state_updates: ChainState::new(), duration: Duration::ZERO }
+//!     }
+//! }
+//!
+//! let mut registry = HookRegistry::new();
+//! registry.register(
+//!     Hook {
+//!         name: "noop".into(),
+//!         hook_type: HookType::PreInject,
+//!         priority: 50,
+//!         handler: Box::new(Noop),
+//!         timeout: Duration::from_secs(1),
+//!         enabled: true,
+//!         description: String::new(),
+//!     },
+//!     HookScope::Deployment,
+//!     None,
+//! ).unwrap();
+//!
+//! let metrics = HookMetrics::new();
+//! let executor = HookExecutor::new(&registry).with_metrics(&metrics);
+//! let input = HookInput {
+//!     context: serde_json::json!({}),
+//!     constitution: serde_json::json!({}),
+//!     event: serde_json::json!({}),
+//!     session_id: "s".into(),
+//!     chain_state: ChainState::new(),
+//! };
+//! executor.execute(HookType::PreInject, "s", input);
+//!
+//! let snapshot = metrics.snapshot();
+//! assert_eq!(snapshot.hook(HookType::PreInject, "noop").unwrap().invocations, 1);
+//! assert!(snapshot.to_prometheus().contains("vcp_chain_executions_total{hook_type=\"pre_inject\"} 1"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use crate::hooks::{HookAction, HookType};
+
+// ── Stats ───────────────────────────────────────────────────
+
+/// Counters and timings for one hook.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookStats {
+    /// Times the handler ran.
+    pub invocations: u64,
+    /// Invocations that returned `Abort`.
+    pub aborts: u64,
+    /// Invocations that returned `Modify`.
+    pub modifies: u64,
+    /// Invocations where the handler panicked (counted as `Continue`).
+    pub panics: u64,
+    /// Sum of handler durations.
+    pub total_duration: Duration,
+    /// Slowest single invocation.
+    pub max_duration: Duration,
+}
+
+impl HookStats {
+    /// Fraction of invocations that aborted, 0.0 when never invoked.
+    pub fn abort_rate(&self) -> f64 {
+        ratio(self.aborts, self.invocations)
+    }
+
+    /// Fraction of invocations that modified the context.
+    pub fn modify_rate(&self) -> f64 {
+        ratio(self.modifies, self.invocations)
+    }
+
+    /// Average handler duration.
+    pub fn mean_duration(&self) -> Duration {
+        mean(self.total_duration, self.invocations)
+    }
+}
+
+/// Counters and timings for whole chain runs of one hook type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainStats {
+    /// Times the chain ran.
+    pub executions: u64,
+    /// Runs that a hook aborted.
+    pub aborts: u64,
+    /// Sum of chain durations.
+    pub total_duration: Duration,
+}
+
+impl ChainStats {
+    /// Fraction of runs that aborted.
+    pub fn abort_rate(&self) -> f64 {
+        ratio(self.aborts, self.executions)
+    }
+
+    /// Average chain duration.
+    pub fn mean_duration(&self) -> Duration {
+        mean(self.total_duration, self.executions)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ratio(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+fn mean(total: Duration, count: u64) -> Duration {
+    if count == 0 {
+        return Duration::ZERO;
+    }
+    let nanos = total.as_nanos() / u128::from(count);
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
+
+// ── Snapshot ────────────────────────────────────────────────
+
+/// Point-in-time copy of everything a [`HookMetrics`] has recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainMetrics {
+    /// Per-hook stats keyed by hook type and hook name.
+    pub hooks: BTreeMap<(HookType, String), HookStats>,
+    /// Per-chain stats keyed by hook type.
+    pub chains: BTreeMap<HookType, ChainStats>,
+}
+
+impl ChainMetrics {
+    /// Stats for one hook, if it has run.
+    pub fn hook(&self, hook_type: HookType, name: &str) -> Option<&HookStats> {
+        self.hooks.get(&(hook_type, name.to_string()))
+    }
+
+    /// Stats for one chain type, if it has run.
+    pub fn chain(&self, hook_type: HookType) -> Option<&ChainStats> {
+        self.chains.get(&hook_type)
+    }
+
+    /// Render in the Prometheus text exposition format (version 0.0.4).
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        self.write_hook_metrics(&mut out);
+        self.write_chain_metrics(&mut out);
+        out
+    }
+
+    fn write_hook_metrics(&self, out: &mut String) {
+        type Counter = fn(&HookStats) -> u64;
+        let counters: [(&str, &str, Counter); 4] = [
+            (
+                "vcp_hook_invocations_total",
+                "Hook handler invocations.",
+                |s| s.invocations,
+            ),
+            (
+                "vcp_hook_aborts_total",
+                "Hook invocations that aborted the chain.",
+                |s| s.aborts,
+            ),
+            (
+                "vcp_hook_modifies_total",
+                "Hook invocations that modified the context.",
+                |s| s.modifies,
+            ),
+            (
+                "vcp_hook_panics_total",
+                "Hook invocations that panicked.",
+                |s| s.panics,
+            ),
+        ];
+        for (name, help, value) in counters {
+            header(out, name, help, "counter");
+            for ((hook_type, hook), stats) in &self.hooks {
+                sample(out, name, &hook_labels(*hook_type, hook), value(stats));
+            }
+        }
+
+        header(
+            out,
+            "vcp_hook_duration_seconds",
+            "Hook handler duration.",
+            "summary",
+        );
+        for ((hook_type, hook), stats) in &self.hooks {
+            let labels = hook_labels(*hook_type, hook);
+            let sum = stats.total_duration.as_secs_f64();
+            sample(out, "vcp_hook_duration_seconds_sum", &labels, sum);
+            sample(
+                out,
+                "vcp_hook_duration_seconds_count",
+                &labels,
+                stats.invocations,
+            );
+        }
+
+        header(
+            out,
+            "vcp_hook_duration_seconds_max",
+            "Slowest hook invocation.",
+            "gauge",
+        );
+        for ((hook_type, hook), stats) in &self.hooks {
+            let labels = hook_labels(*hook_type, hook);
+            let max = stats.max_duration.as_secs_f64();
+            sample(out, "vcp_hook_duration_seconds_max", &labels, max);
+        }
+    }
+
+    fn write_chain_metrics(&self, out: &mut String) {
+        header(
+            out,
+            "vcp_chain_executions_total",
+            "Hook chain runs.",
+            "counter",
+        );
+        for (hook_type, stats) in &self.chains {
+            sample(
+                out,
+                "vcp_chain_executions_total",
+                &chain_labels(*hook_type),
+                stats.executions,
+            );
+        }
+
+        header(
+            out,
+            "vcp_chain_aborts_total",
+            "Hook chain runs that were aborted.",
+            "counter",
+        );
+        for (hook_type, stats) in &self.chains {
+            sample(
+                out,
+                "vcp_chain_aborts_total",
+                &chain_labels(*hook_type),
+                stats.aborts,
+            );
+        }
+
+        header(
+            out,
+            "vcp_chain_duration_seconds",
+            "Hook chain duration.",
+            "summary",
+        );
+        for (hook_type, stats) in &self.chains {
+            let labels = chain_labels(*hook_type);
+            let sum = stats.total_duration.as_secs_f64();
+            sample(out, "vcp_chain_duration_seconds_sum", &labels, sum);
+            sample(
+                out,
+                "vcp_chain_duration_seconds_count",
+                &labels,
+                stats.executions,
+            );
+        }
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn sample(out: &mut String, name: &str, labels: &str, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "{name}{labels} {value}");
+}
+
+/// Hook names are restricted to `[a-z0-9_-]`, so they need no escaping.
+fn hook_labels(hook_type: HookType, hook: &str) -> String {
+    format!("{{hook_type=\"{hook_type}\",hook=\"{hook}\"}}")
+}
+
+fn chain_labels(hook_type: HookType) -> String {
+    format!("{{hook_type=\"{hook_type}\"}}")
+}
+
+// ── Recorder ────────────────────────────────────────────────
+
+/// Thread-safe recorder that executors write into.
+#[derive(Debug, Default)]
+pub struct HookMetrics {
+    inner: Mutex<ChainMetrics>,
+}
+
+impl HookMetrics {
+    /// Create an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy out everything recorded so far.
+    pub fn snapshot(&self) -> ChainMetrics {
+        self.lock().clone()
+    }
+
+    /// Clear all counters.
+    pub fn reset(&self) {
+        *self.lock() = ChainMetrics::default();
+    }
+
+    pub(crate) fn record_hook(
+        &self,
+        hook_type: HookType,
+        name: &str,
+        action: &HookAction,
+        panicked: bool,
+        duration: Duration,
+    ) {
+        let mut inner = self.lock();
+        let stats = inner
+            .hooks
+            .entry((hook_type, name.to_string()))
+            .or_default();
+        stats.invocations += 1;
+        match action {
+            HookAction::Abort { .. } => stats.aborts += 1,
+            HookAction::Modify(_) => stats.modifies += 1,
+            HookAction::Continue => {}
+        }
+        if panicked {
+            stats.panics += 1;
+        }
+        stats.total_duration += duration;
+        stats.max_duration = stats.max_duration.max(duration);
+    }
+
+    pub(crate) fn record_chain(&self, hook_type: HookType, completed: bool, duration: Duration) {
+        let mut inner = self.lock();
+        let stats = inner.chains.entry(hook_type).or_default();
+        stats.executions += 1;
+        if !completed {
+            stats.aborts += 1;
+        }
+        stats.total_duration += duration;
+    }
+
+    /// A panicking hook cannot leave the counters half-written, so a
+    /// poisoned lock is still safe to read.
+    fn lock(&self) -> MutexGuard<'_, ChainMetrics> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_actions_and_rates() {
+        let metrics = HookMetrics::new();
+        let ms = Duration::from_millis;
+        metrics.record_hook(
+            HookType::PreInject,
+            "guard",
+            &HookAction::Continue,
+            false,
+            ms(2),
+        );
+        metrics.record_hook(
+            HookType::PreInject,
+            "guard",
+            &HookAction::Abort {
+                reason: "no".into(),
+            },
+            false,
+            ms(6),
+        );
+        metrics.record_hook(
+            HookType::PreInject,
+            "guard",
+            &HookAction::Modify(serde_json::json!({})),
+            false,
+            ms(1),
+        );
+        metrics.record_hook(
+            HookType::PreInject,
+            "guard",
+            &HookAction::Continue,
+            true,
+            ms(3),
+        );
+        metrics.record_chain(HookType::PreInject, false, ms(7));
+
+        let snap = metrics.snapshot();
+        let guard = snap.hook(HookType::PreInject, "guard").unwrap();
+        assert_eq!(guard.invocations, 4);
+        assert_eq!(guard.panics, 1);
+        assert!((guard.abort_rate() - 0.25).abs() < f64::EPSILON);
+        assert!((guard.modify_rate() - 0.25).abs() < f64::EPSILON);
+        assert_eq!(guard.mean_duration(), ms(3));
+        assert_eq!(guard.max_duration, ms(6));
+        assert!((snap.chain(HookType::PreInject).unwrap().abort_rate() - 1.0).abs() < f64::EPSILON);
+        assert!(snap.hook(HookType::PostSelect, "guard").is_none());
+
+        metrics.reset();
+        assert_eq!(metrics.snapshot(), ChainMetrics::default());
+    }
+
+    #[test]
+    fn prometheus_output() {
+        let metrics = HookMetrics::new();
+        metrics.record_hook(
+            HookType::OnConflict,
+            "resolver",
+            &HookAction::Continue,
+            false,
+            Duration::from_millis(250),
+        );
+        metrics.record_chain(HookType::OnConflict, true, Duration::from_millis(500));
+
+        let text = metrics.snapshot().to_prometheus();
+        assert!(text.contains("# TYPE vcp_hook_invocations_total counter\n"));
+        assert!(text.contains(
+            "vcp_hook_invocations_total{hook_type=\"on_conflict\",hook=\"resolver\"} 1\n"
+        ));
+        assert!(text.contains(
+            "vcp_hook_duration_seconds_sum{hook_type=\"on_conflict\",hook=\"resolver\"} 0.25\n"
+        ));
+        assert!(text.contains("vcp_chain_aborts_total{hook_type=\"on_conflict\"} 0\n"));
+        assert!(text.contains("vcp_chain_duration_seconds_count{hook_type=\"on_conflict\"} 1\n"));
+        assert!(text
+            .lines()
+            .all(|l| l.starts_with('#') || l.starts_with("vcp_")));
+    }
+}
@@ -9,14 +9,24 @@
 //!
 //! - [`HookRegistry`] stores hooks at deployment or session scope.
 //! - [`HookExecutor`] runs the merged chain for a given hook type and session.
+//! - [`SharedHookRegistry`] is a cloneable, thread-safe handle for servers
+//!   that register and deregister hooks while other threads run chains;
+//!   its [`SharedHookExecutor`] snapshots a [`HookChain`] and runs it
+//!   without holding the lock.
 //! - [`HookHandler`] is the trait that hook implementations must satisfy.
+//! - [`ChainState`] carries typed, namespaced state from one hook to the
+//!   next through [`HookResult::state_updates`].
+//! - [`HookMetrics`] optionally records per-hook counts and timings; see
+//!   [`hook_metrics`](crate::hook_metrics).
+//! - [`ChainRecord`] saves a chain's input and result as JSON;
+//!   [`HookExecutor::replay`] checks whether the current hooks reproduce it.
 //!
 //! # Example
 //!
 //! ```
 //! use vcp_core::hooks::{
-//!     Hook, HookAction, HookExecutor, HookHandler, HookInput, HookRegistry,
-//!     HookResult, HookScope, HookType,
+//!     ChainState, Hook, HookAction, HookExecutor, HookHandler, HookInput,
+//!     HookRegistry, HookResult, HookScope, HookType,
 //! };
 //! use std::collections::HashMap;
 //! use std::time::Duration;
@@ -28,6 +38,7 @@
 //!         HookResult {
 //!             action: HookAction::Continue,
 //!             annotations: HashMap::new(),
+//!             state_updates: ChainState::new(),
 //!             duration: Duration::ZERO,
 //!         }
 //!     }
@@ -53,7 +64,7 @@
 //!     constitution: serde_json::json!({}),
 //!     event: serde_json::json!({}),
 //!     session_id: "sess-1".into(),
-//!     chain_state: HashMap::new(),
+//!     chain_state: ChainState::new(),
 //! };
 //!
 //! let executor = HookExecutor::new(&registry);
@@ -61,11 +72,14 @@
 //! assert!(result.completed);
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::panic::{self, AssertUnwindSafe};
-use std::time::{Duration, Instant};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
 
+use crate::clock::Stopwatch;
 use crate::error::{VcpError, VcpResult};
+use crate::hook_metrics::HookMetrics;
 
 // ── Hook types ──────────────────────────────────────────────
 
@@ -73,7 +87,10 @@ use crate::error::{VcpError, VcpResult};
 ///
 /// Each type corresponds to a distinct interception point in the
 /// adaptation pipeline.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
 pub enum HookType {
     /// Before a constitution is injected into LLM context.
     PreInject,
@@ -89,6 +106,18 @@ pub enum HookType {
     Periodic,
 }
 
+impl HookType {
+    /// Every hook type, in pipeline order.
+    pub const ALL: [HookType; 6] = [
+        HookType::PreInject,
+        HookType::PostSelect,
+        HookType::OnTransition,
+        HookType::OnConflict,
+        HookType::OnViolation,
+        HookType::Periodic,
+    ];
+}
+
 impl std::fmt::Display for HookType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let label = match self {
@@ -106,7 +135,8 @@ impl std::fmt::Display for HookType {
 // ── Hook action / result ────────────────────────────────────
 
 /// Result status from a hook execution, controlling pipeline flow.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum HookAction {
     /// No change. Pass to next hook in the chain.
     Continue,
@@ -120,7 +150,7 @@ pub enum HookAction {
 }
 
 /// Input provided to a hook handler during chain execution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct HookInput {
     /// The current VCP context object (may have been modified by previous hooks).
     pub context: serde_json::Value,
@@ -130,21 +160,151 @@ pub struct HookInput {
     pub event: serde_json::Value,
     /// Session identifier.
     pub session_id: String,
-    /// Mutable key-value store shared across hooks in a single chain execution.
-    pub chain_state: HashMap<String, serde_json::Value>,
+    /// Key-value state shared across hooks in a single chain execution.
+    pub chain_state: ChainState,
 }
 
 /// Result returned from a hook execution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct HookResult {
     /// The action controlling pipeline flow.
     pub action: HookAction,
     /// Metadata attached to the pipeline event for audit.
+    #[serde(default)]
     pub annotations: HashMap<String, serde_json::Value>,
+    /// Entries merged into the chain state before the next hook runs;
+    /// see [`ChainState`].
+    #[serde(default)]
+    pub state_updates: ChainState,
     /// Actual execution time (set by the executor).
+    #[serde(default)]
     pub duration: Duration,
 }
 
+// ── Chain state ─────────────────────────────────────────────
+
+/// Key-value state shared by the hooks of one chain run.
+///
+/// A hook reads it from [`HookInput::chain_state`] and changes it by
+/// returning [`HookResult::state_updates`], which the executor merges in
+/// before the next hook runs; a `null` update removes the key.
+///
+/// Keys under `vcp.` are reserved. The executor sets
+/// [`HOOK_TYPE`](Self::HOOK_TYPE), hooks may set
+/// [`ABORT_HINT`](Self::ABORT_HINT), and updates to any other `vcp.` key
+/// are ignored. Hooks keep their own keys under a namespace of their own,
+/// built with [`key`](Self::key).
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::hooks::ChainState;
+///
+/// let mut state = ChainState::new();
+/// state.set(ChainState::key("acme", "risk_score"), 7);
+/// assert_eq!(state.get_as::<u32>("acme.risk_score"), Some(7));
+/// assert_eq!(state.get_as::<String>("acme.risk_score"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct ChainState(HashMap<String, serde_json::Value>);
+
+impl ChainState {
+    /// The running chain's hook type, as its `snake_case` name. Set by
+    /// the executor.
+    pub const HOOK_TYPE: &'static str = "vcp.hook_type";
+    /// A string reason for a later hook to abort, from a hook that flags a
+    /// problem without aborting itself.
+    pub const ABORT_HINT: &'static str = "vcp.abort_hint";
+
+    /// Create empty state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The key `name` in `namespace`: `namespace.name`.
+    pub fn key(namespace: &str, name: &str) -> String {
+        format!("{namespace}.{name}")
+    }
+
+    /// Whether `key` is in the reserved `vcp.` namespace.
+    pub fn is_reserved(key: &str) -> bool {
+        key.starts_with("vcp.")
+    }
+
+    /// The raw value at `key`.
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
+
+    /// The value at `key` as a `T`; `None` if it is absent or not a `T`.
+    pub fn get_as<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.0.get(key).and_then(|v| T::deserialize(v).ok())
+    }
+
+    /// Set `key` to `value`.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) {
+        self.0.insert(key.into(), value.into());
+    }
+
+    /// Remove `key`, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<serde_json::Value> {
+        self.0.remove(key)
+    }
+
+    /// Whether `key` is set.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// All entries, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &serde_json::Value)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The [`ABORT_HINT`](Self::ABORT_HINT), if a hook has set one.
+    pub fn abort_hint(&self) -> Option<&str> {
+        self.get(Self::ABORT_HINT)
+            .and_then(serde_json::Value::as_str)
+    }
+
+    /// Merge a hook's updates, dropping those to executor-owned keys.
+    fn apply(&mut self, updates: &ChainState) {
+        for (key, value) in &updates.0 {
+            if Self::is_reserved(key) && key != Self::ABORT_HINT {
+                continue;
+            }
+            if value.is_null() {
+                self.0.remove(key);
+            } else {
+                self.0.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl From<HashMap<String, serde_json::Value>> for ChainState {
+    fn from(map: HashMap<String, serde_json::Value>) -> Self {
+        Self(map)
+    }
+}
+
+impl FromIterator<(String, serde_json::Value)> for ChainState {
+    fn from_iter<I: IntoIterator<Item = (String, serde_json::Value)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 // ── Hook handler trait ──────────────────────────────────────
 
 /// Trait that hook implementations must satisfy.
@@ -204,7 +364,7 @@ pub enum HookScope {
 // ── Chain result ────────────────────────────────────────────
 
 /// Result of executing an entire hook chain.
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ChainResult {
     /// Whether the chain completed without an abort.
     pub completed: bool,
@@ -218,6 +378,14 @@ pub struct ChainResult {
     pub modified_constitution: Option<serde_json::Value>,
     /// Ordered list of (`hook_name`, result) pairs for each executed hook.
     pub results: Vec<(String, HookResult)>,
+    /// Every executed hook's annotations, keyed `hook_name.key`. Hook
+    /// names cannot contain `.`, so everything before the first `.` is the
+    /// hook name and keys from different hooks never collide.
+    #[serde(default)]
+    pub annotations: BTreeMap<String, serde_json::Value>,
+    /// The chain state after the last hook that ran.
+    #[serde(default)]
+    pub chain_state: ChainState,
 }
 
 // ── Hook name validation regex ──────────────────────────────
@@ -240,8 +408,8 @@ fn is_valid_hook_name(name: &str) -> bool {
 /// Deployment hooks execute before session hooks at the same priority
 /// level, ensuring organizational policies take precedence.
 pub struct HookRegistry {
-    deployment_hooks: HashMap<HookType, Vec<Hook>>,
-    session_hooks: HashMap<String, HashMap<HookType, Vec<Hook>>>,
+    deployment_hooks: HashMap<HookType, Vec<Arc<Hook>>>,
+    session_hooks: HashMap<String, HashMap<HookType, Vec<Arc<Hook>>>>,
 }
 
 impl std::fmt::Debug for HookRegistry {
@@ -299,7 +467,7 @@ impl HookRegistry {
                     )));
                 }
 
-                hooks.push(hook);
+                hooks.push(Arc::new(hook));
                 // Sort descending by priority (higher runs first).
                 hooks.sort_by_key(|h| std::cmp::Reverse(h.priority));
             }
@@ -319,7 +487,7 @@ impl HookRegistry {
                     )));
                 }
 
-                hooks.push(hook);
+                hooks.push(Arc::new(hook));
                 hooks.sort_by_key(|h| std::cmp::Reverse(h.priority));
             }
         }
@@ -349,12 +517,43 @@ impl HookRegistry {
         }
     }
 
+    /// Remove every hook registered for `session_id`, returning how many
+    /// were removed.
+    pub fn clear_session(&mut self, session_id: &str) -> usize {
+        self.session_hooks
+            .remove(session_id)
+            .map_or(0, |m| m.values().map(Vec::len).sum())
+    }
+
+    /// Number of hooks registered for `session_id`.
+    pub fn session_hook_count(&self, session_id: &str) -> usize {
+        self.session_hooks
+            .get(session_id)
+            .map_or(0, |m| m.values().map(Vec::len).sum())
+    }
+
     /// Get the merged hook chain for a given type and session.
     ///
     /// Deployment hooks come before session hooks at equal priority,
     /// ensuring organizational policies take precedence over session
     /// customizations.
     pub fn get_chain(&self, hook_type: HookType, session_id: &str) -> Vec<&Hook> {
+        self.merged_chain(hook_type, session_id)
+            .into_iter()
+            .map(AsRef::as_ref)
+            .collect()
+    }
+
+    /// Owned handles to the merged chain, for running it after the
+    /// registry is unlocked.
+    fn chain_snapshot(&self, hook_type: HookType, session_id: &str) -> Vec<Arc<Hook>> {
+        self.merged_chain(hook_type, session_id)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    fn merged_chain(&self, hook_type: HookType, session_id: &str) -> Vec<&Arc<Hook>> {
         let deployment = self
             .deployment_hooks
             .get(&hook_type)
@@ -371,7 +570,10 @@ impl HookRegistry {
 
     /// Merge two priority-sorted hook slices, preferring deployment hooks
     /// at equal priority (stable merge).
-    fn merge_by_priority<'a>(deployment: &'a [Hook], session: &'a [Hook]) -> Vec<&'a Hook> {
+    fn merge_by_priority<'a>(
+        deployment: &'a [Arc<Hook>],
+        session: &'a [Arc<Hook>],
+    ) -> Vec<&'a Arc<Hook>> {
         let mut result = Vec::with_capacity(deployment.len() + session.len());
         let (mut d, mut s) = (0, 0);
 
@@ -437,12 +639,23 @@ impl HookRegistry {
 /// passing (possibly modified) context forward through the chain.
 pub struct HookExecutor<'a> {
     registry: &'a HookRegistry,
+    metrics: Option<&'a HookMetrics>,
 }
 
 impl<'a> HookExecutor<'a> {
     /// Create an executor backed by the given registry.
     pub fn new(registry: &'a HookRegistry) -> Self {
-        Self { registry }
+        Self {
+            registry,
+            metrics: None,
+        }
+    }
+
+    /// Record every hook invocation and chain run into `metrics`.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: &'a HookMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
     }
 
     /// Execute the hook chain for the given type and session.
@@ -457,95 +670,594 @@ impl<'a> HookExecutor<'a> {
     /// - Panics in handlers are caught via `catch_unwind` and treated as `Continue`.
     /// - Timeout enforcement is best-effort (the handler runs synchronously; the
     ///   duration is recorded but cannot be pre-empted in a sync context).
-    pub fn execute(
-        &self,
-        hook_type: HookType,
-        session_id: &str,
-        mut input: HookInput,
-    ) -> ChainResult {
+    pub fn execute(&self, hook_type: HookType, session_id: &str, input: HookInput) -> ChainResult {
         let chain = self.registry.get_chain(hook_type, session_id);
-        let mut results: Vec<(String, HookResult)> = Vec::new();
-        let mut modified_context: Option<serde_json::Value> = None;
-        let mut modified_constitution: Option<serde_json::Value> = None;
+        run_chain(&chain, hook_type, input, self.metrics)
+    }
 
-        for hook in &chain {
-            if !hook.enabled {
-                continue;
+    /// Execute the chain as [`execute`](Self::execute) does, keeping the
+    /// input alongside the result for later [`replay`](Self::replay).
+    pub fn record(&self, hook_type: HookType, session_id: &str, input: HookInput) -> ChainRecord {
+        let result = self.execute(hook_type, session_id, input.clone());
+        ChainRecord {
+            hook_type,
+            session_id: session_id.to_string(),
+            input,
+            result,
+        }
+    }
+
+    /// Re-run a recorded chain against the current hooks and compare the
+    /// outcome with the recorded one. Metrics are not recorded.
+    pub fn replay(&self, record: &ChainRecord) -> ReplayReport {
+        let chain = self
+            .registry
+            .get_chain(record.hook_type, &record.session_id);
+        replay_chain(&chain, record)
+    }
+}
+
+/// Run `chain` in order, recording into `metrics` if given.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "vcp.hooks.chain",
+        level = "debug",
+        skip_all,
+        fields(
+            hook_type = %hook_type,
+            hooks = chain.len(),
+            completed = tracing::field::Empty,
+            aborted_by = tracing::field::Empty,
+            duration_us = tracing::field::Empty,
+        ),
+    )
+)]
+fn run_chain(
+    chain: &[&Hook],
+    hook_type: HookType,
+    input: HookInput,
+    metrics: Option<&HookMetrics>,
+) -> ChainResult {
+    let start = Stopwatch::start();
+    let result = run_hooks(chain, hook_type, input, metrics);
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::Span::current();
+        span.record("completed", result.completed);
+        if let Some(name) = &result.aborted_by {
+            span.record("aborted_by", name.as_str());
+        }
+        span.record("duration_us", start.elapsed().as_micros());
+    }
+    if let Some(metrics) = metrics {
+        metrics.record_chain(hook_type, result.completed, start.elapsed());
+    }
+    result
+}
+
+fn run_hooks(
+    chain: &[&Hook],
+    hook_type: HookType,
+    mut input: HookInput,
+    metrics: Option<&HookMetrics>,
+) -> ChainResult {
+    let mut results: Vec<(String, HookResult)> = Vec::new();
+    let mut modified_context: Option<serde_json::Value> = None;
+    let mut modified_constitution: Option<serde_json::Value> = None;
+    let mut annotations = BTreeMap::new();
+    input
+        .chain_state
+        .set(ChainState::HOOK_TYPE, hook_type.to_string());
+
+    for hook in chain {
+        if !hook.enabled {
+            continue;
+        }
+
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("vcp.hook", hook = %hook.name, priority = hook.priority).entered();
+        let start = Stopwatch::start();
+
+        // Execute with panic safety. We use AssertUnwindSafe because
+        // HookInput contains types that are not UnwindSafe by default,
+        // but we accept this for the fail-open semantics required by spec.
+        let panic_result = panic::catch_unwind(AssertUnwindSafe(|| hook.handler.execute(&input)));
+
+        let elapsed = start.elapsed();
+
+        let panicked = panic_result.is_err();
+        let hook_result = match panic_result {
+            Ok(mut result) => {
+                result.duration = elapsed;
+                result
             }
+            Err(_) => {
+                // Spec: exception -> treat as Continue, chain continues.
+                HookResult {
+                    action: HookAction::Continue,
+                    annotations: HashMap::new(),
+                    state_updates: ChainState::new(),
+                    duration: elapsed,
+                }
+            }
+        };
 
-            let start = Instant::now();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            action = match &hook_result.action {
+                HookAction::Continue => "continue",
+                HookAction::Abort { .. } => "abort",
+                HookAction::Modify(_) => "modify",
+            },
+            panicked,
+            duration_us = elapsed.as_micros(),
+            "hook finished"
+        );
 
-            // Execute with panic safety. We use AssertUnwindSafe because
-            // HookInput contains types that are not UnwindSafe by default,
-            // but we accept this for the fail-open semantics required by spec.
-            let panic_result =
-                panic::catch_unwind(AssertUnwindSafe(|| hook.handler.execute(&input)));
+        if let Some(metrics) = metrics {
+            metrics.record_hook(
+                hook_type,
+                &hook.name,
+                &hook_result.action,
+                panicked,
+                elapsed,
+            );
+        }
 
-            let elapsed = start.elapsed();
+        input.chain_state.apply(&hook_result.state_updates);
+        // Registration keeps `.` out of hook names, so these keys are
+        // unambiguous even when an annotation key contains one.
+        for (key, value) in &hook_result.annotations {
+            annotations.insert(format!("{}.{key}", hook.name), value.clone());
+        }
 
-            let hook_result = match panic_result {
-                Ok(mut result) => {
-                    result.duration = elapsed;
-                    result
+        match &hook_result.action {
+            HookAction::Abort { reason } => {
+                let abort_reason = reason.clone();
+                let hook_name = hook.name.clone();
+                results.push((hook.name.clone(), hook_result));
+                return ChainResult {
+                    completed: false,
+                    aborted_by: Some(hook_name),
+                    abort_reason: Some(abort_reason),
+                    modified_context,
+                    modified_constitution,
+                    results,
+                    annotations,
+                    chain_state: input.chain_state,
+                };
+            }
+            HookAction::Modify(value) => {
+                // The Modify action carries a JSON value. By convention, if
+                // it has a "context" key we update the context; if it has a
+                // "constitution" key we update the constitution; otherwise
+                // we treat the whole value as a modified context.
+                if let Some(ctx) = value.get("context") {
+                    input.context = ctx.clone();
+                    modified_context = Some(ctx.clone());
                 }
-                Err(_) => {
-                    // Spec: exception -> treat as Continue, chain continues.
-                    HookResult {
-                        action: HookAction::Continue,
-                        annotations: HashMap::new(),
-                        duration: elapsed,
-                    }
+                if let Some(con) = value.get("constitution") {
+                    input.constitution = con.clone();
+                    modified_constitution = Some(con.clone());
                 }
-            };
-
-            match &hook_result.action {
-                HookAction::Abort { reason } => {
-                    let abort_reason = reason.clone();
-                    let hook_name = hook.name.clone();
-                    results.push((hook.name.clone(), hook_result));
-                    return ChainResult {
-                        completed: false,
-                        aborted_by: Some(hook_name),
-                        abort_reason: Some(abort_reason),
-                        modified_context,
-                        modified_constitution,
-                        results,
-                    };
+                // If neither key exists, treat entire value as modified context.
+                if value.get("context").is_none() && value.get("constitution").is_none() {
+                    input.context = value.clone();
+                    modified_context = Some(value.clone());
                 }
-                HookAction::Modify(value) => {
-                    // The Modify action carries a JSON value. By convention, if
-                    // it has a "context" key we update the context; if it has a
-                    // "constitution" key we update the constitution; otherwise
-                    // we treat the whole value as a modified context.
-                    if let Some(ctx) = value.get("context") {
-                        input.context = ctx.clone();
-                        modified_context = Some(ctx.clone());
-                    }
-                    if let Some(con) = value.get("constitution") {
-                        input.constitution = con.clone();
-                        modified_constitution = Some(con.clone());
-                    }
-                    // If neither key exists, treat entire value as modified context.
-                    if value.get("context").is_none() && value.get("constitution").is_none() {
-                        input.context = value.clone();
-                        modified_context = Some(value.clone());
-                    }
-                }
-                HookAction::Continue => {}
             }
+            HookAction::Continue => {}
+        }
+
+        results.push((hook.name.clone(), hook_result));
+    }
+
+    ChainResult {
+        completed: true,
+        aborted_by: None,
+        abort_reason: None,
+        modified_context,
+        modified_constitution,
+        results,
+        annotations,
+        chain_state: input.chain_state,
+    }
+}
+
+// ── SharedHookRegistry ──────────────────────────────────────
+
+/// A thread-safe, cloneable handle to a [`HookRegistry`].
+///
+/// Clones share one registry. Registration takes a short write lock;
+/// chains are run from a [`HookChain`] snapshot, so a slow handler never
+/// blocks registration and a hook deregistered mid-run finishes the run
+/// it was already part of.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::hooks::{
+///     ChainState, Hook, HookAction, HookHandler, HookInput, HookResult, HookScope,
+///     HookType, SharedHookRegistry,
+/// };
+/// use std::collections::HashMap;
+/// use std::time::Duration;
+///
+/// struct Noop;
+/// impl HookHandler for Noop {
+///     fn execute(&self, _: &HookInput) -> HookResult {
+///         HookResult { action: HookAction::Continue, annotations: HashMap::new(), state_updates: ChainState::new(), duration: Duration::ZERO }
+///     }
+/// }
+///
+/// let registry = SharedHookRegistry::new();
+/// let executor = registry.executor();
+///
+/// let worker = {
+///     let registry = registry.clone();
+///     std::thread::spawn(move || {
+///         let hook = Hook {
+///             name: "noop".into(),
+///             hook_type: HookType::PreInject,
+///             priority: 50,
+///             handler: Box::new(Noop),
+///             timeout: Duration::from_millis(100),
+///             enabled: true,
+///             description: String::new(),
+///         };
+///         registry.register(hook, HookScope::Deployment, None).unwrap();
+///     })
+/// };
+/// worker.join().unwrap();
+///
+/// let input = HookInput {
+///     context: serde_json::json!({}),
+///     constitution: serde_json::json!({}),
+///     event: serde_json::json!({}),
+///     session_id: "sess-1".into(),
+///     chain_state: ChainState::new(),
+/// };
+/// let result = executor.execute(HookType::PreInject, "sess-1", input);
+/// assert_eq!(result.results.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SharedHookRegistry {
+    inner: Arc<RwLock<HookRegistry>>,
+}
 
-            results.push((hook.name.clone(), hook_result));
+impl From<HookRegistry> for SharedHookRegistry {
+    fn from(registry: HookRegistry) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(registry)),
         }
+    }
+}
+
+impl SharedHookRegistry {
+    /// Create a handle to an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a hook; see [`HookRegistry::register`].
+    ///
+    /// # Errors
+    ///
+    /// As [`HookRegistry::register`].
+    pub fn register(
+        &self,
+        hook: Hook,
+        scope: HookScope,
+        session_id: Option<&str>,
+    ) -> VcpResult<()> {
+        self.write().register(hook, scope, session_id)
+    }
+
+    /// Remove a hook by name; see [`HookRegistry::deregister`].
+    pub fn deregister(&self, name: &str, scope: HookScope, session_id: Option<&str>) {
+        self.write().deregister(name, scope, session_id);
+    }
+
+    /// Remove every hook registered for `session_id`.
+    pub fn clear_session(&self, session_id: &str) -> usize {
+        self.write().clear_session(session_id)
+    }
+
+    /// Number of hooks registered for `session_id`.
+    pub fn session_hook_count(&self, session_id: &str) -> usize {
+        self.read().session_hook_count(session_id)
+    }
+
+    /// Snapshot the merged chain for a hook type and session.
+    pub fn snapshot(&self, hook_type: HookType, session_id: &str) -> HookChain {
+        HookChain {
+            hook_type,
+            hooks: self.read().chain_snapshot(hook_type, session_id),
+        }
+    }
+
+    /// An executor over this registry.
+    pub fn executor(&self) -> SharedHookExecutor {
+        SharedHookExecutor {
+            registry: self.clone(),
+            metrics: None,
+        }
+    }
+
+    /// Registry operations validate before mutating, so a poisoned lock
+    /// still guards a consistent registry.
+    fn read(&self) -> RwLockReadGuard<'_, HookRegistry> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HookRegistry> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A point-in-time copy of one merged hook chain.
+///
+/// Holds its own handles to the hooks, so it can run after they have
+/// been deregistered.
+#[derive(Debug, Clone)]
+pub struct HookChain {
+    hook_type: HookType,
+    hooks: Vec<Arc<Hook>>,
+}
+
+impl HookChain {
+    /// The hook type this chain was taken for.
+    pub fn hook_type(&self) -> HookType {
+        self.hook_type
+    }
+
+    /// Hook names in execution order.
+    pub fn names(&self) -> Vec<&str> {
+        self.hooks.iter().map(|h| h.name.as_str()).collect()
+    }
+
+    /// Number of hooks, including disabled ones.
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Returns `true` if the chain has no hooks.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Run the chain with the semantics of [`HookExecutor::execute`].
+    pub fn execute(&self, input: HookInput, metrics: Option<&HookMetrics>) -> ChainResult {
+        let chain: Vec<&Hook> = self.hooks.iter().map(AsRef::as_ref).collect();
+        run_chain(&chain, self.hook_type, input, metrics)
+    }
+}
+
+/// Executes chains from a [`SharedHookRegistry`].
+///
+/// Owns its handles, so it is `'static` and can be cloned into request
+/// handlers. Each call snapshots the chain under a read lock and runs it
+/// after releasing the lock.
+#[derive(Debug, Clone)]
+pub struct SharedHookExecutor {
+    registry: SharedHookRegistry,
+    metrics: Option<Arc<HookMetrics>>,
+}
+
+impl SharedHookExecutor {
+    /// Record every hook invocation and chain run into `metrics`.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<HookMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Execute the current chain for the given type and session.
+    pub fn execute(&self, hook_type: HookType, session_id: &str, input: HookInput) -> ChainResult {
+        self.registry
+            .snapshot(hook_type, session_id)
+            .execute(input, self.metrics.as_deref())
+    }
+
+    /// Execute and keep the input; see [`HookExecutor::record`].
+    pub fn record(&self, hook_type: HookType, session_id: &str, input: HookInput) -> ChainRecord {
+        let result = self.execute(hook_type, session_id, input.clone());
+        ChainRecord {
+            hook_type,
+            session_id: session_id.to_string(),
+            input,
+            result,
+        }
+    }
+
+    /// Replay against the current chain; see [`HookExecutor::replay`].
+    pub fn replay(&self, record: &ChainRecord) -> ReplayReport {
+        let snapshot = self.registry.snapshot(record.hook_type, &record.session_id);
+        let chain: Vec<&Hook> = snapshot.hooks.iter().map(AsRef::as_ref).collect();
+        replay_chain(&chain, record)
+    }
+}
+
+// ── Chain records ───────────────────────────────────────────
+
+/// A chain run kept for post-incident analysis: what went in, and every
+/// hook's action, annotations and modifications that came out.
+///
+/// Round-trips through JSON, and [`HookExecutor::replay`] checks whether
+/// the hooks registered now would produce the same outcome.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::hooks::{
+///     ChainRecord, ChainState, HookExecutor, HookInput, HookRegistry, HookType,
+/// };
+///
+/// let registry = HookRegistry::new();
+/// let executor = HookExecutor::new(&registry);
+/// let input = HookInput {
+///     context: serde_json::json!({"mood": "calm"}),
+///     constitution: serde_json::json!({}),
+///     event: serde_json::json!({}),
+///     session_id: "sess-1".into(),
+///     chain_state: ChainState::new(),
+/// };
+///
+/// let record = executor.record(HookType::PreInject, "sess-1", input);
+/// let saved = record.to_json().unwrap();
+///
+/// let report = executor.replay(&ChainRecord::from_json(&saved).unwrap());
+/// assert!(report.matches());
+/// ```
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChainRecord {
+    /// The hook type the chain ran for.
+    pub hook_type: HookType,
+    /// The session whose chain ran.
+    pub session_id: String,
+    /// The input given to the first hook.
+    pub input: HookInput,
+    /// What the chain produced.
+    pub result: ChainResult,
+}
 
-        ChainResult {
-            completed: true,
-            aborted_by: None,
-            abort_reason: None,
-            modified_context,
-            modified_constitution,
-            results,
+impl ChainRecord {
+    /// Parse a record saved with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if the JSON is not a chain record.
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// The outcome of replaying a [`ChainRecord`].
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    /// What the current hooks produced.
+    pub result: ChainResult,
+    /// Where that differs from the recording; empty if it matches.
+    pub mismatches: Vec<ReplayMismatch>,
+}
+
+impl ReplayReport {
+    /// Whether the current hooks reproduced the recorded outcome.
+    /// Durations are not compared.
+    pub fn matches(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// One difference between a recorded and a replayed chain.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ReplayMismatch {
+    /// What differs: a [`ChainResult`] field, `hooks` for the sequence of
+    /// hooks that ran, or `hooks[<name>].action` / `.annotations` / `.state_updates`.
+    pub field: String,
+    pub recorded: serde_json::Value,
+    pub replayed: serde_json::Value,
+}
+
+impl std::fmt::Display for ReplayMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: recorded {}, replayed {}",
+            self.field, self.recorded, self.replayed
+        )
+    }
+}
+
+/// Run `chain` on the recorded input, without metrics, and compare.
+fn replay_chain(chain: &[&Hook], record: &ChainRecord) -> ReplayReport {
+    let result = run_chain(chain, record.hook_type, record.input.clone(), None);
+    let mismatches = compare_results(&record.result, &result);
+    ReplayReport { result, mismatches }
+}
+
+fn compare_results(recorded: &ChainResult, replayed: &ChainResult) -> Vec<ReplayMismatch> {
+    use serde_json::json;
+
+    let mut mismatches = Vec::new();
+    let mut check = |field: String, recorded: serde_json::Value, replayed: serde_json::Value| {
+        if recorded != replayed {
+            mismatches.push(ReplayMismatch {
+                field,
+                recorded,
+                replayed,
+            });
         }
+    };
+    check(
+        "completed".into(),
+        json!(recorded.completed),
+        json!(replayed.completed),
+    );
+    check(
+        "aborted_by".into(),
+        json!(recorded.aborted_by),
+        json!(replayed.aborted_by),
+    );
+    check(
+        "abort_reason".into(),
+        json!(recorded.abort_reason),
+        json!(replayed.abort_reason),
+    );
+    check(
+        "modified_context".into(),
+        json!(recorded.modified_context),
+        json!(replayed.modified_context),
+    );
+    check(
+        "modified_constitution".into(),
+        json!(recorded.modified_constitution),
+        json!(replayed.modified_constitution),
+    );
+    check(
+        "chain_state".into(),
+        json!(recorded.chain_state),
+        json!(replayed.chain_state),
+    );
+
+    let names = |r: &ChainResult| r.results.iter().map(|(n, _)| n.clone()).collect::<Vec<_>>();
+    if names(recorded) != names(replayed) {
+        check(
+            "hooks".into(),
+            json!(names(recorded)),
+            json!(names(replayed)),
+        );
+        return mismatches;
+    }
+    for ((name, before), (_, after)) in recorded.results.iter().zip(&replayed.results) {
+        check(
+            format!("hooks[{name}].action"),
+            json!(before.action),
+            json!(after.action),
+        );
+        check(
+            format!("hooks[{name}].annotations"),
+            json!(before.annotations),
+            json!(after.annotations),
+        );
+        check(
+            format!("hooks[{name}].state_updates"),
+            json!(before.state_updates),
+            json!(after.state_updates),
+        );
     }
+    mismatches
 }
 
 // ── Tests ───────────────────────────────────────────────────
@@ -561,6 +1273,7 @@ mod tests {
             HookResult {
                 action: HookAction::Continue,
                 annotations: HashMap::new(),
+                state_updates: ChainState::new(),
                 duration: Duration::ZERO,
             }
         }
@@ -577,6 +1290,7 @@ mod tests {
                     reason: self.reason.clone(),
                 },
                 annotations: HashMap::new(),
+                state_updates: ChainState::new(),
                 duration: Duration::ZERO,
             }
         }
@@ -591,6 +1305,7 @@ mod tests {
             HookResult {
                 action: HookAction::Modify(self.value.clone()),
                 annotations: HashMap::new(),
+                state_updates: ChainState::new(),
                 duration: Duration::ZERO,
             }
         }
@@ -615,7 +1330,7 @@ mod tests {
             hook_type,
             priority,
             handler,
-            timeout: Duration::from_millis(5000),
+            timeout: Duration::from_secs(5),
             enabled: true,
             description: format!("Test hook: {name}"),
         }
@@ -627,7 +1342,7 @@ mod tests {
             constitution: serde_json::json!({"rules": []}),
             event: serde_json::json!({}),
             session_id: "test-session".to_string(),
-            chain_state: HashMap::new(),
+            chain_state: ChainState::new(),
         }
     }
 
@@ -683,6 +1398,25 @@ mod tests {
             .contains("invalid hook name"));
     }
 
+    #[test]
+    fn dotted_name_rejected() {
+        let mut reg = HookRegistry::new();
+        let result = reg.register(
+            make_hook(
+                "acme.audit",
+                HookType::PreInject,
+                50,
+                Box::new(ContinueHandler),
+            ),
+            HookScope::Deployment,
+            None,
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("invalid hook name"));
+    }
+
     #[test]
     fn empty_name_rejected() {
         let mut reg = HookRegistry::new();
@@ -1135,4 +1869,355 @@ mod tests {
         reg.deregister("sess-hook", HookScope::Session, Some("sess-1"));
         assert_eq!(reg.get_chain(HookType::PreInject, "sess-1").len(), 0);
     }
+
+    #[test]
+    fn executor_records_metrics() {
+        let mut reg = HookRegistry::new();
+        reg.register(
+            make_hook("panicker", HookType::PreInject, 90, Box::new(PanicHandler)),
+            HookScope::Deployment,
+            None,
+        )
+        .unwrap();
+        reg.register(
+            make_hook(
+                "blocker",
+                HookType::PreInject,
+                50,
+                Box::new(AbortHandler {
+                    reason: "blocked".into(),
+                }),
+            ),
+            HookScope::Deployment,
+            None,
+        )
+        .unwrap();
+
+        let metrics = HookMetrics::new();
+        let executor = HookExecutor::new(&reg).with_metrics(&metrics);
+        executor.execute(HookType::PreInject, "s", make_input());
+        executor.execute(HookType::PreInject, "s", make_input());
+        // Chains with no hooks still count as runs.
+        executor.execute(HookType::Periodic, "s", make_input());
+
+        let snap = metrics.snapshot();
+        assert_eq!(
+            snap.hook(HookType::PreInject, "panicker").unwrap().panics,
+            2
+        );
+        let blocker = snap.hook(HookType::PreInject, "blocker").unwrap();
+        assert_eq!(blocker.invocations, 2);
+        assert_eq!(blocker.aborts, 2);
+        assert_eq!(snap.chain(HookType::PreInject).unwrap().aborts, 2);
+        assert_eq!(snap.chain(HookType::Periodic).unwrap().executions, 1);
+        assert_eq!(snap.chain(HookType::Periodic).unwrap().aborts, 0);
+    }
+
+    // ── SharedHookRegistry tests ────────────────────────────
+
+    #[test]
+    fn shared_snapshot_survives_deregister() {
+        let registry = SharedHookRegistry::new();
+        registry
+            .register(
+                make_hook("first", HookType::PreInject, 80, Box::new(ContinueHandler)),
+                HookScope::Deployment,
+                None,
+            )
+            .unwrap();
+        registry
+            .register(
+                make_hook("mine", HookType::PreInject, 90, Box::new(ContinueHandler)),
+                HookScope::Session,
+                Some("s1"),
+            )
+            .unwrap();
+
+        let chain = registry.snapshot(HookType::PreInject, "s1");
+        assert_eq!(chain.names(), ["mine", "first"]);
+
+        registry.deregister("first", HookScope::Deployment, None);
+        assert_eq!(registry.clear_session("s1"), 1);
+        assert!(registry.snapshot(HookType::PreInject, "s1").is_empty());
+
+        let result = chain.execute(make_input(), None);
+        assert!(result.completed);
+        assert_eq!(result.results.len(), 2);
+    }
+
+    #[test]
+    fn shared_registry_registers_while_executing() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let registry = SharedHookRegistry::new();
+        let metrics = Arc::new(HookMetrics::new());
+        let executor = registry.executor().with_metrics(Arc::clone(&metrics));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        std::thread::scope(|scope| {
+            for t in 0..4 {
+                let registry = registry.clone();
+                scope.spawn(move || {
+                    for i in 0..25 {
+                        let name = format!("hook-{t}-{i}");
+                        let hook =
+                            make_hook(&name, HookType::PreInject, 50, Box::new(ContinueHandler));
+                        registry
+                            .register(hook, HookScope::Deployment, None)
+                            .unwrap();
+                        if i % 2 == 0 {
+                            registry.deregister(&name, HookScope::Deployment, None);
+                        }
+                    }
+                });
+            }
+            for _ in 0..4 {
+                let (executor, runs) = (executor.clone(), Arc::clone(&runs));
+                scope.spawn(move || {
+                    for _ in 0..25 {
+                        let result = executor.execute(HookType::PreInject, "s", make_input());
+                        assert!(result.completed);
+                        runs.fetch_add(1, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(runs.load(Ordering::Relaxed), 100);
+        assert_eq!(registry.snapshot(HookType::PreInject, "s").len(), 48);
+        assert_eq!(
+            metrics.snapshot().chains[&HookType::PreInject].executions,
+            100
+        );
+    }
+
+    // ── Chain state tests ───────────────────────────────────
+
+    /// Returns fixed state updates.
+    struct StateWriter(ChainState);
+    impl HookHandler for StateWriter {
+        fn execute(&self, _input: &HookInput) -> HookResult {
+            HookResult {
+                action: HookAction::Continue,
+                annotations: HashMap::new(),
+                state_updates: self.0.clone(),
+                duration: Duration::ZERO,
+            }
+        }
+    }
+
+    /// Aborts with the abort hint, if any, and annotates what it read.
+    struct StateReader;
+    impl HookHandler for StateReader {
+        fn execute(&self, input: &HookInput) -> HookResult {
+            let state = &input.chain_state;
+            HookResult {
+                action: state.abort_hint().map_or(HookAction::Continue, |reason| {
+                    HookAction::Abort {
+                        reason: reason.into(),
+                    }
+                }),
+                annotations: HashMap::from([(
+                    "count".into(),
+                    serde_json::json!(state.get_as::<u32>("acme.count")),
+                )]),
+                state_updates: ChainState::new(),
+                duration: Duration::ZERO,
+            }
+        }
+    }
+
+    #[test]
+    fn state_updates_reach_later_hooks() {
+        let updates: ChainState = [
+            (ChainState::key("acme", "count"), serde_json::json!(3)),
+            ("stale".into(), serde_json::Value::Null),
+            (ChainState::HOOK_TYPE.into(), serde_json::json!("forged")),
+            (
+                ChainState::ABORT_HINT.into(),
+                serde_json::json!("too risky"),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let mut reg = HookRegistry::new();
+        reg.register(
+            make_hook(
+                "writer",
+                HookType::PreInject,
+                90,
+                Box::new(StateWriter(updates)),
+            ),
+            HookScope::Deployment,
+            None,
+        )
+        .unwrap();
+        reg.register(
+            make_hook("reader", HookType::PreInject, 10, Box::new(StateReader)),
+            HookScope::Deployment,
+            None,
+        )
+        .unwrap();
+
+        let mut input = make_input();
+        input.chain_state.set("stale", true);
+        let result = HookExecutor::new(&reg).execute(HookType::PreInject, "s", input);
+
+        assert_eq!(result.aborted_by.as_deref(), Some("reader"));
+        assert_eq!(result.abort_reason.as_deref(), Some("too risky"));
+        assert_eq!(
+            result.results[1].1.annotations["count"],
+            serde_json::json!(3)
+        );
+        assert_eq!(
+            result.annotations.keys().collect::<Vec<_>>(),
+            ["reader.count"]
+        );
+        assert_eq!(
+            result.chain_state.get_as::<String>(ChainState::HOOK_TYPE),
+            Some("pre_inject".into())
+        );
+        assert!(!result.chain_state.contains_key("stale"));
+    }
+
+    /// Continues, leaving fixed annotations.
+    struct Annotator(&'static [(&'static str, &'static str)]);
+    impl HookHandler for Annotator {
+        fn execute(&self, _input: &HookInput) -> HookResult {
+            HookResult {
+                action: HookAction::Continue,
+                annotations: self
+                    .0
+                    .iter()
+                    .map(|(k, v)| ((*k).to_string(), serde_json::json!(v)))
+                    .collect(),
+                state_updates: ChainState::new(),
+                duration: Duration::ZERO,
+            }
+        }
+    }
+
+    #[test]
+    fn aborted_chain_keeps_earlier_annotations() {
+        let mut reg = HookRegistry::new();
+        for (name, priority, handler) in [
+            (
+                "scan",
+                90,
+                Box::new(Annotator(&[("risk", "low"), ("pii.email", "found")]))
+                    as Box<dyn HookHandler>,
+            ),
+            ("tag", 50, Box::new(Annotator(&[("risk", "high")]))),
+            (
+                "gate",
+                10,
+                Box::new(AbortHandler {
+                    reason: "blocked".into(),
+                }),
+            ),
+        ] {
+            reg.register(
+                make_hook(name, HookType::PreInject, priority, handler),
+                HookScope::Deployment,
+                None,
+            )
+            .unwrap();
+        }
+
+        let result = HookExecutor::new(&reg).execute(HookType::PreInject, "s", make_input());
+        assert_eq!(result.aborted_by.as_deref(), Some("gate"));
+        assert_eq!(
+            result.annotations,
+            BTreeMap::from([
+                ("scan.pii.email".to_string(), serde_json::json!("found")),
+                ("scan.risk".to_string(), serde_json::json!("low")),
+                ("tag.risk".to_string(), serde_json::json!("high")),
+            ])
+        );
+    }
+
+    // ── Chain record tests ──────────────────────────────────
+
+    /// Annotates with a fixed verdict and modifies the context.
+    struct VerdictHandler(&'static str);
+    impl HookHandler for VerdictHandler {
+        fn execute(&self, _input: &HookInput) -> HookResult {
+            HookResult {
+                action: HookAction::Modify(serde_json::json!({"context": {"verdict": self.0}})),
+                annotations: HashMap::from([("verdict".into(), serde_json::json!(self.0))]),
+                state_updates: ChainState::new(),
+                duration: Duration::ZERO,
+            }
+        }
+    }
+
+    fn verdict_hook(verdict: &'static str) -> Hook {
+        Hook {
+            name: "verdict".into(),
+            hook_type: HookType::PreInject,
+            priority: 50,
+            handler: Box::new(VerdictHandler(verdict)),
+            timeout: Duration::from_millis(100),
+            enabled: true,
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn chain_record_round_trips_and_replays() {
+        let mut registry = HookRegistry::new();
+        registry
+            .register(verdict_hook("allow"), HookScope::Deployment, None)
+            .unwrap();
+        let record =
+            HookExecutor::new(&registry).record(HookType::PreInject, "sess-1", make_input());
+
+        let restored = ChainRecord::from_json(&record.to_json().unwrap()).unwrap();
+        assert_eq!(restored.session_id, "sess-1");
+        assert_eq!(restored.input.context, serde_json::json!({"key": "value"}));
+        assert_eq!(
+            restored.result.modified_context,
+            Some(serde_json::json!({"verdict": "allow"}))
+        );
+        assert_eq!(
+            restored.result.results[0].1.annotations["verdict"],
+            serde_json::json!("allow")
+        );
+
+        let report = HookExecutor::new(&registry).replay(&restored);
+        assert!(report.matches(), "{:?}", report.mismatches);
+    }
+
+    #[test]
+    fn replay_reports_changed_hooks() {
+        let mut registry = HookRegistry::new();
+        registry
+            .register(verdict_hook("allow"), HookScope::Deployment, None)
+            .unwrap();
+        let record =
+            HookExecutor::new(&registry).record(HookType::PreInject, "sess-1", make_input());
+
+        let shared = SharedHookRegistry::new();
+        shared
+            .register(verdict_hook("deny"), HookScope::Deployment, None)
+            .unwrap();
+        let report = shared.executor().replay(&record);
+        let fields: Vec<&str> = report.mismatches.iter().map(|m| m.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "modified_context",
+                "hooks[verdict].action",
+                "hooks[verdict].annotations"
+            ]
+        );
+        assert_eq!(
+            report.mismatches[0].to_string(),
+            r#"modified_context: recorded {"verdict":"allow"}, replayed {"verdict":"deny"}"#
+        );
+
+        shared.deregister("verdict", HookScope::Deployment, None);
+        let report = shared.executor().replay(&record);
+        assert!(report.mismatches.iter().any(|m| m.field == "hooks"));
+    }
 }
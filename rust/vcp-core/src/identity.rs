@@ -24,11 +24,13 @@
 //! assert_eq!(token.to_string(), "family.safe.guide@1.2.0");
 //! ```
 
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-use crate::error::{VcpError, VcpResult};
+use crate::error::{Span, VcpError, VcpResult};
+use crate::namespace::NamespaceRegistry;
 
 /// Maximum total length of a raw token string.
 const MAX_LENGTH: usize = 256;
@@ -38,6 +40,9 @@ const MAX_SEGMENT_LEN: usize = 32;
 const MIN_SEGMENTS: usize = 3;
 /// Maximum number of dot-separated segments.
 const MAX_SEGMENTS: usize = 10;
+/// Batch size from which [`VcpToken::parse_many`] parses in parallel
+/// (feature `parallel`).
+pub const PARALLEL_BATCH_THRESHOLD: usize = 256;
 
 /// Semantic version triplet `major.minor.patch`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -112,15 +117,22 @@ impl VcpToken {
     /// the maximum length, has too few or too many segments, or contains
     /// invalid characters. Returns [`VcpError::ParseError`] if the version
     /// string is malformed.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.parse.identity", level = "trace", skip_all, fields(token = raw), err(Display))
+    )]
     pub fn parse(raw: &str) -> VcpResult<Self> {
         if raw.is_empty() {
-            return Err(VcpError::MalformedToken("token cannot be empty".into()));
+            return Err(VcpError::MalformedToken("token cannot be empty".into())
+                .at(Span::new(0, 0))
+                .expecting(&["domain.approach.role"]));
         }
         if raw.len() > MAX_LENGTH {
             return Err(VcpError::MalformedToken(format!(
                 "token exceeds max length {MAX_LENGTH}: {}",
                 raw.len()
-            )));
+            ))
+            .at(Span::new(MAX_LENGTH, raw.len() - MAX_LENGTH)));
         }
 
         let mut remaining = raw;
@@ -129,7 +141,10 @@ impl VcpToken {
         let namespace = if let Some(colon_idx) = remaining.rfind(':') {
             // Namespace must come after any `@` version.
             let ns_str = &remaining[colon_idx + 1..];
-            Self::validate_namespace(ns_str)?;
+            Self::validate_namespace(ns_str).map_err(|e| {
+                e.at(Span::of(raw, ns_str))
+                    .expecting(&["namespace of A-Z and 0-9, starting with a letter"])
+            })?;
             remaining = &remaining[..colon_idx];
             Some(ns_str.to_string())
         } else {
@@ -139,7 +154,10 @@ impl VcpToken {
         // Extract version (`@X.Y.Z`).
         let version = if let Some(at_idx) = remaining.rfind('@') {
             let ver_str = &remaining[at_idx + 1..];
-            let ver = SemVer::parse(ver_str)?;
+            let ver = SemVer::parse(ver_str).map_err(|e| {
+                e.at(Span::of(raw, ver_str))
+                    .expecting(&["version MAJOR.MINOR.PATCH"])
+            })?;
             remaining = &remaining[..at_idx];
             Some(ver)
         } else {
@@ -147,32 +165,106 @@ impl VcpToken {
         };
 
         // Remaining string is the dot-separated path.
-        let segments: Vec<String> = remaining.split('.').map(String::from).collect();
+        let segments: Vec<&str> = remaining.split('.').collect();
 
         if segments.len() < MIN_SEGMENTS {
             return Err(VcpError::MalformedToken(format!(
                 "token requires at least {MIN_SEGMENTS} segments, got {}",
                 segments.len()
-            )));
+            ))
+            .at(Span::of(raw, remaining))
+            .expecting(&["domain.approach.role"]));
         }
         if segments.len() > MAX_SEGMENTS {
             return Err(VcpError::MalformedToken(format!(
                 "token exceeds maximum {MAX_SEGMENTS} segments, got {}",
                 segments.len()
-            )));
+            ))
+            .at(Span::of(raw, remaining)));
         }
 
         for (i, seg) in segments.iter().enumerate() {
-            Self::validate_segment(seg, i)?;
+            Self::validate_segment(seg, i).map_err(|e| {
+                e.at(Span::of(raw, seg))
+                    .expecting(&["segment of a-z, 0-9 and '-', starting with a letter"])
+            })?;
         }
 
         Ok(VcpToken {
-            segments,
+            segments: segments.into_iter().map(String::from).collect(),
             version,
             namespace,
         })
     }
 
+    /// Parse every input, collecting errors by index instead of stopping
+    /// at the first, and deduplicating identical tokens.
+    ///
+    /// With the `parallel` feature, batches of at least
+    /// [`PARALLEL_BATCH_THRESHOLD`] inputs are parsed on the rayon thread
+    /// pool; the result is the same either way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vcp_core::identity::VcpToken;
+    ///
+    /// let batch = VcpToken::parse_many(&["family.safe.guide", "bad", "family.safe.guide"]);
+    /// assert_eq!(batch.tokens.len(), 1);
+    /// assert_eq!(batch.token_at(2), batch.token_at(0));
+    /// assert_eq!(batch.errors[0].0, 1);
+    /// assert_eq!(batch.duplicates(), 1);
+    /// ```
+    pub fn parse_many<S: AsRef<str> + Sync>(inputs: &[S]) -> BatchParseResult {
+        #[cfg(feature = "parallel")]
+        let parsed: Vec<VcpResult<Self>> = if inputs.len() >= PARALLEL_BATCH_THRESHOLD {
+            use rayon::prelude::*;
+            inputs.par_iter().map(|s| Self::parse(s.as_ref())).collect()
+        } else {
+            inputs.iter().map(|s| Self::parse(s.as_ref())).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let parsed: Vec<VcpResult<Self>> = inputs.iter().map(|s| Self::parse(s.as_ref())).collect();
+
+        let mut batch = BatchParseResult::default();
+        let mut seen: HashMap<VcpToken, usize> = HashMap::new();
+        for (i, result) in parsed.into_iter().enumerate() {
+            match result {
+                Ok(token) => {
+                    let next = batch.tokens.len();
+                    let index = *seen.entry(token).or_insert_with_key(|token| {
+                        batch.tokens.push(token.clone());
+                        next
+                    });
+                    batch.indices.push(Some(index));
+                }
+                Err(e) => {
+                    batch.indices.push(None);
+                    batch.errors.push((i, e));
+                }
+            }
+        }
+        batch
+    }
+
+    /// Parse a token and check its namespace against `namespaces`.
+    ///
+    /// # Errors
+    ///
+    /// As [`parse`](Self::parse), and [`VcpError::MalformedToken`] if the
+    /// namespace is reserved, or unregistered in a strict registry (see
+    /// [`NamespaceRegistry::check`]).
+    pub fn parse_with_namespaces(raw: &str, namespaces: &NamespaceRegistry) -> VcpResult<Self> {
+        let token = Self::parse(raw)?;
+        if let Some(ns) = &token.namespace {
+            namespaces.check(ns).map_err(|e| {
+                let at = Span::new(raw.len() - ns.len(), ns.len());
+                e.at(at).expecting(&["a registered namespace"])
+            })?;
+        }
+        Ok(token)
+    }
+
     // ── Accessors ───────────────────────────────────────────
 
     /// First segment -- the domain / category.
@@ -308,6 +400,45 @@ impl VcpToken {
         other.is_ancestor_of(self)
     }
 
+    /// Ancestors of this token, nearest first, down to the minimum depth.
+    ///
+    /// Like [`parent`](Self::parent), each drops the version and keeps the
+    /// namespace.
+    pub fn ancestors(&self) -> impl Iterator<Item = Self> {
+        std::iter::successors(self.parent(), Self::parent)
+    }
+
+    /// The deepest token that is this token or one of its ancestors and
+    /// also `other` or one of its ancestors, or `None` if the two share
+    /// fewer than three leading segments.
+    ///
+    /// The result has no version, and keeps the namespace only if both
+    /// tokens have the same one.
+    pub fn common_ancestor(&self, other: &VcpToken) -> Option<Self> {
+        let shared = self
+            .segments
+            .iter()
+            .zip(&other.segments)
+            .take_while(|(a, b)| a == b)
+            .count();
+        (shared >= MIN_SEGMENTS).then(|| VcpToken {
+            segments: self.segments[..shared].to_vec(),
+            version: None,
+            namespace: self
+                .namespace
+                .clone()
+                .filter(|_| self.namespace == other.namespace),
+        })
+    }
+
+    /// Check whether this token's leading segments are the dot-separated
+    /// `prefix`, which may be shorter than a token (`family.safe`).
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        let prefix: Vec<&str> = prefix.split('.').collect();
+        prefix.len() <= self.segments.len()
+            && prefix.iter().zip(&self.segments).all(|(p, s)| *p == s)
+    }
+
     /// Check whether this token matches a glob-like pattern.
     ///
     /// Supports `*` as a single-segment wildcard and `**` as a
@@ -349,6 +480,19 @@ impl VcpToken {
             .all(|(pat, seg)| *pat == "*" || *pat == seg.as_str())
     }
 
+    /// Resolve this token through `aliases`.
+    ///
+    /// A token whose name is an alias becomes the token the alias maps
+    /// to, keeping its own version and namespace if it has them; any
+    /// other token is returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// See [`AliasMap::resolve`].
+    pub fn resolve_alias(&self, aliases: &AliasMap) -> VcpResult<Self> {
+        aliases.resolve(&self.full())
+    }
+
     // ── Validation helpers ──────────────────────────────────
 
     fn validate_segment(seg: &str, index: usize) -> VcpResult<()> {
@@ -379,7 +523,7 @@ impl VcpToken {
         Ok(())
     }
 
-    fn validate_namespace(ns: &str) -> VcpResult<()> {
+    pub(crate) fn validate_namespace(ns: &str) -> VcpResult<()> {
         if ns.is_empty() {
             return Err(VcpError::MalformedToken("namespace cannot be empty".into()));
         }
@@ -407,6 +551,641 @@ impl fmt::Display for VcpToken {
     }
 }
 
+// ── Batch parsing ───────────────────────────────────────────
+
+/// The outcome of [`VcpToken::parse_many`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchParseResult {
+    /// Distinct tokens, in order of first appearance.
+    pub tokens: Vec<VcpToken>,
+    /// For each input, the index into `tokens` of its token, or `None` if
+    /// it failed to parse.
+    pub indices: Vec<Option<usize>>,
+    /// `(input index, error)` for each input that failed, in input order.
+    pub errors: Vec<(usize, VcpError)>,
+}
+
+impl BatchParseResult {
+    /// Whether every input parsed.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The token parsed from input `index`, if it parsed.
+    pub fn token_at(&self, index: usize) -> Option<&VcpToken> {
+        self.indices
+            .get(index)
+            .copied()
+            .flatten()
+            .map(|i| &self.tokens[i])
+    }
+
+    /// Number of inputs that parsed to a token seen earlier in the batch.
+    pub fn duplicates(&self) -> usize {
+        self.indices.len() - self.errors.len() - self.tokens.len()
+    }
+
+    /// The distinct tokens, or the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first input that failed to parse.
+    pub fn into_result(self) -> VcpResult<Vec<VcpToken>> {
+        match self.errors.into_iter().next() {
+            Some((_, e)) => Err(e),
+            None => Ok(self.tokens),
+        }
+    }
+}
+
+// ── Token bindings ──────────────────────────────────────────
+
+/// Which versions of a token a [`TokenBinding`] accepts.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VersionReq {
+    /// `*` -- any version.
+    Any,
+    /// `X.Y.Z` -- exactly this version.
+    Exact(SemVer),
+    /// `^X.Y.Z` -- this version or later with the same major (same minor
+    /// when major is 0).
+    Caret(SemVer),
+    /// `~X.Y.Z` -- this version or later with the same major and minor.
+    Tilde(SemVer),
+}
+
+impl VersionReq {
+    /// Parse `*`, `X.Y.Z`, `^X.Y.Z` or `~X.Y.Z`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the version is malformed.
+    pub fn parse(s: &str) -> VcpResult<Self> {
+        if s == "*" {
+            return Ok(Self::Any);
+        }
+        if let Some(rest) = s.strip_prefix('^') {
+            return SemVer::parse(rest).map(Self::Caret);
+        }
+        if let Some(rest) = s.strip_prefix('~') {
+            return SemVer::parse(rest).map(Self::Tilde);
+        }
+        SemVer::parse(s).map(Self::Exact)
+    }
+
+    /// Whether `version` satisfies the requirement.
+    pub fn matches(&self, version: &SemVer) -> bool {
+        let key = |v: &SemVer| (v.major, v.minor, v.patch);
+        match self {
+            Self::Any => true,
+            Self::Exact(req) => req == version,
+            Self::Caret(req) => {
+                key(version) >= key(req)
+                    && version.major == req.major
+                    && (req.major > 0 || version.minor == req.minor)
+            }
+            Self::Tilde(req) => {
+                key(version) >= key(req) && version.major == req.major && version.minor == req.minor
+            }
+        }
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => f.write_str("*"),
+            Self::Exact(v) => write!(f, "{v}"),
+            Self::Caret(v) => write!(f, "^{v}"),
+            Self::Tilde(v) => write!(f, "~{v}"),
+        }
+    }
+}
+
+/// The set of tokens a manifest declares itself bound to.
+///
+/// Written like a token, but segments may be `*` (one segment) or `**`
+/// (any number, see [`VcpToken::matches_pattern`]) and the version is a
+/// [`VersionReq`]. A token matches when its segments fit the pattern, its
+/// version satisfies the requirement and, if the binding names a
+/// namespace, its namespace is the same. A token without a version is not
+/// constrained by the requirement.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::identity::{TokenBinding, VcpToken};
+///
+/// let binding = TokenBinding::parse("family.*.guide@^1.2.0").unwrap();
+/// assert!(binding.matches(&VcpToken::parse("family.safe.guide@1.4.1").unwrap()));
+/// assert!(!binding.matches(&VcpToken::parse("family.safe.guide@2.0.0").unwrap()));
+/// assert!(!binding.matches(&VcpToken::parse("work.safe.guide@1.2.0").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenBinding {
+    pattern: String,
+    version: Option<VersionReq>,
+    namespace: Option<String>,
+}
+
+impl TokenBinding {
+    /// Parse a binding such as `family.safe.guide@^1.2.0:SEC`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::MalformedToken`] for invalid segments or
+    /// namespace, and [`VcpError::ParseError`] for a malformed version.
+    pub fn parse(raw: &str) -> VcpResult<Self> {
+        if raw.len() > MAX_LENGTH {
+            return Err(VcpError::MalformedToken(format!(
+                "binding exceeds max length {MAX_LENGTH}: {}",
+                raw.len()
+            )));
+        }
+        let mut remaining = raw;
+        let namespace = match remaining.rsplit_once(':') {
+            Some((rest, ns)) => {
+                VcpToken::validate_namespace(ns)?;
+                remaining = rest;
+                Some(ns.to_string())
+            }
+            None => None,
+        };
+        let version = match remaining.rsplit_once('@') {
+            Some((rest, req)) => {
+                remaining = rest;
+                Some(VersionReq::parse(req)?)
+            }
+            None => None,
+        };
+
+        let segments: Vec<&str> = remaining.split('.').collect();
+        if segments.len() < 2 || segments.len() > MAX_SEGMENTS {
+            return Err(VcpError::MalformedToken(format!(
+                "binding needs 2 to {MAX_SEGMENTS} segments, got {}",
+                segments.len()
+            )));
+        }
+        if segments.iter().filter(|s| **s == "**").count() > 1 {
+            return Err(VcpError::MalformedToken(
+                "binding may contain at most one ** segment".into(),
+            ));
+        }
+        for (i, seg) in segments.iter().enumerate() {
+            if !matches!(*seg, "*" | "**") {
+                VcpToken::validate_segment(seg, i)?;
+            }
+        }
+
+        Ok(Self {
+            pattern: remaining.to_string(),
+            version,
+            namespace,
+        })
+    }
+
+    /// The segment pattern, without version or namespace.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// The version requirement, if any.
+    pub fn version(&self) -> Option<&VersionReq> {
+        self.version.as_ref()
+    }
+
+    /// The required namespace, if any.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Whether `token` is covered by this binding.
+    pub fn matches(&self, token: &VcpToken) -> bool {
+        if !token.matches_pattern(&self.pattern) {
+            return false;
+        }
+        if let (Some(req), Some(version)) = (&self.version, &token.version) {
+            if !req.matches(version) {
+                return false;
+            }
+        }
+        match &self.namespace {
+            Some(ns) => token.namespace.as_ref() == Some(ns),
+            None => true,
+        }
+    }
+}
+
+impl fmt::Display for TokenBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)?;
+        if let Some(version) = &self.version {
+            write!(f, "@{version}")?;
+        }
+        if let Some(ns) = &self.namespace {
+            write!(f, ":{ns}")?;
+        }
+        Ok(())
+    }
+}
+
+// ── Token trees ─────────────────────────────────────────────
+
+/// A set of tokens indexed by segment, for prefix queries over many
+/// tokens.
+///
+/// Tokens are stored in a trie of their segments, so finding everything
+/// under `family.safe` visits only that branch. Tokens that differ only in
+/// version or namespace are distinct entries at the same node. Iteration is
+/// depth-first, in segment order.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::identity::{TokenTree, VcpToken};
+///
+/// let tree: TokenTree = ["family.safe.guide", "family.safe.guide.teen", "work.safe.guide"]
+///     .into_iter()
+///     .map(|t| VcpToken::parse(t).unwrap())
+///     .collect();
+///
+/// let under: Vec<String> = tree.with_prefix("family.safe").map(|t| t.full()).collect();
+/// assert_eq!(under, ["family.safe.guide", "family.safe.guide.teen"]);
+///
+/// let teen = VcpToken::parse("family.safe.guide.teen").unwrap();
+/// assert_eq!(tree.ancestors_of(&teen).count(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenTree {
+    root: TreeNode,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct TreeNode {
+    tokens: Vec<VcpToken>,
+    children: BTreeMap<String, TreeNode>,
+}
+
+impl TreeNode {
+    fn find(&self, segments: &[impl AsRef<str>]) -> Option<&TreeNode> {
+        segments
+            .iter()
+            .try_fold(self, |node, seg| node.children.get(seg.as_ref()))
+    }
+
+    fn collect<'a>(&'a self, out: &mut Vec<&'a VcpToken>) {
+        out.extend(&self.tokens);
+        for child in self.children.values() {
+            child.collect(out);
+        }
+    }
+
+    /// Remove `token` below this node, pruning branches left empty.
+    fn remove(&mut self, token: &VcpToken, depth: usize) -> bool {
+        let Some(seg) = token.segments.get(depth) else {
+            let before = self.tokens.len();
+            self.tokens.retain(|t| t != token);
+            return self.tokens.len() != before;
+        };
+        let Some(child) = self.children.get_mut(seg) else {
+            return false;
+        };
+        let removed = child.remove(token, depth + 1);
+        if child.tokens.is_empty() && child.children.is_empty() {
+            self.children.remove(seg);
+        }
+        removed
+    }
+}
+
+impl TokenTree {
+    /// An empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `token`, returning `false` if it was already present.
+    pub fn insert(&mut self, token: VcpToken) -> bool {
+        let node = token.segments.iter().fold(&mut self.root, |node, seg| {
+            node.children.entry(seg.clone()).or_default()
+        });
+        if node.tokens.contains(&token) {
+            return false;
+        }
+        node.tokens.push(token);
+        self.len += 1;
+        true
+    }
+
+    /// Remove `token`, returning whether it was present.
+    pub fn remove(&mut self, token: &VcpToken) -> bool {
+        let removed = self.root.remove(token, 0);
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Whether `token` is present, version and namespace included.
+    pub fn contains(&self, token: &VcpToken) -> bool {
+        self.root
+            .find(&token.segments)
+            .is_some_and(|node| node.tokens.contains(token))
+    }
+
+    /// Number of tokens.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the tree has no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// All tokens, depth-first in segment order.
+    pub fn iter(&self) -> impl Iterator<Item = &VcpToken> {
+        self.with_segments(&[] as &[&str])
+    }
+
+    /// Tokens whose leading segments are the dot-separated `prefix` (see
+    /// [`VcpToken::starts_with`]).
+    pub fn with_prefix(&self, prefix: &str) -> impl Iterator<Item = &VcpToken> {
+        self.with_segments(&prefix.split('.').collect::<Vec<_>>())
+    }
+
+    /// Tokens that are descendants of `token`, shallowest first along each
+    /// branch.
+    pub fn descendants_of<'a>(&'a self, token: &VcpToken) -> impl Iterator<Item = &'a VcpToken> {
+        let mut out = Vec::new();
+        if let Some(node) = self.root.find(&token.segments) {
+            for child in node.children.values() {
+                child.collect(&mut out);
+            }
+        }
+        out.into_iter()
+    }
+
+    /// Tokens that are ancestors of `token`, outermost first.
+    pub fn ancestors_of<'a>(&'a self, token: &VcpToken) -> impl Iterator<Item = &'a VcpToken> {
+        let depth = token.segments.len().saturating_sub(1);
+        token.segments[..depth]
+            .iter()
+            .scan(&self.root, |node, seg| {
+                *node = node.children.get(seg)?;
+                Some(&node.tokens)
+            })
+            .flatten()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn with_segments(&self, segments: &[impl AsRef<str>]) -> std::vec::IntoIter<&VcpToken> {
+        let mut out = Vec::new();
+        if let Some(node) = self.root.find(segments) {
+            node.collect(&mut out);
+        }
+        out.into_iter()
+    }
+}
+
+impl Extend<VcpToken> for TokenTree {
+    fn extend<I: IntoIterator<Item = VcpToken>>(&mut self, iter: I) {
+        for token in iter {
+            self.insert(token);
+        }
+    }
+}
+
+impl FromIterator<VcpToken> for TokenTree {
+    fn from_iter<I: IntoIterator<Item = VcpToken>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+// ── Aliases ─────────────────────────────────────────────────
+
+/// Shorthand names for tokens, such as `fam.guide` for
+/// `family.safe.guide@1.2.0`.
+///
+/// Each alias maps to a token or to another alias. An alias is made of
+/// one to ten token segments, so a full token such as
+/// `family.nanny.guide` can itself be an alias, mapping a retired name to
+/// its canonical one. Every alias must resolve: a map with a cycle or a
+/// dangling alias is rejected when it is built or loaded.
+///
+/// As JSON, the map is an object from alias to target:
+///
+/// ```json
+/// {"fam.guide": "family.safe.guide@1.2.0", "guide": "fam.guide"}
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::identity::{AliasMap, VcpToken};
+///
+/// let aliases = AliasMap::from_json(
+///     r#"{"fam.guide": "family.safe.guide@1.2.0", "guide": "fam.guide"}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(aliases.resolve("guide").unwrap().full(), "family.safe.guide@1.2.0");
+/// // A version or namespace on the alias overrides the target's.
+/// assert_eq!(
+///     aliases.resolve("fam.guide@2.0.0:SEC").unwrap().full(),
+///     "family.safe.guide@2.0.0:SEC"
+/// );
+/// // Names that are not aliases are parsed as tokens.
+/// let token = VcpToken::parse("work.safe.guide").unwrap();
+/// assert_eq!(token.resolve_alias(&aliases).unwrap(), token);
+///
+/// assert!(AliasMap::from_json(r#"{"a.b": "b.a", "b.a": "a.b"}"#).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    try_from = "BTreeMap<String, String>",
+    into = "BTreeMap<String, String>"
+)]
+pub struct AliasMap {
+    entries: BTreeMap<String, String>,
+}
+
+impl AliasMap {
+    /// An empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map `alias` to `target`, a token or an existing alias, replacing
+    /// any previous target.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::MalformedToken`] if `alias` is not a valid
+    /// alias name, or if `target` does not resolve to a token or would
+    /// close a cycle. The map is unchanged on error.
+    pub fn insert(&mut self, alias: &str, target: &str) -> VcpResult<()> {
+        Self::validate_alias(alias)?;
+        let previous = self.entries.insert(alias.to_string(), target.to_string());
+        if let Err(e) = self.resolve(alias) {
+            match previous {
+                Some(previous) => self.entries.insert(alias.to_string(), previous),
+                None => self.entries.remove(alias),
+            };
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// The target `alias` maps to directly, if it is an alias.
+    pub fn get(&self, alias: &str) -> Option<&str> {
+        self.entries.get(alias).map(String::as_str)
+    }
+
+    /// Iterate over `(alias, target)` pairs in alias order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Number of aliases.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the map has no aliases.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Resolve `name` to a token, following aliases until a name that is
+    /// not one, which is parsed as a token.
+    ///
+    /// `name` may carry a `@version` and `:NAMESPACE`, which take
+    /// precedence over any set further along the chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::MalformedToken`] if the chain loops, and the
+    /// errors of [`VcpToken::parse`] if it ends at an invalid token.
+    pub fn resolve(&self, name: &str) -> VcpResult<VcpToken> {
+        let mut chain: Vec<&str> = Vec::new();
+        let mut current = name;
+        let mut version = None;
+        let mut namespace = None;
+        loop {
+            let alias = Self::split_qualifiers(current)
+                .ok()
+                .and_then(|(base, ver, ns)| self.entries.get(base).map(|t| (base, ver, ns, t)));
+            let Some((base, ver, ns, target)) = alias else {
+                let token = if chain.is_empty() {
+                    VcpToken::parse(current)?
+                } else {
+                    VcpToken::parse(current).map_err(|e| {
+                        VcpError::MalformedToken(format!(
+                            "alias '{}' maps to '{current}', which is neither an alias nor a valid token: {e}",
+                            chain[chain.len() - 1]
+                        ))
+                    })?
+                };
+                return Ok(VcpToken {
+                    version: version.or(token.version),
+                    namespace: namespace.or(token.namespace),
+                    segments: token.segments,
+                });
+            };
+            if chain.contains(&base) {
+                chain.push(base);
+                return Err(VcpError::MalformedToken(format!(
+                    "alias cycle: {}",
+                    chain.join(" -> ")
+                )));
+            }
+            chain.push(base);
+            version = version.or(ver);
+            namespace = namespace.or(ns);
+            current = target;
+        }
+    }
+
+    /// Parse from a JSON object of alias to target.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if the JSON is malformed, an alias
+    /// name is invalid, or an alias does not resolve.
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    fn validate_alias(alias: &str) -> VcpResult<()> {
+        let segments: Vec<&str> = alias.split('.').collect();
+        if alias.len() > MAX_LENGTH || segments.len() > MAX_SEGMENTS {
+            return Err(VcpError::MalformedToken(format!(
+                "alias '{alias}' is too long"
+            )));
+        }
+        for (i, seg) in segments.iter().enumerate() {
+            VcpToken::validate_segment(seg, i)
+                .map_err(|e| VcpError::MalformedToken(format!("invalid alias '{alias}': {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// Split `name@version:NAMESPACE` into its parts.
+    fn split_qualifiers(name: &str) -> VcpResult<(&str, Option<SemVer>, Option<String>)> {
+        let mut base = name;
+        let namespace = match base.rsplit_once(':') {
+            Some((rest, ns)) => {
+                VcpToken::validate_namespace(ns)?;
+                base = rest;
+                Some(ns.to_string())
+            }
+            None => None,
+        };
+        let version = match base.rsplit_once('@') {
+            Some((rest, ver)) => {
+                base = rest;
+                Some(SemVer::parse(ver)?)
+            }
+            None => None,
+        };
+        Ok((base, version, namespace))
+    }
+}
+
+impl TryFrom<BTreeMap<String, String>> for AliasMap {
+    type Error = VcpError;
+
+    fn try_from(entries: BTreeMap<String, String>) -> VcpResult<Self> {
+        for alias in entries.keys() {
+            Self::validate_alias(alias)?;
+        }
+        let map = Self { entries };
+        for alias in map.entries.keys() {
+            map.resolve(alias)?;
+        }
+        Ok(map)
+    }
+}
+
+impl From<AliasMap> for BTreeMap<String, String> {
+    fn from(map: AliasMap) -> Self {
+        map.entries
+    }
+}
+
 // ── Tests ───────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -469,6 +1248,58 @@ mod tests {
         assert_eq!(t.role(), "web-guard");
     }
 
+    // ── Batch parsing ───────────────────────────────────
+
+    #[test]
+    fn parse_many_aggregates_errors_and_dedups() {
+        let inputs = [
+            "family.safe.guide@1.0.0",
+            "",
+            "work.safe.guide",
+            "family.safe.guide@1.0.0",
+            "family.safe.guide",
+            "Work.safe.guide",
+        ];
+        let batch = VcpToken::parse_many(&inputs);
+        assert!(!batch.is_ok());
+        assert_eq!(batch.tokens.len(), 3);
+        assert_eq!(
+            batch.indices,
+            [Some(0), None, Some(1), Some(0), Some(2), None]
+        );
+        assert_eq!(
+            batch.errors.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            [1, 5]
+        );
+        assert_eq!(batch.duplicates(), 1);
+        assert_eq!(batch.token_at(3).unwrap().full(), "family.safe.guide@1.0.0");
+        assert_eq!(batch.token_at(1), None);
+        assert_eq!(batch.token_at(99), None);
+        assert!(batch.into_result().is_err());
+    }
+
+    #[test]
+    fn parse_many_large_batch_keeps_input_order() {
+        // Large enough for the parallel path when it is compiled in.
+        let inputs: Vec<String> = (0..PARALLEL_BATCH_THRESHOLD * 4)
+            .map(|i| match i % 3 {
+                0 => format!("family.safe.guide@1.0.{}", i % 50),
+                1 => format!("bad{i}"),
+                _ => format!("work.team-{}.lead", i % 7),
+            })
+            .collect();
+        let batch = VcpToken::parse_many(&inputs);
+        assert_eq!(batch.indices.len(), inputs.len());
+        assert_eq!(batch.tokens.len(), 50 + 7);
+        assert!(batch.errors.iter().all(|(i, _)| i % 3 == 1));
+        for (i, input) in inputs.iter().enumerate() {
+            if let Some(token) = batch.token_at(i) {
+                assert_eq!(&token.full(), input);
+            }
+        }
+        assert!(VcpToken::parse_many(&inputs[..1]).into_result().is_ok());
+    }
+
     // ── Display roundtrip ───────────────────────────────
 
     #[test]
@@ -512,6 +1343,20 @@ mod tests {
         assert!(VcpToken::parse("a.b.c:sec").is_err());
     }
 
+    #[test]
+    fn errors_carry_spans() {
+        let span = |raw| VcpToken::parse(raw).unwrap_err().span();
+        assert_eq!(span("a.b.c@1.2"), Some(Span::new(6, 3)));
+        assert_eq!(span("a.b.c@1.2.0:sec"), Some(Span::new(12, 3)));
+        assert_eq!(span("family.Safe.guide"), Some(Span::new(7, 4)));
+        assert_eq!(span("a.b"), Some(Span::new(0, 3)));
+        assert_eq!(span(""), Some(Span::new(0, 0)));
+
+        let err = VcpToken::parse("family.safe.guide@1.x.0").unwrap_err();
+        assert_eq!(err.expected(), ["version MAJOR.MINOR.PATCH"]);
+        assert!(matches!(err.without_span(), VcpError::ParseError(_)));
+    }
+
     #[test]
     fn segment_too_long() {
         let long = "a".repeat(33);
@@ -555,6 +1400,42 @@ mod tests {
         assert!(!descendant.is_ancestor_of(&ancestor));
     }
 
+    #[test]
+    fn ancestors_nearest_first() {
+        let t = VcpToken::parse("company.acme.legal.compliance.gdpr@1.0.0:SEC").unwrap();
+        let ancestors: Vec<String> = t.ancestors().map(|a| a.full()).collect();
+        assert_eq!(
+            ancestors,
+            [
+                "company.acme.legal.compliance:SEC",
+                "company.acme.legal:SEC"
+            ]
+        );
+        assert!(t.ancestors().all(|a| a.is_ancestor_of(&t)));
+        assert_eq!(VcpToken::parse("a.b.c").unwrap().ancestors().count(), 0);
+    }
+
+    #[test]
+    fn common_ancestor_and_prefix() {
+        let a = VcpToken::parse("company.acme.legal.compliance:SEC").unwrap();
+        let b = VcpToken::parse("company.acme.legal.privacy@2.0.0:SEC").unwrap();
+        let c = VcpToken::parse("company.acme.hr.policy").unwrap();
+        assert_eq!(
+            a.common_ancestor(&b).unwrap().full(),
+            "company.acme.legal:SEC"
+        );
+        assert_eq!(
+            a.common_ancestor(&a.parent().unwrap()).unwrap(),
+            a.parent().unwrap()
+        );
+        assert_eq!(a.common_ancestor(&c), None);
+
+        assert!(a.starts_with("company.acme"));
+        assert!(a.starts_with("company.acme.legal.compliance"));
+        assert!(!a.starts_with("company.acme.leg"));
+        assert!(!a.starts_with("company.acme.legal.compliance.gdpr"));
+    }
+
     // ── Pattern matching ────────────────────────────────
 
     #[test]
@@ -582,4 +1463,191 @@ mod tests {
             "creed://creed.space/family.safe.guide@1.0.0"
         );
     }
+
+    // ── Bindings ────────────────────────────────────────
+
+    fn token(raw: &str) -> VcpToken {
+        VcpToken::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn version_req_rules() {
+        let v = |s| SemVer::parse(s).unwrap();
+        let caret = VersionReq::parse("^1.2.0").unwrap();
+        assert!(caret.matches(&v("1.2.0")));
+        assert!(caret.matches(&v("1.9.3")));
+        assert!(!caret.matches(&v("1.1.9")));
+        assert!(!caret.matches(&v("2.0.0")));
+
+        let caret_zero = VersionReq::parse("^0.3.1").unwrap();
+        assert!(caret_zero.matches(&v("0.3.4")));
+        assert!(!caret_zero.matches(&v("0.4.0")));
+
+        let tilde = VersionReq::parse("~1.2.3").unwrap();
+        assert!(tilde.matches(&v("1.2.9")));
+        assert!(!tilde.matches(&v("1.3.0")));
+
+        assert!(VersionReq::parse("*").unwrap().matches(&v("9.9.9")));
+        assert!(!VersionReq::parse("1.2.0").unwrap().matches(&v("1.2.1")));
+        assert!(VersionReq::parse(">=1.0.0").is_err());
+    }
+
+    #[test]
+    fn binding_matches_segments_version_and_namespace() {
+        let binding = TokenBinding::parse("company.**@~2.1.0:SEC").unwrap();
+        assert_eq!(binding.pattern(), "company.**");
+        assert_eq!(binding.namespace(), Some("SEC"));
+        assert_eq!(binding.to_string(), "company.**@~2.1.0:SEC");
+
+        assert!(binding.matches(&token("company.acme.legal.compliance@2.1.4:SEC")));
+        assert!(!binding.matches(&token("company.acme.legal.compliance@2.2.0:SEC")));
+        assert!(!binding.matches(&token("company.acme.legal.compliance@2.1.4")));
+        assert!(!binding.matches(&token("org.acme.legal@2.1.4:SEC")));
+        // An unversioned token is not constrained by the requirement.
+        assert!(binding.matches(&token("company.acme.legal:SEC")));
+    }
+
+    #[test]
+    fn binding_rejects_malformed() {
+        for bad in [
+            "family",
+            "Family.safe.guide",
+            "**.safe.**",
+            "family.safe.guide@1.x",
+            "a.b.c:ns",
+        ] {
+            assert!(TokenBinding::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    // ── Aliases ─────────────────────────────────────────
+
+    #[test]
+    fn alias_chain_resolves_with_outer_qualifiers_winning() {
+        let mut aliases = AliasMap::new();
+        aliases
+            .insert("fam.guide", "family.safe.guide@1.2.0:FAM")
+            .unwrap();
+        aliases.insert("guide", "fam.guide@1.3.0").unwrap();
+
+        assert_eq!(
+            aliases.resolve("guide").unwrap(),
+            token("family.safe.guide@1.3.0:FAM")
+        );
+        assert_eq!(
+            aliases.resolve("guide:SEC").unwrap(),
+            token("family.safe.guide@1.3.0:SEC")
+        );
+        assert_eq!(
+            token("fam.safe.x").resolve_alias(&aliases).unwrap(),
+            token("fam.safe.x")
+        );
+    }
+
+    #[test]
+    fn retired_token_maps_to_canonical() {
+        let aliases =
+            AliasMap::from_json(r#"{"family.nanny.guide": "family.safe.guide"}"#).unwrap();
+        let resolved = token("family.nanny.guide@2.0.0")
+            .resolve_alias(&aliases)
+            .unwrap();
+        assert_eq!(resolved, token("family.safe.guide@2.0.0"));
+        assert_eq!(
+            AliasMap::from_json(&aliases.to_json().unwrap()).unwrap(),
+            aliases
+        );
+    }
+
+    #[test]
+    fn insert_rejects_cycles_and_dangling_targets() {
+        let mut aliases = AliasMap::new();
+        aliases.insert("a", "family.safe.guide").unwrap();
+        aliases.insert("b", "a").unwrap();
+
+        let err = aliases.insert("a", "b").unwrap_err().to_string();
+        assert!(err.contains("a -> b -> a"), "{err}");
+        // The previous target is kept.
+        assert_eq!(aliases.get("a"), Some("family.safe.guide"));
+
+        assert!(aliases.insert("c", "nowhere").is_err());
+        assert_eq!(aliases.get("c"), None);
+        assert!(aliases.insert("Bad", "a").is_err());
+        assert_eq!(aliases.len(), 2);
+    }
+
+    #[test]
+    fn from_json_rejects_invalid_maps() {
+        for bad in [
+            r#"{"a": "b", "b": "c", "c": "a"}"#,
+            r#"{"self": "self"}"#,
+            r#"{"fam.guide": "fam.missing"}"#,
+            r#"{"Fam": "family.safe.guide"}"#,
+            r#"["family.safe.guide"]"#,
+        ] {
+            assert!(AliasMap::from_json(bad).is_err(), "{bad}");
+        }
+    }
+
+    // ── Token trees ─────────────────────────────────────
+
+    fn tree(tokens: &[&str]) -> TokenTree {
+        tokens.iter().map(|t| token(t)).collect()
+    }
+
+    #[test]
+    fn tree_prefix_and_hierarchy_queries() {
+        let tree = tree(&[
+            "company.acme.legal",
+            "company.acme.legal.compliance@1.0.0",
+            "company.acme.legal.compliance@2.0.0",
+            "company.acme.legal.compliance.gdpr",
+            "company.acme.hr.policy",
+            "family.safe.guide",
+        ]);
+        let full = |it: &mut dyn Iterator<Item = &VcpToken>| -> Vec<String> {
+            it.map(VcpToken::full).collect()
+        };
+
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.iter().count(), 6);
+        assert_eq!(tree.with_prefix("company.acme").count(), 5);
+        assert_eq!(tree.with_prefix("company.acme.le").count(), 0);
+        assert_eq!(
+            full(&mut tree.descendants_of(&token("company.acme.legal"))),
+            [
+                "company.acme.legal.compliance@1.0.0",
+                "company.acme.legal.compliance@2.0.0",
+                "company.acme.legal.compliance.gdpr",
+            ]
+        );
+        assert_eq!(
+            full(&mut tree.ancestors_of(&token("company.acme.legal.compliance.gdpr.art17"))),
+            [
+                "company.acme.legal",
+                "company.acme.legal.compliance@1.0.0",
+                "company.acme.legal.compliance@2.0.0",
+                "company.acme.legal.compliance.gdpr",
+            ]
+        );
+        assert_eq!(tree.ancestors_of(&token("family.safe.guide")).count(), 0);
+    }
+
+    #[test]
+    fn tree_insert_remove() {
+        let mut tree = tree(&["family.safe.guide", "family.safe.guide.teen"]);
+        assert!(!tree.insert(token("family.safe.guide")));
+        assert!(tree.insert(token("family.safe.guide:FAM")));
+        assert!(tree.contains(&token("family.safe.guide:FAM")));
+        assert!(!tree.contains(&token("family.safe.guide@1.0.0")));
+
+        assert!(tree.remove(&token("family.safe.guide.teen")));
+        assert!(!tree.remove(&token("family.safe.guide.teen")));
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.descendants_of(&token("family.safe.guide")).count(), 0);
+
+        assert!(tree.remove(&token("family.safe.guide")));
+        assert!(tree.remove(&token("family.safe.guide:FAM")));
+        assert!(tree.is_empty());
+        assert_eq!(tree, TokenTree::new());
+    }
 }
@@ -0,0 +1,233 @@
+//! Identifier generation for JTIs, session IDs and fixtures.
+//!
+//! Anything that mints identifiers takes an [`IdGenerator`] rather than
+//! calling `rand` directly, so tests and recorded transcripts can swap in
+//! [`SeededIdGenerator`] and get the same IDs on every run.
+//!
+//! | Generator | Output |
+//! |-----------|--------|
+//! | [`UuidV7Generator`] | Time-ordered random `UUIDv7` (default) |
+//! | [`SeededIdGenerator`] | `UUIDv7`-shaped IDs derived from a seed and a counter |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::ids::{IdGenerator, SeededIdGenerator};
+//!
+//! let a = SeededIdGenerator::new(42);
+//! let b = SeededIdGenerator::new(42);
+//! assert_eq!(a.next_id(), b.next_id());
+//! assert_ne!(a.next_id(), SeededIdGenerator::new(7).next_id());
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::clock::{Clock, SystemClock};
+
+/// Source of unique identifiers.
+pub trait IdGenerator: Send + Sync {
+    /// Return the next identifier.
+    fn next_id(&self) -> String;
+}
+
+impl<T: IdGenerator + ?Sized> IdGenerator for Arc<T> {
+    fn next_id(&self) -> String {
+        (**self).next_id()
+    }
+}
+
+impl<T: IdGenerator + ?Sized> IdGenerator for Box<T> {
+    fn next_id(&self) -> String {
+        (**self).next_id()
+    }
+}
+
+/// The generator used when none is supplied.
+pub fn default_generator() -> Arc<dyn IdGenerator> {
+    Arc::new(UuidV7Generator)
+}
+
+// ── UUIDv7 ───────────────────────────────────────────────────
+
+/// Random `UUIDv7` generator (RFC 9562): 48-bit Unix-millisecond timestamp
+/// followed by 74 random bits.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV7Generator;
+
+impl IdGenerator for UuidV7Generator {
+    fn next_id(&self) -> String {
+        let now_ms = u64::try_from(SystemClock.now().timestamp_millis()).unwrap_or(0);
+        uuid_v7(now_ms, rand::random::<u64>(), rand::random::<u64>())
+    }
+}
+
+// ── Seeded ───────────────────────────────────────────────────
+
+/// Deterministic generator for tests and golden vectors.
+///
+/// The `n`th ID depends only on the seed and `n`, using `SplitMix64`
+/// rather than `rand`'s RNGs so output is stable across `rand` releases.
+/// The seed is mixed before use, so nearby seeds give unrelated IDs.
+/// The embedded timestamp is `base_ms + n`, so IDs still sort in
+/// generation order.
+#[derive(Debug)]
+pub struct SeededIdGenerator {
+    /// The seed after one `SplitMix64` round.
+    key: u64,
+    base_ms: u64,
+    counter: AtomicU64,
+}
+
+impl SeededIdGenerator {
+    /// Create a generator with timestamp base 0.
+    pub fn new(seed: u64) -> Self {
+        Self::with_base_ms(seed, 0)
+    }
+
+    /// Create a generator whose first ID carries timestamp `base_ms`.
+    pub fn with_base_ms(seed: u64, base_ms: u64) -> Self {
+        Self {
+            // Unmixed, seeds 0 and 1 would give the same words swapped.
+            key: splitmix64(seed),
+            base_ms,
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of IDs handed out so far.
+    pub fn issued(&self) -> u64 {
+        self.counter.load(Ordering::Relaxed)
+    }
+}
+
+impl IdGenerator for SeededIdGenerator {
+    fn next_id(&self) -> String {
+        let n = self.counter.fetch_add(1, Ordering::Relaxed);
+        let hi = splitmix64(self.key ^ n.wrapping_mul(2));
+        let lo = splitmix64(self.key ^ n.wrapping_mul(2).wrapping_add(1));
+        uuid_v7(self.base_ms.wrapping_add(n), hi, lo)
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Lay out a `UUIDv7` from a millisecond timestamp and two random words.
+fn uuid_v7(ms: u64, rand_hi: u64, rand_lo: u64) -> String {
+    let time_hi = (ms >> 16) & 0xFFFF_FFFF;
+    let time_lo = ms & 0xFFFF;
+    let ver_rand_a = 0x7000 | (rand_hi & 0x0FFF);
+    let var_rand_b = 0x8000 | ((rand_hi >> 12) & 0x3FFF);
+    let rand_tail = rand_lo & 0xFFFF_FFFF_FFFF;
+    format!("{time_hi:08x}-{time_lo:04x}-{ver_rand_a:04x}-{var_rand_b:04x}-{rand_tail:012x}")
+}
+
+// ── Tests ────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn assert_v7_shape(id: &str) {
+        assert_eq!(id.len(), 36);
+        let parts: Vec<&str> = id.split('-').collect();
+        assert_eq!(
+            parts.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            [8, 4, 4, 4, 12]
+        );
+        assert!(parts[2].starts_with('7'));
+        assert!(matches!(parts[3].as_bytes()[0], b'8' | b'9' | b'a' | b'b'));
+    }
+
+    #[test]
+    fn uuid_v7_is_well_formed_and_unique() {
+        let g = UuidV7Generator;
+        let ids: HashSet<String> = (0..100).map(|_| g.next_id()).collect();
+        assert_eq!(ids.len(), 100);
+        for id in &ids {
+            assert_v7_shape(id);
+        }
+    }
+
+    #[test]
+    fn seeded_is_reproducible() {
+        let a = SeededIdGenerator::new(1);
+        let b = SeededIdGenerator::new(1);
+        let xs: Vec<String> = (0..10).map(|_| a.next_id()).collect();
+        let ys: Vec<String> = (0..10).map(|_| b.next_id()).collect();
+        assert_eq!(xs, ys);
+        assert_eq!(xs.iter().collect::<HashSet<_>>().len(), 10);
+        assert_eq!(a.issued(), 10);
+        for id in &xs {
+            assert_v7_shape(id);
+        }
+    }
+
+    #[test]
+    fn seeded_golden_value() {
+        // Pinned so a change to the derivation is caught.
+        let g = SeededIdGenerator::with_base_ms(0, 1_700_000_000_000);
+        assert_eq!(g.next_id(), "018bcfe5-6800-7e6f-9197-fda8c892b50e");
+        assert_eq!(g.next_id(), "018bcfe5-6801-7a39-b5ff-565020d09481");
+    }
+
+    #[test]
+    fn adjacent_seeds_are_unrelated() {
+        // (rand_a, rand_b, tail): the random parts of an ID.
+        let parts = |id: &str| {
+            let fields: Vec<&str> = id.split('-').collect();
+            (
+                fields[2][1..].to_string(),
+                fields[3].to_string(),
+                fields[4].to_string(),
+            )
+        };
+        let ids: Vec<Vec<(String, String, String)>> = (0..8)
+            .map(|seed| {
+                let g = SeededIdGenerator::new(seed);
+                (0..8).map(|_| parts(&g.next_id())).collect()
+            })
+            .collect();
+
+        // Unmixed, seed 0's second ID repeated seed 2's first.
+        let tails: HashSet<&String> = ids.iter().flatten().map(|(_, _, tail)| tail).collect();
+        assert_eq!(tails.len(), 64);
+
+        // Unmixed, seeds 0 and 1 swapped words: one's rand_a reappeared as
+        // the end of the other's tail.
+        for (x, y) in ids.iter().zip(&ids[1..]) {
+            for ((rand_a, _, tail), (other_a, _, other_tail)) in x.iter().zip(y) {
+                assert!(
+                    !other_tail.ends_with(rand_a.as_str()),
+                    "{rand_a} / {other_tail}"
+                );
+                assert!(!tail.ends_with(other_a.as_str()), "{other_a} / {tail}");
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_ids_sort_in_order() {
+        let g = SeededIdGenerator::with_base_ms(9, 1_000);
+        let ids: Vec<String> = (0..5).map(|_| g.next_id()).collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn shared_generator_through_arc() {
+        let g: Arc<dyn IdGenerator> = Arc::new(SeededIdGenerator::new(3));
+        let g2 = Arc::clone(&g);
+        assert_ne!(g.next_id(), g2.next_id());
+        assert_v7_shape(&default_generator().next_id());
+    }
+}
@@ -11,15 +11,53 @@
 //! | Module | Purpose |
 //! |--------|---------|
 //! | [`identity`] | VCP/I token parsing (`family.safe.guide@1.2.0`) |
+//! | [`namespace`] | Namespace ownership registry, strict token parsing and approved-namespace checks |
 //! | [`csm1`] | CSM-1 compact codes and 8-line tokens |
+//! | [`goal`] | Line-4 goal taxonomy: typed terms, synonym normalization, strict/lenient validation |
+//! | [`persona`] | Persona capability matrix, custom persona registry, handoff compatibility checks |
 //! | [`personal`] | Personal state dimensions (cognitive, emotional, ...) |
 //! | [`situational`] | Situational context (time, space, company, ...) |
 //! | [`context`] | Full context wire format (situational + personal) |
+//! | [`context_schema`] | Semantic context validation: allowed categories, conflicting signals |
+//! | [`privacy`] | Redaction profiles applied before sharing a context |
+//! | [`consent`] | Consent metadata carried with shared contexts, enforced by strict parsing |
+//! | [`budget`] | Token count estimation with pluggable tokenizers |
 //! | [`transport`] | Content hashing, canonicalization, signing, bundle verification |
+//! | [`manifest_schema`] | Manifest validation against the embedded JSON Schemas, with JSON Pointer errors |
+//! | [`signer`] | Pluggable sync/async manifest signers for KMS and HSM keys |
 //! | [`trust`] | Trust anchor management for issuers and auditors |
-//! | [`hooks`] | Hook system for the adaptation pipeline (6 hook types) |
+//! | [`did`] | `did:key` / `did:web` resolution into trust anchors |
+//! | [`keys`] | Ed25519 key generation, PEM/raw/base64 import-export, encrypted key files |
+//! | [`multisig`] | Multi-party manifest signatures, threshold policies, detached files |
+//! | [`hooks`] | Hook system for the adaptation pipeline (6 hook types), with chain recording and replay |
+//! | [`process_hooks`] | Hook handlers run as subprocesses exchanging JSON over stdio, killed on timeout |
+//! | [`hook_metrics`] | Per-hook counters and timings with Prometheus export |
+//! | [`diff`] | Rule-level changelogs between constitution versions |
+//! | [`composer_session`] | Incremental constitution composition over a topic-word index |
+//! | [`condition`] | Applicability expressions that activate rules by context |
+//! | [`adaptation`] | VCP/A request/response envelopes |
+//! | [`session`] | Session lifecycle with TTLs and automatic session-hook cleanup; agent-to-agent context exchange |
+//! | [`policy`] | Organization-wide constraints applied to incoming codes and tokens |
 //! | [`revocation`] | Bundle revocation checking with SSRF protection |
+//! | [`resolution`] | Offline-first resolution policies: when a cached copy stands in for a fetch |
+//! | [`cache`] | On-disk store of hash-checked bundles with LRU eviction and offline mode |
 //! | [`error`] | Error types and verification codes |
+//! | [`ids`] | Pluggable ID generation (`UUIDv7`, seeded for tests) |
+//! | [`clock`] | Injectable time source (system, fixed, mock) for expiry, validity and decay checks |
+//! | [`events`] | Versioned event envelope for event streams |
+//! | [`explain`] | Annotated field-by-field breakdowns of any VCP artifact |
+//! | [`headers`] | `VCP-Code`, `VCP-Token` and `VCP-Context` HTTP header encoding |
+//! | [`lint`] | Best-practice checks and mechanical fixes for CSM-1 tokens and manifests |
+//! | [`content_lint`] | Structure and style checks for constitution text, with configurable rule levels |
+//! | [`scrub`] | Anonymization of tokens and contexts for bug reports |
+//! | [`conformance`] | Runner for the shared cross-SDK conformance vectors |
+//! | [`capabilities`](mod@capabilities) | Supported specs, algorithms, hooks and features |
+//! | [`protocol`] | Protocol versions, the features each enables, and version negotiation |
+//! | [`reload`] | Swappable shared configs, and file watchers that hot-reload trust config and policy (`watch` feature) |
+//! | `mcp` | Model Context Protocol tool definitions and dispatch (feature `mcp`) |
+//! | `proto` | Protobuf messages and conversions (feature `proto`) |
+//! | `fetch` | Size-limited, SSRF-checked downloads of registry-hosted bundles (feature `http`) |
+//! | `wasm_hooks` | Untrusted hook handlers run as WASM modules in a fuel- and time-limited wasmtime sandbox (feature `wasm-hooks`) |
 //!
 //! ## Quick Start
 //!
@@ -47,18 +85,60 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::must_use_candidate)]
 
+pub mod adaptation;
+pub mod budget;
+pub mod cache;
+pub mod capabilities;
+pub mod clock;
 pub mod composer;
+pub mod composer_session;
+pub mod condition;
+pub mod conformance;
+pub mod consent;
+pub mod content_lint;
 pub mod context;
+pub mod context_schema;
 pub mod csm1;
+pub mod did;
+pub mod diff;
 pub mod error;
+pub mod events;
+pub mod explain;
+#[cfg(feature = "http")]
+pub mod fetch;
+pub mod goal;
+pub mod headers;
+pub mod hook_metrics;
 pub mod hooks;
 pub mod identity;
+pub mod ids;
+pub mod keys;
+pub mod lint;
+pub mod manifest_schema;
+#[cfg(feature = "mcp")]
+pub mod mcp;
+pub mod multisig;
+pub mod namespace;
 pub mod orchestrator;
+pub mod persona;
 pub mod personal;
+pub mod policy;
+pub mod privacy;
+pub mod process_hooks;
+#[cfg(feature = "proto")]
+pub mod proto;
+pub mod protocol;
+pub mod reload;
+pub mod resolution;
 pub mod revocation;
+pub mod scrub;
+pub mod session;
+pub mod signer;
 pub mod situational;
 pub mod transport;
 pub mod trust;
+#[cfg(feature = "wasm-hooks")]
+pub mod wasm_hooks;
 
 // VCP v2.0 extensions
 pub mod extensions;
@@ -66,12 +146,13 @@ pub mod negotiation;
 pub mod types;
 
 // Re-export commonly used types at crate root.
-pub use context::{ConformanceLevel, FullContext};
+pub use capabilities::{capabilities, Capabilities};
+pub use context::{ConformanceLevel, FullContext, WireFormat};
 pub use csm1::{Csm1Code, Csm1Token, Persona, Scope};
-pub use error::{VcpError, VcpResult};
+pub use error::{ErrorCode, VcpError, VcpResult};
 pub use hooks::{
-    ChainResult, Hook, HookAction, HookExecutor, HookHandler, HookInput, HookRegistry, HookResult,
-    HookScope, HookType,
+    ChainRecord, ChainResult, ChainState, Hook, HookAction, HookExecutor, HookHandler, HookInput,
+    HookRegistry, HookResult, HookScope, HookType, SharedHookRegistry,
 };
 pub use identity::VcpToken;
 pub use personal::{PersonalDimension, PersonalState};
@@ -83,8 +164,14 @@ pub use transport::{
 pub use trust::{TrustAnchor, TrustConfig};
 
 // Orchestrator and composition engine.
-pub use composer::{Composer, CompositionMode, CompositionResult, Conflict, Constitution};
-pub use orchestrator::{Orchestrator, ReplayCache, VerificationContext};
+pub use composer::{
+    Composer, CompositionMode, CompositionResult, Conflict, ConflictResolution, Constitution,
+    MergedRule, RuleOrigin,
+};
+pub use orchestrator::{
+    DegradedMode, Orchestrator, OrchestratorSnapshot, ReplayCache, ReplayCacheStats, TrustSource,
+    VerificationContext, VerificationOutcome,
+};
 
 // VCP v2.0 type definitions.
 pub use types::{AdoptionStatus, EnforcementMode, TestimonyType, TokenType};
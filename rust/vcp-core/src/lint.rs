@@ -0,0 +1,610 @@
+//! Best-practice checks for CSM-1 tokens and bundle manifests.
+//!
+//! Parsing accepts anything well-formed. [`lint`] looks for things that
+//! parse (or nearly parse) but are probably mistakes, and [`fix`] rewrites
+//! the purely mechanical ones. Nothing here rejects an artifact; callers
+//! decide what to do with the [`Severity`] of each [`LintIssue`].
+//!
+//! | Check | Code | Applies to | Severity | Fixable |
+//! |-------|------|------------|----------|---------|
+//! | Does not parse | `invalid` | both | error | no |
+//! | Stray whitespace, `\r`, blank lines, empty list items | `whitespace` | token | warning | yes |
+//! | Constraint, flag or marker list unsorted or repeated | `unsorted_list` | token | warning | yes |
+//! | No `R:` personal-state line | `missing_personal_state` | token | warning | no |
+//! | Adherence 0 with constraints or scopes set | `disabled_with_constraints` | both | error | no |
+//! | `metadata.csm1` with no scopes, or with `G` (general) | `broad_scopes` | manifest | warning | no |
+//! | `timestamps.exp` in the past | `expired` | manifest | error | no |
+//! | `timestamps.exp` within [`EXPIRING_SOON_DAYS`] days | `expiring_soon` | manifest | warning | no |
+//! | Namespace under 3 characters or a placeholder (`TEST`, ...) | `weak_namespace` | manifest | warning | no |
+//!
+//! Manifests are never rewritten: changing a signed document would
+//! invalidate its signature.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::lint::{fix, lint, LintCode};
+//!
+//! let token = "VCP:1.0:p1\nC:family@1.0.0\nP:N:4\nG:\nX:no-violence, no-profanity\nF:\nS:\n";
+//! let report = lint(token).unwrap();
+//! assert!(report.issues.iter().any(|i| i.code == LintCode::UnsortedList));
+//! assert!(report.issues.iter().any(|i| i.code == LintCode::MissingPersonalState));
+//!
+//! let fixed = fix(token);
+//! assert!(fixed.contains("X:no-profanity,no-violence\n"));
+//! assert!(lint(&fixed).unwrap().issues.iter().all(|i| !i.fixable));
+//! ```
+
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::context_schema::Severity;
+use crate::csm1::{Csm1Code, Csm1Token, Scope};
+use crate::error::{VcpError, VcpResult};
+use crate::explain::InputKind;
+use crate::identity::TokenBinding;
+
+/// A manifest expiring within this many days gets an `expiring_soon` warning.
+pub const EXPIRING_SOON_DAYS: i64 = 7;
+
+/// Namespaces that look like they were never replaced with a real one.
+const PLACEHOLDER_NAMESPACES: &[&str] = &[
+    "DEFAULT", "DEMO", "EXAMPLE", "FOO", "NS", "TEMP", "TEST", "TMP",
+];
+
+/// Token lines holding comma-separated lists.
+const LIST_PREFIXES: &[(&str, &str)] = &[
+    ("X:", "constraints"),
+    ("F:", "flags"),
+    ("S:", "private markers"),
+];
+
+// ── Issues ──────────────────────────────────────────────────
+
+/// Machine-readable kind of a [`LintIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintCode {
+    /// The artifact does not parse.
+    Invalid,
+    /// Stray whitespace, carriage returns, blank lines or empty list items.
+    Whitespace,
+    /// A list is out of order or repeats an item.
+    UnsortedList,
+    /// A 7-line token without the optional `R:` line.
+    MissingPersonalState,
+    /// Adherence 0 disables the persona, yet constraints or scopes are set.
+    DisabledWithConstraints,
+    /// The CSM-1 code applies everywhere.
+    BroadScopes,
+    /// The manifest has expired.
+    Expired,
+    /// The manifest expires within [`EXPIRING_SOON_DAYS`].
+    ExpiringSoon,
+    /// A namespace that is too short or a placeholder.
+    WeakNamespace,
+}
+
+impl fmt::Display for LintCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Invalid => "invalid",
+            Self::Whitespace => "whitespace",
+            Self::UnsortedList => "unsorted_list",
+            Self::MissingPersonalState => "missing_personal_state",
+            Self::DisabledWithConstraints => "disabled_with_constraints",
+            Self::BroadScopes => "broad_scopes",
+            Self::Expired => "expired",
+            Self::ExpiringSoon => "expiring_soon",
+            Self::WeakNamespace => "weak_namespace",
+        })
+    }
+}
+
+/// One finding from [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintIssue {
+    /// How serious the issue is.
+    pub severity: Severity,
+    /// What kind of issue this is.
+    pub code: LintCode,
+    /// `line N` for tokens, a dotted field path for manifests.
+    pub location: String,
+    /// Human-readable description.
+    pub message: String,
+    /// Whether [`fix`] resolves it.
+    pub fixable: bool,
+}
+
+impl LintIssue {
+    /// Returns `true` for [`Severity::Error`].
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}: {}",
+            self.severity, self.code, self.location, self.message
+        )?;
+        if self.fixable {
+            f.write_str(" (fixable)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Everything [`lint`] found in one artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintReport {
+    /// What was linted: [`InputKind::Csm1Token`] or [`InputKind::Manifest`].
+    pub kind: InputKind,
+    /// Findings in the order they were checked.
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    fn new(kind: InputKind) -> Self {
+        Self {
+            kind,
+            issues: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        code: LintCode,
+        location: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.issues.push(LintIssue {
+            severity,
+            code,
+            location: location.into(),
+            message: message.into(),
+            fixable: matches!(code, LintCode::Whitespace | LintCode::UnsortedList),
+        });
+    }
+
+    /// Whether any issue is an error.
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(LintIssue::is_error)
+    }
+
+    /// Whether [`fix`] would change anything.
+    pub fn is_fixable(&self) -> bool {
+        self.issues.iter().any(|i| i.fixable)
+    }
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return writeln!(f, "{}: no issues", self.kind);
+        }
+        for issue in &self.issues {
+            writeln!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+// ── Entry points ────────────────────────────────────────────
+
+/// Check a CSM-1 token or manifest for likely mistakes.
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if `raw` is neither a CSM-1 token nor
+/// a manifest. Parse failures of either are reported as `invalid` issues.
+pub fn lint(raw: &str) -> VcpResult<LintReport> {
+    match InputKind::detect(raw) {
+        InputKind::Csm1Token => Ok(lint_token(raw)),
+        InputKind::Manifest => Ok(match serde_json::from_str(raw.trim()) {
+            Ok(manifest) => lint_manifest(&manifest, Utc::now()),
+            Err(e) => {
+                let mut report = LintReport::new(InputKind::Manifest);
+                report.push(
+                    Severity::Error,
+                    LintCode::Invalid,
+                    "manifest",
+                    format!("invalid JSON: {e}"),
+                );
+                report
+            }
+        }),
+        kind => Err(VcpError::ParseError(format!(
+            "lint supports CSM-1 tokens and manifests, not {kind}"
+        ))),
+    }
+}
+
+/// Apply the mechanical fixes: trim every token line, drop blank lines
+/// and `\r`, and sort and de-duplicate the `X:`, `F:` and `S:` lists.
+///
+/// Anything other than a CSM-1 token is returned unchanged.
+pub fn fix(raw: &str) -> String {
+    if InputKind::detect(raw) != InputKind::Csm1Token {
+        return raw.to_string();
+    }
+    let mut out: Vec<String> = Vec::new();
+    for line in raw.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match list_line(line) {
+            Some((prefix, _, items)) => out.push(format!("{prefix}{}", canonical_list(items))),
+            None => out.push(line.to_string()),
+        }
+    }
+    let mut fixed = out.join("\n");
+    if raw.ends_with('\n') {
+        fixed.push('\n');
+    }
+    fixed
+}
+
+// ── Tokens ──────────────────────────────────────────────────
+
+/// Split a list line into its prefix, list name and raw items.
+fn list_line(line: &str) -> Option<(&'static str, &'static str, &str)> {
+    LIST_PREFIXES
+        .iter()
+        .find_map(|(prefix, name)| Some((*prefix, *name, line.strip_prefix(prefix)?)))
+}
+
+fn canonical_list(items: &str) -> String {
+    let mut items: Vec<&str> = items
+        .split(',')
+        .map(str::trim)
+        .filter(|i| !i.is_empty())
+        .collect();
+    items.sort_unstable();
+    items.dedup();
+    items.join(",")
+}
+
+fn lint_token(raw: &str) -> LintReport {
+    let mut report = LintReport::new(InputKind::Csm1Token);
+    let mut content_lines = 0;
+    let mut adherence_zero = false;
+    let mut has_constraints = false;
+
+    // Split on '\n' alone so a '\r' before it shows up as whitespace.
+    let body = raw.strip_suffix('\n').unwrap_or(raw);
+    for (i, line) in body.split('\n').enumerate() {
+        let location = format!("line {}", i + 1);
+        if line.trim().is_empty() {
+            report.push(
+                Severity::Warning,
+                LintCode::Whitespace,
+                location,
+                "blank line",
+            );
+            continue;
+        }
+        content_lines += 1;
+        if line != line.trim() {
+            report.push(
+                Severity::Warning,
+                LintCode::Whitespace,
+                location.as_str(),
+                "leading or trailing whitespace",
+            );
+        }
+        let line = line.trim();
+        if let Some(adherence) = line.strip_prefix("P:").and_then(|p| p.split(':').nth(1)) {
+            adherence_zero = adherence.trim() == "0";
+        }
+        let Some((prefix, name, items)) = list_line(line) else {
+            continue;
+        };
+        has_constraints |= prefix == "X:" && !items.trim().is_empty();
+        if items.is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = items.split(',').collect();
+        if parts.iter().any(|p| p.is_empty() || *p != p.trim()) {
+            report.push(
+                Severity::Warning,
+                LintCode::Whitespace,
+                location.as_str(),
+                format!("{name} list has spaces or empty items"),
+            );
+        }
+        let written: Vec<&str> = parts
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if canonical_list(items) != written.join(",") {
+            report.push(
+                Severity::Warning,
+                LintCode::UnsortedList,
+                location,
+                format!("{name} are not sorted or contain repeats"),
+            );
+        }
+    }
+
+    if adherence_zero && has_constraints {
+        report.push(
+            Severity::Error,
+            LintCode::DisabledWithConstraints,
+            "line 3",
+            "adherence 0 disables the persona, but constraints are set on line 5",
+        );
+    }
+    if content_lines == 7 {
+        report.push(
+            Severity::Warning,
+            LintCode::MissingPersonalState,
+            "line 8",
+            "no R: line; receivers cannot adapt to the user's personal state",
+        );
+    }
+
+    match Csm1Token::parse(&fix(raw)) {
+        Err(e)
+            if adherence_zero
+                && has_constraints
+                && e.without_span() == &VcpError::InvalidAdherence(0) => {}
+        Err(e) => report.push(Severity::Error, LintCode::Invalid, "token", e.to_string()),
+        Ok(_) => {}
+    }
+    report
+}
+
+// ── Manifests ───────────────────────────────────────────────
+
+fn is_weak_namespace(ns: &str) -> bool {
+    ns.len() < 3 || PLACEHOLDER_NAMESPACES.contains(&ns.to_ascii_uppercase().as_str())
+}
+
+fn lint_manifest(manifest: &Value, now: DateTime<Utc>) -> LintReport {
+    let mut report = LintReport::new(InputKind::Manifest);
+    if !manifest.is_object() {
+        report.push(
+            Severity::Error,
+            LintCode::Invalid,
+            "manifest",
+            "manifest must be a JSON object",
+        );
+        return report;
+    }
+
+    if let Some(exp) = manifest.pointer("/timestamps/exp").and_then(Value::as_str) {
+        match DateTime::parse_from_rfc3339(exp) {
+            Ok(exp) if exp <= now => report.push(
+                Severity::Error,
+                LintCode::Expired,
+                "timestamps.exp",
+                format!("manifest expired at {}", exp.to_rfc3339()),
+            ),
+            Ok(exp) if exp <= now + Duration::days(EXPIRING_SOON_DAYS) => {
+                let left = exp.with_timezone(&Utc) - now;
+                report.push(
+                    Severity::Warning,
+                    LintCode::ExpiringSoon,
+                    "timestamps.exp",
+                    format!(
+                        "manifest expires in {}h; re-issue it before {}",
+                        left.num_hours(),
+                        exp.to_rfc3339()
+                    ),
+                );
+            }
+            Ok(_) => {}
+            Err(e) => report.push(
+                Severity::Error,
+                LintCode::Invalid,
+                "timestamps.exp",
+                format!("not an RFC 3339 timestamp: {e}"),
+            ),
+        }
+    }
+
+    if let Some(raw) = manifest.pointer("/metadata/csm1").and_then(Value::as_str) {
+        match Csm1Code::parse(raw) {
+            Ok(code) => lint_manifest_code(&mut report, &code),
+            Err(e) => report.push(
+                Severity::Error,
+                LintCode::Invalid,
+                "metadata.csm1",
+                e.to_string(),
+            ),
+        }
+    }
+
+    if let Some(raw) = manifest.pointer("/binding/token").and_then(Value::as_str) {
+        match TokenBinding::parse(raw) {
+            Ok(binding) => {
+                if let Some(ns) = binding.namespace().filter(|ns| is_weak_namespace(ns)) {
+                    report.push(
+                        Severity::Warning,
+                        LintCode::WeakNamespace,
+                        "binding.token",
+                        format!("namespace {ns} is too short or a placeholder"),
+                    );
+                }
+            }
+            Err(e) => report.push(
+                Severity::Error,
+                LintCode::Invalid,
+                "binding.token",
+                e.to_string(),
+            ),
+        }
+    }
+    report
+}
+
+fn lint_manifest_code(report: &mut LintReport, code: &Csm1Code) {
+    if !code.is_active() && !code.scopes.is_empty() {
+        report.push(
+            Severity::Error,
+            LintCode::DisabledWithConstraints,
+            "metadata.csm1",
+            "adherence 0 disables the persona, but scopes are set",
+        );
+    }
+    if code.scopes.is_empty() {
+        report.push(
+            Severity::Warning,
+            LintCode::BroadScopes,
+            "metadata.csm1",
+            "no scopes: the persona applies everywhere",
+        );
+    } else if code.scopes.contains(&Scope::General) {
+        report.push(
+            Severity::Warning,
+            LintCode::BroadScopes,
+            "metadata.csm1",
+            "G (general) scope makes the persona apply everywhere",
+        );
+    }
+    if let Some(ns) = code.namespace.as_deref().filter(|ns| is_weak_namespace(ns)) {
+        report.push(
+            Severity::Warning,
+            LintCode::WeakNamespace,
+            "metadata.csm1",
+            format!("namespace {ns} is too short or a placeholder"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN_8: &str = "\
+VCP:1.0:profile-123
+C:family-safe@1.2.0
+P:N:5
+G:protect:guided:gentle
+X:no-profanity,no-violence
+F:coppa,gdpr
+S:internal-marker
+R:\u{1F9E0}focused:4";
+
+    fn codes(report: &LintReport) -> Vec<LintCode> {
+        report.issues.iter().map(|i| i.code).collect()
+    }
+
+    fn manifest(exp: &str, csm1: &str) -> Value {
+        serde_json::json!({
+            "vcp_version": "1.0",
+            "bundle": {"id": "creed://acme/family", "version": "1.0.0", "content_hash": "sha256:ab"},
+            "timestamps": {"iat": "2026-01-01T00:00:00Z", "exp": exp},
+            "metadata": {"csm1": csm1}
+        })
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn clean_token_has_no_issues() {
+        let report = lint(TOKEN_8).unwrap();
+        assert_eq!(report.kind, InputKind::Csm1Token);
+        assert!(report.issues.is_empty(), "{report}");
+        assert_eq!(fix(TOKEN_8), TOKEN_8);
+    }
+
+    #[test]
+    fn token_whitespace_and_order_are_fixed() {
+        let messy = TOKEN_8
+            .replace("P:N:5", "P:N:5  ")
+            .replace("F:coppa,gdpr", "F:gdpr, coppa,gdpr\r")
+            .replace("S:internal-marker", "\nS:internal-marker");
+        let report = lint(&messy).unwrap();
+        assert_eq!(
+            codes(&report),
+            [
+                LintCode::Whitespace,
+                LintCode::Whitespace,
+                LintCode::Whitespace,
+                LintCode::UnsortedList,
+                LintCode::Whitespace,
+            ]
+        );
+        assert!(report.is_fixable());
+        assert!(!report.has_errors());
+
+        let fixed = fix(&messy);
+        assert_eq!(fixed, TOKEN_8);
+        assert!(lint(&fixed).unwrap().issues.is_empty());
+    }
+
+    #[test]
+    fn token_without_personal_state_is_flagged() {
+        let seven = TOKEN_8.rsplit_once('\n').unwrap().0;
+        let report = lint(seven).unwrap();
+        assert_eq!(codes(&report), [LintCode::MissingPersonalState]);
+        assert!(!report.is_fixable());
+    }
+
+    #[test]
+    fn disabled_token_with_constraints_is_an_error() {
+        let report = lint(&TOKEN_8.replace("P:N:5", "P:N:0")).unwrap();
+        assert_eq!(codes(&report), [LintCode::DisabledWithConstraints]);
+        assert!(report.has_errors());
+
+        // Without constraints the parse error stands on its own.
+        let report = lint(
+            &TOKEN_8
+                .replace("P:N:5", "P:N:0")
+                .replace("X:no-profanity,no-violence", "X:"),
+        )
+        .unwrap();
+        assert_eq!(codes(&report), [LintCode::Invalid]);
+    }
+
+    #[test]
+    fn manifest_expiry() {
+        let report = lint_manifest(&manifest("2026-02-01T00:00:00Z", "N5+F:ACME"), now());
+        assert_eq!(codes(&report), [LintCode::Expired]);
+        assert!(report.has_errors());
+
+        let report = lint_manifest(&manifest("2026-03-04T00:00:00Z", "N5+F:ACME"), now());
+        assert_eq!(codes(&report), [LintCode::ExpiringSoon]);
+        assert!(report.issues[0].message.contains("72h"));
+
+        let report = lint_manifest(&manifest("2027-01-01T00:00:00Z", "N5+F:ACME"), now());
+        assert!(report.issues.is_empty(), "{report}");
+    }
+
+    #[test]
+    fn manifest_code_checks() {
+        let exp = "2027-01-01T00:00:00Z";
+        let check = |csm1| codes(&lint_manifest(&manifest(exp, csm1), now()));
+        assert_eq!(check("N5"), [LintCode::BroadScopes]);
+        assert_eq!(check("N5+F+G:ACME"), [LintCode::BroadScopes]);
+        assert_eq!(check("N0+F:ACME"), [LintCode::DisabledWithConstraints]);
+        assert_eq!(check("N5+F:TEST"), [LintCode::WeakNamespace]);
+        assert_eq!(check("N5+F:AB"), [LintCode::WeakNamespace]);
+        assert_eq!(check("Q5"), [LintCode::Invalid]);
+
+        let mut bound = manifest(exp, "N5+F:ACME");
+        bound["binding"] = serde_json::json!({"token": "family.safe.guide:DEMO"});
+        assert_eq!(
+            codes(&lint_manifest(&bound, now())),
+            [LintCode::WeakNamespace]
+        );
+    }
+
+    #[test]
+    fn manifests_are_not_rewritten_and_other_kinds_are_rejected() {
+        let raw = "{ \"bundle\": {} }  ";
+        assert_eq!(fix(raw), raw);
+        assert_eq!(codes(&lint("{ not json").unwrap()), [LintCode::Invalid]);
+        assert!(lint("N5+F").is_err());
+    }
+}
@@ -0,0 +1,711 @@
+//! Manifest validation against the protocol JSON Schemas.
+//!
+//! The v1 and v2 manifest schemas from `schemas/` are embedded at build
+//! time and compiled once into a validator covering the keywords they use:
+//! `type`, `const`, `enum`, `required`, `properties`,
+//! `additionalProperties`, `items`, `oneOf`, `pattern`, `format`
+//! (`date-time`, `uuid`, `uri`), `minimum` / `maximum`, `maxLength` and
+//! `minItems` / `maxItems`.
+//!
+//! Every violation carries a JSON Pointer ([RFC 6901]) to the offending
+//! value, so a report reads `/timestamps/exp: not a valid date-time`
+//! rather than a bare [`InvalidSchema`](crate::error::VerificationCode::InvalidSchema).
+//!
+//! [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::manifest_schema::validate_manifest;
+//!
+//! let manifest = serde_json::json!({"vcp_version": "1.0", "bundle": {"id": 7}});
+//! let violations = validate_manifest(&manifest);
+//!
+//! assert!(violations.iter().any(|v| v.pointer == "/bundle/id" && v.keyword == "type"));
+//! assert!(violations.iter().any(|v| v.pointer.is_empty() && v.message.contains("issuer")));
+//! ```
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use chrono::DateTime;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{VcpError, VcpResult};
+
+const V1_SCHEMA: &str = include_str!("../../../schemas/vcp-manifest-v1.schema.json");
+const V2_SCHEMA: &str = include_str!("../../../schemas/vcp-manifest-v2.schema.json");
+
+static V1: LazyLock<ManifestSchema> = LazyLock::new(|| {
+    ManifestSchema::compile(SchemaVersion::V1, V1_SCHEMA).expect("embedded v1 schema compiles")
+});
+static V2: LazyLock<ManifestSchema> = LazyLock::new(|| {
+    ManifestSchema::compile(SchemaVersion::V2, V2_SCHEMA).expect("embedded v2 schema compiles")
+});
+
+// ── Violations ──────────────────────────────────────────────
+
+/// One way in which a manifest fails its schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON Pointer to the offending value; empty for the document root.
+    pub pointer: String,
+    /// The schema keyword that failed (`required`, `pattern`, ...).
+    pub keyword: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl SchemaViolation {
+    pub(crate) fn new(pointer: &str, keyword: &str, message: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.to_string(),
+            keyword: keyword.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "(root)"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{pointer}: {}", self.message)
+    }
+}
+
+// ── Schema versions ─────────────────────────────────────────
+
+/// Which manifest schema applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SchemaVersion {
+    /// `vcp-manifest-v1.schema.json` (`vcp_version: "1.0"`).
+    V1,
+    /// `vcp-manifest-v2.schema.json` (`vcp_version: "2.0"`).
+    V2,
+}
+
+impl SchemaVersion {
+    /// The schema for a manifest's `vcp_version`, if it names a known one.
+    pub fn from_vcp_version(version: &str) -> Option<Self> {
+        match version {
+            "1.0" => Some(Self::V1),
+            "2.0" => Some(Self::V2),
+            _ => None,
+        }
+    }
+}
+
+// ── Compiled schema ─────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonType {
+    fn parse(name: &str) -> VcpResult<Self> {
+        Ok(match name {
+            "null" => Self::Null,
+            "boolean" => Self::Boolean,
+            "integer" => Self::Integer,
+            "number" => Self::Number,
+            "string" => Self::String,
+            "array" => Self::Array,
+            "object" => Self::Object,
+            other => {
+                return Err(VcpError::ParseError(format!(
+                    "unknown schema type: {other}"
+                )));
+            }
+        })
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Self::Null => value.is_null(),
+            Self::Boolean => value.is_boolean(),
+            Self::Integer => value.is_i64() || value.is_u64(),
+            Self::Number => value.is_number(),
+            Self::String => value.is_string(),
+            Self::Array => value.is_array(),
+            Self::Object => value.is_object(),
+        }
+    }
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    DateTime,
+    Uuid,
+    Uri,
+}
+
+impl Format {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "date-time" => Some(Self::DateTime),
+            "uuid" => Some(Self::Uuid),
+            "uri" => Some(Self::Uri),
+            // Unknown formats are annotations only.
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::DateTime => "date-time",
+            Self::Uuid => "uuid",
+            Self::Uri => "uri",
+        }
+    }
+
+    fn matches(self, s: &str) -> bool {
+        match self {
+            Self::DateTime => DateTime::parse_from_rfc3339(s).is_ok(),
+            Self::Uuid => {
+                let groups: Vec<&str> = s.split('-').collect();
+                groups.iter().map(|g| g.len()).eq([8, 4, 4, 4, 12])
+                    && groups
+                        .iter()
+                        .all(|g| g.bytes().all(|b| b.is_ascii_hexdigit()))
+            }
+            Self::Uri => s.split_once(':').is_some_and(|(scheme, rest)| {
+                !rest.is_empty()
+                    && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+            }),
+        }
+    }
+}
+
+/// One compiled subschema.
+#[derive(Debug, Default)]
+struct Node {
+    types: Vec<JsonType>,
+    constant: Option<Value>,
+    enumeration: Option<Vec<Value>>,
+    required: Vec<String>,
+    properties: Vec<(String, Node)>,
+    deny_additional: bool,
+    items: Option<Box<Node>>,
+    one_of: Vec<Node>,
+    pattern: Option<Regex>,
+    format: Option<Format>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    max_length: Option<usize>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+}
+
+fn as_usize(value: Option<&Value>) -> Option<usize> {
+    value
+        .and_then(Value::as_u64)
+        .and_then(|n| usize::try_from(n).ok())
+}
+
+impl Node {
+    fn compile(schema: &Value) -> VcpResult<Self> {
+        let Some(obj) = schema.as_object() else {
+            return Err(VcpError::ParseError("subschema must be an object".into()));
+        };
+        let mut node = Node {
+            constant: obj.get("const").cloned(),
+            enumeration: obj.get("enum").and_then(Value::as_array).cloned(),
+            deny_additional: obj.get("additionalProperties") == Some(&Value::Bool(false)),
+            format: obj
+                .get("format")
+                .and_then(Value::as_str)
+                .and_then(Format::parse),
+            minimum: obj.get("minimum").and_then(Value::as_f64),
+            maximum: obj.get("maximum").and_then(Value::as_f64),
+            max_length: as_usize(obj.get("maxLength")),
+            min_items: as_usize(obj.get("minItems")),
+            max_items: as_usize(obj.get("maxItems")),
+            ..Node::default()
+        };
+        match obj.get("type") {
+            Some(Value::String(t)) => node.types.push(JsonType::parse(t)?),
+            Some(Value::Array(ts)) => {
+                for t in ts.iter().filter_map(Value::as_str) {
+                    node.types.push(JsonType::parse(t)?);
+                }
+            }
+            _ => {}
+        }
+        if let Some(required) = obj.get("required").and_then(Value::as_array) {
+            node.required = required
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(props) = obj.get("properties").and_then(Value::as_object) {
+            for (name, sub) in props {
+                node.properties.push((name.clone(), Node::compile(sub)?));
+            }
+        }
+        if let Some(items) = obj.get("items") {
+            node.items = Some(Box::new(Node::compile(items)?));
+        }
+        if let Some(variants) = obj.get("oneOf").and_then(Value::as_array) {
+            node.one_of = variants
+                .iter()
+                .map(Node::compile)
+                .collect::<VcpResult<_>>()?;
+        }
+        if let Some(pattern) = obj.get("pattern").and_then(Value::as_str) {
+            let re = Regex::new(pattern).map_err(|e| {
+                VcpError::ParseError(format!("invalid schema pattern {pattern}: {e}"))
+            })?;
+            node.pattern = Some(re);
+        }
+        Ok(node)
+    }
+
+    fn validate(&self, value: &Value, pointer: &str, out: &mut Vec<SchemaViolation>) {
+        if !self.types.is_empty() && !self.types.iter().any(|t| t.matches(value)) {
+            let expected: Vec<String> = self.types.iter().map(ToString::to_string).collect();
+            out.push(SchemaViolation::new(
+                pointer,
+                "type",
+                format!(
+                    "expected {}, got {}",
+                    expected.join(" or "),
+                    type_name(value)
+                ),
+            ));
+            // Nothing below applies to a value of the wrong type.
+            return;
+        }
+        if let Some(constant) = &self.constant {
+            if value != constant {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "const",
+                    format!("must be {constant}"),
+                ));
+            }
+        }
+        if let Some(allowed) = &self.enumeration {
+            if !allowed.contains(value) {
+                let names: Vec<String> = allowed.iter().map(ToString::to_string).collect();
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "enum",
+                    format!("{value} is not one of {}", names.join(", ")),
+                ));
+            }
+        }
+        if !self.one_of.is_empty() {
+            let matching = self
+                .one_of
+                .iter()
+                .filter(|variant| {
+                    let mut scratch = Vec::new();
+                    variant.validate(value, pointer, &mut scratch);
+                    scratch.is_empty()
+                })
+                .count();
+            if matching != 1 {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "oneOf",
+                    format!(
+                        "matches {matching} of {} alternatives, expected exactly 1",
+                        self.one_of.len()
+                    ),
+                ));
+            }
+        }
+        match value {
+            Value::Object(map) => self.validate_object(map, pointer, out),
+            Value::Array(items) => self.validate_array(items, pointer, out),
+            Value::String(s) => self.validate_string(s, pointer, out),
+            Value::Number(n) => self.validate_number(n.as_f64().unwrap_or(f64::NAN), pointer, out),
+            Value::Null | Value::Bool(_) => {}
+        }
+    }
+
+    fn validate_object(
+        &self,
+        map: &Map<String, Value>,
+        pointer: &str,
+        out: &mut Vec<SchemaViolation>,
+    ) {
+        for name in &self.required {
+            if !map.contains_key(name) {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "required",
+                    format!("missing required property {name}"),
+                ));
+            }
+        }
+        for (name, value) in map {
+            let child = format!("{pointer}/{}", escape_pointer(name));
+            match self.properties.iter().find(|(n, _)| n == name) {
+                Some((_, node)) => node.validate(value, &child, out),
+                None if self.deny_additional => {
+                    out.push(SchemaViolation::new(
+                        &child,
+                        "additionalProperties",
+                        format!("unknown property {name}"),
+                    ));
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn validate_array(&self, items: &[Value], pointer: &str, out: &mut Vec<SchemaViolation>) {
+        if let Some(min) = self.min_items {
+            if items.len() < min {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "minItems",
+                    format!("has {} items, expected at least {min}", items.len()),
+                ));
+            }
+        }
+        if let Some(max) = self.max_items {
+            if items.len() > max {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "maxItems",
+                    format!("has {} items, expected at most {max}", items.len()),
+                ));
+            }
+        }
+        if let Some(node) = &self.items {
+            for (i, item) in items.iter().enumerate() {
+                node.validate(item, &format!("{pointer}/{i}"), out);
+            }
+        }
+    }
+
+    fn validate_string(&self, s: &str, pointer: &str, out: &mut Vec<SchemaViolation>) {
+        if let Some(max) = self.max_length {
+            let len = s.chars().count();
+            if len > max {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "maxLength",
+                    format!("is {len} characters, expected at most {max}"),
+                ));
+            }
+        }
+        if let Some(re) = &self.pattern {
+            if !re.is_match(s) {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "pattern",
+                    format!("{s:?} does not match {}", re.as_str()),
+                ));
+            }
+        }
+        if let Some(format) = self.format {
+            if !format.matches(s) {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "format",
+                    format!("{s:?} is not a valid {}", format.name()),
+                ));
+            }
+        }
+    }
+
+    fn validate_number(&self, n: f64, pointer: &str, out: &mut Vec<SchemaViolation>) {
+        if let Some(min) = self.minimum {
+            if n < min {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "minimum",
+                    format!("{n} is below {min}"),
+                ));
+            }
+        }
+        if let Some(max) = self.maximum {
+            if n > max {
+                out.push(SchemaViolation::new(
+                    pointer,
+                    "maximum",
+                    format!("{n} is above {max}"),
+                ));
+            }
+        }
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escape a property name as a JSON Pointer reference token.
+fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// A compiled manifest schema.
+#[derive(Debug)]
+pub struct ManifestSchema {
+    version: SchemaVersion,
+    root: Node,
+}
+
+impl ManifestSchema {
+    /// Compile a schema from its JSON text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if `json` is not valid JSON, uses an
+    /// unknown `type`, or contains a `pattern` that does not compile.
+    pub fn compile(version: SchemaVersion, json: &str) -> VcpResult<Self> {
+        let schema: Value = serde_json::from_str(json)
+            .map_err(|e| VcpError::ParseError(format!("invalid schema JSON: {e}")))?;
+        Ok(Self {
+            version,
+            root: Node::compile(&schema)?,
+        })
+    }
+
+    /// The embedded schema for `version`.
+    pub fn embedded(version: SchemaVersion) -> &'static Self {
+        match version {
+            SchemaVersion::V1 => &V1,
+            SchemaVersion::V2 => &V2,
+        }
+    }
+
+    /// Which version this schema describes.
+    pub fn version(&self) -> SchemaVersion {
+        self.version
+    }
+
+    /// Validate `manifest`, returning every violation found.
+    pub fn validate(&self, manifest: &Value) -> Vec<SchemaViolation> {
+        let mut out = Vec::new();
+        self.root.validate(manifest, "", &mut out);
+        out
+    }
+}
+
+/// Validate `manifest` against the embedded schema its `vcp_version`
+/// selects.
+///
+/// A missing or unknown `vcp_version` is itself reported as a violation at
+/// `/vcp_version`; the rest of the manifest is then checked against the v1
+/// schema so the report is still useful.
+pub fn validate_manifest(manifest: &Value) -> Vec<SchemaViolation> {
+    let declared = manifest.get("vcp_version").and_then(Value::as_str);
+    let Some(version) = declared.and_then(SchemaVersion::from_vcp_version) else {
+        let mut out = vec![match declared {
+            Some(v) => SchemaViolation::new(
+                "/vcp_version",
+                "const",
+                format!("unsupported vcp_version {v:?}, expected \"1.0\" or \"2.0\""),
+            ),
+            None if manifest.get("vcp_version").is_some() => {
+                SchemaViolation::new("/vcp_version", "type", "expected string")
+            }
+            None => SchemaViolation::new("", "required", "missing required property vcp_version"),
+        }];
+        out.extend(
+            ManifestSchema::embedded(SchemaVersion::V1)
+                .validate(manifest)
+                .into_iter()
+                .filter(|v| v.pointer != "/vcp_version" && !v.message.ends_with(" vcp_version")),
+        );
+        return out;
+    };
+    ManifestSchema::embedded(version).validate(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn valid_v1() -> Value {
+        json!({
+            "vcp_version": "1.0",
+            "bundle": {
+                "id": "creed://creed.space/family.safe.guide",
+                "version": "1.2.0",
+                "content_hash": format!("sha256:{}", "a".repeat(64)),
+            },
+            "issuer": {"id": "creed.space", "public_key": "ed25519:AAAA", "key_id": "creed-2026"},
+            "timestamps": {
+                "iat": "2026-01-01T00:00:00Z",
+                "nbf": "2026-01-01T00:00:00Z",
+                "exp": "2026-03-01T00:00:00Z",
+                "jti": "550e8400-e29b-41d4-a716-446655440000",
+            },
+            "budget": {"token_count": 1000, "tokenizer": "cl100k_base"},
+            "safety_attestation": {
+                "auditor": "auditor.example",
+                "auditor_key_id": "aud-1",
+                "reviewed_at": "2026-01-01T00:00:00Z",
+                "attestation_type": "full-audit",
+                "signature": "base64:AAAA",
+            },
+            "signature": {
+                "algorithm": "ed25519",
+                "value": "base64:AAAA",
+                "signed_fields": ["bundle", "issuer", "timestamps", "budget", "safety_attestation", "vcp_version"],
+            },
+        })
+    }
+
+    fn pointers(violations: &[SchemaViolation]) -> Vec<&str> {
+        violations.iter().map(|v| v.pointer.as_str()).collect()
+    }
+
+    #[test]
+    fn embedded_schemas_compile() {
+        assert_eq!(
+            ManifestSchema::embedded(SchemaVersion::V1).version(),
+            SchemaVersion::V1
+        );
+        assert_eq!(
+            ManifestSchema::embedded(SchemaVersion::V2).version(),
+            SchemaVersion::V2
+        );
+    }
+
+    #[test]
+    fn valid_manifest_has_no_violations() {
+        let violations = validate_manifest(&valid_v1());
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn violations_point_at_the_offending_value() {
+        let mut manifest = valid_v1();
+        manifest["timestamps"]["exp"] = json!("next tuesday");
+        manifest["budget"]["token_count"] = json!(0);
+        manifest["signature"]["signed_fields"][1] = json!("nonsense");
+        manifest["bundle"]["extra"] = json!(true);
+
+        let violations = validate_manifest(&manifest);
+        let mut found = pointers(&violations);
+        found.sort_unstable();
+        assert_eq!(
+            found,
+            [
+                "/budget/token_count",
+                "/bundle/extra",
+                "/signature/signed_fields/1",
+                "/timestamps/exp",
+            ]
+        );
+        let exp = violations
+            .iter()
+            .find(|v| v.pointer == "/timestamps/exp")
+            .unwrap();
+        assert_eq!(exp.keyword, "format");
+        assert_eq!(
+            exp.to_string(),
+            "/timestamps/exp: \"next tuesday\" is not a valid date-time"
+        );
+    }
+
+    #[test]
+    fn issuers_and_auditors_may_be_dids() {
+        let mut manifest = valid_v1();
+        manifest["issuer"]["id"] = json!("did:web:creed.space");
+        manifest["issuer"]["key_id"] = json!("did:web:creed.space#key-1");
+        manifest["safety_attestation"]["auditor"] =
+            json!("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp");
+        manifest["safety_attestation"]["auditor_key_id"] = json!("#z6MkiTBz1ymuepAQ4HEH");
+        let violations = validate_manifest(&manifest);
+        assert!(violations.is_empty(), "{violations:?}");
+
+        manifest["issuer"]["id"] = json!("did:example:creed");
+        manifest["issuer"]["key_id"] = json!("did:web:creed.space");
+        let violations = validate_manifest(&manifest);
+        let mut found = pointers(&violations);
+        found.sort_unstable();
+        assert_eq!(found, ["/issuer/id", "/issuer/key_id"]);
+    }
+
+    #[test]
+    fn missing_sections_are_reported_at_their_parent() {
+        let mut manifest = valid_v1();
+        manifest.as_object_mut().unwrap().remove("signature");
+        manifest["issuer"].as_object_mut().unwrap().remove("key_id");
+
+        let violations = validate_manifest(&manifest);
+        assert!(violations
+            .iter()
+            .any(|v| v.pointer.is_empty() && v.message.ends_with("signature")));
+        assert!(violations
+            .iter()
+            .any(|v| v.pointer == "/issuer" && v.message.ends_with("key_id")));
+    }
+
+    #[test]
+    fn unknown_version_is_reported_and_rest_still_checked() {
+        let mut manifest = valid_v1();
+        manifest["vcp_version"] = json!("9.9");
+        manifest["budget"]["tokenizer"] = json!("sentencepiece");
+
+        let violations = validate_manifest(&manifest);
+        assert_eq!(pointers(&violations), ["/vcp_version", "/budget/tokenizer"]);
+    }
+
+    #[test]
+    fn v2_selected_by_vcp_version() {
+        let mut manifest = valid_v1();
+        manifest["vcp_version"] = json!("2.0");
+        manifest["token_type"] = json!("constitution");
+        let violations = validate_manifest(&manifest);
+        assert!(
+            !pointers(&violations).contains(&"/token_type"),
+            "{violations:?}"
+        );
+    }
+
+    #[test]
+    fn pointer_tokens_are_escaped() {
+        let mut manifest = valid_v1();
+        manifest["bundle"]["a/b~c"] = json!(1);
+        let violations = validate_manifest(&manifest);
+        assert_eq!(pointers(&violations), ["/bundle/a~1b~0c"]);
+    }
+}
@@ -0,0 +1,480 @@
+//! Model Context Protocol tool definitions (feature `mcp`).
+//!
+//! Exposes vcp-core operations as MCP tools. [`tool_definitions`] returns
+//! the entries for a `tools/list` response; [`dispatch`] takes the params
+//! of a `tools/call` request and runs the matching operation.
+//!
+//! | Tool | Operation |
+//! |------|-----------|
+//! | `vcp_parse_token` | [`VcpToken::parse`] |
+//! | `vcp_parse_csm1` | [`Csm1Code::parse`] |
+//! | `vcp_verify_bundle` | [`verify_bundle`] |
+//! | `vcp_compose` | [`Composer::compose`] |
+//! | `vcp_encode_context` | [`FullContext::to_wire`] |
+//!
+//! Following MCP conventions, a failing VCP operation is reported as a
+//! successful call whose result has `isError: true`; only a malformed
+//! call or an unknown tool name is returned as `Err`.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "mcp")] {
+//! use serde_json::json;
+//! use vcp_core::mcp::{dispatch, tool_definitions};
+//!
+//! assert!(tool_definitions().iter().any(|t| t.name == "vcp_parse_token"));
+//!
+//! let result = dispatch(&json!({
+//!     "name": "vcp_parse_token",
+//!     "arguments": { "token": "family.safe.guide@1.2.0" }
+//! }))
+//! .unwrap();
+//! assert!(!result.is_error);
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::composer::{Composer, CompositionMode, Constitution};
+use crate::context::FullContext;
+use crate::csm1::Csm1Code;
+use crate::error::{VcpError, VcpResult};
+use crate::identity::VcpToken;
+use crate::transport::verify_bundle;
+
+// ── Tool definitions ─────────────────────────────────────────
+
+/// An MCP tool definition as listed by `tools/list`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpTool {
+    /// Tool name.
+    pub name: String,
+    /// Human-readable description shown to the model.
+    pub description: String,
+    /// JSON Schema for the tool's `arguments`.
+    #[serde(rename = "inputSchema")]
+    pub input_schema: Value,
+}
+
+impl McpTool {
+    fn new(name: &str, description: &str, input_schema: Value) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            input_schema,
+        }
+    }
+}
+
+/// Definitions for every VCP tool.
+pub fn tool_definitions() -> Vec<McpTool> {
+    vec![
+        McpTool::new(
+            "vcp_parse_token",
+            "Parse a VCP/I identity token (e.g. family.safe.guide@1.2.0) into \
+             domain, approach, role, version and namespace.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "token": { "type": "string", "description": "VCP/I token" },
+                    "registry": {
+                        "type": "string",
+                        "description": "Registry host for the creed:// URI (default creed.space)"
+                    }
+                },
+                "required": ["token"]
+            }),
+        ),
+        McpTool::new(
+            "vcp_parse_csm1",
+            "Parse a CSM-1 compact code (e.g. N5+F+E) into persona, adherence \
+             level, scopes, namespace and version.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "code": { "type": "string", "description": "CSM-1 code" }
+                },
+                "required": ["code"]
+            }),
+        ),
+        McpTool::new(
+            "vcp_verify_bundle",
+            "Check that constitution content matches the content hash in a \
+             VCP bundle manifest.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "manifest": {
+                        "type": ["object", "string"],
+                        "description": "Manifest as a JSON object or JSON string"
+                    },
+                    "content": { "type": "string", "description": "Constitution text" }
+                },
+                "required": ["manifest", "content"]
+            }),
+        ),
+        McpTool::new(
+            "vcp_compose",
+            "Compose several constitutions into one rule set, reporting conflicts.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "constitutions": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "string" },
+                                "rules": { "type": "array", "items": { "type": "string" } },
+                                "priority": { "type": "integer" }
+                            },
+                            "required": ["id", "rules"]
+                        }
+                    },
+                    "mode": {
+                        "type": "string",
+                        "enum": ["base", "extend", "override", "strict"],
+                        "default": "extend"
+                    }
+                },
+                "required": ["constitutions"]
+            }),
+        ),
+        McpTool::new(
+            "vcp_encode_context",
+            "Encode situational and personal context into the VCP/A wire format.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "situational": {
+                        "type": "object",
+                        "description": "Dimension name to list of values (e.g. {\"time\": [\"🌅\"]})",
+                        "additionalProperties": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "personal": {
+                        "type": "object",
+                        "description": "Dimension name to {value, intensity, extended?}",
+                        "additionalProperties": {
+                            "type": "object",
+                            "properties": {
+                                "value": { "type": "string" },
+                                "intensity": { "type": "integer", "minimum": 1, "maximum": 5 },
+                                "extended": { "type": "string" }
+                            },
+                            "required": ["value", "intensity"]
+                        }
+                    }
+                }
+            }),
+        ),
+    ]
+}
+
+// ── Dispatch ─────────────────────────────────────────────────
+
+/// A single content item in a tool result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum McpContent {
+    /// Plain text (VCP tools return JSON-encoded text).
+    Text {
+        /// The text payload.
+        text: String,
+    },
+}
+
+/// The result of a `tools/call` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpToolResult {
+    /// Result content.
+    pub content: Vec<McpContent>,
+    /// Whether the operation failed.
+    #[serde(rename = "isError")]
+    pub is_error: bool,
+}
+
+impl McpToolResult {
+    fn ok(value: &Value) -> Self {
+        Self {
+            content: vec![McpContent::Text {
+                text: value.to_string(),
+            }],
+            is_error: false,
+        }
+    }
+
+    fn error(err: &VcpError) -> Self {
+        Self {
+            content: vec![McpContent::Text {
+                text: json!({ "error": err.to_string() }).to_string(),
+            }],
+            is_error: true,
+        }
+    }
+}
+
+/// Run a `tools/call` request (`{"name": ..., "arguments": {...}}`).
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if `call` has no `name`, `arguments`
+/// is not an object, or the tool name is unknown. Failures of the VCP
+/// operation itself are returned as `Ok` with `is_error` set.
+pub fn dispatch(call: &Value) -> VcpResult<McpToolResult> {
+    let name = call
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| VcpError::ParseError("tool call missing name".into()))?;
+    let empty = json!({});
+    let args = call.get("arguments").unwrap_or(&empty);
+    if !args.is_object() {
+        return Err(VcpError::ParseError(
+            "tool arguments must be an object".into(),
+        ));
+    }
+
+    let outcome = match name {
+        "vcp_parse_token" => parse_token(args),
+        "vcp_parse_csm1" => parse_csm1(args),
+        "vcp_verify_bundle" => verify(args),
+        "vcp_compose" => compose(args),
+        "vcp_encode_context" => encode_context(args),
+        other => return Err(VcpError::ParseError(format!("unknown tool: {other}"))),
+    };
+
+    Ok(match outcome {
+        Ok(value) => McpToolResult::ok(&value),
+        Err(err) => McpToolResult::error(&err),
+    })
+}
+
+// ── Tool handlers ────────────────────────────────────────────
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> VcpResult<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| VcpError::ParseError(format!("missing string argument: {key}")))
+}
+
+fn parse_token(args: &Value) -> VcpResult<Value> {
+    let token = VcpToken::parse(str_arg(args, "token")?)?;
+    let registry = args
+        .get("registry")
+        .and_then(Value::as_str)
+        .unwrap_or("creed.space");
+    Ok(json!({
+        "canonical": token.canonical(),
+        "domain": token.domain(),
+        "approach": token.approach(),
+        "role": token.role(),
+        "path": token.path(),
+        "version": token.version.as_ref().map(ToString::to_string),
+        "namespace": token.namespace,
+        "uri": token.to_uri(registry),
+    }))
+}
+
+fn parse_csm1(args: &Value) -> VcpResult<Value> {
+    let code = Csm1Code::parse(str_arg(args, "code")?)?;
+    Ok(json!({
+        "persona": code.persona,
+        "persona_description": code.persona.description(),
+        "adherence_level": code.adherence_level,
+        "scopes": code.scopes,
+        "namespace": code.namespace,
+        "version": code.version,
+        "encoded": code.encode(),
+    }))
+}
+
+fn verify(args: &Value) -> VcpResult<Value> {
+    let manifest = match args.get("manifest") {
+        Some(Value::String(s)) => s.clone(),
+        Some(v @ Value::Object(_)) => v.to_string(),
+        _ => return Err(VcpError::ParseError("missing argument: manifest".into())),
+    };
+    let result = verify_bundle(&manifest, str_arg(args, "content")?)?;
+    Ok(json!({
+        "valid": result.is_valid(),
+        "code": result.code,
+        "message": result.message,
+    }))
+}
+
+fn compose(args: &Value) -> VcpResult<Value> {
+    let mode = match args.get("mode").and_then(Value::as_str).unwrap_or("extend") {
+        "base" => CompositionMode::Base,
+        "extend" => CompositionMode::Extend,
+        "override" => CompositionMode::Override,
+        "strict" => CompositionMode::Strict,
+        other => {
+            return Err(VcpError::ParseError(format!(
+                "unknown composition mode: {other}"
+            )))
+        }
+    };
+
+    let constitutions = args
+        .get("constitutions")
+        .and_then(Value::as_array)
+        .ok_or_else(|| VcpError::ParseError("missing argument: constitutions".into()))?
+        .iter()
+        .map(|c| {
+            let id = str_arg(c, "id")?;
+            let rules = c
+                .get("rules")
+                .and_then(Value::as_array)
+                .ok_or_else(|| VcpError::ParseError(format!("constitution {id} has no rules")))?
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect();
+            let priority = c
+                .get("priority")
+                .and_then(Value::as_i64)
+                .and_then(|p| i32::try_from(p).ok())
+                .unwrap_or(0);
+            Ok(Constitution::new(id, rules, priority))
+        })
+        .collect::<VcpResult<Vec<_>>>()?;
+
+    let result = Composer::new()
+        .compose(&constitutions, mode)
+        .map_err(|e| VcpError::ParseError(e.to_string()))?;
+    Ok(serde_json::to_value(result)?)
+}
+
+fn encode_context(args: &Value) -> VcpResult<Value> {
+    let ctx = FullContext::new(
+        args.get("situational")
+            .map(|v| serde_json::from_value(v.clone()))
+            .transpose()?
+            .unwrap_or_default(),
+        args.get("personal")
+            .map(|v| serde_json::from_value(v.clone()))
+            .transpose()?
+            .unwrap_or_default(),
+    );
+    Ok(json!({
+        "wire": ctx.to_wire(),
+        "conformance_level": ctx.conformance_level().label(),
+    }))
+}
+
+// ── Tests ────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::compute_content_hash;
+
+    fn call(name: &str, arguments: &Value) -> McpToolResult {
+        dispatch(&json!({ "name": name, "arguments": arguments })).unwrap()
+    }
+
+    fn payload(result: &McpToolResult) -> Value {
+        let McpContent::Text { text } = &result.content[0];
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn definitions_serialize_with_mcp_field_names() {
+        let tools = tool_definitions();
+        assert_eq!(tools.len(), 5);
+        let json = serde_json::to_value(&tools[0]).unwrap();
+        assert!(json.get("inputSchema").is_some());
+        for tool in &tools {
+            assert_eq!(tool.input_schema["type"], "object");
+        }
+    }
+
+    #[test]
+    fn parse_token_tool() {
+        let result = call(
+            "vcp_parse_token",
+            &json!({ "token": "family.safe.guide@1.2.0" }),
+        );
+        assert!(!result.is_error);
+        let out = payload(&result);
+        assert_eq!(out["domain"], "family");
+        assert_eq!(out["version"], "1.2.0");
+        assert_eq!(out["uri"], "creed://creed.space/family.safe.guide@1.2.0");
+    }
+
+    #[test]
+    fn invalid_token_is_tool_error() {
+        let result = call("vcp_parse_token", &json!({ "token": "bad" }));
+        assert!(result.is_error);
+        assert!(payload(&result)["error"].is_string());
+    }
+
+    #[test]
+    fn parse_csm1_tool() {
+        let out = payload(&call("vcp_parse_csm1", &json!({ "code": "N5+F+E" })));
+        assert_eq!(out["adherence_level"], 5);
+        assert_eq!(out["encoded"], "N5+F+E");
+    }
+
+    #[test]
+    fn verify_bundle_tool() {
+        let content = "Be kind.";
+        let manifest = json!({
+            "bundle": { "content_hash": compute_content_hash(content).unwrap() }
+        });
+        let out = payload(&call(
+            "vcp_verify_bundle",
+            &json!({ "manifest": manifest, "content": content }),
+        ));
+        assert_eq!(out["valid"], true);
+
+        let out = payload(&call(
+            "vcp_verify_bundle",
+            &json!({ "manifest": manifest.to_string(), "content": "Be rude." }),
+        ));
+        assert_eq!(out["valid"], false);
+        assert_eq!(out["code"], "hash_mismatch");
+    }
+
+    #[test]
+    fn compose_tool() {
+        let out = payload(&call(
+            "vcp_compose",
+            &json!({
+                "constitutions": [
+                    { "id": "a", "rules": ["Always be honest."] },
+                    { "id": "b", "rules": ["Respect privacy."], "priority": 1 }
+                ]
+            }),
+        ));
+        assert_eq!(out["merged_rules"].as_array().unwrap().len(), 2);
+        assert_eq!(out["mode_used"], "extend");
+
+        let result = call(
+            "vcp_compose",
+            &json!({ "constitutions": [], "mode": "nope" }),
+        );
+        assert!(result.is_error);
+    }
+
+    #[test]
+    fn encode_context_tool() {
+        let out = payload(&call(
+            "vcp_encode_context",
+            &json!({
+                "situational": { "time": ["\u{1F305}"] },
+                "personal": { "cognitive": { "value": "focused", "intensity": 4 } }
+            }),
+        ));
+        assert_eq!(out["wire"], "\u{23F0}\u{1F305}\u{2016}\u{1F9E0}focused:4");
+        assert_eq!(out["conformance_level"], "VCP-Standard");
+    }
+
+    #[test]
+    fn unknown_tool_and_bad_call_are_errors() {
+        assert!(dispatch(&json!({ "name": "nope" })).is_err());
+        assert!(dispatch(&json!({ "arguments": {} })).is_err());
+        assert!(dispatch(&json!({ "name": "vcp_parse_csm1", "arguments": [] })).is_err());
+    }
+}
@@ -0,0 +1,823 @@
+//! Multi-party manifest signatures and detached signature files.
+//!
+//! A manifest may carry a `signatures` array alongside (or instead of) the
+//! single issuer `signature` object, so an issuer, the deploying
+//! organization and an auditor can each sign the same bundle. Every entry
+//! signs the same bytes: [`canonicalize_manifest`] drops both fields.
+//!
+//! ```json
+//! "signatures": [
+//!   {"signer": "creed-space", "key_id": "key-2025-01", "role": "issuer",
+//!    "algorithm": "ed25519", "value": "base64:..."},
+//!   {"signer": "safety-review", "role": "auditor",
+//!    "algorithm": "ed25519", "value": "base64:..."}
+//! ]
+//! ```
+//!
+//! [`verify_all_signatures`] checks each entry against a [`TrustConfig`]
+//! and a [`SignaturePolicy`] decides whether enough of them hold. Each
+//! role has its own anchors: issuer signatures must come from the
+//! manifest's `issuer.id`, organization signatures from
+//! [`TrustConfig::organizations`], auditor signatures from
+//! [`TrustConfig::auditors`]. The
+//! signatures can also travel next to the manifest as a
+//! [`DetachedSignatures`] file, bound to it by hash.
+//!
+//! # Examples
+//!
+//! ```
+//! use chrono::{Duration, Utc};
+//! use ed25519_dalek::SigningKey;
+//! use vcp_core::multisig::{
+//!     add_signature, sign_manifest_as, verify_all_signatures, SignaturePolicy, SignatureRole,
+//! };
+//! use vcp_core::trust::{AnchorState, AnchorType, TrustAnchor, TrustConfig};
+//!
+//! let issuer = SigningKey::from_bytes(&[1u8; 32]);
+//! let auditor = SigningKey::from_bytes(&[2u8; 32]);
+//!
+//! let mut trust = TrustConfig::new();
+//! for (id, key, anchor_type) in [
+//!     ("creed-space", &issuer, AnchorType::Issuer),
+//!     ("safety-review", &auditor, AnchorType::Auditor),
+//! ] {
+//!     let anchor = TrustAnchor {
+//!         id: id.into(),
+//!         key_id: "k1".into(),
+//!         algorithm: "ed25519".into(),
+//!         public_key: TrustAnchor::encode_public_key(&key.verifying_key().to_bytes()),
+//!         anchor_type,
+//!         valid_from: Utc::now() - Duration::days(1),
+//!         valid_until: Utc::now() + Duration::days(1),
+//!         state: AnchorState::Active,
+//!     };
+//!     match anchor_type {
+//!         AnchorType::Issuer => trust.add_issuer(id, anchor),
+//!         AnchorType::Auditor => trust.add_auditor(id, anchor),
+//!         AnchorType::Organization => trust.add_organization(id, anchor),
+//!     }
+//! }
+//!
+//! let mut manifest = serde_json::json!({
+//!     "issuer": {"id": "creed-space"},
+//!     "bundle": {"id": "b", "content_hash": "sha256:00"},
+//! });
+//! for (signer, key, role) in [
+//!     ("creed-space", &issuer, SignatureRole::Issuer),
+//!     ("safety-review", &auditor, SignatureRole::Auditor),
+//! ] {
+//!     let sig = sign_manifest_as(&manifest, signer, Some("k1"), role, &key.to_bytes()).unwrap();
+//!     add_signature(&mut manifest, sig).unwrap();
+//! }
+//!
+//! let report = verify_all_signatures(&manifest, &trust).unwrap();
+//! let policy = SignaturePolicy::threshold(2).requiring(SignatureRole::Auditor);
+//! assert!(report.satisfies(&policy));
+//! ```
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::{VcpError, VcpResult};
+use crate::transport::{canonicalize_manifest, sign_manifest, verify_manifest_signature};
+use crate::trust::{TrustAnchor, TrustConfig};
+
+/// The only algorithm this SDK signs or verifies with.
+const ED25519: &str = "ed25519";
+
+// ── Signature entries ───────────────────────────────────────
+
+/// Why a party signed the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureRole {
+    /// The bundle author.
+    Issuer,
+    /// An organization approving the bundle for its deployments.
+    Organization,
+    /// A safety auditor.
+    Auditor,
+}
+
+impl SignatureRole {
+    /// Look up the signer's key for this role, in the role's own anchors.
+    fn anchor<'a>(
+        self,
+        trust: &'a TrustConfig,
+        signer: &str,
+        key_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Option<&'a TrustAnchor> {
+        match self {
+            SignatureRole::Issuer => trust.get_issuer_key_at(signer, key_id, now),
+            SignatureRole::Organization => trust.get_organization_key_at(signer, key_id, now),
+            SignatureRole::Auditor => trust.get_auditor_key_at(signer, key_id, now),
+        }
+    }
+}
+
+/// One entry of a manifest's `signatures` array.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    /// Entity ID of the signer, as keyed in the [`TrustConfig`].
+    pub signer: String,
+    /// Key within the signer's anchors; `None` accepts any valid key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    /// Capacity in which the signer signed.
+    pub role: SignatureRole,
+    /// Signature algorithm.
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String,
+    /// `base64:`-prefixed signature over the canonical manifest.
+    pub value: String,
+}
+
+fn default_algorithm() -> String {
+    ED25519.to_string()
+}
+
+/// Sign a manifest on behalf of `signer`, producing a `signatures` entry.
+///
+/// # Errors
+///
+/// Returns [`VcpError::SignatureError`] if the secret key is not 32 bytes,
+/// or [`VcpError::ParseError`] if the manifest is not an object.
+pub fn sign_manifest_as(
+    manifest: &Value,
+    signer: &str,
+    key_id: Option<&str>,
+    role: SignatureRole,
+    secret_key: &[u8],
+) -> VcpResult<ManifestSignature> {
+    let value = sign_manifest(manifest, secret_key)?;
+    Ok(ManifestSignature {
+        signer: signer.to_string(),
+        key_id: key_id.map(str::to_string),
+        role,
+        algorithm: default_algorithm(),
+        value: format!("base64:{value}"),
+    })
+}
+
+/// Append a signature to the manifest's `signatures` array, creating it
+/// if needed.
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if the manifest is not an object or
+/// `signatures` is present but not an array.
+pub fn add_signature(manifest: &mut Value, signature: ManifestSignature) -> VcpResult<()> {
+    let obj = manifest
+        .as_object_mut()
+        .ok_or_else(|| VcpError::ParseError("manifest must be a JSON object".into()))?;
+    let entries = obj
+        .entry("signatures")
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| VcpError::ParseError("'signatures' must be an array".into()))?;
+    entries.push(serde_json::to_value(signature)?);
+    Ok(())
+}
+
+/// Collect every signature a manifest carries.
+///
+/// Entries from the `signatures` array come first. A legacy single
+/// `signature` object is included as an [`SignatureRole::Issuer`] entry
+/// attributed to `issuer.id` / `issuer.key_id`.
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if the manifest is not an object or an
+/// entry is malformed.
+pub fn manifest_signatures(manifest: &Value) -> VcpResult<Vec<ManifestSignature>> {
+    let obj = manifest
+        .as_object()
+        .ok_or_else(|| VcpError::ParseError("manifest must be a JSON object".into()))?;
+
+    let mut signatures = match obj.get("signatures") {
+        None => Vec::new(),
+        Some(entries) => Vec::<ManifestSignature>::deserialize(entries)
+            .map_err(|e| VcpError::ParseError(format!("invalid 'signatures' entry: {e}")))?,
+    };
+
+    if let Some(legacy) = obj.get("signature") {
+        let value = legacy
+            .get("value")
+            .and_then(Value::as_str)
+            .ok_or_else(|| VcpError::ParseError("'signature' is missing 'value'".into()))?;
+        let issuer = obj.get("issuer");
+        let signer = issuer
+            .and_then(|i| i.get("id"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| VcpError::ParseError("signed manifest is missing 'issuer.id'".into()))?;
+        signatures.push(ManifestSignature {
+            signer: signer.to_string(),
+            key_id: issuer
+                .and_then(|i| i.get("key_id"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            role: SignatureRole::Issuer,
+            algorithm: legacy
+                .get("algorithm")
+                .and_then(Value::as_str)
+                .map_or_else(default_algorithm, str::to_string),
+            value: value.to_string(),
+        });
+    }
+
+    Ok(signatures)
+}
+
+// ── Verification ────────────────────────────────────────────
+
+/// Outcome of checking a single signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// Signed by a trusted, currently valid key.
+    Valid,
+    /// The signature does not match the manifest or the key.
+    Invalid,
+    /// No valid anchor for the signer and key ID in the trust config.
+    UntrustedSigner,
+    /// The entry uses an algorithm other than Ed25519.
+    UnsupportedAlgorithm,
+    /// An [`SignatureRole::Organization`] signature by the manifest's own
+    /// issuer, which cannot approve its own bundle.
+    SelfEndorsement,
+    /// An [`SignatureRole::Issuer`] signature by someone other than the
+    /// manifest's `issuer.id`.
+    NotManifestIssuer,
+}
+
+/// A signature entry and the result of verifying it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureCheck {
+    /// The entry as found in the manifest.
+    pub signature: ManifestSignature,
+    /// Verification result.
+    pub status: SignatureStatus,
+}
+
+/// Per-signature results from [`verify_all_signatures`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureReport {
+    /// One check per signature, in manifest order.
+    pub checks: Vec<SignatureCheck>,
+}
+
+impl SignatureReport {
+    /// Valid signatures, counting each signer once.
+    pub fn valid_signers(&self) -> HashSet<&str> {
+        self.checks
+            .iter()
+            .filter(|c| c.status == SignatureStatus::Valid)
+            .map(|c| c.signature.signer.as_str())
+            .collect()
+    }
+
+    /// Whether at least one valid signature was made in `role`.
+    pub fn has_valid_role(&self, role: SignatureRole) -> bool {
+        self.checks
+            .iter()
+            .any(|c| c.status == SignatureStatus::Valid && c.signature.role == role)
+    }
+
+    /// Whether the valid signatures meet `policy`.
+    pub fn satisfies(&self, policy: &SignaturePolicy) -> bool {
+        if policy.reject_invalid
+            && self
+                .checks
+                .iter()
+                .any(|c| c.status == SignatureStatus::Invalid)
+        {
+            return false;
+        }
+        self.valid_signers().len() >= policy.threshold
+            && policy
+                .required_roles
+                .iter()
+                .all(|role| self.has_valid_role(*role))
+    }
+}
+
+/// How many, and which, signatures a manifest needs to be accepted.
+///
+/// Signers count once each, so one party cannot meet a threshold by
+/// signing with several keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignaturePolicy {
+    /// Minimum number of distinct signers with a valid signature.
+    pub threshold: usize,
+    /// Roles that must each have at least one valid signature.
+    #[serde(default)]
+    pub required_roles: Vec<SignatureRole>,
+    /// Fail if any signature from a trusted key does not verify, even
+    /// when the threshold is otherwise met.
+    #[serde(default = "default_reject_invalid")]
+    pub reject_invalid: bool,
+}
+
+fn default_reject_invalid() -> bool {
+    true
+}
+
+impl SignaturePolicy {
+    /// Require `n` distinct valid signers (e.g. 2 of 3).
+    pub fn threshold(n: usize) -> Self {
+        Self {
+            threshold: n,
+            required_roles: Vec::new(),
+            reject_invalid: true,
+        }
+    }
+
+    /// Additionally require a valid signature in `role`.
+    #[must_use]
+    pub fn requiring(mut self, role: SignatureRole) -> Self {
+        if !self.required_roles.contains(&role) {
+            self.required_roles.push(role);
+        }
+        self
+    }
+
+    /// Tolerate bad signatures as long as enough valid ones remain.
+    #[must_use]
+    pub fn allow_invalid(mut self) -> Self {
+        self.reject_invalid = false;
+        self
+    }
+}
+
+impl Default for SignaturePolicy {
+    /// A single valid signature, no role requirements.
+    fn default() -> Self {
+        Self::threshold(1)
+    }
+}
+
+/// Verify every signature on a manifest against the trust configuration.
+///
+/// Each entry's key is resolved through the [`TrustConfig`] by signer,
+/// key ID and role; entries without a currently valid anchor are reported
+/// as [`SignatureStatus::UntrustedSigner`] rather than checked. An issuer
+/// entry from anyone but `issuer.id` is reported as
+/// [`SignatureStatus::NotManifestIssuer`], and an organization entry from
+/// `issuer.id` as [`SignatureStatus::SelfEndorsement`].
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if the manifest or one of its
+/// signature entries is malformed.
+pub fn verify_all_signatures(manifest: &Value, trust: &TrustConfig) -> VcpResult<SignatureReport> {
+    verify_all_signatures_at(manifest, trust, Utc::now())
+}
+
+/// As [`verify_all_signatures`], judging anchor validity at `now`.
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if the manifest or one of its
+/// signature entries is malformed.
+pub fn verify_all_signatures_at(
+    manifest: &Value,
+    trust: &TrustConfig,
+    now: DateTime<Utc>,
+) -> VcpResult<SignatureReport> {
+    let issuer = manifest
+        .get("issuer")
+        .and_then(|issuer| issuer.get("id"))
+        .and_then(Value::as_str);
+    let checks = manifest_signatures(manifest)?
+        .into_iter()
+        .map(|signature| {
+            let by_issuer = issuer == Some(signature.signer.as_str());
+            let status = match signature.role {
+                SignatureRole::Issuer if !by_issuer => SignatureStatus::NotManifestIssuer,
+                SignatureRole::Organization if by_issuer => SignatureStatus::SelfEndorsement,
+                _ => check_signature(manifest, &signature, trust, now),
+            };
+            SignatureCheck { signature, status }
+        })
+        .collect();
+    Ok(SignatureReport { checks })
+}
+
+fn check_signature(
+    manifest: &Value,
+    signature: &ManifestSignature,
+    trust: &TrustConfig,
+    now: DateTime<Utc>,
+) -> SignatureStatus {
+    if !signature.algorithm.eq_ignore_ascii_case(ED25519) {
+        return SignatureStatus::UnsupportedAlgorithm;
+    }
+    let Some(anchor) =
+        signature
+            .role
+            .anchor(trust, &signature.signer, signature.key_id.as_deref(), now)
+    else {
+        return SignatureStatus::UntrustedSigner;
+    };
+    if !anchor.algorithm.eq_ignore_ascii_case(ED25519) {
+        return SignatureStatus::UnsupportedAlgorithm;
+    }
+    let Some(key) = anchor.public_key_bytes() else {
+        return SignatureStatus::UntrustedSigner;
+    };
+
+    match verify_manifest_signature(manifest, &key, &signature.value) {
+        Ok(true) => SignatureStatus::Valid,
+        _ => SignatureStatus::Invalid,
+    }
+}
+
+// ── Detached signatures ─────────────────────────────────────
+
+/// Signatures stored beside a manifest (e.g. `manifest.json.sig`).
+///
+/// `manifest_hash` binds the file to one manifest: it is the SHA-256 of
+/// the canonical manifest bytes, so it ignores any signatures embedded in
+/// the manifest itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetachedSignatures {
+    /// `sha256:<hex>` of [`canonicalize_manifest`] output.
+    pub manifest_hash: String,
+    /// The signatures, in the same shape as the embedded array.
+    pub signatures: Vec<ManifestSignature>,
+}
+
+impl DetachedSignatures {
+    /// Start an empty signature file for `manifest`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the manifest is not an object.
+    pub fn for_manifest(manifest: &Value) -> VcpResult<Self> {
+        Ok(Self {
+            manifest_hash: manifest_hash(manifest)?,
+            signatures: Vec::new(),
+        })
+    }
+
+    /// Move a manifest's embedded signatures into a detached file.
+    ///
+    /// Returns the manifest without its `signatures` array alongside the
+    /// file. A legacy `signature` object is left in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the manifest or its
+    /// `signatures` entries are malformed.
+    pub fn detach(manifest: &Value) -> VcpResult<(Value, Self)> {
+        let mut stripped = manifest.clone();
+        let signatures = match stripped
+            .as_object_mut()
+            .and_then(|o| o.remove("signatures"))
+        {
+            None => Vec::new(),
+            Some(entries) => serde_json::from_value(entries)
+                .map_err(|e| VcpError::ParseError(format!("invalid 'signatures' entry: {e}")))?,
+        };
+        let mut file = Self::for_manifest(&stripped)?;
+        file.signatures = signatures;
+        Ok((stripped, file))
+    }
+
+    /// Whether this file was produced for `manifest`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the manifest is not an object.
+    pub fn matches(&self, manifest: &Value) -> VcpResult<bool> {
+        Ok(manifest_hash(manifest)? == self.manifest_hash)
+    }
+
+    /// Embed these signatures into `manifest`, appending to any it has.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::HashMismatch`] if the file belongs to a
+    /// different manifest, or [`VcpError::ParseError`] if the manifest is
+    /// malformed.
+    pub fn attach(&self, manifest: &Value) -> VcpResult<Value> {
+        let computed = manifest_hash(manifest)?;
+        if computed != self.manifest_hash {
+            return Err(VcpError::HashMismatch {
+                expected: self.manifest_hash.clone(),
+                actual: computed,
+            });
+        }
+        let mut attached = manifest.clone();
+        for signature in &self.signatures {
+            add_signature(&mut attached, signature.clone())?;
+        }
+        Ok(attached)
+    }
+
+    /// Parse a detached signature file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if the JSON is invalid.
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Verify a manifest together with its detached signature file.
+///
+/// Embedded signatures are checked as well.
+///
+/// # Errors
+///
+/// Returns [`VcpError::HashMismatch`] if the file belongs to a different
+/// manifest, or [`VcpError::ParseError`] if either is malformed.
+pub fn verify_detached(
+    manifest: &Value,
+    detached: &DetachedSignatures,
+    trust: &TrustConfig,
+) -> VcpResult<SignatureReport> {
+    verify_all_signatures(&detached.attach(manifest)?, trust)
+}
+
+/// `sha256:<hex>` of the canonical manifest bytes.
+fn manifest_hash(manifest: &Value) -> VcpResult<String> {
+    let canonical = canonicalize_manifest(manifest)?;
+    Ok(format!("sha256:{:x}", Sha256::digest(&canonical)))
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trust::{AnchorState, AnchorType};
+    use chrono::{Duration, Utc};
+    use ed25519_dalek::SigningKey;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn anchor(id: &str, key: &SigningKey, anchor_type: AnchorType) -> TrustAnchor {
+        TrustAnchor {
+            id: id.to_string(),
+            key_id: "k1".to_string(),
+            algorithm: "ed25519".to_string(),
+            public_key: TrustAnchor::encode_public_key(&key.verifying_key().to_bytes()),
+            anchor_type,
+            valid_from: Utc::now() - Duration::days(1),
+            valid_until: Utc::now() + Duration::days(1),
+            state: AnchorState::Active,
+        }
+    }
+
+    /// Issuer (seed 1), organization (seed 2), auditor (seed 3), and a
+    /// second trusted issuer (seed 4) that did not write the manifest.
+    fn trust() -> TrustConfig {
+        let mut trust = TrustConfig::new();
+        trust.add_issuer("issuer", anchor("issuer", &key(1), AnchorType::Issuer));
+        trust.add_organization("org", anchor("org", &key(2), AnchorType::Organization));
+        trust.add_auditor("auditor", anchor("auditor", &key(3), AnchorType::Auditor));
+        trust.add_issuer("rival", anchor("rival", &key(4), AnchorType::Issuer));
+        trust
+    }
+
+    fn manifest() -> Value {
+        serde_json::json!({
+            "vcp_version": "1.0",
+            "issuer": {"id": "issuer", "key_id": "k1"},
+            "bundle": {"id": "b", "content_hash": "sha256:00", "weight": 0.5}
+        })
+    }
+
+    fn signed(parties: &[(&str, u8, SignatureRole)]) -> Value {
+        let mut m = manifest();
+        for (signer, seed, role) in parties {
+            let sig =
+                sign_manifest_as(&m, signer, Some("k1"), *role, &key(*seed).to_bytes()).unwrap();
+            add_signature(&mut m, sig).unwrap();
+        }
+        m
+    }
+
+    #[test]
+    fn every_party_signs_the_same_bytes() {
+        let m = signed(&[
+            ("issuer", 1, SignatureRole::Issuer),
+            ("org", 2, SignatureRole::Organization),
+            ("auditor", 3, SignatureRole::Auditor),
+        ]);
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        assert_eq!(report.checks.len(), 3);
+        assert!(report
+            .checks
+            .iter()
+            .all(|c| c.status == SignatureStatus::Valid));
+        assert!(report.satisfies(&SignaturePolicy::threshold(3)));
+    }
+
+    #[test]
+    fn threshold_two_of_three() {
+        let m = signed(&[
+            ("issuer", 1, SignatureRole::Issuer),
+            ("auditor", 3, SignatureRole::Auditor),
+        ]);
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        assert!(report.satisfies(&SignaturePolicy::threshold(2)));
+        assert!(!report.satisfies(&SignaturePolicy::threshold(3)));
+        assert!(!report
+            .satisfies(&SignaturePolicy::threshold(2).requiring(SignatureRole::Organization)));
+    }
+
+    #[test]
+    fn same_signer_counts_once() {
+        let m = signed(&[
+            ("issuer", 1, SignatureRole::Issuer),
+            ("issuer", 1, SignatureRole::Organization),
+        ]);
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        assert_eq!(report.valid_signers().len(), 1);
+        assert!(!report.satisfies(&SignaturePolicy::threshold(2)));
+    }
+
+    #[test]
+    fn issuer_cannot_sign_as_organization() {
+        let m = signed(&[
+            ("issuer", 1, SignatureRole::Issuer),
+            ("issuer", 1, SignatureRole::Organization),
+            ("auditor", 3, SignatureRole::Auditor),
+        ]);
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        assert_eq!(report.checks[1].status, SignatureStatus::SelfEndorsement);
+        assert!(!report.has_valid_role(SignatureRole::Organization));
+        let policy = SignaturePolicy::threshold(2).requiring(SignatureRole::Organization);
+        assert!(!report.satisfies(&policy));
+
+        // A different organization's signature does count.
+        let m = signed(&[
+            ("issuer", 1, SignatureRole::Issuer),
+            ("org", 2, SignatureRole::Organization),
+        ]);
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        assert!(report.satisfies(&policy));
+    }
+
+    #[test]
+    fn other_issuer_cannot_sign_as_issuer_or_organization() {
+        let m = signed(&[
+            ("rival", 4, SignatureRole::Issuer),
+            ("rival", 4, SignatureRole::Organization),
+            ("auditor", 3, SignatureRole::Auditor),
+        ]);
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        let statuses: Vec<_> = report.checks.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                SignatureStatus::NotManifestIssuer,
+                SignatureStatus::UntrustedSigner,
+                SignatureStatus::Valid
+            ]
+        );
+        assert!(!report.satisfies(&SignaturePolicy::threshold(1).requiring(SignatureRole::Issuer)));
+        assert!(!report
+            .satisfies(&SignaturePolicy::threshold(1).requiring(SignatureRole::Organization)));
+    }
+
+    #[test]
+    fn wrong_key_and_unknown_signer() {
+        let m = signed(&[
+            ("issuer", 1, SignatureRole::Issuer),
+            ("org", 9, SignatureRole::Organization),
+            ("stranger", 4, SignatureRole::Auditor),
+        ]);
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        let statuses: Vec<_> = report.checks.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                SignatureStatus::Valid,
+                SignatureStatus::Invalid,
+                SignatureStatus::UntrustedSigner
+            ]
+        );
+        assert!(!report.satisfies(&SignaturePolicy::default()));
+        assert!(report.satisfies(&SignaturePolicy::default().allow_invalid()));
+    }
+
+    #[test]
+    fn tampering_invalidates_every_signature() {
+        let mut m = signed(&[
+            ("issuer", 1, SignatureRole::Issuer),
+            ("auditor", 3, SignatureRole::Auditor),
+        ]);
+        m["bundle"]["id"] = serde_json::json!("other");
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        assert!(report.valid_signers().is_empty());
+    }
+
+    #[test]
+    fn legacy_signature_counts_as_issuer() {
+        let mut m = signed(&[("auditor", 3, SignatureRole::Auditor)]);
+        let value = sign_manifest(&m, &key(1).to_bytes()).unwrap();
+        m["signature"] = serde_json::json!({"algorithm": "ed25519", "value": value});
+
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        assert_eq!(report.checks[1].signature.signer, "issuer");
+        assert!(report.satisfies(&SignaturePolicy::threshold(2).requiring(SignatureRole::Issuer)));
+    }
+
+    #[test]
+    fn unsupported_algorithm_is_reported() {
+        let mut m = manifest();
+        let mut sig = sign_manifest_as(
+            &m,
+            "issuer",
+            None,
+            SignatureRole::Issuer,
+            &key(1).to_bytes(),
+        )
+        .unwrap();
+        sig.algorithm = "ed448".to_string();
+        add_signature(&mut m, sig).unwrap();
+        let report = verify_all_signatures(&m, &trust()).unwrap();
+        assert_eq!(
+            report.checks[0].status,
+            SignatureStatus::UnsupportedAlgorithm
+        );
+    }
+
+    #[test]
+    fn malformed_entries_error() {
+        let mut m = manifest();
+        m["signatures"] = serde_json::json!([{"signer": "issuer"}]);
+        assert!(verify_all_signatures(&m, &trust()).is_err());
+
+        m["signatures"] = serde_json::json!({});
+        assert!(add_signature(
+            &mut m,
+            sign_manifest_as(
+                &manifest(),
+                "issuer",
+                None,
+                SignatureRole::Issuer,
+                &key(1).to_bytes()
+            )
+            .unwrap()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn detached_roundtrip() {
+        let m = signed(&[
+            ("issuer", 1, SignatureRole::Issuer),
+            ("org", 2, SignatureRole::Organization),
+        ]);
+        let (bare, file) = DetachedSignatures::detach(&m).unwrap();
+        assert!(bare.get("signatures").is_none());
+        assert!(file.matches(&bare).unwrap());
+        assert!(file.matches(&m).unwrap());
+
+        let file = DetachedSignatures::from_json(&file.to_json().unwrap()).unwrap();
+        let report = verify_detached(&bare, &file, &trust()).unwrap();
+        assert!(report.satisfies(&SignaturePolicy::threshold(2)));
+    }
+
+    #[test]
+    fn detached_file_is_bound_to_its_manifest() {
+        let mut file = DetachedSignatures::for_manifest(&manifest()).unwrap();
+        file.signatures.push(
+            sign_manifest_as(
+                &manifest(),
+                "issuer",
+                None,
+                SignatureRole::Issuer,
+                &key(1).to_bytes(),
+            )
+            .unwrap(),
+        );
+        let mut other = manifest();
+        other["bundle"]["id"] = serde_json::json!("other");
+        assert!(matches!(
+            verify_detached(&other, &file, &trust()),
+            Err(VcpError::HashMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,389 @@
+//! Namespace authority: who owns the `:NAMESPACE` suffixes on tokens.
+//!
+//! [`VcpToken::parse`] accepts any well-formed namespace. A
+//! [`NamespaceRegistry`] records which namespaces are registered, to whom,
+//! and which are reserved, and is consulted by
+//! [`VcpToken::parse_with_namespaces`] and, through
+//! [`VerificationContext::with_namespace_registry`](crate::orchestrator::VerificationContext::with_namespace_registry),
+//! by bundle verification. Verification also checks who signed: a
+//! manifest may bind a token in a namespace only if its `issuer.id` is the
+//! namespace's owner or one of the entry's `issuers`.
+//!
+//! | Namespace | Lenient registry | Strict registry |
+//! |-----------|------------------|-----------------|
+//! | none | accepted | accepted |
+//! | registered | accepted | accepted |
+//! | reserved | rejected | rejected |
+//! | unregistered | accepted | rejected |
+//!
+//! As JSON, a registry looks like:
+//!
+//! ```json
+//! {
+//!   "strict": true,
+//!   "namespaces": {
+//!     "SEC": {
+//!       "owner": "acme-compliance",
+//!       "description": "Securities rules",
+//!       "issuers": ["acme-legal"]
+//!     },
+//!     "VCP": {"owner": "creed-space", "reserved": true}
+//!   }
+//! }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::identity::VcpToken;
+//! use vcp_core::namespace::{NamespaceEntry, NamespaceRegistry};
+//!
+//! let mut registry = NamespaceRegistry::new().with_strict(true);
+//! registry.register("SEC", NamespaceEntry::new("acme-compliance")).unwrap();
+//! registry.register("VCP", NamespaceEntry::new("creed-space").reserved()).unwrap();
+//!
+//! assert!(VcpToken::parse_with_namespaces("company.acme.legal:SEC", &registry).is_ok());
+//! assert!(VcpToken::parse_with_namespaces("company.acme.legal", &registry).is_ok());
+//! assert!(VcpToken::parse_with_namespaces("company.acme.legal:VCP", &registry).is_err());
+//! assert!(VcpToken::parse_with_namespaces("company.acme.legal:GOV", &registry).is_err());
+//!
+//! // Another owner cannot take over a registered namespace.
+//! assert!(registry.register("SEC", NamespaceEntry::new("mallory")).is_err());
+//!
+//! // Only the owner's issuers may bind tokens in it.
+//! assert!(registry.is_approved_for("SEC", "acme-compliance"));
+//! assert!(!registry.is_approved_for("SEC", "mallory"));
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{VcpError, VcpResult};
+use crate::identity::VcpToken;
+
+/// Ownership metadata for one namespace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceEntry {
+    /// Who controls the namespace (an organization or team identifier).
+    pub owner: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Held back by `owner`: no token may use it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reserved: bool,
+    /// Manifest issuer IDs, besides `owner` itself, that may bind tokens
+    /// in the namespace.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issuers: Vec<String>,
+}
+
+impl NamespaceEntry {
+    /// A namespace registered to `owner`.
+    pub fn new(owner: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            description: None,
+            reserved: false,
+            issuers: Vec::new(),
+        }
+    }
+
+    /// Attach a human-readable description.
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Mark the namespace reserved.
+    #[must_use]
+    pub fn reserved(mut self) -> Self {
+        self.reserved = true;
+        self
+    }
+
+    /// Let manifests issued by `issuer` bind tokens in the namespace.
+    #[must_use]
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuers.push(issuer.into());
+        self
+    }
+
+    /// Whether a manifest from `issuer` speaks for the owner.
+    pub fn authorizes(&self, issuer: &str) -> bool {
+        self.owner == issuer || self.issuers.iter().any(|id| id == issuer)
+    }
+}
+
+/// Registered and reserved namespaces, and whether unregistered ones are
+/// allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawRegistry")]
+pub struct NamespaceRegistry {
+    /// Reject namespaces that are not registered.
+    #[serde(default)]
+    strict: bool,
+    #[serde(default)]
+    namespaces: BTreeMap<String, NamespaceEntry>,
+}
+
+/// The unchecked JSON form of a [`NamespaceRegistry`].
+#[derive(Deserialize)]
+struct RawRegistry {
+    #[serde(default)]
+    strict: bool,
+    #[serde(default)]
+    namespaces: BTreeMap<String, NamespaceEntry>,
+}
+
+impl TryFrom<RawRegistry> for NamespaceRegistry {
+    type Error = VcpError;
+
+    fn try_from(raw: RawRegistry) -> VcpResult<Self> {
+        for namespace in raw.namespaces.keys() {
+            VcpToken::validate_namespace(namespace)?;
+        }
+        Ok(Self {
+            strict: raw.strict,
+            namespaces: raw.namespaces,
+        })
+    }
+}
+
+impl NamespaceRegistry {
+    /// An empty, lenient registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject unregistered namespaces when `strict` is set.
+    #[must_use]
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether unregistered namespaces are rejected.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Register `namespace`, or update its entry if `entry` has the same
+    /// owner.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::MalformedToken`] if `namespace` is not a valid
+    /// namespace, and [`VcpError::ParseError`] if it is already
+    /// registered to another owner.
+    pub fn register(&mut self, namespace: &str, entry: NamespaceEntry) -> VcpResult<()> {
+        VcpToken::validate_namespace(namespace)?;
+        if let Some(existing) = self.namespaces.get(namespace) {
+            if existing.owner != entry.owner {
+                return Err(VcpError::ParseError(format!(
+                    "namespace {namespace} is owned by {}",
+                    existing.owner
+                )));
+            }
+        }
+        self.namespaces.insert(namespace.to_string(), entry);
+        Ok(())
+    }
+
+    /// Remove `namespace`, returning its entry.
+    pub fn unregister(&mut self, namespace: &str) -> Option<NamespaceEntry> {
+        self.namespaces.remove(namespace)
+    }
+
+    /// The entry for `namespace`, if registered or reserved.
+    pub fn get(&self, namespace: &str) -> Option<&NamespaceEntry> {
+        self.namespaces.get(namespace)
+    }
+
+    /// Whether `namespace` is registered and not reserved.
+    pub fn is_approved(&self, namespace: &str) -> bool {
+        self.get(namespace).is_some_and(|entry| !entry.reserved)
+    }
+
+    /// Whether `namespace` is approved and a manifest from `issuer` may
+    /// bind tokens in it (see [`NamespaceEntry::authorizes`]).
+    pub fn is_approved_for(&self, namespace: &str, issuer: &str) -> bool {
+        self.get(namespace)
+            .is_some_and(|entry| !entry.reserved && entry.authorizes(issuer))
+    }
+
+    /// Namespaces registered to `owner`, reserved ones included.
+    pub fn owned_by<'a>(&'a self, owner: &'a str) -> impl Iterator<Item = &'a str> {
+        self.iter()
+            .filter(move |(_, entry)| entry.owner == owner)
+            .map(|(namespace, _)| namespace)
+    }
+
+    /// Iterate over `(namespace, entry)` pairs in namespace order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &NamespaceEntry)> {
+        self.namespaces.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Number of registered and reserved namespaces.
+    pub fn len(&self) -> usize {
+        self.namespaces.len()
+    }
+
+    /// Whether no namespaces are registered.
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+    }
+
+    /// Check that a token may use `namespace`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::MalformedToken`] if `namespace` is reserved, or
+    /// unregistered in a strict registry.
+    pub fn check(&self, namespace: &str) -> VcpResult<()> {
+        match self.get(namespace) {
+            Some(entry) if entry.reserved => Err(VcpError::MalformedToken(format!(
+                "namespace {namespace} is reserved by {}",
+                entry.owner
+            ))),
+            None if self.strict => Err(VcpError::MalformedToken(format!(
+                "namespace {namespace} is not registered"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check `token`'s namespace, if it has one.
+    ///
+    /// # Errors
+    ///
+    /// As [`check`](Self::check).
+    pub fn validate(&self, token: &VcpToken) -> VcpResult<()> {
+        token
+            .namespace
+            .as_deref()
+            .map_or(Ok(()), |namespace| self.check(namespace))
+    }
+
+    /// Parse from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if the JSON is malformed or names
+    /// an invalid namespace.
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> NamespaceRegistry {
+        let mut registry = NamespaceRegistry::new();
+        registry
+            .register(
+                "SEC",
+                NamespaceEntry::new("acme").with_description("Securities"),
+            )
+            .unwrap();
+        registry
+            .register("VCP", NamespaceEntry::new("creed-space").reserved())
+            .unwrap();
+        registry
+    }
+
+    #[test]
+    fn lenient_registry_rejects_only_reserved() {
+        let registry = registry();
+        assert!(registry.check("SEC").is_ok());
+        assert!(registry.check("GOV").is_ok());
+        let err = registry.check("VCP").unwrap_err().to_string();
+        assert!(err.contains("reserved by creed-space"), "{err}");
+
+        assert!(registry.is_approved("SEC"));
+        assert!(!registry.is_approved("VCP"));
+        assert!(!registry.is_approved("GOV"));
+    }
+
+    #[test]
+    fn strict_registry_rejects_unregistered() {
+        let registry = registry().with_strict(true);
+        assert!(registry.check("GOV").is_err());
+        assert!(registry
+            .validate(&VcpToken::parse("family.safe.guide").unwrap())
+            .is_ok());
+        assert!(registry
+            .validate(&VcpToken::parse("family.safe.guide:GOV").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn registration_is_owner_checked() {
+        let mut registry = registry();
+        assert!(registry
+            .register("SEC", NamespaceEntry::new("other"))
+            .is_err());
+        assert_eq!(registry.get("SEC").unwrap().owner, "acme");
+
+        // The owner may update its own entry.
+        registry
+            .register("SEC", NamespaceEntry::new("acme").reserved())
+            .unwrap();
+        assert!(registry.check("SEC").is_err());
+        assert_eq!(registry.owned_by("acme").collect::<Vec<_>>(), ["SEC"]);
+
+        assert!(registry
+            .register("sec", NamespaceEntry::new("acme"))
+            .is_err());
+        assert!(registry.unregister("SEC").is_some());
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn approval_is_per_issuer() {
+        let mut registry = registry();
+        assert!(registry.is_approved_for("SEC", "acme"));
+        assert!(!registry.is_approved_for("SEC", "globex"));
+        assert!(!registry.is_approved_for("VCP", "creed-space"));
+        assert!(!registry.is_approved_for("GOV", "acme"));
+
+        registry
+            .register("SEC", NamespaceEntry::new("acme").with_issuer("acme-legal"))
+            .unwrap();
+        assert!(registry.is_approved_for("SEC", "acme-legal"));
+        assert!(!registry.is_approved_for("SEC", "globex"));
+    }
+
+    #[test]
+    fn json_roundtrip_and_validation() {
+        let mut registry = registry().with_strict(true);
+        registry
+            .register("GOV", NamespaceEntry::new("state").with_issuer("state-ca"))
+            .unwrap();
+        let json = registry.to_json().unwrap();
+        assert_eq!(NamespaceRegistry::from_json(&json).unwrap(), registry);
+
+        let loaded =
+            NamespaceRegistry::from_json(r#"{"namespaces": {"GOV": {"owner": "state"}}}"#).unwrap();
+        assert!(!loaded.is_strict());
+        assert!(loaded.is_approved("GOV"));
+
+        assert!(
+            NamespaceRegistry::from_json(r#"{"namespaces": {"gov": {"owner": "state"}}}"#).is_err()
+        );
+    }
+}
@@ -4,18 +4,44 @@
 //! providing the same verification steps:
 //!
 //! 1. Size limits (manifest < 64 KB, content < 256 KB)
-//! 2. Parse manifest JSON (schema validation)
-//! 3. Content hash verification (SHA-256)
-//! 4. Issuer trust lookup
+//! 2. Parse manifest JSON (schema validation, optionally against the full
+//!    embedded JSON Schema with pointer-precise errors)
+//! 3. Content hash verification (SHA-256), per file for multi-file bundles
+//! 4. Issuer trust lookup (or a trust-on-first-use pin, when enabled)
 //! 5. Issuer signature verification (Ed25519)
 //! 6. Auditor trust + safety attestation verification
 //! 7. Temporal validation (iat, nbf, exp, jti)
 //! 8. Replay detection (JTI cache)
 //! 9. Token budget validation
-//! 10. Scope verification (model family, purpose, environment)
+//! 10. Scope verification (model family, purpose, environment, token binding,
+//!     binding namespace)
 //! 11. Content safety scan (injection patterns)
 //! 12. Return Valid
 //!
+//! When trust data comes from a local cache rather than a live fetch, the
+//! orchestrator refuses to verify unless a [`DegradedMode`] policy is set.
+//! With one, [`Orchestrator::verify_outcome`] runs the full pipeline and
+//! marks the result `degraded` with a shortened validity window.
+//!
+//! [`Orchestrator::verify_files_outcome`] runs the same pipeline over a
+//! multi-file bundle ([`BundleContents`]); budget and safety checks then
+//! see every file. [`Orchestrator::verify_jws`] accepts a manifest
+//! delivered as a compact JWS and checks the JWS signature in step 5.
+//!
+//! [`Orchestrator::check_freshness`] looks ahead instead: it lists
+//! [`FreshnessAdvisory`] entries for a manifest or trust anchor nearing
+//! expiry, or a stale CRL, so bundles can be renewed before they fail.
+//!
+//! Verification takes `&self`: the replay cache is sharded behind its own
+//! locks, so one orchestrator can be shared (e.g. in an `Arc`) by every
+//! request thread and a JTI is still accepted at most once.
+//!
+//! [`Orchestrator::snapshot`] and [`Orchestrator::restore`] carry replay
+//! state (and, optionally, cached CRLs) across a restart.
+//!
+//! With the `http` feature, [`Orchestrator::fetch_and_verify`] downloads a
+//! registry-hosted manifest and content and runs the pipeline on them.
+//!
 //! # Examples
 //!
 //! ```
@@ -23,7 +49,7 @@
 //! use vcp_core::trust::TrustConfig;
 //!
 //! let trust = TrustConfig::default();
-//! let mut orch = Orchestrator::new(trust.clone());
+//! let orch = Orchestrator::new(trust.clone());
 //! let ctx = VerificationContext::new(trust);
 //!
 //! // A trivially invalid manifest (empty) will fail at schema validation.
@@ -31,23 +57,45 @@
 //! assert!(!code.is_valid());
 //! ```
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fs;
+use std::hash::{BuildHasher, RandomState};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::{Duration, SystemTime};
 
+use chrono::{DateTime, Utc};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use sha2::{Digest, Sha256};
+
+use crate::budget::BudgetEstimator;
+use crate::clock::{default_clock, Clock};
 use crate::error::{VcpError, VcpResult, VerificationCode};
-use crate::transport::{verify_content_hash, verify_manifest_signature};
-use crate::trust::TrustConfig;
+use crate::identity::VcpToken;
+use crate::keys::import_public_key;
+use crate::manifest_schema::{validate_manifest, SchemaViolation};
+use crate::multisig::{verify_all_signatures_at, SignaturePolicy};
+use crate::namespace::NamespaceRegistry;
+use crate::reload::SharedConfig;
+use crate::revocation::{CachedCrl, RevocationChecker};
+use crate::transport::{
+    verify_content_hash, verify_manifest_signature, BundleContents, Jws, Manifest, ManifestBinding,
+};
+use crate::trust::{AnchorType, KeyPins, PinStatus, TrustAnchor, TrustConfig};
 
 // ── Constants ────────────────────────────────────────────────
 
 /// Maximum manifest size in bytes (64 KB).
-const MAX_MANIFEST_SIZE: usize = 65_536;
+pub const MAX_MANIFEST_SIZE: usize = 65_536;
 
 /// Maximum content size in bytes (256 KB).
-const MAX_CONTENT_SIZE: usize = 262_144;
+pub const MAX_CONTENT_SIZE: usize = 262_144;
 
 /// Clock skew tolerance in minutes.
 const CLOCK_SKEW_MINUTES: i64 = 5;
@@ -58,6 +106,16 @@ const MAX_EXP_DAYS: i64 = 90;
 /// Default maximum replay cache entries.
 const DEFAULT_MAX_REPLAY_ENTRIES: usize = 100_000;
 
+/// Independently locked shards in a [`ReplayCache`].
+const REPLAY_SHARDS: usize = 16;
+
+/// Smaller caches use fewer shards, so that capacity is not split into
+/// slices too small to be useful.
+const MIN_REPLAY_SHARD_CAPACITY: usize = 1024;
+
+/// Format version written by [`Orchestrator::snapshot`].
+pub const SNAPSHOT_VERSION: u32 = 1;
+
 /// Injection patterns to scan for in constitution content.
 const INJECTION_PATTERNS: &[&str] = &[
     r"(?i)ignore\s+(all\s+)?(previous|above|prior)\s+instructions",
@@ -89,6 +147,10 @@ const FORBIDDEN_CHARS: &[char] = &[
 pub struct VerificationContext {
     /// Trust configuration with issuer and auditor keys.
     pub trust_config: TrustConfig,
+    /// Reloadable trust configuration. When set, each verification reads
+    /// its current value instead of `trust_config`, so a reload takes
+    /// effect on the next call.
+    pub shared_trust_config: Option<SharedConfig<TrustConfig>>,
     /// Maximum model context window in tokens.
     pub model_context_limit: usize,
     /// Model family identifier for scope matching (e.g. `"claude-*"`).
@@ -97,6 +159,19 @@ pub struct VerificationContext {
     pub purpose: String,
     /// Deployment environment for scope matching (e.g. `"production"`).
     pub environment: String,
+    /// Where `trust_config` came from.
+    pub trust_source: TrustSource,
+    /// Co-signature requirements checked against the manifest's
+    /// `signatures` array; `None` checks only the issuer `signature`.
+    pub signature_policy: Option<SignaturePolicy>,
+    /// VCP/I token the bundle is being loaded as. When set, the manifest's
+    /// `binding.token` must cover it or verification fails with
+    /// [`VerificationCode::TokenMismatch`].
+    pub expected_token: Option<VcpToken>,
+    /// Namespace authority. When set, the manifest's `binding.token` must
+    /// name a namespace the registry approves for the manifest's issuer or
+    /// verification fails with [`VerificationCode::ScopeMismatch`].
+    pub namespace_registry: Option<NamespaceRegistry>,
 }
 
 impl VerificationContext {
@@ -105,70 +180,418 @@ impl VerificationContext {
     pub fn new(trust_config: TrustConfig) -> Self {
         Self {
             trust_config,
+            shared_trust_config: None,
             model_context_limit: 128_000,
             model_family: "claude-*".to_string(),
             purpose: "general-assistant".to_string(),
             environment: "production".to_string(),
+            trust_source: TrustSource::Live,
+            signature_policy: None,
+            expected_token: None,
+            namespace_registry: None,
+        }
+    }
+
+    /// Read the trust configuration from `shared`, so that replacing its
+    /// value, e.g. from a [`ConfigWatcher`](crate::reload::ConfigWatcher),
+    /// changes which issuers and auditors are trusted.
+    #[must_use]
+    pub fn with_shared_trust_config(mut self, shared: SharedConfig<TrustConfig>) -> Self {
+        self.shared_trust_config = Some(shared);
+        self
+    }
+
+    /// The shared trust configuration's current value, if one is set.
+    fn shared_trust(&self) -> Option<Arc<TrustConfig>> {
+        self.shared_trust_config.as_ref().map(SharedConfig::current)
+    }
+
+    /// Mark the trust configuration as live or cached.
+    #[must_use]
+    pub fn with_trust_source(mut self, source: TrustSource) -> Self {
+        self.trust_source = source;
+        self
+    }
+
+    /// Require the manifest's signatures to satisfy `policy`.
+    #[must_use]
+    pub fn with_signature_policy(mut self, policy: SignaturePolicy) -> Self {
+        self.signature_policy = Some(policy);
+        self
+    }
+
+    /// Require the manifest to be bound to `token`.
+    #[must_use]
+    pub fn with_expected_token(mut self, token: VcpToken) -> Self {
+        self.expected_token = Some(token);
+        self
+    }
+
+    /// Require the manifest to be bound in a namespace `registry` approves
+    /// for its issuer (see [`NamespaceRegistry::is_approved_for`]).
+    #[must_use]
+    pub fn with_namespace_registry(mut self, registry: NamespaceRegistry) -> Self {
+        self.namespace_registry = Some(registry);
+        self
+    }
+}
+
+/// Provenance of the trust data used for a verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TrustSource {
+    /// Fetched from the trust and revocation endpoints for this session.
+    #[default]
+    Live,
+    /// Loaded from a local cache because the endpoints were unreachable.
+    Cached {
+        /// When the cached data was last fetched successfully.
+        fetched_at: DateTime<Utc>,
+    },
+}
+
+// ── Degraded mode ────────────────────────────────────────────
+
+/// Soft-fail policy for devices that cannot reach trust endpoints.
+///
+/// Without a policy, verification against [`TrustSource::Cached`] data
+/// fails with [`VerificationCode::FetchFailed`]. With one, cached data no
+/// older than `max_cache_age` is accepted and the outcome is flagged
+/// `degraded`, valid for at most `max_validity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DegradedMode {
+    /// Oldest cached trust data that will still be used.
+    pub max_cache_age: chrono::Duration,
+    /// Upper bound on how long a degraded acceptance stays valid.
+    pub max_validity: chrono::Duration,
+}
+
+impl DegradedMode {
+    /// Create a policy with explicit limits.
+    pub fn new(max_cache_age: chrono::Duration, max_validity: chrono::Duration) -> Self {
+        Self {
+            max_cache_age,
+            max_validity,
+        }
+    }
+}
+
+impl Default for DegradedMode {
+    /// Accept trust data up to 7 days old; degraded results last 1 hour.
+    fn default() -> Self {
+        Self::new(chrono::Duration::days(7), chrono::Duration::hours(1))
+    }
+}
+
+/// Verification result with degraded-mode metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationOutcome {
+    /// Result of the pipeline.
+    pub code: VerificationCode,
+    /// `true` when the bundle was accepted from cached trust data.
+    pub degraded: bool,
+    /// When this acceptance should be re-checked: the manifest's `exp`,
+    /// capped by [`DegradedMode::max_validity`] for degraded results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+    /// Where the manifest failed step 2, when `code` is
+    /// [`VerificationCode::InvalidSchema`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schema_errors: Vec<SchemaViolation>,
+}
+
+impl VerificationOutcome {
+    fn failed(code: VerificationCode) -> Self {
+        Self {
+            code,
+            degraded: false,
+            valid_until: None,
+            schema_errors: Vec::new(),
+        }
+    }
+
+    fn invalid_schema(schema_errors: Vec<SchemaViolation>) -> Self {
+        Self {
+            schema_errors,
+            ..Self::failed(VerificationCode::InvalidSchema)
         }
     }
+
+    /// Returns `true` when the bundle was accepted (degraded or not).
+    pub fn is_valid(&self) -> bool {
+        self.code.is_valid()
+    }
 }
 
 // ── Replay cache ─────────────────────────────────────────────
 
 /// Cache for tracking seen JTIs to prevent replay attacks.
 ///
-/// Stores JTI strings with their expiration times. Expired entries are
-/// cleaned up automatically when the cache is queried.
+/// Stores JTI strings with their expiration times. The cache is split
+/// into independently locked shards keyed by JTI hash, so concurrent
+/// verifications rarely contend and an [`Orchestrator`] can be shared
+/// across threads.
+///
+/// Each shard keeps its entries in a min-heap by expiry, so expired
+/// entries are dropped from the front as every operation runs, at
+/// amortized O(log n) per entry rather than a full scan. If a shard
+/// still exceeds its share of `max_entries`, the least recently used
+/// unexpired entries are evicted. Such an eviction forgets a live JTI
+/// and so reopens it to replay; [`stats`](Self::stats) counts them so
+/// the limit can be raised before that matters.
 #[derive(Debug)]
 pub struct ReplayCache {
-    seen: HashMap<String, SystemTime>,
+    shards: Box<[Mutex<ReplayShard>]>,
+    hasher: RandomState,
     max_entries: usize,
+    expired_evictions: AtomicU64,
+    capacity_evictions: AtomicU64,
+    replays_detected: AtomicU64,
+    clock: Arc<dyn Clock>,
+}
+
+/// Counters for a [`ReplayCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayCacheStats {
+    /// Entries currently held, including expired ones not yet dropped.
+    pub entries: usize,
+    /// Entries dropped because they expired.
+    pub expired_evictions: u64,
+    /// Unexpired entries dropped to stay within `max_entries`.
+    pub capacity_evictions: u64,
+    /// Lookups through [`ReplayCache::check_and_record`] that found the
+    /// JTI already present.
+    pub replays_detected: u64,
 }
 
 impl ReplayCache {
     /// Create a new replay cache with the given maximum entry count.
     #[must_use]
     pub fn new(max_entries: usize) -> Self {
+        let shards = (max_entries / MIN_REPLAY_SHARD_CAPACITY).clamp(1, REPLAY_SHARDS);
         Self {
-            seen: HashMap::new(),
+            shards: (0..shards)
+                .map(|_| Mutex::new(ReplayShard::default()))
+                .collect(),
+            hasher: RandomState::new(),
             max_entries,
+            expired_evictions: AtomicU64::new(0),
+            capacity_evictions: AtomicU64::new(0),
+            replays_detected: AtomicU64::new(0),
+            clock: default_clock(),
         }
     }
 
+    /// Judge expiry against `clock` rather than the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Check whether a JTI has already been seen (and is not expired).
-    pub fn is_seen(&mut self, jti: &str) -> bool {
-        self.cleanup();
-        self.seen.contains_key(jti)
+    pub fn is_seen(&self, jti: &str) -> bool {
+        let mut shard = self.shard(jti);
+        self.expire(&mut shard);
+        shard.entries.contains_key(jti)
     }
 
     /// Record a JTI with its expiration time.
+    pub fn record(&self, jti: String, exp: SystemTime) {
+        let mut shard = self.shard(&jti);
+        self.expire(&mut shard);
+        shard.insert(jti.into(), exp);
+        self.enforce_capacity(&mut shard);
+    }
+
+    /// Record `jti` unless it is already present, in one step.
     ///
-    /// If the cache exceeds `max_entries` after insertion, expired
-    /// entries are purged.
-    pub fn record(&mut self, jti: String, exp: SystemTime) {
-        self.seen.insert(jti, exp);
-        if self.seen.len() > self.max_entries {
-            self.cleanup();
+    /// Returns `true` if `jti` was already seen, i.e. this is a replay.
+    /// Unlike [`is_seen`](Self::is_seen) followed by
+    /// [`record`](Self::record), two threads presenting the same JTI at
+    /// once cannot both be accepted. A replayed JTI counts as recently
+    /// used, so it is the last to be evicted for capacity.
+    pub fn check_and_record(&self, jti: &str, exp: SystemTime) -> bool {
+        let mut shard = self.shard(jti);
+        self.expire(&mut shard);
+        if shard.touch(jti) {
+            self.replays_detected.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+        shard.insert(jti.into(), exp);
+        self.enforce_capacity(&mut shard);
+        false
+    }
+
+    /// Current entry count and eviction counters.
+    pub fn stats(&self) -> ReplayCacheStats {
+        ReplayCacheStats {
+            entries: self.len(),
+            expired_evictions: self.expired_evictions.load(Ordering::Relaxed),
+            capacity_evictions: self.capacity_evictions.load(Ordering::Relaxed),
+            replays_detected: self.replays_detected.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Unexpired entries, in no particular order.
+    fn entries(&self) -> Vec<(String, SystemTime)> {
+        let now = self.clock.system_time();
+        self.shards
+            .iter()
+            .flat_map(|shard| {
+                lock_shard(shard)
+                    .entries
+                    .iter()
+                    .filter(|(_, entry)| entry.exp > now)
+                    .map(|(jti, entry)| (jti.to_string(), entry.exp))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn shard(&self, jti: &str) -> MutexGuard<'_, ReplayShard> {
+        let count = self.shards.len() as u64;
+        let index = usize::try_from(self.hasher.hash_one(jti) % count).unwrap_or(0);
+        lock_shard(&self.shards[index])
+    }
+
+    fn expire(&self, shard: &mut ReplayShard) {
+        let expired = shard.expire(self.clock.system_time());
+        if expired > 0 {
+            self.expired_evictions.fetch_add(expired, Ordering::Relaxed);
         }
     }
 
-    /// Remove all entries whose expiration time has passed.
-    fn cleanup(&mut self) {
-        let now = SystemTime::now();
-        self.seen.retain(|_, exp| *exp > now);
+    fn enforce_capacity(&self, shard: &mut ReplayShard) {
+        let capacity = self.max_entries.div_ceil(self.shards.len()).max(1);
+        while shard.entries.len() > capacity && shard.evict_lru() {
+            self.capacity_evictions.fetch_add(1, Ordering::Relaxed);
+        }
     }
 
     /// Number of currently tracked entries (including expired ones
-    /// that have not yet been cleaned up).
+    /// that have not yet been dropped).
     #[must_use]
     pub fn len(&self) -> usize {
-        self.seen.len()
+        self.shards
+            .iter()
+            .map(|shard| lock_shard(shard).entries.len())
+            .sum()
     }
 
     /// Returns `true` if the cache contains no entries.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.seen.is_empty()
+        self.len() == 0
+    }
+}
+
+/// Shard updates are single map operations, so a poisoned lock still
+/// guards a consistent shard.
+fn lock_shard(shard: &Mutex<ReplayShard>) -> MutexGuard<'_, ReplayShard> {
+    shard.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[derive(Debug)]
+struct ReplaySlot {
+    exp: SystemTime,
+    last_use: u64,
+}
+
+/// One lock's worth of a [`ReplayCache`].
+///
+/// `by_expiry` and `by_use` are indexes into `entries` that are cleaned
+/// lazily: an index item whose expiry or use counter no longer matches
+/// the entry is skipped when reached, and the indexes are rebuilt once
+/// stale items outnumber live ones.
+#[derive(Debug, Default)]
+struct ReplayShard {
+    entries: HashMap<Arc<str>, ReplaySlot>,
+    by_expiry: BinaryHeap<Reverse<(SystemTime, Arc<str>)>>,
+    by_use: VecDeque<(u64, Arc<str>)>,
+    uses: u64,
+}
+
+impl ReplayShard {
+    fn insert(&mut self, jti: Arc<str>, exp: SystemTime) {
+        self.uses += 1;
+        self.entries.insert(
+            Arc::clone(&jti),
+            ReplaySlot {
+                exp,
+                last_use: self.uses,
+            },
+        );
+        self.by_expiry.push(Reverse((exp, Arc::clone(&jti))));
+        self.by_use.push_back((self.uses, jti));
+        self.compact();
+    }
+
+    /// Mark `jti` as used, returning whether it is present.
+    fn touch(&mut self, jti: &str) -> bool {
+        let Some(key) = self.entries.get_key_value(jti).map(|(k, _)| Arc::clone(k)) else {
+            return false;
+        };
+        self.uses += 1;
+        if let Some(slot) = self.entries.get_mut(jti) {
+            slot.last_use = self.uses;
+        }
+        self.by_use.push_back((self.uses, key));
+        self.compact();
+        true
+    }
+
+    /// Drop entries that expired at or before `now`, returning how many.
+    fn expire(&mut self, now: SystemTime) -> u64 {
+        let mut expired = 0;
+        while let Some(Reverse((exp, _))) = self.by_expiry.peek() {
+            if *exp > now {
+                break;
+            }
+            let Some(Reverse((exp, jti))) = self.by_expiry.pop() else {
+                break;
+            };
+            if self.entries.get(&jti).is_some_and(|slot| slot.exp == exp) {
+                self.entries.remove(&jti);
+                expired += 1;
+            }
+        }
+        expired
+    }
+
+    /// Drop the least recently used entry, returning whether one was found.
+    fn evict_lru(&mut self) -> bool {
+        while let Some((last_use, jti)) = self.by_use.pop_front() {
+            if self
+                .entries
+                .get(&jti)
+                .is_some_and(|slot| slot.last_use == last_use)
+            {
+                self.entries.remove(&jti);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn compact(&mut self) {
+        let limit = 2 * self.entries.len() + 16;
+        if self.by_expiry.len() > limit {
+            self.by_expiry = self
+                .entries
+                .iter()
+                .map(|(jti, slot)| Reverse((slot.exp, Arc::clone(jti))))
+                .collect();
+        }
+        if self.by_use.len() > limit {
+            let mut by_use: Vec<_> = self
+                .entries
+                .iter()
+                .map(|(jti, slot)| (slot.last_use, Arc::clone(jti)))
+                .collect();
+            by_use.sort_unstable();
+            self.by_use = by_use.into();
+        }
     }
 }
 
@@ -186,13 +609,19 @@ impl Default for ReplayCache {
 /// signature, auditor trust, temporal claims, replay, budget, scope, and
 /// injection safety before accepting a bundle as valid.
 pub struct Orchestrator {
-    trust_config: TrustConfig,
+    trust_config: Arc<TrustConfig>,
+    shared_trust_config: Option<SharedConfig<TrustConfig>>,
     replay_cache: ReplayCache,
+    clock: Arc<dyn Clock>,
     max_manifest_size: usize,
     max_content_size: usize,
     clock_skew: Duration,
     max_exp_days: u32,
     injection_patterns: Vec<Regex>,
+    degraded_mode: Option<DegradedMode>,
+    strict_schema: bool,
+    budget_estimator: Option<BudgetEstimator>,
+    freshness: FreshnessPolicy,
 }
 
 impl Orchestrator {
@@ -209,25 +638,124 @@ impl Orchestrator {
             .collect();
 
         Self {
-            trust_config,
+            trust_config: Arc::new(trust_config),
+            shared_trust_config: None,
             replay_cache: ReplayCache::default(),
+            clock: default_clock(),
             max_manifest_size: MAX_MANIFEST_SIZE,
             max_content_size: MAX_CONTENT_SIZE,
             clock_skew: Duration::from_secs(u64::try_from(CLOCK_SKEW_MINUTES * 60).unwrap_or(300)),
             max_exp_days: u32::try_from(MAX_EXP_DAYS).unwrap_or(90),
             injection_patterns,
+            degraded_mode: None,
+            strict_schema: false,
+            budget_estimator: None,
+            freshness: FreshnessPolicy::default(),
         }
     }
 
-    /// Returns a reference to the trust configuration.
+    /// The trust configuration passed to [`new`](Self::new).
+    ///
+    /// Once a shared configuration is attached with
+    /// [`with_shared_trust_config`](Self::with_shared_trust_config), the
+    /// orchestrator uses that instead; see
+    /// [`current_trust_config`](Self::current_trust_config).
     pub fn trust_config(&self) -> &TrustConfig {
         &self.trust_config
     }
 
+    /// The trust configuration in use: the shared one's current value if
+    /// attached, otherwise [`trust_config`](Self::trust_config).
+    pub fn current_trust_config(&self) -> Arc<TrustConfig> {
+        self.shared_trust_config
+            .as_ref()
+            .map_or_else(|| Arc::clone(&self.trust_config), SharedConfig::current)
+    }
+
+    /// Read the trust configuration from `shared`, so that replacing its
+    /// value, e.g. from a [`ConfigWatcher`](crate::reload::ConfigWatcher),
+    /// takes effect without rebuilding the orchestrator.
+    #[must_use]
+    pub fn with_shared_trust_config(mut self, shared: SharedConfig<TrustConfig>) -> Self {
+        self.shared_trust_config = Some(shared);
+        self
+    }
+
+    /// The shared trust configuration, if one is attached; replacing its
+    /// value updates this orchestrator.
+    pub fn shared_trust_config(&self) -> Option<&SharedConfig<TrustConfig>> {
+        self.shared_trust_config.as_ref()
+    }
+
+    /// A [`VerificationContext`] with this orchestrator's trust
+    /// configuration. With a shared configuration attached, the context
+    /// shares it too, so that it follows reloads.
+    pub fn verification_context(&self) -> VerificationContext {
+        match &self.shared_trust_config {
+            Some(shared) => VerificationContext::new(TrustConfig::default())
+                .with_shared_trust_config(shared.clone()),
+            None => VerificationContext::new((*self.trust_config).clone()),
+        }
+    }
+
+    /// Replay cache size and eviction counters.
+    pub fn replay_stats(&self) -> ReplayCacheStats {
+        self.replay_cache.stats()
+    }
+
     /// Create an orchestrator with a custom replay cache.
+    ///
+    /// The cache is switched to the orchestrator's clock.
     #[must_use]
     pub fn with_replay_cache(mut self, cache: ReplayCache) -> Self {
-        self.replay_cache = cache;
+        self.replay_cache = cache.with_clock(Arc::clone(&self.clock));
+        self
+    }
+
+    /// Read the current time from `clock` for temporal checks, trust
+    /// anchor validity, key pinning, freshness and the replay cache.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.replay_cache.clock = Arc::clone(&clock);
+        self.clock = clock;
+        self
+    }
+
+    /// Allow verification from cached trust data under the given policy.
+    #[must_use]
+    pub fn with_degraded_mode(mut self, mode: DegradedMode) -> Self {
+        self.degraded_mode = Some(mode);
+        self
+    }
+
+    /// Validate manifests against the full embedded JSON Schema in step 2.
+    ///
+    /// By default step 2 only checks the fields later steps depend on.
+    /// With strict validation every field, pattern and format in the
+    /// manifest's schema version is enforced, and each failure is listed in
+    /// [`VerificationOutcome::schema_errors`].
+    #[must_use]
+    pub fn with_schema_validation(mut self) -> Self {
+        self.strict_schema = true;
+        self
+    }
+
+    /// Count the content's tokens in step 9 instead of trusting
+    /// `budget.token_count`.
+    ///
+    /// A declared count outside the estimator's tolerance fails with
+    /// [`VerificationCode::BudgetExceeded`]; a manifest that declares no
+    /// count is checked against the estimate.
+    #[must_use]
+    pub fn with_budget_estimator(mut self, estimator: BudgetEstimator) -> Self {
+        self.budget_estimator = Some(estimator);
+        self
+    }
+
+    /// Warning windows for [`check_freshness`](Self::check_freshness).
+    #[must_use]
+    pub fn with_freshness_policy(mut self, policy: FreshnessPolicy) -> Self {
+        self.freshness = policy;
         self
     }
 
@@ -235,63 +763,290 @@ impl Orchestrator {
     ///
     /// Returns a [`VerificationCode`] indicating the result. The first
     /// failing step short-circuits and returns the corresponding code.
+    /// A degraded acceptance also returns [`VerificationCode::Valid`]; use
+    /// [`verify_outcome`](Self::verify_outcome) to see the flag.
     ///
     /// # Arguments
     ///
     /// * `manifest_json` - JSON string of the VCP manifest.
     /// * `body` - The constitution content to verify.
     /// * `ctx` - Verification context with trust config and runtime parameters.
-    #[allow(clippy::too_many_lines)]
     pub fn verify(
-        &mut self,
+        &self,
         manifest_json: &str,
         body: &str,
         ctx: &VerificationContext,
     ) -> VerificationCode {
-        // Step 1: Size limits.
-        if manifest_json.len() > self.max_manifest_size || body.len() > self.max_content_size {
-            return VerificationCode::SizeExceeded;
+        self.verify_outcome(manifest_json, body, ctx).code
+    }
+
+    /// Run the pipeline and report whether the result is degraded.
+    ///
+    /// With [`TrustSource::Cached`] trust data this fails with
+    /// [`VerificationCode::FetchFailed`] unless a [`DegradedMode`] is
+    /// configured and the cache is younger than its `max_cache_age`.
+    pub fn verify_outcome(
+        &self,
+        manifest_json: &str,
+        body: &str,
+        ctx: &VerificationContext,
+    ) -> VerificationOutcome {
+        self.verify_content(manifest_json, Content::Single(body), ctx, None)
+    }
+
+    /// Run the pipeline over a multi-file bundle.
+    ///
+    /// Step 3 checks every file against `bundle.files`; the size limit,
+    /// budget and safety scan apply to all files together.
+    pub fn verify_files(
+        &self,
+        manifest_json: &str,
+        contents: &BundleContents,
+        ctx: &VerificationContext,
+    ) -> VerificationCode {
+        self.verify_files_outcome(manifest_json, contents, ctx).code
+    }
+
+    /// As [`verify_files`](Self::verify_files), reporting whether the
+    /// result is degraded.
+    pub fn verify_files_outcome(
+        &self,
+        manifest_json: &str,
+        contents: &BundleContents,
+        ctx: &VerificationContext,
+    ) -> VerificationOutcome {
+        self.verify_content(manifest_json, Content::Files(contents), ctx, None)
+    }
+
+    /// Run the pipeline over a manifest delivered as a compact JWS.
+    ///
+    /// The JWS is decoded with [`Jws::decode`] (a malformed token fails
+    /// with [`VerificationCode::InvalidSchema`]) and its signature is
+    /// checked in step 5 against the issuer's trusted key, alongside any
+    /// signature embedded in the manifest. The JWT claims have already
+    /// been folded into `timestamps`, so step 7 sees them.
+    pub fn verify_jws(&self, jws: &str, body: &str, ctx: &VerificationContext) -> VerificationCode {
+        self.verify_jws_outcome(jws, body, ctx).code
+    }
+
+    /// As [`verify_jws`](Self::verify_jws), reporting whether the result
+    /// is degraded.
+    pub fn verify_jws_outcome(
+        &self,
+        jws: &str,
+        body: &str,
+        ctx: &VerificationContext,
+    ) -> VerificationOutcome {
+        let jws = match Jws::decode(jws) {
+            Ok(jws) => jws,
+            Err(e) => {
+                return VerificationOutcome::invalid_schema(vec![SchemaViolation::new(
+                    "",
+                    "jws",
+                    e.to_string(),
+                )]);
+            }
+        };
+        let manifest_json = jws.manifest.to_string();
+        self.verify_content(&manifest_json, Content::Single(body), ctx, Some(&jws))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vcp.verify",
+            level = "debug",
+            skip_all,
+            fields(
+                manifest_bytes = manifest_json.len(),
+                jws = jws.is_some(),
+                code = tracing::field::Empty,
+                degraded = tracing::field::Empty,
+            ),
+        )
+    )]
+    fn verify_content(
+        &self,
+        manifest_json: &str,
+        content: Content<'_>,
+        ctx: &VerificationContext,
+        jws: Option<&Jws>,
+    ) -> VerificationOutcome {
+        let outcome = self.verify_steps(manifest_json, content, ctx, jws);
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("code", tracing::field::display(outcome.code));
+            span.record("degraded", outcome.degraded);
         }
+        outcome
+    }
+
+    fn verify_steps(
+        &self,
+        manifest_json: &str,
+        content: Content<'_>,
+        ctx: &VerificationContext,
+        jws: Option<&Jws>,
+    ) -> VerificationOutcome {
+        let now = self.clock.now();
+        let degraded = match (ctx.trust_source, self.degraded_mode) {
+            (TrustSource::Live, _) => None,
+            (TrustSource::Cached { .. }, None) => {
+                return VerificationOutcome::failed(VerificationCode::FetchFailed);
+            }
+            (TrustSource::Cached { fetched_at }, Some(mode)) => {
+                if now - fetched_at > mode.max_cache_age {
+                    return VerificationOutcome::failed(VerificationCode::FetchFailed);
+                }
+                Some(mode)
+            }
+        };
 
-        // Step 2: Parse manifest JSON + validate required fields.
-        let Ok(manifest) = serde_json::from_str::<Value>(manifest_json) else {
-            return VerificationCode::InvalidSchema;
+        // Step 1: Size limits.
+        let content_size = match content {
+            Content::Single(body) => body.len(),
+            Content::Files(contents) => contents.total_len(),
         };
-        let Some(bundle) = manifest.get("bundle") else {
-            return VerificationCode::InvalidSchema;
+        if manifest_json.len() > self.max_manifest_size || content_size > self.max_content_size {
+            return VerificationOutcome::failed(VerificationCode::SizeExceeded);
+        }
+
+        // Step 2: Parse manifest JSON + schema validation.
+        let (raw, manifest) = match self.parse_manifest(manifest_json) {
+            Ok(parsed) => parsed,
+            Err(violations) => return VerificationOutcome::invalid_schema(violations),
         };
-        let Some(hash) = bundle.get("content_hash").and_then(Value::as_str) else {
-            return VerificationCode::InvalidSchema;
+
+        if let Err(code) = self.run_pipeline(&raw, &manifest, content, ctx, jws) {
+            return VerificationOutcome::failed(code);
+        }
+
+        let exp = manifest.timestamps.as_ref().and_then(|t| t.exp);
+        let valid_until = match degraded {
+            None => exp,
+            Some(mode) => {
+                let cap = now + mode.max_validity;
+                Some(exp.map_or(cap, |exp| exp.min(cap)))
+            }
         };
 
-        // Step 3: Content hash verification.
-        if !matches!(verify_content_hash(body, hash), Ok(true)) {
-            return VerificationCode::HashMismatch;
+        VerificationOutcome {
+            code: VerificationCode::Valid,
+            degraded: degraded.is_some(),
+            valid_until,
+            schema_errors: Vec::new(),
+        }
+    }
+
+    /// Step 2: parse the manifest and check it against the schema.
+    ///
+    /// Without [`with_schema_validation`](Self::with_schema_validation) only
+    /// `bundle.content_hash`, which step 3 needs, is required here; later
+    /// steps report their own missing fields. The raw value is returned
+    /// alongside the typed [`Manifest`] because signatures cover fields the
+    /// typed form drops.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vcp.verify.parse_manifest",
+            level = "debug",
+            skip_all,
+            fields(bytes = manifest_json.len(), strict = self.strict_schema),
+        )
+    )]
+    fn parse_manifest(
+        &self,
+        manifest_json: &str,
+    ) -> Result<(Value, Manifest), Vec<SchemaViolation>> {
+        let manifest = serde_json::from_str::<Value>(manifest_json).map_err(|e| {
+            vec![SchemaViolation::new(
+                "",
+                "json",
+                format!("invalid JSON: {e}"),
+            )]
+        })?;
+
+        if self.strict_schema {
+            let violations = validate_manifest(&manifest);
+            if !violations.is_empty() {
+                return Err(violations);
+            }
+        } else {
+            let Some(bundle) = manifest.get("bundle") else {
+                return Err(vec![SchemaViolation::new(
+                    "",
+                    "required",
+                    "missing required property bundle",
+                )]);
+            };
+            match bundle.get("content_hash") {
+                Some(Value::String(_)) => {}
+                Some(_) => {
+                    return Err(vec![SchemaViolation::new(
+                        "/bundle/content_hash",
+                        "type",
+                        "expected string",
+                    )]);
+                }
+                None => {
+                    return Err(vec![SchemaViolation::new(
+                        "/bundle",
+                        "required",
+                        "missing required property content_hash",
+                    )]);
+                }
+            }
+        }
+
+        match Manifest::deserialize(&manifest) {
+            Ok(typed) => Ok((manifest, typed)),
+            Err(e) => Err(vec![SchemaViolation::new("", "type", e.to_string())]),
         }
+    }
+
+    /// Steps 3-11 over an already-parsed manifest.
+    fn run_pipeline(
+        &self,
+        raw: &Value,
+        manifest: &Manifest,
+        content: Content<'_>,
+        ctx: &VerificationContext,
+        jws: Option<&Jws>,
+    ) -> Result<(), VerificationCode> {
+        // Step 3: Content hash verification.
+        let body = Self::verify_hash(manifest, content)?;
+        let body = body.as_ref();
 
         // Steps 4-5: Issuer trust + signature.
-        if let Some(code) = self.verify_issuer(&manifest, ctx) {
-            return code;
+        if let Some(code) = self.verify_issuer(raw, manifest, ctx, jws) {
+            return Err(code);
         }
 
         // Step 6: Auditor trust + attestation.
-        if let Some(code) = Self::verify_attestation(&manifest, ctx) {
-            return code;
+        if let Some(code) = self.verify_attestation(manifest, ctx) {
+            return Err(code);
         }
 
         // Steps 7-8: Temporal validation + replay detection.
-        if let Some(code) = self.verify_temporal(&manifest) {
-            return code;
+        if let Some(code) = self.verify_temporal(manifest) {
+            return Err(code);
         }
 
         // Step 9: Token budget validation.
-        if let Some(code) = Self::verify_budget(&manifest, ctx) {
-            return code;
+        if let Some(code) = self.verify_budget(manifest, body, ctx) {
+            return Err(code);
         }
 
-        // Step 10: Scope verification.
-        if let Some(code) = Self::verify_scope(&manifest, ctx) {
-            return code;
+        // Step 10: Scope verification + token binding.
+        if let Some(code) = Self::verify_scope(manifest, ctx) {
+            return Err(code);
+        }
+        if let Some(code) = Self::verify_token_binding(manifest, ctx) {
+            return Err(code);
+        }
+        if let Some(code) = Self::verify_binding_namespace(manifest, ctx) {
+            return Err(code);
         }
 
         // Step 11: Content safety scan.
@@ -300,72 +1055,175 @@ impl Orchestrator {
         let _safety_issues = self.scan_for_injection(body);
 
         // Step 12: All checks passed.
-        VerificationCode::Valid
+        Ok(())
+    }
+
+    /// Step 3: check the content against the manifest's hashes and return
+    /// the body the later steps scan.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vcp.verify.content_hash",
+            level = "debug",
+            skip_all,
+            fields(files = matches!(content, Content::Files(_))),
+            err(Display),
+        )
+    )]
+    fn verify_hash<'c>(
+        manifest: &Manifest,
+        content: Content<'c>,
+    ) -> Result<Cow<'c, str>, VerificationCode> {
+        match content {
+            Content::Single(body) => {
+                if !matches!(
+                    verify_content_hash(body, &manifest.bundle.content_hash),
+                    Ok(true)
+                ) {
+                    return Err(VerificationCode::HashMismatch);
+                }
+                Ok(Cow::Borrowed(body))
+            }
+            Content::Files(contents) => {
+                let result = contents.verify(manifest);
+                if !result.is_valid() {
+                    return Err(result.code);
+                }
+                Ok(Cow::Owned(contents.select(manifest, &[])))
+            }
+        }
     }
 
     /// Verify issuer trust and signature (steps 4-5).
     ///
     /// Returns `Some(code)` on failure, `None` on success.
-    #[allow(clippy::unused_self)] // Method, not associated fn, for API consistency.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.verify.issuer", level = "debug", skip_all, ret)
+    )]
     fn verify_issuer(
         &self,
-        manifest: &Value,
+        raw: &Value,
+        manifest: &Manifest,
         ctx: &VerificationContext,
+        jws: Option<&Jws>,
     ) -> Option<VerificationCode> {
-        let Some(issuer) = manifest.get("issuer") else {
-            return Some(VerificationCode::InvalidSchema);
-        };
-        let Some(issuer_id) = issuer.get("id").and_then(Value::as_str) else {
+        let Some(issuer) = &manifest.issuer else {
             return Some(VerificationCode::InvalidSchema);
         };
-        let issuer_key_id = issuer.get("key_id").and_then(Value::as_str);
-        let Some(anchor) = ctx.trust_config.get_issuer_key(issuer_id, issuer_key_id) else {
-            return Some(VerificationCode::UntrustedIssuer);
+        let key_id = issuer
+            .key_id
+            .as_deref()
+            .or_else(|| jws.and_then(|jws| jws.header.kid.as_deref()));
+        let now = self.clock.now();
+        let shared = ctx.shared_trust();
+        let trust = shared.as_deref().unwrap_or(&ctx.trust_config);
+        let (key_bytes, pins) = match trust.get_issuer_key_at(&issuer.id, key_id, now) {
+            Some(anchor) => (anchor.public_key_bytes(), None),
+            None => match Self::first_use_key(manifest, trust, jws) {
+                Ok((key, pins)) => (Some(key), Some(pins)),
+                Err(code) => return Some(code),
+            },
         };
 
         // Signature verification (only if manifest contains a signature).
-        if let Some(sig_value) = manifest
-            .get("signature")
-            .and_then(|s| s.get("value"))
-            .and_then(Value::as_str)
-        {
-            let raw_b64 = anchor
-                .public_key
-                .strip_prefix("base64:")
-                .unwrap_or(&anchor.public_key);
-
-            let Ok(key_bytes) =
-                base64::Engine::decode(&base64::engine::general_purpose::STANDARD, raw_b64)
-            else {
+        if let Some(signature) = &manifest.signature {
+            let Some(key_bytes) = &key_bytes else {
                 return Some(VerificationCode::InvalidSignature);
             };
 
             if !matches!(
-                verify_manifest_signature(manifest, &key_bytes, sig_value),
+                verify_manifest_signature(raw, key_bytes, &signature.value),
                 Ok(true)
             ) {
                 return Some(VerificationCode::InvalidSignature);
             }
         }
 
+        // The JWS envelope, when the manifest arrived as one.
+        if let Some(jws) = jws {
+            let verified = key_bytes
+                .as_deref()
+                .is_some_and(|key| matches!(jws.verify(key), Ok(true)));
+            if !verified {
+                return Some(VerificationCode::InvalidSignature);
+            }
+        }
+
+        // Trust on first use: the signature is good, so pin the key or
+        // hold a changed key for approval.
+        if let (Some(pins), Some(key)) = (pins, &key_bytes) {
+            if pins.observe_at(&issuer.id, key, now) == PinStatus::Changed {
+                return Some(VerificationCode::UntrustedIssuer);
+            }
+        }
+
+        // Co-signatures (issuer, organization, auditor) under the policy.
+        if let Some(policy) = &ctx.signature_policy {
+            let Ok(report) = verify_all_signatures_at(raw, trust, now) else {
+                return Some(VerificationCode::InvalidSchema);
+            };
+            if !report.satisfies(policy) {
+                return Some(VerificationCode::InvalidSignature);
+            }
+        }
+
         None
     }
 
+    /// The manifest's own `issuer.public_key`, for an issuer with no anchor
+    /// under trust-on-first-use. Only signed manifests qualify.
+    fn first_use_key<'a>(
+        manifest: &Manifest,
+        trust: &'a TrustConfig,
+        jws: Option<&Jws>,
+    ) -> Result<(Vec<u8>, &'a KeyPins), VerificationCode> {
+        let pins = trust.key_pins().ok_or(VerificationCode::UntrustedIssuer)?;
+        if manifest.signature.is_none() && jws.is_none() {
+            return Err(VerificationCode::UntrustedIssuer);
+        }
+        let presented = manifest
+            .issuer
+            .as_ref()
+            .and_then(|issuer| issuer.public_key.as_deref())
+            .ok_or(VerificationCode::UntrustedIssuer)?;
+        let key = import_public_key(
+            presented
+                .strip_prefix("ed25519:")
+                .unwrap_or(presented)
+                .as_bytes(),
+        )
+        .map_err(|_| VerificationCode::UntrustedIssuer)?;
+        Ok((key.to_vec(), pins))
+    }
+
     /// Verify auditor trust and safety attestation (step 6).
-    fn verify_attestation(manifest: &Value, ctx: &VerificationContext) -> Option<VerificationCode> {
-        let Some(attestation) = manifest.get("safety_attestation") else {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.verify.attestation", level = "debug", skip_all, ret)
+    )]
+    fn verify_attestation(
+        &self,
+        manifest: &Manifest,
+        ctx: &VerificationContext,
+    ) -> Option<VerificationCode> {
+        let Some(attestation) = &manifest.safety_attestation else {
             return None; // No attestation present is acceptable.
         };
 
-        let Some(auditor_id) = attestation.get("auditor").and_then(Value::as_str) else {
+        let Some(auditor_id) = &attestation.auditor else {
             return Some(VerificationCode::InvalidAttestation);
         };
 
-        let auditor_key_id = attestation.get("auditor_key_id").and_then(Value::as_str);
-
-        if ctx
-            .trust_config
-            .get_auditor_key(auditor_id, auditor_key_id)
+        let shared = ctx.shared_trust();
+        if shared
+            .as_deref()
+            .unwrap_or(&ctx.trust_config)
+            .get_auditor_key_at(
+                auditor_id,
+                attestation.auditor_key_id.as_deref(),
+                self.clock.now(),
+            )
             .is_none()
         {
             return Some(VerificationCode::UntrustedAuditor);
@@ -375,87 +1233,87 @@ impl Orchestrator {
     }
 
     /// Verify temporal claims and replay detection (steps 7-8).
-    fn verify_temporal(&mut self, manifest: &Value) -> Option<VerificationCode> {
-        let timestamps = manifest.get("timestamps")?;
-        let now = chrono::Utc::now();
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.verify.temporal", level = "debug", skip_all, ret)
+    )]
+    fn verify_temporal(&self, manifest: &Manifest) -> Option<VerificationCode> {
+        let timestamps = manifest.timestamps.as_ref()?;
+        let now = self.clock.now();
 
         // nbf -- not before.
-        if let Some(nbf_str) = timestamps.get("nbf").and_then(Value::as_str) {
-            if let Ok(nbf) = chrono::DateTime::parse_from_rfc3339(nbf_str) {
-                if now < nbf {
-                    return Some(VerificationCode::NotYetValid);
-                }
-            }
+        if timestamps.nbf.is_some_and(|nbf| now < nbf) {
+            return Some(VerificationCode::NotYetValid);
         }
 
         // exp -- expiration.
-        if let Some(exp_str) = timestamps.get("exp").and_then(Value::as_str) {
-            if let Ok(exp) = chrono::DateTime::parse_from_rfc3339(exp_str) {
-                if now > exp {
-                    return Some(VerificationCode::Expired);
-                }
-            }
+        if timestamps.exp.is_some_and(|exp| now > exp) {
+            return Some(VerificationCode::Expired);
         }
 
         // iat -- issued at, clock skew + max expiration check.
-        if let Some(iat_str) = timestamps.get("iat").and_then(Value::as_str) {
-            if let Ok(iat) = chrono::DateTime::parse_from_rfc3339(iat_str) {
-                let skew = chrono::Duration::minutes(CLOCK_SKEW_MINUTES);
-                if iat > now + skew {
-                    return Some(VerificationCode::FutureTimestamp);
-                }
+        if let Some(iat) = timestamps.iat {
+            let skew = chrono::Duration::minutes(CLOCK_SKEW_MINUTES);
+            if iat > now + skew {
+                return Some(VerificationCode::FutureTimestamp);
+            }
 
-                if let Some(exp_str) = timestamps.get("exp").and_then(Value::as_str) {
-                    if let Ok(exp) = chrono::DateTime::parse_from_rfc3339(exp_str) {
-                        let max_exp = iat + chrono::Duration::days(i64::from(self.max_exp_days));
-                        if exp > max_exp {
-                            return Some(VerificationCode::Expired);
-                        }
-                    }
-                }
+            let max_exp = iat + chrono::Duration::days(i64::from(self.max_exp_days));
+            if timestamps.exp.is_some_and(|exp| exp > max_exp) {
+                return Some(VerificationCode::Expired);
             }
         }
 
         // Replay detection (JTI).
-        if let Some(jti) = timestamps.get("jti").and_then(Value::as_str) {
-            if self.replay_cache.is_seen(jti) {
-                return Some(VerificationCode::ReplayDetected);
-            }
-
+        if let Some(jti) = &timestamps.jti {
             let cache_exp = timestamps
-                .get("exp")
-                .and_then(Value::as_str)
-                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                .exp
                 .and_then(|exp| {
-                    exp.signed_duration_since(chrono::DateTime::UNIX_EPOCH)
+                    exp.signed_duration_since(DateTime::UNIX_EPOCH)
                         .to_std()
                         .ok()
                         .map(|d| SystemTime::UNIX_EPOCH + d)
                 })
-                .unwrap_or_else(|| SystemTime::now() + self.clock_skew);
+                .unwrap_or_else(|| self.clock.system_time() + self.clock_skew);
 
-            self.replay_cache.record(jti.to_string(), cache_exp);
+            if self.replay_cache.check_and_record(jti, cache_exp) {
+                return Some(VerificationCode::ReplayDetected);
+            }
         }
 
         None
     }
 
     /// Verify token budget constraints (step 9).
-    fn verify_budget(manifest: &Value, ctx: &VerificationContext) -> Option<VerificationCode> {
-        let budget = manifest.get("budget")?;
-        let token_count = budget.get("token_count").and_then(Value::as_u64)?;
-
-        let max_share = budget
-            .get("max_context_share")
-            .and_then(Value::as_f64)
-            .unwrap_or(0.25);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.verify.budget", level = "debug", skip_all, ret)
+    )]
+    fn verify_budget(
+        &self,
+        manifest: &Manifest,
+        body: &str,
+        ctx: &VerificationContext,
+    ) -> Option<VerificationCode> {
+        let budget = manifest.budget.as_ref()?;
+        let tokenizer = budget.tokenizer.as_deref();
+        let token_count = match (&self.budget_estimator, budget.token_count) {
+            (Some(estimator), Some(declared)) => {
+                if estimator.check(declared, tokenizer, body).is_err() {
+                    return Some(VerificationCode::BudgetExceeded);
+                }
+                declared
+            }
+            (Some(estimator), None) => estimator.estimate(tokenizer, body),
+            (None, declared) => declared?,
+        };
 
         #[allow(
             clippy::cast_possible_truncation,
             clippy::cast_sign_loss,
             clippy::cast_precision_loss
         )]
-        let max_tokens = (ctx.model_context_limit as f64 * max_share) as u64;
+        let max_tokens = (ctx.model_context_limit as f64 * budget.max_context_share()) as u64;
 
         if token_count > max_tokens {
             return Some(VerificationCode::BudgetExceeded);
@@ -465,36 +1323,94 @@ impl Orchestrator {
     }
 
     /// Verify scope binding (step 10).
-    fn verify_scope(manifest: &Value, ctx: &VerificationContext) -> Option<VerificationCode> {
-        let scope = manifest.get("scope")?;
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.verify.scope", level = "debug", skip_all, ret)
+    )]
+    fn verify_scope(manifest: &Manifest, ctx: &VerificationContext) -> Option<VerificationCode> {
+        let scope = manifest.scope.as_ref()?;
 
         // Model family check (glob matching).
-        if let Some(families) = scope.get("model_families").and_then(Value::as_array) {
-            let strs: Vec<&str> = families.iter().filter_map(Value::as_str).collect();
-            if !strs.is_empty() && !strs.iter().any(|pat| glob_match(pat, &ctx.model_family)) {
-                return Some(VerificationCode::ScopeMismatch);
-            }
+        if !scope.model_families.is_empty()
+            && !scope
+                .model_families
+                .iter()
+                .any(|pat| glob_match(pat, &ctx.model_family))
+        {
+            return Some(VerificationCode::ScopeMismatch);
         }
 
         // Purpose check.
-        if let Some(purposes) = scope.get("purposes").and_then(Value::as_array) {
-            let strs: Vec<&str> = purposes.iter().filter_map(Value::as_str).collect();
-            if !strs.is_empty() && !strs.contains(&ctx.purpose.as_str()) {
-                return Some(VerificationCode::ScopeMismatch);
-            }
+        if !scope.purposes.is_empty() && !scope.purposes.contains(&ctx.purpose) {
+            return Some(VerificationCode::ScopeMismatch);
         }
 
         // Environment check.
-        if let Some(envs) = scope.get("environments").and_then(Value::as_array) {
-            let strs: Vec<&str> = envs.iter().filter_map(Value::as_str).collect();
-            if !strs.is_empty() && !strs.contains(&ctx.environment.as_str()) {
-                return Some(VerificationCode::ScopeMismatch);
-            }
+        if !scope.environments.is_empty() && !scope.environments.contains(&ctx.environment) {
+            return Some(VerificationCode::ScopeMismatch);
         }
 
         None
     }
 
+    /// Verify the manifest is bound to the expected token (step 10).
+    ///
+    /// Without an expected token nothing is checked. With one, a manifest
+    /// that declares no binding does not match.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.verify.token_binding", level = "debug", skip_all, ret)
+    )]
+    fn verify_token_binding(
+        manifest: &Manifest,
+        ctx: &VerificationContext,
+    ) -> Option<VerificationCode> {
+        let expected = ctx.expected_token.as_ref()?;
+        let declared = manifest
+            .binding
+            .as_ref()
+            .map(ManifestBinding::token_binding);
+        match declared {
+            Some(Ok(Some(binding))) if binding.matches(expected) => None,
+            Some(Err(_)) => Some(VerificationCode::InvalidSchema),
+            _ => Some(VerificationCode::TokenMismatch),
+        }
+    }
+
+    /// Verify the manifest is bound in a namespace approved for its
+    /// issuer (step 10).
+    ///
+    /// Without a namespace registry nothing is checked. With one, a
+    /// manifest whose binding names no namespace, or whose issuer is not
+    /// the namespace owner's, does not match.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "vcp.verify.binding_namespace", level = "debug", skip_all, ret)
+    )]
+    fn verify_binding_namespace(
+        manifest: &Manifest,
+        ctx: &VerificationContext,
+    ) -> Option<VerificationCode> {
+        let registry = ctx.namespace_registry.as_ref()?;
+        let issuer = manifest.issuer.as_ref().map(|issuer| issuer.id.as_str());
+        let declared = manifest
+            .binding
+            .as_ref()
+            .map(ManifestBinding::token_binding);
+        match declared {
+            Some(Ok(Some(binding)))
+                if binding
+                    .namespace()
+                    .zip(issuer)
+                    .is_some_and(|(ns, issuer)| registry.is_approved_for(ns, issuer)) =>
+            {
+                None
+            }
+            Some(Err(_)) => Some(VerificationCode::InvalidSchema),
+            _ => Some(VerificationCode::ScopeMismatch),
+        }
+    }
+
     /// Verify a bundle, returning `Ok(())` on success or a [`VcpError`] on failure.
     ///
     /// # Errors
@@ -502,23 +1418,36 @@ impl Orchestrator {
     /// Returns a [`VcpError::ParseError`] containing the verification code
     /// description when verification fails.
     pub fn verify_or_err(
-        &mut self,
+        &self,
         manifest_json: &str,
         body: &str,
         ctx: &VerificationContext,
     ) -> VcpResult<()> {
-        let code = self.verify(manifest_json, body, ctx);
-        if code.is_valid() {
-            Ok(())
-        } else {
-            Err(VcpError::ParseError(format!("verification failed: {code}")))
+        let outcome = self.verify_outcome(manifest_json, body, ctx);
+        if outcome.is_valid() {
+            return Ok(());
+        }
+        let mut message = format!("verification failed: {}", outcome.code);
+        for violation in &outcome.schema_errors {
+            message.push_str("; ");
+            message.push_str(&violation.to_string());
         }
+        Err(VcpError::ParseError(message))
     }
 
     /// Scan content for injection patterns and forbidden characters.
     ///
     /// Returns a list of human-readable descriptions of each finding.
     #[must_use]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vcp.verify.safety_scan",
+            level = "debug",
+            skip_all,
+            fields(bytes = content.len(), findings = tracing::field::Empty),
+        )
+    )]
     pub fn scan_for_injection(&self, content: &str) -> Vec<String> {
         let mut findings = Vec::new();
 
@@ -536,10 +1465,427 @@ impl Orchestrator {
             }
         }
 
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("findings", findings.len());
         findings
     }
 }
 
+/// What a manifest is verified against.
+#[derive(Clone, Copy)]
+enum Content<'a> {
+    Single(&'a str),
+    Files(&'a BundleContents),
+}
+
+// ── Freshness advisories ─────────────────────────────────────
+
+/// Warning windows for [`Orchestrator::check_freshness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreshnessPolicy {
+    /// Warn when the manifest expires within this window.
+    pub manifest_warning: chrono::Duration,
+    /// Warn when an issuer or auditor anchor expires within this window.
+    pub anchor_warning: chrono::Duration,
+    /// Cached CRLs older than this are stale.
+    pub max_crl_age: chrono::Duration,
+}
+
+impl Default for FreshnessPolicy {
+    /// 14 days for manifests, 30 for anchors, 24 hours for CRLs.
+    fn default() -> Self {
+        Self {
+            manifest_warning: chrono::Duration::days(14),
+            anchor_warning: chrono::Duration::days(30),
+            max_crl_age: chrono::Duration::hours(24),
+        }
+    }
+}
+
+/// Something about a manifest that will stop it verifying unless renewed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FreshnessAdvisory {
+    /// `timestamps.exp` has passed.
+    Expired {
+        /// The manifest's `timestamps.exp`.
+        expired_at: DateTime<Utc>,
+    },
+    /// `timestamps.exp` falls within the warning window.
+    ExpiresSoon {
+        /// The manifest's `timestamps.exp`.
+        expires_at: DateTime<Utc>,
+        /// Whole days until `expires_at`.
+        days_left: i64,
+    },
+    /// The trust anchor for the issuer or auditor expires within the
+    /// warning window; `days_left` is negative once it has expired.
+    AnchorExpiring {
+        /// The issuer or auditor the anchor belongs to.
+        entity_id: String,
+        /// The anchor's key ID.
+        key_id: String,
+        /// Whether the anchor is an issuer's or an auditor's.
+        anchor_type: AnchorType,
+        /// End of the anchor's validity window.
+        valid_until: DateTime<Utc>,
+        /// Whole days until `valid_until`.
+        days_left: i64,
+    },
+    /// The cached CRL for `revocation.crl_uri` is older than the policy allows.
+    CrlStale {
+        /// The manifest's `revocation.crl_uri`.
+        uri: String,
+        /// When the cached CRL was fetched.
+        fetched_at: DateTime<Utc>,
+        /// Whole hours since `fetched_at`.
+        age_hours: i64,
+    },
+}
+
+impl FreshnessAdvisory {
+    /// `true` if verification already fails because of this advisory.
+    pub fn is_expired(&self) -> bool {
+        match self {
+            Self::Expired { .. } => true,
+            Self::AnchorExpiring { days_left, .. } => *days_left < 0,
+            Self::ExpiresSoon { .. } | Self::CrlStale { .. } => false,
+        }
+    }
+}
+
+impl std::fmt::Display for FreshnessAdvisory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expired { expired_at } => {
+                write!(f, "manifest expired at {}", expired_at.to_rfc3339())
+            }
+            Self::ExpiresSoon {
+                expires_at,
+                days_left,
+            } => write!(
+                f,
+                "manifest expires in {days_left} day(s) ({})",
+                expires_at.to_rfc3339()
+            ),
+            Self::AnchorExpiring {
+                entity_id,
+                key_id,
+                anchor_type,
+                valid_until,
+                days_left,
+            } => {
+                let role = match anchor_type {
+                    AnchorType::Issuer => "issuer",
+                    AnchorType::Auditor => "auditor",
+                    AnchorType::Organization => "organization",
+                };
+                if *days_left < 0 {
+                    write!(
+                        f,
+                        "{role} anchor {entity_id}/{key_id} expired {} day(s) ago ({})",
+                        -days_left,
+                        valid_until.to_rfc3339()
+                    )
+                } else {
+                    write!(
+                        f,
+                        "{role} anchor {entity_id}/{key_id} expires in {days_left} day(s) ({})",
+                        valid_until.to_rfc3339()
+                    )
+                }
+            }
+            Self::CrlStale { uri, age_hours, .. } => {
+                write!(f, "CRL {uri} is {age_hours} hour(s) old")
+            }
+        }
+    }
+}
+
+impl Orchestrator {
+    /// Report what will need renewing before `manifest_json` stops
+    /// verifying: its own expiry, and the issuer and auditor anchors it
+    /// relies on in this orchestrator's trust config.
+    ///
+    /// Advisories are ordered manifest, issuer anchor, auditor anchor. An
+    /// empty list means nothing expires within the
+    /// [`FreshnessPolicy`] windows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] or [`VcpError::ParseError`] if the
+    /// manifest cannot be parsed.
+    pub fn check_freshness(&self, manifest_json: &str) -> VcpResult<Vec<FreshnessAdvisory>> {
+        self.check_freshness_at(manifest_json, self.clock.now(), &[])
+    }
+
+    /// As [`check_freshness`](Self::check_freshness) at a given time, also
+    /// reporting a stale CRL when `crls` (from
+    /// [`RevocationChecker::cached_crls`]) holds the manifest's
+    /// `revocation.crl_uri`.
+    ///
+    /// # Errors
+    ///
+    /// As [`check_freshness`](Self::check_freshness).
+    pub fn check_freshness_at(
+        &self,
+        manifest_json: &str,
+        now: DateTime<Utc>,
+        crls: &[CachedCrl],
+    ) -> VcpResult<Vec<FreshnessAdvisory>> {
+        let raw: Value = serde_json::from_str(manifest_json)
+            .map_err(|e| VcpError::JsonError(format!("invalid manifest JSON: {e}")))?;
+        let manifest = Manifest::from_value(&raw)?;
+        let policy = &self.freshness;
+        let mut advisories = Vec::new();
+
+        if let Some(exp) = manifest.timestamps.as_ref().and_then(|t| t.exp) {
+            if exp <= now {
+                advisories.push(FreshnessAdvisory::Expired { expired_at: exp });
+            } else if exp - now <= policy.manifest_warning {
+                advisories.push(FreshnessAdvisory::ExpiresSoon {
+                    expires_at: exp,
+                    days_left: (exp - now).num_days(),
+                });
+            }
+        }
+
+        let trust = self.current_trust_config();
+        let issuer = manifest
+            .issuer
+            .as_ref()
+            .map(|i| (&trust.issuers, i.id.as_str(), i.key_id.as_deref()));
+        let auditor = manifest.safety_attestation.as_ref().and_then(|a| {
+            Some((
+                &trust.auditors,
+                a.auditor.as_deref()?,
+                a.auditor_key_id.as_deref(),
+            ))
+        });
+        for (anchors, entity_id, key_id) in issuer.into_iter().chain(auditor) {
+            let Some(anchor) = latest_anchor(anchors.get(entity_id), entity_id, key_id) else {
+                continue;
+            };
+            if anchor.valid_until - now <= policy.anchor_warning {
+                let left = anchor.valid_until - now;
+                advisories.push(FreshnessAdvisory::AnchorExpiring {
+                    entity_id: entity_id.to_string(),
+                    key_id: anchor.key_id.clone(),
+                    anchor_type: anchor.anchor_type,
+                    valid_until: anchor.valid_until,
+                    days_left: if left < chrono::Duration::zero() {
+                        left.num_days().min(-1)
+                    } else {
+                        left.num_days()
+                    },
+                });
+            }
+        }
+
+        let crl_uri = raw.pointer("/revocation/crl_uri").and_then(Value::as_str);
+        if let Some(cached) = crl_uri.and_then(|uri| crls.iter().find(|c| c.uri == uri)) {
+            let age = now - cached.fetched_at;
+            if age > policy.max_crl_age {
+                advisories.push(FreshnessAdvisory::CrlStale {
+                    uri: cached.uri.clone(),
+                    fetched_at: cached.fetched_at,
+                    age_hours: age.num_hours(),
+                });
+            }
+        }
+
+        Ok(advisories)
+    }
+}
+
+/// The usable anchor with the latest expiry for `entity_id`, restricted
+/// to `key_id` when given.
+fn latest_anchor<'a>(
+    anchors: Option<&'a Vec<TrustAnchor>>,
+    entity_id: &str,
+    key_id: Option<&str>,
+) -> Option<&'a TrustAnchor> {
+    let key_id = key_id.map(|kid| crate::did::key_fragment(entity_id, kid));
+    anchors?
+        .iter()
+        .filter(|a| a.state.allows_verification())
+        .filter(|a| key_id.is_none_or(|kid| a.key_id == kid))
+        .max_by_key(|a| a.valid_until)
+}
+
+// ── Remote bundles ───────────────────────────────────────────
+
+#[cfg(feature = "http")]
+impl Orchestrator {
+    /// Fetch a manifest and its content over HTTP(S) and verify them.
+    ///
+    /// Both URLs go through the SSRF checks of
+    /// [`fetch_limited`](crate::fetch::fetch_limited), and each download
+    /// stops at the step 1 size limit, so an oversized bundle fails with
+    /// [`VerificationCode::SizeExceeded`] without being read in full. The
+    /// content is decoded as by
+    /// [`decode_content`](crate::transport::decode_content), then the full
+    /// pipeline runs as in [`verify_outcome`](Self::verify_outcome).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when either URL is unsafe or cannot be fetched,
+    /// or when the manifest or content is not valid text.
+    pub fn fetch_and_verify(
+        &self,
+        manifest_url: &str,
+        content_url: &str,
+        ctx: &VerificationContext,
+    ) -> VcpResult<VerificationOutcome> {
+        use crate::fetch::{fetch_limited, Fetched};
+
+        let Fetched::Body(manifest) = fetch_limited(manifest_url, self.max_manifest_size)? else {
+            return Ok(VerificationOutcome::failed(VerificationCode::SizeExceeded));
+        };
+        let Fetched::Body(content) = fetch_limited(content_url, self.max_content_size)? else {
+            return Ok(VerificationOutcome::failed(VerificationCode::SizeExceeded));
+        };
+        let manifest = String::from_utf8(manifest)
+            .map_err(|_| VcpError::ParseError(format!("{manifest_url}: manifest is not UTF-8")))?;
+        let content = crate::transport::decode_content(&content)?;
+        Ok(self.verify_outcome(&manifest, &content.text, ctx))
+    }
+}
+
+// ── Warm-start snapshots ─────────────────────────────────────
+
+/// A replay-cache entry in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    /// The manifest's `timestamps.jti`.
+    pub jti: String,
+    /// When the entry may be forgotten (the bundle's `exp`).
+    pub exp: DateTime<Utc>,
+}
+
+/// Orchestrator state persisted across restarts.
+///
+/// Restoring the replay cache closes the window in which a bundle seen
+/// before a restart could be replayed after it; carrying cached CRLs
+/// avoids every instance refetching them at once.
+///
+/// There are no verification results to carry: the orchestrator does not
+/// cache them, and every call re-runs the pipeline against the current
+/// trust configuration, so a restored instance verifies exactly as the
+/// one it replaces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrchestratorSnapshot {
+    /// Snapshot format version ([`SNAPSHOT_VERSION`]).
+    pub version: u32,
+    /// The orchestrator clock's time when the snapshot was taken.
+    pub taken_at: DateTime<Utc>,
+    /// [`Orchestrator::trust_store_version`] at the time of the snapshot.
+    pub trust_store_version: String,
+    /// Unexpired replay-cache entries, ordered by `jti`.
+    pub replay: Vec<ReplayEntry>,
+    /// CRLs from a [`RevocationChecker`], if one was attached.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crls: Vec<CachedCrl>,
+}
+
+impl OrchestratorSnapshot {
+    /// Include the CRL cache of a revocation checker.
+    #[must_use]
+    pub fn with_revocation(mut self, checker: &RevocationChecker) -> Self {
+        self.crls = checker.cached_crls();
+        self
+    }
+
+    /// Write the snapshot as JSON, replacing `path` atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::IoError`] if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> VcpResult<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_vec(self)?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Read a snapshot written by [`save`](Self::save).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::IoError`] if the file cannot be read,
+    /// [`VcpError::JsonError`] if it is not a snapshot, or
+    /// [`VcpError::ParseError`] if it has an unsupported format version.
+    pub fn load(path: impl AsRef<Path>) -> VcpResult<Self> {
+        let snapshot: Self = serde_json::from_slice(&fs::read(path)?)?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(VcpError::ParseError(format!(
+                "unsupported snapshot version {} (expected {SNAPSHOT_VERSION})",
+                snapshot.version
+            )));
+        }
+        Ok(snapshot)
+    }
+}
+
+impl Orchestrator {
+    /// Content hash of the trust configuration, used to tell whether a
+    /// snapshot was taken against the same trust store.
+    pub fn trust_store_version(&self) -> String {
+        let value = serde_json::to_value(&*self.current_trust_config()).unwrap_or_default();
+        format!("sha256:{:x}", Sha256::digest(value.to_string().as_bytes()))
+    }
+
+    /// Capture the unexpired replay-cache entries and trust-store version.
+    pub fn snapshot(&self) -> OrchestratorSnapshot {
+        let mut replay: Vec<ReplayEntry> = self
+            .replay_cache
+            .entries()
+            .into_iter()
+            .map(|(jti, exp)| ReplayEntry {
+                jti,
+                exp: DateTime::<Utc>::from(exp),
+            })
+            .collect();
+        replay.sort_by(|a, b| a.jti.cmp(&b.jti));
+
+        OrchestratorSnapshot {
+            version: SNAPSHOT_VERSION,
+            taken_at: self.clock.now(),
+            trust_store_version: self.trust_store_version(),
+            replay,
+            crls: Vec::new(),
+        }
+    }
+
+    /// Merge a snapshot's replay entries into this orchestrator.
+    ///
+    /// Entries that have expired since the snapshot was taken are dropped.
+    /// Replay state is restored even if the trust store has changed; compare
+    /// [`OrchestratorSnapshot::trust_store_version`] with
+    /// [`trust_store_version`](Self::trust_store_version) to detect that.
+    /// Restore `crls` with [`RevocationChecker::restore_crls`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for an unsupported format version.
+    pub fn restore(&self, snapshot: &OrchestratorSnapshot) -> VcpResult<()> {
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(VcpError::ParseError(format!(
+                "unsupported snapshot version {} (expected {SNAPSHOT_VERSION})",
+                snapshot.version
+            )));
+        }
+        let now = self.clock.now();
+        for entry in snapshot.replay.iter().filter(|e| e.exp > now) {
+            self.replay_cache
+                .record(entry.jti.clone(), SystemTime::from(entry.exp));
+        }
+        Ok(())
+    }
+}
+
 // ── Glob matching ────────────────────────────────────────────
 
 /// Simple glob pattern matching supporting `*` as wildcard.
@@ -571,9 +1917,11 @@ fn glob_match_inner(pattern: &[char], text: &[char]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ids::{IdGenerator, SeededIdGenerator};
     use crate::transport::compute_content_hash;
     use crate::trust::{AnchorState, AnchorType, TrustAnchor, TrustConfig};
     use chrono::{Duration as ChronoDuration, Utc};
+    use std::sync::LazyLock;
 
     // Use std Duration for SystemTime arithmetic, chrono Duration for date math.
     use std::time::Duration as StdDuration;
@@ -609,6 +1957,10 @@ mod tests {
         config
     }
 
+    /// Seeded so fixture JTIs are unique within a run yet reproducible.
+    static FIXTURE_IDS: LazyLock<SeededIdGenerator> =
+        LazyLock::new(|| SeededIdGenerator::new(0x5EED));
+
     /// Helper: build a valid manifest JSON string for a given content.
     fn valid_manifest(content: &str) -> String {
         let hash = compute_content_hash(content).unwrap();
@@ -638,7 +1990,7 @@ mod tests {
                 "iat": iat,
                 "nbf": nbf,
                 "exp": exp,
-                "jti": format!("jti-{}", rand::random::<u64>()),
+                "jti": FIXTURE_IDS.next_id(),
             },
             "budget": {
                 "token_count": 1000,
@@ -649,12 +2001,39 @@ mod tests {
         .to_string()
     }
 
+    // ── Trust reload tests ───────────────────────────────────
+
+    #[test]
+    fn trust_reload_applies_to_live_verification() {
+        let shared = SharedConfig::new(test_trust_config());
+        let orch =
+            Orchestrator::new(TrustConfig::default()).with_shared_trust_config(shared.clone());
+        let ctx = orch.verification_context();
+        let content = "Be kind.";
+        assert_eq!(
+            orch.verify(&valid_manifest(content), content, &ctx),
+            VerificationCode::Valid
+        );
+
+        // Revoke the issuer by reloading a config without it.
+        let mut reloaded = test_trust_config();
+        reloaded.issuers.remove("test-issuer");
+        shared.replace(reloaded);
+
+        assert_eq!(
+            orch.verify(&valid_manifest(content), content, &ctx),
+            VerificationCode::UntrustedIssuer
+        );
+        assert!(orch.current_trust_config().issuers.is_empty());
+        assert!(orch.trust_config().issuers.is_empty());
+    }
+
     // ── Size limit tests ─────────────────────────────────────
 
     #[test]
     fn size_limit_manifest_too_large() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let ctx = VerificationContext::new(trust);
 
         // Create a manifest larger than 64 KB.
@@ -666,7 +2045,7 @@ mod tests {
     #[test]
     fn size_limit_content_too_large() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let ctx = VerificationContext::new(trust);
 
         let large_content = "x".repeat(MAX_CONTENT_SIZE + 1);
@@ -680,7 +2059,7 @@ mod tests {
     #[test]
     fn invalid_json_returns_invalid_schema() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let ctx = VerificationContext::new(trust);
 
         let code = orch.verify("not json at all", "content", &ctx);
@@ -690,13 +2069,63 @@ mod tests {
     #[test]
     fn missing_bundle_field_returns_invalid_schema() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let ctx = VerificationContext::new(trust);
 
         let code = orch.verify("{\"vcp_version\": \"1.0\"}", "content", &ctx);
         assert_eq!(code, VerificationCode::InvalidSchema);
     }
 
+    #[test]
+    fn schema_failure_reports_pointer() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let ctx = VerificationContext::new(trust);
+
+        let outcome = orch.verify_outcome(r#"{"bundle": {"id": "b"}}"#, "content", &ctx);
+        assert_eq!(outcome.code, VerificationCode::InvalidSchema);
+        assert_eq!(outcome.schema_errors.len(), 1);
+        assert_eq!(outcome.schema_errors[0].pointer, "/bundle");
+        assert_eq!(outcome.schema_errors[0].keyword, "required");
+    }
+
+    #[test]
+    fn mistyped_section_returns_invalid_schema() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let ctx = VerificationContext::new(trust);
+
+        let mut manifest: Value = serde_json::from_str(&valid_manifest("content")).unwrap();
+        manifest["timestamps"]["exp"] = Value::from("next tuesday");
+        let outcome = orch.verify_outcome(&manifest.to_string(), "content", &ctx);
+        assert_eq!(outcome.code, VerificationCode::InvalidSchema);
+        assert_eq!(outcome.schema_errors.len(), 1);
+        assert_eq!(outcome.schema_errors[0].keyword, "type");
+    }
+
+    #[test]
+    fn strict_schema_validation_lists_every_violation() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone()).with_schema_validation();
+        let ctx = VerificationContext::new(trust);
+
+        // The fixture satisfies the pipeline but not the v2 schema.
+        let outcome = orch.verify_outcome(&valid_manifest("content"), "content", &ctx);
+        assert_eq!(outcome.code, VerificationCode::InvalidSchema);
+        let pointers: Vec<&str> = outcome
+            .schema_errors
+            .iter()
+            .map(|v| v.pointer.as_str())
+            .collect();
+        assert!(pointers.contains(&"/bundle/id"), "{pointers:?}");
+        assert!(pointers.contains(&"/issuer"), "{pointers:?}");
+
+        let err = orch
+            .verify_or_err(&valid_manifest("content"), "content", &ctx)
+            .unwrap_err();
+        assert!(err.to_string().contains("/bundle/id: "), "{err}");
+    }
+
     // ── Injection pattern tests ──────────────────────────────
 
     #[test]
@@ -753,21 +2182,21 @@ mod tests {
 
     #[test]
     fn replay_cache_first_time_returns_false() {
-        let mut cache = ReplayCache::new(100);
+        let cache = ReplayCache::new(100);
         assert!(!cache.is_seen("jti-001"));
     }
 
     #[test]
     fn replay_cache_second_time_returns_true() {
-        let mut cache = ReplayCache::new(100);
-        let exp = SystemTime::now() + StdDuration::from_secs(3600);
+        let cache = ReplayCache::new(100);
+        let exp = SystemTime::now() + StdDuration::from_hours(1);
         cache.record("jti-001".to_string(), exp);
         assert!(cache.is_seen("jti-001"));
     }
 
     #[test]
     fn replay_cache_cleanup_removes_expired() {
-        let mut cache = ReplayCache::new(100);
+        let cache = ReplayCache::new(100);
         // Record an entry that expired 10 seconds ago.
         let past = SystemTime::now() - StdDuration::from_secs(10);
         cache.record("old-jti".to_string(), past);
@@ -779,8 +2208,8 @@ mod tests {
 
     #[test]
     fn replay_cache_max_entries_triggers_cleanup() {
-        let mut cache = ReplayCache::new(3);
-        let future = SystemTime::now() + StdDuration::from_secs(3600);
+        let cache = ReplayCache::new(3);
+        let future = SystemTime::now() + StdDuration::from_hours(1);
         let past = SystemTime::now() - StdDuration::from_secs(10);
 
         cache.record("a".to_string(), past);
@@ -796,6 +2225,107 @@ mod tests {
         assert!(cache.is_seen("d"));
     }
 
+    #[test]
+    fn replay_cache_evicts_least_recently_used_at_capacity() {
+        let cache = ReplayCache::new(3);
+        let future = SystemTime::now() + StdDuration::from_hours(1);
+        for jti in ["a", "b", "c"] {
+            cache.record(jti.to_string(), future);
+        }
+        // A replay attempt on "a" makes "b" the least recently used.
+        assert!(cache.check_and_record("a", future));
+        assert!(!cache.check_and_record("d", future));
+
+        assert!(cache.is_seen("a"));
+        assert!(!cache.is_seen("b"));
+        assert!(cache.is_seen("c"));
+        assert_eq!(
+            cache.stats(),
+            ReplayCacheStats {
+                entries: 3,
+                expired_evictions: 0,
+                capacity_evictions: 1,
+                replays_detected: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn replay_cache_counts_expired_evictions_and_bounds_indexes() {
+        let cache = ReplayCache::new(10);
+        let past = SystemTime::now() - StdDuration::from_secs(10);
+        let future = SystemTime::now() + StdDuration::from_hours(1);
+        cache.record("old-1".to_string(), past);
+        cache.record("old-2".to_string(), past);
+        // Recording "old-2" dropped "old-1"; the lookup drops "old-2".
+        assert_eq!(cache.stats().expired_evictions, 1);
+        assert!(!cache.is_seen("old-2"));
+        assert_eq!(cache.stats().expired_evictions, 2);
+        cache.record("live".to_string(), future);
+
+        // Repeated touches leave stale index items that compaction bounds.
+        for _ in 0..1000 {
+            assert!(cache.check_and_record("live", future));
+        }
+        let shard = cache.shard("live");
+        assert_eq!(shard.entries.len(), 1);
+        assert!(shard.by_use.len() <= 2 * shard.entries.len() + 17);
+    }
+
+    #[test]
+    fn replay_cache_check_and_record_is_atomic() {
+        let cache = ReplayCache::default();
+        let exp = SystemTime::now() + StdDuration::from_hours(1);
+        assert!(!cache.check_and_record("jti-001", exp));
+        assert!(cache.check_and_record("jti-001", exp));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn parallel_verification_accepts_each_jti_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        const THREADS: usize = 8;
+        const MANIFESTS: usize = 64;
+
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let ctx = VerificationContext::new(trust);
+        let content = "Be helpful and honest.";
+        let manifests: Vec<String> = (0..MANIFESTS).map(|_| valid_manifest(content)).collect();
+        let (valid, replayed) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        // Every thread presents every manifest, in a different order.
+        std::thread::scope(|scope| {
+            for t in 0..THREADS {
+                let (orch, ctx, manifests) = (&orch, &ctx, &manifests);
+                let (valid, replayed) = (&valid, &replayed);
+                scope.spawn(move || {
+                    for i in 0..MANIFESTS {
+                        let manifest = &manifests[(i * (2 * t + 1)) % MANIFESTS];
+                        match orch.verify(manifest, content, ctx) {
+                            VerificationCode::Valid => valid.fetch_add(1, Ordering::Relaxed),
+                            VerificationCode::ReplayDetected => {
+                                replayed.fetch_add(1, Ordering::Relaxed)
+                            }
+                            other => panic!("unexpected {other:?}"),
+                        };
+                    }
+                });
+            }
+        });
+
+        assert_eq!(valid.load(Ordering::Relaxed), MANIFESTS);
+        assert_eq!(replayed.load(Ordering::Relaxed), MANIFESTS * (THREADS - 1));
+        assert_eq!(orch.replay_cache.len(), MANIFESTS);
+    }
+
+    #[test]
+    fn orchestrator_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Orchestrator>();
+    }
+
     // ── Verification code tests ──────────────────────────────
 
     #[test]
@@ -817,7 +2347,7 @@ mod tests {
     #[test]
     fn hash_mismatch_detected() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let ctx = VerificationContext::new(trust);
 
         // Build manifest with hash for different content.
@@ -831,7 +2361,7 @@ mod tests {
     #[test]
     fn untrusted_issuer_detected() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let ctx = VerificationContext::new(trust);
 
         let content = "Be kind.";
@@ -846,12 +2376,204 @@ mod tests {
         assert_eq!(code, VerificationCode::UntrustedIssuer);
     }
 
+    // ── Co-signature policy ──────────────────────────────────
+
+    #[test]
+    fn signature_policy_requires_auditor_cosignature() {
+        use crate::multisig::{add_signature, sign_manifest_as, SignatureRole};
+        use ed25519_dalek::SigningKey;
+
+        let auditor_key = SigningKey::from_bytes(&[3u8; 32]);
+        let mut trust = test_trust_config();
+        trust.auditors.get_mut("test-auditor").unwrap()[0].public_key =
+            TrustAnchor::encode_public_key(&auditor_key.verifying_key().to_bytes());
+        let policy = SignaturePolicy::threshold(1).requiring(SignatureRole::Auditor);
+        let ctx = VerificationContext::new(trust.clone()).with_signature_policy(policy);
+
+        let content = "Be kind.";
+        let unsigned: Value = serde_json::from_str(&valid_manifest(content)).unwrap();
+        let orch = Orchestrator::new(trust.clone());
+        assert_eq!(
+            orch.verify(&unsigned.to_string(), content, &ctx),
+            VerificationCode::InvalidSignature
+        );
+
+        let mut signed: Value = serde_json::from_str(&valid_manifest(content)).unwrap();
+        let sig = sign_manifest_as(
+            &signed,
+            "test-auditor",
+            Some("aud-key-01"),
+            SignatureRole::Auditor,
+            &auditor_key.to_bytes(),
+        )
+        .unwrap();
+        add_signature(&mut signed, sig).unwrap();
+        assert_eq!(
+            orch.verify(&signed.to_string(), content, &ctx),
+            VerificationCode::Valid
+        );
+    }
+
+    // ── DID issuers ──────────────────────────────────────────
+
+    #[test]
+    fn verifies_manifests_from_did_issuers() {
+        use crate::did::{did_key, DidResolver};
+        use crate::keys::KeyPair;
+        use crate::transport::sign_manifest;
+
+        let key = KeyPair::from_seed(&[8u8; 32]);
+        let issuer = did_key(&key.public_bytes());
+        let mut trust = test_trust_config();
+        DidResolver::new()
+            .trust_issuer(&mut trust, &issuer)
+            .unwrap();
+        let ctx = VerificationContext::new(trust.clone());
+        let orch = Orchestrator::new(trust);
+
+        let content = "Be kind.";
+        let mut manifest: Value = serde_json::from_str(&valid_manifest(content)).unwrap();
+        manifest["issuer"] = serde_json::json!({
+            "id": issuer,
+            "key_id": format!("{issuer}#{}", &issuer["did:key:".len()..]),
+        });
+        let sig = sign_manifest(&manifest, &key.secret_bytes()).unwrap();
+        manifest["signature"] = serde_json::json!({"algorithm": "ed25519", "value": sig});
+        assert_eq!(
+            orch.verify(&manifest.to_string(), content, &ctx),
+            VerificationCode::Valid
+        );
+
+        manifest["issuer"]["id"] = did_key(&[1u8; 32]).into();
+        assert_eq!(
+            orch.verify(&manifest.to_string(), content, &ctx),
+            VerificationCode::UntrustedIssuer
+        );
+    }
+
+    // ── Trust on first use ───────────────────────────────────
+
+    #[test]
+    fn tofu_pins_unknown_issuers_and_flags_key_changes() {
+        use crate::keys::KeyPair;
+        use crate::transport::sign_manifest;
+        use crate::trust::KeyPins;
+
+        let signed = |key: &KeyPair, content: &str| {
+            let mut manifest: Value = serde_json::from_str(&valid_manifest(content)).unwrap();
+            manifest["issuer"] = serde_json::json!({
+                "id": "small.example",
+                "key_id": "k1",
+                "public_key": format!("ed25519:{}", &key.public_key_base64()["base64:".len()..]),
+            });
+            let sig = sign_manifest(&manifest, &key.secret_bytes()).unwrap();
+            manifest["signature"] = serde_json::json!({"algorithm": "ed25519", "value": sig});
+            manifest.to_string()
+        };
+        let first = KeyPair::from_seed(&[10u8; 32]);
+        let second = KeyPair::from_seed(&[11u8; 32]);
+        let content = "Be kind.";
+
+        // Without TOFU an unknown issuer is untrusted.
+        let ctx = VerificationContext::new(test_trust_config());
+        let orch = Orchestrator::new(test_trust_config());
+        assert_eq!(
+            orch.verify(&signed(&first, content), content, &ctx),
+            VerificationCode::UntrustedIssuer
+        );
+
+        let pins = Arc::new(KeyPins::new());
+        let trust = test_trust_config().with_tofu(Arc::clone(&pins));
+        let ctx = VerificationContext::new(trust.clone());
+        let orch = Orchestrator::new(trust);
+
+        // A bad signature pins nothing.
+        let mut forged: Value = serde_json::from_str(&signed(&first, content)).unwrap();
+        forged["signature"]["value"] = serde_json::from_str::<Value>(&signed(&second, content))
+            .unwrap()["signature"]["value"]
+            .clone();
+        assert_eq!(
+            orch.verify(&forged.to_string(), content, &ctx),
+            VerificationCode::InvalidSignature
+        );
+        assert!(pins.pins().is_empty());
+
+        assert_eq!(
+            orch.verify(&signed(&first, content), content, &ctx),
+            VerificationCode::Valid
+        );
+        let pin = pins.get("small.example").unwrap();
+        assert_eq!(pin.fingerprint, first.fingerprint());
+        assert_eq!(
+            orch.verify(&signed(&first, content), content, &ctx),
+            VerificationCode::Valid
+        );
+
+        assert_eq!(
+            orch.verify(&signed(&second, content), content, &ctx),
+            VerificationCode::UntrustedIssuer
+        );
+        let changes = pins.pending_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].pinned, pin);
+        assert_eq!(changes[0].presented.fingerprint, second.fingerprint());
+
+        pins.approve_change("small.example").unwrap();
+        assert_eq!(
+            orch.verify(&signed(&second, content), content, &ctx),
+            VerificationCode::Valid
+        );
+        assert_eq!(
+            orch.verify(&signed(&first, content), content, &ctx),
+            VerificationCode::UntrustedIssuer
+        );
+    }
+
+    // ── JWS delivery ─────────────────────────────────────────
+
+    #[test]
+    fn verifies_manifests_delivered_as_jws() {
+        use crate::transport::to_jws;
+        use ed25519_dalek::SigningKey;
+
+        let issuer_key = SigningKey::from_bytes(&[6u8; 32]);
+        let mut trust = test_trust_config();
+        trust.issuers.get_mut("test-issuer").unwrap()[0].public_key =
+            TrustAnchor::encode_public_key(&issuer_key.verifying_key().to_bytes());
+        let ctx = VerificationContext::new(trust.clone());
+        let orch = Orchestrator::new(trust);
+
+        let content = "Be kind.";
+        let manifest: Value = serde_json::from_str(&valid_manifest(content)).unwrap();
+        let jws = to_jws(&manifest, &issuer_key.to_bytes()).unwrap();
+        assert_eq!(
+            orch.verify_jws(&jws, content, &ctx),
+            VerificationCode::Valid
+        );
+        // The JTI claim reaches replay detection.
+        assert_eq!(
+            orch.verify_jws(&jws, content, &ctx),
+            VerificationCode::ReplayDetected
+        );
+
+        let manifest: Value = serde_json::from_str(&valid_manifest(content)).unwrap();
+        let forged = to_jws(&manifest, &[7u8; 32]).unwrap();
+        assert_eq!(
+            orch.verify_jws(&forged, content, &ctx),
+            VerificationCode::InvalidSignature
+        );
+        assert_eq!(
+            orch.verify_jws("not-a-jws", content, &ctx),
+            VerificationCode::InvalidSchema
+        );
+    }
+
     // ── Scope mismatch test ──────────────────────────────────
 
     #[test]
     fn scope_mismatch_wrong_environment() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let mut ctx = VerificationContext::new(trust);
         ctx.environment = "production".to_string();
 
@@ -877,12 +2599,110 @@ mod tests {
         assert_eq!(code, VerificationCode::ScopeMismatch);
     }
 
+    // ── Token binding tests ──────────────────────────────────
+
+    fn bound_manifest(binding: Option<&str>, content: &str) -> String {
+        let mut manifest: Value = serde_json::from_str(&valid_manifest(content)).unwrap();
+        if let Some(token) = binding {
+            manifest["binding"] = serde_json::json!({ "token": token });
+        }
+        manifest.to_string()
+    }
+
+    #[test]
+    fn token_binding_checked_against_expected_token() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let expected = VcpToken::parse("family.safe.guide@1.3.0").unwrap();
+        let ctx = VerificationContext::new(trust).with_expected_token(expected);
+        let content = "Be kind.";
+
+        for (binding, code) in [
+            (Some("family.safe.guide@^1.2.0"), VerificationCode::Valid),
+            (Some("family.*.guide"), VerificationCode::Valid),
+            (
+                Some("family.safe.guide@~1.2.0"),
+                VerificationCode::TokenMismatch,
+            ),
+            (Some("work.safe.guide"), VerificationCode::TokenMismatch),
+            (None, VerificationCode::TokenMismatch),
+            (Some("not a token"), VerificationCode::InvalidSchema),
+        ] {
+            let manifest = bound_manifest(binding, content);
+            assert_eq!(orch.verify(&manifest, content, &ctx), code, "{binding:?}");
+        }
+    }
+
+    #[test]
+    fn token_binding_ignored_without_expected_token() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let ctx = VerificationContext::new(trust);
+        let content = "Be kind.";
+
+        let manifest = bound_manifest(Some("work.safe.guide"), content);
+        assert_eq!(
+            orch.verify(&manifest, content, &ctx),
+            VerificationCode::Valid
+        );
+    }
+
+    #[test]
+    fn binding_namespace_checked_against_registry() {
+        use crate::namespace::NamespaceEntry;
+
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let mut registry = NamespaceRegistry::new();
+        registry
+            .register(
+                "SEC",
+                NamespaceEntry::new("acme").with_issuer("test-issuer"),
+            )
+            .unwrap();
+        registry
+            .register("GLOBEX", NamespaceEntry::new("globex"))
+            .unwrap();
+        registry
+            .register("VCP", NamespaceEntry::new("creed-space").reserved())
+            .unwrap();
+        let ctx = VerificationContext::new(trust).with_namespace_registry(registry);
+        let content = "Be kind.";
+
+        for (binding, code) in [
+            (Some("company.acme.legal:SEC"), VerificationCode::Valid),
+            // Approved, but owned by an organisation test-issuer does not
+            // sign for.
+            (
+                Some("company.globex.legal:GLOBEX"),
+                VerificationCode::ScopeMismatch,
+            ),
+            (
+                Some("company.acme.legal:VCP"),
+                VerificationCode::ScopeMismatch,
+            ),
+            (
+                Some("company.acme.legal:GOV"),
+                VerificationCode::ScopeMismatch,
+            ),
+            (Some("company.acme.legal"), VerificationCode::ScopeMismatch),
+            (None, VerificationCode::ScopeMismatch),
+            (
+                Some("company.acme.legal:sec"),
+                VerificationCode::InvalidSchema,
+            ),
+        ] {
+            let manifest = bound_manifest(binding, content);
+            assert_eq!(orch.verify(&manifest, content, &ctx), code, "{binding:?}");
+        }
+    }
+
     // ── Budget exceeded test ─────────────────────────────────
 
     #[test]
     fn budget_exceeded_detected() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let mut ctx = VerificationContext::new(trust);
         ctx.model_context_limit = 100_000;
 
@@ -911,12 +2731,60 @@ mod tests {
         assert_eq!(code, VerificationCode::BudgetExceeded);
     }
 
+    #[test]
+    fn budget_estimator_cross_checks_declared_count() {
+        let trust = test_trust_config();
+        let ctx = VerificationContext::new(trust.clone());
+        let orch = Orchestrator::new(trust)
+            .with_budget_estimator(BudgetEstimator::default().with_tolerance(0.1));
+        let content = "word ".repeat(300); // 400 tokens by the word heuristic
+
+        let with_count = |count: Option<u64>| {
+            let mut manifest: Value = serde_json::from_str(&valid_manifest(&content)).unwrap();
+            match count {
+                Some(n) => manifest["budget"]["token_count"] = n.into(),
+                None => {
+                    manifest["budget"]
+                        .as_object_mut()
+                        .unwrap()
+                        .remove("token_count");
+                }
+            }
+            manifest.to_string()
+        };
+
+        assert_eq!(
+            orch.verify(&with_count(Some(390)), &content, &ctx),
+            VerificationCode::Valid
+        );
+        assert_eq!(
+            orch.verify(&with_count(Some(10)), &content, &ctx),
+            VerificationCode::BudgetExceeded
+        );
+        assert_eq!(
+            orch.verify(&with_count(None), &content, &ctx),
+            VerificationCode::Valid
+        );
+
+        // Without a declared count the estimate is held to the limit.
+        let long = "word ".repeat(30_000);
+        let mut manifest: Value = serde_json::from_str(&valid_manifest(&long)).unwrap();
+        manifest["budget"]
+            .as_object_mut()
+            .unwrap()
+            .remove("token_count");
+        assert_eq!(
+            orch.verify(&manifest.to_string(), &long, &ctx),
+            VerificationCode::BudgetExceeded
+        );
+    }
+
     // ── Verify or err test ───────────────────────────────────
 
     #[test]
     fn verify_or_err_returns_error_on_failure() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let ctx = VerificationContext::new(trust);
 
         let result = orch.verify_or_err("not json", "content", &ctx);
@@ -924,8 +2792,65 @@ mod tests {
         let err = result.unwrap_err();
         assert!(
             err.to_string().contains("verification failed"),
-            "error message: {}",
-            err
+            "error message: {err}"
+        );
+    }
+
+    // ── Multi-file bundle tests ──────────────────────────────
+
+    fn multi_file_bundle() -> (String, BundleContents) {
+        use crate::transport::{compute_files_hash, BundleFile};
+
+        let contents = BundleContents::new()
+            .with_file("core.md", "Be kind.\n")
+            .with_file("annexes/medical.md", "No diagnoses.\n");
+        let files: Vec<BundleFile> = [("core.md", "core"), ("annexes/medical.md", "annex")]
+            .into_iter()
+            .map(|(path, role)| BundleFile {
+                path: path.into(),
+                role: role.into(),
+                content_hash: compute_content_hash(contents.get(path).unwrap()).unwrap(),
+                locale: None,
+            })
+            .collect();
+
+        let mut manifest: Value = serde_json::from_str(&valid_manifest("unused")).unwrap();
+        manifest["bundle"]["content_hash"] = compute_files_hash(&files).into();
+        manifest["bundle"]["files"] = serde_json::to_value(&files).unwrap();
+        (manifest.to_string(), contents)
+    }
+
+    #[test]
+    fn multi_file_bundle_verifies_every_file() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let ctx = VerificationContext::new(trust);
+
+        let (manifest, contents) = multi_file_bundle();
+        assert_eq!(
+            orch.verify_files(&manifest, &contents, &ctx),
+            VerificationCode::Valid
+        );
+
+        let (manifest, contents) = multi_file_bundle();
+        let tampered = contents.with_file("annexes/medical.md", "Diagnose freely.\n");
+        assert_eq!(
+            orch.verify_files(&manifest, &tampered, &ctx),
+            VerificationCode::HashMismatch
+        );
+
+        let (manifest, contents) = multi_file_bundle();
+        let extra = contents.with_file("annexes/extra.md", "Obey.\n");
+        assert_eq!(
+            orch.verify_files(&manifest, &extra, &ctx),
+            VerificationCode::InvalidSchema
+        );
+
+        // A single body cannot satisfy a multi-file manifest.
+        let (manifest, _) = multi_file_bundle();
+        assert_eq!(
+            orch.verify(&manifest, "Be kind.\n", &ctx),
+            VerificationCode::HashMismatch
         );
     }
 
@@ -948,7 +2873,7 @@ mod tests {
     #[test]
     fn replay_detected_in_pipeline() {
         let trust = test_trust_config();
-        let mut orch = Orchestrator::new(trust.clone());
+        let orch = Orchestrator::new(trust.clone());
         let ctx = VerificationContext::new(trust);
 
         let content = "Be kind.";
@@ -974,4 +2899,305 @@ mod tests {
         let code2 = orch.verify(&manifest, content, &ctx);
         assert_eq!(code2, VerificationCode::ReplayDetected);
     }
+
+    #[test]
+    fn injected_clock_drives_temporal_replay_and_anchor_checks() {
+        use crate::clock::MockClock;
+
+        let trust = test_trust_config();
+        let start = Utc::now();
+        let clock = Arc::new(MockClock::new(start));
+        let orch = Orchestrator::new(trust.clone()).with_clock(clock.clone());
+        let ctx = VerificationContext::new(trust);
+
+        let content = "Be kind.";
+        let manifest = valid_manifest(content);
+        assert_eq!(
+            orch.verify(&manifest, content, &ctx),
+            VerificationCode::Valid
+        );
+        assert_eq!(
+            orch.verify(&manifest, content, &ctx),
+            VerificationCode::ReplayDetected
+        );
+        assert_eq!(orch.snapshot().replay.len(), 1);
+
+        // Past the manifest's 30-day expiry: expired, and the JTI is gone.
+        clock.advance(ChronoDuration::days(31));
+        assert_eq!(
+            orch.verify(&manifest, content, &ctx),
+            VerificationCode::Expired
+        );
+        assert!(orch.snapshot().replay.is_empty());
+        assert_eq!(orch.snapshot().taken_at, clock.now());
+
+        // Before the issuer anchor's validity window opens.
+        clock.set(start - ChronoDuration::days(2));
+        assert_eq!(
+            orch.verify(&manifest, content, &ctx),
+            VerificationCode::UntrustedIssuer
+        );
+    }
+
+    // ── Degraded mode ────────────────────────────────────────
+
+    fn cached_ctx(trust: TrustConfig, age: ChronoDuration) -> VerificationContext {
+        VerificationContext::new(trust).with_trust_source(TrustSource::Cached {
+            fetched_at: Utc::now() - age,
+        })
+    }
+
+    #[test]
+    fn cached_trust_without_policy_fails() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let ctx = cached_ctx(trust, ChronoDuration::minutes(5));
+
+        let content = "Be kind.";
+        let outcome = orch.verify_outcome(&valid_manifest(content), content, &ctx);
+        assert_eq!(outcome.code, VerificationCode::FetchFailed);
+        assert!(!outcome.degraded);
+    }
+
+    #[test]
+    fn cached_trust_with_policy_is_degraded() {
+        let trust = test_trust_config();
+        let mode = DegradedMode::new(ChronoDuration::days(2), ChronoDuration::hours(1));
+        let orch = Orchestrator::new(trust.clone()).with_degraded_mode(mode);
+        let ctx = cached_ctx(trust, ChronoDuration::days(1));
+
+        let content = "Be kind.";
+        let outcome = orch.verify_outcome(&valid_manifest(content), content, &ctx);
+        assert!(outcome.is_valid());
+        assert!(outcome.degraded);
+
+        // Manifest exp is 30 days out; degraded validity is capped at 1 hour.
+        let valid_until = outcome.valid_until.unwrap();
+        assert!(valid_until <= Utc::now() + ChronoDuration::hours(1));
+        assert!(valid_until > Utc::now() + ChronoDuration::minutes(59));
+    }
+
+    #[test]
+    fn stale_cache_fails_even_with_policy() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone()).with_degraded_mode(DegradedMode::default());
+        let ctx = cached_ctx(trust, ChronoDuration::days(8));
+
+        let content = "Be kind.";
+        let code = orch.verify(&valid_manifest(content), content, &ctx);
+        assert_eq!(code, VerificationCode::FetchFailed);
+    }
+
+    #[test]
+    fn degraded_mode_still_runs_pipeline() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone()).with_degraded_mode(DegradedMode::default());
+        let ctx = cached_ctx(trust, ChronoDuration::hours(1));
+
+        let outcome = orch.verify_outcome(&valid_manifest("original"), "tampered", &ctx);
+        assert_eq!(outcome.code, VerificationCode::HashMismatch);
+        assert!(!outcome.degraded);
+        assert!(outcome.valid_until.is_none());
+    }
+
+    #[test]
+    fn live_outcome_uses_manifest_exp() {
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone()).with_degraded_mode(DegradedMode::default());
+        let ctx = VerificationContext::new(trust);
+
+        let content = "Be kind.";
+        let outcome = orch.verify_outcome(&valid_manifest(content), content, &ctx);
+        assert!(outcome.is_valid());
+        assert!(!outcome.degraded);
+        assert!(outcome.valid_until.unwrap() > Utc::now() + ChronoDuration::days(29));
+    }
+
+    // ── Freshness advisories ─────────────────────────────────
+
+    #[test]
+    fn freshness_reports_expiry_anchors_and_stale_crls() {
+        use crate::revocation::{CachedCrl, Crl};
+
+        let mut trust = test_trust_config();
+        trust.issuers.get_mut("test-issuer").unwrap()[0].valid_until =
+            Utc::now() + ChronoDuration::days(20);
+        trust.auditors.get_mut("test-auditor").unwrap()[0].valid_until =
+            Utc::now() - ChronoDuration::hours(36);
+        let orch = Orchestrator::new(trust);
+
+        let mut manifest: Value = serde_json::from_str(&valid_manifest("x")).unwrap();
+        manifest["revocation"] = serde_json::json!({"crl_uri": "https://creed.space/crl"});
+        let manifest = manifest.to_string();
+        let now = Utc::now();
+        let crl = CachedCrl {
+            uri: "https://creed.space/crl".into(),
+            fetched_at: now - ChronoDuration::hours(30),
+            crl: Crl {
+                issuer: "test-issuer".into(),
+                updated_at: String::new(),
+                next_update: String::new(),
+                revoked: Vec::new(),
+            },
+        };
+
+        // The manifest expires in 30 days, outside the 14-day window.
+        let advisories = orch
+            .check_freshness_at(&manifest, now, std::slice::from_ref(&crl))
+            .unwrap();
+        let kinds: Vec<String> = advisories.iter().map(ToString::to_string).collect();
+        assert_eq!(advisories.len(), 3, "{kinds:?}");
+        assert!(matches!(
+            &advisories[0],
+            FreshnessAdvisory::AnchorExpiring {
+                anchor_type: AnchorType::Issuer,
+                days_left: 19 | 20,
+                ..
+            }
+        ));
+        assert!(!advisories[0].is_expired());
+        assert!(kinds[1].starts_with("auditor anchor test-auditor/aud-key-01 expired 1 day(s) ago"));
+        assert!(advisories[1].is_expired());
+        assert_eq!(kinds[2], "CRL https://creed.space/crl is 30 hour(s) old");
+
+        let later = now + ChronoDuration::days(25);
+        let advisories = Orchestrator::new(test_trust_config())
+            .check_freshness_at(&manifest, later, &[])
+            .unwrap();
+        assert!(matches!(
+            advisories[..],
+            [FreshnessAdvisory::ExpiresSoon {
+                days_left: 4 | 5,
+                ..
+            }]
+        ));
+        let advisories = orch
+            .with_freshness_policy(FreshnessPolicy {
+                anchor_warning: ChronoDuration::zero(),
+                ..FreshnessPolicy::default()
+            })
+            .check_freshness_at(&manifest, now + ChronoDuration::days(31), &[])
+            .unwrap();
+        assert!(advisories[0].is_expired());
+        assert_eq!(advisories.len(), 3);
+
+        assert!(Orchestrator::new(test_trust_config())
+            .check_freshness("not json")
+            .is_err());
+    }
+
+    // ── Warm-start snapshot ──────────────────────────────────
+
+    #[test]
+    fn snapshot_restore_blocks_replay_after_restart() {
+        let trust = test_trust_config();
+        let ctx = VerificationContext::new(trust.clone());
+        let content = "Be kind.";
+        let manifest = valid_manifest(content);
+
+        let before = Orchestrator::new(trust.clone());
+        assert_eq!(
+            before.verify(&manifest, content, &ctx),
+            VerificationCode::Valid
+        );
+
+        let dir = std::env::temp_dir().join(format!("vcp-snapshot-{}", FIXTURE_IDS.next_id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("orchestrator.json");
+        before.snapshot().save(&path).unwrap();
+
+        let snapshot = OrchestratorSnapshot::load(&path).unwrap();
+        assert_eq!(snapshot.replay.len(), 1);
+
+        let after = Orchestrator::new(trust);
+        assert_eq!(snapshot.trust_store_version, after.trust_store_version());
+        after.restore(&snapshot).unwrap();
+        assert_eq!(
+            after.verify(&manifest, content, &ctx),
+            VerificationCode::ReplayDetected
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn restore_skips_expired_entries_and_checks_version() {
+        let orch = Orchestrator::new(test_trust_config());
+        let mut snapshot = orch.snapshot();
+        snapshot.replay.push(ReplayEntry {
+            jti: "gone".into(),
+            exp: Utc::now() - ChronoDuration::minutes(1),
+        });
+        orch.restore(&snapshot).unwrap();
+        assert!(orch.replay_cache.is_empty());
+
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        assert!(orch.restore(&snapshot).is_err());
+    }
+
+    #[test]
+    fn trust_store_version_tracks_config() {
+        let a = Orchestrator::new(test_trust_config());
+        let b = Orchestrator::new(TrustConfig::new());
+        assert_ne!(a.trust_store_version(), b.trust_store_version());
+        assert!(a.trust_store_version().starts_with("sha256:"));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn verification_emits_a_span_per_step() {
+        use std::sync::Mutex;
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Records the name of every span opened.
+        #[derive(Default)]
+        struct SpanNames(Mutex<Vec<&'static str>>);
+
+        impl Subscriber for &'static SpanNames {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut names = self.0.lock().unwrap();
+                names.push(span.metadata().name());
+                Id::from_u64(names.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let names: &'static SpanNames = Box::leak(Box::default());
+        let trust = test_trust_config();
+        let orch = Orchestrator::new(trust.clone());
+        let ctx = VerificationContext::new(trust);
+        let content = "Be kind.";
+        let manifest = serde_json::json!({
+            "bundle": { "id": "test", "content_hash": compute_content_hash(content).unwrap() },
+            "issuer": { "id": "test-issuer", "key_id": "key-01" },
+        })
+        .to_string();
+
+        let code =
+            tracing::subscriber::with_default(names, || orch.verify(&manifest, content, &ctx));
+        assert_eq!(code, VerificationCode::Valid);
+        assert_eq!(
+            *names.0.lock().unwrap(),
+            [
+                "vcp.verify",
+                "vcp.verify.parse_manifest",
+                "vcp.verify.content_hash",
+                "vcp.verify.issuer",
+                "vcp.verify.attestation",
+                "vcp.verify.temporal",
+                "vcp.verify.budget",
+                "vcp.verify.scope",
+                "vcp.verify.token_binding",
+                "vcp.verify.binding_namespace",
+                "vcp.verify.safety_scan",
+            ]
+        );
+    }
 }
@@ -0,0 +1,857 @@
+//! Persona capability matrix and handoff compatibility.
+//!
+//! Each [`Persona`] at each adherence level grants a [`Permission`] for a
+//! small set of [`Behavior`]s. [`capabilities`] looks one cell of the
+//! matrix up; [`matrix`] returns all of it.
+//!
+//! | Persona | Sensitive content | Tool use | Data retention |
+//! |---------|-------------------|----------|----------------|
+//! | Nanny (N) | forbidden | restricted | forbidden |
+//! | Sentinel (Z) | restricted | restricted | forbidden |
+//! | Godparent (G) | restricted | allowed | restricted |
+//! | Ambassador (A) | restricted | allowed | restricted |
+//! | Muse (M) | allowed | allowed | restricted |
+//! | Mediator (D) | restricted | restricted | restricted |
+//! | Custom (C) | restricted | restricted | restricted |
+//!
+//! The table shows levels 3 and 4. Levels 1 and 2 relax every cell one
+//! step, level 5 tightens every cell one step, and level 0 (disabled)
+//! allows everything.
+//!
+//! When agents exchange CSM-1 codes, [`check_compatibility`] lists why a
+//! handoff between them would be unsafe: a different namespace or major
+//! version, no scope in common, or a behavior one side allows and the
+//! other forbids. [`Csm1Code::is_compatible_with`] is the boolean form.
+//!
+//! [`Persona::Custom`] codes name their persona in the namespace
+//! (`C5:TUTOR`). A [`PersonaRegistry`] holds the deployment's
+//! [`CustomPersona`] definitions; it parses and encodes their codes,
+//! including single-letter aliases (`T5` for `C5:TUTOR`), and applies
+//! their capability profiles in compatibility checks.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::csm1::{Csm1Code, Persona};
+//! use vcp_core::persona::{capabilities, Behavior, Permission};
+//!
+//! let nanny = capabilities(Persona::Nanny, 5);
+//! assert_eq!(nanny.permission(Behavior::SensitiveContent), Permission::Forbidden);
+//! assert_eq!(nanny.permission(Behavior::ToolUse), Permission::Forbidden);
+//!
+//! let nanny = Csm1Code::parse("N4+F").unwrap();
+//! assert!(nanny.is_compatible_with(&Csm1Code::parse("Z3+F").unwrap()));
+//! assert!(!nanny.is_compatible_with(&Csm1Code::parse("M3+F").unwrap()));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::csm1::{Csm1Code, Persona, Scope};
+use crate::error::{VcpError, VcpResult};
+
+// ── Behaviors ───────────────────────────────────────────────
+
+/// A behavior whose permission varies by persona and adherence level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Behavior {
+    /// Content that default content filters would block.
+    SensitiveContent,
+    /// Invoking external tools on the user's behalf.
+    ToolUse,
+    /// Keeping user data beyond the session.
+    DataRetention,
+}
+
+impl Behavior {
+    /// All behaviors, in matrix column order.
+    pub fn all() -> &'static [Behavior] {
+        &[Self::SensitiveContent, Self::ToolUse, Self::DataRetention]
+    }
+}
+
+impl fmt::Display for Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SensitiveContent => "sensitive_content",
+            Self::ToolUse => "tool_use",
+            Self::DataRetention => "data_retention",
+        })
+    }
+}
+
+/// How freely a behavior may be exercised. Ordered from least to most
+/// strict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    Allowed,
+    /// Allowed only with confirmation or within the active scopes.
+    Restricted,
+    Forbidden,
+}
+
+impl Permission {
+    fn from_step(step: i8) -> Self {
+        match step {
+            i8::MIN..=0 => Self::Allowed,
+            1 => Self::Restricted,
+            _ => Self::Forbidden,
+        }
+    }
+
+    fn step(self) -> i8 {
+        match self {
+            Self::Allowed => 0,
+            Self::Restricted => 1,
+            Self::Forbidden => 2,
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Allowed => "allowed",
+            Self::Restricted => "restricted",
+            Self::Forbidden => "forbidden",
+        })
+    }
+}
+
+// ── Capability Matrix ───────────────────────────────────────
+
+/// One cell of the capability matrix: what a persona permits at one
+/// adherence level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PersonaCapabilities {
+    pub persona: Persona,
+    pub adherence_level: u8,
+    pub sensitive_content: Permission,
+    pub tool_use: Permission,
+    pub data_retention: Permission,
+}
+
+impl PersonaCapabilities {
+    /// The permission granted for `behavior`.
+    pub fn permission(&self, behavior: Behavior) -> Permission {
+        match behavior {
+            Behavior::SensitiveContent => self.sensitive_content,
+            Behavior::ToolUse => self.tool_use,
+            Behavior::DataRetention => self.data_retention,
+        }
+    }
+
+    /// Whether `behavior` is permitted without restriction.
+    pub fn allows(&self, behavior: Behavior) -> bool {
+        self.permission(behavior) == Permission::Allowed
+    }
+
+    /// Whether every behavior is at least as strict as in `other`.
+    pub fn is_at_least_as_strict_as(&self, other: &PersonaCapabilities) -> bool {
+        Behavior::all()
+            .iter()
+            .all(|&b| self.permission(b) >= other.permission(b))
+    }
+}
+
+/// Baseline permissions a persona grants at levels 3 and 4, which other
+/// levels shift from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CapabilityProfile {
+    pub sensitive_content: Permission,
+    pub tool_use: Permission,
+    pub data_retention: Permission,
+}
+
+impl Default for CapabilityProfile {
+    /// Every behavior restricted, as for an unregistered custom persona.
+    fn default() -> Self {
+        Self {
+            sensitive_content: Permission::Restricted,
+            tool_use: Permission::Restricted,
+            data_retention: Permission::Restricted,
+        }
+    }
+}
+
+impl CapabilityProfile {
+    /// The built-in profile of `persona` (one row of the module table).
+    pub fn of(persona: Persona) -> Self {
+        use Permission::{Allowed, Forbidden, Restricted};
+        let [sensitive_content, tool_use, data_retention] = match persona {
+            Persona::Nanny => [Forbidden, Restricted, Forbidden],
+            Persona::Sentinel => [Restricted, Restricted, Forbidden],
+            Persona::Godparent | Persona::Ambassador => [Restricted, Allowed, Restricted],
+            Persona::Muse => [Allowed, Allowed, Restricted],
+            Persona::Mediator | Persona::Custom => return Self::default(),
+        };
+        Self {
+            sensitive_content,
+            tool_use,
+            data_retention,
+        }
+    }
+
+    /// This profile shifted to `adherence_level` (above 5 is treated as 5).
+    pub fn at_level(&self, persona: Persona, adherence_level: u8) -> PersonaCapabilities {
+        let level = adherence_level.min(5);
+        let shift: i8 = match level {
+            0 => -2,
+            1 | 2 => -1,
+            3 | 4 => 0,
+            _ => 1,
+        };
+        let shifted = |p: Permission| Permission::from_step(p.step() + shift);
+        PersonaCapabilities {
+            persona,
+            adherence_level: level,
+            sensitive_content: shifted(self.sensitive_content),
+            tool_use: shifted(self.tool_use),
+            data_retention: shifted(self.data_retention),
+        }
+    }
+}
+
+/// What `persona` permits at `adherence_level`. Levels above 5 are
+/// treated as 5.
+pub fn capabilities(persona: Persona, adherence_level: u8) -> PersonaCapabilities {
+    CapabilityProfile::of(persona).at_level(persona, adherence_level)
+}
+
+/// The full matrix: every persona at every level 0-5.
+pub fn matrix() -> Vec<PersonaCapabilities> {
+    Persona::all()
+        .iter()
+        .flat_map(|&p| (0..=5).map(move |level| capabilities(p, level)))
+        .collect()
+}
+
+// ── Compatibility ───────────────────────────────────────────
+
+/// A reason two CSM-1 codes cannot safely hand off to each other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum Incompatibility {
+    /// The codes belong to different namespaces.
+    Namespace {
+        left: Option<String>,
+        right: Option<String>,
+    },
+    /// Both codes carry a version and the major versions differ.
+    Version { left: String, right: String },
+    /// Both codes are scoped and share no scope.
+    DisjointScopes,
+    /// One side allows a behavior the other forbids.
+    Behavior {
+        behavior: Behavior,
+        left: Permission,
+        right: Permission,
+    },
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Namespace { left, right } => write!(
+                f,
+                "namespace {} differs from {}",
+                left.as_deref().unwrap_or("(none)"),
+                right.as_deref().unwrap_or("(none)")
+            ),
+            Self::Version { left, right } => {
+                write!(f, "version {left} is not compatible with {right}")
+            }
+            Self::DisjointScopes => f.write_str("no scope in common"),
+            Self::Behavior {
+                behavior,
+                left,
+                right,
+            } => write!(
+                f,
+                "{behavior} is {left} on one side and {right} on the other"
+            ),
+        }
+    }
+}
+
+fn major_version(version: &str) -> &str {
+    version.split('.').next().unwrap_or(version)
+}
+
+fn scopes_overlap(left: &[Scope], right: &[Scope]) -> bool {
+    left.is_empty() || right.is_empty() || left.iter().any(|s| right.contains(s))
+}
+
+/// One side of a compatibility check, after custom personas are resolved.
+struct Side<'a> {
+    code: &'a Csm1Code,
+    /// The code's namespace, unless it only names a custom persona.
+    namespace: Option<&'a str>,
+    capabilities: PersonaCapabilities,
+}
+
+impl<'a> Side<'a> {
+    fn builtin(code: &'a Csm1Code) -> Self {
+        Self {
+            code,
+            namespace: code.namespace.as_deref(),
+            capabilities: capabilities(code.persona, code.adherence_level),
+        }
+    }
+}
+
+/// Every reason `left` and `right` are incompatible; empty when a handoff
+/// between them is safe in either direction.
+///
+/// Custom personas are judged by the built-in [`Persona::Custom`] row; use
+/// [`PersonaRegistry::check_compatibility`] to apply registered profiles.
+pub fn check_compatibility(left: &Csm1Code, right: &Csm1Code) -> Vec<Incompatibility> {
+    incompatibilities(&Side::builtin(left), &Side::builtin(right))
+}
+
+fn incompatibilities(left: &Side<'_>, right: &Side<'_>) -> Vec<Incompatibility> {
+    let mut problems = Vec::new();
+
+    if left.namespace != right.namespace {
+        problems.push(Incompatibility::Namespace {
+            left: left.namespace.map(str::to_string),
+            right: right.namespace.map(str::to_string),
+        });
+    }
+    if let (Some(l), Some(r)) = (&left.code.version, &right.code.version) {
+        if major_version(l) != major_version(r) {
+            problems.push(Incompatibility::Version {
+                left: l.clone(),
+                right: r.clone(),
+            });
+        }
+    }
+    if !scopes_overlap(&left.code.scopes, &right.code.scopes) {
+        problems.push(Incompatibility::DisjointScopes);
+    }
+
+    for &behavior in Behavior::all() {
+        let (l, r) = (
+            left.capabilities.permission(behavior),
+            right.capabilities.permission(behavior),
+        );
+        if l.min(r) == Permission::Allowed && l.max(r) == Permission::Forbidden {
+            problems.push(Incompatibility::Behavior {
+                behavior,
+                left: l,
+                right: r,
+            });
+        }
+    }
+
+    problems
+}
+
+// ── Custom Personas ─────────────────────────────────────────
+
+/// Longest custom persona id accepted by [`PersonaRegistry::register`].
+pub const MAX_CUSTOM_ID_LEN: usize = 32;
+
+/// A deployment-defined persona, referenced from CSM-1 as
+/// `C<level>:<ID>` or by its alias letter (`X<level>`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomPersona {
+    /// Upper-case identifier used as the code's namespace (e.g. `TUTOR`).
+    pub id: String,
+    /// Letter that stands for `C..:<ID>` in compact codes. Must not be a
+    /// built-in persona letter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<char>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Scopes applied when a code names none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_scopes: Vec<Scope>,
+    #[serde(default)]
+    pub capabilities: CapabilityProfile,
+}
+
+impl CustomPersona {
+    /// A persona with no alias, no default scopes and the
+    /// [default](CapabilityProfile::default) capability profile.
+    pub fn new(id: &str, name: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            alias: None,
+            name: name.to_string(),
+            description: String::new(),
+            default_scopes: Vec::new(),
+            capabilities: CapabilityProfile::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_alias(mut self, alias: char) -> Self {
+        self.alias = Some(alias);
+        self
+    }
+
+    #[must_use]
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_default_scopes(mut self, scopes: Vec<Scope>) -> Self {
+        self.default_scopes = scopes;
+        self
+    }
+
+    #[must_use]
+    pub fn with_capabilities(mut self, capabilities: CapabilityProfile) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// What this persona permits at `adherence_level`.
+    pub fn capabilities_at(&self, adherence_level: u8) -> PersonaCapabilities {
+        self.capabilities.at_level(Persona::Custom, adherence_level)
+    }
+}
+
+fn is_custom_id(id: &str) -> bool {
+    id.len() <= MAX_CUSTOM_ID_LEN
+        && id.as_bytes().first().is_some_and(u8::is_ascii_uppercase)
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_')
+}
+
+/// Custom personas known to a deployment, keyed by id.
+///
+/// Serializes as `{"personas": [...]}`; deserializing re-runs the checks
+/// of [`register`](Self::register).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "RegistryFile", try_from = "RegistryFile")]
+pub struct PersonaRegistry {
+    personas: BTreeMap<String, CustomPersona>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RegistryFile {
+    personas: Vec<CustomPersona>,
+}
+
+impl From<PersonaRegistry> for RegistryFile {
+    fn from(registry: PersonaRegistry) -> Self {
+        Self {
+            personas: registry.personas.into_values().collect(),
+        }
+    }
+}
+
+impl TryFrom<RegistryFile> for PersonaRegistry {
+    type Error = VcpError;
+
+    fn try_from(file: RegistryFile) -> VcpResult<Self> {
+        let mut registry = Self::new();
+        for persona in file.personas {
+            registry.register(persona)?;
+        }
+        Ok(registry)
+    }
+}
+
+impl PersonaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a custom persona.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the id is not upper-case
+    /// alphanumeric (up to [`MAX_CUSTOM_ID_LEN`]) or is already
+    /// registered, or if the alias is not an upper-case letter, is a
+    /// built-in persona letter, or is taken.
+    pub fn register(&mut self, persona: CustomPersona) -> VcpResult<()> {
+        if !is_custom_id(&persona.id) {
+            return Err(VcpError::ParseError(format!(
+                "invalid custom persona id: {}",
+                persona.id
+            )));
+        }
+        if self.personas.contains_key(&persona.id) {
+            return Err(VcpError::ParseError(format!(
+                "custom persona already registered: {}",
+                persona.id
+            )));
+        }
+        if let Some(alias) = persona.alias {
+            if !alias.is_ascii_uppercase() || Persona::from_char(alias).is_ok() {
+                return Err(VcpError::ParseError(format!(
+                    "invalid custom persona alias: {alias}"
+                )));
+            }
+            if self.by_alias(alias).is_some() {
+                return Err(VcpError::ParseError(format!(
+                    "custom persona alias already registered: {alias}"
+                )));
+            }
+        }
+        self.personas.insert(persona.id.clone(), persona);
+        Ok(())
+    }
+
+    /// Remove a custom persona, returning it if it was registered.
+    pub fn deregister(&mut self, id: &str) -> Option<CustomPersona> {
+        self.personas.remove(id)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CustomPersona> {
+        self.personas.get(id)
+    }
+
+    /// The persona registered under alias letter `alias`.
+    pub fn by_alias(&self, alias: char) -> Option<&CustomPersona> {
+        self.personas.values().find(|p| p.alias == Some(alias))
+    }
+
+    /// Registered personas in id order.
+    pub fn iter(&self) -> impl Iterator<Item = &CustomPersona> {
+        self.personas.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.personas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.personas.is_empty()
+    }
+
+    /// The custom persona a code refers to: a [`Persona::Custom`] code whose
+    /// namespace is a registered id.
+    pub fn resolve(&self, code: &Csm1Code) -> Option<&CustomPersona> {
+        if code.persona != Persona::Custom {
+            return None;
+        }
+        code.namespace.as_deref().and_then(|ns| self.get(ns))
+    }
+
+    /// Parse a compact code, expanding alias letters to `C..:<ID>` and
+    /// filling in the default scopes of a registered persona.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::InvalidPersona`] if the first letter is neither
+    /// a built-in persona nor a registered alias,
+    /// [`VcpError::ParseError`] if an aliased code also carries a
+    /// namespace, or any error of [`Csm1Code::parse`].
+    pub fn parse(&self, raw: &str) -> VcpResult<Csm1Code> {
+        let first = raw
+            .chars()
+            .next()
+            .ok_or_else(|| VcpError::ParseError("CSM1 code cannot be empty".into()))?
+            .to_ascii_uppercase();
+
+        let mut code = match self.by_alias(first) {
+            Some(persona) => {
+                let mut code = Csm1Code::parse(&format!("C{}", &raw[first.len_utf8()..]))?;
+                if code.namespace.is_some() {
+                    return Err(VcpError::ParseError(format!(
+                        "aliased code cannot carry a namespace: {raw}"
+                    )));
+                }
+                code.namespace = Some(persona.id.clone());
+                code
+            }
+            None => Csm1Code::parse(raw)?,
+        };
+
+        if code.scopes.is_empty() {
+            if let Some(persona) = self.resolve(&code) {
+                code.scopes.clone_from(&persona.default_scopes);
+            }
+        }
+        Ok(code)
+    }
+
+    /// Encode a code, using the alias letter of a registered persona and
+    /// leaving out scopes equal to its defaults.
+    pub fn encode(&self, code: &Csm1Code) -> String {
+        let Some(persona) = self.resolve(code) else {
+            return code.encode();
+        };
+        let mut short = code.clone();
+        if short.scopes == persona.default_scopes {
+            short.scopes.clear();
+        }
+        match persona.alias {
+            Some(alias) => {
+                short.namespace = None;
+                let encoded = short.encode();
+                format!("{alias}{}", &encoded[1..])
+            }
+            None => short.encode(),
+        }
+    }
+
+    /// What a code permits, using the registered profile for custom
+    /// personas.
+    pub fn capabilities(&self, code: &Csm1Code) -> PersonaCapabilities {
+        match self.resolve(code) {
+            Some(persona) => persona.capabilities_at(code.adherence_level),
+            None => capabilities(code.persona, code.adherence_level),
+        }
+    }
+
+    /// [`check_compatibility`] with registered custom personas resolved.
+    /// A namespace naming a custom persona identifies the persona, so it
+    /// is not compared as a namespace.
+    pub fn check_compatibility(&self, left: &Csm1Code, right: &Csm1Code) -> Vec<Incompatibility> {
+        let side = |code| match self.resolve(code) {
+            Some(persona) => Side {
+                code,
+                namespace: None,
+                capabilities: persona.capabilities_at(code.adherence_level),
+            },
+            None => Side::builtin(code),
+        };
+        incompatibilities(&side(left), &side(right))
+    }
+
+    /// Parse a registry file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if the JSON is invalid or any
+    /// persona fails the checks of [`register`](Self::register).
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(s: &str) -> Csm1Code {
+        Csm1Code::parse(s).unwrap()
+    }
+
+    #[test]
+    fn baseline_levels_match_table() {
+        for level in [3, 4] {
+            let caps = capabilities(Persona::Sentinel, level);
+            assert_eq!(caps.sensitive_content, Permission::Restricted);
+            assert_eq!(caps.tool_use, Permission::Restricted);
+            assert_eq!(caps.data_retention, Permission::Forbidden);
+        }
+    }
+
+    #[test]
+    fn levels_shift_permissions() {
+        let disabled = capabilities(Persona::Nanny, 0);
+        assert!(Behavior::all().iter().all(|&b| disabled.allows(b)));
+
+        let light = capabilities(Persona::Nanny, 1);
+        assert_eq!(light.sensitive_content, Permission::Restricted);
+        assert_eq!(light.tool_use, Permission::Allowed);
+
+        let max = capabilities(Persona::Muse, 5);
+        assert_eq!(max.sensitive_content, Permission::Restricted);
+        assert_eq!(max.data_retention, Permission::Forbidden);
+
+        assert_eq!(capabilities(Persona::Muse, 9).adherence_level, 5);
+    }
+
+    #[test]
+    fn strictness_is_monotonic_in_level() {
+        for &persona in Persona::all() {
+            for level in 1..=5 {
+                let (lower, higher) = (
+                    capabilities(persona, level - 1),
+                    capabilities(persona, level),
+                );
+                assert!(higher.is_at_least_as_strict_as(&lower), "{persona} {level}");
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_covers_every_cell() {
+        let all = matrix();
+        assert_eq!(all.len(), Persona::all().len() * 6);
+        assert_eq!(all[0], capabilities(Persona::Nanny, 0));
+    }
+
+    #[test]
+    fn compatible_codes() {
+        assert!(check_compatibility(&code("N4+F"), &code("Z3+F+P")).is_empty());
+        // An unscoped code applies everywhere.
+        assert!(code("G3").is_compatible_with(&code("A4+W")));
+        assert!(code("M2@1.0.0").is_compatible_with(&code("M3@1.4.2")));
+    }
+
+    #[test]
+    fn incompatible_codes_list_every_reason() {
+        let problems = check_compatibility(&code("N5+F:SEC@1.0.0"), &code("M1+W@2.0.0"));
+        assert_eq!(
+            problems,
+            vec![
+                Incompatibility::Namespace {
+                    left: Some("SEC".into()),
+                    right: None,
+                },
+                Incompatibility::Version {
+                    left: "1.0.0".into(),
+                    right: "2.0.0".into(),
+                },
+                Incompatibility::DisjointScopes,
+                Incompatibility::Behavior {
+                    behavior: Behavior::SensitiveContent,
+                    left: Permission::Forbidden,
+                    right: Permission::Allowed,
+                },
+                Incompatibility::Behavior {
+                    behavior: Behavior::ToolUse,
+                    left: Permission::Forbidden,
+                    right: Permission::Allowed,
+                },
+                Incompatibility::Behavior {
+                    behavior: Behavior::DataRetention,
+                    left: Permission::Forbidden,
+                    right: Permission::Allowed,
+                },
+            ]
+        );
+        assert_eq!(
+            problems[3].to_string(),
+            "sensitive_content is forbidden on one side and allowed on the other"
+        );
+    }
+
+    #[test]
+    fn compatibility_is_symmetric() {
+        let (a, b) = (code("N3"), code("C0"));
+        assert!(!a.is_compatible_with(&b));
+        assert!(!b.is_compatible_with(&a));
+    }
+
+    fn tutor() -> CustomPersona {
+        CustomPersona::new("TUTOR", "Tutor")
+            .with_alias('T')
+            .with_description("Homework helper")
+            .with_default_scopes(vec![Scope::Education])
+            .with_capabilities(CapabilityProfile {
+                sensitive_content: Permission::Forbidden,
+                tool_use: Permission::Allowed,
+                data_retention: Permission::Restricted,
+            })
+    }
+
+    #[test]
+    fn registry_rejects_bad_entries() {
+        let mut registry = PersonaRegistry::new();
+        registry.register(tutor()).unwrap();
+        assert!(registry.register(tutor()).is_err());
+        assert!(registry
+            .register(CustomPersona::new("OTHER", "Other").with_alias('T'))
+            .is_err());
+        assert!(registry
+            .register(CustomPersona::new("OTHER", "Other").with_alias('N'))
+            .is_err());
+        assert!(registry
+            .register(CustomPersona::new("lower", "Lower"))
+            .is_err());
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.by_alias('T').unwrap().id, "TUTOR");
+    }
+
+    #[test]
+    fn registry_parses_and_encodes_custom_codes() {
+        let mut registry = PersonaRegistry::new();
+        registry.register(tutor()).unwrap();
+        registry
+            .register(CustomPersona::new("MYPERSONA", "Mine"))
+            .unwrap();
+
+        let aliased = registry.parse("t4@1.0.0").unwrap();
+        assert_eq!(aliased.persona, Persona::Custom);
+        assert_eq!(aliased.namespace.as_deref(), Some("TUTOR"));
+        assert_eq!(aliased.scopes, vec![Scope::Education]);
+        assert_eq!(registry.encode(&aliased), "T4@1.0.0");
+        assert_eq!(registry.parse("C4:TUTOR@1.0.0").unwrap(), aliased);
+
+        let explicit = registry.parse("T2+F").unwrap();
+        assert_eq!(explicit.scopes, vec![Scope::Family]);
+        assert_eq!(registry.encode(&explicit), "T2+F");
+
+        let plain = registry.parse("C5:MYPERSONA").unwrap();
+        assert_eq!(registry.resolve(&plain).unwrap().name, "Mine");
+        assert_eq!(registry.encode(&plain), "C5:MYPERSONA");
+
+        assert!(registry.parse("T3:OTHER").is_err());
+        assert_eq!(
+            registry.parse("Q3").unwrap_err().without_span(),
+            &VcpError::InvalidPersona('Q')
+        );
+        assert_eq!(registry.encode(&code("N5+F")), "N5+F");
+    }
+
+    #[test]
+    fn registry_applies_custom_profiles() {
+        let mut registry = PersonaRegistry::new();
+        registry.register(tutor()).unwrap();
+
+        let tutor = registry.parse("T3").unwrap();
+        let caps = registry.capabilities(&tutor);
+        assert_eq!(caps.sensitive_content, Permission::Forbidden);
+        assert_eq!(caps.tool_use, Permission::Allowed);
+
+        // The built-in Custom row restricts everything.
+        assert!(check_compatibility(&tutor, &code("M1")).len() == 1);
+        let problems = registry.check_compatibility(&tutor, &code("M1+E"));
+        assert_eq!(
+            problems,
+            vec![Incompatibility::Behavior {
+                behavior: Behavior::SensitiveContent,
+                left: Permission::Forbidden,
+                right: Permission::Allowed,
+            }]
+        );
+        assert!(registry.check_compatibility(&tutor, &code("N3")).is_empty());
+    }
+
+    #[test]
+    fn registry_json_round_trip() {
+        let mut registry = PersonaRegistry::new();
+        registry.register(tutor()).unwrap();
+        registry
+            .register(CustomPersona::new("PLAIN", "Plain"))
+            .unwrap();
+
+        let json = registry.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["personas"][1]["alias"], "T");
+        assert!(value["personas"][0].get("alias").is_none());
+        assert_eq!(PersonaRegistry::from_json(&json).unwrap(), registry);
+
+        let duplicate = r#"{"personas": [{"id": "A", "name": "a"}, {"id": "A", "name": "b"}]}"#;
+        assert!(PersonaRegistry::from_json(duplicate).is_err());
+    }
+}
@@ -6,12 +6,43 @@
 //!
 //! This layer is **not diagnostic or therapeutic**; it reflects
 //! self-reported state for adaptation purposes only.
+//!
+//! ## Decay metadata
+//!
+//! A dimension may carry where it came from, when it was declared and how
+//! sure the sender is, so a receiver can decay it the same way. Each is an
+//! optional suffix, written in this order after `value:intensity[ext]`:
+//!
+//! | Suffix | Field | Example |
+//! |--------|-------|---------|
+//! | `~<code>` | [`source`](PersonalDimension::source) (`d`eclared, `i`nferred, `l`ocal inference, `p`reset, decaye`x`) | `~d` |
+//! | `@<unix seconds>` | [`declared_at`](PersonalDimension::declared_at) | `@1700000000` |
+//! | `!<0-1>` | [`confidence`](PersonalDimension::confidence), to two decimals | `!0.8` |
+//!
+//! `🧠focused:4~d@1700000000!0.8` round-trips all three; `🧠focused:4`
+//! still parses, with none set. The ASCII `ctx1` form uses the same
+//! suffixes.
+//!
+//! ## Noised export
+//!
+//! Telemetry should not carry exact states. [`PersonalState::noised`]
+//! perturbs every intensity by randomized response over the 1-5 scale:
+//! the true intensity is kept with probability
+//! `e^ε / (e^ε + 4)` and otherwise replaced by one of the other four,
+//! chosen uniformly. Each report is then ε-differentially private with
+//! respect to its intensities; smaller ε means more noise (ε = 0 is pure
+//! noise, ε ≈ 1.4 keeps the truth half the time). Aggregators recover
+//! population frequencies with [`estimate_intensity_frequencies`].
 
 use std::fmt;
 
+use chrono::{DateTime, Utc};
+use rand::{Rng, RngExt};
 use serde::{Deserialize, Serialize};
 
-use crate::error::{VcpError, VcpResult};
+use crate::context::{ascii_escape, ascii_unescape};
+use crate::error::{Span, VcpError, VcpResult};
+use crate::extensions::personal::SignalSource;
 
 // ── Dimension enums ─────────────────────────────────────────
 
@@ -50,6 +81,22 @@ impl PersonalDimensionKind {
         }
     }
 
+    /// All five dimensions in wire order.
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::CognitiveState,
+            Self::EmotionalTone,
+            Self::EnergyLevel,
+            Self::PerceivedUrgency,
+            Self::BodySignals,
+        ]
+    }
+
+    /// Parse the snake-case label produced by `Display` (e.g. `energy_level`).
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|k| k.to_string() == label)
+    }
+
     /// The set of valid category names for this dimension.
     pub fn valid_values(self) -> &'static [&'static str] {
         match self {
@@ -88,6 +135,15 @@ pub struct PersonalDimension {
     /// Optional extended qualifier (e.g. "migraine", "bathroom").
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extended: Option<String>,
+    /// How the signal was obtained, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SignalSource>,
+    /// When the signal was declared, to whole seconds on the wire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declared_at: Option<DateTime<Utc>>,
+    /// How sure the sender is of the signal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
 }
 
 impl PersonalDimension {
@@ -104,6 +160,9 @@ impl PersonalDimension {
             value: value.into(),
             intensity,
             extended: None,
+            source: None,
+            declared_at: None,
+            confidence: None,
         })
     }
 
@@ -124,10 +183,35 @@ impl PersonalDimension {
             value: value.into(),
             intensity,
             extended: Some(extended.into()),
+            source: None,
+            declared_at: None,
+            confidence: None,
         })
     }
 
-    /// Encode to wire-format segment: `value:intensity` or `value:intensity[ext]`.
+    /// Record how the signal was obtained.
+    #[must_use]
+    pub fn with_source(mut self, source: SignalSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Record when the signal was declared.
+    #[must_use]
+    pub fn with_declared_at(mut self, at: DateTime<Utc>) -> Self {
+        self.declared_at = Some(at);
+        self
+    }
+
+    /// Record how sure the sender is of the signal.
+    #[must_use]
+    pub fn with_confidence(mut self, confidence: Confidence) -> Self {
+        self.confidence = Some(confidence);
+        self
+    }
+
+    /// Encode to wire-format segment: `value:intensity[ext]` followed by
+    /// any [decay metadata](self#decay-metadata) suffixes.
     pub fn to_wire(&self) -> String {
         let mut s = format!("{}:{}", self.value, self.intensity);
         if let Some(ref ext) = self.extended {
@@ -135,16 +219,39 @@ impl PersonalDimension {
             s.push_str(ext);
             s.push(']');
         }
+        s.push_str(&self.metadata_suffix());
         s
     }
 
-    /// Parse from wire-format segment: `value:intensity` or `value:intensity[ext]`.
+    /// The `~source@declared!confidence` suffix, empty if none are set.
+    pub(crate) fn metadata_suffix(&self) -> String {
+        let mut s = String::new();
+        if let Some(source) = self.source {
+            s.push('~');
+            s.push(source.code());
+        }
+        if let Some(at) = self.declared_at {
+            s.push('@');
+            s.push_str(&at.timestamp().to_string());
+        }
+        if let Some(confidence) = self.confidence {
+            s.push('!');
+            s.push_str(&confidence.to_string());
+        }
+        s
+    }
+
+    /// Parse from wire-format segment: `value:intensity`,
+    /// `value:intensity[ext]`, and either followed by
+    /// [decay metadata](self#decay-metadata) suffixes.
     ///
     /// # Errors
     ///
     /// Returns [`VcpError::ParseError`] if the wire format is malformed,
     /// or [`VcpError::InvalidIntensity`] if the intensity is out of range.
     pub fn from_wire(wire: &str) -> VcpResult<Self> {
+        let (wire, suffix) = split_metadata_suffix(wire);
+
         // Check for extended: value:intensity[ext]
         let (main, extended) = if let Some(bracket_start) = wire.find('[') {
             if !wire.ends_with(']') {
@@ -174,11 +281,47 @@ impl PersonalDimension {
             return Err(VcpError::InvalidIntensity(intensity));
         }
 
-        Ok(Self {
+        let mut dim = Self {
             value,
             intensity,
             extended,
-        })
+            source: None,
+            declared_at: None,
+            confidence: None,
+        };
+        dim.parse_metadata_suffix(suffix)?;
+        Ok(dim)
+    }
+
+    /// Fill in the fields named by a `~source@declared!confidence` suffix.
+    fn parse_metadata_suffix(&mut self, suffix: &str) -> VcpResult<()> {
+        let bad = |what: &str| {
+            VcpError::ParseError(format!("invalid {what} in personal metadata: {suffix}"))
+        };
+        let mut rest = suffix;
+        if let Some(tail) = rest.strip_prefix('~') {
+            let mut chars = tail.chars();
+            let code = chars.next().ok_or_else(|| bad("source"))?;
+            self.source = Some(SignalSource::from_code(code).ok_or_else(|| bad("source"))?);
+            rest = chars.as_str();
+        }
+        if let Some(tail) = rest.strip_prefix('@') {
+            let end = tail.find('!').unwrap_or(tail.len());
+            let secs: i64 = tail[..end].parse().map_err(|_| bad("timestamp"))?;
+            self.declared_at =
+                Some(DateTime::from_timestamp(secs, 0).ok_or_else(|| bad("timestamp"))?);
+            rest = &tail[end..];
+        }
+        if let Some(tail) = rest.strip_prefix('!') {
+            let value: f64 = tail.parse().map_err(|_| bad("confidence"))?;
+            self.confidence = Some(Confidence::new(value)?);
+            rest = "";
+        }
+        if rest.is_empty() {
+            Ok(())
+        } else {
+            Err(bad("suffix"))
+        }
     }
 }
 
@@ -188,6 +331,89 @@ impl fmt::Display for PersonalDimension {
     }
 }
 
+/// Split `value:intensity[ext]~s@t!c` into the dimension and its metadata
+/// suffix. The suffix starts at the first `~`, `@` or `!` after the
+/// intensity (and after the closing `]` when there is one).
+pub(crate) fn split_metadata_suffix(wire: &str) -> (&str, &str) {
+    let search_from = match wire.find('[') {
+        Some(_) => wire.rfind(']').map_or(wire.len(), |i| i + 1),
+        None => wire.find(':').unwrap_or(0),
+    };
+    match wire[search_from..].find(['~', '@', '!']) {
+        Some(i) => wire.split_at(search_from + i),
+        None => (wire, ""),
+    }
+}
+
+// ── Confidence ──────────────────────────────────────────────
+
+/// Confidence in a signal, 0.0-1.0.
+///
+/// Kept in hundredths, so personal state stays `Eq` and the wire form
+/// round-trips exactly; finer values are rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "f64", try_from = "f64")]
+pub struct Confidence(u8);
+
+impl Confidence {
+    /// Full confidence.
+    pub const CERTAIN: Self = Self(100);
+
+    /// Round `value` to hundredths.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] unless `value` is in 0.0..=1.0.
+    pub fn new(value: f64) -> VcpResult<Self> {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(VcpError::ParseError(format!(
+                "confidence must be between 0 and 1, got {value}"
+            )));
+        }
+        // In range, so the rounded value fits in 0..=100.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let hundredths = (value * 100.0).round() as u8;
+        Ok(Self(hundredths))
+    }
+
+    /// The confidence as a fraction.
+    pub fn get(self) -> f64 {
+        f64::from(self.0) / 100.0
+    }
+
+    /// The confidence in hundredths, 0-100.
+    pub fn hundredths(self) -> u8 {
+        self.0
+    }
+}
+
+impl From<Confidence> for f64 {
+    fn from(confidence: Confidence) -> Self {
+        confidence.get()
+    }
+}
+
+impl TryFrom<f64> for Confidence {
+    type Error = VcpError;
+
+    fn try_from(value: f64) -> VcpResult<Self> {
+        Self::new(value)
+    }
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 => f.write_str("0"),
+            100 => f.write_str("1"),
+            h => {
+                let digits = format!("{h:02}");
+                write!(f, "0.{}", digits.trim_end_matches('0'))
+            }
+        }
+    }
+}
+
 // ── Full personal state ─────────────────────────────────────
 
 /// Complete personal state across all five dimensions.
@@ -222,6 +448,146 @@ impl PersonalState {
             || self.body.is_some()
     }
 
+    /// Get the dimension for `kind`, if set.
+    pub fn get(&self, kind: PersonalDimensionKind) -> Option<&PersonalDimension> {
+        match kind {
+            PersonalDimensionKind::CognitiveState => self.cognitive.as_ref(),
+            PersonalDimensionKind::EmotionalTone => self.emotional.as_ref(),
+            PersonalDimensionKind::EnergyLevel => self.energy.as_ref(),
+            PersonalDimensionKind::PerceivedUrgency => self.urgency.as_ref(),
+            PersonalDimensionKind::BodySignals => self.body.as_ref(),
+        }
+    }
+
+    /// Set the dimension for `kind`.
+    pub fn set(&mut self, kind: PersonalDimensionKind, dim: PersonalDimension) {
+        let slot = match kind {
+            PersonalDimensionKind::CognitiveState => &mut self.cognitive,
+            PersonalDimensionKind::EmotionalTone => &mut self.emotional,
+            PersonalDimensionKind::EnergyLevel => &mut self.energy,
+            PersonalDimensionKind::PerceivedUrgency => &mut self.urgency,
+            PersonalDimensionKind::BodySignals => &mut self.body,
+        };
+        *slot = Some(dim);
+    }
+
+    /// Clear the dimension for `kind`, returning it.
+    pub fn remove(&mut self, kind: PersonalDimensionKind) -> Option<PersonalDimension> {
+        match kind {
+            PersonalDimensionKind::CognitiveState => self.cognitive.take(),
+            PersonalDimensionKind::EmotionalTone => self.emotional.take(),
+            PersonalDimensionKind::EnergyLevel => self.energy.take(),
+            PersonalDimensionKind::PerceivedUrgency => self.urgency.take(),
+            PersonalDimensionKind::BodySignals => self.body.take(),
+        }
+    }
+
+    /// Overlay `other`: every dimension it sets replaces the one here.
+    pub fn merge(&mut self, other: &PersonalState) {
+        for &kind in PersonalDimensionKind::all() {
+            if let Some(dim) = other.get(kind) {
+                self.set(kind, dim.clone());
+            }
+        }
+    }
+
+    /// A copy with every intensity perturbed by randomized response, for
+    /// analytics export.
+    ///
+    /// `epsilon` is the privacy budget per dimension (see the
+    /// [module docs](self)); negative or NaN values are treated as 0.
+    /// Categorical values are kept; extended qualifiers and decay metadata
+    /// are dropped, since free text and timestamps would defeat the noise.
+    #[must_use]
+    pub fn noised<R: Rng + ?Sized>(&self, epsilon: f64, rng: &mut R) -> Self {
+        let keep = intensity_keep_probability(epsilon);
+        let mut out = PersonalState::default();
+        for &kind in PersonalDimensionKind::all() {
+            if let Some(dim) = self.get(kind) {
+                let intensity = if rng.random_bool(keep) {
+                    dim.intensity
+                } else {
+                    // Uniform over the four other intensities.
+                    let other = rng.random_range(1..INTENSITY_LEVELS);
+                    if other >= dim.intensity {
+                        other + 1
+                    } else {
+                        other
+                    }
+                };
+                out.set(
+                    kind,
+                    PersonalDimension {
+                        value: dim.value.clone(),
+                        intensity,
+                        extended: None,
+                        source: None,
+                        declared_at: None,
+                        confidence: None,
+                    },
+                );
+            }
+        }
+        out
+    }
+
+    /// Encode to the ASCII-safe personal half of the `ctx1` wire format.
+    ///
+    /// Format: `<label>=<value>:<intensity>[ext]` plus any decay metadata
+    /// suffixes, joined by `;`. Values and extended qualifiers are
+    /// percent-encoded outside `[A-Za-z0-9_.-]`.
+    pub fn to_ascii_wire(&self) -> String {
+        PersonalDimensionKind::all()
+            .iter()
+            .filter_map(|&kind| {
+                let dim = self.get(kind)?;
+                let mut s = format!("{kind}={}:{}", ascii_escape(&dim.value, &[]), dim.intensity);
+                if let Some(ref ext) = dim.extended {
+                    s.push('[');
+                    s.push_str(&ascii_escape(ext, &[]));
+                    s.push(']');
+                }
+                s.push_str(&dim.metadata_suffix());
+                Some(s)
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Parse the personal half of the `ctx1` wire format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for an unknown dimension label or
+    /// malformed segment, or [`VcpError::InvalidIntensity`] if an
+    /// intensity is out of range.
+    pub fn from_ascii_wire(wire: &str) -> VcpResult<Self> {
+        let mut state = PersonalState::default();
+        for segment in wire.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            let (label, rest) = segment.split_once('=').ok_or_else(|| {
+                VcpError::ParseError(format!("expected <dimension>=<value>, got: {segment}"))
+                    .at(Span::of(wire, segment))
+                    .expecting(&["<dimension>=<value>:<intensity>"])
+            })?;
+            let kind = PersonalDimensionKind::from_label(label).ok_or_else(|| {
+                VcpError::ParseError(format!("unknown personal dimension: {label}"))
+                    .at(Span::of(wire, label))
+                    .expecting(&["personal dimension label"])
+            })?;
+            let at_rest = |e: VcpError| {
+                e.at(Span::of(wire, rest))
+                    .expecting(&["<value>:<intensity 1-5>"])
+            };
+            let mut dim = PersonalDimension::from_wire(rest).map_err(at_rest)?;
+            dim.value = ascii_unescape(&dim.value).map_err(at_rest)?;
+            if let Some(ext) = dim.extended.take() {
+                dim.extended = Some(ascii_unescape(&ext).map_err(at_rest)?);
+            }
+            state.set(kind, dim);
+        }
+        Ok(state)
+    }
+
     /// Encode personal state to wire format (the part after `\u{2016}`).
     ///
     /// Format: `<symbol><value>:<intensity>[|...]`
@@ -266,13 +632,21 @@ impl PersonalState {
 
             // The segment starts with an emoji, then value:intensity.
             // Emojis can be multi-byte, so we need to find the split point.
-            let (symbol, rest) = split_leading_emoji(segment)?;
+            let (symbol, rest) = split_leading_emoji(segment).map_err(|e| {
+                e.at(Span::of(wire, segment))
+                    .expecting(&["personal dimension symbol"])
+            })?;
 
             let kind = PersonalDimensionKind::from_symbol(symbol).ok_or_else(|| {
                 VcpError::ParseError(format!("unknown personal dimension symbol: {symbol}"))
+                    .at(Span::of(wire, symbol))
+                    .expecting(&["personal dimension symbol"])
             })?;
 
-            let dim = PersonalDimension::from_wire(rest)?;
+            let dim = PersonalDimension::from_wire(rest).map_err(|e| {
+                e.at(Span::of(wire, rest))
+                    .expecting(&["<value>:<intensity 1-5>"])
+            })?;
 
             match kind {
                 PersonalDimensionKind::CognitiveState => state.cognitive = Some(dim),
@@ -323,9 +697,44 @@ fn split_leading_emoji(s: &str) -> VcpResult<(&str, &str)> {
     Ok((&s[..first_char_len], &s[first_char_len..]))
 }
 
+// ── Randomized response ─────────────────────────────────────
+
+/// Number of intensity levels (1-5).
+const INTENSITY_LEVELS: u8 = 5;
+
+/// Probability that [`PersonalState::noised`] reports the true intensity
+/// at privacy budget `epsilon`: `e^ε / (e^ε + 4)`.
+///
+/// Ranges from 0.2 (ε ≤ 0, uniform noise) to 1 (ε = ∞, no noise).
+pub fn intensity_keep_probability(epsilon: f64) -> f64 {
+    let epsilon = epsilon.max(0.0);
+    1.0 / (1.0 + f64::from(INTENSITY_LEVELS - 1) * (-epsilon).exp())
+}
+
+/// Estimate the true share of each intensity (index 0 = intensity 1) from
+/// `counts` of noised reports made at `epsilon`.
+///
+/// Inverts the randomized response: with keep probability `p` and
+/// `q = (1 - p) / 4`, each share is `(observed - q) / (p - q)`. Estimates
+/// are unbiased, so individual entries may fall slightly outside 0..=1 for
+/// small samples. Returns all zeros when there are no reports or
+/// `epsilon` is 0 (pure noise carries no signal).
+pub fn estimate_intensity_frequencies(counts: &[u64; 5], epsilon: f64) -> [f64; 5] {
+    let total: u64 = counts.iter().sum();
+    let keep = intensity_keep_probability(epsilon);
+    let flip = (1.0 - keep) / f64::from(INTENSITY_LEVELS - 1);
+    if total == 0 || keep - flip <= f64::EPSILON {
+        return [0.0; 5];
+    }
+    #[allow(clippy::cast_precision_loss)] // report counts stay far below 2^52
+    let share = |count: u64| count as f64 / total as f64;
+    counts.map(|count| (share(count) - flip) / (keep - flip))
+}
+
 // ── Tests ───────────────────────────────────────────────────
 
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
@@ -371,6 +780,53 @@ mod tests {
         assert!(PersonalDimension::from_wire("focused:9").is_err());
     }
 
+    #[test]
+    fn dimension_metadata_roundtrips_and_old_form_still_parses() {
+        let declared = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let dim = PersonalDimension::new("focused", 4)
+            .unwrap()
+            .with_source(SignalSource::Declared)
+            .with_declared_at(declared)
+            .with_confidence(Confidence::new(0.8).unwrap());
+        assert_eq!(dim.to_wire(), "focused:4~d@1700000000!0.8");
+        assert_eq!(PersonalDimension::from_wire(&dim.to_wire()).unwrap(), dim);
+
+        // Any subset, after an extended qualifier.
+        let body = PersonalDimension::from_wire("pain:3[migraine]@1700000000").unwrap();
+        assert_eq!(body.extended.as_deref(), Some("migraine"));
+        assert_eq!(body.declared_at, Some(declared));
+        assert!(body.source.is_none() && body.confidence.is_none());
+        let inferred = PersonalDimension::from_wire("calm:2~i!0.05").unwrap();
+        assert_eq!(inferred.source, Some(SignalSource::Inferred));
+        assert_eq!(inferred.to_wire(), "calm:2~i!0.05");
+
+        let old = PersonalDimension::from_wire("focused:4").unwrap();
+        assert_eq!(old, PersonalDimension::new("focused", 4).unwrap());
+
+        let mut state = PersonalState::default();
+        state.cognitive = Some(dim);
+        state.body = Some(body);
+        assert_eq!(PersonalState::from_wire(&state.to_wire()).unwrap(), state);
+        assert_eq!(
+            PersonalState::from_ascii_wire(&state.to_ascii_wire()).unwrap(),
+            state
+        );
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(json.contains(r#""confidence":0.8"#), "{json}");
+        assert_eq!(serde_json::from_str::<PersonalState>(&json).unwrap(), state);
+
+        for bad in [
+            "focused:4~q",
+            "focused:4~",
+            "focused:4@soon",
+            "focused:4!1.5",
+            "focused:4!0.8~d",
+            "focused:4~dd",
+        ] {
+            assert!(PersonalDimension::from_wire(bad).is_err(), "{bad}");
+        }
+    }
+
     #[test]
     fn personal_state_empty() {
         let ps = PersonalState::default();
@@ -452,4 +908,39 @@ mod tests {
         let parsed: PersonalState = serde_json::from_str(&json).unwrap();
         assert_eq!(ps, parsed);
     }
+
+    #[test]
+    fn noised_intensities_follow_randomized_response() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let ps = PersonalState::from_wire("🧠focused:4|🩺pain:2[migraine]").unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let exact = ps.noised(f64::INFINITY, &mut rng);
+        assert_eq!(exact.to_wire(), "🧠focused:4|🩺pain:2");
+
+        let epsilon = 1.0;
+        let mut counts = [0u64; 5];
+        for _ in 0..20_000 {
+            let noised = ps.noised(epsilon, &mut rng);
+            let cognitive = noised.cognitive.unwrap();
+            assert_eq!(cognitive.value, "focused");
+            assert!(noised.body.unwrap().extended.is_none());
+            assert!(noised.energy.is_none());
+            counts[usize::from(cognitive.intensity - 1)] += 1;
+        }
+        let estimate = estimate_intensity_frequencies(&counts, epsilon);
+        for (i, share) in estimate.iter().enumerate() {
+            let expected = if i == 3 { 1.0 } else { 0.0 };
+            assert!((share - expected).abs() < 0.05, "{estimate:?}");
+        }
+
+        assert!((intensity_keep_probability(0.0) - 0.2).abs() < 1e-12);
+        assert!((intensity_keep_probability(-1.0) - 0.2).abs() < 1e-12);
+        assert!((intensity_keep_probability(f64::NAN) - 0.2).abs() < 1e-12);
+        assert!(estimate_intensity_frequencies(&counts, 0.0)
+            .iter()
+            .all(|share| *share == 0.0));
+    }
 }
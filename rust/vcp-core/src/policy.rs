@@ -0,0 +1,409 @@
+//! Organization-wide policy applied to incoming codes and tokens.
+//!
+//! A deployment describes what it will accept in an [`OrgPolicy`] and
+//! runs every CSM-1 code or VCP/I token through a [`PolicyEngine`] before
+//! injecting it. The engine either rejects the input or returns it,
+//! possibly tightened, together with the reasons.
+//!
+//! | Constraint | Applies to | Effect |
+//! |------------|------------|--------|
+//! | `min_adherence`, `min_adherence_by_scope` | codes | level raised to the minimum |
+//! | `banned_personas` | codes | rejected |
+//! | `required_namespaces` | codes, tokens | rejected unless the namespace is listed |
+//! | `allowed_domains` | tokens | rejected unless the domain is listed |
+//!
+//! Empty lists impose no constraint. A code applies to every scope it
+//! lists, or to all scopes when it lists none, and must meet the highest
+//! minimum among them.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::csm1::{Csm1Code, Persona, Scope};
+//! use vcp_core::identity::VcpToken;
+//! use vcp_core::policy::{OrgPolicy, PolicyEngine};
+//!
+//! let engine = PolicyEngine::new(
+//!     OrgPolicy::new()
+//!         .with_scope_minimum(Scope::Family, 4)
+//!         .with_banned_persona(Persona::Muse)
+//!         .with_allowed_domain("family"),
+//! );
+//!
+//! let decision = engine.apply(&Csm1Code::parse("N2+F").unwrap());
+//! assert!(decision.allowed);
+//! assert_eq!(decision.adjusted.unwrap().encode(), "N4+F");
+//!
+//! assert!(!engine.apply(&Csm1Code::parse("M3").unwrap()).allowed);
+//! assert!(!engine.apply(&VcpToken::parse("work.safe.guide").unwrap()).allowed);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::csm1::{Csm1Code, Persona, Scope};
+use crate::error::VcpResult;
+use crate::identity::VcpToken;
+
+// ── Policy ──────────────────────────────────────────────────
+
+/// Constraints an organization places on every code and token.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgPolicy {
+    /// Minimum adherence level in every scope.
+    #[serde(default)]
+    pub min_adherence: u8,
+    /// Higher minimums for particular scopes.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub min_adherence_by_scope: BTreeMap<Scope, u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub banned_personas: Vec<Persona>,
+    /// Namespaces a code or token must carry; empty allows any, including
+    /// none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_namespaces: Vec<String>,
+    /// Token domains accepted; empty allows any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_domains: Vec<String>,
+}
+
+impl OrgPolicy {
+    /// A policy with no constraints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_min_adherence(mut self, level: u8) -> Self {
+        self.min_adherence = level;
+        self
+    }
+
+    #[must_use]
+    pub fn with_scope_minimum(mut self, scope: Scope, level: u8) -> Self {
+        self.min_adherence_by_scope.insert(scope, level);
+        self
+    }
+
+    #[must_use]
+    pub fn with_banned_persona(mut self, persona: Persona) -> Self {
+        self.banned_personas.push(persona);
+        self
+    }
+
+    #[must_use]
+    pub fn with_required_namespace(mut self, namespace: &str) -> Self {
+        self.required_namespaces.push(namespace.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn with_allowed_domain(mut self, domain: &str) -> Self {
+        self.allowed_domains.push(domain.to_string());
+        self
+    }
+
+    /// The minimum adherence a code applying to `scopes` must meet, capped
+    /// at 5. An empty `scopes` means every scope.
+    pub fn required_adherence(&self, scopes: &[Scope]) -> u8 {
+        let by_scope = self
+            .min_adherence_by_scope
+            .iter()
+            .filter(|(scope, _)| scopes.is_empty() || scopes.contains(scope))
+            .map(|(_, &level)| level);
+        by_scope.fold(self.min_adherence, u8::max).min(5)
+    }
+
+    /// Parse a policy file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`](crate::error::VcpError::JsonError)
+    /// if the JSON is invalid.
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`](crate::error::VcpError::JsonError)
+    /// if serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+// ── Decisions ───────────────────────────────────────────────
+
+/// Why the engine rejected or changed an input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum PolicyReason {
+    BannedPersona {
+        persona: Persona,
+    },
+    NamespaceNotAllowed {
+        namespace: Option<String>,
+    },
+    DomainNotAllowed {
+        domain: String,
+    },
+    /// The code's level was below the policy minimum and was raised.
+    AdherenceRaised {
+        from: u8,
+        to: u8,
+    },
+}
+
+impl PolicyReason {
+    /// Whether this reason rejects the input (rather than adjusting it).
+    pub fn is_denial(&self) -> bool {
+        !matches!(self, Self::AdherenceRaised { .. })
+    }
+}
+
+impl fmt::Display for PolicyReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BannedPersona { persona } => write!(f, "persona {} is banned", persona.name()),
+            Self::NamespaceNotAllowed { namespace: None } => {
+                f.write_str("a required namespace is missing")
+            }
+            Self::NamespaceNotAllowed {
+                namespace: Some(ns),
+            } => write!(f, "namespace {ns} is not allowed"),
+            Self::DomainNotAllowed { domain } => write!(f, "domain {domain} is not allowed"),
+            Self::AdherenceRaised { from, to } => {
+                write!(f, "adherence raised from {from} to {to}")
+            }
+        }
+    }
+}
+
+/// The engine's verdict on one input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyDecision {
+    pub allowed: bool,
+    /// The code to inject instead of the input, when the policy tightened
+    /// it. Always `None` for rejected inputs and for tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adjusted: Option<Csm1Code>,
+    pub reasons: Vec<PolicyReason>,
+}
+
+impl PolicyDecision {
+    fn from_reasons(reasons: Vec<PolicyReason>, adjusted: Option<Csm1Code>) -> Self {
+        let allowed = !reasons.iter().any(PolicyReason::is_denial);
+        Self {
+            allowed,
+            adjusted: adjusted.filter(|_| allowed),
+            reasons,
+        }
+    }
+}
+
+// ── Engine ──────────────────────────────────────────────────
+
+/// An input the engine can judge.
+#[derive(Debug, Clone, Copy)]
+pub enum PolicySubject<'a> {
+    Code(&'a Csm1Code),
+    Token(&'a VcpToken),
+}
+
+impl<'a> From<&'a Csm1Code> for PolicySubject<'a> {
+    fn from(code: &'a Csm1Code) -> Self {
+        Self::Code(code)
+    }
+}
+
+impl<'a> From<&'a VcpToken> for PolicySubject<'a> {
+    fn from(token: &'a VcpToken) -> Self {
+        Self::Token(token)
+    }
+}
+
+/// Applies an [`OrgPolicy`] to codes and tokens.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyEngine {
+    policy: OrgPolicy,
+}
+
+impl PolicyEngine {
+    pub fn new(policy: OrgPolicy) -> Self {
+        Self { policy }
+    }
+
+    pub fn policy(&self) -> &OrgPolicy {
+        &self.policy
+    }
+
+    /// Judge a code or token.
+    pub fn apply<'a>(&self, subject: impl Into<PolicySubject<'a>>) -> PolicyDecision {
+        match subject.into() {
+            PolicySubject::Code(code) => self.apply_code(code),
+            PolicySubject::Token(token) => self.apply_token(token),
+        }
+    }
+
+    fn check_namespace(&self, namespace: Option<&String>, reasons: &mut Vec<PolicyReason>) {
+        let required = &self.policy.required_namespaces;
+        if !required.is_empty() && !namespace.is_some_and(|ns| required.contains(ns)) {
+            reasons.push(PolicyReason::NamespaceNotAllowed {
+                namespace: namespace.cloned(),
+            });
+        }
+    }
+
+    fn apply_code(&self, code: &Csm1Code) -> PolicyDecision {
+        let mut reasons = Vec::new();
+
+        if self.policy.banned_personas.contains(&code.persona) {
+            reasons.push(PolicyReason::BannedPersona {
+                persona: code.persona,
+            });
+        }
+        self.check_namespace(code.namespace.as_ref(), &mut reasons);
+
+        let required = self.policy.required_adherence(&code.scopes);
+        let mut adjusted = None;
+        if code.adherence_level < required {
+            reasons.push(PolicyReason::AdherenceRaised {
+                from: code.adherence_level,
+                to: required,
+            });
+            adjusted = code.with_level(required).ok();
+        }
+
+        PolicyDecision::from_reasons(reasons, adjusted)
+    }
+
+    fn apply_token(&self, token: &VcpToken) -> PolicyDecision {
+        let mut reasons = Vec::new();
+
+        let domains = &self.policy.allowed_domains;
+        if !domains.is_empty() && !domains.iter().any(|d| d == token.domain()) {
+            reasons.push(PolicyReason::DomainNotAllowed {
+                domain: token.domain().to_string(),
+            });
+        }
+        self.check_namespace(token.namespace.as_ref(), &mut reasons);
+
+        PolicyDecision::from_reasons(reasons, None)
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(s: &str) -> Csm1Code {
+        Csm1Code::parse(s).unwrap()
+    }
+
+    fn token(s: &str) -> VcpToken {
+        VcpToken::parse(s).unwrap()
+    }
+
+    #[test]
+    fn empty_policy_allows_everything_unchanged() {
+        let engine = PolicyEngine::default();
+        let decision = engine.apply(&code("M0"));
+        assert!(decision.allowed);
+        assert_eq!(decision.adjusted, None);
+        assert!(decision.reasons.is_empty());
+        assert!(engine.apply(&token("work.safe.guide:ACME")).allowed);
+    }
+
+    #[test]
+    fn adherence_minimum_uses_highest_applicable_scope() {
+        let policy = OrgPolicy::new()
+            .with_min_adherence(2)
+            .with_scope_minimum(Scope::Family, 4)
+            .with_scope_minimum(Scope::Healthcare, 5);
+        assert_eq!(policy.required_adherence(&[Scope::Work]), 2);
+        assert_eq!(policy.required_adherence(&[Scope::Work, Scope::Family]), 4);
+        assert_eq!(policy.required_adherence(&[]), 5);
+
+        let engine = PolicyEngine::new(policy);
+        let decision = engine.apply(&code("G1+W+F:ORG"));
+        assert!(decision.allowed);
+        assert_eq!(decision.adjusted.unwrap().encode(), "G4+W+F:ORG");
+        assert_eq!(
+            decision.reasons,
+            vec![PolicyReason::AdherenceRaised { from: 1, to: 4 }]
+        );
+
+        let unchanged = engine.apply(&code("G3+W"));
+        assert_eq!(unchanged.adjusted, None);
+    }
+
+    #[test]
+    fn denials_drop_adjustments() {
+        let engine = PolicyEngine::new(
+            OrgPolicy::new()
+                .with_min_adherence(3)
+                .with_banned_persona(Persona::Muse)
+                .with_required_namespace("ORG"),
+        );
+        let decision = engine.apply(&code("M1"));
+        assert!(!decision.allowed);
+        assert_eq!(decision.adjusted, None);
+        assert_eq!(
+            decision.reasons,
+            vec![
+                PolicyReason::BannedPersona {
+                    persona: Persona::Muse
+                },
+                PolicyReason::NamespaceNotAllowed { namespace: None },
+                PolicyReason::AdherenceRaised { from: 1, to: 3 },
+            ]
+        );
+        assert_eq!(decision.reasons[0].to_string(), "persona muse is banned");
+        assert!(engine.apply(&code("N3:ORG")).allowed);
+    }
+
+    #[test]
+    fn tokens_checked_for_domain_and_namespace() {
+        let engine = PolicyEngine::new(
+            OrgPolicy::new()
+                .with_allowed_domain("family")
+                .with_required_namespace("ACME"),
+        );
+        assert!(engine.apply(&token("family.safe.guide:ACME")).allowed);
+
+        let decision = engine.apply(&token("work.safe.guide:OTHER"));
+        assert!(!decision.allowed);
+        assert_eq!(
+            decision.reasons,
+            vec![
+                PolicyReason::DomainNotAllowed {
+                    domain: "work".into()
+                },
+                PolicyReason::NamespaceNotAllowed {
+                    namespace: Some("OTHER".into())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn policy_json_round_trip() {
+        let policy = OrgPolicy::new()
+            .with_scope_minimum(Scope::Family, 4)
+            .with_banned_persona(Persona::Muse)
+            .with_allowed_domain("family");
+        let json = policy.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["min_adherence_by_scope"]["Family"], 4);
+        assert!(value.get("required_namespaces").is_none());
+        assert_eq!(OrgPolicy::from_json(&json).unwrap(), policy);
+        assert_eq!(OrgPolicy::from_json("{}").unwrap(), OrgPolicy::new());
+    }
+}
@@ -0,0 +1,245 @@
+//! Redaction profiles for sharing contexts.
+//!
+//! Personal state is sensitive. Before a context leaves the user's agent,
+//! [`FullContext::redacted`] strips the dimensions a [`RedactionProfile`]
+//! names and records the profile on the context. The record travels in
+//! the wire header, so a receiver knows the context was filtered and a
+//! missing dimension means "withheld", not "unknown":
+//!
+//! | Profile | Removes |
+//! |---------|---------|
+//! | [`share-with-third-party`](RedactionProfile::share_with_third_party) | Body signals, emotional tone |
+//! | [`minimal`](RedactionProfile::minimal) | All personal state |
+//!
+//! | Format | Redacted wire |
+//! |--------|---------------|
+//! | Emoji | `🔒minimal;⏰🌅\|📍🏡` |
+//! | ASCII | `ctx1;redacted=minimal;time=morning;space=home` |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::context::FullContext;
+//! use vcp_core::privacy::RedactionProfile;
+//!
+//! let ctx = FullContext::from_wire("⏰🌅|📍🏡‖🧠focused:4|💭calm:3|🩺pain:2").unwrap();
+//! let shared = ctx.redacted(&RedactionProfile::share_with_third_party());
+//! assert_eq!(shared.to_wire(), "🔒share-with-third-party;⏰🌅|📍🏡‖🧠focused:4");
+//!
+//! let received = FullContext::from_wire(&shared.to_wire()).unwrap();
+//! assert_eq!(received.redactions, ["share-with-third-party"]);
+//! assert!(received.personal.body.is_none());
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::context::FullContext;
+use crate::error::{VcpError, VcpResult};
+use crate::personal::PersonalDimensionKind;
+use crate::situational::SituationalDimension;
+
+/// A named set of dimensions to strip before sharing a context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionProfile {
+    name: String,
+    situational: Vec<SituationalDimension>,
+    personal: Vec<PersonalDimensionKind>,
+}
+
+impl RedactionProfile {
+    /// Name of [`share_with_third_party`](Self::share_with_third_party).
+    pub const SHARE_WITH_THIRD_PARTY: &'static str = "share-with-third-party";
+    /// Name of [`minimal`](Self::minimal).
+    pub const MINIMAL: &'static str = "minimal";
+
+    /// An empty profile. Add dimensions with
+    /// [`with_stripped_situational`](Self::with_stripped_situational) and
+    /// [`with_stripped_personal`](Self::with_stripped_personal).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] unless `name` is non-empty
+    /// lowercase ASCII letters, digits and `-`; it is written to the wire.
+    pub fn new(name: &str) -> VcpResult<Self> {
+        if !is_profile_name(name) {
+            return Err(VcpError::ParseError(format!(
+                "invalid redaction profile name '{name}' (expected [a-z0-9-]+)"
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            situational: Vec::new(),
+            personal: Vec::new(),
+        })
+    }
+
+    /// Strips body signals and emotional tone: the dimensions closest to
+    /// health and mood data.
+    pub fn share_with_third_party() -> Self {
+        Self {
+            name: Self::SHARE_WITH_THIRD_PARTY.to_string(),
+            situational: Vec::new(),
+            personal: vec![
+                PersonalDimensionKind::BodySignals,
+                PersonalDimensionKind::EmotionalTone,
+            ],
+        }
+    }
+
+    /// Strips all personal state, keeping only the situational half.
+    pub fn minimal() -> Self {
+        Self {
+            name: Self::MINIMAL.to_string(),
+            situational: Vec::new(),
+            personal: PersonalDimensionKind::all().to_vec(),
+        }
+    }
+
+    /// The built-in profile called `name`, if any.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            Self::SHARE_WITH_THIRD_PARTY => Some(Self::share_with_third_party()),
+            Self::MINIMAL => Some(Self::minimal()),
+            _ => None,
+        }
+    }
+
+    /// Also strip a situational dimension.
+    #[must_use]
+    pub fn with_stripped_situational(mut self, dim: SituationalDimension) -> Self {
+        if !self.situational.contains(&dim) {
+            self.situational.push(dim);
+        }
+        self
+    }
+
+    /// Also strip a personal dimension.
+    #[must_use]
+    pub fn with_stripped_personal(mut self, kind: PersonalDimensionKind) -> Self {
+        if !self.personal.contains(&kind) {
+            self.personal.push(kind);
+        }
+        self
+    }
+
+    /// The name recorded on redacted contexts.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Situational dimensions this profile strips.
+    pub fn stripped_situational(&self) -> &[SituationalDimension] {
+        &self.situational
+    }
+
+    /// Personal dimensions this profile strips.
+    pub fn stripped_personal(&self) -> &[PersonalDimensionKind] {
+        &self.personal
+    }
+
+    /// Strip this profile's dimensions from `ctx` and record the profile
+    /// in [`FullContext::redactions`]. Applying a profile twice records
+    /// it once.
+    pub fn apply(&self, ctx: &mut FullContext) {
+        for &dim in &self.situational {
+            ctx.situational.remove(dim);
+        }
+        for &kind in &self.personal {
+            ctx.personal.remove(kind);
+        }
+        if !ctx.redactions.contains(&self.name) {
+            ctx.redactions.push(self.name.clone());
+        }
+    }
+}
+
+impl fmt::Display for RedactionProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl FromStr for RedactionProfile {
+    type Err = VcpError;
+
+    /// Look up a built-in profile by name.
+    fn from_str(s: &str) -> VcpResult<Self> {
+        Self::builtin(s).ok_or_else(|| {
+            VcpError::ParseError(format!(
+                "unknown redaction profile '{s}' (expected {} or {})",
+                Self::SHARE_WITH_THIRD_PARTY,
+                Self::MINIMAL
+            ))
+        })
+    }
+}
+
+/// `true` if `name` can appear in a wire redaction header.
+pub(crate) fn is_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WireFormat;
+
+    const WIRE: &str = "⏰🌅|📍🏡|👥👶‖🧠focused:4|💭calm:3|🔋rested:3|🩺pain:2";
+
+    #[test]
+    fn builtin_profiles_strip_their_dimensions() {
+        let ctx = FullContext::from_wire(WIRE).unwrap();
+
+        let shared = ctx.redacted(&RedactionProfile::share_with_third_party());
+        assert!(shared.personal.body.is_none());
+        assert!(shared.personal.emotional.is_none());
+        assert!(shared.personal.cognitive.is_some());
+        assert_eq!(shared.situational, ctx.situational);
+
+        let minimal = shared.redacted(&"minimal".parse().unwrap());
+        assert!(!minimal.personal.has_any());
+        assert_eq!(minimal.redactions, ["share-with-third-party", "minimal"]);
+        assert_eq!(
+            minimal.to_wire(),
+            "🔒share-with-third-party,minimal;⏰🌅|📍🏡|👥👶"
+        );
+        assert!(ctx.redactions.is_empty());
+        assert!("everything".parse::<RedactionProfile>().is_err());
+    }
+
+    #[test]
+    fn redaction_flag_round_trips_in_both_wire_formats() {
+        let profile = RedactionProfile::new("no-company")
+            .unwrap()
+            .with_stripped_situational(SituationalDimension::Company)
+            .with_stripped_personal(PersonalDimensionKind::EnergyLevel);
+        let ctx = FullContext::from_wire(WIRE).unwrap().redacted(&profile);
+        assert!(ctx.situational.company.is_none());
+        assert!(ctx.personal.energy.is_none());
+
+        for format in [WireFormat::Emoji, WireFormat::Ascii] {
+            let wire = ctx.to_wire_as(format);
+            assert_eq!(FullContext::from_wire(&wire).unwrap(), ctx, "{wire}");
+        }
+        assert!(ctx
+            .to_ascii_wire()
+            .starts_with("ctx1;redacted=no-company;time="));
+
+        // A fully redacted context still says so.
+        let empty = FullContext::default().redacted(&RedactionProfile::minimal());
+        assert_eq!(empty.to_wire(), "🔒minimal;");
+        assert_eq!(FullContext::from_wire("🔒minimal;").unwrap(), empty);
+        assert_eq!(
+            FullContext::from_wire("ctx1;redacted=minimal").unwrap(),
+            empty
+        );
+
+        assert!(RedactionProfile::new("Bad Name").is_err());
+        assert!(FullContext::from_wire("🔒;⏰🌅").is_err());
+        assert!(FullContext::from_wire("ctx1;redacted=A;time=morning").is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! Hook handlers run as separate processes.
+//!
+//! [`ExternalProcessHook`] lets a policy written in Python, Node or any
+//! other language sit in a hook chain. Each run spawns the program, writes
+//! the [`HookInput`] as JSON to its stdin and closes it, then reads a
+//! [`HookResult`] as JSON from its stdout:
+//!
+//! ```json
+//! {"action": {"abort": {"reason": "blocked by tenant policy"}}, "annotations": {"rule": 7}}
+//! ```
+//!
+//! `annotations` and `state_updates` are optional, and `duration` is
+//! ignored. The action is
+//! `"continue"`, `{"abort": {"reason": "..."}}` or `{"modify": {...}}`.
+//! The program's stderr is passed through.
+//!
+//! A program that runs past its timeout is killed. One that is killed,
+//! exits unsuccessfully or prints no valid result is treated as
+//! `Continue`, like a panicking native handler, with the error in a
+//! `process_error` annotation;
+//! [`with_fail_closed`](ExternalProcessHook::with_fail_closed) turns such
+//! failures into an `Abort`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use vcp_core::hooks::{Hook, HookRegistry, HookScope, HookType};
+//! use vcp_core::process_hooks::ExternalProcessHook;
+//! use std::time::Duration;
+//!
+//! let handler = ExternalProcessHook::new("python3")
+//!     .with_args(["policies/tenant_policy.py"])
+//!     .with_timeout(Duration::from_secs(2))
+//!     .with_fail_closed(true);
+//!
+//! let mut registry = HookRegistry::new();
+//! registry.register(
+//!     Hook {
+//!         name: "tenant-policy".into(),
+//!         hook_type: HookType::PreInject,
+//!         priority: 80,
+//!         handler: Box::new(handler),
+//!         timeout: Duration::from_secs(2),
+//!         enabled: true,
+//!         description: "Tenant policy in Python".into(),
+//!     },
+//!     HookScope::Deployment,
+//!     None,
+//! )?;
+//! # Ok::<(), vcp_core::VcpError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::clock::Stopwatch;
+use crate::error::{VcpError, VcpResult};
+use crate::hooks::{ChainState, HookAction, HookHandler, HookInput, HookResult};
+
+/// How long a run may take by default.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest stdout accepted by default, in bytes.
+pub const DEFAULT_MAX_OUTPUT: usize = 1 << 20;
+
+/// How often a running process is checked for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// A [`HookHandler`] that runs an external program per invocation.
+#[derive(Debug, Clone)]
+pub struct ExternalProcessHook {
+    program: PathBuf,
+    args: Vec<OsString>,
+    env: Vec<(OsString, OsString)>,
+    timeout: Duration,
+    max_output: usize,
+    fail_closed: bool,
+}
+
+impl ExternalProcessHook {
+    /// Run `program`, looked up on `PATH` if it is a bare name.
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            env: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+            max_output: DEFAULT_MAX_OUTPUT,
+            fail_closed: false,
+        }
+    }
+
+    /// Pass `args` to the program.
+    #[must_use]
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set an environment variable for the program.
+    #[must_use]
+    pub fn with_env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Kill the program if it has not exited within `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Reject stdout longer than `bytes`.
+    #[must_use]
+    pub fn with_max_output(mut self, bytes: usize) -> Self {
+        self.max_output = bytes;
+        self
+    }
+
+    /// Abort the chain, rather than continue it, when the program fails.
+    #[must_use]
+    pub fn with_fail_closed(mut self, fail_closed: bool) -> Self {
+        self.fail_closed = fail_closed;
+        self
+    }
+
+    /// The wall-clock limit for one run.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Run the program once on `input`.
+    fn run(&self, input: &HookInput) -> VcpResult<HookResult> {
+        let input = serde_json::to_vec(input)?;
+        let program = self.program.display();
+        let stopwatch = Stopwatch::start();
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| VcpError::HookError(format!("cannot run {program}: {e}")))?;
+
+        // Feed stdin and drain stdout on their own threads, so a program
+        // that writes before it has read everything cannot deadlock us. A
+        // program that exits early closes stdin; the write error is moot.
+        let mut stdin = child.stdin.take();
+        std::thread::spawn(move || {
+            if let Some(stdin) = stdin.as_mut() {
+                let _ = stdin.write_all(&input);
+            }
+        });
+        let (tx, rx) = mpsc::channel();
+        let stdout = child.stdout.take();
+        let limit = self.max_output;
+        std::thread::spawn(move || {
+            let mut body = Vec::new();
+            // Past the limit, keep draining so the program can exit.
+            let read = stdout.map_or(Ok(0), |mut out| {
+                let cap = u64::try_from(limit).unwrap_or(u64::MAX).saturating_add(1);
+                (&mut out).take(cap).read_to_end(&mut body)?;
+                std::io::copy(&mut out, &mut std::io::sink())
+            });
+            let _ = tx.send(read.map(|_| body));
+        });
+
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if stopwatch.elapsed() >= self.timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(VcpError::HookError(format!(
+                    "{program} timed out after {:?} and was killed",
+                    self.timeout
+                )));
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+        if !status.success() {
+            return Err(VcpError::HookError(format!(
+                "{program} exited with {status}"
+            )));
+        }
+
+        // A grandchild still holding stdout must not hold us past the
+        // timeout either.
+        let remaining = self.timeout.saturating_sub(stopwatch.elapsed());
+        let body = rx
+            .recv_timeout(remaining)
+            .map_err(|_| {
+                VcpError::HookError(format!("{program} did not close stdout within the timeout"))
+            })?
+            .map_err(|e| VcpError::HookError(format!("cannot read output of {program}: {e}")))?;
+        if body.len() > self.max_output {
+            return Err(VcpError::HookError(format!(
+                "{program} wrote more than {} bytes",
+                self.max_output
+            )));
+        }
+        serde_json::from_slice(&body)
+            .map_err(|e| VcpError::HookError(format!("invalid output from {program}: {e}")))
+    }
+}
+
+impl HookHandler for ExternalProcessHook {
+    fn execute(&self, input: &HookInput) -> HookResult {
+        match self.run(input) {
+            Ok(result) => result,
+            Err(e) => HookResult {
+                action: if self.fail_closed {
+                    HookAction::Abort {
+                        reason: format!("external hook failed: {e}"),
+                    }
+                } else {
+                    HookAction::Continue
+                },
+                annotations: HashMap::from([(
+                    "process_error".to_string(),
+                    serde_json::Value::String(e.to_string()),
+                )]),
+                state_updates: ChainState::new(),
+                duration: Duration::ZERO,
+            },
+        }
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn input() -> HookInput {
+        HookInput {
+            context: serde_json::json!({"mood": "calm"}),
+            constitution: serde_json::json!({}),
+            event: serde_json::json!({}),
+            session_id: "sess-1".into(),
+            chain_state: ChainState::new(),
+        }
+    }
+
+    fn sh(script: &str) -> ExternalProcessHook {
+        ExternalProcessHook::new("sh").with_args(["-c", script])
+    }
+
+    #[test]
+    fn stdout_result_becomes_the_hook_result() {
+        let hook = sh(
+            r#"cat >/dev/null; echo '{"action":{"abort":{"reason":"tenant policy"}},"annotations":{"rule":7}}'"#,
+        );
+        let result = hook.execute(&input());
+        assert_eq!(
+            result.action,
+            HookAction::Abort {
+                reason: "tenant policy".into()
+            }
+        );
+        assert_eq!(result.annotations["rule"], serde_json::json!(7));
+    }
+
+    #[test]
+    fn program_reads_input_from_stdin() {
+        let hook = sh(r#"printf '{"action":{"modify":'; cat; printf '}}'"#);
+        let HookAction::Modify(value) = hook.execute(&input()).action else {
+            panic!("expected Modify");
+        };
+        assert_eq!(value["context"], serde_json::json!({"mood": "calm"}));
+        assert_eq!(value["session_id"], "sess-1");
+    }
+
+    #[test]
+    fn slow_program_is_killed_and_fails_open() {
+        let hook = sh("exec sleep 10").with_timeout(Duration::from_millis(100));
+        let stopwatch = Stopwatch::start();
+        let result = hook.execute(&input());
+        assert!(stopwatch.elapsed() < Duration::from_secs(5));
+        assert_eq!(result.action, HookAction::Continue);
+        let error = result.annotations["process_error"].as_str().unwrap();
+        assert!(error.contains("timed out"), "{error}");
+    }
+
+    #[test]
+    fn failures_abort_when_fail_closed() {
+        for hook in [
+            sh("exit 3"),
+            sh("echo not json"),
+            sh(r#"echo '{"action":"continue"}'"#).with_max_output(4),
+            ExternalProcessHook::new("/nonexistent/vcp-policy"),
+        ] {
+            let result = hook.with_fail_closed(true).execute(&input());
+            assert!(
+                matches!(result.action, HookAction::Abort { .. }),
+                "{:?}",
+                result.annotations
+            );
+        }
+    }
+}
@@ -0,0 +1,894 @@
+//! Protobuf types and conversions (feature `proto`).
+//!
+//! The schema lives in `proto/vcp/v1/vcp.proto`; the types in [`v1`] are
+//! generated from it by prost at build time. This module adds `From` /
+//! `TryFrom` conversions between the generated messages and the native
+//! vcp-core types so gRPC services can carry VCP structures directly
+//! rather than wrapping JSON in `bytes` fields.
+//!
+//! Conversions into protobuf are infallible. Conversions out of protobuf
+//! re-apply the same range checks as the native constructors (persona
+//! set, adherence 1-5, intensity 1-5) and fail with [`VcpError`].
+//!
+//! A `Manifest` message carries the typed [`Manifest`] fields and, in
+//! `canonical_json`, the JSON its signatures were made over. Converting
+//! back to JSON checks that the two describe the same manifest, so a
+//! peer reading only the typed fields cannot be shown something other
+//! than what gets verified.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "proto")] {
+//! use prost::Message;
+//! use vcp_core::context::FullContext;
+//! use vcp_core::proto::v1;
+//!
+//! let ctx = FullContext::from_wire("⏰🌅|📍🏡").unwrap();
+//! let bytes = v1::FullContext::from(&ctx).encode_to_vec();
+//!
+//! let decoded = v1::FullContext::decode(bytes.as_slice()).unwrap();
+//! assert_eq!(FullContext::try_from(decoded).unwrap(), ctx);
+//! # }
+//! ```
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::Value;
+
+use crate::consent::Consent;
+use crate::context::FullContext;
+use crate::csm1::{ConstitutionRef, ConstraintFlag, Csm1Token, GoalContext, Persona};
+use crate::error::{VcpError, VcpResult, VerificationCode};
+use crate::extensions::personal::SignalSource;
+use crate::personal::{Confidence, PersonalDimension, PersonalDimensionKind, PersonalState};
+use crate::situational::{SituationalContext, SituationalDimension};
+use crate::transport::{
+    canonicalize_json, BundleFile, Manifest, ManifestAttachment, ManifestBinding, ManifestBundle,
+    ManifestIssuer, ManifestScope, ManifestSignature, ManifestTimestamps, SafetyAttestation,
+    TokenBudget, VerificationResult,
+};
+
+/// Generated protobuf messages for package `vcp.v1`.
+#[allow(clippy::all, clippy::pedantic, missing_docs)]
+pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/vcp.v1.rs"));
+}
+
+// ── CSM-1 token ─────────────────────────────────────────────
+
+impl From<Persona> for v1::Persona {
+    fn from(persona: Persona) -> Self {
+        match persona {
+            Persona::Nanny => v1::Persona::Nanny,
+            Persona::Sentinel => v1::Persona::Sentinel,
+            Persona::Godparent => v1::Persona::Godparent,
+            Persona::Ambassador => v1::Persona::Ambassador,
+            Persona::Muse => v1::Persona::Muse,
+            Persona::Mediator => v1::Persona::Mediator,
+            Persona::Custom => v1::Persona::Custom,
+        }
+    }
+}
+
+impl TryFrom<v1::Persona> for Persona {
+    type Error = VcpError;
+
+    fn try_from(persona: v1::Persona) -> VcpResult<Self> {
+        match persona {
+            v1::Persona::Unspecified => Err(VcpError::ParseError("persona is unspecified".into())),
+            v1::Persona::Nanny => Ok(Persona::Nanny),
+            v1::Persona::Sentinel => Ok(Persona::Sentinel),
+            v1::Persona::Godparent => Ok(Persona::Godparent),
+            v1::Persona::Ambassador => Ok(Persona::Ambassador),
+            v1::Persona::Muse => Ok(Persona::Muse),
+            v1::Persona::Mediator => Ok(Persona::Mediator),
+            v1::Persona::Custom => Ok(Persona::Custom),
+        }
+    }
+}
+
+impl From<&Csm1Token> for v1::Csm1Token {
+    fn from(token: &Csm1Token) -> Self {
+        Self {
+            version: token.version.clone(),
+            profile_id: token.profile_id.clone(),
+            constitution: Some(v1::ConstitutionRef {
+                id: token.constitution.id.clone(),
+                version: token.constitution.version.clone(),
+            }),
+            persona: v1::Persona::from(token.persona).into(),
+            adherence: u32::from(token.adherence),
+            goal: token.goal.as_ref().map(|g| v1::GoalContext {
+                goal: g.goal.clone(),
+                experience: g.experience.clone(),
+                style: g.style.clone(),
+            }),
+            constraints: token.constraints.iter().map(|c| c.0.clone()).collect(),
+            flags: token.flags.clone(),
+            private_markers: token.private_markers.clone(),
+            personal_state: token.personal_state.as_ref().map(v1::PersonalState::from),
+            extensions: token
+                .extensions
+                .iter()
+                .map(|(key, value)| v1::TokenExtension {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<v1::Csm1Token> for Csm1Token {
+    type Error = VcpError;
+
+    fn try_from(token: v1::Csm1Token) -> VcpResult<Self> {
+        let constitution = token
+            .constitution
+            .ok_or_else(|| VcpError::ParseError("csm1 token missing constitution".into()))?;
+        let persona = v1::Persona::try_from(token.persona)
+            .map_err(|_| VcpError::ParseError(format!("unknown persona: {}", token.persona)))?;
+        let adherence = narrow_level(token.adherence, VcpError::InvalidAdherence)?;
+
+        Ok(Self {
+            version: token.version,
+            profile_id: token.profile_id,
+            constitution: ConstitutionRef {
+                id: constitution.id,
+                version: constitution.version,
+            },
+            persona: Persona::try_from(persona)?,
+            adherence,
+            goal: token.goal.map(|g| GoalContext {
+                goal: g.goal,
+                experience: g.experience,
+                style: g.style,
+            }),
+            constraints: token.constraints.into_iter().map(ConstraintFlag).collect(),
+            flags: token.flags,
+            private_markers: token.private_markers,
+            personal_state: token
+                .personal_state
+                .map(PersonalState::try_from)
+                .transpose()?,
+            extensions: token
+                .extensions
+                .into_iter()
+                .map(|ext| (ext.key, ext.value))
+                .collect(),
+        })
+    }
+}
+
+// ── Context ─────────────────────────────────────────────────
+
+impl From<&PersonalDimension> for v1::PersonalDimension {
+    fn from(dim: &PersonalDimension) -> Self {
+        Self {
+            value: dim.value.clone(),
+            intensity: u32::from(dim.intensity),
+            extended: dim.extended.clone(),
+            source: dim.source.map(|source| source.code().to_string()),
+            declared_at: dim
+                .declared_at
+                .map(|at| at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            confidence: dim.confidence.map(|c| u32::from(c.hundredths())),
+        }
+    }
+}
+
+impl TryFrom<v1::PersonalDimension> for PersonalDimension {
+    type Error = VcpError;
+
+    fn try_from(dim: v1::PersonalDimension) -> VcpResult<Self> {
+        let intensity = narrow_level(dim.intensity, VcpError::InvalidIntensity)?;
+        let mut out = match dim.extended {
+            Some(ext) => PersonalDimension::with_extended(dim.value, intensity, ext)?,
+            None => PersonalDimension::new(dim.value, intensity)?,
+        };
+        if let Some(code) = dim.source {
+            let mut chars = code.chars();
+            out.source = match (chars.next(), chars.next()) {
+                (Some(c), None) => SignalSource::from_code(c),
+                _ => None,
+            };
+            if out.source.is_none() {
+                return Err(VcpError::ParseError(format!(
+                    "unknown personal signal source: {code}"
+                )));
+            }
+        }
+        if let Some(at) = dim.declared_at {
+            let at = DateTime::parse_from_rfc3339(&at)
+                .map_err(|e| VcpError::ParseError(format!("personal.declared_at: {e}")))?;
+            out.declared_at = Some(at.with_timezone(&Utc));
+        }
+        out.confidence = dim
+            .confidence
+            .map(|h| Confidence::new(f64::from(h) / 100.0))
+            .transpose()?;
+        Ok(out)
+    }
+}
+
+impl From<&PersonalState> for v1::PersonalState {
+    fn from(state: &PersonalState) -> Self {
+        Self {
+            cognitive: state.cognitive.as_ref().map(Into::into),
+            emotional: state.emotional.as_ref().map(Into::into),
+            energy: state.energy.as_ref().map(Into::into),
+            urgency: state.urgency.as_ref().map(Into::into),
+            body: state.body.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<v1::PersonalState> for PersonalState {
+    type Error = VcpError;
+
+    fn try_from(state: v1::PersonalState) -> VcpResult<Self> {
+        Ok(Self {
+            cognitive: state.cognitive.map(TryInto::try_into).transpose()?,
+            emotional: state.emotional.map(TryInto::try_into).transpose()?,
+            energy: state.energy.map(TryInto::try_into).transpose()?,
+            urgency: state.urgency.map(TryInto::try_into).transpose()?,
+            body: state.body.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<&SituationalContext> for v1::SituationalContext {
+    fn from(ctx: &SituationalContext) -> Self {
+        let list = |v: &Option<Vec<String>>| v.clone().unwrap_or_default();
+        Self {
+            time: list(&ctx.time),
+            space: list(&ctx.space),
+            company: list(&ctx.company),
+            culture: list(&ctx.culture),
+            occasion: list(&ctx.occasion),
+            environment: list(&ctx.environment),
+            agency: list(&ctx.agency),
+            constraints: list(&ctx.constraints),
+            system_context: list(&ctx.system_context),
+            embodiment: list(&ctx.embodiment),
+            proximity: list(&ctx.proximity),
+            relationship: list(&ctx.relationship),
+            formality: list(&ctx.formality),
+        }
+    }
+}
+
+impl From<v1::SituationalContext> for SituationalContext {
+    fn from(ctx: v1::SituationalContext) -> Self {
+        let opt = |v: Vec<String>| if v.is_empty() { None } else { Some(v) };
+        Self {
+            time: opt(ctx.time),
+            space: opt(ctx.space),
+            company: opt(ctx.company),
+            culture: opt(ctx.culture),
+            occasion: opt(ctx.occasion),
+            environment: opt(ctx.environment),
+            agency: opt(ctx.agency),
+            constraints: opt(ctx.constraints),
+            system_context: opt(ctx.system_context),
+            embodiment: opt(ctx.embodiment),
+            proximity: opt(ctx.proximity),
+            relationship: opt(ctx.relationship),
+            formality: opt(ctx.formality),
+        }
+    }
+}
+
+impl From<&FullContext> for v1::FullContext {
+    fn from(ctx: &FullContext) -> Self {
+        Self {
+            situational: Some((&ctx.situational).into()),
+            personal: Some((&ctx.personal).into()),
+            redactions: ctx.redactions.clone(),
+            consent: ctx.consent.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<v1::FullContext> for FullContext {
+    type Error = VcpError;
+
+    fn try_from(ctx: v1::FullContext) -> VcpResult<Self> {
+        Ok(Self {
+            situational: ctx.situational.map(Into::into).unwrap_or_default(),
+            personal: ctx
+                .personal
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
+            redactions: ctx.redactions,
+            consent: ctx.consent.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<&Consent> for v1::Consent {
+    fn from(consent: &Consent) -> Self {
+        Self {
+            purposes: consent.purposes.clone(),
+            expires_at: consent
+                .expires_at
+                .map(|exp| exp.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            situational: consent
+                .situational
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            personal: consent.personal.iter().map(ToString::to_string).collect(),
+            revocation: consent.revocation.clone(),
+        }
+    }
+}
+
+impl TryFrom<v1::Consent> for Consent {
+    type Error = VcpError;
+
+    fn try_from(consent: v1::Consent) -> VcpResult<Self> {
+        let mut out = Consent {
+            purposes: consent.purposes,
+            revocation: consent.revocation,
+            ..Consent::default()
+        };
+        if let Some(exp) = consent.expires_at {
+            let exp = DateTime::parse_from_rfc3339(&exp)
+                .map_err(|e| VcpError::ParseError(format!("consent.expires_at: {e}")))?;
+            out.expires_at = Some(exp.with_timezone(&Utc));
+        }
+        for label in &consent.situational {
+            let dim = SituationalDimension::from_label(label).ok_or_else(|| {
+                VcpError::ParseError(format!("unknown situational dimension: {label}"))
+            })?;
+            out = out.with_situational(dim);
+        }
+        for label in &consent.personal {
+            let kind = PersonalDimensionKind::from_label(label).ok_or_else(|| {
+                VcpError::ParseError(format!("unknown personal dimension: {label}"))
+            })?;
+            out = out.with_personal(kind);
+        }
+        Ok(out)
+    }
+}
+
+// ── Verification ────────────────────────────────────────────
+
+// The protobuf enum value is the native discriminant plus one.
+impl From<&VerificationResult> for v1::VerificationReport {
+    fn from(result: &VerificationResult) -> Self {
+        Self {
+            code: i32::from(result.code as u8) + 1,
+            message: result.message.clone(),
+        }
+    }
+}
+
+impl TryFrom<v1::VerificationReport> for VerificationResult {
+    type Error = VcpError;
+
+    fn try_from(report: v1::VerificationReport) -> VcpResult<Self> {
+        let code = report
+            .code
+            .checked_sub(1)
+            .and_then(|i| usize::try_from(i).ok())
+            .and_then(|i| VerificationCode::ALL.get(i))
+            .ok_or_else(|| {
+                VcpError::ParseError(format!("unknown verification code: {}", report.code))
+            })?;
+        Ok(Self {
+            code: *code,
+            message: report.message,
+        })
+    }
+}
+
+// ── Manifest ────────────────────────────────────────────────
+
+impl From<&Manifest> for v1::Manifest {
+    /// The typed fields only; `canonical_json` is left empty.
+    fn from(m: &Manifest) -> Self {
+        Self {
+            vcp_version: m.vcp_version.clone(),
+            bundle: Some(v1::Bundle {
+                id: m.bundle.id.clone(),
+                version: m.bundle.version.clone(),
+                content_hash: m.bundle.content_hash.clone(),
+                content_encoding: m.bundle.content_encoding.clone(),
+                content_format: m.bundle.content_format.clone(),
+                locale: m.bundle.locale.clone(),
+                files: m
+                    .bundle
+                    .files
+                    .iter()
+                    .map(|f| v1::BundleFile {
+                        path: f.path.clone(),
+                        role: f.role.clone(),
+                        content_hash: f.content_hash.clone(),
+                        locale: f.locale.clone(),
+                    })
+                    .collect(),
+            }),
+            issuer: m.issuer.as_ref().map(|i| v1::Issuer {
+                id: i.id.clone(),
+                public_key: i.public_key.clone(),
+                key_id: i.key_id.clone(),
+            }),
+            timestamps: m.timestamps.as_ref().map(|t| v1::Timestamps {
+                iat: t.iat.map(timestamp),
+                nbf: t.nbf.map(timestamp),
+                exp: t.exp.map(timestamp),
+                jti: t.jti.clone(),
+            }),
+            budget: m.budget.as_ref().map(|b| v1::Budget {
+                token_count: b.token_count,
+                tokenizer: b.tokenizer.clone(),
+                max_context_share: b.max_context_share,
+            }),
+            scope: m.scope.as_ref().map(|s| v1::Scope {
+                model_families: s.model_families.clone(),
+                purposes: s.purposes.clone(),
+                environments: s.environments.clone(),
+            }),
+            safety_attestation: m
+                .safety_attestation
+                .as_ref()
+                .map(|a| v1::SafetyAttestation {
+                    auditor: a.auditor.clone(),
+                    auditor_key_id: a.auditor_key_id.clone(),
+                    reviewed_at: a.reviewed_at.map(timestamp),
+                    attestation_type: a.attestation_type.clone(),
+                    signature: a.signature.clone(),
+                }),
+            signature: m.signature.as_ref().map(|s| v1::Signature {
+                algorithm: s.algorithm.clone(),
+                value: s.value.clone(),
+                signed_fields: s.signed_fields.clone(),
+            }),
+            binding: m.binding.as_ref().map(|b| v1::Binding {
+                token: b.token.clone(),
+            }),
+            attachments: m
+                .attachments
+                .iter()
+                .map(|a| v1::Attachment {
+                    name: a.name.clone(),
+                    hash: a.hash.clone(),
+                    media_type: a.media_type.clone(),
+                })
+                .collect(),
+            canonical_json: Vec::new(),
+        }
+    }
+}
+
+impl TryFrom<&v1::Manifest> for Manifest {
+    type Error = VcpError;
+
+    /// Read the typed fields, ignoring `canonical_json`.
+    fn try_from(m: &v1::Manifest) -> VcpResult<Self> {
+        let bundle = m
+            .bundle
+            .as_ref()
+            .ok_or_else(|| VcpError::ParseError("manifest missing bundle".into()))?;
+        Ok(Self {
+            vcp_version: m.vcp_version.clone(),
+            bundle: ManifestBundle {
+                id: bundle.id.clone(),
+                version: bundle.version.clone(),
+                content_hash: bundle.content_hash.clone(),
+                content_encoding: bundle.content_encoding.clone(),
+                content_format: bundle.content_format.clone(),
+                locale: bundle.locale.clone(),
+                files: bundle
+                    .files
+                    .iter()
+                    .map(|f| BundleFile {
+                        path: f.path.clone(),
+                        role: f.role.clone(),
+                        content_hash: f.content_hash.clone(),
+                        locale: f.locale.clone(),
+                    })
+                    .collect(),
+            },
+            issuer: m.issuer.as_ref().map(|i| ManifestIssuer {
+                id: i.id.clone(),
+                key_id: i.key_id.clone(),
+                public_key: i.public_key.clone(),
+            }),
+            signature: m.signature.as_ref().map(|s| ManifestSignature {
+                algorithm: s.algorithm.clone(),
+                value: s.value.clone(),
+                signed_fields: s.signed_fields.clone(),
+            }),
+            safety_attestation: m
+                .safety_attestation
+                .as_ref()
+                .map(|a| {
+                    Ok::<_, VcpError>(SafetyAttestation {
+                        auditor: a.auditor.clone(),
+                        auditor_key_id: a.auditor_key_id.clone(),
+                        reviewed_at: parse_timestamp(
+                            a.reviewed_at.as_deref(),
+                            "safety_attestation.reviewed_at",
+                        )?,
+                        attestation_type: a.attestation_type.clone(),
+                        signature: a.signature.clone(),
+                    })
+                })
+                .transpose()?,
+            timestamps: m
+                .timestamps
+                .as_ref()
+                .map(|t| {
+                    Ok::<_, VcpError>(ManifestTimestamps {
+                        iat: parse_timestamp(t.iat.as_deref(), "timestamps.iat")?,
+                        nbf: parse_timestamp(t.nbf.as_deref(), "timestamps.nbf")?,
+                        exp: parse_timestamp(t.exp.as_deref(), "timestamps.exp")?,
+                        jti: t.jti.clone(),
+                    })
+                })
+                .transpose()?,
+            budget: m.budget.as_ref().map(|b| TokenBudget {
+                token_count: b.token_count,
+                tokenizer: b.tokenizer.clone(),
+                max_context_share: b.max_context_share,
+            }),
+            scope: m.scope.as_ref().map(|s| ManifestScope {
+                model_families: s.model_families.clone(),
+                purposes: s.purposes.clone(),
+                environments: s.environments.clone(),
+            }),
+            binding: m.binding.as_ref().map(|b| ManifestBinding {
+                token: b.token.clone(),
+            }),
+            attachments: m
+                .attachments
+                .iter()
+                .map(|a| ManifestAttachment {
+                    name: a.name.clone(),
+                    hash: a.hash.clone(),
+                    media_type: a.media_type.clone(),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl TryFrom<&Value> for v1::Manifest {
+    type Error = VcpError;
+
+    /// Convert a JSON manifest into its protobuf form.
+    ///
+    /// The typed fields come from [`Manifest::from_value`]; the JSON
+    /// itself goes in `canonical_json`, so every signature over it,
+    /// including over sections the typed fields do not model, still
+    /// verifies after the trip.
+    fn try_from(manifest: &Value) -> VcpResult<Self> {
+        let typed = Manifest::from_value(manifest)?;
+        Ok(Self {
+            canonical_json: canonicalize_json(manifest),
+            ..Self::from(&typed)
+        })
+    }
+}
+
+impl TryFrom<&v1::Manifest> for Value {
+    type Error = VcpError;
+
+    /// Convert a protobuf manifest back to JSON.
+    ///
+    /// With `canonical_json` set, returns it once its typed reading
+    /// matches the typed fields, so a consumer that only reads those
+    /// sees what gets verified. Without it, serializes the typed fields,
+    /// for peers that do not sign.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the typed fields are invalid
+    /// or disagree with `canonical_json`, or [`VcpError::JsonError`] if
+    /// `canonical_json` is not JSON.
+    fn try_from(m: &v1::Manifest) -> VcpResult<Self> {
+        let typed = Manifest::try_from(m)?;
+        if m.canonical_json.is_empty() {
+            return Ok(serde_json::to_value(&typed)?);
+        }
+        let value: Value = serde_json::from_slice(&m.canonical_json)?;
+        if !value.is_object() {
+            return Err(VcpError::ParseError(
+                "canonical_json must be a JSON object".into(),
+            ));
+        }
+        if Manifest::from_value(&value)? != typed {
+            return Err(VcpError::ParseError(
+                "manifest fields disagree with canonical_json".into(),
+            ));
+        }
+        Ok(value)
+    }
+}
+
+// ── Helpers ─────────────────────────────────────────────────
+
+/// Narrow a protobuf `uint32` to a 1-5 level, using `err` for anything
+/// out of range.
+fn narrow_level(value: u32, err: fn(u8) -> VcpError) -> VcpResult<u8> {
+    let level = u8::try_from(value).unwrap_or(u8::MAX);
+    if (1..=5).contains(&level) {
+        Ok(level)
+    } else {
+        Err(err(level))
+    }
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn parse_timestamp(value: Option<&str>, field: &str) -> VcpResult<Option<DateTime<Utc>>> {
+    value
+        .map(|v| {
+            DateTime::parse_from_rfc3339(v)
+                .map(|at| at.with_timezone(&Utc))
+                .map_err(|e| VcpError::ParseError(format!("{field}: {e}")))
+        })
+        .transpose()
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+    use serde_json::json;
+
+    const SAMPLE_TOKEN: &str = "VCP:1.0:profile-123\n\
+        C:family-safe@1.2.0\n\
+        P:N:5\n\
+        G:protect:guided:gentle\n\
+        X:no-profanity\n\
+        F:coppa\n\
+        S:none\n\
+        R:\u{1F9E0}focused:4|\u{1F4AD}calm:3";
+
+    fn sample_manifest() -> Value {
+        json!({
+            "vcp_version": "1.0",
+            "bundle": {
+                "id": "creed://creed.space/family.safe.guide",
+                "version": "1.2.0",
+                "content_hash": "sha256:abc"
+            },
+            "issuer": { "id": "creed.space", "public_key": "ed25519:AAAA", "key_id": "k1" },
+            "timestamps": {
+                "iat": "2026-01-01T00:00:00Z",
+                "nbf": "2026-01-01T00:00:00Z",
+                "exp": "2026-02-01T00:00:00Z",
+                "jti": "8f0c0f6e-1b0a-4c0e-9a55-2f1b9f6e0c11"
+            },
+            "budget": { "token_count": 1200, "tokenizer": "cl100k_base", "max_context_share": 0.25 },
+            "scope": { "model_families": ["claude-*"], "purposes": ["general-assistant"] },
+            "safety_attestation": {
+                "auditor": "audit.example",
+                "auditor_key_id": "a1",
+                "reviewed_at": "2026-01-01T00:00:00Z",
+                "attestation_type": "full-audit",
+                "signature": "base64:AAAA"
+            },
+            "signature": {
+                "algorithm": "ed25519",
+                "value": "base64:BBBB",
+                "signed_fields": ["bundle", "issuer"]
+            }
+        })
+    }
+
+    #[test]
+    fn csm1_token_roundtrip_through_bytes() {
+        let token = Csm1Token::parse(SAMPLE_TOKEN).unwrap();
+        let bytes = v1::Csm1Token::from(&token).encode_to_vec();
+        let decoded = v1::Csm1Token::decode(bytes.as_slice()).unwrap();
+        assert_eq!(Csm1Token::try_from(decoded).unwrap(), token);
+    }
+
+    #[test]
+    fn csm1_token_rejects_unspecified_persona() {
+        let token = Csm1Token::parse(SAMPLE_TOKEN).unwrap();
+        let mut msg = v1::Csm1Token::from(&token);
+        msg.persona = v1::Persona::Unspecified.into();
+        assert!(Csm1Token::try_from(msg).is_err());
+    }
+
+    #[test]
+    fn csm1_token_rejects_bad_adherence() {
+        let token = Csm1Token::parse(SAMPLE_TOKEN).unwrap();
+        let mut msg = v1::Csm1Token::from(&token);
+        msg.adherence = 9;
+        assert!(matches!(
+            Csm1Token::try_from(msg),
+            Err(VcpError::InvalidAdherence(9))
+        ));
+    }
+
+    #[test]
+    fn full_context_roundtrip() {
+        let ctx = FullContext::from_wire(
+            "\u{23F0}\u{1F305}|\u{1F4CD}\u{1F3E1}\u{2016}\u{1F9E0}focused:4~d@1700000000!0.8",
+        )
+        .unwrap();
+        let decoded =
+            v1::FullContext::decode(v1::FullContext::from(&ctx).encode_to_vec().as_slice())
+                .unwrap();
+        assert_eq!(FullContext::try_from(decoded).unwrap(), ctx);
+
+        let ctx = FullContext::from_wire(
+            "\u{1F512}minimal;\u{1F4DC}purposes=support,expires=2026-11-01T00:00:00Z,\
+             situational=time,revocation=urn:consent:42;\u{23F0}\u{1F305}",
+        )
+        .unwrap();
+        let decoded =
+            v1::FullContext::decode(v1::FullContext::from(&ctx).encode_to_vec().as_slice())
+                .unwrap();
+        assert_eq!(FullContext::try_from(decoded).unwrap(), ctx);
+    }
+
+    #[test]
+    fn personal_dimension_rejects_zero_intensity() {
+        let dim = v1::PersonalDimension {
+            value: "calm".into(),
+            intensity: 0,
+            extended: None,
+            source: None,
+            declared_at: None,
+            confidence: None,
+        };
+        assert!(matches!(
+            PersonalDimension::try_from(dim),
+            Err(VcpError::InvalidIntensity(0))
+        ));
+    }
+
+    #[test]
+    fn verification_report_codes_are_offset() {
+        let ok = VerificationResult::valid();
+        let report = v1::VerificationReport::from(&ok);
+        assert_eq!(report.code(), v1::VerificationCode::Valid);
+
+        let fail = VerificationResult::fail(VerificationCode::FetchFailed, "timeout");
+        let report = v1::VerificationReport::from(&fail);
+        assert_eq!(report.code(), v1::VerificationCode::FetchFailed);
+        assert_eq!(VerificationResult::try_from(report).unwrap(), fail);
+    }
+
+    #[test]
+    fn verification_report_rejects_unspecified() {
+        let report = v1::VerificationReport::default();
+        assert!(VerificationResult::try_from(report).is_err());
+        for code in [i32::MIN, -1, 1000] {
+            let report = v1::VerificationReport {
+                code,
+                message: String::new(),
+            };
+            assert!(VerificationResult::try_from(report).is_err(), "{code}");
+        }
+    }
+
+    #[test]
+    fn manifest_json_roundtrip() {
+        let json = sample_manifest();
+        let msg = v1::Manifest::try_from(&json).unwrap();
+        assert_eq!(msg.budget.as_ref().unwrap().token_count, Some(1200));
+
+        let decoded = v1::Manifest::decode(msg.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(Value::try_from(&decoded).unwrap(), json);
+
+        // A peer that fills only the structured fields.
+        let structured = v1::Manifest {
+            canonical_json: Vec::new(),
+            ..decoded
+        };
+        assert_eq!(Value::try_from(&structured).unwrap(), json);
+    }
+
+    #[test]
+    fn signed_manifest_survives_the_round_trip() {
+        use crate::keys::KeyPair;
+        use crate::multisig::{add_signature, sign_manifest_as, SignatureRole};
+        use crate::transport::{sign_manifest, verify_manifest_signature};
+
+        let issuer = KeyPair::generate();
+        let org = KeyPair::generate();
+        let mut json = sample_manifest();
+        json["vcp_version"] = "2.0".into();
+        json["bundle"]["locale"] = "en-GB".into();
+        json["bundle"]["files"] = json!([
+            {"path": "main.md", "role": "core", "content_hash": "sha256:abc", "locale": "en-GB"}
+        ]);
+        json["binding"] = json!({"token": "family.safe.guide@^1.2.0:FAM"});
+        json["attachments"] = json!([
+            {"name": "logo.png", "hash": "sha256:def", "media_type": "image/png"}
+        ]);
+        json["budget"]["max_context_share"] = json!(0.1);
+        json["revocation"] = json!({"crl_uri": "https://creed.space/crl"});
+        json["x-vendor"] = json!({"note": "kept verbatim"});
+        let value = sign_manifest(&json, &issuer.secret_bytes()).unwrap();
+        json["signature"] = json!({"algorithm": "ed25519", "value": value});
+        let org_sig = sign_manifest_as(
+            &json,
+            "acme",
+            Some("o1"),
+            SignatureRole::Organization,
+            &org.secret_bytes(),
+        )
+        .unwrap();
+        add_signature(&mut json, org_sig).unwrap();
+
+        let msg = v1::Manifest::try_from(&json).unwrap();
+        let decoded = v1::Manifest::decode(msg.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(
+            decoded.binding.as_ref().unwrap().token.as_deref(),
+            Some("family.safe.guide@^1.2.0:FAM")
+        );
+        assert_eq!(decoded.attachments[0].name, "logo.png");
+        assert_eq!(decoded.bundle.as_ref().unwrap().files[0].path, "main.md");
+        let back = Value::try_from(&decoded).unwrap();
+        assert_eq!(back, json);
+        assert!(verify_manifest_signature(&back, &issuer.public_bytes(), &value).unwrap());
+        let org_value = back["signatures"][0]["value"].as_str().unwrap();
+        let org_value = org_value.strip_prefix("base64:").unwrap();
+        assert!(verify_manifest_signature(&back, &org.public_bytes(), org_value).unwrap());
+    }
+
+    #[test]
+    fn corrupt_canonical_json_is_rejected() {
+        let mut msg = v1::Manifest::try_from(&sample_manifest()).unwrap();
+        msg.canonical_json = b"{not json".to_vec();
+        assert!(Value::try_from(&msg).is_err());
+        msg.canonical_json = b"[1]".to_vec();
+        assert!(Value::try_from(&msg).is_err());
+    }
+
+    #[test]
+    fn fields_disagreeing_with_canonical_json_are_rejected() {
+        let msg = v1::Manifest::try_from(&sample_manifest()).unwrap();
+
+        let mut forged = msg.clone();
+        forged.bundle.as_mut().unwrap().content_hash = "sha256:def".into();
+        assert!(Value::try_from(&forged).is_err());
+
+        let mut forged = msg.clone();
+        forged.scope = None;
+        assert!(Value::try_from(&forged).is_err());
+
+        let mut forged = msg.clone();
+        forged.timestamps.as_mut().unwrap().exp = Some("2099-01-01T00:00:00Z".into());
+        assert!(Value::try_from(&forged).is_err());
+
+        // The blob alone is not enough either.
+        let bare = v1::Manifest {
+            canonical_json: msg.canonical_json.clone(),
+            ..v1::Manifest::default()
+        };
+        assert!(Value::try_from(&bare).is_err());
+
+        // Equal instants in another notation still agree.
+        let mut offset = msg;
+        offset.timestamps.as_mut().unwrap().iat = Some("2026-01-01T01:00:00+01:00".into());
+        assert_eq!(Value::try_from(&offset).unwrap(), sample_manifest());
+    }
+
+    #[test]
+    fn manifest_requires_bundle() {
+        let mut json = sample_manifest();
+        json.as_object_mut().unwrap().remove("bundle");
+        assert!(v1::Manifest::try_from(&json).is_err());
+
+        let mut msg = v1::Manifest::try_from(&sample_manifest()).unwrap();
+        msg.timestamps.as_mut().unwrap().nbf = Some("yesterday".into());
+        assert!(Value::try_from(&msg).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+//! Protocol versions and the wire features each one enables.
+//!
+//! Two endpoints exchange their [`Version`] and call [`negotiate`] to
+//! learn what both can read. The common version is the lower of the two;
+//! a feature is available if that version is at least the one that
+//! introduced it.
+//!
+//! | Feature | Since | Effect |
+//! |---------|-------|--------|
+//! | [`Feature::PersonalStateLine`] | 1.1 | Line 8 (`R:`) of a CSM-1 token |
+//! | [`Feature::DecayMetadata`] | 3.1 | `~source@time!confidence` suffixes on personal dimensions |
+//! | [`Feature::Patches`] | 3.1 | Incremental context updates instead of full resends |
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::protocol::{negotiate, Feature, Version};
+//!
+//! let ours: Version = "3.1".parse().unwrap();
+//! let common = negotiate(ours, Version::V1_1);
+//! assert_eq!(common.version, Version::V1_1);
+//! assert!(common.supports(Feature::PersonalStateLine));
+//! assert!(!common.supports(Feature::DecayMetadata));
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::csm1::Csm1Token;
+use crate::error::{VcpError, VcpResult};
+
+// ── Version ─────────────────────────────────────────────────
+
+/// A `MAJOR.MINOR` protocol version.
+///
+/// Patch levels never change the wire, so `"3.1.0"` parses as `3.1`.
+/// Serializes as its string form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Version {
+    /// The original 7-line CSM-1 token.
+    pub const V1_0: Self = Self::new(1, 0);
+    /// Adds the personal state line.
+    pub const V1_1: Self = Self::new(1, 1);
+    /// The v3.1 extensions: decay metadata and patches.
+    pub const V3_1: Self = Self::new(3, 1);
+    /// The newest version this build speaks.
+    pub const CURRENT: Self = Self::V3_1;
+
+    /// Build from components.
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Parse `"MAJOR.MINOR"` or `"MAJOR.MINOR.PATCH"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the string has fewer than two
+    /// or more than three numeric components.
+    pub fn parse(s: &str) -> VcpResult<Self> {
+        let parts: Vec<&str> = s.split('.').collect();
+        if !(2..=3).contains(&parts.len()) {
+            return Err(VcpError::ParseError(format!(
+                "protocol version must be X.Y or X.Y.Z, got: {s}"
+            )));
+        }
+        let number = |part: &str, name: &str| {
+            part.parse::<u32>()
+                .map_err(|_| VcpError::ParseError(format!("invalid {name} version: {part}")))
+        };
+        let major = number(parts[0], "major")?;
+        let minor = number(parts[1], "minor")?;
+        if let Some(patch) = parts.get(2) {
+            number(patch, "patch")?;
+        }
+        Ok(Self { major, minor })
+    }
+
+    /// Features available at this version.
+    pub fn features(self) -> BTreeSet<Feature> {
+        Feature::ALL
+            .into_iter()
+            .filter(|feature| feature.since() <= self)
+            .collect()
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for Version {
+    type Err = VcpError;
+
+    fn from_str(s: &str) -> VcpResult<Self> {
+        Self::parse(s)
+    }
+}
+
+impl From<Version> for String {
+    fn from(version: Version) -> Self {
+        version.to_string()
+    }
+}
+
+impl TryFrom<String> for Version {
+    type Error = VcpError;
+
+    fn try_from(s: String) -> VcpResult<Self> {
+        Self::parse(&s)
+    }
+}
+
+// ── Features ────────────────────────────────────────────────
+
+/// A wire feature gated on protocol version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// CSM-1 line 8, `R:<personal-state>`.
+    PersonalStateLine,
+    /// Source, declared-time and confidence suffixes on personal dimensions.
+    DecayMetadata,
+    /// Incremental context updates.
+    Patches,
+}
+
+impl Feature {
+    /// Every feature, oldest first.
+    pub const ALL: [Self; 3] = [Self::PersonalStateLine, Self::DecayMetadata, Self::Patches];
+
+    /// The version that introduced this feature.
+    pub const fn since(self) -> Version {
+        match self {
+            Self::PersonalStateLine => Version::V1_1,
+            Self::DecayMetadata | Self::Patches => Version::V3_1,
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::PersonalStateLine => "personal_state_line",
+            Self::DecayMetadata => "decay_metadata",
+            Self::Patches => "patches",
+        })
+    }
+}
+
+// ── Negotiation ─────────────────────────────────────────────
+
+/// What two endpoints agreed they can both read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommonCapabilities {
+    /// The lower of the two versions.
+    pub version: Version,
+    /// Features available at `version`.
+    pub features: BTreeSet<Feature>,
+}
+
+impl CommonCapabilities {
+    /// Whether both sides understand `feature`.
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Copy of `token` without anything the peer cannot read.
+    ///
+    /// Drops the personal state line, or just the dimension metadata,
+    /// when the matching feature was not agreed.
+    pub fn restrict_token(&self, token: &Csm1Token) -> Csm1Token {
+        let mut token = token.clone();
+        if !self.supports(Feature::PersonalStateLine) {
+            token.personal_state = None;
+        } else if !self.supports(Feature::DecayMetadata) {
+            if let Some(state) = &mut token.personal_state {
+                for dim in [
+                    &mut state.cognitive,
+                    &mut state.emotional,
+                    &mut state.energy,
+                    &mut state.urgency,
+                    &mut state.body,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    dim.source = None;
+                    dim.declared_at = None;
+                    dim.confidence = None;
+                }
+            }
+        }
+        token
+    }
+}
+
+/// Agree on a common version and feature set.
+pub fn negotiate(ours: Version, theirs: Version) -> CommonCapabilities {
+    let version = ours.min(theirs);
+    CommonCapabilities {
+        version,
+        features: version.features(),
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extensions::personal::SignalSource;
+    use crate::personal::{Confidence, PersonalDimension, PersonalState};
+
+    #[test]
+    fn parses_and_orders_versions() {
+        assert_eq!(Version::parse("1.1").unwrap(), Version::V1_1);
+        assert_eq!(Version::parse("3.1.0").unwrap(), Version::V3_1);
+        assert_eq!(Version::V3_1.to_string(), "3.1");
+        for bad in ["", "3", "1.x", "1.1.1.1", "1.1.x"] {
+            assert!(Version::parse(bad).is_err(), "{bad}");
+        }
+        assert!(Version::V1_0 < Version::V1_1);
+        assert!(Version::new(1, 10) > Version::new(1, 9));
+        assert!(Version::new(2, 0) < Version::V3_1);
+
+        let json = serde_json::to_value(Version::V1_1).unwrap();
+        assert_eq!(json, "1.1");
+        assert_eq!(
+            serde_json::from_value::<Version>(json).unwrap(),
+            Version::V1_1
+        );
+    }
+
+    #[test]
+    fn negotiation_takes_the_lower_version() {
+        let common = negotiate(Version::V1_0, Version::CURRENT);
+        assert_eq!(common.version, Version::V1_0);
+        assert!(common.features.is_empty());
+
+        let common = negotiate(Version::V3_1, Version::new(4, 0));
+        assert_eq!(common.version, Version::V3_1);
+        assert_eq!(common.features, Feature::ALL.into_iter().collect());
+
+        let common = negotiate(Version::new(2, 0), Version::V3_1);
+        assert!(common.supports(Feature::PersonalStateLine));
+        assert!(!common.supports(Feature::Patches));
+    }
+
+    #[test]
+    fn restrict_token_strips_unagreed_features() {
+        let dim = PersonalDimension::new("focused", 4)
+            .unwrap()
+            .with_source(SignalSource::Declared)
+            .with_confidence(Confidence::CERTAIN);
+        let token = Csm1Token::builder()
+            .profile_id("p")
+            .constitution("c", "1.0.0")
+            .persona(crate::csm1::Persona::Ambassador)
+            .personal_state(PersonalState {
+                cognitive: Some(dim),
+                ..PersonalState::default()
+            })
+            .build()
+            .unwrap();
+
+        let full = negotiate(Version::CURRENT, Version::V3_1).restrict_token(&token);
+        assert_eq!(full, token);
+
+        let r_line = negotiate(Version::CURRENT, Version::V1_1).restrict_token(&token);
+        let cognitive = r_line.personal_state.unwrap().cognitive.unwrap();
+        assert_eq!(
+            (cognitive.value.as_str(), cognitive.intensity),
+            ("focused", 4)
+        );
+        assert!(cognitive.source.is_none() && cognitive.confidence.is_none());
+
+        let bare = negotiate(Version::CURRENT, Version::V1_0).restrict_token(&token);
+        assert!(bare.personal_state.is_none());
+        assert_eq!(bare.encode().lines().count(), 7);
+    }
+}
@@ -0,0 +1,415 @@
+//! Configuration that can be swapped while a service runs.
+//!
+//! A [`SharedConfig`] holds the current value behind an `Arc`. Readers take
+//! a snapshot with [`current`](SharedConfig::current) and keep using it
+//! even if a newer value is swapped in meanwhile; the swap itself is a
+//! single pointer replacement. An [`Orchestrator`](crate::orchestrator::Orchestrator)
+//! given one through
+//! [`with_shared_trust_config`](crate::orchestrator::Orchestrator::with_shared_trust_config)
+//! reads its trust config from it and hands it to the contexts from
+//! [`verification_context`](crate::orchestrator::Orchestrator::verification_context),
+//! so everything sharing the handle sees a reload at once.
+//!
+//! With the `watch` feature, a [`ConfigWatcher`] keeps a `SharedConfig`
+//! in step with a JSON file on disk:
+//!
+//! | Config | Loaded with | Rejected when |
+//! |--------|-------------|---------------|
+//! | [`TrustConfig`] | [`TrustConfig::from_json`] | No `trust_anchors` object, or [`TrustConfig::validate`] fails |
+//! | [`OrgPolicy`] | [`OrgPolicy::from_json`] | The JSON does not describe a policy |
+//!
+//! A rejected file leaves the last good value in place; the watcher
+//! records the error and tells its listeners. Trust-on-first-use pins are
+//! not stored in the file and carry over to each reloaded trust config.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::orchestrator::Orchestrator;
+//! use vcp_core::reload::SharedConfig;
+//! use vcp_core::trust::TrustConfig;
+//!
+//! let trust = SharedConfig::new(TrustConfig::default());
+//! let orch = Orchestrator::new(TrustConfig::default()).with_shared_trust_config(trust.clone());
+//!
+//! let before = orch.trust_store_version();
+//! let mut updated = TrustConfig::default();
+//! updated.issuers.insert("acme".into(), Vec::new());
+//! trust.replace(updated);
+//! assert_ne!(orch.trust_store_version(), before);
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::error::{VcpError, VcpResult};
+use crate::policy::OrgPolicy;
+use crate::trust::TrustConfig;
+
+#[cfg(feature = "watch")]
+pub use self::watcher::{ConfigWatcher, PolicyWatcher, ReloadEvent, TrustConfigWatcher};
+
+// ── Shared config ───────────────────────────────────────────
+
+/// A handle to a value that can be replaced while others read it.
+///
+/// Clones share the value.
+pub struct SharedConfig<T> {
+    inner: Arc<RwLock<Arc<T>>>,
+}
+
+impl<T> SharedConfig<T> {
+    /// Share `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Arc::new(value))),
+        }
+    }
+
+    /// The current value.
+    pub fn current(&self) -> Arc<T> {
+        Arc::clone(&self.inner.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Swap in `value`, returning the one it replaced.
+    pub fn replace(&self, value: T) -> Arc<T> {
+        let mut guard = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *guard, Arc::new(value))
+    }
+}
+
+impl<T> Clone for SharedConfig<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Default> Default for SharedConfig<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for SharedConfig<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedConfig<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedConfig")
+            .field(&self.current())
+            .finish()
+    }
+}
+
+// ── Reloadable configs ──────────────────────────────────────
+
+/// A config a [`ConfigWatcher`] can load from a file.
+pub trait Reloadable: Sized + Send + Sync + 'static {
+    /// Parse and validate file contents.
+    ///
+    /// # Errors
+    ///
+    /// Any error rejects the file.
+    fn load(json: &str) -> VcpResult<Self>;
+
+    /// Copy state that is not stored in the file from the value being
+    /// replaced.
+    fn carry_over(&mut self, _previous: &Self) {}
+}
+
+impl Reloadable for TrustConfig {
+    fn load(json: &str) -> VcpResult<Self> {
+        let data: serde_json::Value = serde_json::from_str(json)?;
+        // `from_dict` reads a missing object as "no anchors"; from a file
+        // that is more likely a mistake than a request to trust no one.
+        if !data
+            .get("trust_anchors")
+            .is_some_and(serde_json::Value::is_object)
+        {
+            return Err(VcpError::ParseError(
+                "trust config has no \"trust_anchors\" object".into(),
+            ));
+        }
+        let config = Self::from_dict(&data)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn carry_over(&mut self, previous: &Self) {
+        if self.tofu.is_none() {
+            self.tofu.clone_from(&previous.tofu);
+        }
+    }
+}
+
+impl Reloadable for OrgPolicy {
+    fn load(json: &str) -> VcpResult<Self> {
+        Self::from_json(json)
+    }
+}
+
+// ── Watcher ─────────────────────────────────────────────────
+
+#[cfg(feature = "watch")]
+mod watcher {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+    use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+    use super::{Reloadable, SharedConfig};
+    use crate::error::{VcpError, VcpResult};
+    use crate::policy::OrgPolicy;
+    use crate::trust::TrustConfig;
+
+    /// Watches a trust config file.
+    pub type TrustConfigWatcher = ConfigWatcher<TrustConfig>;
+    /// Watches an organization policy file.
+    pub type PolicyWatcher = ConfigWatcher<OrgPolicy>;
+
+    /// What happened when a watched file changed.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ReloadEvent {
+        /// The new contents were swapped in.
+        Reloaded { path: PathBuf },
+        /// The new contents were rejected; the last good value stays.
+        Rejected { path: PathBuf, error: String },
+    }
+
+    type Listener = Box<dyn Fn(&ReloadEvent) + Send + Sync>;
+
+    struct State<T> {
+        path: PathBuf,
+        shared: SharedConfig<T>,
+        /// Contents of the file last swapped in, to skip repeat events.
+        loaded: Mutex<Option<String>>,
+        last_error: Mutex<Option<String>>,
+        listeners: Mutex<Vec<Listener>>,
+    }
+
+    fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+        mutex.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    impl<T: Reloadable> State<T> {
+        fn reload(&self) -> VcpResult<bool> {
+            // Held until the swap, so concurrent reloads apply in order.
+            let mut loaded = lock(&self.loaded);
+            let result = fs::read_to_string(&self.path)
+                .map_err(|e| VcpError::IoError(format!("{}: {e}", self.path.display())))
+                .and_then(|json| {
+                    if loaded.as_deref() == Some(json.as_str()) {
+                        return Ok(None);
+                    }
+                    T::load(&json).map(|value| Some((json, value)))
+                });
+
+            let (event, result) = match result {
+                Ok(None) => return Ok(false),
+                Ok(Some((json, mut value))) => {
+                    value.carry_over(&self.shared.current());
+                    self.shared.replace(value);
+                    *loaded = Some(json);
+                    *lock(&self.last_error) = None;
+                    let event = ReloadEvent::Reloaded {
+                        path: self.path.clone(),
+                    };
+                    (event, Ok(true))
+                }
+                Err(e) => {
+                    *lock(&self.last_error) = Some(e.to_string());
+                    let event = ReloadEvent::Rejected {
+                        path: self.path.clone(),
+                        error: e.to_string(),
+                    };
+                    (event, Err(e))
+                }
+            };
+            drop(loaded);
+            self.notify(&event);
+            result
+        }
+
+        fn notify(&self, event: &ReloadEvent) {
+            for listener in lock(&self.listeners).iter() {
+                listener(event);
+            }
+        }
+    }
+
+    /// Reloads a [`SharedConfig`] whenever its JSON file changes.
+    ///
+    /// The file's directory is watched rather than the file, so editors
+    /// and deploy tools that replace the file by renaming are seen. Stops
+    /// watching when dropped.
+    pub struct ConfigWatcher<T> {
+        state: Arc<State<T>>,
+        _watcher: RecommendedWatcher,
+    }
+
+    impl<T: Reloadable> ConfigWatcher<T> {
+        /// Load `path` into `shared`, then keep reloading it on change.
+        ///
+        /// # Errors
+        ///
+        /// Fails without touching `shared` if the file cannot be read or
+        /// is rejected, and with [`VcpError::IoError`] if it cannot be
+        /// watched.
+        pub fn watch(path: impl AsRef<Path>, shared: SharedConfig<T>) -> VcpResult<Self> {
+            let path = path.as_ref().to_path_buf();
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+            let name = path.file_name().map(ToOwned::to_owned);
+
+            let state = Arc::new(State {
+                path,
+                shared,
+                loaded: Mutex::new(None),
+                last_error: Mutex::new(None),
+                listeners: Mutex::new(Vec::new()),
+            });
+            state.reload()?;
+
+            let handler = {
+                let state = Arc::clone(&state);
+                move |event: notify::Result<notify::Event>| {
+                    let Ok(event) = event else { return };
+                    if matches!(event.kind, EventKind::Access(_)) {
+                        return;
+                    }
+                    if event.paths.iter().any(|p| p.file_name() == name.as_deref()) {
+                        // A rejection is recorded and reported to listeners.
+                        let _ = state.reload();
+                    }
+                }
+            };
+            let io = |e: notify::Error| {
+                VcpError::IoError(format!("cannot watch {}: {e}", dir.display()))
+            };
+            let mut watcher = notify::recommended_watcher(handler).map_err(io)?;
+            watcher
+                .watch(&dir, RecursiveMode::NonRecursive)
+                .map_err(io)?;
+            Ok(Self {
+                state,
+                _watcher: watcher,
+            })
+        }
+
+        /// Reload now, without waiting for a change event.
+        ///
+        /// Returns whether a new value was swapped in; unchanged contents
+        /// are skipped.
+        ///
+        /// # Errors
+        ///
+        /// As [`watch`](Self::watch); the last good value stays in place.
+        pub fn reload(&self) -> VcpResult<bool> {
+            self.state.reload()
+        }
+
+        /// Call `listener` after every reload or rejection.
+        pub fn on_reload(&self, listener: impl Fn(&ReloadEvent) + Send + Sync + 'static) {
+            lock(&self.state.listeners).push(Box::new(listener));
+        }
+
+        /// The handle being kept up to date.
+        pub fn shared(&self) -> &SharedConfig<T> {
+            &self.state.shared
+        }
+
+        /// Why the latest change was rejected, if it was.
+        pub fn last_error(&self) -> Option<String> {
+            lock(&self.state.last_error).clone()
+        }
+
+        /// The watched file.
+        pub fn path(&self) -> &Path {
+            &self.state.path
+        }
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANCHORS: &str = r#"{"trust_anchors": {"acme": {"type": "issuer", "keys": [{
+        "id": "k1", "algorithm": "ed25519",
+        "public_key": "base64:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "valid_from": "2025-01-01T00:00:00Z", "valid_until": "2030-01-01T00:00:00Z"}]}}}"#;
+
+    #[test]
+    fn shared_config_swaps_for_every_handle() {
+        let shared = SharedConfig::new(1);
+        let other = shared.clone();
+        let snapshot = shared.current();
+        assert_eq!(*other.replace(2), 1);
+        assert_eq!(*shared.current(), 2);
+        assert_eq!(*snapshot, 1);
+    }
+
+    #[test]
+    fn trust_config_load_validates() {
+        let config = TrustConfig::load(ANCHORS).unwrap();
+        assert!(config.get_issuer_key("acme", Some("k1")).is_some());
+
+        assert!(TrustConfig::load("{}").is_err());
+        assert!(TrustConfig::load("{\"trust_anchors\": ").is_err());
+        let bad_key = ANCHORS.replace("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=", "AAAA");
+        assert!(TrustConfig::load(&bad_key).is_err());
+
+        let pins = Arc::new(crate::trust::KeyPins::new());
+        let previous = TrustConfig::default().with_tofu(Arc::clone(&pins));
+        let mut reloaded = TrustConfig::load(ANCHORS).unwrap();
+        reloaded.carry_over(&previous);
+        assert!(reloaded.key_pins().is_some());
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn watcher_keeps_last_good_config() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let dir = std::env::temp_dir().join(format!("vcp-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("trust.json");
+        std::fs::write(&path, ANCHORS).unwrap();
+
+        let shared = SharedConfig::new(TrustConfig::default());
+        let watcher = TrustConfigWatcher::watch(&path, shared.clone()).unwrap();
+        assert!(shared.current().issuers.contains_key("acme"));
+        assert!(!watcher.reload().unwrap());
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(watcher.reload().is_err());
+        assert!(watcher.last_error().is_some());
+        assert!(shared.current().issuers.contains_key("acme"));
+
+        let (tx, rx) = mpsc::channel();
+        let tx = std::sync::Mutex::new(tx);
+        watcher.on_reload(move |event| {
+            if matches!(event, ReloadEvent::Reloaded { .. }) {
+                let _ = tx.lock().unwrap().send(());
+            }
+        });
+        std::fs::write(&path, ANCHORS.replace("acme", "globex")).unwrap();
+        rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(shared.current().issuers.contains_key("globex"));
+        assert!(watcher.last_error().is_none());
+
+        drop(watcher);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
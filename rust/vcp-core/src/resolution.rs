@@ -0,0 +1,156 @@
+//! When a cached copy may stand in for a fetch.
+//!
+//! A [`ResolutionPolicy`] makes a cache's behaviour under network
+//! partition explicit. Its consumers,
+//! [`RevocationChecker::resolve`](crate::revocation::RevocationChecker::resolve)
+//! and [`BundleStore::get_or_insert_with`](crate::cache::BundleStore::get_or_insert_with),
+//! ask it for a [`Plan`] given the age of their cached copy, fetch when
+//! told to, and when the fetch fails either fall back to the cached copy
+//! or report the resource unavailable, rather than assume a default.
+//!
+//! | Mode | Cached copy within `fresh_for` | Older, but within `max_stale` past it | Older still, or none |
+//! |------|------|------|------|
+//! | [`PreferCache`](ResolutionMode::PreferCache) | used | used | fetched; unavailable if that fails |
+//! | [`RequireFresh`](ResolutionMode::RequireFresh) | used | fetched; the cached copy if that fails | fetched; unavailable if that fails |
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use vcp_core::resolution::{Plan, ResolutionPolicy};
+//!
+//! let policy = ResolutionPolicy::require_fresh(Duration::from_mins(5))
+//!     .with_max_stale(Duration::from_hours(1));
+//!
+//! assert_eq!(policy.plan(Some(Duration::from_mins(1))), Plan::UseCached);
+//! assert_eq!(
+//!     policy.plan(Some(Duration::from_mins(10))),
+//!     Plan::Fetch { fallback: true }
+//! );
+//! assert_eq!(
+//!     policy.plan(Some(Duration::from_hours(2))),
+//!     Plan::Fetch { fallback: false }
+//! );
+//! assert_eq!(policy.plan(None), Plan::Fetch { fallback: false });
+//! ```
+
+use std::time::Duration;
+
+/// Whether a usable cached copy is served as is or revalidated first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionMode {
+    /// Serve any usable cached copy without fetching.
+    PreferCache,
+    /// Fetch once the cached copy is past `fresh_for`, keeping it only as
+    /// a fallback.
+    RequireFresh,
+}
+
+/// What a consumer should do about one resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plan {
+    /// Serve the cached copy without fetching.
+    UseCached,
+    /// Fetch. If that fails, serve the cached copy when `fallback` is set;
+    /// otherwise the resource is unavailable.
+    Fetch { fallback: bool },
+}
+
+/// How fresh a cached copy must be, and how stale it may get before it is
+/// no use even when the network is down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolutionPolicy {
+    pub mode: ResolutionMode,
+    /// How long after it was fetched a copy counts as fresh.
+    pub fresh_for: Duration,
+    /// How long past `fresh_for` a copy may still be used; `None` for no
+    /// limit.
+    pub max_stale: Option<Duration>,
+}
+
+impl ResolutionPolicy {
+    /// Serve cached copies of any age; fetch only on a miss.
+    pub fn prefer_cache() -> Self {
+        Self {
+            mode: ResolutionMode::PreferCache,
+            fresh_for: Duration::ZERO,
+            max_stale: None,
+        }
+    }
+
+    /// Fetch once a copy is older than `fresh_for`, with no stale
+    /// fallback: when the fetch fails, the resource is unavailable.
+    pub fn require_fresh(fresh_for: Duration) -> Self {
+        Self {
+            mode: ResolutionMode::RequireFresh,
+            fresh_for,
+            max_stale: Some(Duration::ZERO),
+        }
+    }
+
+    /// Allow copies up to `max_stale` past `fresh_for`.
+    #[must_use]
+    pub fn with_max_stale(mut self, max_stale: Duration) -> Self {
+        self.max_stale = Some(max_stale);
+        self
+    }
+
+    /// Whether a copy of `age` is still fresh.
+    pub fn is_fresh(&self, age: Duration) -> bool {
+        age < self.fresh_for
+    }
+
+    /// Whether a copy of `age` may be used at all.
+    pub fn is_usable(&self, age: Duration) -> bool {
+        self.is_fresh(age)
+            || self
+                .max_stale
+                .is_none_or(|max| age <= self.fresh_for.saturating_add(max))
+    }
+
+    /// What to do given a cached copy of `age`, or `None` if there is no
+    /// cached copy.
+    pub fn plan(&self, age: Option<Duration>) -> Plan {
+        match age {
+            Some(age) if !self.is_usable(age) => Plan::Fetch { fallback: false },
+            Some(age) if self.mode == ResolutionMode::PreferCache || self.is_fresh(age) => {
+                Plan::UseCached
+            }
+            Some(_) => Plan::Fetch { fallback: true },
+            None => Plan::Fetch { fallback: false },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN: Duration = Duration::from_mins(1);
+
+    #[test]
+    fn prefer_cache_serves_any_age_until_max_stale() {
+        let policy = ResolutionPolicy::prefer_cache();
+        assert_eq!(policy.plan(Some(MIN * 60 * 24 * 365)), Plan::UseCached);
+        assert_eq!(policy.plan(None), Plan::Fetch { fallback: false });
+
+        let policy = ResolutionPolicy {
+            fresh_for: MIN,
+            ..ResolutionPolicy::prefer_cache()
+        }
+        .with_max_stale(MIN * 4);
+        assert_eq!(policy.plan(Some(MIN * 5)), Plan::UseCached);
+        assert_eq!(policy.plan(Some(MIN * 6)), Plan::Fetch { fallback: false });
+    }
+
+    #[test]
+    fn require_fresh_without_max_stale_fails_closed() {
+        let policy = ResolutionPolicy::require_fresh(MIN * 5);
+        assert_eq!(policy.plan(Some(MIN * 4)), Plan::UseCached);
+        assert_eq!(policy.plan(Some(MIN * 5)), Plan::Fetch { fallback: true });
+        assert_eq!(
+            policy.plan(Some(MIN * 5 + Duration::from_secs(1))),
+            Plan::Fetch { fallback: false }
+        );
+    }
+}
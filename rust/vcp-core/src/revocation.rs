@@ -15,9 +15,16 @@
 //!
 //! Actual HTTP fetching requires a sync HTTP client crate (e.g. `ureq`
 //! or `minreq`). Since neither is a current dependency, the online
-//! check methods return `None` to indicate "cannot determine" and the
-//! CRL fetch returns a default not-revoked status. The SSRF validation
-//! and CRL parsing are fully implemented and tested.
+//! check methods return `None` to indicate "cannot determine" and CRL
+//! fetches fail. The SSRF validation and CRL parsing are fully
+//! implemented and tested.
+//!
+//! # Network Partition
+//!
+//! [`RevocationChecker::resolve`] reports a status it cannot determine as
+//! an error, after falling back to cached results as far as its
+//! [`ResolutionPolicy`](crate::resolution::ResolutionPolicy) allows.
+//! [`RevocationChecker::check`] fails open instead.
 //!
 //! # Example
 //!
@@ -39,11 +46,15 @@
 
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::Duration;
 
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
+use crate::clock::{default_clock, Clock};
 use crate::error::{VcpError, VcpResult};
+use crate::resolution::{Plan, ResolutionPolicy};
 
 // ── RevocationStatus ────────────────────────────────────────
 
@@ -217,7 +228,7 @@ pub fn validate_uri(uri: &str) -> VcpResult<()> {
 // ── CRL types ───────────────────────────────────────────────
 
 /// An entry in a Certificate Revocation List.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrlEntry {
     /// The JTI (unique identifier) of the revoked bundle.
     pub jti: String,
@@ -228,7 +239,7 @@ pub struct CrlEntry {
 }
 
 /// A Certificate Revocation List (CRL) for VCP bundles.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Crl {
     /// The issuer that published this CRL.
     pub issuer: String,
@@ -260,39 +271,55 @@ impl Crl {
     }
 }
 
+/// A cached CRL with its wall-clock fetch time, for persisting the
+/// checker's cache across restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedCrl {
+    /// URI the CRL was fetched from.
+    pub uri: String,
+    /// When the CRL was fetched.
+    pub fetched_at: DateTime<Utc>,
+    /// The CRL itself.
+    pub crl: Crl,
+}
+
 // ── RevocationChecker ───────────────────────────────────────
 
 /// Synchronous revocation checker with caching.
 ///
 /// Checks bundle revocation status via online endpoints and CRL lists.
-/// Results are cached for the configured TTL to avoid redundant network
-/// requests.
+/// Cached results and CRLs are reused or refetched according to a
+/// [`ResolutionPolicy`]; by default, one that keeps them fresh for the
+/// configured TTL and has no stale fallback.
 ///
 /// # HTTP Note
 ///
 /// Actual HTTP fetching is not implemented because no sync HTTP client
 /// crate is in the current dependencies. Online checks return `None`
-/// (indeterminate) and CRL fetches return not-revoked. Add `ureq` or
-/// `minreq` to `Cargo.toml` and implement `fetch_json` to enable
-/// network-based revocation checking.
+/// (indeterminate) and CRL fetches fail, so only cached and inserted CRLs
+/// answer. Add `ureq` or `minreq` to `Cargo.toml` and implement
+/// `fetch_json` to enable network-based revocation checking.
 pub struct RevocationChecker {
-    /// How long cached results remain valid.
-    cache_ttl: Duration,
+    /// When cached results and CRLs are reused, refetched or given up on.
+    policy: ResolutionPolicy,
     /// Maximum time to wait for an HTTP response.
     timeout: Duration,
     /// Cache of individual JTI revocation results.
-    cache: HashMap<String, (RevocationStatus, Instant)>,
+    cache: HashMap<String, (RevocationStatus, DateTime<Utc>)>,
     /// Cache of parsed CRLs keyed by URI.
-    crl_cache: HashMap<String, (Crl, Instant)>,
+    crl_cache: HashMap<String, (Crl, DateTime<Utc>)>,
+    /// Source of cache timestamps.
+    clock: Arc<dyn Clock>,
 }
 
 impl std::fmt::Debug for RevocationChecker {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RevocationChecker")
-            .field("cache_ttl", &self.cache_ttl)
+            .field("policy", &self.policy)
             .field("timeout", &self.timeout)
             .field("cache_entries", &self.cache.len())
             .field("crl_entries", &self.crl_cache.len())
+            .field("clock", &self.clock)
             .finish()
     }
 }
@@ -306,55 +333,98 @@ impl RevocationChecker {
     /// * `timeout` - Maximum time to wait for an HTTP response.
     pub fn new(cache_ttl: Duration, timeout: Duration) -> Self {
         Self {
-            cache_ttl,
+            policy: ResolutionPolicy::require_fresh(cache_ttl),
             timeout,
             cache: HashMap::new(),
             crl_cache: HashMap::new(),
+            clock: default_clock(),
         }
     }
 
+    /// Age cache entries against `clock` rather than the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Replace the resolution policy, including the TTL given to
+    /// [`new`](Self::new).
+    #[must_use]
+    pub fn with_resolution_policy(mut self, policy: ResolutionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The resolution policy in effect.
+    pub fn resolution_policy(&self) -> &ResolutionPolicy {
+        &self.policy
+    }
+
+    /// How long ago `cached_at` was.
+    fn age(&self, cached_at: DateTime<Utc>) -> Duration {
+        (self.clock.now() - cached_at).to_std().unwrap_or_default()
+    }
+
     /// Check the revocation status of a bundle by JTI.
     ///
-    /// Checks in order:
-    /// 1. Cache (if a cached result exists and has not expired).
-    /// 2. Online endpoint (if `check_uri` is provided).
-    /// 3. CRL (if `crl_uri` is provided).
-    ///
-    /// If no check succeeds, returns not-revoked (fail-open).
+    /// Same as [`resolve`](Self::resolve), except that when the status
+    /// cannot be determined it returns not-revoked (fail-open).
     pub fn check(
         &mut self,
         jti: &str,
         check_uri: Option<&str>,
         crl_uri: Option<&str>,
     ) -> RevocationStatus {
-        // 1. Check cache first.
-        if let Some((status, cached_at)) = self.cache.get(jti) {
-            if cached_at.elapsed() < self.cache_ttl {
-                return status.clone();
-            }
-            // Expired, remove it.
-            self.cache.remove(jti);
-        }
+        self.resolve(jti, check_uri, crl_uri)
+            .unwrap_or_else(|_| RevocationStatus::not_revoked())
+    }
 
-        // 2. Online check.
-        if let Some(uri) = check_uri {
-            if let Some(status) = self.check_online(uri, jti) {
-                self.cache
-                    .insert(jti.to_string(), (status.clone(), Instant::now()));
-                return status;
-            }
+    /// Determine the revocation status of a bundle by JTI.
+    ///
+    /// Uses the cached result when the policy allows it. Otherwise asks
+    /// the online endpoint (if `check_uri` is provided), then the CRL (if
+    /// `crl_uri` is provided), and if neither answers falls back to the
+    /// cached result when the policy allows that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::RevocationError`] when the status cannot be
+    /// determined: no source answered and no usable result is cached.
+    pub fn resolve(
+        &mut self,
+        jti: &str,
+        check_uri: Option<&str>,
+        crl_uri: Option<&str>,
+    ) -> VcpResult<RevocationStatus> {
+        let cached = self.cache.get(jti).cloned();
+        let plan = self
+            .policy
+            .plan(cached.as_ref().map(|(_, at)| self.age(*at)));
+        if let (Plan::UseCached, Some((status, _))) = (plan, &cached) {
+            return Ok(status.clone());
         }
 
-        // 3. CRL check.
-        if let Some(uri) = crl_uri {
-            let status = self.check_crl(uri, jti);
+        // A result read from a CRL is as old as the CRL.
+        let fetched = check_uri
+            .and_then(|uri| self.check_online(uri, jti))
+            .map(|status| (status, self.clock.now()))
+            .or_else(|| crl_uri.and_then(|uri| self.check_crl(uri, jti)));
+        if let Some((status, fetched_at)) = fetched {
             self.cache
-                .insert(jti.to_string(), (status.clone(), Instant::now()));
-            return status;
+                .insert(jti.to_string(), (status.clone(), fetched_at));
+            return Ok(status);
         }
 
-        // No checks available, fail-open.
-        RevocationStatus::not_revoked()
+        match cached {
+            Some((status, _)) if plan == (Plan::Fetch { fallback: true }) => Ok(status),
+            _ => {
+                self.cache.remove(jti);
+                Err(VcpError::RevocationError(format!(
+                    "revocation status of {jti} is unavailable"
+                )))
+            }
+        }
     }
 
     /// Attempt an online revocation check against a status endpoint.
@@ -378,31 +448,74 @@ impl RevocationChecker {
 
     /// Check revocation status against a cached or fetched CRL.
     ///
-    /// If the CRL for the given URI is cached and not expired, uses the
-    /// cached version. Otherwise attempts to fetch and parse a fresh CRL.
-    fn check_crl(&mut self, uri: &str, jti: &str) -> RevocationStatus {
-        // Check CRL cache.
-        if let Some((crl, cached_at)) = self.crl_cache.get(uri) {
-            if cached_at.elapsed() < self.cache_ttl {
-                return crl_lookup_status(crl, jti);
+    /// Uses the cached CRL for `uri` when the policy allows it, otherwise
+    /// refetches it, keeping a stale copy only as the policy's fallback.
+    /// Returns the status with when the CRL was fetched, or `None` if no
+    /// usable CRL is available.
+    fn check_crl(&mut self, uri: &str, jti: &str) -> Option<(RevocationStatus, DateTime<Utc>)> {
+        let age = self.crl_cache.get(uri).map(|(_, at)| self.age(*at));
+        let plan = self.policy.plan(age);
+        if plan != Plan::UseCached {
+            match self.fetch_crl(uri) {
+                Some(crl) => self.insert_crl(uri, crl),
+                None if plan == (Plan::Fetch { fallback: true }) => {}
+                None => {
+                    self.crl_cache.remove(uri);
+                    return None;
+                }
             }
         }
+        self.crl_cache
+            .get(uri)
+            .map(|(crl, fetched_at)| (crl_lookup_status(crl, jti), *fetched_at))
+    }
 
+    /// Fetch and parse the CRL at `uri`.
+    ///
+    /// Returns `None` if it cannot be fetched (URI validation failure,
+    /// network error, or missing HTTP client).
+    #[allow(clippy::unused_self)] // Will use self for HTTP client state when ureq/minreq is added.
+    fn fetch_crl(&self, uri: &str) -> Option<Crl> {
         // Validate URI for SSRF safety.
         if validate_uri(uri).is_err() {
-            return RevocationStatus::not_revoked();
+            return None;
         }
 
         // TODO: Fetch CRL via HTTP GET when a sync HTTP client is available.
-        // For now, return not-revoked (fail-open).
-        RevocationStatus::not_revoked()
+        None
     }
 
     /// Manually insert a CRL into the cache (useful for testing and
     /// offline operation).
     pub fn insert_crl(&mut self, uri: &str, crl: Crl) {
         self.crl_cache
-            .insert(uri.to_string(), (crl, Instant::now()));
+            .insert(uri.to_string(), (crl, self.clock.now()));
+    }
+
+    /// Export the CRL cache with wall-clock timestamps.
+    pub fn cached_crls(&self) -> Vec<CachedCrl> {
+        self.crl_cache
+            .iter()
+            .map(|(uri, (crl, cached_at))| CachedCrl {
+                uri: uri.clone(),
+                fetched_at: *cached_at,
+                crl: crl.clone(),
+            })
+            .collect()
+    }
+
+    /// Re-seed the CRL cache from exported entries.
+    ///
+    /// Entries keep their original age, so a CRL fetched just before a
+    /// restart is not treated as fresh for another full TTL. Entries the
+    /// policy would no longer use, even as a fallback, are skipped.
+    pub fn restore_crls(&mut self, entries: impl IntoIterator<Item = CachedCrl>) {
+        for entry in entries {
+            if self.policy.is_usable(self.age(entry.fetched_at)) {
+                self.crl_cache
+                    .insert(entry.uri, (entry.crl, entry.fetched_at));
+            }
+        }
     }
 
     /// Clear all caches.
@@ -699,7 +812,7 @@ mod tests {
 
     #[test]
     fn checker_returns_not_revoked_by_default() {
-        let mut checker = RevocationChecker::new(Duration::from_secs(300), Duration::from_secs(5));
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
 
         let status = checker.check("some-jti", None, None);
         assert!(!status.revoked);
@@ -707,7 +820,7 @@ mod tests {
 
     #[test]
     fn checker_crl_cache_lookup() {
-        let mut checker = RevocationChecker::new(Duration::from_secs(300), Duration::from_secs(5));
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
 
         let crl = Crl {
             issuer: "test".into(),
@@ -742,7 +855,7 @@ mod tests {
 
     #[test]
     fn checker_caches_results() {
-        let mut checker = RevocationChecker::new(Duration::from_secs(300), Duration::from_secs(5));
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
 
         let crl = Crl {
             issuer: "test".into(),
@@ -767,7 +880,7 @@ mod tests {
 
     #[test]
     fn checker_clear_cache() {
-        let mut checker = RevocationChecker::new(Duration::from_secs(300), Duration::from_secs(5));
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
 
         let crl = Crl {
             issuer: "test".into(),
@@ -791,9 +904,70 @@ mod tests {
         assert!(!status.revoked);
     }
 
+    #[test]
+    fn checker_crl_cache_survives_export_and_restore() {
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
+        let crl = Crl {
+            issuer: "test".into(),
+            updated_at: "2026-02-01T00:00:00Z".into(),
+            next_update: "2026-03-01T00:00:00Z".into(),
+            revoked: vec![CrlEntry {
+                jti: "restored-jti".into(),
+                revoked_at: "2026-01-15T12:00:00Z".into(),
+                reason: "test".into(),
+            }],
+        };
+        checker.insert_crl("https://example.com/crl.json", crl);
+
+        let json = serde_json::to_string(&checker.cached_crls()).unwrap();
+        let mut entries: Vec<CachedCrl> = serde_json::from_str(&json).unwrap();
+        let mut stale = entries[0].clone();
+        stale.uri = "https://example.com/old.json".into();
+        stale.fetched_at -= chrono::Duration::minutes(10);
+        entries.push(stale);
+
+        let mut restarted = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
+        restarted.restore_crls(entries);
+        assert_eq!(restarted.cached_crls().len(), 1);
+        let status = restarted.check("restored-jti", None, Some("https://example.com/crl.json"));
+        assert!(status.revoked);
+    }
+
+    #[test]
+    fn checker_cache_expires_against_its_clock() {
+        use crate::clock::MockClock;
+
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5))
+            .with_clock(clock.clone());
+        let crl = Crl {
+            issuer: "test".into(),
+            updated_at: "2026-02-01T00:00:00Z".into(),
+            next_update: "2026-03-01T00:00:00Z".into(),
+            revoked: vec![CrlEntry {
+                jti: "aging-jti".into(),
+                revoked_at: "2026-01-15T12:00:00Z".into(),
+                reason: "test".into(),
+            }],
+        };
+        checker.insert_crl("https://example.com/crl.json", crl);
+        assert!(
+            checker
+                .check("aging-jti", None, Some("https://example.com/crl.json"))
+                .revoked
+        );
+        assert_eq!(checker.cached_crls()[0].fetched_at, clock.now());
+
+        clock.advance(chrono::Duration::minutes(4));
+        assert!(checker.check("aging-jti", None, None).revoked);
+
+        clock.advance(chrono::Duration::minutes(2));
+        assert!(!checker.check("aging-jti", None, None).revoked);
+    }
+
     #[test]
     fn checker_rejects_unsafe_crl_uri() {
-        let mut checker = RevocationChecker::new(Duration::from_secs(300), Duration::from_secs(5));
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
 
         // Private IP CRL URI should fail SSRF validation, returning not-revoked.
         let status = checker.check("some-jti", None, Some("https://192.168.1.1/crl.json"));
@@ -802,11 +976,86 @@ mod tests {
 
     #[test]
     fn checker_rejects_unsafe_check_uri() {
-        let mut checker = RevocationChecker::new(Duration::from_secs(300), Duration::from_secs(5));
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
 
         // Online check with private IP should return None (indeterminate),
         // falling through to not-revoked.
         let status = checker.check("some-jti", Some("https://10.0.0.1/revoked"), None);
         assert!(!status.revoked);
     }
+
+    #[test]
+    fn checker_resolve_reports_unknown_status() {
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5));
+
+        assert!(matches!(
+            checker.resolve("some-jti", Some("https://10.0.0.1/revoked"), None),
+            Err(VcpError::RevocationError(_))
+        ));
+        assert!(checker
+            .resolve("some-jti", None, Some("https://example.com/crl.json"))
+            .is_err());
+        assert!(!checker.check("some-jti", None, None).revoked);
+    }
+
+    #[test]
+    fn checker_falls_back_to_stale_crl_within_max_stale() {
+        use crate::clock::MockClock;
+
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let policy = ResolutionPolicy::require_fresh(Duration::from_mins(5))
+            .with_max_stale(Duration::from_mins(10));
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5))
+            .with_resolution_policy(policy)
+            .with_clock(clock.clone());
+        let crl = Crl {
+            issuer: "test".into(),
+            updated_at: "2026-02-01T00:00:00Z".into(),
+            next_update: "2026-03-01T00:00:00Z".into(),
+            revoked: vec![CrlEntry {
+                jti: "stale-jti".into(),
+                revoked_at: "2026-01-15T12:00:00Z".into(),
+                reason: "test".into(),
+            }],
+        };
+        let uri = "https://example.com/crl.json";
+        checker.insert_crl(uri, crl);
+
+        // Past the TTL the refetch fails, so the stale CRL answers.
+        clock.advance(chrono::Duration::minutes(12));
+        assert!(
+            checker
+                .resolve("stale-jti", None, Some(uri))
+                .unwrap()
+                .revoked
+        );
+
+        // Past max_stale it is dropped and the status is unknown.
+        clock.advance(chrono::Duration::minutes(4));
+        assert!(checker.resolve("stale-jti", None, Some(uri)).is_err());
+        assert!(checker.cached_crls().is_empty());
+    }
+
+    #[test]
+    fn checker_prefer_cache_keeps_answering_offline() {
+        let mut checker = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5))
+            .with_resolution_policy(ResolutionPolicy::prefer_cache());
+        let crl = Crl {
+            issuer: "test".into(),
+            updated_at: "2026-02-01T00:00:00Z".into(),
+            next_update: "2026-03-01T00:00:00Z".into(),
+            revoked: Vec::new(),
+        };
+        checker.insert_crl("https://example.com/crl.json", crl);
+        let mut entries = checker.cached_crls();
+        entries[0].fetched_at -= chrono::Duration::days(30);
+
+        let mut restarted = RevocationChecker::new(Duration::from_mins(5), Duration::from_secs(5))
+            .with_resolution_policy(ResolutionPolicy::prefer_cache());
+        restarted.restore_crls(entries);
+        let status = restarted
+            .resolve("good-jti", None, Some("https://example.com/crl.json"))
+            .unwrap();
+        assert!(!status.revoked);
+    }
 }
@@ -0,0 +1,732 @@
+//! Anonymization of VCP artifacts for bug reports.
+//!
+//! A failing token or context string often carries the very values a user
+//! would rather not paste into a public issue: profile IDs, organisation
+//! namespaces, private markers and free-form categories. [`Scrubber`]
+//! replaces those values with stable pseudonyms and leaves everything else
+//! byte-for-byte intact, so the scrubbed artifact still fails (or passes)
+//! the parser in the same way as the original.
+//!
+//! Pseudonyms preserve the *shape* of the original value: lowercase maps
+//! to lowercase, uppercase to uppercase, digits to digits, and punctuation
+//! is kept. A value that trips a character-class check before scrubbing
+//! trips the same check afterwards. The same value always maps to the same
+//! pseudonym for a given salt, so cross-references within a report survive.
+//!
+//! | Artifact | Scrubbed values |
+//! |----------|-----------------|
+//! | VCP/I token | `:NAMESPACE` suffix |
+//! | CSM-1 code | `:NAMESPACE` suffix |
+//! | CSM-1 token | profile ID (line 1), private markers (`S:`), personal state (`R:`) |
+//! | Context wire | free-form situational tags, custom personal categories and sub-signals |
+//!
+//! The ASCII context wire (`ctx1;time=morning||cognitive_state=focused:4`)
+//! is scrubbed the same way: dimension labels and vocabulary names are
+//! kept, and custom values are pseudonymized and re-escaped.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::scrub::{ArtifactKind, Scrubber};
+//!
+//! let raw = "VCP:1.0:alice-42\nC:family.safe@1.0\nP:N:5\nG:\nX:\nF:\nS:diabetic,pregnant";
+//! let report = Scrubber::new("issue-123").scrub(raw);
+//!
+//! assert_eq!(report.kind, ArtifactKind::Csm1Token);
+//! assert!(!report.output.contains("alice"));
+//! assert!(!report.output.contains("diabetic"));
+//! assert!(report.output.starts_with("VCP:1.0:"));
+//! assert!(report.reproduces_error());
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::context::{
+    ascii_escape, ascii_unescape, detect_wire_format, FullContext, WireFormat,
+    ASCII_WIRE_SEPARATOR, WIRE_SEPARATOR,
+};
+use crate::csm1::{Csm1Code, Csm1Token};
+use crate::error::VcpError;
+use crate::identity::VcpToken;
+use crate::personal::{split_metadata_suffix, PersonalDimensionKind};
+use crate::situational::SituationalDimension;
+
+// ── Artifact kinds ──────────────────────────────────────────
+
+/// The kind of artifact being scrubbed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    /// VCP/I identity token (`family.safe.guide@1.2.0:ACME`).
+    IdentityToken,
+    /// CSM-1 compact code (`N5+F+E:ACME@1.0.0`).
+    Csm1Code,
+    /// CSM-1 7- or 8-line token.
+    Csm1Token,
+    /// Full context wire string (situational `‖` personal), emoji or
+    /// `ctx1;` ASCII.
+    Context,
+}
+
+impl ArtifactKind {
+    /// Guess the artifact kind from its raw text.
+    ///
+    /// Detection is deliberately lexical so it works on input that does
+    /// not parse.
+    pub fn detect(raw: &str) -> Self {
+        let trimmed = raw.trim();
+        if detect_wire_format(trimmed) == WireFormat::Ascii {
+            return Self::Context;
+        }
+        if trimmed.starts_with("VCP:") || trimmed.contains('\n') {
+            return Self::Csm1Token;
+        }
+        if trimmed.contains(WIRE_SEPARATOR) || trimmed.chars().next().is_some_and(|c| !c.is_ascii())
+        {
+            return Self::Context;
+        }
+        let head = trimmed.split(['@', ':']).next().unwrap_or_default();
+        if head.contains('.') {
+            Self::IdentityToken
+        } else {
+            Self::Csm1Code
+        }
+    }
+
+    /// Run the matching parser and return its error, if any.
+    pub fn parse_error(self, raw: &str) -> Option<VcpError> {
+        let result = match self {
+            Self::IdentityToken => VcpToken::parse(raw).map(drop),
+            Self::Csm1Code => Csm1Code::parse(raw).map(drop),
+            Self::Csm1Token => Csm1Token::parse(raw).map(drop),
+            Self::Context => FullContext::from_wire(raw).map(drop),
+        };
+        result.err()
+    }
+}
+
+// ── Report ──────────────────────────────────────────────────
+
+/// Result of scrubbing one artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScrubReport {
+    /// Detected artifact kind.
+    pub kind: ArtifactKind,
+    /// The scrubbed artifact text.
+    pub output: String,
+    /// Parse error of the original, with identifying values pseudonymized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_error: Option<String>,
+    /// Parse error of the scrubbed output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_error: Option<String>,
+    /// Whether both inputs failed with the same error variant, or both
+    /// parsed.
+    pub reproduced: bool,
+}
+
+impl ScrubReport {
+    /// Whether the scrubbed output fails the same way as the original.
+    ///
+    /// Messages may still differ where the parser quotes a fragment of a
+    /// scrubbed value (for example a single offending character).
+    pub fn reproduces_error(&self) -> bool {
+        self.reproduced
+    }
+
+    /// Whether the error messages match exactly after pseudonymization.
+    pub fn exact_match(&self) -> bool {
+        self.reproduced && self.expected_error == self.actual_error
+    }
+}
+
+// ── Scrubber ────────────────────────────────────────────────
+
+/// Replaces identifying values with stable, shape-preserving pseudonyms.
+///
+/// A scrubber remembers every value it has replaced, so one instance can
+/// be used across several related artifacts and keep their pseudonyms
+/// consistent.
+#[derive(Debug, Clone, Default)]
+pub struct Scrubber {
+    salt: String,
+    forward: BTreeMap<String, String>,
+    taken: BTreeMap<String, String>,
+}
+
+impl Scrubber {
+    /// Create a scrubber. Different salts give unrelated pseudonyms.
+    pub fn new(salt: impl Into<String>) -> Self {
+        Self {
+            salt: salt.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Original → pseudonym mappings made so far.
+    pub fn mappings(&self) -> &BTreeMap<String, String> {
+        &self.forward
+    }
+
+    /// Detect the artifact kind, scrub it, and check that the parse
+    /// outcome is unchanged.
+    pub fn scrub(&mut self, raw: &str) -> ScrubReport {
+        self.scrub_as(ArtifactKind::detect(raw), raw)
+    }
+
+    /// Scrub `raw` as an artifact of the given kind.
+    pub fn scrub_as(&mut self, kind: ArtifactKind, raw: &str) -> ScrubReport {
+        let output = match kind {
+            ArtifactKind::IdentityToken => self.scrub_identity_token(raw),
+            ArtifactKind::Csm1Code => self.scrub_csm1_code(raw),
+            ArtifactKind::Csm1Token => self.scrub_csm1_token(raw),
+            ArtifactKind::Context => self.scrub_context(raw),
+        };
+        let before = kind.parse_error(raw);
+        let after = kind.parse_error(&output);
+        let reproduced = match (&before, &after) {
+            (None, None) => true,
+            (Some(a), Some(b)) => std::mem::discriminant(a) == std::mem::discriminant(b),
+            _ => false,
+        };
+        ScrubReport {
+            kind,
+            expected_error: before.map(|e| self.redact(&e.to_string())),
+            actual_error: after.map(|e| e.to_string()),
+            output,
+            reproduced,
+        }
+    }
+
+    /// Scrub the namespace of a VCP/I token.
+    pub fn scrub_identity_token(&mut self, raw: &str) -> String {
+        match raw.rfind(':') {
+            Some(idx) => format!("{}:{}", &raw[..idx], self.pseudonym(&raw[idx + 1..])),
+            None => raw.to_string(),
+        }
+    }
+
+    /// Scrub the namespace of a CSM-1 compact code.
+    ///
+    /// The parser upper-cases its input, so the namespace is upper-cased
+    /// before pseudonymizing to keep error messages comparable.
+    pub fn scrub_csm1_code(&mut self, raw: &str) -> String {
+        let Some(colon) = raw.find(':') else {
+            return raw.to_string();
+        };
+        let tail = &raw[colon + 1..];
+        let end = tail.find('@').unwrap_or(tail.len());
+        let ns = self.pseudonym(&tail[..end].to_uppercase());
+        format!("{}:{ns}{}", &raw[..colon], &tail[end..])
+    }
+
+    /// Scrub a CSM-1 multi-line token line by line.
+    ///
+    /// Lines are recognised by prefix rather than position, so a token with
+    /// missing or reordered lines is still scrubbed.
+    pub fn scrub_csm1_token(&mut self, raw: &str) -> String {
+        let mut out = String::with_capacity(raw.len());
+        for line in raw.split_inclusive('\n') {
+            let (body, ending) = split_line_ending(line);
+            if let Some(header) = body.strip_prefix("VCP:") {
+                out.push_str("VCP:");
+                match header.split_once(':') {
+                    Some((version, profile)) => {
+                        out.push_str(version);
+                        out.push(':');
+                        out.push_str(&self.pseudonym(profile));
+                    }
+                    None => out.push_str(header),
+                }
+            } else if let Some(markers) = body.strip_prefix("S:") {
+                out.push_str("S:");
+                let scrubbed: Vec<String> =
+                    markers.split(',').map(|m| self.scrub_trimmed(m)).collect();
+                out.push_str(&scrubbed.join(","));
+            } else if let Some(state) = body.strip_prefix("R:") {
+                out.push_str("R:");
+                out.push_str(&self.scrub_personal(state));
+            } else {
+                out.push_str(body);
+            }
+            out.push_str(ending);
+        }
+        out
+    }
+
+    /// Scrub a full context wire string, in either format.
+    pub fn scrub_context(&mut self, raw: &str) -> String {
+        if detect_wire_format(raw) == WireFormat::Ascii {
+            return self.scrub_ascii_context(raw);
+        }
+        match raw.split_once(WIRE_SEPARATOR) {
+            Some((situational, personal)) => format!(
+                "{}{WIRE_SEPARATOR}{}",
+                self.scrub_words(situational),
+                self.scrub_personal(personal)
+            ),
+            None => self.scrub_words(raw),
+        }
+    }
+
+    /// Return the pseudonym for `value`, minting one on first use.
+    pub fn pseudonym(&mut self, value: &str) -> String {
+        if let Some(p) = self.forward.get(value) {
+            return p.clone();
+        }
+        if !value.chars().any(char::is_alphanumeric) {
+            return value.to_string();
+        }
+        let mut attempt = 0u32;
+        let pseudonym = loop {
+            let candidate = shape_pseudonym(&self.salt, value, attempt);
+            if candidate != value && !self.taken.contains_key(&candidate) {
+                break candidate;
+            }
+            attempt += 1;
+        };
+        self.forward.insert(value.to_string(), pseudonym.clone());
+        self.taken.insert(pseudonym.clone(), value.to_string());
+        pseudonym
+    }
+
+    /// Apply every known mapping to free text such as an error message.
+    ///
+    /// Longest originals win, and replaced text is never rescanned.
+    pub fn redact(&self, text: &str) -> String {
+        let mut keys: Vec<&String> = self.forward.keys().filter(|k| !k.is_empty()).collect();
+        keys.sort_by_key(|k| std::cmp::Reverse(k.len()));
+
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        'scan: while let Some(ch) = rest.chars().next() {
+            for key in &keys {
+                if rest.starts_with(key.as_str()) {
+                    out.push_str(&self.forward[*key]);
+                    rest = &rest[key.len()..];
+                    continue 'scan;
+                }
+            }
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+        out
+    }
+
+    // ── Helpers ─────────────────────────────────────────────
+
+    /// Scrub a `ctx<N>;` wire segment by segment, keeping the header,
+    /// dimension labels and vocabulary names.
+    fn scrub_ascii_context(&mut self, raw: &str) -> String {
+        let Some((header, body)) = raw.split_once(';') else {
+            return raw.to_string();
+        };
+        let (situational, personal) = match body.split_once(ASCII_WIRE_SEPARATOR) {
+            Some((sit, per)) => (sit, Some(per)),
+            None => (body, None),
+        };
+        let mut out = format!("{header};");
+        let segments: Vec<String> = situational
+            .split(';')
+            .map(|seg| self.scrub_ascii_situational(seg))
+            .collect();
+        out.push_str(&segments.join(";"));
+        if let Some(personal) = personal {
+            out.push_str(ASCII_WIRE_SEPARATOR);
+            let segments: Vec<String> = personal
+                .split(';')
+                .map(|seg| self.scrub_ascii_personal(seg))
+                .collect();
+            out.push_str(&segments.join(";"));
+        }
+        out
+    }
+
+    /// `label=tag,tag` or an extension segment (`redacted=`, `consent=`).
+    fn scrub_ascii_situational(&mut self, seg: &str) -> String {
+        let Some((label, values)) = seg.split_once('=') else {
+            return self.scrub_words(seg);
+        };
+        let Some(dim) = SituationalDimension::from_label(label.trim()) else {
+            // Extension keys are fixed; their values are as free-form as
+            // the emoji header's.
+            let label = if matches!(label, "redacted" | "consent") {
+                label.to_string()
+            } else {
+                self.pseudonym(label)
+            };
+            return format!("{label}={}", self.scrub_words(values));
+        };
+        let tags: Vec<String> = values
+            .split(',')
+            .map(|tag| {
+                if tag.is_empty() || dim.tag_emoji(tag).is_some() {
+                    tag.to_string()
+                } else {
+                    self.scrub_escaped(tag, &[':'])
+                }
+            })
+            .collect();
+        format!("{label}={}", tags.join(","))
+    }
+
+    /// `label=value:intensity[ext]` with optional metadata suffixes.
+    fn scrub_ascii_personal(&mut self, seg: &str) -> String {
+        let Some((label, rest)) = seg.split_once('=') else {
+            return self.scrub_words(seg);
+        };
+        match PersonalDimensionKind::from_label(label.trim()) {
+            Some(kind) => format!(
+                "{label}={}",
+                self.scrub_dimension_body(Some(kind), rest, true)
+            ),
+            None => format!(
+                "{}={}",
+                self.pseudonym(label),
+                self.scrub_dimension_body(None, rest, true)
+            ),
+        }
+    }
+
+    /// Pseudonymize a percent-escaped value and escape the result again,
+    /// so escapes stay valid. A value with a bad escape is pseudonymized
+    /// as written, which still fails to parse.
+    fn scrub_escaped(&mut self, value: &str, extra: &[char]) -> String {
+        match ascii_unescape(value) {
+            Ok(plain) => ascii_escape(&self.pseudonym(&plain), extra),
+            Err(_) => self.pseudonym(value),
+        }
+    }
+
+    /// Pseudonymize the trimmed core of `s`, keeping surrounding whitespace.
+    fn scrub_trimmed(&mut self, s: &str) -> String {
+        let core = s.trim();
+        if core.is_empty() {
+            return s.to_string();
+        }
+        let start = s.len() - s.trim_start().len();
+        let end = start + core.len();
+        format!("{}{}{}", &s[..start], self.pseudonym(core), &s[end..])
+    }
+
+    /// Pseudonymize each maximal run of alphanumeric characters.
+    ///
+    /// Used for situational tags: the standard vocabulary is emoji, so
+    /// any words present are user-supplied.
+    fn scrub_words(&mut self, s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut word_start = None;
+        for (i, ch) in s.char_indices() {
+            let is_word = ch.is_alphanumeric() || ch == '_' || ch == '-';
+            match (is_word, word_start) {
+                (true, None) => word_start = Some(i),
+                (false, Some(start)) => {
+                    out.push_str(&self.pseudonym(&s[start..i]));
+                    out.push(ch);
+                    word_start = None;
+                }
+                (false, None) => out.push(ch),
+                (true, Some(_)) => {}
+            }
+        }
+        if let Some(start) = word_start {
+            out.push_str(&self.pseudonym(&s[start..]));
+        }
+        out
+    }
+
+    /// Scrub personal-state segments: custom categories and every
+    /// `[sub-signal]` are pseudonymized; standard categories are kept.
+    fn scrub_personal(&mut self, s: &str) -> String {
+        let segments: Vec<String> = s.split('|').map(|seg| self.scrub_dimension(seg)).collect();
+        segments.join("|")
+    }
+
+    fn scrub_dimension(&mut self, seg: &str) -> String {
+        let value_start = seg
+            .find(|c: char| c.is_ascii_alphanumeric())
+            .unwrap_or(seg.len());
+        let (symbol, rest) = seg.split_at(value_start);
+        let kind = PersonalDimensionKind::from_symbol(symbol.trim());
+        format!("{symbol}{}", self.scrub_dimension_body(kind, rest, false))
+    }
+
+    /// Scrub `value:intensity[ext]` plus metadata; `escaped` values are
+    /// percent-encoded, as in the ASCII wire.
+    fn scrub_dimension_body(
+        &mut self,
+        kind: Option<PersonalDimensionKind>,
+        rest: &str,
+        escaped: bool,
+    ) -> String {
+        let scrub = |this: &mut Self, v: &str| {
+            if escaped {
+                this.scrub_escaped(v, &[])
+            } else {
+                this.pseudonym(v)
+            }
+        };
+        let (rest, metadata) = split_metadata_suffix(rest);
+        let (main, extended) = match rest.find('[') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, ""),
+        };
+        let (value, intensity) = match main.find(':') {
+            Some(idx) => main.split_at(idx),
+            None => (main, ""),
+        };
+
+        let standard = kind.is_some_and(|kind| kind.valid_values().contains(&value));
+        let value = if standard {
+            value.to_string()
+        } else {
+            scrub(self, value)
+        };
+
+        let extended = match extended.strip_prefix('[') {
+            Some(inner) => {
+                let (inner, close) = match inner.strip_suffix(']') {
+                    Some(i) => (i, "]"),
+                    None => (inner, ""),
+                };
+                format!("[{}{close}", scrub(self, inner))
+            }
+            None => String::new(),
+        };
+
+        format!("{value}{intensity}{extended}{metadata}")
+    }
+}
+
+/// Split a line from `split_inclusive('\n')` into body and terminator.
+fn split_line_ending(line: &str) -> (&str, &str) {
+    if let Some(body) = line.strip_suffix("\r\n") {
+        (body, "\r\n")
+    } else if let Some(body) = line.strip_suffix('\n') {
+        (body, "\n")
+    } else {
+        (line, "")
+    }
+}
+
+/// Derive a pseudonym with the same character classes as `value`.
+fn shape_pseudonym(salt: &str, value: &str, attempt: u32) -> String {
+    let mut block = [0u8; 32];
+    let mut out = String::with_capacity(value.len());
+    for (i, ch) in value.chars().enumerate() {
+        if i % 32 == 0 {
+            let mut hasher = Sha256::new();
+            hasher.update(salt.as_bytes());
+            hasher.update([0]);
+            hasher.update(value.as_bytes());
+            hasher.update(attempt.to_le_bytes());
+            hasher.update(((i / 32) as u64).to_le_bytes());
+            block.copy_from_slice(&hasher.finalize());
+        }
+        let b = block[i % 32];
+        out.push(match ch {
+            'a'..='z' => char::from(b'a' + b % 26),
+            'A'..='Z' => char::from(b'A' + b % 26),
+            '0'..='9' => char::from(b'0' + b % 10),
+            c if c.is_lowercase() => char::from_u32(0x03B1 + u32::from(b % 17)).unwrap_or(c),
+            c if c.is_alphanumeric() => char::from_u32(0x0391 + u32::from(b % 17)).unwrap_or(c),
+            c => c,
+        });
+    }
+    out
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "VCP:1.0:user-alice-7\n\
+                         C:family.safe.guide@1.2.0\n\
+                         P:N:5\n\
+                         G:learn:beginner:visual\n\
+                         X:no_violence\n\
+                         F:\n\
+                         S:diabetic, pregnant\n\
+                         R:\u{1F9E0}focused:3|\u{1FA7A}pain:4[migraine]";
+
+    #[test]
+    fn detects_artifact_kinds() {
+        assert_eq!(ArtifactKind::detect(TOKEN), ArtifactKind::Csm1Token);
+        assert_eq!(
+            ArtifactKind::detect("family.safe.guide@1.2.0:ACME"),
+            ArtifactKind::IdentityToken
+        );
+        assert_eq!(
+            ArtifactKind::detect("N5+F:ACME@1.0.0"),
+            ArtifactKind::Csm1Code
+        );
+        assert_eq!(
+            ArtifactKind::detect("\u{1F305}\u{2016}\u{1F9E0}focused:3"),
+            ArtifactKind::Context
+        );
+        assert_eq!(
+            ArtifactKind::detect("ctx1;time=morning||cognitive_state=focused:3"),
+            ArtifactKind::Context
+        );
+    }
+
+    #[test]
+    fn token_keeps_structure_and_drops_identifiers() {
+        let mut s = Scrubber::new("t");
+        let report = s.scrub(TOKEN);
+        assert!(report.reproduces_error());
+        assert!(report.actual_error.is_none());
+
+        let parsed = Csm1Token::parse(&report.output).unwrap();
+        let original = Csm1Token::parse(TOKEN).unwrap();
+        assert_ne!(parsed.profile_id, original.profile_id);
+        assert_eq!(parsed.profile_id.len(), original.profile_id.len());
+        assert_eq!(parsed.constitution, original.constitution);
+        assert_eq!(parsed.persona, original.persona);
+        assert_eq!(parsed.private_markers.len(), 2);
+        assert!(!report.output.contains("diabetic"));
+        assert!(!report.output.contains("migraine"));
+        // Standard categories survive.
+        assert!(report.output.contains("focused:3"));
+        assert!(report.output.contains("pain:4["));
+    }
+
+    #[test]
+    fn pseudonyms_are_stable_and_shape_preserving() {
+        let mut a = Scrubber::new("salt");
+        let mut b = Scrubber::new("salt");
+        let p = a.pseudonym("Acme-42");
+        assert_eq!(p, b.pseudonym("Acme-42"));
+        assert_eq!(p, a.pseudonym("Acme-42"));
+        assert_ne!(p, Scrubber::new("other").pseudonym("Acme-42"));
+
+        let shape: Vec<(bool, bool, bool)> = p
+            .chars()
+            .map(|c| {
+                (
+                    c.is_ascii_uppercase(),
+                    c.is_ascii_lowercase(),
+                    c.is_ascii_digit(),
+                )
+            })
+            .collect();
+        let expected: Vec<(bool, bool, bool)> = "Acme-42"
+            .chars()
+            .map(|c| {
+                (
+                    c.is_ascii_uppercase(),
+                    c.is_ascii_lowercase(),
+                    c.is_ascii_digit(),
+                )
+            })
+            .collect();
+        assert_eq!(shape, expected);
+        assert_eq!(p.as_bytes()[4], b'-');
+    }
+
+    #[test]
+    fn distinct_values_get_distinct_pseudonyms() {
+        let mut s = Scrubber::default();
+        let names: Vec<String> = (0..200).map(|i| s.pseudonym(&format!("x{i}"))).collect();
+        let unique: std::collections::BTreeSet<&String> = names.iter().collect();
+        assert_eq!(unique.len(), names.len());
+    }
+
+    #[test]
+    fn identity_namespace_error_is_reproduced() {
+        let mut s = Scrubber::new("t");
+        let ok = s.scrub("family.safe.guide@1.2.0:ACMECORP");
+        assert!(ok.actual_error.is_none());
+        assert!(ok.output.starts_with("family.safe.guide@1.2.0:"));
+        assert!(!ok.output.contains("ACMECORP"));
+
+        let bad = s.scrub("family.safe.guide:Acme");
+        assert!(bad.actual_error.is_some());
+        assert!(bad.reproduces_error());
+        // The parser quotes the offending character, which is scrubbed too.
+        assert!(!bad.exact_match());
+
+        let bad = s.scrub("Family.safe.guide:ACME");
+        assert!(bad.actual_error.is_some());
+        assert!(bad.exact_match());
+        assert!(!bad.output.contains("Acme"));
+    }
+
+    #[test]
+    fn csm1_code_namespace() {
+        let mut s = Scrubber::new("t");
+        let report = s.scrub("n5+f:acme@1.0.0");
+        assert!(report.actual_error.is_none());
+        let code = Csm1Code::parse(&report.output).unwrap();
+        assert_ne!(code.namespace.as_deref(), Some("ACME"));
+        assert_eq!(code.version.as_deref(), Some("1.0.0"));
+
+        let bad = s.scrub("N5:1ACME");
+        assert!(bad.actual_error.is_some());
+        assert!(bad.exact_match());
+    }
+
+    #[test]
+    fn broken_token_error_is_reproduced() {
+        let broken = TOKEN.replace("P:N:5", "P:N:9");
+        let report = Scrubber::new("t").scrub(&broken);
+        assert!(report.actual_error.is_some());
+        assert!(report.exact_match());
+
+        let short = "VCP:1.0:bob\nC:x@1\nS:secret";
+        let report = Scrubber::new("t").scrub(short);
+        assert!(report.reproduces_error());
+        assert!(!report.output.contains("bob"));
+        assert!(!report.output.contains("secret"));
+    }
+
+    #[test]
+    fn context_free_form_tags_are_scrubbed() {
+        let wire = "\u{23F0}\u{1F305}|\u{1FAA2}coworker:mentor\u{2016}\u{1F4AD}grieving:4|\u{26A1}pressured:2";
+        let mut s = Scrubber::new("t");
+        let report = s.scrub(wire);
+        assert_eq!(report.kind, ArtifactKind::Context);
+        assert!(report.reproduces_error());
+        assert!(!report.output.contains("coworker"));
+        assert!(!report.output.contains("grieving"));
+        assert!(report.output.contains("\u{26A1}pressured:2"));
+        assert!(report.output.contains("\u{23F0}\u{1F305}|\u{1FAA2}"));
+        assert!(report.actual_error.is_none());
+    }
+
+    #[test]
+    fn ascii_context_keeps_labels_and_vocabulary() {
+        let wire = "ctx1;redacted=share-with-third-party;time=morning;company=coworker%3Amentor\
+                    ||emotional_tone=grieving:4[lost%20my%20dog];perceived_urgency=pressured:2";
+        let mut s = Scrubber::new("t");
+        let report = s.scrub(wire);
+        assert_eq!(report.kind, ArtifactKind::Context);
+        assert!(report.actual_error.is_none(), "{:?}", report.actual_error);
+        assert!(report.reproduces_error());
+        assert!(report.output.starts_with("ctx1;redacted="));
+        assert!(report.output.contains(";time=morning;company="));
+        assert!(report.output.contains("||emotional_tone="));
+        assert!(report.output.ends_with(";perceived_urgency=pressured:2"));
+        for secret in ["coworker", "mentor", "grieving", "lost", "dog"] {
+            assert!(!report.output.contains(secret), "{secret}");
+        }
+
+        let bad = s.scrub("ctx1;time=morning||focus=deep:9");
+        assert!(bad.actual_error.is_some());
+        assert!(bad.reproduces_error());
+        assert!(!bad.output.contains("focus"));
+    }
+
+    #[test]
+    fn redact_prefers_longest_match() {
+        let mut s = Scrubber::new("t");
+        let short = s.pseudonym("ab");
+        let long = s.pseudonym("abc");
+        assert_eq!(s.redact("ab abc"), format!("{short} {long}"));
+    }
+}
@@ -0,0 +1,773 @@
+//! Agent-to-agent context exchange.
+//!
+//! Two agents sharing a session first swap [`Handshake`]s. Each one
+//! advertises the VCP versions the agent speaks, the personas it can
+//! operate as, and its Ed25519 public key, and is signed with that key.
+//! Both sides derive the same [`Agreement`] from the pair: the highest
+//! common version and the common personas.
+//!
+//! After that they send [`ExchangeMessage`]s:
+//!
+//! | `type` | Payload |
+//! |--------|---------|
+//! | `context_update` | [`ContextUpdate`] — the sender's current context as a wire string |
+//! | `constitution_selection` | [`ConstitutionSelection`] — the constitution, persona and adherence to apply |
+//!
+//! Every message carries the session ID, the sender, a sequence number
+//! and a signature. An [`Exchange`] holds one side's state: it numbers
+//! and signs outgoing messages, and accepts an incoming one only if the
+//! peer's handshake key signed it for this session and its sequence
+//! number is higher than any already accepted. A replayed or reordered
+//! message is rejected with [`VcpError::SessionError`].
+//!
+//! Signatures cover the RFC 8785 canonical JSON of a message without its
+//! `signature` field, the same bytes a manifest signature covers, so
+//! other SDKs can check them with their manifest tooling. A valid
+//! handshake only proves possession of the key it advertises; whether
+//! to trust that key (e.g. against a [`TrustConfig`](crate::trust::TrustConfig))
+//! is up to the caller.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//! use vcp_core::context::FullContext;
+//! use vcp_core::csm1::Persona;
+//! use vcp_core::session::exchange::{Exchange, Handshake, Received};
+//! use vcp_core::signer::Ed25519Signer;
+//!
+//! let alice_key = Arc::new(Ed25519Signer::from_seed(&[1; 32]));
+//! let bob_key = Arc::new(Ed25519Signer::from_seed(&[2; 32]));
+//! let alice_hello = Handshake::new("alice")
+//!     .with_vcp_versions(["1.0", "1.1"])
+//!     .with_personas([Persona::Ambassador, Persona::Muse])
+//!     .signed(alice_key.as_ref())
+//!     .unwrap();
+//! let bob_hello = Handshake::new("bob")
+//!     .with_vcp_versions(["1.0"])
+//!     .with_personas([Persona::Ambassador])
+//!     .signed(bob_key.as_ref())
+//!     .unwrap();
+//!
+//! let mut alice = Exchange::new("s-1", alice_hello.clone(), bob_hello.clone(), alice_key).unwrap();
+//! let mut bob = Exchange::new("s-1", bob_hello, alice_hello, bob_key).unwrap();
+//! assert_eq!(alice.agreement().vcp_version, "1.0");
+//!
+//! let ctx = FullContext::from_wire("⏰🌅|📍🏡").unwrap();
+//! let message = alice.send_context(&ctx).unwrap();
+//! let json = message.to_json().unwrap();
+//!
+//! let received = bob.receive(&serde_json::from_str(&json).unwrap()).unwrap();
+//! assert_eq!(received, Received::Context(Box::new(ctx)));
+//!
+//! // The same message again is a replay.
+//! assert!(bob.receive(&message).is_err());
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::context::FullContext;
+use crate::csm1::Persona;
+use crate::error::{VcpError, VcpResult};
+use crate::identity::VcpToken;
+use crate::signer::{sign_manifest_with, ManifestSigner};
+use crate::transport::verify_manifest_signature;
+use crate::trust::TrustAnchor;
+
+/// Exchange protocol version produced by this crate.
+pub const EXCHANGE_VERSION: &str = "1.0";
+
+/// Highest adherence level a [`ConstitutionSelection`] may request.
+const MAX_ADHERENCE: u8 = 5;
+
+// ── Handshake ───────────────────────────────────────────────
+
+/// The first message each side sends: what it supports and its key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Handshake {
+    /// Exchange protocol version (`EXCHANGE_VERSION` for handshakes built here).
+    pub version: String,
+    /// The sending agent.
+    pub agent_id: String,
+    /// VCP versions the agent speaks.
+    pub vcp_versions: Vec<String>,
+    /// Personas the agent can operate as.
+    pub personas: Vec<Persona>,
+    /// The agent's Ed25519 public key as `base64:<key>`.
+    pub public_key: String,
+    /// Signature by `public_key` over the rest of the handshake.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl Handshake {
+    /// Start an unsigned handshake for `agent_id` that supports nothing yet.
+    pub fn new(agent_id: impl Into<String>) -> Self {
+        Self {
+            version: EXCHANGE_VERSION.to_string(),
+            agent_id: agent_id.into(),
+            vcp_versions: Vec::new(),
+            personas: Vec::new(),
+            public_key: String::new(),
+            signature: None,
+        }
+    }
+
+    /// Advertise `versions` (e.g. `"1.0"`).
+    #[must_use]
+    pub fn with_vcp_versions<I, S>(mut self, versions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.vcp_versions
+            .extend(versions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Advertise `personas`.
+    #[must_use]
+    pub fn with_personas(mut self, personas: impl IntoIterator<Item = Persona>) -> Self {
+        self.personas.extend(personas);
+        self
+    }
+
+    /// Set `public_key` from `signer` and sign the handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns whatever error the signer reports.
+    pub fn signed<S: ManifestSigner + ?Sized>(mut self, signer: &S) -> VcpResult<Self> {
+        self.public_key = TrustAnchor::encode_public_key(&signer.public_key()?);
+        self.signature = Some(sign(&self, signer)?);
+        Ok(self)
+    }
+
+    /// The decoded 32-byte public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] if `public_key` is not a
+    /// base64-encoded 32-byte key.
+    pub fn public_key_bytes(&self) -> VcpResult<[u8; 32]> {
+        let raw = self
+            .public_key
+            .strip_prefix("base64:")
+            .unwrap_or(&self.public_key);
+        BASE64
+            .decode(raw)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                VcpError::SignatureError(format!(
+                    "handshake from {} has an invalid public key",
+                    self.agent_id
+                ))
+            })
+    }
+
+    /// Check the protocol version and that the handshake is signed by
+    /// the key it advertises.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SessionError`] for an unsupported protocol
+    /// version and [`VcpError::SignatureError`] for a missing or invalid
+    /// signature.
+    pub fn verify(&self) -> VcpResult<()> {
+        check_version(&self.version)?;
+        check_signature(self, self.signature.as_deref(), &self.public_key_bytes()?)
+    }
+}
+
+// ── Agreement ───────────────────────────────────────────────
+
+/// What two handshakes have in common.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Agreement {
+    /// The highest VCP version both sides speak.
+    pub vcp_version: String,
+    /// Personas both sides can operate as, in [`Persona::all`] order.
+    pub personas: Vec<Persona>,
+}
+
+impl Agreement {
+    /// Compute the agreement between two handshakes. The result does not
+    /// depend on argument order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SessionError`] if the handshakes share no VCP
+    /// version or no persona.
+    pub fn between(a: &Handshake, b: &Handshake) -> VcpResult<Self> {
+        let vcp_version = a
+            .vcp_versions
+            .iter()
+            .filter(|v| b.vcp_versions.contains(v))
+            .max_by(|x, y| compare_versions(x, y))
+            .cloned()
+            .ok_or_else(|| {
+                VcpError::SessionError(format!(
+                    "{} and {} share no VCP version",
+                    a.agent_id, b.agent_id
+                ))
+            })?;
+        let personas: Vec<Persona> = Persona::all()
+            .iter()
+            .copied()
+            .filter(|p| a.personas.contains(p) && b.personas.contains(p))
+            .collect();
+        if personas.is_empty() {
+            return Err(VcpError::SessionError(format!(
+                "{} and {} share no persona",
+                a.agent_id, b.agent_id
+            )));
+        }
+        Ok(Self {
+            vcp_version,
+            personas,
+        })
+    }
+}
+
+/// Compare dotted versions numerically (`1.10` > `1.9`), falling back to
+/// string order for non-numeric parts.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let order = match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y),
+                    _ => x.cmp(y),
+                };
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+        }
+    }
+}
+
+// ── Messages ────────────────────────────────────────────────
+
+/// The sender's current context.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextUpdate {
+    /// Context in full wire format (emoji or ASCII).
+    pub context: String,
+}
+
+/// The constitution the sender wants applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstitutionSelection {
+    /// VCP/I token of the constitution.
+    pub token: String,
+    /// Persona to operate as; must be in the [`Agreement`].
+    pub persona: Persona,
+    /// Adherence level, 0-5.
+    pub adherence: u8,
+}
+
+impl ConstitutionSelection {
+    /// Select `token` under `persona` at `adherence`.
+    pub fn new(token: impl Into<String>, persona: Persona, adherence: u8) -> Self {
+        Self {
+            token: token.into(),
+            persona,
+            adherence,
+        }
+    }
+
+    fn validate(&self, agreement: &Agreement) -> VcpResult<()> {
+        VcpToken::parse(&self.token)?;
+        if self.adherence > MAX_ADHERENCE {
+            return Err(VcpError::InvalidAdherence(self.adherence));
+        }
+        if !agreement.personas.contains(&self.persona) {
+            return Err(VcpError::SessionError(format!(
+                "persona {:?} is not in the agreement",
+                self.persona
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Message type and payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum ExchangeBody {
+    /// The sender's context changed.
+    ContextUpdate(ContextUpdate),
+    /// The sender selected a constitution.
+    ConstitutionSelection(ConstitutionSelection),
+}
+
+/// A signed, sequenced message within an exchange.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeMessage {
+    /// Exchange protocol version.
+    pub version: String,
+    /// The session the message belongs to.
+    pub session_id: String,
+    /// The sending agent.
+    pub sender: String,
+    /// Per-sender sequence number, starting at 1.
+    pub sequence: u64,
+    /// When the message was sent.
+    pub sent_at: DateTime<Utc>,
+    /// Message type and payload (`type` + `payload` on the wire).
+    #[serde(flatten)]
+    pub body: ExchangeBody,
+    /// Signature by the sender's handshake key over the rest of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl ExchangeMessage {
+    /// Serialize to a single-line JSON string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> VcpResult<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserialize a message, rejecting unsupported major versions.
+    ///
+    /// The signature is not checked; pass the message to
+    /// [`Exchange::receive`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] for malformed JSON or an unknown
+    /// `type`, and [`VcpError::SessionError`] if the major version differs
+    /// from [`EXCHANGE_VERSION`].
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        let message: Self = serde_json::from_str(json)?;
+        check_version(&message.version)?;
+        Ok(message)
+    }
+}
+
+/// A message accepted by [`Exchange::receive`], with its payload decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Received {
+    /// The peer's new context.
+    Context(Box<FullContext>),
+    /// The peer's constitution selection.
+    Constitution(ConstitutionSelection),
+}
+
+// ── Exchange ────────────────────────────────────────────────
+
+/// One side of a context exchange.
+pub struct Exchange {
+    session_id: String,
+    local: Handshake,
+    peer: Handshake,
+    peer_key: [u8; 32],
+    agreement: Agreement,
+    signer: Arc<dyn ManifestSigner + Send + Sync>,
+    next_sequence: u64,
+    last_received: u64,
+}
+
+impl fmt::Debug for Exchange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Exchange")
+            .field("session_id", &self.session_id)
+            .field("local", &self.local.agent_id)
+            .field("peer", &self.peer.agent_id)
+            .field("agreement", &self.agreement)
+            .field("next_sequence", &self.next_sequence)
+            .field("last_received", &self.last_received)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Exchange {
+    /// Start an exchange in `session_id` once handshakes are swapped.
+    ///
+    /// `local` is the handshake this side sent, signed by `signer`;
+    /// `peer` is the one it received.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] if the peer handshake does not
+    /// verify or `local` was not signed by `signer`, and
+    /// [`VcpError::SessionError`] if the two sides have nothing in common
+    /// or share an agent ID.
+    pub fn new(
+        session_id: impl Into<String>,
+        local: Handshake,
+        peer: Handshake,
+        signer: Arc<dyn ManifestSigner + Send + Sync>,
+    ) -> VcpResult<Self> {
+        peer.verify()?;
+        if local.public_key_bytes()? != signer.public_key()? {
+            return Err(VcpError::SignatureError(
+                "local handshake was not signed by this signer".into(),
+            ));
+        }
+        if local.agent_id == peer.agent_id {
+            return Err(VcpError::SessionError(format!(
+                "both sides claim agent ID {}",
+                local.agent_id
+            )));
+        }
+        let agreement = Agreement::between(&local, &peer)?;
+        let peer_key = peer.public_key_bytes()?;
+        Ok(Self {
+            session_id: session_id.into(),
+            local,
+            peer,
+            peer_key,
+            agreement,
+            signer,
+            next_sequence: 1,
+            last_received: 0,
+        })
+    }
+
+    /// The session this exchange belongs to.
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// The handshake received from the peer.
+    pub fn peer(&self) -> &Handshake {
+        &self.peer
+    }
+
+    /// What both sides agreed on.
+    pub fn agreement(&self) -> &Agreement {
+        &self.agreement
+    }
+
+    /// Sequence number of the last accepted incoming message, or 0.
+    pub fn last_received(&self) -> u64 {
+        self.last_received
+    }
+
+    /// Sign a context update stamped now.
+    ///
+    /// # Errors
+    ///
+    /// Returns whatever error the signer reports.
+    pub fn send_context(&mut self, context: &FullContext) -> VcpResult<ExchangeMessage> {
+        self.send_context_at(context, Utc::now())
+    }
+
+    /// Sign a context update stamped `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns whatever error the signer reports.
+    pub fn send_context_at(
+        &mut self,
+        context: &FullContext,
+        now: DateTime<Utc>,
+    ) -> VcpResult<ExchangeMessage> {
+        let body = ExchangeBody::ContextUpdate(ContextUpdate {
+            context: context.to_wire(),
+        });
+        self.send(body, now)
+    }
+
+    /// Sign a constitution selection stamped now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token does not parse, the adherence is
+    /// above 5, or the persona is not in the agreement, and otherwise
+    /// whatever error the signer reports.
+    pub fn select_constitution(
+        &mut self,
+        selection: ConstitutionSelection,
+    ) -> VcpResult<ExchangeMessage> {
+        self.select_constitution_at(selection, Utc::now())
+    }
+
+    /// Sign a constitution selection stamped `now`.
+    ///
+    /// # Errors
+    ///
+    /// As for [`select_constitution`](Self::select_constitution).
+    pub fn select_constitution_at(
+        &mut self,
+        selection: ConstitutionSelection,
+        now: DateTime<Utc>,
+    ) -> VcpResult<ExchangeMessage> {
+        selection.validate(&self.agreement)?;
+        self.send(ExchangeBody::ConstitutionSelection(selection), now)
+    }
+
+    fn send(&mut self, body: ExchangeBody, now: DateTime<Utc>) -> VcpResult<ExchangeMessage> {
+        let mut message = ExchangeMessage {
+            version: EXCHANGE_VERSION.to_string(),
+            session_id: self.session_id.clone(),
+            sender: self.local.agent_id.clone(),
+            sequence: self.next_sequence,
+            sent_at: now,
+            body,
+            signature: None,
+        };
+        message.signature = Some(sign(&message, self.signer.as_ref())?);
+        self.next_sequence += 1;
+        Ok(message)
+    }
+
+    /// Check an incoming message and decode its payload.
+    ///
+    /// The sequence number is only recorded once every check has passed,
+    /// so a rejected message does not advance the exchange.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SessionError`] if the message is for another
+    /// session, from someone other than the peer, or not newer than the
+    /// last accepted message (a replay); [`VcpError::SignatureError`] if
+    /// the peer's key did not sign it; and a parse error if the payload
+    /// is invalid.
+    pub fn receive(&mut self, message: &ExchangeMessage) -> VcpResult<Received> {
+        check_version(&message.version)?;
+        if message.session_id != self.session_id {
+            return Err(VcpError::SessionError(format!(
+                "message is for session {}, not {}",
+                message.session_id, self.session_id
+            )));
+        }
+        if message.sender != self.peer.agent_id {
+            return Err(VcpError::SessionError(format!(
+                "message is from {}, not the peer {}",
+                message.sender, self.peer.agent_id
+            )));
+        }
+        check_signature(message, message.signature.as_deref(), &self.peer_key)?;
+        if message.sequence <= self.last_received {
+            return Err(VcpError::SessionError(format!(
+                "replayed or out-of-order message: sequence {} after {}",
+                message.sequence, self.last_received
+            )));
+        }
+
+        let received = match &message.body {
+            ExchangeBody::ContextUpdate(update) => {
+                Received::Context(Box::new(FullContext::from_wire(&update.context)?))
+            }
+            ExchangeBody::ConstitutionSelection(selection) => {
+                selection.validate(&self.agreement)?;
+                Received::Constitution(selection.clone())
+            }
+        };
+        self.last_received = message.sequence;
+        Ok(received)
+    }
+}
+
+// ── Signing ─────────────────────────────────────────────────
+
+fn check_version(version: &str) -> VcpResult<()> {
+    let major = |v: &str| v.split('.').next().map(str::to_owned);
+    if major(version) == major(EXCHANGE_VERSION) {
+        Ok(())
+    } else {
+        Err(VcpError::SessionError(format!(
+            "unsupported exchange version: {version}"
+        )))
+    }
+}
+
+/// Sign the canonical JSON of `value` minus its `signature` field.
+fn sign<T: Serialize, S: ManifestSigner + ?Sized>(value: &T, signer: &S) -> VcpResult<String> {
+    sign_manifest_with(&serde_json::to_value(value)?, signer)
+}
+
+fn check_signature<T: Serialize>(
+    value: &T,
+    signature: Option<&str>,
+    public_key: &[u8],
+) -> VcpResult<()> {
+    let signature =
+        signature.ok_or_else(|| VcpError::SignatureError("message is not signed".into()))?;
+    if verify_manifest_signature(&serde_json::to_value(value)?, public_key, signature)? {
+        Ok(())
+    } else {
+        Err(VcpError::SignatureError(
+            "signature does not match the sender's key".into(),
+        ))
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::Ed25519Signer;
+
+    fn signer(seed: u8) -> Arc<Ed25519Signer> {
+        Arc::new(Ed25519Signer::from_seed(&[seed; 32]))
+    }
+
+    fn handshake(agent: &str, versions: &[&str], seed: u8) -> Handshake {
+        Handshake::new(agent)
+            .with_vcp_versions(versions.iter().copied())
+            .with_personas([Persona::Ambassador, Persona::Mediator])
+            .signed(signer(seed).as_ref())
+            .unwrap()
+    }
+
+    fn pair() -> (Exchange, Exchange) {
+        let a = handshake("alice", &["1.0", "1.1"], 1);
+        let b = handshake("bob", &["1.1", "1.0", "2.0"], 2);
+        (
+            Exchange::new("s-1", a.clone(), b.clone(), signer(1)).unwrap(),
+            Exchange::new("s-1", b, a, signer(2)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn handshakes_agree_on_highest_common_version() {
+        let a = handshake("alice", &["1.0", "1.9", "1.10"], 1);
+        let b = handshake("bob", &["1.10", "1.9"], 2);
+        let agreement = Agreement::between(&a, &b).unwrap();
+        assert_eq!(agreement, Agreement::between(&b, &a).unwrap());
+        assert_eq!(agreement.vcp_version, "1.10");
+        assert_eq!(agreement.personas, [Persona::Ambassador, Persona::Mediator]);
+
+        let c = handshake("carol", &["2.0"], 3);
+        assert!(matches!(
+            Agreement::between(&a, &c),
+            Err(VcpError::SessionError(_))
+        ));
+    }
+
+    #[test]
+    fn handshake_signature_covers_its_fields() {
+        let mut hello = handshake("alice", &["1.0"], 1);
+        hello.verify().unwrap();
+        let json = serde_json::to_string(&hello).unwrap();
+        assert_eq!(serde_json::from_str::<Handshake>(&json).unwrap(), hello);
+
+        hello.personas.push(Persona::Nanny);
+        assert!(matches!(hello.verify(), Err(VcpError::SignatureError(_))));
+
+        let unsigned = Handshake::new("bob").with_vcp_versions(["1.0"]);
+        assert!(unsigned.verify().is_err());
+    }
+
+    #[test]
+    fn exchange_rejects_mismatched_handshakes() {
+        let a = handshake("alice", &["1.0"], 1);
+        let b = handshake("bob", &["1.0"], 2);
+        assert!(Exchange::new("s-1", a.clone(), b.clone(), signer(9)).is_err());
+        let twin = handshake("alice", &["1.0"], 2);
+        assert!(Exchange::new("s-1", a, twin, signer(1)).is_err());
+    }
+
+    #[test]
+    fn context_updates_round_trip() {
+        let (mut alice, mut bob) = pair();
+        let ctx = FullContext::from_wire("⏰🌅|📍🏡").unwrap();
+        let now = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let first = alice.send_context_at(&ctx, now).unwrap();
+        let second = alice.send_context_at(&FullContext::default(), now).unwrap();
+        assert_eq!((first.sequence, second.sequence), (1, 2));
+
+        let json = first.to_json().unwrap();
+        assert!(json.contains(r#""type":"context_update""#));
+        let decoded = ExchangeMessage::from_json(&json).unwrap();
+        assert_eq!(decoded, first);
+
+        assert_eq!(
+            bob.receive(&decoded).unwrap(),
+            Received::Context(Box::new(ctx))
+        );
+        assert_eq!(
+            bob.receive(&second).unwrap(),
+            Received::Context(Box::default())
+        );
+        assert_eq!(bob.last_received(), 2);
+    }
+
+    #[test]
+    fn replays_and_reordering_are_rejected() {
+        let (mut alice, mut bob) = pair();
+        let first = alice.send_context(&FullContext::default()).unwrap();
+        let second = alice.send_context(&FullContext::default()).unwrap();
+
+        bob.receive(&second).unwrap();
+        for stale in [&first, &second] {
+            assert!(matches!(bob.receive(stale), Err(VcpError::SessionError(_))));
+        }
+        assert_eq!(bob.last_received(), 2);
+    }
+
+    #[test]
+    fn tampered_or_misdirected_messages_are_rejected() {
+        let (mut alice, mut bob) = pair();
+        let message = alice.send_context(&FullContext::default()).unwrap();
+
+        let mut tampered = message.clone();
+        tampered.sequence = 99;
+        assert!(matches!(
+            bob.receive(&tampered),
+            Err(VcpError::SignatureError(_))
+        ));
+
+        let mut other_session = message.clone();
+        other_session.session_id = "s-2".into();
+        assert!(matches!(
+            bob.receive(&other_session),
+            Err(VcpError::SessionError(_))
+        ));
+
+        // Bob cannot accept his own message as if it came from Alice.
+        let echo = bob.send_context(&FullContext::default()).unwrap();
+        assert!(bob.receive(&echo).is_err());
+
+        // None of the rejections advanced the sequence.
+        assert_eq!(bob.last_received(), 0);
+        bob.receive(&message).unwrap();
+    }
+
+    #[test]
+    fn constitution_selection_is_checked_against_agreement() {
+        let (mut alice, mut bob) = pair();
+        let selection = ConstitutionSelection::new("family.safe.guide@1.2.0", Persona::Mediator, 4);
+        let message = alice.select_constitution(selection.clone()).unwrap();
+        assert_eq!(
+            bob.receive(&message).unwrap(),
+            Received::Constitution(selection)
+        );
+
+        let nanny = ConstitutionSelection::new("family.safe.guide", Persona::Nanny, 3);
+        assert!(matches!(
+            alice.select_constitution(nanny),
+            Err(VcpError::SessionError(_))
+        ));
+        let too_strict = ConstitutionSelection::new("family.safe.guide", Persona::Mediator, 6);
+        assert!(matches!(
+            alice.select_constitution(too_strict),
+            Err(VcpError::InvalidAdherence(6))
+        ));
+    }
+}
@@ -0,0 +1,519 @@
+//! Session lifecycle on top of the hook registry.
+//!
+//! Session-scoped hooks live in the [`HookRegistry`] until something
+//! removes them. A [`SessionManager`] owns the registry, hands out session
+//! IDs with a time-to-live, keeps each session's current [`FullContext`],
+//! and when a session ends (by [`terminate`](SessionManager::terminate) or
+//! by expiring) it:
+//!
+//! 1. runs the `on_transition` chain with a `session_end` event, so both
+//!    deployment and session hooks see the end, then
+//! 2. deregisters every hook registered for that session.
+//!
+//! The `on_transition` event payload is:
+//!
+//! ```json
+//! {"transition": "session_end", "session_id": "...", "reason": "expired"}
+//! ```
+//!
+//! | Module | Purpose |
+//! |--------|---------|
+//! | [`exchange`] | Agent-to-agent context exchange: handshake, then signed, sequenced updates |
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//! use vcp_core::context::FullContext;
+//! use vcp_core::hooks::HookRegistry;
+//! use vcp_core::session::{SessionEndReason, SessionManager};
+//!
+//! let mut sessions = SessionManager::new(HookRegistry::new()).with_ttl(Duration::from_secs(60));
+//! let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+//! let id = sessions.create_at(FullContext::default(), start);
+//! assert!(sessions.is_active_at(&id, start));
+//!
+//! let ended = sessions.reap_expired_at(start + Duration::from_secs(61));
+//! assert_eq!(ended.len(), 1);
+//! assert_eq!(ended[0].reason, SessionEndReason::Expired);
+//! assert!(sessions.get(&id).is_none());
+//! ```
+
+pub mod exchange;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{default_clock, Clock};
+use crate::context::FullContext;
+use crate::error::{VcpError, VcpResult};
+use crate::events::ContextTransitionEvent;
+use crate::hooks::{
+    ChainResult, ChainState, Hook, HookExecutor, HookInput, HookRegistry, HookScope, HookType,
+};
+use crate::ids::{default_generator, IdGenerator};
+
+/// Time-to-live used when none is configured.
+pub const DEFAULT_SESSION_TTL: Duration = Duration::from_mins(30);
+
+// ── Session ─────────────────────────────────────────────────
+
+/// One live session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    /// Session identifier (also the hook registry session key).
+    pub id: String,
+    /// When the session was created.
+    pub created_at: SystemTime,
+    /// When the session expires unless touched.
+    pub expires_at: SystemTime,
+    /// The session's current context.
+    pub context: FullContext,
+}
+
+impl Session {
+    /// Returns `true` if the session has expired at `now`.
+    pub fn is_expired_at(&self, now: SystemTime) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Why a session ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEndReason {
+    /// The TTL elapsed.
+    Expired,
+    /// [`SessionManager::terminate`] was called.
+    Terminated,
+}
+
+impl fmt::Display for SessionEndReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Expired => "expired",
+            Self::Terminated => "terminated",
+        })
+    }
+}
+
+/// Outcome of ending a session.
+#[derive(Debug)]
+pub struct SessionEnd {
+    /// The session that ended.
+    pub session_id: String,
+    /// Why it ended.
+    pub reason: SessionEndReason,
+    /// Number of session hooks that were deregistered.
+    pub hooks_removed: usize,
+    /// Result of the `on_transition` chain run for the end event.
+    pub chain: ChainResult,
+}
+
+// ── Manager ─────────────────────────────────────────────────
+
+/// Creates, tracks and ends sessions, cleaning up their hooks.
+pub struct SessionManager {
+    registry: HookRegistry,
+    sessions: HashMap<String, Session>,
+    ttl: Duration,
+    ids: Arc<dyn IdGenerator>,
+    clock: Arc<dyn Clock>,
+}
+
+impl fmt::Debug for SessionManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionManager")
+            .field("registry", &self.registry)
+            .field("session_count", &self.sessions.len())
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SessionManager {
+    /// Create a manager around `registry` with [`DEFAULT_SESSION_TTL`].
+    pub fn new(registry: HookRegistry) -> Self {
+        Self {
+            registry,
+            sessions: HashMap::new(),
+            ttl: DEFAULT_SESSION_TTL,
+            ids: default_generator(),
+            clock: default_clock(),
+        }
+    }
+
+    /// Use `ttl` for new sessions and for [`touch`](Self::touch).
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Mint session IDs from `ids` instead of random `UUIDv7`s.
+    #[must_use]
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Read "now" from `clock` in the methods without an `_at` variant.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The underlying hook registry.
+    pub fn registry(&self) -> &HookRegistry {
+        &self.registry
+    }
+
+    /// Mutable access to the registry, e.g. for deployment hooks.
+    pub fn registry_mut(&mut self) -> &mut HookRegistry {
+        &mut self.registry
+    }
+
+    /// An executor over the managed registry.
+    pub fn executor(&self) -> HookExecutor<'_> {
+        HookExecutor::new(&self.registry)
+    }
+
+    /// Start a session now, returning its ID.
+    pub fn create(&mut self, context: FullContext) -> String {
+        self.create_at(context, self.clock.system_time())
+    }
+
+    /// Start a session at `now`.
+    pub fn create_at(&mut self, context: FullContext, now: SystemTime) -> String {
+        let id = self.ids.next_id();
+        self.sessions.insert(
+            id.clone(),
+            Session {
+                id: id.clone(),
+                created_at: now,
+                expires_at: now + self.ttl,
+                context,
+            },
+        );
+        id
+    }
+
+    /// Look up a session, including one that has expired but not yet
+    /// been reaped.
+    pub fn get(&self, session_id: &str) -> Option<&Session> {
+        self.sessions.get(session_id)
+    }
+
+    /// Returns `true` if the session exists and has not expired at `now`.
+    pub fn is_active_at(&self, session_id: &str, now: SystemTime) -> bool {
+        self.get(session_id).is_some_and(|s| !s.is_expired_at(now))
+    }
+
+    /// Number of tracked sessions (including expired, unreaped ones).
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Returns `true` if no sessions are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Iterate over tracked sessions in no particular order.
+    pub fn sessions(&self) -> impl Iterator<Item = &Session> {
+        self.sessions.values()
+    }
+
+    /// Push a session's expiry out to one TTL from now.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SessionError`] if the session is unknown or has
+    /// already expired.
+    pub fn touch(&mut self, session_id: &str) -> VcpResult<()> {
+        self.touch_at(session_id, self.clock.system_time())
+    }
+
+    /// [`touch`](Self::touch) at an explicit time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SessionError`] if the session is unknown or has
+    /// already expired at `now`.
+    pub fn touch_at(&mut self, session_id: &str, now: SystemTime) -> VcpResult<()> {
+        let ttl = self.ttl;
+        let session = self.active_mut(session_id, now)?;
+        session.expires_at = now + ttl;
+        Ok(())
+    }
+
+    /// Replace a session's context, returning what changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SessionError`] if the session is unknown or has
+    /// expired.
+    pub fn update_context(
+        &mut self,
+        session_id: &str,
+        context: FullContext,
+    ) -> VcpResult<ContextTransitionEvent> {
+        let session = self.active_mut(session_id, self.clock.system_time())?;
+        let event =
+            ContextTransitionEvent::between(Some(session.id.clone()), &session.context, &context);
+        session.context = context;
+        Ok(event)
+    }
+
+    /// Register a hook scoped to a live session.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SessionError`] if the session is unknown or has
+    /// expired, or [`VcpError::HookError`] if the registry rejects the hook.
+    pub fn register_hook(&mut self, session_id: &str, hook: Hook) -> VcpResult<()> {
+        self.active_mut(session_id, self.clock.system_time())?;
+        self.registry
+            .register(hook, HookScope::Session, Some(session_id))
+    }
+
+    /// End a session now, running the end event and removing its hooks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SessionError`] if the session is unknown.
+    pub fn terminate(&mut self, session_id: &str) -> VcpResult<SessionEnd> {
+        let session = self
+            .sessions
+            .remove(session_id)
+            .ok_or_else(|| unknown(session_id))?;
+        Ok(self.end(session, SessionEndReason::Terminated))
+    }
+
+    /// End every session whose TTL has elapsed.
+    pub fn reap_expired(&mut self) -> Vec<SessionEnd> {
+        self.reap_expired_at(self.clock.system_time())
+    }
+
+    /// End every session expired at `now`, oldest expiry first.
+    pub fn reap_expired_at(&mut self, now: SystemTime) -> Vec<SessionEnd> {
+        let mut expired: Vec<Session> = Vec::new();
+        self.sessions.retain(|_, session| {
+            if session.is_expired_at(now) {
+                expired.push(session.clone());
+                false
+            } else {
+                true
+            }
+        });
+        expired.sort_by(|a, b| a.expires_at.cmp(&b.expires_at).then(a.id.cmp(&b.id)));
+        expired
+            .into_iter()
+            .map(|session| self.end(session, SessionEndReason::Expired))
+            .collect()
+    }
+
+    fn active_mut(&mut self, session_id: &str, now: SystemTime) -> VcpResult<&mut Session> {
+        match self.sessions.get_mut(session_id) {
+            Some(session) if !session.is_expired_at(now) => Ok(session),
+            Some(_) => Err(VcpError::SessionError(format!(
+                "session '{session_id}' has expired"
+            ))),
+            None => Err(unknown(session_id)),
+        }
+    }
+
+    fn end(&mut self, session: Session, reason: SessionEndReason) -> SessionEnd {
+        let input = HookInput {
+            context: serde_json::to_value(&session.context).unwrap_or_default(),
+            constitution: serde_json::Value::Null,
+            event: serde_json::json!({
+                "transition": "session_end",
+                "session_id": session.id,
+                "reason": reason,
+            }),
+            session_id: session.id.clone(),
+            chain_state: ChainState::new(),
+        };
+        let chain = self
+            .executor()
+            .execute(HookType::OnTransition, &session.id, input);
+        let hooks_removed = self.registry.clear_session(&session.id);
+        SessionEnd {
+            session_id: session.id,
+            reason,
+            hooks_removed,
+            chain,
+        }
+    }
+}
+
+fn unknown(session_id: &str) -> VcpError {
+    VcpError::SessionError(format!("unknown session '{session_id}'"))
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::hooks::{HookAction, HookHandler, HookResult};
+    use crate::ids::SeededIdGenerator;
+    use chrono::DateTime;
+    use std::sync::Mutex;
+
+    /// Records every event it sees.
+    struct Recorder(Arc<Mutex<Vec<serde_json::Value>>>);
+
+    impl HookHandler for Recorder {
+        fn execute(&self, input: &HookInput) -> HookResult {
+            self.0.lock().unwrap().push(input.event.clone());
+            HookResult {
+                action: HookAction::Continue,
+                annotations: HashMap::new(),
+                state_updates: ChainState::new(),
+                duration: Duration::ZERO,
+            }
+        }
+    }
+
+    fn hook(name: &str, hook_type: HookType, seen: &Arc<Mutex<Vec<serde_json::Value>>>) -> Hook {
+        Hook {
+            name: name.into(),
+            hook_type,
+            priority: 50,
+            handler: Box::new(Recorder(Arc::clone(seen))),
+            timeout: Duration::from_secs(1),
+            enabled: true,
+            description: String::new(),
+        }
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn manager() -> SessionManager {
+        SessionManager::new(HookRegistry::new())
+            .with_ttl(Duration::from_secs(10))
+            .with_id_generator(Arc::new(SeededIdGenerator::new(1)))
+    }
+
+    #[test]
+    fn terminate_runs_end_event_then_removes_hooks() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut sessions = manager();
+        let id = sessions.create(FullContext::default());
+        sessions
+            .register_hook(&id, hook("watcher", HookType::OnTransition, &seen))
+            .unwrap();
+        sessions
+            .register_hook(&id, hook("gate", HookType::PreInject, &seen))
+            .unwrap();
+        assert_eq!(sessions.registry().session_hook_count(&id), 2);
+
+        let end = sessions.terminate(&id).unwrap();
+        assert_eq!(end.reason, SessionEndReason::Terminated);
+        assert_eq!(end.hooks_removed, 2);
+        assert!(end.chain.completed);
+        assert_eq!(sessions.registry().session_hook_count(&id), 0);
+
+        let events = seen.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["transition"], "session_end");
+        assert_eq!(events[0]["reason"], "terminated");
+        assert_eq!(events[0]["session_id"], id.as_str());
+    }
+
+    #[test]
+    fn expiry_and_touch() {
+        let mut sessions = manager();
+        let a = sessions.create_at(FullContext::default(), at(100));
+        let b = sessions.create_at(FullContext::default(), at(105));
+
+        sessions.touch_at(&a, at(108)).unwrap();
+        assert!(sessions.is_active_at(&a, at(117)));
+        assert!(!sessions.is_active_at(&b, at(115)));
+        assert!(matches!(
+            sessions.touch_at(&b, at(115)),
+            Err(VcpError::SessionError(_))
+        ));
+
+        let ended = sessions.reap_expired_at(at(115));
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].session_id, b);
+        assert_eq!(sessions.len(), 1);
+        assert!(sessions
+            .reap_expired_at(at(118))
+            .iter()
+            .all(|e| e.session_id == a));
+        assert!(sessions.is_empty());
+    }
+
+    #[test]
+    fn clock_drives_the_methods_without_at() {
+        let clock = Arc::new(MockClock::new(DateTime::from(at(100))));
+        let mut sessions = manager().with_clock(clock.clone());
+        let id = sessions.create(FullContext::default());
+        assert!(sessions.is_active_at(&id, at(109)));
+
+        clock.advance(chrono::Duration::seconds(8));
+        sessions.touch(&id).unwrap();
+        assert!(sessions.reap_expired().is_empty());
+
+        clock.advance(chrono::Duration::seconds(11));
+        assert_eq!(sessions.reap_expired().len(), 1);
+    }
+
+    #[test]
+    fn deployment_hooks_see_expiry_and_survive_it() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut sessions = manager();
+        sessions
+            .registry_mut()
+            .register(
+                hook("audit", HookType::OnTransition, &seen),
+                HookScope::Deployment,
+                None,
+            )
+            .unwrap();
+        sessions.create_at(FullContext::default(), at(0));
+        let ended = sessions.reap_expired_at(at(60));
+
+        assert_eq!(ended[0].hooks_removed, 0);
+        assert_eq!(seen.lock().unwrap()[0]["reason"], "expired");
+        assert_eq!(
+            sessions
+                .registry()
+                .get_chain(HookType::OnTransition, "x")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn context_updates_and_unknown_sessions() {
+        let mut sessions = manager();
+        let id = sessions.create(FullContext::default());
+        let next = FullContext::from_wire("ctx1;time=night").unwrap();
+
+        let event = sessions.update_context(&id, next.clone()).unwrap();
+        assert_eq!(event.changed, vec!["time"]);
+        assert_eq!(sessions.get(&id).unwrap().context, next);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        assert!(sessions
+            .register_hook("nope", hook("h", HookType::PreInject, &seen))
+            .is_err());
+        assert!(sessions.terminate("nope").is_err());
+        assert!(sessions
+            .update_context("nope", FullContext::default())
+            .is_err());
+    }
+}
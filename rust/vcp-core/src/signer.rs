@@ -0,0 +1,260 @@
+//! Pluggable manifest signers for keys held outside the process.
+//!
+//! [`sign_manifest`](crate::transport::sign_manifest) takes a raw seed,
+//! which rules out keys in a cloud KMS, an HSM or a hardware token. Those
+//! implement [`ManifestSigner`] (or [`AsyncManifestSigner`] when signing
+//! is a network call) and are passed to [`sign_manifest_with`] /
+//! [`sign_manifest_async`]. Both produce exactly what `sign_manifest`
+//! does: a base64 Ed25519 signature over the canonical manifest.
+//!
+//! | Signer | Key location |
+//! |--------|--------------|
+//! | [`Ed25519Signer`] | In memory (the default used by `sign_manifest`) |
+//! | [`KeyPair`] | In memory, from the [`keys`](crate::keys) module |
+//! | Your type | Anywhere that can produce an Ed25519 signature |
+//!
+//! Every [`ManifestSigner`] that is `Sync` is also an
+//! [`AsyncManifestSigner`], so async callers accept both.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::error::VcpResult;
+//! use vcp_core::signer::{sign_manifest_with, Ed25519Signer, ManifestSigner};
+//! use vcp_core::transport::verify_manifest_signature;
+//!
+//! /// Stand-in for a remote signer that only exposes "sign these bytes".
+//! struct Remote(Ed25519Signer);
+//!
+//! impl ManifestSigner for Remote {
+//!     fn public_key(&self) -> VcpResult<[u8; 32]> {
+//!         self.0.public_key()
+//!     }
+//!     fn sign_bytes(&self, message: &[u8]) -> VcpResult<[u8; 64]> {
+//!         self.0.sign_bytes(message)
+//!     }
+//! }
+//!
+//! let remote = Remote(Ed25519Signer::from_seed(&[9u8; 32]));
+//! let manifest = serde_json::json!({"bundle": {"id": "b"}});
+//! let sig = sign_manifest_with(&manifest, &remote).unwrap();
+//! let public = remote.public_key().unwrap();
+//! assert!(verify_manifest_signature(&manifest, &public, &sig).unwrap());
+//! ```
+
+use std::future::Future;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use ed25519_dalek::{Signer, SigningKey};
+
+use crate::error::{VcpError, VcpResult};
+use crate::keys::KeyPair;
+use crate::transport::canonicalize_manifest;
+
+// ── Traits ──────────────────────────────────────────────────
+
+/// Produces Ed25519 signatures over canonical manifest bytes.
+///
+/// Implementations only see the bytes to sign; canonicalization and
+/// encoding stay in [`sign_manifest_with`].
+pub trait ManifestSigner {
+    /// The 32-byte Ed25519 public key matching this signer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] if the key cannot be read.
+    fn public_key(&self) -> VcpResult<[u8; 32]>;
+
+    /// Sign `message`, returning the 64-byte Ed25519 signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] if the backend refuses or
+    /// fails to sign.
+    fn sign_bytes(&self, message: &[u8]) -> VcpResult<[u8; 64]>;
+}
+
+/// Async counterpart of [`ManifestSigner`] for network-backed keys.
+///
+/// Not object-safe; take it as a generic parameter. Method names carry an
+/// `_async` suffix so both traits can be in scope at once.
+pub trait AsyncManifestSigner {
+    /// The 32-byte Ed25519 public key matching this signer.
+    fn public_key_async(&self) -> impl Future<Output = VcpResult<[u8; 32]>> + Send;
+
+    /// Sign `message`, returning the 64-byte Ed25519 signature.
+    fn sign_bytes_async(&self, message: &[u8]) -> impl Future<Output = VcpResult<[u8; 64]>> + Send;
+}
+
+impl<S: ManifestSigner + Sync + ?Sized> AsyncManifestSigner for S {
+    fn public_key_async(&self) -> impl Future<Output = VcpResult<[u8; 32]>> + Send {
+        std::future::ready(self.public_key())
+    }
+
+    fn sign_bytes_async(&self, message: &[u8]) -> impl Future<Output = VcpResult<[u8; 64]>> + Send {
+        std::future::ready(self.sign_bytes(message))
+    }
+}
+
+// ── Signing ─────────────────────────────────────────────────
+
+/// Sign a manifest through `signer`.
+///
+/// Returns the same base64 string as
+/// [`sign_manifest`](crate::transport::sign_manifest).
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if the manifest is not an object, or
+/// whatever error the signer reports.
+pub fn sign_manifest_with<S: ManifestSigner + ?Sized>(
+    manifest: &serde_json::Value,
+    signer: &S,
+) -> VcpResult<String> {
+    let canonical = canonicalize_manifest(manifest)?;
+    Ok(BASE64.encode(signer.sign_bytes(&canonical)?))
+}
+
+/// Sign a manifest through an async `signer`.
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] if the manifest is not an object, or
+/// whatever error the signer reports.
+pub async fn sign_manifest_async<S: AsyncManifestSigner + ?Sized>(
+    manifest: &serde_json::Value,
+    signer: &S,
+) -> VcpResult<String> {
+    let canonical = canonicalize_manifest(manifest)?;
+    Ok(BASE64.encode(signer.sign_bytes_async(&canonical).await?))
+}
+
+// ── In-memory signer ────────────────────────────────────────
+
+/// Ed25519 key held in process memory.
+pub struct Ed25519Signer {
+    key: SigningKey,
+}
+
+impl Ed25519Signer {
+    /// Wrap a 32-byte seed.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        Self {
+            key: SigningKey::from_bytes(seed),
+        }
+    }
+
+    /// Wrap a seed slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] if `secret_key` is not exactly
+    /// 32 bytes.
+    pub fn from_secret_bytes(secret_key: &[u8]) -> VcpResult<Self> {
+        let seed: [u8; 32] = secret_key.try_into().map_err(|_| {
+            VcpError::SignatureError(format!(
+                "secret key must be exactly 32 bytes, got {}",
+                secret_key.len()
+            ))
+        })?;
+        Ok(Self::from_seed(&seed))
+    }
+}
+
+impl std::fmt::Debug for Ed25519Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ed25519Signer").finish_non_exhaustive()
+    }
+}
+
+impl ManifestSigner for Ed25519Signer {
+    fn public_key(&self) -> VcpResult<[u8; 32]> {
+        Ok(self.key.verifying_key().to_bytes())
+    }
+
+    fn sign_bytes(&self, message: &[u8]) -> VcpResult<[u8; 64]> {
+        Ok(self.key.sign(message).to_bytes())
+    }
+}
+
+impl ManifestSigner for KeyPair {
+    fn public_key(&self) -> VcpResult<[u8; 32]> {
+        Ok(self.public_bytes())
+    }
+
+    fn sign_bytes(&self, message: &[u8]) -> VcpResult<[u8; 64]> {
+        Ed25519Signer::from_seed(&self.secret_bytes()).sign_bytes(message)
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::{sign_manifest, verify_manifest_signature};
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Drive a future that never actually waits.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(out) = future.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    fn manifest() -> serde_json::Value {
+        serde_json::json!({"bundle": {"id": "b", "content_hash": "sha256:00"}, "weight": 0.5})
+    }
+
+    /// A backend that is always unavailable.
+    struct Offline;
+
+    impl ManifestSigner for Offline {
+        fn public_key(&self) -> VcpResult<[u8; 32]> {
+            Err(VcpError::SignatureError("kms unavailable".into()))
+        }
+
+        fn sign_bytes(&self, _message: &[u8]) -> VcpResult<[u8; 64]> {
+            Err(VcpError::SignatureError("kms unavailable".into()))
+        }
+    }
+
+    #[test]
+    fn default_signer_matches_sign_manifest() {
+        let seed = [5u8; 32];
+        let via_trait = sign_manifest_with(&manifest(), &Ed25519Signer::from_seed(&seed)).unwrap();
+        assert_eq!(via_trait, sign_manifest(&manifest(), &seed).unwrap());
+    }
+
+    #[test]
+    fn keypair_is_a_signer() {
+        let key = KeyPair::from_seed(&[6u8; 32]);
+        let signer: &dyn ManifestSigner = &key;
+        let sig = sign_manifest_with(&manifest(), signer).unwrap();
+        assert!(verify_manifest_signature(&manifest(), &key.public_bytes(), &sig).unwrap());
+    }
+
+    #[test]
+    fn async_signing_matches_sync() {
+        let signer = Ed25519Signer::from_seed(&[7u8; 32]);
+        let sync = sign_manifest_with(&manifest(), &signer).unwrap();
+        let async_sig = block_on(sign_manifest_async(&manifest(), &signer)).unwrap();
+        assert_eq!(async_sig, sync);
+    }
+
+    #[test]
+    fn backend_errors_propagate() {
+        assert!(matches!(
+            sign_manifest_with(&manifest(), &Offline),
+            Err(VcpError::SignatureError(_))
+        ));
+        assert!(block_on(sign_manifest_async(&manifest(), &Offline)).is_err());
+        assert!(Ed25519Signer::from_secret_bytes(&[0u8; 16]).is_err());
+    }
+}
@@ -26,12 +26,18 @@
 //!
 //! Wire format example (full 13-dim + VEP-0004):
 //! `⏰🌅|📍🏢|👥👔|🎭💼|🧍✋|↔️🤏|🪢colleague:professional|🎩💼`
+//!
+//! [`SituationalContext::infer_defaults`] fills the time dimension from the
+//! host clock in its local timezone. On `wasm32` it needs the `wasm-clock`
+//! feature, which reads the browser's `Date`.
 
 use std::fmt;
 
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Weekday};
 use serde::{Deserialize, Serialize};
 
-use crate::error::{VcpError, VcpResult};
+use crate::context::{ascii_escape, ascii_unescape};
+use crate::error::{Span, VcpError, VcpResult};
 
 /// The thirteen situational context dimensions (VCP v3.2, incl. VEP-0004).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -162,6 +168,117 @@ impl SituationalDimension {
             Self::Formality,
         ]
     }
+
+    /// Standard tag vocabulary as `(emoji, name)` pairs, matching the
+    /// Python SDK. Empty for the free-form [`Self::Relationship`].
+    pub fn vocabulary(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Time => &[
+                ("\u{1F305}", "morning"),
+                ("\u{2600}\u{FE0F}", "midday"),
+                ("\u{1F306}", "evening"),
+                ("\u{1F319}", "night"),
+                ("\u{1F4C5}", "weekday"),
+                ("\u{1F389}", "weekend"),
+            ],
+            Self::Space => &[
+                ("\u{1F3E1}", "home"),
+                ("\u{1F3E2}", "office"),
+                ("\u{1F3EB}", "school"),
+                ("\u{1F3E5}", "hospital"),
+                ("\u{1F697}", "transit"),
+            ],
+            Self::Company => &[
+                ("\u{1F464}", "alone"),
+                ("\u{1F476}", "children"),
+                ("\u{1F454}", "colleagues"),
+                ("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}", "family"),
+                ("\u{1F465}", "strangers"),
+            ],
+            Self::Culture => &[
+                ("\u{1F507}", "high_context"),
+                ("\u{1F4E2}", "low_context"),
+                ("\u{1F3A9}", "formal"),
+                ("\u{1F60E}", "casual"),
+                ("\u{1F310}", "mixed"),
+            ],
+            Self::Occasion => &[
+                ("\u{2796}", "normal"),
+                ("\u{1F382}", "celebration"),
+                ("\u{1F622}", "mourning"),
+                ("\u{1F6A8}", "emergency"),
+                ("\u{1F4BC}", "business"),
+            ],
+            Self::Environment => &[
+                ("\u{2600}\u{FE0F}", "comfortable"),
+                ("\u{1F975}", "hot"),
+                ("\u{1F976}", "cold"),
+                ("\u{1F507}", "quiet"),
+                ("\u{1F50A}", "noisy"),
+            ],
+            Self::Agency => &[
+                ("\u{1F451}", "leader"),
+                ("\u{1F91D}", "peer"),
+                ("\u{1F4CB}", "subordinate"),
+                ("\u{1F510}", "limited"),
+            ],
+            Self::Constraints => &[
+                ("\u{25CB}", "minimal"),
+                ("\u{2696}\u{FE0F}", "legal"),
+                ("\u{1F4B8}", "economic"),
+                ("\u{23F1}\u{FE0F}", "time"),
+            ],
+            Self::SystemContext => &[
+                ("\u{1F7E2}", "online"),
+                ("\u{1F7E1}", "degraded"),
+                ("\u{1F534}", "offline"),
+                ("\u{1F512}", "sandboxed"),
+                ("\u{1F9EA}", "testing"),
+            ],
+            Self::Embodiment => &[
+                ("\u{1FA91}", "stationary"),
+                ("\u{1F6B6}", "navigating"),
+                ("\u{270B}", "manipulating"),
+                ("\u{1F4E6}", "carrying"),
+                ("\u{1F6D1}", "emergency_stop"),
+            ],
+            Self::Proximity => &[
+                ("\u{1F310}", "distant"),
+                ("\u{1F3E0}", "same_room"),
+                ("\u{1F463}", "nearby"),
+                ("\u{1F90F}", "close"),
+                ("\u{1F446}", "contact"),
+            ],
+            Self::Relationship => &[],
+            Self::Formality => &[
+                ("\u{1F60E}", "casual"),
+                ("\u{1F4BC}", "professional"),
+                ("\u{1F393}", "formal"),
+                ("\u{1F3DB}\u{FE0F}", "ceremonial"),
+            ],
+        }
+    }
+
+    /// Name of a vocabulary emoji tag (e.g. `🌅` → `morning`).
+    pub fn tag_name(self, emoji: &str) -> Option<&'static str> {
+        self.vocabulary()
+            .iter()
+            .find(|(e, _)| *e == emoji)
+            .map(|(_, name)| *name)
+    }
+
+    /// Emoji for a vocabulary tag name (e.g. `morning` → `🌅`).
+    pub fn tag_emoji(self, name: &str) -> Option<&'static str> {
+        self.vocabulary()
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(emoji, _)| *emoji)
+    }
+
+    /// Parse the snake-case label produced by `Display` (e.g. `system_context`).
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|d| d.to_string() == label)
+    }
 }
 
 impl fmt::Display for SituationalDimension {
@@ -308,7 +425,10 @@ impl SituationalContext {
                 continue;
             }
 
-            let (dim, rest) = split_situational_symbol(segment)?;
+            let (dim, rest) = split_situational_symbol(segment).map_err(|e| {
+                e.at(Span::of(wire, segment))
+                    .expecting(&["situational dimension symbol"])
+            })?;
             let tags = if rest.is_empty() {
                 Vec::new()
             } else {
@@ -339,6 +459,65 @@ impl SituationalContext {
         Ok(ctx)
     }
 
+    /// Encode to the ASCII-safe body of the `ctx1` wire format.
+    ///
+    /// Each dimension becomes `<label>=<tag>[,<tag>...]`, joined by `;`.
+    /// Vocabulary emoji are written by name (`time=morning`); anything
+    /// else is percent-encoded, so the output is plain ASCII.
+    pub fn to_ascii_wire(&self) -> String {
+        let mut parts = Vec::new();
+        for &dim in SituationalDimension::all() {
+            let Some(tags) = self.get(dim).filter(|t| !t.is_empty()) else {
+                continue;
+            };
+            let tags: Vec<String> = tags
+                .iter()
+                .map(|tag| match dim.tag_name(tag) {
+                    Some(name) => name.to_string(),
+                    None => ascii_escape(tag, &[':']),
+                })
+                .collect();
+            parts.push(format!("{dim}={}", tags.join(",")));
+        }
+        parts.join(";")
+    }
+
+    /// Parse the body produced by [`to_ascii_wire`](Self::to_ascii_wire).
+    ///
+    /// Known tag names are mapped back to their emoji; other values are
+    /// kept as written (after percent-decoding).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for an unknown dimension label, a
+    /// segment without `=`, or a bad percent escape.
+    pub fn from_ascii_wire(wire: &str) -> VcpResult<Self> {
+        let mut ctx = SituationalContext::default();
+        for segment in wire.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            let (label, values) = segment.split_once('=').ok_or_else(|| {
+                VcpError::ParseError(format!("expected <dimension>=<tags>, got: {segment}"))
+                    .at(Span::of(wire, segment))
+                    .expecting(&["<dimension>=<tags>"])
+            })?;
+            let dim = SituationalDimension::from_label(label).ok_or_else(|| {
+                VcpError::ParseError(format!("unknown situational dimension: {label}"))
+                    .at(Span::of(wire, label))
+                    .expecting(&["situational dimension label"])
+            })?;
+            let tags = values
+                .split(',')
+                .filter(|v| !v.is_empty())
+                .map(|v| match dim.tag_emoji(v) {
+                    Some(emoji) => Ok(emoji.to_string()),
+                    None => ascii_unescape(v),
+                })
+                .collect::<VcpResult<Vec<_>>>()
+                .map_err(|e| e.at(Span::of(wire, values)))?;
+            ctx.set(dim, tags);
+        }
+        Ok(ctx)
+    }
+
     /// Get tags for a specific dimension.
     pub fn get(&self, dim: SituationalDimension) -> Option<&Vec<String>> {
         match dim {
@@ -376,6 +555,34 @@ impl SituationalContext {
             SituationalDimension::Formality => self.formality = Some(tags),
         }
     }
+
+    /// Clear a dimension, returning its tags.
+    pub fn remove(&mut self, dim: SituationalDimension) -> Option<Vec<String>> {
+        match dim {
+            SituationalDimension::Time => self.time.take(),
+            SituationalDimension::Space => self.space.take(),
+            SituationalDimension::Company => self.company.take(),
+            SituationalDimension::Culture => self.culture.take(),
+            SituationalDimension::Occasion => self.occasion.take(),
+            SituationalDimension::Environment => self.environment.take(),
+            SituationalDimension::Agency => self.agency.take(),
+            SituationalDimension::Constraints => self.constraints.take(),
+            SituationalDimension::SystemContext => self.system_context.take(),
+            SituationalDimension::Embodiment => self.embodiment.take(),
+            SituationalDimension::Proximity => self.proximity.take(),
+            SituationalDimension::Relationship => self.relationship.take(),
+            SituationalDimension::Formality => self.formality.take(),
+        }
+    }
+
+    /// Overlay `other`: every dimension it sets replaces the one here.
+    pub fn merge(&mut self, other: &SituationalContext) {
+        for &dim in SituationalDimension::all() {
+            if let Some(tags) = other.get(dim) {
+                self.set(dim, tags.clone());
+            }
+        }
+    }
 }
 
 impl fmt::Display for SituationalContext {
@@ -434,9 +641,58 @@ fn split_situational_symbol(s: &str) -> VcpResult<(SituationalDimension, &str)>
     )))
 }
 
+// ── Clock defaults ──────────────────────────────────────────
+
+impl SituationalContext {
+    /// Fill unset dimensions that can be read off the host clock.
+    ///
+    /// Currently that is `time`: a time-of-day bucket and weekday/weekend,
+    /// both in the host's local timezone. Dimensions that are already set
+    /// are left alone.
+    #[cfg(any(not(target_arch = "wasm32"), feature = "wasm-clock"))]
+    #[must_use]
+    pub fn infer_defaults(self) -> Self {
+        self.infer_defaults_at(&chrono::Local::now())
+    }
+
+    /// [`infer_defaults`](Self::infer_defaults) for an explicit instant;
+    /// the instant's timezone decides the local hour and day.
+    #[must_use]
+    pub fn infer_defaults_at<Tz: TimeZone>(mut self, at: &DateTime<Tz>) -> Self {
+        if self.time.is_none() {
+            self.time = Some(time_tags(at));
+        }
+        self
+    }
+}
+
+/// Time-of-day bucket plus weekday/weekend tag for `at`.
+///
+/// Buckets follow the context spec: morning 06-12, midday 12-17,
+/// evening 17-21, night 21-06.
+pub fn time_tags<Tz: TimeZone>(at: &DateTime<Tz>) -> Vec<String> {
+    let bucket = match at.hour() {
+        6..=11 => "morning",
+        12..=16 => "midday",
+        17..=20 => "evening",
+        _ => "night",
+    };
+    let day = if matches!(at.weekday(), Weekday::Sat | Weekday::Sun) {
+        "weekend"
+    } else {
+        "weekday"
+    };
+    [bucket, day]
+        .iter()
+        .filter_map(|name| SituationalDimension::Time.tag_emoji(name))
+        .map(str::to_string)
+        .collect()
+}
+
 // ── Tests ───────────────────────────────────────────────────
 
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
@@ -612,4 +868,30 @@ mod tests {
                         |\u{1F3A9}\u{1F4BC}";
         assert_eq!(ctx.to_wire(), expected);
     }
+
+    #[test]
+    fn clock_defaults_use_local_hour_and_day() {
+        use chrono::{FixedOffset, Utc};
+
+        // 2026-10-16 22:30 UTC is a Friday night in London, but Saturday
+        // morning in Auckland (+13:00).
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 22, 30, 0).unwrap();
+        assert_eq!(time_tags(&at), vec!["\u{1F319}", "\u{1F4C5}"]);
+
+        let auckland = at.with_timezone(&FixedOffset::east_opt(13 * 3600).unwrap());
+        let ctx = SituationalContext::default().infer_defaults_at(&auckland);
+        assert_eq!(ctx.time, Some(vec!["\u{1F305}".into(), "\u{1F389}".into()]));
+    }
+
+    #[test]
+    fn clock_defaults_keep_explicit_time() {
+        let mut ctx = SituationalContext::default();
+        ctx.time = Some(vec!["\u{1F306}".into()]);
+        let ctx = ctx.infer_defaults();
+        assert_eq!(ctx.time, Some(vec!["\u{1F306}".into()]));
+        assert!(SituationalContext::default()
+            .infer_defaults()
+            .time
+            .is_some());
+    }
 }
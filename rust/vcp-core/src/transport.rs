@@ -10,20 +10,45 @@
 //! 5. Reject control characters (except `\n`, `\t`)
 //! 6. UTF-8 encode without BOM
 //!
+//! [`canonicalize_with_report`] also lists what steps 1-4 changed, by
+//! input line, for authors whose local hash differs from the manifest's.
+//! Files saved as UTF-16 or with a BOM go through [`decode_content`]
+//! first; the hash is always of the UTF-8 text.
+//!
 //! **Manifest canonicalization (RFC 8785 JCS):**
-//! - Sort object keys lexicographically
+//! - Sort object keys by UTF-16 code units
 //! - No whitespace between tokens
-//! - UTF-8 encoding
+//! - Numbers in ECMAScript `Number.prototype.toString` form
+//! - Minimal string escaping, UTF-8 encoding
+//!
+//! [`Manifest`] gives the manifest sections typed fields for callers that
+//! read them; signing and signature checks stay on the raw JSON value.
+//!
+//! Multi-file bundles list their documents in `bundle.files`;
+//! [`BundleContents`] verifies them and selects a subset by role.
+//!
+//! [`BundleArchive`] packs a manifest, its content and any attachments
+//! into one `.vcpb` file, checking every member against the manifest.
+//!
+//! [`to_jws`] and [`from_jws`] carry a manifest as a compact JWS (`EdDSA`)
+//! for infrastructure that only passes JWTs.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Write as _};
+use std::io::{Read, Write};
 
-use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64_URL};
 use base64::Engine as _;
-use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Verifier, VerifyingKey};
 use sha2::{Digest, Sha256};
 use unicode_normalization::UnicodeNormalization;
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::{VcpError, VcpResult, VerificationCode};
+use crate::identity::TokenBinding;
+use crate::signer::{sign_manifest_with, Ed25519Signer, ManifestSigner};
 
 // ── Content canonicalization ────────────────────────────────
 
@@ -43,44 +68,22 @@ const FORBIDDEN_CODEPOINTS: &[char] = &[
 /// Returns [`VcpError::ParseError`] if the content contains illegal
 /// control characters or forbidden Unicode codepoints.
 pub fn canonicalize_content(text: &str) -> VcpResult<Vec<u8>> {
-    // 1. Unicode NFC normalization.
-    let text: String = text.nfc().collect();
-
-    // 2. Line ending normalization.
-    let text = text.replace("\r\n", "\n").replace('\r', "\n");
-
-    // 3. Strip trailing whitespace from each line.
-    let lines: Vec<&str> = text
-        .split('\n')
-        .map(|l| l.trim_end_matches([' ', '\t']))
-        .collect();
-
-    // 4. Remove trailing empty lines, ensure single trailing newline.
-    let mut lines = lines;
-    while lines.last().is_some_and(|l| l.is_empty()) {
-        lines.pop();
-    }
-    let mut text = lines.join("\n");
-    text.push('\n');
-
-    // 5. Reject control characters (except \n, \t).
-    for (i, ch) in text.char_indices() {
-        if ch.is_control() && ch != '\n' && ch != '\t' {
-            return Err(VcpError::ParseError(format!(
-                "illegal control character at position {i}: U+{:04X}",
-                ch as u32
-            )));
-        }
-        if FORBIDDEN_CODEPOINTS.contains(&ch) {
-            return Err(VcpError::ParseError(format!(
-                "forbidden Unicode character at position {i}: U+{:04X}",
-                ch as u32
-            )));
-        }
-    }
+    canonicalize_to_writer(text, Vec::with_capacity(text.len() + 1))
+}
 
-    // 6. UTF-8 encode without BOM.
-    Ok(text.into_bytes())
+/// Canonicalize content straight into `writer`, returning the writer.
+///
+/// Produces exactly the bytes of [`canonicalize_content`] without
+/// building intermediate strings.
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] for illegal characters and
+/// [`VcpError::IoError`] if the writer fails.
+pub fn canonicalize_to_writer<W: Write>(text: &str, writer: W) -> VcpResult<W> {
+    let mut canon = ContentCanonicalizer::new(writer);
+    canon.update(text)?;
+    canon.finish()
 }
 
 /// Compute `sha256:<hex>` hash of canonical content.
@@ -89,11 +92,9 @@ pub fn canonicalize_content(text: &str) -> VcpResult<Vec<u8>> {
 ///
 /// Returns [`VcpError::ParseError`] if the content fails canonicalization.
 pub fn compute_content_hash(content: &str) -> VcpResult<String> {
-    let canonical = canonicalize_content(content)?;
-    let mut hasher = Sha256::new();
-    hasher.update(&canonical);
-    let digest = hasher.finalize();
-    Ok(format!("sha256:{digest:x}"))
+    let mut hasher = ContentHasher::new();
+    hasher.update(content)?;
+    hasher.finalize()
 }
 
 /// Verify that content matches an expected hash string.
@@ -106,45 +107,880 @@ pub fn verify_content_hash(content: &str, expected: &str) -> VcpResult<bool> {
     Ok(computed == expected)
 }
 
+// ── Canonicalization report ─────────────────────────────────
+
+/// A kind of change canonicalization makes to content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentChangeKind {
+    /// Text rewritten by Unicode NFC normalization; counts lines.
+    UnicodeNormalized,
+    /// `\r\n` line endings converted to `\n`; counts line endings.
+    CrlfLineEnding,
+    /// Lone `\r` line endings converted to `\n`; counts line endings.
+    CrLineEnding,
+    /// Spaces and tabs removed from line ends; counts characters.
+    TrailingWhitespace,
+    /// Empty lines removed from the end of the document; counts lines.
+    TrailingBlankLine,
+    /// A final `\n` appended; counts 1.
+    MissingFinalNewline,
+}
+
+impl fmt::Display for ContentChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnicodeNormalized => "Unicode NFC normalization",
+            Self::CrlfLineEnding => "CRLF line endings",
+            Self::CrLineEnding => "CR line endings",
+            Self::TrailingWhitespace => "trailing whitespace",
+            Self::TrailingBlankLine => "trailing blank lines",
+            Self::MissingFinalNewline => "missing final newline",
+        })
+    }
+}
+
+/// One kind of change, with where it was made.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentChange {
+    /// What was changed.
+    pub kind: ContentChangeKind,
+    /// How much was changed, in the unit documented on the kind.
+    pub count: usize,
+    /// 1-based input lines affected. A line ends at `\r\n`, `\r` or `\n`.
+    pub lines: Vec<usize>,
+}
+
+impl fmt::Display for ContentChange {
+    /// `trailing whitespace: 5 on lines 3, 7`, listing at most ten lines.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const SHOWN: usize = 10;
+        write!(f, "{}: {} on ", self.kind, self.count)?;
+        f.write_str(if self.lines.len() == 1 {
+            "line "
+        } else {
+            "lines "
+        })?;
+        for (i, line) in self.lines.iter().take(SHOWN).enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{line}")?;
+        }
+        if self.lines.len() > SHOWN {
+            write!(f, " (+{} more)", self.lines.len() - SHOWN)?;
+        }
+        Ok(())
+    }
+}
+
+/// Canonical bytes and the changes made to produce them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalContent {
+    /// Exactly the bytes of [`canonicalize_content`].
+    pub bytes: Vec<u8>,
+    /// Changes in step order; empty when the input was already canonical.
+    pub changes: Vec<ContentChange>,
+}
+
+impl CanonicalContent {
+    /// Whether the input was already canonical.
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// As [`canonicalize_content`], also reporting what was changed.
+///
+/// Line endings, trailing whitespace and Unicode normalization are
+/// invisible in most editors, so a hash that differs from the manifest's
+/// is usually explained by one of these changes.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::{canonicalize_with_report, ContentChangeKind};
+///
+/// let report = canonicalize_with_report("Be kind.  \r\nBe honest.").unwrap();
+/// assert_eq!(report.bytes, b"Be kind.\nBe honest.\n");
+/// let kinds: Vec<_> = report.changes.iter().map(|c| c.kind).collect();
+/// assert_eq!(
+///     kinds,
+///     [
+///         ContentChangeKind::CrlfLineEnding,
+///         ContentChangeKind::TrailingWhitespace,
+///         ContentChangeKind::MissingFinalNewline,
+///     ]
+/// );
+/// assert_eq!(report.changes[1].to_string(), "trailing whitespace: 2 on line 1");
+/// ```
+///
+/// # Errors
+///
+/// As [`canonicalize_content`].
+pub fn canonicalize_with_report(text: &str) -> VcpResult<CanonicalContent> {
+    let bytes = canonicalize_content(text)?;
+    let mut changes: Vec<ContentChange> = [
+        ContentChangeKind::UnicodeNormalized,
+        ContentChangeKind::CrlfLineEnding,
+        ContentChangeKind::CrLineEnding,
+        ContentChangeKind::TrailingWhitespace,
+        ContentChangeKind::TrailingBlankLine,
+        ContentChangeKind::MissingFinalNewline,
+    ]
+    .into_iter()
+    .map(|kind| ContentChange {
+        kind,
+        count: 0,
+        lines: Vec::new(),
+    })
+    .collect();
+    let mut record = |kind: ContentChangeKind, line: usize, count: usize| {
+        if let Some(change) = changes.iter_mut().find(|c| c.kind == kind) {
+            change.count += count;
+            change.lines.push(line);
+        }
+    };
+
+    let lines = split_lines(text);
+    // Index of the last line with content once trailing whitespace goes.
+    let last_content = lines
+        .iter()
+        .rposition(|(line, _)| !line.trim_end_matches([' ', '\t']).is_empty());
+    for (i, (line, ending)) in lines.iter().enumerate() {
+        let number = i + 1;
+        if !unicode_normalization::is_nfc(line) {
+            record(ContentChangeKind::UnicodeNormalized, number, 1);
+        }
+        match *ending {
+            "\r\n" => record(ContentChangeKind::CrlfLineEnding, number, 1),
+            "\r" => record(ContentChangeKind::CrLineEnding, number, 1),
+            _ => {}
+        }
+        let stripped = line.len() - line.trim_end_matches([' ', '\t']).len();
+        if stripped > 0 {
+            record(ContentChangeKind::TrailingWhitespace, number, stripped);
+        }
+        // The final newline ends the last content line, or the first line
+        // of an empty document; the last line has no ending to remove.
+        if i > last_content.unwrap_or(0) && number < lines.len() {
+            record(ContentChangeKind::TrailingBlankLine, number, 1);
+        }
+    }
+    let final_line = lines.len();
+    if last_content == Some(final_line - 1) || (final_line == 1 && last_content.is_none()) {
+        record(ContentChangeKind::MissingFinalNewline, final_line, 1);
+    }
+
+    changes.retain(|change| change.count > 0);
+    Ok(CanonicalContent { bytes, changes })
+}
+
+/// Split at `\r\n`, `\r` and `\n`, keeping each line's ending. Always
+/// returns at least one line; the last has an empty ending.
+fn split_lines(text: &str) -> Vec<(&str, &str)> {
+    let mut lines = Vec::new();
+    let mut rest = text;
+    while let Some(at) = rest.find(['\r', '\n']) {
+        let len = if rest[at..].starts_with("\r\n") { 2 } else { 1 };
+        lines.push((&rest[..at], &rest[at..at + len]));
+        rest = &rest[at + len..];
+    }
+    lines.push((rest, ""));
+    lines
+}
+
+// ── Encoding detection ──────────────────────────────────────
+
+/// Encodings [`decode_content`] recognizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextEncoding {
+    /// UTF-8 without a byte order mark, as canonical content is stored.
+    Utf8,
+    /// UTF-8 preceded by `EF BB BF`.
+    Utf8Bom,
+    /// UTF-16 little-endian, with or without the `FF FE` mark.
+    Utf16Le,
+    /// UTF-16 big-endian, with or without the `FE FF` mark.
+    Utf16Be,
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf8Bom => "UTF-8 with BOM",
+            Self::Utf16Le => "UTF-16 LE",
+            Self::Utf16Be => "UTF-16 BE",
+        })
+    }
+}
+
+/// Text decoded by [`decode_content`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedContent {
+    /// The text, without any byte order mark.
+    pub text: String,
+    /// The encoding the bytes were in.
+    pub encoding: TextEncoding,
+}
+
+/// Decode a content file that may have been saved as UTF-16 or with a BOM.
+///
+/// A byte order mark decides the encoding. Without one, bytes where at
+/// least half the code units have a NUL high byte (or low byte), and
+/// hardly any the other, are taken as UTF-16 LE (or BE), since canonical
+/// content cannot contain NUL; anything else must be UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::{compute_content_hash, decode_content, TextEncoding};
+///
+/// let utf16: Vec<u8> = [0xFF, 0xFE].into_iter()
+///     .chain("Be kind.\n".encode_utf16().flat_map(u16::to_le_bytes))
+///     .collect();
+/// let decoded = decode_content(&utf16).unwrap();
+/// assert_eq!(decoded.encoding, TextEncoding::Utf16Le);
+/// assert_eq!(
+///     compute_content_hash(&decoded.text).unwrap(),
+///     compute_content_hash("Be kind.\n").unwrap()
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns [`VcpError::ParseError`] for invalid UTF-8, or for UTF-16
+/// with an odd byte count or an unpaired surrogate.
+pub fn decode_content(bytes: &[u8]) -> VcpResult<DecodedContent> {
+    let (encoding, bom) = detect_encoding(bytes);
+    let body = &bytes[bom..];
+    let text = match encoding {
+        TextEncoding::Utf8 | TextEncoding::Utf8Bom => std::str::from_utf8(body)
+            .map_err(|e| {
+                VcpError::ParseError(format!("invalid UTF-8 at byte {}", e.valid_up_to()))
+            })?
+            .to_string(),
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            let mut decoder = Utf16Decoder::new(encoding);
+            let text = decoder.decode(body)?;
+            decoder.finish()?;
+            text
+        }
+    };
+    Ok(DecodedContent { text, encoding })
+}
+
+/// The encoding of content starting with `head`, and the length of its
+/// byte order mark.
+fn detect_encoding(head: &[u8]) -> (TextEncoding, usize) {
+    if head.starts_with(b"\xEF\xBB\xBF") {
+        (TextEncoding::Utf8Bom, 3)
+    } else if head.starts_with(b"\xFF\xFE") {
+        (TextEncoding::Utf16Le, 2)
+    } else if head.starts_with(b"\xFE\xFF") {
+        (TextEncoding::Utf16Be, 2)
+    } else {
+        (sniff_utf16(head).unwrap_or(TextEncoding::Utf8), 0)
+    }
+}
+
+/// UTF-16 without a BOM, judged by where the NUL bytes fall in whole
+/// code units.
+fn sniff_utf16(bytes: &[u8]) -> Option<TextEncoding> {
+    let pairs = bytes.len() / 2;
+    if pairs == 0 {
+        return None;
+    }
+    let (mut high_nul, mut low_nul) = (0, 0);
+    for pair in bytes.chunks_exact(2) {
+        low_nul += usize::from(pair[0] == 0);
+        high_nul += usize::from(pair[1] == 0);
+    }
+    // Surrogates and some CJK units have a NUL byte too, so the other
+    // position only needs to be far rarer.
+    if high_nul * 2 >= pairs && low_nul * 10 <= high_nul {
+        Some(TextEncoding::Utf16Le)
+    } else if low_nul * 2 >= pairs && high_nul * 10 <= low_nul {
+        Some(TextEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+/// UTF-16 decoding across chunk boundaries, which may split a code unit
+/// or a surrogate pair.
+#[derive(Debug)]
+struct Utf16Decoder {
+    encoding: TextEncoding,
+    odd_byte: Option<u8>,
+    high_surrogate: Option<u16>,
+}
+
+impl Utf16Decoder {
+    fn new(encoding: TextEncoding) -> Self {
+        Self {
+            encoding,
+            odd_byte: None,
+            high_surrogate: None,
+        }
+    }
+
+    /// Decode the next chunk, holding back a trailing half unit or pair.
+    fn decode(&mut self, bytes: &[u8]) -> VcpResult<String> {
+        let mut units = Vec::with_capacity(bytes.len() / 2 + 1);
+        units.extend(self.high_surrogate.take());
+        for &byte in bytes {
+            match self.odd_byte.take() {
+                None => self.odd_byte = Some(byte),
+                Some(first) => units.push(if self.encoding == TextEncoding::Utf16Le {
+                    u16::from_le_bytes([first, byte])
+                } else {
+                    u16::from_be_bytes([first, byte])
+                }),
+            }
+        }
+        if units
+            .last()
+            .is_some_and(|unit| (0xD800..0xDC00).contains(unit))
+        {
+            self.high_surrogate = units.pop();
+        }
+        char::decode_utf16(units)
+            .collect::<Result<String, _>>()
+            .map_err(|e| self.unpaired(e.unpaired_surrogate()))
+    }
+
+    /// Fail if the input ended inside a code unit or surrogate pair.
+    fn finish(&self) -> VcpResult<()> {
+        if self.odd_byte.is_some() {
+            return Err(VcpError::ParseError(format!(
+                "{} content has an odd number of bytes",
+                self.encoding
+            )));
+        }
+        match self.high_surrogate {
+            Some(unit) => Err(self.unpaired(unit)),
+            None => Ok(()),
+        }
+    }
+
+    fn unpaired(&self, unit: u16) -> VcpError {
+        VcpError::ParseError(format!(
+            "invalid {}: unpaired surrogate U+{unit:04X}",
+            self.encoding
+        ))
+    }
+}
+
+// ── Streaming canonicalization ──────────────────────────────
+
+/// Single-pass content canonicalizer that accepts input in chunks.
+///
+/// Feeding a document through any sequence of [`update`](Self::update)
+/// calls yields the same bytes as [`canonicalize_content`] on the whole
+/// document. Memory use is bounded by the longest run of non-ASCII
+/// text, since NFC can only be applied once a safe boundary is seen.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::{canonicalize_content, ContentCanonicalizer};
+///
+/// let mut canon = ContentCanonicalizer::new(Vec::new());
+/// canon.update("Be kind.  \r\n").unwrap();
+/// canon.update("Be honest.\n\n").unwrap();
+/// let bytes = canon.finish().unwrap();
+/// assert_eq!(bytes, canonicalize_content("Be kind.  \r\nBe honest.\n\n").unwrap());
+/// ```
+#[derive(Debug)]
+pub struct ContentCanonicalizer<W: Write> {
+    writer: W,
+    /// Input not yet NFC-normalized; always starts at an ASCII char.
+    pending: String,
+    /// Incomplete UTF-8 sequence left over from `update_bytes`.
+    partial: Vec<u8>,
+    /// Spaces and tabs that will be dropped if the line ends here.
+    trailing_ws: String,
+    /// Line breaks held back in case they turn out to be trailing.
+    held_newlines: usize,
+    /// The previous char was `\r`, so a following `\n` is swallowed.
+    after_cr: bool,
+    /// Canonical bytes written so far.
+    written: usize,
+    out: Vec<u8>,
+}
+
+impl<W: Write> ContentCanonicalizer<W> {
+    /// Create a canonicalizer writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            pending: String::new(),
+            partial: Vec::new(),
+            trailing_ws: String::new(),
+            held_newlines: 0,
+            after_cr: false,
+            written: 0,
+            out: Vec::new(),
+        }
+    }
+
+    /// Feed the next chunk of text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for illegal characters and
+    /// [`VcpError::IoError`] if the writer fails.
+    pub fn update(&mut self, chunk: &str) -> VcpResult<()> {
+        self.pending.push_str(chunk);
+        // An ASCII char never composes with what precedes it, so NFC of
+        // everything before the last one cannot change with more input.
+        let split = self
+            .pending
+            .bytes()
+            .rposition(|b| b.is_ascii())
+            .unwrap_or(0);
+        if split > 0 {
+            let rest = self.pending.split_off(split);
+            let ready = std::mem::replace(&mut self.pending, rest);
+            for ch in ready.nfc() {
+                self.push_char(ch)?;
+            }
+            self.flush_out()?;
+        }
+        Ok(())
+    }
+
+    /// Feed the next chunk of UTF-8 bytes, which may split a character.
+    ///
+    /// # Errors
+    ///
+    /// As [`update`](Self::update), plus [`VcpError::ParseError`] for
+    /// invalid UTF-8.
+    pub fn update_bytes(&mut self, chunk: &[u8]) -> VcpResult<()> {
+        self.partial.extend_from_slice(chunk);
+        let valid = match std::str::from_utf8(&self.partial) {
+            Ok(_) => self.partial.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                return Err(VcpError::ParseError(format!(
+                    "invalid UTF-8 at byte {}",
+                    self.written + e.valid_up_to()
+                )))
+            }
+        };
+        let tail = self.partial.split_off(valid);
+        let head = std::mem::replace(&mut self.partial, tail);
+        // `valid` was checked above.
+        let text = String::from_utf8(head).unwrap_or_default();
+        self.update(&text)
+    }
+
+    /// Flush the remaining input, append the final newline and return the
+    /// writer.
+    ///
+    /// # Errors
+    ///
+    /// As [`update`](Self::update), plus [`VcpError::ParseError`] if the
+    /// input ended inside a UTF-8 sequence.
+    pub fn finish(mut self) -> VcpResult<W> {
+        if !self.partial.is_empty() {
+            return Err(VcpError::ParseError(
+                "input ends with an incomplete UTF-8 sequence".into(),
+            ));
+        }
+        let rest = std::mem::take(&mut self.pending);
+        for ch in rest.nfc() {
+            self.push_char(ch)?;
+        }
+        // Held newlines and trailing whitespace are dropped here.
+        self.out.push(b'\n');
+        self.flush_out()?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn push_char(&mut self, ch: char) -> VcpResult<()> {
+        if std::mem::take(&mut self.after_cr) && ch == '\n' {
+            return Ok(());
+        }
+        match ch {
+            '\r' | '\n' => {
+                self.after_cr = ch == '\r';
+                self.trailing_ws.clear();
+                self.held_newlines += 1;
+            }
+            ' ' | '\t' => self.trailing_ws.push(ch),
+            _ => {
+                let pos =
+                    self.written + self.out.len() + self.held_newlines + self.trailing_ws.len();
+                check_char(ch, pos)?;
+                self.out
+                    .extend(std::iter::repeat_n(b'\n', self.held_newlines));
+                self.held_newlines = 0;
+                self.out.extend_from_slice(self.trailing_ws.as_bytes());
+                self.trailing_ws.clear();
+                let mut buf = [0u8; 4];
+                self.out
+                    .extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    fn flush_out(&mut self) -> VcpResult<()> {
+        self.writer.write_all(&self.out)?;
+        self.written += self.out.len();
+        self.out.clear();
+        Ok(())
+    }
+}
+
+/// Reject control characters (other than `\n`/`\t`) and forbidden
+/// codepoints; `pos` is the byte offset in the canonical output.
+fn check_char(ch: char, pos: usize) -> VcpResult<()> {
+    if ch.is_control() {
+        return Err(VcpError::ParseError(format!(
+            "illegal control character at position {pos}: U+{:04X}",
+            ch as u32
+        )));
+    }
+    if FORBIDDEN_CODEPOINTS.contains(&ch) {
+        return Err(VcpError::ParseError(format!(
+            "forbidden Unicode character at position {pos}: U+{:04X}",
+            ch as u32
+        )));
+    }
+    Ok(())
+}
+
+/// Incremental `sha256:<hex>` content hasher.
+///
+/// Canonicalizes and hashes chunks as they arrive, so a host never has
+/// to hold the whole document.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::{compute_content_hash, ContentHasher};
+///
+/// let mut hasher = ContentHasher::new();
+/// hasher.update("Be kind ").unwrap();
+/// hasher.update("to everyone.").unwrap();
+/// assert_eq!(
+///     hasher.finalize().unwrap(),
+///     compute_content_hash("Be kind to everyone.").unwrap()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ContentHasher {
+    canon: ContentCanonicalizer<DigestWriter>,
+}
+
+impl ContentHasher {
+    /// Create an empty hasher.
+    pub fn new() -> Self {
+        Self {
+            canon: ContentCanonicalizer::new(DigestWriter(Sha256::new())),
+        }
+    }
+
+    /// Feed the next chunk of text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for illegal characters.
+    pub fn update(&mut self, chunk: &str) -> VcpResult<()> {
+        self.canon.update(chunk)
+    }
+
+    /// Feed the next chunk of UTF-8 bytes, which may split a character.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for illegal characters or invalid
+    /// UTF-8.
+    pub fn update_bytes(&mut self, chunk: &[u8]) -> VcpResult<()> {
+        self.canon.update_bytes(chunk)
+    }
+
+    /// Feed a whole content file from `reader` in 64 KiB chunks, so
+    /// content of any size is hashed in constant memory.
+    ///
+    /// The encoding is detected as by [`decode_content`], judging a file
+    /// without a BOM by its first chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::IoError`] if reading fails, and otherwise as
+    /// [`decode_content`] and [`update`](Self::update).
+    pub fn update_reader(&mut self, reader: impl Read) -> VcpResult<ReadSummary> {
+        self.update_reader_chunked(reader, READ_CHUNK)
+    }
+
+    fn update_reader_chunked(
+        &mut self,
+        mut reader: impl Read,
+        chunk: usize,
+    ) -> VcpResult<ReadSummary> {
+        let mut buf = vec![0u8; chunk];
+        let mut filled = read_full(&mut reader, &mut buf)?;
+        let (encoding, mut start) = detect_encoding(&buf[..filled]);
+        let mut utf16 = Utf16Decoder::new(encoding);
+        let mut text_len = 0;
+        while filled > 0 {
+            let bytes = &buf[start..filled];
+            if matches!(encoding, TextEncoding::Utf8 | TextEncoding::Utf8Bom) {
+                self.update_bytes(bytes)?;
+                text_len += bytes.len() as u64;
+            } else {
+                let text = utf16.decode(bytes)?;
+                self.update(&text)?;
+                text_len += text.len() as u64;
+            }
+            start = 0;
+            filled = read_full(&mut reader, &mut buf)?;
+        }
+        utf16.finish()?;
+        Ok(ReadSummary { encoding, text_len })
+    }
+
+    /// Finish canonicalization and return the `sha256:<hex>` hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] for illegal characters or a
+    /// truncated UTF-8 sequence.
+    pub fn finalize(self) -> VcpResult<String> {
+        let DigestWriter(digest) = self.canon.finish()?;
+        Ok(format!("sha256:{:x}", digest.finalize()))
+    }
+}
+
+impl Default for ContentHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read size for [`ContentHasher::update_reader`].
+const READ_CHUNK: usize = 64 * 1024;
+
+/// What [`ContentHasher::update_reader`] read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadSummary {
+    /// Encoding detected from the first chunk.
+    pub encoding: TextEncoding,
+    /// Length of the decoded text in UTF-8 bytes, as the orchestrator's
+    /// content size limit counts it.
+    pub text_len: u64,
+}
+
+/// Fill `buf` unless the reader ends first, returning the bytes read.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// `Write` adapter feeding a SHA-256 digest.
+#[derive(Debug)]
+struct DigestWriter(Sha256);
+
+impl Write for DigestWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 // ── Manifest canonicalization (RFC 8785) ────────────────────
 
 /// Canonicalize a JSON manifest for signature computation.
 ///
-/// Implements RFC 8785 JSON Canonicalization Scheme:
-/// - Keys sorted lexicographically
-/// - No whitespace between tokens
-/// - The `"signature"` field is excluded from canonicalization.
+/// Applies [`canonicalize_json`] to the manifest with the top-level
+/// `"signature"` and `"signatures"` fields removed, so every co-signer of a
+/// [multi-signature](crate::multisig) manifest signs the same bytes.
 ///
 /// # Errors
 ///
-/// Returns [`VcpError::ParseError`] if the manifest is not a JSON object,
-/// or [`VcpError::JsonError`] if serialization fails.
+/// Returns [`VcpError::ParseError`] if the manifest is not a JSON object.
 pub fn canonicalize_manifest(manifest: &serde_json::Value) -> VcpResult<Vec<u8>> {
     let obj = manifest
         .as_object()
         .ok_or_else(|| VcpError::ParseError("manifest must be a JSON object".into()))?;
 
-    // Remove "signature" before canonicalizing.
-    let filtered: serde_json::Map<String, serde_json::Value> = obj
-        .iter()
-        .filter(|(k, _)| k.as_str() != "signature")
-        .map(|(k, v)| (k.clone(), v.clone()))
-        .collect();
+    let mut out = String::new();
+    write_jcs_object(
+        obj.iter()
+            .filter(|(k, _)| !matches!(k.as_str(), "signature" | "signatures")),
+        &mut out,
+    );
+    Ok(out.into_bytes())
+}
+
+/// Serialize any JSON value per RFC 8785 (JSON Canonicalization Scheme).
+///
+/// Matches `JSON.stringify` on key-sorted input, which is what the
+/// Python and TypeScript SDKs sign:
+/// - object keys sorted by UTF-16 code units, not UTF-8 bytes
+/// - numbers treated as IEEE-754 doubles and printed in ECMAScript form
+///   (`1.0` becomes `1`, `1e21` becomes `1e+21`); integers beyond 2^53
+///   are rounded exactly as a JS peer would round them
+/// - strings escape only `"`, `\` and control characters
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::canonicalize_json;
+///
+/// let value = serde_json::json!({"b": 1.0, "a": [1e21, 0.000001, "tab\t"]});
+/// assert_eq!(
+///     String::from_utf8(canonicalize_json(&value)).unwrap(),
+///     r#"{"a":[1e+21,0.000001,"tab\t"],"b":1}"#
+/// );
+/// ```
+pub fn canonicalize_json(value: &serde_json::Value) -> Vec<u8> {
+    let mut out = String::new();
+    write_jcs_value(value, &mut out);
+    out.into_bytes()
+}
+
+fn write_jcs_value(value: &serde_json::Value, out: &mut String) {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => {
+            // Every finite serde_json number has an f64 view.
+            write_jcs_number(n.as_f64().unwrap_or(0.0), out);
+        }
+        Value::String(s) => write_jcs_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_jcs_value(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => write_jcs_object(map.iter(), out),
+    }
+}
+
+fn write_jcs_object<'a>(
+    entries: impl Iterator<Item = (&'a String, &'a serde_json::Value)>,
+    out: &mut String,
+) {
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+    out.push('{');
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_jcs_string(key, out);
+        out.push(':');
+        write_jcs_value(value, out);
+    }
+    out.push('}');
+}
+
+fn write_jcs_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
 
-    // Serialize with sorted keys, no whitespace.
-    let canonical = serde_json::to_string(&serde_json::Value::Object(filtered))
-        .map_err(|e| VcpError::JsonError(e.to_string()))?;
+/// ECMAScript `Number::toString(x)` for finite `x` (ECMA-262 §6.1.6.1.20).
+fn write_jcs_number(x: f64, out: &mut String) {
+    if x == 0.0 {
+        // Covers -0 as well.
+        out.push('0');
+        return;
+    }
+    if x < 0.0 {
+        out.push('-');
+    }
 
-    Ok(canonical.into_bytes())
+    // Rust's `{:e}` yields the shortest round-tripping digits, which is
+    // exactly the digit string ECMAScript requires.
+    let sci = format!("{:e}", x.abs());
+    let (mantissa, exp) = sci.split_once('e').unwrap_or((&sci, "0"));
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let k = i32::try_from(digits.len()).unwrap_or(i32::MAX);
+    // Decimal point position: value = 0.digits × 10^n.
+    let n = exp.parse::<i32>().unwrap_or(0) + 1;
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n(
+            '0',
+            usize::try_from(n - k).unwrap_or(0),
+        ));
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(usize::try_from(n).unwrap_or(0));
+        out.push_str(int);
+        out.push('.');
+        out.push_str(frac);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', usize::try_from(-n).unwrap_or(0)));
+        out.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            out.push('.');
+            out.push_str(rest);
+        }
+        let e = n - 1;
+        let _ = write!(out, "e{}{}", if e < 0 { '-' } else { '+' }, e.abs());
+    }
 }
 
 // ── Ed25519 signature operations ────────────────────────────
 
 /// Sign a manifest with an Ed25519 secret key.
 ///
-/// Canonicalizes the manifest (excluding the `"signature"` field), signs
-/// the canonical bytes with the provided 32-byte Ed25519 secret key, and
-/// returns the signature as a standard base64-encoded string.
+/// Canonicalizes the manifest (excluding the `"signature"` and
+/// `"signatures"` fields), signs the canonical bytes with the provided
+/// 32-byte Ed25519 secret key, and returns the signature as a standard
+/// base64-encoded string.
+///
+/// For keys held in a KMS or HSM, use
+/// [`sign_manifest_with`](crate::signer::sign_manifest_with) instead.
 ///
 /// # Arguments
 ///
@@ -174,25 +1010,14 @@ pub fn canonicalize_manifest(manifest: &serde_json::Value) -> VcpResult<Vec<u8>>
 /// assert!(verify_manifest_signature(&manifest, &public_key, &sig).unwrap());
 /// ```
 pub fn sign_manifest(manifest: &serde_json::Value, secret_key: &[u8]) -> VcpResult<String> {
-    let key_bytes: [u8; 32] = secret_key.try_into().map_err(|_| {
-        VcpError::SignatureError(format!(
-            "secret key must be exactly 32 bytes, got {}",
-            secret_key.len()
-        ))
-    })?;
-
-    let signing_key = SigningKey::from_bytes(&key_bytes);
-    let canonical = canonicalize_manifest(manifest)?;
-    let signature = signing_key.sign(&canonical);
-
-    Ok(BASE64.encode(signature.to_bytes()))
+    sign_manifest_with(manifest, &Ed25519Signer::from_secret_bytes(secret_key)?)
 }
 
 /// Verify an Ed25519 signature against a manifest and public key.
 ///
-/// Canonicalizes the manifest (excluding the `"signature"` field) and
-/// verifies the base64-encoded signature against the provided 32-byte
-/// Ed25519 public key.
+/// Canonicalizes the manifest (excluding the `"signature"` and
+/// `"signatures"` fields) and verifies the base64-encoded signature
+/// against the provided 32-byte Ed25519 public key.
 ///
 /// # Arguments
 ///
@@ -261,26 +1086,677 @@ pub fn verify_manifest_signature(
     }
 }
 
-// ── Bundle verification ─────────────────────────────────────
+// ── JWS ─────────────────────────────────────────────────────
 
-/// Result of a bundle verification check.
+/// JWS `typ` header for VCP manifests.
+pub const JWS_TYPE: &str = "vcp+jwt";
+
+/// The only JWS algorithm VCP uses.
+const JWS_ALG: &str = "EdDSA";
+
+/// Registered JWT claims mapped from `timestamps`, plus `iss`.
+const JWT_TIME_CLAIMS: [&str; 3] = ["iat", "nbf", "exp"];
+
+/// The protected header of a VCP JWS.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct VerificationResult {
-    pub code: VerificationCode,
-    pub message: String,
+pub struct JwsHeader {
+    /// Signature algorithm; always `EdDSA`.
+    pub alg: String,
+    /// Media type, [`JWS_TYPE`] for tokens built here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    /// Issuer key ID, copied from `issuer.key_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
 }
 
-impl VerificationResult {
-    /// Create a successful verification result.
-    #[must_use]
-    pub fn valid() -> Self {
-        Self {
-            code: VerificationCode::Valid,
-            message: "verification passed".into(),
+/// A decoded compact JWS carrying a manifest.
+///
+/// [`decode`](Self::decode) does not check the signature; call
+/// [`verify`](Self::verify) with the issuer's key, or use [`from_jws`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jws {
+    /// The protected header.
+    pub header: JwsHeader,
+    /// The manifest, with the JWT claims folded back into `timestamps`
+    /// and removed.
+    pub manifest: serde_json::Value,
+    signing_input: String,
+    signature: [u8; 64],
+}
+
+impl Jws {
+    /// Decode a compact JWS without checking its signature.
+    ///
+    /// The payload's `iat`, `nbf`, `exp` and `jti` claims must agree with
+    /// `timestamps` where both are present; claims without a matching
+    /// timestamp fill it in. `iss` must match `issuer.id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] if the token is not three
+    /// base64url segments, the algorithm is not `EdDSA`, or the signature
+    /// is not 64 bytes, and [`VcpError::ParseError`] if the payload is not
+    /// a JSON object or a claim disagrees with the manifest.
+    pub fn decode(compact: &str) -> VcpResult<Self> {
+        let malformed = |why: &str| VcpError::SignatureError(format!("malformed JWS: {why}"));
+        let mut parts = compact.trim().split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(malformed("expected three segments"));
+        };
+        let decode = |segment: &str, what: &str| {
+            BASE64_URL
+                .decode(segment)
+                .map_err(|e| malformed(&format!("{what} is not base64url: {e}")))
+        };
+
+        let parsed_header: JwsHeader = serde_json::from_slice(&decode(header, "header")?)?;
+        if parsed_header.alg != JWS_ALG {
+            return Err(VcpError::SignatureError(format!(
+                "unsupported JWS algorithm: {}",
+                parsed_header.alg
+            )));
         }
+        let signature: [u8; 64] = decode(signature, "signature")?
+            .try_into()
+            .map_err(|_| malformed("signature must be exactly 64 bytes"))?;
+        let mut manifest: serde_json::Value = serde_json::from_slice(&decode(payload, "payload")?)?;
+        fold_jwt_claims(&mut manifest)?;
+
+        Ok(Self {
+            header: parsed_header,
+            manifest,
+            signing_input: format!("{header}.{payload}"),
+            signature,
+        })
     }
 
-    /// Create a failed result with a specific code.
+    /// Check the signature against a 32-byte Ed25519 public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::SignatureError`] if `public_key` is malformed.
+    pub fn verify(&self, public_key: &[u8]) -> VcpResult<bool> {
+        let key_bytes: [u8; 32] = public_key.try_into().map_err(|_| {
+            VcpError::SignatureError(format!(
+                "public key must be exactly 32 bytes, got {}",
+                public_key.len()
+            ))
+        })?;
+        let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+            .map_err(|e| VcpError::SignatureError(format!("invalid Ed25519 public key: {e}")))?;
+        let signature = ed25519_dalek::Signature::from_bytes(&self.signature);
+        Ok(verifying_key
+            .verify(self.signing_input.as_bytes(), &signature)
+            .is_ok())
+    }
+}
+
+/// Encode a manifest as a compact JWS (`EdDSA`) for JWT-only infrastructure.
+///
+/// The payload is the RFC 8785 canonical JSON of the manifest with the
+/// registered claims `iss` (`issuer.id`), `iat`, `nbf`, `exp` (from
+/// `timestamps`, as seconds since the epoch) and `jti` added at the top
+/// level. Any embedded `signature` is kept, so the manifest round-trips
+/// through [`from_jws`] unchanged.
+///
+/// # Errors
+///
+/// Returns [`VcpError::SignatureError`] if the secret key is not exactly
+/// 32 bytes, and [`VcpError::ParseError`] if the manifest is not an
+/// object, already has a top-level claim name, or has a timestamp that is
+/// not RFC 3339.
+///
+/// # Examples
+///
+/// ```
+/// use ed25519_dalek::SigningKey;
+/// use vcp_core::transport::{from_jws, to_jws};
+///
+/// let key = SigningKey::from_bytes(&[7u8; 32]);
+/// let manifest = serde_json::json!({
+///     "bundle": {"id": "family.safe.guide", "content_hash": "sha256:abc"},
+///     "timestamps": {"exp": "2027-01-01T00:00:00Z", "jti": "m-1"}
+/// });
+///
+/// let jws = to_jws(&manifest, &key.to_bytes()).unwrap();
+/// assert_eq!(jws.split('.').count(), 3);
+/// let decoded = from_jws(&jws, &key.verifying_key().to_bytes()).unwrap();
+/// assert_eq!(decoded, manifest);
+/// ```
+pub fn to_jws(manifest: &serde_json::Value, secret_key: &[u8]) -> VcpResult<String> {
+    to_jws_with(manifest, &Ed25519Signer::from_secret_bytes(secret_key)?)
+}
+
+/// Encode a manifest as a compact JWS, signing through `signer`.
+///
+/// # Errors
+///
+/// As for [`to_jws`], plus whatever error the signer reports.
+pub fn to_jws_with<S: ManifestSigner + ?Sized>(
+    manifest: &serde_json::Value,
+    signer: &S,
+) -> VcpResult<String> {
+    let obj = manifest
+        .as_object()
+        .ok_or_else(|| VcpError::ParseError("manifest must be a JSON object".into()))?;
+    let mut payload = obj.clone();
+    for (claim, value) in jwt_claims(manifest)? {
+        if payload.insert(claim.to_string(), value).is_some() {
+            return Err(VcpError::ParseError(format!(
+                "manifest already has a top-level {claim} field"
+            )));
+        }
+    }
+
+    let header = JwsHeader {
+        alg: JWS_ALG.to_string(),
+        typ: Some(JWS_TYPE.to_string()),
+        kid: manifest
+            .pointer("/issuer/key_id")
+            .and_then(serde_json::Value::as_str)
+            .map(String::from),
+    };
+    let signing_input = format!(
+        "{}.{}",
+        BASE64_URL.encode(serde_json::to_vec(&header)?),
+        BASE64_URL.encode(canonicalize_json(&serde_json::Value::Object(payload)))
+    );
+    let signature = signer.sign_bytes(signing_input.as_bytes())?;
+    Ok(format!("{signing_input}.{}", BASE64_URL.encode(signature)))
+}
+
+/// Decode a compact JWS and verify it against the issuer's public key,
+/// returning the manifest.
+///
+/// # Errors
+///
+/// Returns any error from [`Jws::decode`], or
+/// [`VcpError::SignatureError`] if the signature does not verify.
+pub fn from_jws(compact: &str, public_key: &[u8]) -> VcpResult<serde_json::Value> {
+    let jws = Jws::decode(compact)?;
+    if !jws.verify(public_key)? {
+        return Err(VcpError::SignatureError(
+            "JWS signature does not verify".into(),
+        ));
+    }
+    Ok(jws.manifest)
+}
+
+/// The registered claims for `manifest`, in payload order.
+fn jwt_claims(manifest: &serde_json::Value) -> VcpResult<Vec<(&'static str, serde_json::Value)>> {
+    let mut claims = Vec::new();
+    if let Some(iss) = manifest
+        .pointer("/issuer/id")
+        .and_then(serde_json::Value::as_str)
+    {
+        claims.push(("iss", iss.into()));
+    }
+    for claim in JWT_TIME_CLAIMS {
+        if let Some(value) = manifest.get("timestamps").and_then(|t| t.get(claim)) {
+            claims.push((claim, numeric_date(claim, value)?.into()));
+        }
+    }
+    if let Some(jti) = manifest.pointer("/timestamps/jti") {
+        claims.push(("jti", jti.clone()));
+    }
+    Ok(claims)
+}
+
+/// Seconds since the epoch for an RFC 3339 `timestamps` entry.
+fn numeric_date(claim: &str, value: &serde_json::Value) -> VcpResult<i64> {
+    value
+        .as_str()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.timestamp())
+        .ok_or_else(|| VcpError::ParseError(format!("timestamps.{claim} is not an RFC 3339 time")))
+}
+
+/// Remove the registered claims from a JWS payload, checking them
+/// against the manifest and filling in missing timestamps.
+fn fold_jwt_claims(payload: &mut serde_json::Value) -> VcpResult<()> {
+    use serde_json::Value;
+
+    let obj = payload
+        .as_object_mut()
+        .ok_or_else(|| VcpError::ParseError("JWS payload must be a JSON object".into()))?;
+    let disagree = |claim: &str| {
+        VcpError::ParseError(format!("JWS claim {claim} disagrees with the manifest"))
+    };
+
+    if let Some(iss) = obj.remove("iss") {
+        if obj.get("issuer").and_then(|i| i.get("id")) != Some(&iss) {
+            return Err(disagree("iss"));
+        }
+    }
+
+    let mut filled = serde_json::Map::new();
+    for claim in JWT_TIME_CLAIMS {
+        let Some(value) = obj.remove(claim) else {
+            continue;
+        };
+        let seconds = value.as_i64().ok_or_else(|| disagree(claim))?;
+        if let Some(existing) = obj.get("timestamps").and_then(|t| t.get(claim)) {
+            if numeric_date(claim, existing)? != seconds {
+                return Err(disagree(claim));
+            }
+        } else {
+            let time =
+                DateTime::<Utc>::from_timestamp(seconds, 0).ok_or_else(|| disagree(claim))?;
+            filled.insert(
+                claim.to_string(),
+                Value::String(time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            );
+        }
+    }
+    if let Some(jti) = obj.remove("jti") {
+        match obj.get("timestamps").and_then(|t| t.get("jti")) {
+            Some(existing) if *existing != jti => return Err(disagree("jti")),
+            Some(_) => {}
+            None => {
+                filled.insert("jti".to_string(), jti);
+            }
+        }
+    }
+
+    if !filled.is_empty() {
+        let timestamps = obj
+            .entry("timestamps")
+            .or_insert_with(|| Value::Object(serde_json::Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| VcpError::ParseError("timestamps must be an object".into()))?;
+        timestamps.extend(filled);
+    }
+    Ok(())
+}
+
+// ── Typed manifest ──────────────────────────────────────────
+
+/// A bundle manifest with its sections parsed into typed fields.
+///
+/// Deserializing checks each field's type; [`validate`](Self::validate)
+/// adds the cross-field rules. Unknown fields are ignored, so signatures
+/// must still be verified over the original JSON value with
+/// [`verify_manifest_signature`].
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::Manifest;
+///
+/// let manifest = Manifest::from_json(r#"{
+///     "vcp_version": "1.0",
+///     "bundle": {"id": "family.safe.guide", "content_hash": "sha256:abc"},
+///     "issuer": {"id": "creed.space", "key_id": "k1"},
+///     "scope": {"environments": ["staging"]}
+/// }"#).unwrap();
+///
+/// assert_eq!(manifest.bundle.content_hash, "sha256:abc");
+/// assert_eq!(manifest.issuer.unwrap().key_id.as_deref(), Some("k1"));
+/// assert!(manifest.scope.unwrap().model_families.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vcp_version: Option<String>,
+    pub bundle: ManifestBundle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<ManifestIssuer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ManifestSignature>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety_attestation: Option<SafetyAttestation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamps: Option<ManifestTimestamps>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<TokenBudget>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<ManifestScope>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binding: Option<ManifestBinding>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<ManifestAttachment>,
+}
+
+/// The `bundle` section: what the manifest describes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestBundle {
+    /// Bundle identifier; required by the schema but not by
+    /// [`verify_bundle`], which only checks the hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// `sha256:<hex>` digest of the canonical content, or for a
+    /// multi-file bundle the [`compute_files_hash`] of [`files`](Self::files).
+    pub content_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_format: Option<String>,
+    /// BCP-47 language tag of the content, and of files that declare none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// The documents of a multi-file bundle, in injection order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<BundleFile>,
+}
+
+/// One document of a multi-file bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// Relative path, e.g. `annexes/privacy.md`.
+    pub path: String,
+    /// What the document is for, e.g. `core`, `annex` or `locale`;
+    /// [`BundleContents::select`] injects a subset by role.
+    pub role: String,
+    /// `sha256:<hex>` digest of the file's canonical content.
+    pub content_hash: String,
+    /// BCP-47 tag (e.g. `de-DE`) if this file is a language variant;
+    /// files without one are the default variant of their role.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+/// The `issuer` section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestIssuer {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+/// The issuer's `signature` section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    /// Base64 signature, optionally prefixed with `base64:`.
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signed_fields: Vec<String>,
+}
+
+/// The `safety_attestation` section.
+///
+/// `auditor` is optional here so that an attestation without one is
+/// reported as [`VerificationCode::InvalidAttestation`] rather than a
+/// schema error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafetyAttestation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auditor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auditor_key_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// The `timestamps` section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestTimestamps {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<DateTime<Utc>>,
+    /// Unique manifest ID used for replay detection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+}
+
+/// The `budget` section.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenBudget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer: Option<String>,
+    /// Largest share of the model context the content may take
+    /// (default 0.25).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_context_share: Option<f64>,
+}
+
+impl TokenBudget {
+    /// Share of the context window used when `max_context_share` is unset.
+    pub const DEFAULT_MAX_CONTEXT_SHARE: f64 = 0.25;
+
+    /// `max_context_share`, or [`DEFAULT_MAX_CONTEXT_SHARE`](Self::DEFAULT_MAX_CONTEXT_SHARE).
+    pub fn max_context_share(&self) -> f64 {
+        self.max_context_share
+            .unwrap_or(Self::DEFAULT_MAX_CONTEXT_SHARE)
+    }
+}
+
+/// The `scope` section. Empty lists do not restrict.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestScope {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_families: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub purposes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub environments: Vec<String>,
+}
+
+/// The `binding` section: which VCP/I tokens the bundle may be used as.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestBinding {
+    /// A [`TokenBinding`] such as `family.safe.guide@^1.2.0`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl ManifestBinding {
+    /// Parse [`token`](Self::token), if present.
+    ///
+    /// # Errors
+    ///
+    /// As [`TokenBinding::parse`].
+    pub fn token_binding(&self) -> VcpResult<Option<TokenBinding>> {
+        self.token.as_deref().map(TokenBinding::parse).transpose()
+    }
+}
+
+/// An extra file shipped alongside the content in a [`BundleArchive`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestAttachment {
+    /// File name inside the archive's `attachments/` directory.
+    pub name: String,
+    /// `sha256:<hex>` digest of the raw bytes (see [`hash_attachment`]).
+    pub hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+}
+
+impl Manifest {
+    /// Parse a manifest from JSON text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::JsonError`] if `json` is not valid JSON, or
+    /// [`VcpError::ParseError`] if a section is missing or mistyped.
+    pub fn from_json(json: &str) -> VcpResult<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        Self::from_value(&value)
+    }
+
+    /// Parse a manifest from an already-decoded JSON value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if a section is missing or mistyped.
+    pub fn from_value(value: &serde_json::Value) -> VcpResult<Self> {
+        Self::deserialize(value).map_err(|e| VcpError::ParseError(format!("invalid manifest: {e}")))
+    }
+
+    /// Check the rules serde cannot express.
+    ///
+    /// - `bundle.id` is present and `bundle.content_hash` is `sha256:`
+    ///   followed by 64 hex digits
+    /// - `nbf` and `iat` are not after `exp`
+    /// - `budget.max_context_share` is in `(0, 1]`
+    /// - `binding.token` is a well-formed [`TokenBinding`]
+    /// - `bundle.files` paths are unique relative paths with well-formed
+    ///   hashes, and `bundle.content_hash` is their [`compute_files_hash`]
+    /// - `bundle.locale` and file locales are well-formed BCP-47 tags
+    /// - attachment names are unique plain file names and their hashes
+    ///   are well-formed
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] naming the first rule broken.
+    pub fn validate(&self) -> VcpResult<()> {
+        if self.bundle.id.as_deref().is_none_or(str::is_empty) {
+            return Err(VcpError::ParseError(
+                "manifest bundle.id is required".into(),
+            ));
+        }
+        if !is_sha256_hash(&self.bundle.content_hash) {
+            return Err(VcpError::ParseError(format!(
+                "manifest bundle.content_hash must be sha256:<64 hex digits>, got {:?}",
+                self.bundle.content_hash
+            )));
+        }
+        if let Some(ts) = &self.timestamps {
+            if let Some(exp) = ts.exp {
+                for (name, at) in [("nbf", ts.nbf), ("iat", ts.iat)] {
+                    if at.is_some_and(|at| at > exp) {
+                        return Err(VcpError::ParseError(format!(
+                            "manifest timestamps.{name} is after timestamps.exp"
+                        )));
+                    }
+                }
+            }
+        }
+        if let Some(share) = self.budget.as_ref().and_then(|b| b.max_context_share) {
+            if !(share > 0.0 && share <= 1.0) {
+                return Err(VcpError::ParseError(format!(
+                    "manifest budget.max_context_share must be in (0, 1], got {share}"
+                )));
+            }
+        }
+        if let Some(binding) = &self.binding {
+            binding.token_binding().map_err(|e| {
+                VcpError::ParseError(format!("manifest binding.token is invalid: {e}"))
+            })?;
+        }
+        let mut paths = HashSet::new();
+        for file in &self.bundle.files {
+            if !is_relative_path(&file.path) || !paths.insert(&file.path) {
+                return Err(VcpError::ParseError(format!(
+                    "manifest bundle file path {:?} is invalid or repeated",
+                    file.path
+                )));
+            }
+            if !is_sha256_hash(&file.content_hash) {
+                return Err(VcpError::ParseError(format!(
+                    "manifest bundle file {:?} content_hash must be sha256:<64 hex digits>",
+                    file.path
+                )));
+            }
+        }
+        let locales = self
+            .bundle
+            .locale
+            .iter()
+            .chain(self.bundle.files.iter().filter_map(|f| f.locale.as_ref()));
+        for locale in locales {
+            if !is_language_tag(locale) {
+                return Err(VcpError::ParseError(format!(
+                    "manifest locale {locale:?} is not a BCP-47 language tag"
+                )));
+            }
+        }
+        if !self.bundle.files.is_empty()
+            && compute_files_hash(&self.bundle.files) != self.bundle.content_hash
+        {
+            return Err(VcpError::ParseError(
+                "manifest bundle.content_hash does not cover bundle.files".into(),
+            ));
+        }
+        let mut names = HashSet::new();
+        for attachment in &self.attachments {
+            if !is_attachment_name(&attachment.name) || !names.insert(&attachment.name) {
+                return Err(VcpError::ParseError(format!(
+                    "manifest attachment name {:?} is invalid or repeated",
+                    attachment.name
+                )));
+            }
+            if !is_sha256_hash(&attachment.hash) {
+                return Err(VcpError::ParseError(format!(
+                    "manifest attachment {:?} hash must be sha256:<64 hex digits>",
+                    attachment.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_sha256_hash(hash: &str) -> bool {
+    hash.strip_prefix("sha256:")
+        .is_some_and(|hex| hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// A `/`-separated relative path with no empty, `.` or `..` segments.
+fn is_relative_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.contains(['\\', '\0'])
+        && path
+            .split('/')
+            .all(|segment| !matches!(segment, "" | "." | ".."))
+}
+
+/// Structural BCP-47 check: a 2-8 letter language subtag followed by
+/// 1-8 character alphanumeric subtags.
+fn is_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    subtags.next().is_some_and(|lang| {
+        (2..=8).contains(&lang.len()) && lang.bytes().all(|b| b.is_ascii_alphabetic())
+    }) && subtags
+        .all(|s| (1..=8).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric()))
+}
+
+/// A single path component that is safe to write to disk.
+fn is_attachment_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_ATTACHMENT_NAME
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', '\0'])
+}
+
+// ── Bundle verification ─────────────────────────────────────
+
+/// Result of a bundle verification check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationResult {
+    pub code: VerificationCode,
+    pub message: String,
+}
+
+impl VerificationResult {
+    /// Create a successful verification result.
+    #[must_use]
+    pub fn valid() -> Self {
+        Self {
+            code: VerificationCode::Valid,
+            message: "verification passed".into(),
+        }
+    }
+
+    /// Create a failed result with a specific code.
     #[must_use]
     pub fn fail(code: VerificationCode, message: impl Into<String>) -> Self {
         Self {
@@ -347,51 +1823,736 @@ impl<'de> Deserialize<'de> for VerificationCode {
             )),
         }
     }
-}
+}
+
+/// Verify that the content hash in a bundle matches the actual content.
+pub fn verify_bundle_content(content: &str, expected_hash: &str) -> VerificationResult {
+    check_computed_hash(compute_content_hash(content), expected_hash)
+}
+
+fn check_computed_hash(computed: VcpResult<String>, expected_hash: &str) -> VerificationResult {
+    match computed {
+        Ok(computed) => {
+            if computed == expected_hash {
+                VerificationResult::valid()
+            } else {
+                VerificationResult::fail(
+                    VerificationCode::HashMismatch,
+                    format!("expected {expected_hash}, got {computed}"),
+                )
+            }
+        }
+        Err(e) => VerificationResult::fail(
+            VerificationCode::InvalidSchema,
+            format!("content canonicalization failed: {e}"),
+        ),
+    }
+}
+
+/// Verify a bundle manifest JSON against its content.
+///
+/// Checks:
+/// 1. Content hash matches `bundle.content_hash`.
+/// 2. Manifest is well-formed JSON with required fields.
+///
+/// # Errors
+///
+/// Returns [`VcpError::JsonError`] if `manifest_json` is not valid JSON,
+/// or [`VcpError::ParseError`] if the manifest is missing required fields.
+pub fn verify_bundle(manifest_json: &str, content: &str) -> VcpResult<VerificationResult> {
+    let manifest = Manifest::from_json(manifest_json)?;
+    Ok(verify_bundle_content(
+        content,
+        &manifest.bundle.content_hash,
+    ))
+}
+
+/// Verify a bundle manifest JSON against content already fed through a
+/// [`ContentHasher`].
+///
+/// Lets a host stream large content in chunks instead of holding it as
+/// one string; the result is the same as [`verify_bundle`] on the
+/// concatenated chunks.
+///
+/// # Errors
+///
+/// As for [`verify_bundle`].
+pub fn verify_bundle_streaming(
+    manifest_json: &str,
+    hasher: ContentHasher,
+) -> VcpResult<VerificationResult> {
+    let manifest = Manifest::from_json(manifest_json)?;
+    Ok(check_computed_hash(
+        hasher.finalize(),
+        &manifest.bundle.content_hash,
+    ))
+}
+
+// ── Multi-file bundles ──────────────────────────────────────
+
+/// The `bundle.content_hash` of a multi-file bundle.
+///
+/// Hashes one `<content_hash> <path>` line per file, in order, so the
+/// signed manifest commits to every file, its path and the injection
+/// order. Roles are covered by the manifest signature itself.
+pub fn compute_files_hash(files: &[BundleFile]) -> String {
+    let mut digest = Sha256::new();
+    for file in files {
+        digest.update(file.content_hash.as_bytes());
+        digest.update(b" ");
+        digest.update(file.path.as_bytes());
+        digest.update(b"\n");
+    }
+    format!("sha256:{:x}", digest.finalize())
+}
+
+/// The documents of a multi-file bundle, by path.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::{compute_content_hash, compute_files_hash, BundleContents, BundleFile, Manifest};
+///
+/// let file = |path: &str, role: &str, text: &str| BundleFile {
+///     path: path.into(),
+///     role: role.into(),
+///     content_hash: compute_content_hash(text).unwrap(),
+///     locale: None,
+/// };
+/// let files = vec![
+///     file("core.md", "core", "Be kind.\n"),
+///     file("annexes/medical.md", "annex", "No diagnoses.\n"),
+/// ];
+/// let manifest = Manifest::from_value(&serde_json::json!({
+///     "bundle": {"id": "demo", "content_hash": compute_files_hash(&files), "files": files},
+/// })).unwrap();
+///
+/// let contents = BundleContents::new()
+///     .with_file("core.md", "Be kind.\n")
+///     .with_file("annexes/medical.md", "No diagnoses.\n");
+/// assert!(contents.verify(&manifest).is_valid());
+/// assert_eq!(contents.select(&manifest, &["core"]), "Be kind.\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundleContents {
+    files: BTreeMap<String, String>,
+}
+
+impl BundleContents {
+    /// No files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add (or replace) a file.
+    #[must_use]
+    pub fn with_file(mut self, path: impl Into<String>, text: impl Into<String>) -> Self {
+        self.insert(path, text);
+        self
+    }
+
+    /// Add (or replace) a file in place.
+    pub fn insert(&mut self, path: impl Into<String>, text: impl Into<String>) {
+        self.files.insert(path.into(), text.into());
+    }
+
+    /// The text of `path`, if present.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    /// Total size of all files in bytes.
+    pub fn total_len(&self) -> usize {
+        self.files.values().map(String::len).sum()
+    }
+
+    /// Check every file against `manifest.bundle.files`.
+    ///
+    /// Fails with [`VerificationCode::InvalidSchema`] if the manifest lists
+    /// no files or a file is missing or unlisted, and with
+    /// [`VerificationCode::HashMismatch`] if a file's content differs.
+    pub fn verify(&self, manifest: &Manifest) -> VerificationResult {
+        let listed = &manifest.bundle.files;
+        if listed.is_empty() {
+            return VerificationResult::fail(
+                VerificationCode::InvalidSchema,
+                "manifest lists no bundle.files",
+            );
+        }
+        if compute_files_hash(listed) != manifest.bundle.content_hash {
+            return VerificationResult::fail(
+                VerificationCode::HashMismatch,
+                "bundle.content_hash does not cover bundle.files",
+            );
+        }
+        for file in listed {
+            let Some(text) = self.get(&file.path) else {
+                return VerificationResult::fail(
+                    VerificationCode::InvalidSchema,
+                    format!("missing bundle file {}", file.path),
+                );
+            };
+            let result = verify_bundle_content(text, &file.content_hash);
+            if !result.is_valid() {
+                return VerificationResult::fail(
+                    result.code,
+                    format!("{}: {}", file.path, result.message),
+                );
+            }
+        }
+        if let Some(extra) = self
+            .files
+            .keys()
+            .find(|path| !listed.iter().any(|f| &f.path == *path))
+        {
+            return VerificationResult::fail(
+                VerificationCode::InvalidSchema,
+                format!("file {extra} is not listed in bundle.files"),
+            );
+        }
+        VerificationResult::valid()
+    }
+
+    /// Concatenate, in manifest order, the files whose role is in
+    /// `roles`, for injection. An empty `roles` selects every file.
+    ///
+    /// Files are separated by a newline where one is not already present.
+    /// Only call this after [`verify`](Self::verify) has passed.
+    pub fn select(&self, manifest: &Manifest, roles: &[&str]) -> String {
+        let mut out = String::new();
+        for file in &manifest.bundle.files {
+            if !roles.is_empty() && !roles.contains(&file.role.as_str()) {
+                continue;
+            }
+            if let Some(text) = self.get(&file.path) {
+                push_document(&mut out, text);
+            }
+        }
+        out
+    }
+}
+
+/// Tags to try for `locale`, most specific first: `de-CH-1996` gives
+/// `de-ch-1996`, `de-ch`, `de`.
+///
+/// Tags are lowercased, since BCP-47 matching is case-insensitive.
+pub fn locale_fallback_chain(locale: &str) -> Vec<String> {
+    let locale = locale.trim().to_ascii_lowercase();
+    let mut chain = Vec::new();
+    let mut tag = locale.as_str();
+    while !tag.is_empty() {
+        chain.push(tag.to_string());
+        tag = tag.rsplit_once('-').map_or("", |(prefix, _)| prefix);
+    }
+    chain
+}
+
+/// The files chosen for one locale by [`select_content_for_locale`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocaleSelection {
+    /// The most specific tag any file matched, as written in the
+    /// manifest, or `None` if only default variants were chosen.
+    pub locale: Option<String>,
+    /// Paths of the chosen files, in manifest order.
+    pub paths: Vec<String>,
+    /// Their concatenated text, as for [`BundleContents::select`].
+    pub text: String,
+}
+
+/// Pick the language variant of each role that best fits `locale`.
+///
+/// For every role the first tag of [`locale_fallback_chain`] that some
+/// file of that role declares wins (`de-DE`, then `de`); failing that,
+/// files in `bundle.locale` or with no locale are used. Roles are never
+/// dropped for lack of a translation as long as a default exists.
+///
+/// Verify the files first with [`BundleContents::verify`] (or
+/// [`Orchestrator::verify_files`](crate::orchestrator::Orchestrator::verify_files)):
+/// every variant is covered by one verification.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::{compute_files_hash, select_content_for_locale, BundleContents, BundleFile, Manifest};
+///
+/// let file = |path: &str, locale: Option<&str>| BundleFile {
+///     path: path.into(),
+///     role: "core".into(),
+///     content_hash: format!("sha256:{}", "0".repeat(64)),
+///     locale: locale.map(Into::into),
+/// };
+/// let files = vec![file("core.md", None), file("core.de.md", Some("de"))];
+/// let manifest = Manifest::from_value(&serde_json::json!({
+///     "bundle": {"id": "demo", "content_hash": compute_files_hash(&files), "locale": "en", "files": files},
+/// })).unwrap();
+/// let contents = BundleContents::new()
+///     .with_file("core.md", "Be kind.\n")
+///     .with_file("core.de.md", "Sei freundlich.\n");
+///
+/// let de = select_content_for_locale(&manifest, &contents, "de-DE");
+/// assert_eq!(de.locale.as_deref(), Some("de"));
+/// assert_eq!(de.text, "Sei freundlich.\n");
+///
+/// let fr = select_content_for_locale(&manifest, &contents, "fr-FR");
+/// assert_eq!(fr.locale, None);
+/// assert_eq!(fr.paths, ["core.md"]);
+/// ```
+pub fn select_content_for_locale(
+    manifest: &Manifest,
+    contents: &BundleContents,
+    locale: &str,
+) -> LocaleSelection {
+    let files = &manifest.bundle.files;
+    let chain = locale_fallback_chain(locale);
+    let default = manifest.bundle.locale.as_deref();
+    let matches = |file: &BundleFile, tag: Option<&str>| match (file.locale.as_deref(), tag) {
+        (Some(declared), Some(tag)) => declared.eq_ignore_ascii_case(tag),
+        (declared, None) => declared.is_none() || declared == default,
+        (None, Some(_)) => false,
+    };
+
+    let mut chosen = vec![false; files.len()];
+    let mut best: Option<(usize, &str)> = None;
+    let mut seen_roles = HashSet::new();
+    for file in files {
+        if !seen_roles.insert(file.role.as_str()) {
+            continue;
+        }
+        let levels = chain
+            .iter()
+            .map(|tag| Some(tag.as_str()))
+            .chain(std::iter::once(None));
+        for (level, tag) in levels.enumerate() {
+            let mut any = false;
+            for (i, candidate) in files.iter().enumerate() {
+                if candidate.role == file.role && matches(candidate, tag) {
+                    chosen[i] = true;
+                    any = true;
+                    if tag.is_some() && best.is_none_or(|(b, _)| level < b) {
+                        best = candidate.locale.as_deref().map(|l| (level, l));
+                    }
+                }
+            }
+            if any {
+                break;
+            }
+        }
+    }
+
+    let mut selection = LocaleSelection {
+        locale: best.map(|(_, tag)| tag.to_string()),
+        paths: Vec::new(),
+        text: String::new(),
+    };
+    for (file, _) in files.iter().zip(&chosen).filter(|(_, &c)| c) {
+        if let Some(text) = contents.get(&file.path) {
+            push_document(&mut selection.text, text);
+            selection.paths.push(file.path.clone());
+        }
+    }
+    selection
+}
+
+/// Append a document, starting it on a new line.
+fn push_document(out: &mut String, text: &str) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(text);
+}
+
+/// Verify a multi-file bundle manifest JSON against its files.
+///
+/// # Errors
+///
+/// As [`verify_bundle`].
+pub fn verify_bundle_files(
+    manifest_json: &str,
+    contents: &BundleContents,
+) -> VcpResult<VerificationResult> {
+    Ok(contents.verify(&Manifest::from_json(manifest_json)?))
+}
+
+// ── Bundle archives (.vcpb) ─────────────────────────────────
+
+/// File extension for [`BundleArchive`]s.
+pub const ARCHIVE_EXTENSION: &str = "vcpb";
+
+/// Version recorded in a [`BundleArchive`]'s `index.json`.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Longest attachment name: keeps `attachments/<name>` within a ustar
+/// header's 100-byte name field.
+const MAX_ATTACHMENT_NAME: usize = 100 - ATTACHMENTS_DIR.len();
+
+const INDEX_MEMBER: &str = "index.json";
+const MANIFEST_MEMBER: &str = "manifest.json";
+const CONTENT_MEMBER: &str = "content";
+const ATTACHMENTS_DIR: &str = "attachments/";
+const ARCHIVE_FORMAT: &str = "vcpb";
+const TAR_BLOCK: usize = 512;
+
+/// `sha256:<hex>` digest of raw attachment bytes, as listed in
+/// [`ManifestAttachment::hash`].
+///
+/// Unlike [`compute_content_hash`] no canonicalization is applied:
+/// attachments may be binary.
+pub fn hash_attachment(bytes: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(bytes))
+}
+
+/// A manifest, its content and any attachments as a single artifact.
+///
+/// [`pack`](Self::pack) writes a ustar archive that standard `tar` can
+/// list and extract:
+///
+/// | Member | Contents |
+/// |--------|----------|
+/// | `index.json` | Format version plus size and SHA-256 of every other member |
+/// | `manifest.json` | The manifest, byte for byte |
+/// | `content` | The constitution text |
+/// | `attachments/<name>` | One per entry in the manifest's `attachments` |
+///
+/// Both [`pack`](Self::pack) and [`unpack`](Self::unpack) run
+/// [`verify`](Self::verify), so an archive that round-trips is
+/// internally consistent. Signature and trust checks remain the
+/// orchestrator's job.
+///
+/// # Examples
+///
+/// ```
+/// use vcp_core::transport::{compute_content_hash, hash_attachment, BundleArchive};
+///
+/// let content = "Be kind.\n";
+/// let logo = b"\x89PNG...";
+/// let manifest = serde_json::json!({
+///     "vcp_version": "2.0",
+///     "bundle": {"id": "demo", "content_hash": compute_content_hash(content).unwrap()},
+///     "attachments": [{"name": "logo.png", "hash": hash_attachment(logo)}],
+/// });
+///
+/// let archive = BundleArchive::new(manifest.to_string(), content)
+///     .with_attachment("logo.png", logo.to_vec());
+/// let bytes = archive.pack().unwrap();
+///
+/// let unpacked = BundleArchive::unpack(&bytes).unwrap();
+/// assert_eq!(unpacked, archive);
+/// assert_eq!(unpacked.attachments["logo.png"], logo);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleArchive {
+    /// Manifest JSON text, kept verbatim.
+    pub manifest_json: String,
+    pub content: String,
+    /// Attachment bytes by name.
+    pub attachments: BTreeMap<String, Vec<u8>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveIndex {
+    format: String,
+    version: u32,
+    members: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexEntry {
+    path: String,
+    size: u64,
+    hash: String,
+}
+
+impl BundleArchive {
+    /// An archive with no attachments.
+    pub fn new(manifest_json: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            manifest_json: manifest_json.into(),
+            content: content.into(),
+            attachments: BTreeMap::new(),
+        }
+    }
+
+    /// Add (or replace) an attachment.
+    #[must_use]
+    pub fn with_attachment(mut self, name: impl Into<String>, bytes: Vec<u8>) -> Self {
+        self.attachments.insert(name.into(), bytes);
+        self
+    }
+
+    /// Parse the typed manifest.
+    ///
+    /// # Errors
+    ///
+    /// As [`Manifest::from_json`].
+    pub fn manifest(&self) -> VcpResult<Manifest> {
+        Manifest::from_json(&self.manifest_json)
+    }
+
+    /// Check every member against the manifest.
+    ///
+    /// - the manifest parses and passes [`Manifest::validate`]
+    /// - the content matches `bundle.content_hash`
+    /// - the attachments are exactly those the manifest lists, each
+    ///   matching its hash
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::HashMismatch`] for content or attachment bytes
+    /// that do not match the manifest, and [`VcpError::ParseError`] (or
+    /// [`VcpError::JsonError`]) for anything else.
+    pub fn verify(&self) -> VcpResult<()> {
+        let manifest = self.manifest()?;
+        manifest.validate()?;
+
+        let actual = compute_content_hash(&self.content)?;
+        if actual != manifest.bundle.content_hash {
+            return Err(VcpError::HashMismatch {
+                expected: manifest.bundle.content_hash,
+                actual,
+            });
+        }
+
+        for listed in &manifest.attachments {
+            let bytes = self.attachments.get(&listed.name).ok_or_else(|| {
+                VcpError::ParseError(format!(
+                    "attachment {:?} is listed in the manifest but missing",
+                    listed.name
+                ))
+            })?;
+            let actual = hash_attachment(bytes);
+            if actual != listed.hash {
+                return Err(VcpError::HashMismatch {
+                    expected: listed.hash.clone(),
+                    actual,
+                });
+            }
+        }
+        if let Some(extra) = self
+            .attachments
+            .keys()
+            .find(|name| !manifest.attachments.iter().any(|a| &a.name == *name))
+        {
+            return Err(VcpError::ParseError(format!(
+                "attachment {extra:?} is not listed in the manifest"
+            )));
+        }
+        Ok(())
+    }
 
-/// Verify that the content hash in a bundle matches the actual content.
-pub fn verify_bundle_content(content: &str, expected_hash: &str) -> VerificationResult {
-    match compute_content_hash(content) {
-        Ok(computed) => {
-            if computed == expected_hash {
-                VerificationResult::valid()
+    /// Verify and write the archive.
+    ///
+    /// Output is deterministic: members are written in a fixed order with
+    /// zero timestamps and ownership.
+    ///
+    /// # Errors
+    ///
+    /// As [`verify`](Self::verify).
+    pub fn pack(&self) -> VcpResult<Vec<u8>> {
+        self.verify()?;
+
+        let mut members: Vec<(String, &[u8])> = vec![
+            (MANIFEST_MEMBER.into(), self.manifest_json.as_bytes()),
+            (CONTENT_MEMBER.into(), self.content.as_bytes()),
+        ];
+        members.extend(
+            self.attachments
+                .iter()
+                .map(|(name, bytes)| (format!("{ATTACHMENTS_DIR}{name}"), bytes.as_slice())),
+        );
+
+        let index = ArchiveIndex {
+            format: ARCHIVE_FORMAT.into(),
+            version: ARCHIVE_VERSION,
+            members: members
+                .iter()
+                .map(|(path, bytes)| IndexEntry {
+                    path: path.clone(),
+                    size: bytes.len() as u64,
+                    hash: hash_attachment(bytes),
+                })
+                .collect(),
+        };
+        let index = serde_json::to_vec_pretty(&index)?;
+
+        let mut out = Vec::new();
+        write_tar_member(&mut out, INDEX_MEMBER, &index);
+        for (path, bytes) in &members {
+            write_tar_member(&mut out, path, bytes);
+        }
+        // End-of-archive marker: two zero blocks.
+        out.resize(out.len() + 2 * TAR_BLOCK, 0);
+        Ok(out)
+    }
+
+    /// Read and verify an archive written by [`pack`](Self::pack).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] if the archive is malformed, its
+    /// index does not describe its members, or a required member is
+    /// missing; [`VcpError::HashMismatch`] if a member does not match
+    /// the index or the manifest.
+    pub fn unpack(bytes: &[u8]) -> VcpResult<Self> {
+        let mut members = read_tar_members(bytes)?;
+
+        let index = members
+            .first()
+            .filter(|(path, _)| path == INDEX_MEMBER)
+            .ok_or_else(|| archive_error(format!("first member must be {INDEX_MEMBER}")))?;
+        let index: ArchiveIndex = serde_json::from_slice(&index.1)?;
+        if index.format != ARCHIVE_FORMAT || index.version != ARCHIVE_VERSION {
+            return Err(archive_error(format!(
+                "unsupported format {} v{}",
+                index.format, index.version
+            )));
+        }
+        members.remove(0);
+        if index.members.len() != members.len() {
+            return Err(archive_error(format!(
+                "index lists {} members, archive has {}",
+                index.members.len(),
+                members.len()
+            )));
+        }
+        for (entry, (path, data)) in index.members.iter().zip(&members) {
+            if &entry.path != path || entry.size != data.len() as u64 {
+                return Err(archive_error(format!(
+                    "member {path:?} does not match its index entry"
+                )));
+            }
+            let actual = hash_attachment(data);
+            if actual != entry.hash {
+                return Err(VcpError::HashMismatch {
+                    expected: entry.hash.clone(),
+                    actual,
+                });
+            }
+        }
+
+        let mut manifest_json = None;
+        let mut content = None;
+        let mut attachments = BTreeMap::new();
+        for (path, data) in members {
+            let text = || {
+                String::from_utf8(data.clone())
+                    .map_err(|_| archive_error(format!("{path} is not valid UTF-8")))
+            };
+            if path == MANIFEST_MEMBER && manifest_json.is_none() {
+                manifest_json = Some(text()?);
+            } else if path == CONTENT_MEMBER && content.is_none() {
+                content = Some(text()?);
+            } else if let Some(name) = path
+                .strip_prefix(ATTACHMENTS_DIR)
+                .filter(|name| is_attachment_name(name) && !attachments.contains_key(*name))
+            {
+                attachments.insert(name.to_string(), data);
             } else {
-                VerificationResult::fail(
-                    VerificationCode::HashMismatch,
-                    format!("expected {expected_hash}, got {computed}"),
-                )
+                return Err(archive_error(format!("unexpected member {path:?}")));
             }
         }
-        Err(e) => VerificationResult::fail(
-            VerificationCode::InvalidSchema,
-            format!("content canonicalization failed: {e}"),
-        ),
+
+        let archive = Self {
+            manifest_json: manifest_json
+                .ok_or_else(|| archive_error(format!("missing {MANIFEST_MEMBER}")))?,
+            content: content.ok_or_else(|| archive_error(format!("missing {CONTENT_MEMBER}")))?,
+            attachments,
+        };
+        archive.verify()?;
+        Ok(archive)
     }
 }
 
-/// Verify a bundle manifest JSON against its content.
-///
-/// Checks:
-/// 1. Content hash matches `bundle.content_hash`.
-/// 2. Manifest is well-formed JSON with required fields.
-///
-/// # Errors
-///
-/// Returns [`VcpError::JsonError`] if `manifest_json` is not valid JSON,
-/// or [`VcpError::ParseError`] if the manifest is missing required fields.
-pub fn verify_bundle(manifest_json: &str, content: &str) -> VcpResult<VerificationResult> {
-    let manifest: serde_json::Value = serde_json::from_str(manifest_json)?;
+fn archive_error(message: impl std::fmt::Display) -> VcpError {
+    VcpError::ParseError(format!("invalid bundle archive: {message}"))
+}
 
-    let bundle = manifest
-        .get("bundle")
-        .ok_or_else(|| VcpError::ParseError("missing 'bundle' field in manifest".into()))?;
+/// Append one regular-file member in ustar format.
+fn write_tar_member(out: &mut Vec<u8>, path: &str, data: &[u8]) {
+    let mut header = [0u8; TAR_BLOCK];
+    header[..path.len()].copy_from_slice(path.as_bytes());
+    header[100..107].copy_from_slice(b"0000644");
+    header[108..115].copy_from_slice(b"0000000");
+    header[116..123].copy_from_slice(b"0000000");
+    header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+    header[136..147].copy_from_slice(b"00000000000");
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[148..156].fill(b' ');
+    let checksum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+    header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+
+    out.extend_from_slice(&header);
+    out.extend_from_slice(data);
+    out.resize(out.len().next_multiple_of(TAR_BLOCK), 0);
+}
 
-    let expected_hash = bundle
-        .get("content_hash")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| VcpError::ParseError("missing 'bundle.content_hash' in manifest".into()))?;
+/// Regular-file members in archive order. Directory entries are skipped;
+/// links and other special entries are rejected.
+fn read_tar_members(bytes: &[u8]) -> VcpResult<Vec<(String, Vec<u8>)>> {
+    let mut members = Vec::new();
+    let mut offset = 0;
+    while let Some(header) = bytes.get(offset..offset + TAR_BLOCK) {
+        if header.iter().all(|&b| b == 0) {
+            return Ok(members);
+        }
+        let field = |range: std::ops::Range<usize>| {
+            let raw = &header[range];
+            let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+            std::str::from_utf8(&raw[..end])
+                .map(str::trim)
+                .map_err(|_| archive_error(format!("non-UTF-8 header at byte {offset}")))
+        };
+        let octal = |range: std::ops::Range<usize>| -> VcpResult<u64> {
+            let text = field(range)?;
+            u64::from_str_radix(if text.is_empty() { "0" } else { text }, 8)
+                .map_err(|_| archive_error(format!("bad number {text:?} at byte {offset}")))
+        };
+
+        let stored = octal(148..156)?;
+        let computed: u64 = header
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                if (148..156).contains(&i) {
+                    32
+                } else {
+                    u64::from(b)
+                }
+            })
+            .sum();
+        if stored != computed {
+            return Err(archive_error(format!(
+                "bad header checksum at byte {offset}"
+            )));
+        }
 
-    Ok(verify_bundle_content(content, expected_hash))
+        let mut path = field(0..100)?.to_string();
+        let prefix = field(345..500)?;
+        if !prefix.is_empty() {
+            path = format!("{prefix}/{path}");
+        }
+        let size = usize::try_from(octal(124..136)?)
+            .map_err(|_| archive_error(format!("member {path:?} is too large")))?;
+        let start = offset + TAR_BLOCK;
+        let data = start
+            .checked_add(size)
+            .and_then(|end| bytes.get(start..end))
+            .ok_or_else(|| archive_error(format!("member {path:?} is truncated")))?;
+
+        match header[156] {
+            b'0' | 0 => members.push((path, data.to_vec())),
+            b'5' => {}
+            other => {
+                return Err(archive_error(format!(
+                    "member {path:?} has unsupported type {:?}",
+                    char::from(other)
+                )))
+            }
+        }
+        offset = (start + size).next_multiple_of(TAR_BLOCK);
+    }
+    Err(archive_error("missing end-of-archive marker"))
 }
 
 // ── Tests ───────────────────────────────────────────────────
@@ -399,8 +2560,150 @@ pub fn verify_bundle(manifest_json: &str, content: &str) -> VcpResult<Verificati
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ed25519_dalek::SigningKey;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn report_lists_changes_by_line() {
+        let input = "Be kind.\r\nBe caf\u{65}\u{301}.  \rBe honest.\t\n\n  \n";
+        let report = canonicalize_with_report(input).unwrap();
+        assert_eq!(report.bytes, canonicalize_content(input).unwrap());
+        let change = |kind, count, lines: &[usize]| ContentChange {
+            kind,
+            count,
+            lines: lines.to_vec(),
+        };
+        assert_eq!(
+            report.changes,
+            [
+                change(ContentChangeKind::UnicodeNormalized, 1, &[2]),
+                change(ContentChangeKind::CrlfLineEnding, 1, &[1]),
+                change(ContentChangeKind::CrLineEnding, 1, &[2]),
+                change(ContentChangeKind::TrailingWhitespace, 5, &[2, 3, 5]),
+                change(ContentChangeKind::TrailingBlankLine, 2, &[4, 5]),
+            ]
+        );
+        assert_eq!(
+            report.changes[3].to_string(),
+            "trailing whitespace: 5 on lines 2, 3, 5"
+        );
+    }
+
+    #[test]
+    fn report_is_empty_exactly_when_input_is_canonical() {
+        for input in [
+            "hello\n",
+            "hello",
+            "",
+            "\n",
+            "\n\n",
+            "a\n  ",
+            "  ",
+            "a\n\nb\n",
+            "a\r\n",
+            "x \n",
+            "\u{e9}\n",
+            "e\u{301}\n",
+        ] {
+            let report = canonicalize_with_report(input).unwrap();
+            assert_eq!(
+                report.is_unchanged(),
+                report.bytes == input.as_bytes(),
+                "{input:?}: {:?}",
+                report.changes
+            );
+        }
+        let report = canonicalize_with_report("a").unwrap();
+        assert_eq!(
+            report.changes[0].kind,
+            ContentChangeKind::MissingFinalNewline
+        );
+        assert_eq!(report.changes[0].lines, [1]);
+        assert!(canonicalize_with_report("bad\u{7}").is_err());
+
+        let crlf = canonicalize_with_report(&"line\r\n".repeat(12)).unwrap();
+        assert_eq!(
+            crlf.changes[0].to_string(),
+            "CRLF line endings: 12 on lines 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 (+2 more)"
+        );
+    }
+
+    #[test]
+    fn decode_content_detects_bom_and_utf16() {
+        let text = "Be kind to caf\u{e9} staff.\r\n";
+        let le: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let be: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        let with = |prefix: &[u8], body: &[u8]| [prefix, body].concat();
+
+        for (bytes, encoding) in [
+            (text.as_bytes().to_vec(), TextEncoding::Utf8),
+            (
+                with(b"\xEF\xBB\xBF", text.as_bytes()),
+                TextEncoding::Utf8Bom,
+            ),
+            (with(b"\xFF\xFE", &le), TextEncoding::Utf16Le),
+            (with(b"\xFE\xFF", &be), TextEncoding::Utf16Be),
+            (le.clone(), TextEncoding::Utf16Le),
+            (be.clone(), TextEncoding::Utf16Be),
+        ] {
+            let decoded = decode_content(&bytes).unwrap();
+            assert_eq!(decoded.encoding, encoding);
+            assert_eq!(decoded.text, text, "{encoding}");
+        }
+
+        // Emoji need surrogate pairs.
+        let emoji: Vec<u8> = "\u{1F600}"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        assert_eq!(
+            decode_content(&with(b"\xFF\xFE", &emoji)).unwrap().text,
+            "\u{1F600}"
+        );
+
+        assert_eq!(decode_content(b"").unwrap().encoding, TextEncoding::Utf8);
+        assert_eq!(decode_content(b"ab").unwrap().encoding, TextEncoding::Utf8);
+        assert!(decode_content(b"\xFF\xFEa").is_err());
+        assert!(decode_content(b"\xFF\xFE\x00\xD8").is_err());
+        assert!(decode_content(b"caf\xE9").is_err());
+    }
+
+    #[test]
+    fn update_reader_matches_whole_file_hash_across_chunk_sizes() {
+        let text = "Be kind to caf\u{e9} staff \u{1F600}.  \r\n".repeat(40);
+        let expected = compute_content_hash(&text).unwrap();
+        let le: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let be: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        let bom = [b"\xEF\xBB\xBF".as_slice(), text.as_bytes()].concat();
+
+        for (bytes, encoding) in [
+            (text.as_bytes(), TextEncoding::Utf8),
+            (bom.as_slice(), TextEncoding::Utf8Bom),
+            (le.as_slice(), TextEncoding::Utf16Le),
+            (be.as_slice(), TextEncoding::Utf16Be),
+        ] {
+            for chunk in [3, 5, 64, READ_CHUNK] {
+                let mut hasher = ContentHasher::new();
+                let summary = hasher.update_reader_chunked(bytes, chunk).unwrap();
+                assert_eq!(summary.encoding, encoding, "chunk {chunk}");
+                assert_eq!(summary.text_len, text.len() as u64);
+                assert_eq!(
+                    hasher.finalize().unwrap(),
+                    expected,
+                    "{encoding}, chunk {chunk}"
+                );
+            }
+        }
+
+        let mut hasher = ContentHasher::new();
+        assert!(hasher.update_reader(&b"\xFF\xFEa"[..]).is_err());
+        let mut hasher = ContentHasher::new();
+        assert!(hasher.update_reader(&b"\xFF\xFE\x3D\xD8"[..]).is_err());
+    }
+
     #[test]
     fn canonicalize_normalizes_line_endings() {
         let input = "hello\r\nworld\rfoo\n";
@@ -488,6 +2791,103 @@ mod tests {
         assert_eq!(h1, h2);
     }
 
+    // ── Streaming canonicalization ──────────────────────────
+
+    /// Inputs that exercise every boundary case of the streaming path.
+    const STREAM_CASES: &[&str] = &[
+        "",
+        "plain text",
+        "caf\u{0065}\u{0301} cr\u{0065}\u{0300}me\r\n",
+        "a\r\nb\rc\n\r\n",
+        "trailing   \t\n  \n\n",
+        "\u{1100}\u{1161}\u{11A8}\u{1100}\u{1161}",
+        "  indented\n\tline \u{00C5}\u{212B}\n",
+        "\u{65E5}\u{672C}\u{8A9E}\u{306E}\u{6587}",
+    ];
+
+    fn chunked(text: &str, at: usize) -> VcpResult<Vec<u8>> {
+        let mut canon = ContentCanonicalizer::new(Vec::new());
+        canon.update(&text[..at])?;
+        canon.update(&text[at..])?;
+        canon.finish()
+    }
+
+    #[test]
+    fn streaming_matches_whole_at_every_split() {
+        for text in STREAM_CASES {
+            let whole = canonicalize_content(text).unwrap();
+            for at in (0..=text.len()).filter(|i| text.is_char_boundary(*i)) {
+                assert_eq!(chunked(text, at).unwrap(), whole, "split {at} of {text:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_bytes_may_split_characters() {
+        let text = STREAM_CASES[2];
+        let whole = canonicalize_content(text).unwrap();
+        for at in 0..=text.len() {
+            let mut canon = ContentCanonicalizer::new(Vec::new());
+            canon.update_bytes(&text.as_bytes()[..at]).unwrap();
+            canon.update_bytes(&text.as_bytes()[at..]).unwrap();
+            assert_eq!(canon.finish().unwrap(), whole);
+        }
+    }
+
+    #[test]
+    fn streaming_rejects_bad_utf8() {
+        let mut canon = ContentCanonicalizer::new(Vec::new());
+        assert!(canon.update_bytes(b"ok \xFF").is_err());
+
+        let mut canon = ContentCanonicalizer::new(Vec::new());
+        canon.update_bytes(&"\u{00E9}".as_bytes()[..1]).unwrap();
+        assert!(canon.finish().is_err());
+    }
+
+    #[test]
+    fn streaming_error_positions_match() {
+        let text = "line one  \n\n  x\u{202E}y";
+        let whole = canonicalize_content(text).unwrap_err();
+        assert_eq!(
+            whole,
+            VcpError::ParseError("forbidden Unicode character at position 13: U+202E".into())
+        );
+        for at in (0..=text.len()).filter(|i| text.is_char_boundary(*i)) {
+            assert_eq!(chunked(text, at).unwrap_err(), whole);
+        }
+    }
+
+    #[test]
+    fn content_hasher_matches_compute_content_hash() {
+        for text in STREAM_CASES {
+            let mut hasher = ContentHasher::new();
+            for ch in text.chars() {
+                hasher.update(ch.encode_utf8(&mut [0u8; 4])).unwrap();
+            }
+            assert_eq!(
+                hasher.finalize().unwrap(),
+                compute_content_hash(text).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn canonicalize_to_writer_reports_io_errors() {
+        struct Broken;
+        impl Write for Broken {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk full"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        assert!(matches!(
+            canonicalize_to_writer("hello", Broken),
+            Err(VcpError::IoError(_))
+        ));
+    }
+
     #[test]
     fn manifest_canonicalization_excludes_signature() {
         let manifest = serde_json::json!({
@@ -522,6 +2922,79 @@ mod tests {
         assert!(m_pos < z_pos);
     }
 
+    fn jcs(value: &serde_json::Value) -> String {
+        String::from_utf8(canonicalize_json(value)).unwrap()
+    }
+
+    #[test]
+    fn jcs_numbers_use_ecmascript_form() {
+        let cases: &[(f64, &str)] = &[
+            (0.0, "0"),
+            (-0.0, "0"),
+            (1.0, "1"),
+            (-1.5, "-1.5"),
+            (123.456, "123.456"),
+            (0.000_001, "0.000001"),
+            (1e-7, "1e-7"),
+            (1e20, "100000000000000000000"),
+            (1e21, "1e+21"),
+            (1.5e300, "1.5e+300"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (f64::MAX, "1.7976931348623157e+308"),
+            (5e-324, "5e-324"),
+        ];
+        for (x, expected) in cases {
+            assert_eq!(jcs(&serde_json::json!(x)), *expected, "{x:e}");
+        }
+        // Integers beyond 2^53 round as they would in a JS peer.
+        assert_eq!(
+            jcs(&serde_json::json!(9_007_199_254_740_993_u64)),
+            "9007199254740992"
+        );
+    }
+
+    #[test]
+    fn jcs_strings_escape_minimally() {
+        let value = serde_json::json!("\"\\/\u{8}\u{c}\n\r\t\u{1}\u{1f}\u{7f}\u{e9}\u{20ac}");
+        assert_eq!(
+            jcs(&value),
+            "\"\\\"\\\\/\\b\\f\\n\\r\\t\\u0001\\u001f\u{7f}\u{e9}\u{20ac}\""
+        );
+    }
+
+    #[test]
+    fn jcs_sorts_keys_by_utf16_code_units() {
+        // U+E000 sorts after U+1F600 in UTF-8 but before it in UTF-16,
+        // where the emoji is a 0xD83D surrogate pair.
+        let value = serde_json::json!({"\u{e000}": 1, "\u{1f600}": 2, "a": 3});
+        assert_eq!(jcs(&value), "{\"a\":3,\"\u{1f600}\":2,\"\u{e000}\":1}");
+    }
+
+    #[test]
+    fn jcs_matches_rfc8785_example() {
+        // RFC 8785 §3.2.2, minus the string entry with a lone surrogate.
+        let value: serde_json::Value = serde_json::from_str(
+            r#"{"numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                "literals": [null, true, false]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            jcs(&value),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27]}"#
+        );
+    }
+
+    #[test]
+    fn manifest_canonicalization_formats_floats() {
+        let manifest = serde_json::json!({
+            "weight": 1.0,
+            "threshold": 1e21,
+            "signature": {"value": "ignored"}
+        });
+        let canonical = String::from_utf8(canonicalize_manifest(&manifest).unwrap()).unwrap();
+        assert_eq!(canonical, r#"{"threshold":1e+21,"weight":1}"#);
+    }
+
     #[test]
     fn verify_bundle_valid() {
         let content = "# My Constitution\n\nBe kind.";
@@ -556,6 +3029,399 @@ mod tests {
         assert_eq!(result.code, VerificationCode::HashMismatch);
     }
 
+    #[test]
+    fn verify_bundle_streaming_matches_whole_content() {
+        let content = "# Constitution\n\nBe kind to everyone — always.";
+        let manifest = serde_json::json!({
+            "bundle": {"id": "test-bundle", "content_hash": compute_content_hash(content).unwrap()}
+        })
+        .to_string();
+
+        // Chunks of 3 bytes split the em dash across updates.
+        let mut hasher = ContentHasher::new();
+        for chunk in content.as_bytes().chunks(3) {
+            hasher.update_bytes(chunk).unwrap();
+        }
+        assert!(verify_bundle_streaming(&manifest, hasher)
+            .unwrap()
+            .is_valid());
+
+        let mut tampered = ContentHasher::new();
+        tampered.update("Be unkind.").unwrap();
+        let result = verify_bundle_streaming(&manifest, tampered).unwrap();
+        assert_eq!(result.code, VerificationCode::HashMismatch);
+
+        let mut truncated = ContentHasher::new();
+        truncated.update_bytes(&"—".as_bytes()[..1]).unwrap();
+        let result = verify_bundle_streaming(&manifest, truncated).unwrap();
+        assert_eq!(result.code, VerificationCode::InvalidSchema);
+    }
+
+    #[test]
+    fn verify_bundle_rejects_mistyped_hash() {
+        let err = verify_bundle(r#"{"bundle": {"content_hash": 7}}"#, "content").unwrap_err();
+        assert!(matches!(err, VcpError::ParseError(_)), "{err}");
+        assert!(matches!(
+            verify_bundle("not json", "content"),
+            Err(VcpError::JsonError(_))
+        ));
+    }
+
+    // ── Typed manifest tests ────────────────────────────────
+
+    fn typed_manifest() -> Manifest {
+        Manifest::from_value(&serde_json::json!({
+            "vcp_version": "1.0",
+            "bundle": {
+                "id": "test-bundle",
+                "version": "1.0.0",
+                "content_hash": format!("sha256:{}", "a".repeat(64)),
+            },
+            "issuer": {"id": "test-issuer", "key_id": "key-01"},
+            "signature": {"algorithm": "ed25519", "value": "base64:AAAA"},
+            "timestamps": {
+                "iat": "2026-01-01T00:00:00Z",
+                "nbf": "2026-01-01T00:00:00+01:00",
+                "exp": "2026-02-01T00:00:00Z",
+                "jti": "jti-1",
+            },
+            "budget": {"token_count": 1000, "tokenizer": "cl100k_base"},
+            "scope": {"purposes": ["general-assistant"]},
+            "x-vendor": {"ignored": true},
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn manifest_parses_typed_sections() {
+        let manifest = typed_manifest();
+        assert_eq!(manifest.bundle.id.as_deref(), Some("test-bundle"));
+        assert_eq!(manifest.issuer.as_ref().unwrap().id, "test-issuer");
+        assert_eq!(manifest.signature.as_ref().unwrap().value, "base64:AAAA");
+        let ts = manifest.timestamps.as_ref().unwrap();
+        assert_eq!(ts.nbf.unwrap().to_rfc3339(), "2025-12-31T23:00:00+00:00");
+        let budget = manifest.budget.as_ref().unwrap();
+        assert_eq!(budget.token_count, Some(1000));
+        assert!((budget.max_context_share() - 0.25).abs() < f64::EPSILON);
+        assert_eq!(
+            manifest.scope.as_ref().unwrap().purposes,
+            ["general-assistant"]
+        );
+        manifest.validate().unwrap();
+    }
+
+    #[test]
+    fn manifest_rejects_mistyped_fields() {
+        for bad in [
+            r#"{"bundle": {}}"#,
+            r#"{"bundle": {"content_hash": "sha256:x"}, "timestamps": {"exp": "soon"}}"#,
+            r#"{"bundle": {"content_hash": "sha256:x"}, "budget": {"token_count": -1}}"#,
+            r#"{"bundle": {"content_hash": "sha256:x"}, "issuer": {"key_id": "k"}}"#,
+        ] {
+            assert!(
+                matches!(Manifest::from_json(bad), Err(VcpError::ParseError(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn manifest_validate_checks_cross_field_rules() {
+        let mut manifest = typed_manifest();
+        manifest.bundle.content_hash = "sha256:abc".into();
+        assert!(manifest
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("content_hash"));
+
+        let mut manifest = typed_manifest();
+        let ts = manifest.timestamps.as_mut().unwrap();
+        ts.iat = ts.exp.map(|exp| exp + chrono::Duration::days(1));
+        assert!(manifest.validate().unwrap_err().to_string().contains("iat"));
+
+        let mut manifest = typed_manifest();
+        manifest.budget.as_mut().unwrap().max_context_share = Some(1.5);
+        assert!(manifest.validate().is_err());
+
+        let mut manifest = typed_manifest();
+        manifest.bundle.id = None;
+        assert!(manifest.validate().is_err());
+
+        let mut manifest = typed_manifest();
+        manifest.binding = Some(ManifestBinding {
+            token: Some("Family.safe.guide".into()),
+        });
+        assert!(manifest
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("binding.token"));
+
+        for name in ["../escape", "a/b", "", ".."] {
+            let mut manifest = typed_manifest();
+            manifest.attachments = vec![ManifestAttachment {
+                name: name.into(),
+                hash: hash_attachment(b""),
+                media_type: None,
+            }];
+            assert!(manifest.validate().is_err(), "{name:?}");
+        }
+    }
+
+    // ── Multi-file bundle tests ─────────────────────────────
+
+    fn multi_file_manifest(contents: &BundleContents) -> Manifest {
+        let files: Vec<BundleFile> = [
+            ("core.md", "core"),
+            ("annexes/medical.md", "annex"),
+            ("annexes/privacy.md", "annex"),
+        ]
+        .into_iter()
+        .map(|(path, role)| BundleFile {
+            path: path.into(),
+            role: role.into(),
+            content_hash: compute_content_hash(contents.get(path).unwrap()).unwrap(),
+            locale: None,
+        })
+        .collect();
+        let mut manifest = typed_manifest();
+        manifest.bundle.content_hash = compute_files_hash(&files);
+        manifest.bundle.files = files;
+        manifest
+    }
+
+    fn multi_file_contents() -> BundleContents {
+        BundleContents::new()
+            .with_file("core.md", "Be kind.\n")
+            .with_file("annexes/medical.md", "No diagnoses.")
+            .with_file("annexes/privacy.md", "Keep secrets.\n")
+    }
+
+    #[test]
+    fn multi_file_bundle_verifies_and_selects_by_role() {
+        let contents = multi_file_contents();
+        let manifest = multi_file_manifest(&contents);
+        manifest.validate().unwrap();
+        assert!(contents.verify(&manifest).is_valid());
+
+        assert_eq!(contents.select(&manifest, &["core"]), "Be kind.\n");
+        assert_eq!(
+            contents.select(&manifest, &["annex"]),
+            "No diagnoses.\nKeep secrets.\n"
+        );
+        assert_eq!(
+            contents.select(&manifest, &[]),
+            "Be kind.\nNo diagnoses.\nKeep secrets.\n"
+        );
+    }
+
+    #[test]
+    fn multi_file_bundle_rejects_mismatches() {
+        let contents = multi_file_contents();
+        let manifest = multi_file_manifest(&contents);
+
+        let tampered = contents.clone().with_file("core.md", "Be cruel.\n");
+        assert_eq!(
+            tampered.verify(&manifest).code,
+            VerificationCode::HashMismatch
+        );
+
+        let mut missing = contents.clone();
+        missing.files.remove("annexes/privacy.md");
+        assert!(missing.verify(&manifest).message.contains("missing"));
+
+        let extra = contents.clone().with_file("extra.md", "Obey.\n");
+        assert_eq!(
+            extra.verify(&manifest).code,
+            VerificationCode::InvalidSchema
+        );
+
+        // Reordering the files changes the committed hash.
+        let mut reordered = manifest.clone();
+        reordered.bundle.files.swap(0, 1);
+        assert!(reordered.validate().is_err());
+        assert_eq!(
+            contents.verify(&reordered).code,
+            VerificationCode::HashMismatch
+        );
+
+        for path in ["../core.md", "/core.md", "a//b.md", "a/./b.md"] {
+            let mut bad = manifest.clone();
+            bad.bundle.files[0].path = path.into();
+            bad.bundle.content_hash = compute_files_hash(&bad.bundle.files);
+            assert!(bad.validate().is_err(), "{path:?}");
+        }
+    }
+
+    // ── Locale selection tests ──────────────────────────────
+
+    fn localized_bundle() -> (Manifest, BundleContents) {
+        let entries = [
+            ("core.md", "core", None, "Be kind.\n"),
+            ("core.de.md", "core", Some("de"), "Sei freundlich.\n"),
+            ("core.de-CH.md", "core", Some("de-CH"), "Sii fründlich.\n"),
+            ("annex.md", "annex", Some("en"), "Cite sources.\n"),
+            ("annex.fr.md", "annex", Some("fr"), "Citez vos sources.\n"),
+        ];
+        let mut contents = BundleContents::new();
+        let mut manifest = typed_manifest();
+        for (path, role, locale, text) in entries {
+            contents.insert(path, text);
+            manifest.bundle.files.push(BundleFile {
+                path: path.into(),
+                role: role.into(),
+                content_hash: compute_content_hash(text).unwrap(),
+                locale: locale.map(Into::into),
+            });
+        }
+        manifest.bundle.locale = Some("en".into());
+        manifest.bundle.content_hash = compute_files_hash(&manifest.bundle.files);
+        (manifest, contents)
+    }
+
+    #[test]
+    fn locale_fallback_chain_strips_subtags() {
+        assert_eq!(
+            locale_fallback_chain("de-CH-1996"),
+            ["de-ch-1996", "de-ch", "de"]
+        );
+        assert_eq!(locale_fallback_chain("EN"), ["en"]);
+        assert!(locale_fallback_chain("").is_empty());
+    }
+
+    #[test]
+    fn select_content_for_locale_falls_back_per_role() {
+        let (manifest, contents) = localized_bundle();
+        manifest.validate().unwrap();
+        assert!(contents.verify(&manifest).is_valid());
+
+        let swiss = select_content_for_locale(&manifest, &contents, "de-ch");
+        assert_eq!(swiss.locale.as_deref(), Some("de-CH"));
+        // No German annex: the bundle default (en) is used.
+        assert_eq!(swiss.paths, ["core.de-CH.md", "annex.md"]);
+        assert_eq!(swiss.text, "Sii fründlich.\nCite sources.\n");
+
+        let german = select_content_for_locale(&manifest, &contents, "de-DE");
+        assert_eq!(german.locale.as_deref(), Some("de"));
+        assert_eq!(german.paths, ["core.de.md", "annex.md"]);
+
+        let french = select_content_for_locale(&manifest, &contents, "fr-CA");
+        assert_eq!(french.locale.as_deref(), Some("fr"));
+        assert_eq!(french.paths, ["core.md", "annex.fr.md"]);
+
+        let unknown = select_content_for_locale(&manifest, &contents, "ja");
+        assert_eq!(unknown.locale, None);
+        assert_eq!(unknown.paths, ["core.md", "annex.md"]);
+    }
+
+    #[test]
+    fn manifest_validate_checks_locales() {
+        let (mut manifest, _) = localized_bundle();
+        manifest.bundle.locale = Some("english language".into());
+        assert!(manifest
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("BCP-47"));
+
+        let (mut manifest, _) = localized_bundle();
+        manifest.bundle.files[1].locale = Some("d".into());
+        assert!(manifest.validate().is_err());
+
+        let (mut manifest, _) = localized_bundle();
+        manifest.bundle.files[1].locale = Some("zh-Hant-TW".into());
+        manifest.validate().unwrap();
+    }
+
+    // ── Bundle archive tests ────────────────────────────────
+
+    fn sample_archive() -> BundleArchive {
+        let content = "Be kind.\nBe honest.\n";
+        let manifest = serde_json::json!({
+            "bundle": {"id": "archive-test", "content_hash": compute_content_hash(content).unwrap()},
+            "attachments": [
+                {"name": "notes.txt", "hash": hash_attachment(b"notes"), "media_type": "text/plain"},
+                {"name": "blob.bin", "hash": hash_attachment(&[0, 159, 255])},
+            ],
+        });
+        BundleArchive::new(manifest.to_string(), content)
+            .with_attachment("notes.txt", b"notes".to_vec())
+            .with_attachment("blob.bin", vec![0, 159, 255])
+    }
+
+    #[test]
+    fn archive_roundtrip_is_deterministic() {
+        let archive = sample_archive();
+        let bytes = archive.pack().unwrap();
+        assert_eq!(bytes.len() % TAR_BLOCK, 0);
+        assert_eq!(bytes, archive.pack().unwrap());
+        assert_eq!(BundleArchive::unpack(&bytes).unwrap(), archive);
+
+        let paths: Vec<String> = read_tar_members(&bytes)
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "index.json",
+                "manifest.json",
+                "content",
+                "attachments/blob.bin",
+                "attachments/notes.txt"
+            ]
+        );
+    }
+
+    #[test]
+    fn archive_pack_checks_members_against_manifest() {
+        let mut archive = sample_archive();
+        archive.content.push_str("Obey.\n");
+        assert!(matches!(archive.pack(), Err(VcpError::HashMismatch { .. })));
+
+        let archive = sample_archive().with_attachment("notes.txt", b"edited".to_vec());
+        assert!(matches!(archive.pack(), Err(VcpError::HashMismatch { .. })));
+
+        let archive = sample_archive().with_attachment("extra.txt", vec![]);
+        assert!(archive
+            .pack()
+            .unwrap_err()
+            .to_string()
+            .contains("not listed"));
+
+        let mut archive = sample_archive();
+        archive.attachments.remove("blob.bin");
+        assert!(archive.pack().unwrap_err().to_string().contains("missing"));
+    }
+
+    #[test]
+    fn archive_unpack_detects_tampering() {
+        let bytes = sample_archive().pack().unwrap();
+        let at = bytes
+            .windows(b"Be kind.".len())
+            .position(|w| w == b"Be kind.")
+            .unwrap();
+
+        let mut tampered = bytes.clone();
+        tampered[at] = b'b';
+        assert!(matches!(
+            BundleArchive::unpack(&tampered),
+            Err(VcpError::HashMismatch { .. })
+        ));
+
+        let mut bad_header = bytes.clone();
+        bad_header[0] ^= 1;
+        assert!(BundleArchive::unpack(&bad_header)
+            .unwrap_err()
+            .to_string()
+            .contains("checksum"));
+
+        assert!(BundleArchive::unpack(&bytes[..bytes.len() - 2 * TAR_BLOCK]).is_err());
+        assert!(BundleArchive::unpack(&bytes[..at]).is_err());
+    }
+
     // ── Ed25519 signing tests ───────────────────────────────
 
     /// Helper: generate a deterministic Ed25519 keypair from a seed byte.
@@ -680,4 +3546,105 @@ mod tests {
             "Ed25519 signing should be deterministic for same input"
         );
     }
+
+    // ── JWS tests ───────────────────────────────────────────
+
+    fn jws_manifest() -> serde_json::Value {
+        serde_json::json!({
+            "vcp_version": "1.0",
+            "bundle": {"id": "family.safe.guide", "content_hash": "sha256:abc"},
+            "issuer": {"id": "creed.space", "key_id": "k1"},
+            "timestamps": {
+                "iat": "2026-01-01T00:00:00Z",
+                "nbf": "2026-01-01T00:00:00Z",
+                "exp": "2026-03-01T00:00:00.250Z",
+                "jti": "m-1"
+            },
+            "signature": {"algorithm": "ed25519", "value": "base64:AAAA"}
+        })
+    }
+
+    fn jws_segment(jws: &str, index: usize) -> serde_json::Value {
+        let segment = jws.split('.').nth(index).unwrap();
+        serde_json::from_slice(&BASE64_URL.decode(segment).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn jws_round_trips_with_claims() {
+        let (sk, vk) = test_keypair(4);
+        let manifest = jws_manifest();
+        let jws = to_jws(&manifest, &sk.to_bytes()).unwrap();
+
+        let header = jws_segment(&jws, 0);
+        assert_eq!(
+            header,
+            serde_json::json!({"alg": "EdDSA", "typ": "vcp+jwt", "kid": "k1"})
+        );
+        let payload = jws_segment(&jws, 1);
+        assert_eq!(payload["iss"], "creed.space");
+        assert_eq!(payload["iat"], 1_767_225_600);
+        assert_eq!(payload["exp"], 1_772_323_200);
+        assert_eq!(payload["jti"], "m-1");
+        assert_eq!(payload["signature"], manifest["signature"]);
+
+        assert_eq!(from_jws(&jws, &vk.to_bytes()).unwrap(), manifest);
+        let (_, other) = test_keypair(5);
+        assert!(matches!(
+            from_jws(&jws, &other.to_bytes()),
+            Err(VcpError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn jws_claims_fill_and_must_agree_with_timestamps() {
+        let (sk, vk) = test_keypair(4);
+        let sign = |payload: &serde_json::Value| {
+            let header = BASE64_URL.encode(br#"{"alg":"EdDSA"}"#);
+            let body = BASE64_URL.encode(payload.to_string());
+            let input = format!("{header}.{body}");
+            let sig = Ed25519Signer::from_seed(&sk.to_bytes())
+                .sign_bytes(input.as_bytes())
+                .unwrap();
+            format!("{input}.{}", BASE64_URL.encode(sig))
+        };
+
+        let minted = sign(&serde_json::json!({
+            "bundle": {"id": "b", "content_hash": "sha256:abc"},
+            "exp": 1_772_323_200,
+            "jti": "m-2"
+        }));
+        let manifest = from_jws(&minted, &vk.to_bytes()).unwrap();
+        assert_eq!(manifest["timestamps"]["exp"], "2026-03-01T00:00:00Z");
+        assert_eq!(manifest["timestamps"]["jti"], "m-2");
+        assert!(manifest.get("exp").is_none());
+
+        let mut conflicting = jws_manifest();
+        conflicting["exp"] = 1.into();
+        let err = from_jws(&sign(&conflicting), &vk.to_bytes()).unwrap_err();
+        assert!(err.to_string().contains("claim exp disagrees"));
+    }
+
+    #[test]
+    fn jws_rejects_malformed_tokens() {
+        let (sk, vk) = test_keypair(4);
+        let jws = to_jws(&jws_manifest(), &sk.to_bytes()).unwrap();
+
+        let (header, rest) = jws.split_once('.').unwrap();
+        let none = BASE64_URL.encode(br#"{"alg":"none"}"#);
+        for bad in [
+            "a.b",
+            "a.b.c.d",
+            &format!("{none}.{rest}"),
+            &format!("{header}.{}", rest.replacen('e', "f", 1)),
+        ] {
+            assert!(from_jws(bad, &vk.to_bytes()).is_err(), "{bad}");
+        }
+
+        let mut clash = jws_manifest();
+        clash["iss"] = "someone-else".into();
+        assert!(matches!(
+            to_jws(&clash, &sk.to_bytes()),
+            Err(VcpError::ParseError(_))
+        ));
+    }
 }
@@ -1,9 +1,22 @@
-//! Trust anchor management for VCP issuers and auditors.
+//! Trust anchor management for VCP issuers, auditors and organizations.
 //!
 //! Mirrors the Python SDK's `vcp.trust` module. A [`TrustConfig`] holds
 //! collections of [`TrustAnchor`] entries keyed by entity ID. Each anchor
-//! represents a public key for an issuer or auditor, with validity windows
-//! and lifecycle state tracking.
+//! represents a public key for an issuer, an auditor, or an organization
+//! countersigning bundles (see [`multisig`](crate::multisig)), with
+//! validity windows and lifecycle state tracking. Anchors for DID issuers and auditors
+//! come from [`DidResolver`](crate::did::DidResolver).
+//!
+//! Small deployments without a key registry can enable trust-on-first-use
+//! with [`TrustConfig::with_tofu`]: [`KeyPins`] remembers the first key
+//! seen for each unknown issuer, and a later key for the same issuer is
+//! held as a [`KeyChange`] until [`KeyPins::approve_change`] accepts it.
+//!
+//! Organizations can keep shared anchors in a base file and layer
+//! environment-specific ones over it with [`TrustConfig::load_layered`]
+//! (`base.json` + `production.json`), or combine configs in code with
+//! [`TrustConfig::merge`]. A [`MergeStrategy`] decides what happens when
+//! both define the same key ID for an entity.
 //!
 //! # Examples
 //!
@@ -29,11 +42,18 @@
 //! ```
 
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::did;
 use crate::error::{VcpError, VcpResult};
+use crate::keys::{public_key_fingerprint, Fingerprint};
 
 // ── Anchor types ────────────────────────────────────────────
 
@@ -43,6 +63,7 @@ use crate::error::{VcpError, VcpResult};
 pub enum AnchorType {
     Issuer,
     Auditor,
+    Organization,
 }
 
 /// Lifecycle state of a trust anchor.
@@ -107,6 +128,27 @@ impl TrustAnchor {
         self.state.allows_verification() && at >= self.valid_from && at <= self.valid_until
     }
 
+    /// Decode [`public_key`](Self::public_key), with or without its
+    /// `base64:` prefix. Returns `None` if it is not valid base64.
+    pub fn public_key_bytes(&self) -> Option<Vec<u8>> {
+        let raw = self
+            .public_key
+            .strip_prefix("base64:")
+            .unwrap_or(&self.public_key);
+        BASE64.decode(raw).ok()
+    }
+
+    /// Fingerprint of the decoded key, for verifying it with the issuer
+    /// out of band. Returns `None` if the key is not valid base64.
+    pub fn fingerprint(&self) -> Option<Fingerprint> {
+        self.public_key_bytes().map(|key| Fingerprint::of(&key))
+    }
+
+    /// Format raw key bytes as `"base64:<encoded>"` for [`public_key`](Self::public_key).
+    pub fn encode_public_key(key: &[u8]) -> String {
+        format!("base64:{}", BASE64.encode(key))
+    }
+
     /// Parse a `TrustAnchor` from a dictionary-style JSON value.
     ///
     /// Expects the same shape as the Python `TrustAnchor.from_dict()`:
@@ -150,6 +192,7 @@ impl TrustAnchor {
         let anchor_type_str = obj.get("type").and_then(|v| v.as_str()).unwrap_or("issuer");
         let anchor_type = match anchor_type_str {
             "auditor" => AnchorType::Auditor,
+            "organization" => AnchorType::Organization,
             _ => AnchorType::Issuer,
         };
 
@@ -213,7 +256,7 @@ fn parse_datetime(s: &str) -> VcpResult<DateTime<Utc>> {
 
 // ── TrustConfig ─────────────────────────────────────────────
 
-/// Configuration holding trusted issuers and auditors.
+/// Configuration holding trusted issuers, auditors and organizations.
 ///
 /// Corresponds to the Python SDK's `TrustConfig` dataclass.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -222,6 +265,14 @@ pub struct TrustConfig {
     pub issuers: HashMap<String, Vec<TrustAnchor>>,
     /// Trusted auditor anchors, keyed by entity ID.
     pub auditors: HashMap<String, Vec<TrustAnchor>>,
+    /// Trusted organization anchors, keyed by entity ID. Only these can
+    /// countersign a manifest as an organization.
+    #[serde(default)]
+    pub organizations: HashMap<String, Vec<TrustAnchor>>,
+    /// Trust-on-first-use pins for issuers with no anchor. `None` (the
+    /// default) rejects unknown issuers outright.
+    #[serde(skip)]
+    pub tofu: Option<Arc<KeyPins>>,
 }
 
 impl TrustConfig {
@@ -230,6 +281,24 @@ impl TrustConfig {
         Self::default()
     }
 
+    /// Enable trust-on-first-use: an issuer with no anchor is accepted
+    /// with the `issuer.public_key` from its manifest, and pinned in
+    /// `pins`, once a signature by that key verifies.
+    ///
+    /// Clones of the config share `pins`, so a config handed to a
+    /// [`VerificationContext`](crate::orchestrator::VerificationContext)
+    /// still exposes what it pinned.
+    #[must_use]
+    pub fn with_tofu(mut self, pins: Arc<KeyPins>) -> Self {
+        self.tofu = Some(pins);
+        self
+    }
+
+    /// The trust-on-first-use pins, if enabled.
+    pub fn key_pins(&self) -> Option<&KeyPins> {
+        self.tofu.as_deref()
+    }
+
     /// Add a trusted issuer key.
     pub fn add_issuer(&mut self, issuer_id: &str, anchor: TrustAnchor) {
         self.issuers
@@ -246,40 +315,70 @@ impl TrustConfig {
             .push(anchor);
     }
 
+    /// Add a trusted organization key.
+    pub fn add_organization(&mut self, organization_id: &str, anchor: TrustAnchor) {
+        self.organizations
+            .entry(organization_id.to_string())
+            .or_default()
+            .push(anchor);
+    }
+
     /// Get the first valid trust anchor for an issuer.
     ///
     /// If `key_id` is `Some`, only anchors with that key ID are considered.
     /// Returns the first anchor that is currently valid (active/rotating and
-    /// within its validity window).
+    /// within its validity window). For DID issuers the key ID may also be
+    /// a DID URL or `#fragment`.
     pub fn get_issuer_key(&self, issuer_id: &str, key_id: Option<&str>) -> Option<&TrustAnchor> {
-        let anchors = self.issuers.get(issuer_id)?;
-        let now = Utc::now();
-        anchors.iter().find(|a| {
-            if let Some(kid) = key_id {
-                if a.key_id != kid {
-                    return false;
-                }
-            }
-            a.is_valid(Some(now))
-        })
+        self.get_issuer_key_at(issuer_id, key_id, Utc::now())
+    }
+
+    /// As [`get_issuer_key`](Self::get_issuer_key), judging validity at
+    /// `now`.
+    pub fn get_issuer_key_at(
+        &self,
+        issuer_id: &str,
+        key_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Option<&TrustAnchor> {
+        find_valid(self.issuers.get(issuer_id)?, issuer_id, key_id, now)
     }
 
     /// Get the first valid trust anchor for an auditor.
     ///
     /// If `key_id` is `Some`, only anchors with that key ID are considered.
     /// Returns the first anchor that is currently valid (active/rotating and
-    /// within its validity window).
+    /// within its validity window). For DID auditors the key ID may also be
+    /// a DID URL or `#fragment`.
     pub fn get_auditor_key(&self, auditor_id: &str, key_id: Option<&str>) -> Option<&TrustAnchor> {
-        let anchors = self.auditors.get(auditor_id)?;
-        let now = Utc::now();
-        anchors.iter().find(|a| {
-            if let Some(kid) = key_id {
-                if a.key_id != kid {
-                    return false;
-                }
-            }
-            a.is_valid(Some(now))
-        })
+        self.get_auditor_key_at(auditor_id, key_id, Utc::now())
+    }
+
+    /// As [`get_auditor_key`](Self::get_auditor_key), judging validity at
+    /// `now`.
+    pub fn get_auditor_key_at(
+        &self,
+        auditor_id: &str,
+        key_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Option<&TrustAnchor> {
+        find_valid(self.auditors.get(auditor_id)?, auditor_id, key_id, now)
+    }
+
+    /// Get the first valid trust anchor for an organization, as
+    /// [`get_issuer_key_at`](Self::get_issuer_key_at) does for issuers.
+    pub fn get_organization_key_at(
+        &self,
+        organization_id: &str,
+        key_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Option<&TrustAnchor> {
+        find_valid(
+            self.organizations.get(organization_id)?,
+            organization_id,
+            key_id,
+            now,
+        )
     }
 
     /// Parse a `TrustConfig` from a dictionary-style JSON value.
@@ -333,10 +432,10 @@ impl TrustConfig {
 
                 let anchor = TrustAnchor::from_dict(entity_id, &key_obj)?;
 
-                if entity_type == "auditor" {
-                    config.add_auditor(entity_id, anchor);
-                } else {
-                    config.add_issuer(entity_id, anchor);
+                match entity_type {
+                    "auditor" => config.add_auditor(entity_id, anchor),
+                    "organization" => config.add_organization(entity_id, anchor),
+                    _ => config.add_issuer(entity_id, anchor),
                 }
             }
         }
@@ -344,6 +443,40 @@ impl TrustConfig {
         Ok(config)
     }
 
+    /// Check every anchor's key decodes (to 32 bytes for Ed25519) and its
+    /// validity window is not inverted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::ParseError`] naming the first bad anchor.
+    pub fn validate(&self) -> VcpResult<()> {
+        for anchor in self
+            .issuers
+            .values()
+            .chain(self.auditors.values())
+            .chain(self.organizations.values())
+            .flatten()
+        {
+            let bad = |problem: &str| {
+                Err(VcpError::ParseError(format!(
+                    "trust anchor '{}' key '{}': {problem}",
+                    anchor.id, anchor.key_id
+                )))
+            };
+            match anchor.public_key_bytes() {
+                None => return bad("public key is not valid base64"),
+                Some(key) if anchor.algorithm == "ed25519" && key.len() != 32 => {
+                    return bad(&format!("Ed25519 key is {} bytes, expected 32", key.len()));
+                }
+                Some(_) => {}
+            }
+            if anchor.valid_until < anchor.valid_from {
+                return bad("valid_until is before valid_from");
+            }
+        }
+        Ok(())
+    }
+
     /// Parse a `TrustConfig` from a JSON string.
     ///
     /// # Errors
@@ -361,52 +494,34 @@ impl TrustConfig {
     pub fn to_dict(&self) -> serde_json::Value {
         let mut trust_anchors = serde_json::Map::new();
 
-        for (issuer_id, anchors) in &self.issuers {
-            let keys: Vec<serde_json::Value> = anchors
-                .iter()
-                .map(|a| {
-                    serde_json::json!({
-                        "id": a.key_id,
-                        "algorithm": a.algorithm,
-                        "public_key": a.public_key,
-                        "state": format!("{}", serde_json::to_value(a.state).unwrap_or_default()).trim_matches('"'),
-                        "valid_from": a.valid_from.to_rfc3339(),
-                        "valid_until": a.valid_until.to_rfc3339(),
+        for (entity_type, entities) in [
+            ("issuer", &self.issuers),
+            ("auditor", &self.auditors),
+            ("organization", &self.organizations),
+        ] {
+            for (entity_id, anchors) in entities {
+                let keys: Vec<serde_json::Value> = anchors
+                    .iter()
+                    .map(|a| {
+                        serde_json::json!({
+                            "id": a.key_id,
+                            "algorithm": a.algorithm,
+                            "public_key": a.public_key,
+                            "state": format!("{}", serde_json::to_value(a.state).unwrap_or_default()).trim_matches('"'),
+                            "valid_from": a.valid_from.to_rfc3339(),
+                            "valid_until": a.valid_until.to_rfc3339(),
+                        })
                     })
-                })
-                .collect();
+                    .collect();
 
-            trust_anchors.insert(
-                issuer_id.clone(),
-                serde_json::json!({
-                    "type": "issuer",
-                    "keys": keys,
-                }),
-            );
-        }
-
-        for (auditor_id, anchors) in &self.auditors {
-            let keys: Vec<serde_json::Value> = anchors
-                .iter()
-                .map(|a| {
+                trust_anchors.insert(
+                    entity_id.clone(),
                     serde_json::json!({
-                        "id": a.key_id,
-                        "algorithm": a.algorithm,
-                        "public_key": a.public_key,
-                        "state": format!("{}", serde_json::to_value(a.state).unwrap_or_default()).trim_matches('"'),
-                        "valid_from": a.valid_from.to_rfc3339(),
-                        "valid_until": a.valid_until.to_rfc3339(),
-                    })
-                })
-                .collect();
-
-            trust_anchors.insert(
-                auditor_id.clone(),
-                serde_json::json!({
-                    "type": "auditor",
-                    "keys": keys,
-                }),
-            );
+                        "type": entity_type,
+                        "keys": keys,
+                    }),
+                );
+            }
         }
 
         serde_json::json!({ "trust_anchors": trust_anchors })
@@ -421,10 +536,305 @@ impl TrustConfig {
         serde_json::to_string_pretty(&self.to_dict())
             .map_err(|e| VcpError::JsonError(e.to_string()))
     }
+
+    /// Combine with `other`, resolving anchors both configs hold for the
+    /// same entity and key ID according to `strategy`.
+    ///
+    /// Issuers, auditors and organizations are merged separately. Trust-on-first-use
+    /// pins come from `self`, or from `other` if `self` has none.
+    ///
+    /// # Errors
+    ///
+    /// With [`MergeStrategy::ErrorOnDuplicate`], returns
+    /// [`VcpError::ParseError`] naming the first key ID found in both.
+    pub fn merge(&self, other: &TrustConfig, strategy: MergeStrategy) -> VcpResult<Self> {
+        Ok(Self {
+            issuers: merge_anchors(&self.issuers, &other.issuers, strategy, "issuer")?,
+            auditors: merge_anchors(&self.auditors, &other.auditors, strategy, "auditor")?,
+            organizations: merge_anchors(
+                &self.organizations,
+                &other.organizations,
+                strategy,
+                "organization",
+            )?,
+            tofu: self.tofu.clone().or_else(|| other.tofu.clone()),
+        })
+    }
+
+    /// Load a base config and overlay each later file on it in order,
+    /// e.g. `["base.json", "production.json"]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::IoError`] if a file cannot be read,
+    /// [`VcpError::ParseError`] if `paths` is empty, and otherwise as
+    /// [`from_json`](Self::from_json) and [`merge`](Self::merge), with the
+    /// file name prefixed to the message.
+    pub fn load_layered(paths: &[impl AsRef<Path>], strategy: MergeStrategy) -> VcpResult<Self> {
+        let mut config: Option<Self> = None;
+        for path in paths {
+            let path = path.as_ref();
+            let in_file = |e: VcpError| match e {
+                VcpError::JsonError(m) => VcpError::JsonError(format!("{}: {m}", path.display())),
+                VcpError::ParseError(m) => VcpError::ParseError(format!("{}: {m}", path.display())),
+                other => other,
+            };
+            let json = fs::read_to_string(path)
+                .map_err(|e| VcpError::IoError(format!("{}: {e}", path.display())))?;
+            let layer = Self::from_json(&json).map_err(in_file)?;
+            config = Some(match config {
+                None => layer,
+                Some(base) => base.merge(&layer, strategy).map_err(in_file)?,
+            });
+        }
+        config.ok_or_else(|| VcpError::ParseError("no trust config files to load".into()))
+    }
+}
+
+/// How [`TrustConfig::merge`] resolves an entity's key ID present in both
+/// configs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Keep both anchors, the base's first, so lookups prefer it. Exact
+    /// copies are kept once.
+    #[default]
+    Union,
+    /// The other config's anchor replaces the base's.
+    OtherWins,
+    /// Fail the merge.
+    ErrorOnDuplicate,
+}
+
+fn merge_anchors(
+    base: &HashMap<String, Vec<TrustAnchor>>,
+    other: &HashMap<String, Vec<TrustAnchor>>,
+    strategy: MergeStrategy,
+    role: &str,
+) -> VcpResult<HashMap<String, Vec<TrustAnchor>>> {
+    let mut merged = base.clone();
+    for (entity, anchors) in other {
+        let existing = merged.entry(entity.clone()).or_default();
+        for anchor in anchors {
+            let same_key = |a: &TrustAnchor| a.key_id == anchor.key_id;
+            match strategy {
+                MergeStrategy::Union if existing.contains(anchor) => {}
+                MergeStrategy::OtherWins => {
+                    if let Some(pos) = existing.iter().position(same_key) {
+                        existing.retain(|a| !same_key(a));
+                        existing.insert(pos, anchor.clone());
+                    } else {
+                        existing.push(anchor.clone());
+                    }
+                }
+                MergeStrategy::ErrorOnDuplicate if existing.iter().any(same_key) => {
+                    return Err(VcpError::ParseError(format!(
+                        "{role} '{entity}' key '{}' is defined in both trust configs",
+                        anchor.key_id
+                    )));
+                }
+                MergeStrategy::Union | MergeStrategy::ErrorOnDuplicate => {
+                    existing.push(anchor.clone());
+                }
+            }
+        }
+    }
+    Ok(merged)
+}
+
+/// The first anchor valid at `now`, restricted to `key_id` if given.
+fn find_valid<'a>(
+    anchors: &'a [TrustAnchor],
+    entity_id: &str,
+    key_id: Option<&str>,
+    now: DateTime<Utc>,
+) -> Option<&'a TrustAnchor> {
+    let key_id = key_id.map(|kid| did::key_fragment(entity_id, kid));
+    anchors.iter().find(|a| {
+        if let Some(kid) = key_id {
+            if a.key_id != kid {
+                return false;
+            }
+        }
+        a.is_valid(Some(now))
+    })
 }
 
 // ── Tests ───────────────────────────────────────────────────
 
+// ── Key pinning (TOFU) ──────────────────────────────────────
+
+/// An issuer key pinned on first use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedKey {
+    /// Issuer the key was presented by.
+    pub issuer_id: String,
+    /// The key, as `"base64:<encoded>"`.
+    pub public_key: String,
+    /// `sha256:<hex>` of the raw key.
+    pub fingerprint: String,
+    /// When the key was first seen with a valid signature.
+    pub first_seen: DateTime<Utc>,
+}
+
+impl PinnedKey {
+    /// A pin for `public_key`, first seen now.
+    pub fn new(issuer_id: &str, public_key: &[u8]) -> Self {
+        Self::new_at(issuer_id, public_key, Utc::now())
+    }
+
+    /// A pin for `public_key`, first seen at `first_seen`.
+    pub fn new_at(issuer_id: &str, public_key: &[u8], first_seen: DateTime<Utc>) -> Self {
+        Self {
+            issuer_id: issuer_id.to_string(),
+            public_key: TrustAnchor::encode_public_key(public_key),
+            fingerprint: public_key_fingerprint(public_key),
+            first_seen,
+        }
+    }
+}
+
+/// A validly signed manifest from a pinned issuer, under a different key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyChange {
+    /// The key currently pinned.
+    pub pinned: PinnedKey,
+    /// The key the issuer presented instead.
+    pub presented: PinnedKey,
+}
+
+/// What [`KeyPins::observe`] made of a presented key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinStatus {
+    /// The issuer was unknown and is now pinned to the key.
+    Pinned,
+    /// The key matches the issuer's pin.
+    Matches,
+    /// The issuer is pinned to another key; the change awaits approval.
+    Changed,
+}
+
+/// Trust-on-first-use key pins, shared between clones of a [`TrustConfig`].
+#[derive(Debug, Default)]
+pub struct KeyPins {
+    state: Mutex<PinState>,
+}
+
+#[derive(Debug, Default)]
+struct PinState {
+    pins: HashMap<String, PinnedKey>,
+    changes: HashMap<String, PinnedKey>,
+}
+
+impl KeyPins {
+    /// Empty pins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pins restored from a previous [`pins`](Self::pins) export.
+    pub fn from_pins(pins: impl IntoIterator<Item = PinnedKey>) -> Self {
+        let pins = pins
+            .into_iter()
+            .map(|pin| (pin.issuer_id.clone(), pin))
+            .collect();
+        Self {
+            state: Mutex::new(PinState {
+                pins,
+                changes: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Record that `issuer_id` produced a valid signature with
+    /// `public_key`. Call only after the signature has been verified.
+    pub fn observe(&self, issuer_id: &str, public_key: &[u8]) -> PinStatus {
+        self.observe_at(issuer_id, public_key, Utc::now())
+    }
+
+    /// As [`observe`](Self::observe), stamping new pins and pending
+    /// changes with `now`.
+    pub fn observe_at(&self, issuer_id: &str, public_key: &[u8], now: DateTime<Utc>) -> PinStatus {
+        let presented = PinnedKey::new_at(issuer_id, public_key, now);
+        let mut state = self.lock();
+        match state.pins.get(issuer_id) {
+            Some(pin) if pin.fingerprint == presented.fingerprint => PinStatus::Matches,
+            Some(_) => {
+                state
+                    .changes
+                    .entry(issuer_id.to_string())
+                    .and_modify(|pending| {
+                        if pending.fingerprint != presented.fingerprint {
+                            *pending = presented.clone();
+                        }
+                    })
+                    .or_insert(presented);
+                PinStatus::Changed
+            }
+            None => {
+                state.pins.insert(issuer_id.to_string(), presented);
+                PinStatus::Pinned
+            }
+        }
+    }
+
+    /// The pin for `issuer_id`, if any.
+    pub fn get(&self, issuer_id: &str) -> Option<PinnedKey> {
+        self.lock().pins.get(issuer_id).cloned()
+    }
+
+    /// Every pin, ordered by issuer ID.
+    pub fn pins(&self) -> Vec<PinnedKey> {
+        let mut pins: Vec<PinnedKey> = self.lock().pins.values().cloned().collect();
+        pins.sort_by(|a, b| a.issuer_id.cmp(&b.issuer_id));
+        pins
+    }
+
+    /// Key changes awaiting approval, ordered by issuer ID.
+    pub fn pending_changes(&self) -> Vec<KeyChange> {
+        let state = self.lock();
+        let mut changes: Vec<KeyChange> = state
+            .changes
+            .iter()
+            .filter_map(|(issuer_id, presented)| {
+                Some(KeyChange {
+                    pinned: state.pins.get(issuer_id)?.clone(),
+                    presented: presented.clone(),
+                })
+            })
+            .collect();
+        changes.sort_by(|a, b| a.pinned.issuer_id.cmp(&b.pinned.issuer_id));
+        changes
+    }
+
+    /// Pin the key `issuer_id` changed to. Returns the new pin, or `None`
+    /// if no change was pending.
+    pub fn approve_change(&self, issuer_id: &str) -> Option<PinnedKey> {
+        let mut state = self.lock();
+        let presented = state.changes.remove(issuer_id)?;
+        state.pins.insert(issuer_id.to_string(), presented.clone());
+        Some(presented)
+    }
+
+    /// Discard a pending key change, keeping the current pin.
+    pub fn reject_change(&self, issuer_id: &str) -> Option<PinnedKey> {
+        self.lock().changes.remove(issuer_id)
+    }
+
+    /// Forget an issuer; its next valid key is pinned afresh.
+    pub fn unpin(&self, issuer_id: &str) -> Option<PinnedKey> {
+        let mut state = self.lock();
+        state.changes.remove(issuer_id);
+        state.pins.remove(issuer_id)
+    }
+
+    /// Updates are single map operations, so a poisoned lock still
+    /// guards consistent pins.
+    fn lock(&self) -> MutexGuard<'_, PinState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -662,6 +1072,35 @@ mod tests {
         assert!(config.get_issuer_key("safety-auditor", None).is_none());
     }
 
+    #[test]
+    fn config_from_dict_organizations() {
+        let data = serde_json::json!({
+            "trust_anchors": {
+                "acme-corp": {
+                    "type": "organization",
+                    "keys": [{
+                        "id": "org-key-01",
+                        "algorithm": "ed25519",
+                        "public_key": "base64:org-key",
+                        "valid_from": "2020-01-01T00:00:00Z",
+                        "valid_until": "2030-01-01T00:00:00Z"
+                    }]
+                }
+            }
+        });
+
+        let config = TrustConfig::from_dict(&data).unwrap();
+        let at = "2025-01-01T00:00:00Z".parse().unwrap();
+        let anchor = config
+            .get_organization_key_at("acme-corp", None, at)
+            .unwrap();
+        assert_eq!(anchor.anchor_type, AnchorType::Organization);
+        assert!(config.get_issuer_key_at("acme-corp", None, at).is_none());
+
+        let again = TrustConfig::from_dict(&config.to_dict()).unwrap();
+        assert!(again.organizations.contains_key("acme-corp"));
+    }
+
     #[test]
     fn config_from_dict_mixed() {
         let data = serde_json::json!({
@@ -829,4 +1268,174 @@ mod tests {
         assert!(!AnchorState::Retired.allows_verification());
         assert!(!AnchorState::Compromised.allows_verification());
     }
+
+    // ── Key pinning ──────────────────────────────────────────
+
+    #[test]
+    fn anchor_fingerprint_decodes_key() {
+        let mut anchor = make_anchor("acme", "k1", AnchorType::Issuer, AnchorState::Active, 1, 30);
+        anchor.public_key = TrustAnchor::encode_public_key(&[5u8; 32]);
+        let fingerprint = anchor.fingerprint().unwrap();
+        assert_eq!(fingerprint.to_string(), public_key_fingerprint(&[5u8; 32]));
+
+        anchor.public_key = "base64:not base64!".into();
+        assert!(anchor.fingerprint().is_none());
+    }
+
+    #[test]
+    fn merge_strategies() {
+        let anchor = |key_id: &str, public_key: &str| TrustAnchor {
+            public_key: public_key.to_string(),
+            ..make_anchor(
+                "acme",
+                key_id,
+                AnchorType::Issuer,
+                AnchorState::Active,
+                1,
+                30,
+            )
+        };
+        let mut base = TrustConfig::new();
+        base.add_issuer("acme", anchor("k1", "base64:AAAA"));
+        base.add_auditor(
+            "audit",
+            make_anchor(
+                "audit",
+                "a1",
+                AnchorType::Auditor,
+                AnchorState::Active,
+                1,
+                30,
+            ),
+        );
+        let mut prod = TrustConfig::new();
+        prod.add_issuer("acme", anchor("k1", "base64:BBBB"));
+        prod.add_issuer("acme", anchor("k2", "base64:CCCC"));
+        prod.add_issuer("globex", anchor("g1", "base64:DDDD"));
+
+        let keys = |config: &TrustConfig| -> Vec<(String, String)> {
+            config.issuers["acme"]
+                .iter()
+                .map(|a| (a.key_id.clone(), a.public_key.clone()))
+                .collect()
+        };
+        let pair = |k: &str, p: &str| (k.to_string(), p.to_string());
+
+        let union = base.merge(&prod, MergeStrategy::Union).unwrap();
+        assert_eq!(
+            keys(&union),
+            [
+                pair("k1", "base64:AAAA"),
+                pair("k1", "base64:BBBB"),
+                pair("k2", "base64:CCCC")
+            ]
+        );
+        assert_eq!(
+            union.get_issuer_key("acme", Some("k1")).unwrap().public_key,
+            "base64:AAAA"
+        );
+        assert!(union.issuers.contains_key("globex"));
+        assert!(union.auditors.contains_key("audit"));
+        assert_eq!(
+            keys(&base.merge(&base, MergeStrategy::Union).unwrap()).len(),
+            1
+        );
+
+        let other_wins = base.merge(&prod, MergeStrategy::OtherWins).unwrap();
+        assert_eq!(
+            keys(&other_wins),
+            [pair("k1", "base64:BBBB"), pair("k2", "base64:CCCC")]
+        );
+
+        let err = base
+            .merge(&prod, MergeStrategy::ErrorOnDuplicate)
+            .unwrap_err();
+        assert!(err.to_string().contains("issuer 'acme' key 'k1'"), "{err}");
+        let mut staging = TrustConfig::new();
+        staging.add_issuer("acme", anchor("k9", "base64:EEEE"));
+        assert!(base
+            .merge(&staging, MergeStrategy::ErrorOnDuplicate)
+            .is_ok());
+    }
+
+    #[test]
+    fn load_layered_overlays_files() {
+        let dir = std::env::temp_dir().join(format!("vcp-trust-layers-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = |name: &str, key: &str| {
+            let path = dir.join(name);
+            let json = format!(
+                r#"{{"trust_anchors": {{"acme": {{"type": "issuer", "keys": [{{"id": "k1", "algorithm": "ed25519", "public_key": "{key}", "valid_from": "2025-01-01T00:00:00Z", "valid_until": "2030-01-01T00:00:00Z"}}]}}}}}}"#
+            );
+            fs::write(&path, json).unwrap();
+            path
+        };
+        let base = file("base.json", "base64:AAAA");
+        let prod = file("production.json", "base64:BBBB");
+
+        let config = TrustConfig::load_layered(&[&base, &prod], MergeStrategy::OtherWins).unwrap();
+        assert_eq!(config.issuers["acme"][0].public_key, "base64:BBBB");
+
+        let err = TrustConfig::load_layered(&[&base, &prod], MergeStrategy::ErrorOnDuplicate)
+            .unwrap_err();
+        assert!(err.to_string().contains("production.json"), "{err}");
+        let missing = dir.join("missing.json");
+        assert!(matches!(
+            TrustConfig::load_layered(&[&base, &missing], MergeStrategy::Union),
+            Err(VcpError::IoError(_))
+        ));
+        assert!(TrustConfig::load_layered(&[] as &[&Path], MergeStrategy::Union).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn key_pins_track_first_use_and_changes() {
+        let pins = KeyPins::new();
+        assert_eq!(pins.observe("small.example", &[1u8; 32]), PinStatus::Pinned);
+        assert_eq!(
+            pins.observe("small.example", &[1u8; 32]),
+            PinStatus::Matches
+        );
+        assert_eq!(
+            pins.observe("small.example", &[2u8; 32]),
+            PinStatus::Changed
+        );
+        assert_eq!(
+            pins.observe("small.example", &[3u8; 32]),
+            PinStatus::Changed
+        );
+
+        // The latest presented key is the one awaiting approval.
+        let changes = pins.pending_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            changes[0].presented.fingerprint,
+            public_key_fingerprint(&[3u8; 32])
+        );
+        assert!(pins.reject_change("small.example").is_some());
+        assert_eq!(
+            pins.observe("small.example", &[1u8; 32]),
+            PinStatus::Matches
+        );
+
+        pins.observe("small.example", &[2u8; 32]);
+        let approved = pins.approve_change("small.example").unwrap();
+        assert_eq!(pins.get("small.example"), Some(approved));
+        assert!(pins.approve_change("small.example").is_none());
+
+        // Pins survive an export and restore; clones of a config share them.
+        let restored = Arc::new(KeyPins::from_pins(pins.pins()));
+        let config = TrustConfig::new().with_tofu(Arc::clone(&restored));
+        assert_eq!(
+            config
+                .clone()
+                .key_pins()
+                .unwrap()
+                .observe("small.example", &[2u8; 32]),
+            PinStatus::Matches
+        );
+        assert!(restored.unpin("small.example").is_some());
+        assert!(config.key_pins().unwrap().pins().is_empty());
+    }
 }
@@ -0,0 +1,438 @@
+//! Untrusted hook handlers run as WebAssembly in a wasmtime sandbox
+//! (feature `wasm-hooks`).
+//!
+//! [`WasmHookHandler`] implements [`HookHandler`] for a WASM module, so a
+//! third-party policy plugin can sit in a hook chain of a multi-tenant
+//! deployment. The module sees nothing but its own linear memory and the
+//! host API below, and every run is bounded by fuel, wall-clock time and
+//! memory ([`SandboxLimits`]).
+//!
+//! # Host API
+//!
+//! | Import | Signature | Purpose |
+//! |--------|-----------|---------|
+//! | `vcp.input_len` | `() -> i32` | Length of the input JSON in bytes |
+//! | `vcp.read_input` | `(ptr: i32)` | Copy the input JSON to `ptr` |
+//! | `vcp.set_output` | `(ptr: i32, len: i32)` | Hand back the output JSON |
+//!
+//! The module exports its `memory` and a `run: () -> ()` entry point.
+//! The input is the [`HookInput`] as JSON, and the output a
+//! [`HookResult`]: `{"action": ..., "annotations": {...}}`, with the
+//! action `"continue"`, `{"abort": {"reason": "..."}}` or
+//! `{"modify": {...}}`. `annotations` and `state_updates` are optional,
+//! and `duration` is ignored.
+//!
+//! A module that traps, runs out of fuel, memory or time, or sets no
+//! valid output is treated as `Continue`, like a panicking native handler,
+//! with the error in a `sandbox_error` annotation.
+//! [`with_fail_closed`](WasmHookHandler::with_fail_closed) turns such
+//! failures into an `Abort`.
+//!
+//! # Examples
+//!
+//! ```
+//! use vcp_core::hooks::{ChainState, HookAction, HookHandler, HookInput};
+//! use vcp_core::wasm_hooks::WasmHookHandler;
+//!
+//! let handler = WasmHookHandler::new(
+//!     r#"(module
+//!         (import "vcp" "set_output" (func $set_output (param i32 i32)))
+//!         (memory (export "memory") 1)
+//!         (data (i32.const 0) "{\"action\":\"continue\"}")
+//!         (func (export "run") (call $set_output (i32.const 0) (i32.const 21))))"#,
+//! )?;
+//!
+//! let input = HookInput {
+//!     context: serde_json::json!({}),
+//!     constitution: serde_json::json!({}),
+//!     event: serde_json::json!({}),
+//!     session_id: "sess-1".into(),
+//!     chain_state: ChainState::new(),
+//! };
+//! assert_eq!(handler.execute(&input).action, HookAction::Continue);
+//! # Ok::<(), vcp_core::VcpError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use wasmtime::{
+    Caller, Config, Engine, Extern, ExternType, InstancePre, Linker, Memory, Module, Store,
+    StoreLimits, StoreLimitsBuilder, UpdateDeadline,
+};
+
+use crate::error::{VcpError, VcpResult};
+use crate::hooks::{ChainState, HookAction, HookHandler, HookInput, HookResult};
+
+// ── Limits ──────────────────────────────────────────────────
+
+/// Resource bounds for one run of a sandboxed hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxLimits {
+    /// Fuel a run may consume; roughly one unit per WASM instruction.
+    pub fuel: u64,
+    /// Wall-clock time a run may take.
+    pub timeout: Duration,
+    /// Largest size the module's memory may grow to, in bytes.
+    pub max_memory: usize,
+    /// Longest output JSON accepted, in bytes.
+    pub max_output: usize,
+}
+
+impl Default for SandboxLimits {
+    fn default() -> Self {
+        Self {
+            fuel: 10_000_000,
+            timeout: Duration::from_millis(100),
+            max_memory: 16 << 20,
+            max_output: 64 << 10,
+        }
+    }
+}
+
+// ── Handler ─────────────────────────────────────────────────
+
+/// A [`HookHandler`] backed by a sandboxed WebAssembly module.
+///
+/// The module is compiled and linked once; each run gets a fresh
+/// instance, so no state carries over between runs.
+pub struct WasmHookHandler {
+    engine: Engine,
+    module: InstancePre<HostState>,
+    limits: SandboxLimits,
+    fail_closed: bool,
+}
+
+impl std::fmt::Debug for WasmHookHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmHookHandler")
+            .field("limits", &self.limits)
+            .field("fail_closed", &self.fail_closed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasmHookHandler {
+    /// Compile a module from its binary or text format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::HookError`] if the module does not compile,
+    /// imports anything but the host API, or does not export `memory` and
+    /// a `run: () -> ()` function.
+    pub fn new(wasm: impl AsRef<[u8]>) -> VcpResult<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true).epoch_interruption(true);
+        let engine = Engine::new(&config).map_err(sandbox_error)?;
+        let module = Module::new(&engine, wasm)
+            .map_err(|e| VcpError::HookError(format!("invalid WASM hook module: {e:#}")))?;
+
+        match module.get_export("run") {
+            Some(ExternType::Func(run)) if run.params().len() == 0 && run.results().len() == 0 => {}
+            _ => {
+                return Err(VcpError::HookError(
+                    "WASM hook module must export `run: () -> ()`".into(),
+                ))
+            }
+        }
+        if !matches!(module.get_export("memory"), Some(ExternType::Memory(_))) {
+            return Err(VcpError::HookError(
+                "WASM hook module must export `memory`".into(),
+            ));
+        }
+
+        let module = host_api(&engine)
+            .and_then(|linker| linker.instantiate_pre(&module))
+            .map_err(|e| VcpError::HookError(format!("cannot link WASM hook module: {e:#}")))?;
+        Ok(Self {
+            engine,
+            module,
+            limits: SandboxLimits::default(),
+            fail_closed: false,
+        })
+    }
+
+    /// Compile the module in the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VcpError::IoError`] if the file cannot be read, otherwise
+    /// as for [`new`](Self::new).
+    pub fn from_file(path: impl AsRef<Path>) -> VcpResult<Self> {
+        Self::new(std::fs::read(path)?)
+    }
+
+    /// Replace the default [`SandboxLimits`].
+    #[must_use]
+    pub fn with_limits(mut self, limits: SandboxLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Abort the chain, rather than continue it, when the module fails.
+    #[must_use]
+    pub fn with_fail_closed(mut self, fail_closed: bool) -> Self {
+        self.fail_closed = fail_closed;
+        self
+    }
+
+    /// The limits each run is held to.
+    pub fn limits(&self) -> &SandboxLimits {
+        &self.limits
+    }
+
+    /// Run the module once on `input`.
+    fn run(&self, input: &HookInput) -> VcpResult<HookResult> {
+        let state = HostState {
+            input: serde_json::to_vec(input)?,
+            output: None,
+            max_output: self.limits.max_output,
+            limits: StoreLimitsBuilder::new()
+                .memory_size(self.limits.max_memory)
+                .build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.limits.fuel).map_err(sandbox_error)?;
+
+        // The watchdog bumps the engine's epoch once the timeout passes;
+        // other runs sharing the engine check their own deadline.
+        let deadline = Instant::now() + self.limits.timeout;
+        store.set_epoch_deadline(1);
+        store.epoch_deadline_callback(move |_| {
+            if Instant::now() >= deadline {
+                Err(wasmtime::Error::msg("timed out"))
+            } else {
+                Ok(UpdateDeadline::Continue(1))
+            }
+        });
+        let _watchdog = Watchdog::start(&self.engine, self.limits.timeout);
+
+        let instance = self.module.instantiate(&mut store).map_err(sandbox_error)?;
+        instance
+            .get_typed_func::<(), ()>(&mut store, "run")
+            .and_then(|run| run.call(&mut store, ()))
+            .map_err(sandbox_error)?;
+
+        let output = store
+            .into_data()
+            .output
+            .ok_or_else(|| VcpError::HookError("WASM hook set no output".into()))?;
+        serde_json::from_slice(&output)
+            .map_err(|e| VcpError::HookError(format!("invalid WASM hook output: {e}")))
+    }
+}
+
+impl HookHandler for WasmHookHandler {
+    fn execute(&self, input: &HookInput) -> HookResult {
+        match self.run(input) {
+            Ok(result) => result,
+            Err(e) => HookResult {
+                action: if self.fail_closed {
+                    HookAction::Abort {
+                        reason: format!("sandboxed hook failed: {e}"),
+                    }
+                } else {
+                    HookAction::Continue
+                },
+                annotations: HashMap::from([(
+                    "sandbox_error".to_string(),
+                    serde_json::Value::String(e.to_string()),
+                )]),
+                state_updates: ChainState::new(),
+                duration: Duration::ZERO,
+            },
+        }
+    }
+}
+
+// ── Host side ───────────────────────────────────────────────
+
+/// Per-run state the host API reads and writes.
+struct HostState {
+    input: Vec<u8>,
+    output: Option<Vec<u8>>,
+    max_output: usize,
+    limits: StoreLimits,
+}
+
+fn host_api(engine: &Engine) -> wasmtime::Result<Linker<HostState>> {
+    let mut linker = Linker::new(engine);
+    linker.func_wrap("vcp", "input_len", |caller: Caller<'_, HostState>| {
+        u32::try_from(caller.data().input.len()).unwrap_or(u32::MAX)
+    })?;
+    linker.func_wrap(
+        "vcp",
+        "read_input",
+        |mut caller: Caller<'_, HostState>, ptr: u32| -> wasmtime::Result<()> {
+            let (memory, state) = guest_memory(&mut caller)?.data_and_store_mut(&mut caller);
+            let start = ptr as usize;
+            memory
+                .get_mut(start..start + state.input.len())
+                .ok_or_else(|| wasmtime::Error::msg("read_input: out of bounds"))?
+                .copy_from_slice(&state.input);
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        "vcp",
+        "set_output",
+        |mut caller: Caller<'_, HostState>, ptr: u32, len: u32| -> wasmtime::Result<()> {
+            let (memory, state) = guest_memory(&mut caller)?.data_and_store_mut(&mut caller);
+            let (start, len) = (ptr as usize, len as usize);
+            if len > state.max_output {
+                return Err(wasmtime::Error::msg(format!(
+                    "set_output: {len} bytes exceeds the {} byte limit",
+                    state.max_output
+                )));
+            }
+            let output = memory
+                .get(start..start + len)
+                .ok_or_else(|| wasmtime::Error::msg("set_output: out of bounds"))?;
+            state.output = Some(output.to_vec());
+            Ok(())
+        },
+    )?;
+    Ok(linker)
+}
+
+fn guest_memory(caller: &mut Caller<'_, HostState>) -> wasmtime::Result<Memory> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("module exports no memory"))
+}
+
+#[allow(clippy::needless_pass_by_value)] // Shaped for `map_err`.
+fn sandbox_error(e: wasmtime::Error) -> VcpError {
+    VcpError::HookError(format!("WASM hook: {e:#}"))
+}
+
+/// Bumps an engine's epoch every `timeout` until dropped.
+struct Watchdog {
+    _stop: mpsc::Sender<()>,
+}
+
+impl Watchdog {
+    fn start(engine: &Engine, timeout: Duration) -> Self {
+        let (tx, rx) = mpsc::channel::<()>();
+        let engine = engine.clone();
+        std::thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(timeout) {
+                engine.increment_epoch();
+            }
+        });
+        Self { _stop: tx }
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> HookInput {
+        HookInput {
+            context: serde_json::json!({"mood": "calm"}),
+            constitution: serde_json::json!({}),
+            event: serde_json::json!({}),
+            session_id: "sess-1".into(),
+            chain_state: ChainState::new(),
+        }
+    }
+
+    /// A module that runs `body` and then outputs the JSON in `output`.
+    fn module(body: &str, output: &str) -> String {
+        let escaped = output.replace('"', "\\\"");
+        format!(
+            r#"(module
+                (import "vcp" "input_len" (func $input_len (result i32)))
+                (import "vcp" "read_input" (func $read_input (param i32)))
+                (import "vcp" "set_output" (func $set_output (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{escaped}")
+                (func (export "run") {body}
+                    (call $set_output (i32.const 0) (i32.const {len}))))"#,
+            len = output.len(),
+        )
+    }
+
+    #[test]
+    fn module_output_becomes_the_hook_result() {
+        let handler = WasmHookHandler::new(module(
+            "",
+            r#"{"action":{"abort":{"reason":"tenant policy"}},"annotations":{"rule":7}}"#,
+        ))
+        .unwrap();
+        let result = handler.execute(&input());
+        assert_eq!(
+            result.action,
+            HookAction::Abort {
+                reason: "tenant policy".into()
+            }
+        );
+        assert_eq!(result.annotations["rule"], serde_json::json!(7));
+    }
+
+    #[test]
+    fn module_reads_its_input() {
+        // Echo the input back as a Modify of the whole context.
+        let handler = WasmHookHandler::new(
+            r#"(module
+                (import "vcp" "input_len" (func $input_len (result i32)))
+                (import "vcp" "read_input" (func $read_input (param i32)))
+                (import "vcp" "set_output" (func $set_output (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{\"action\":{\"modify\":")
+                (func (export "run") (local $len i32)
+                    (local.set $len (call $input_len))
+                    (call $read_input (i32.const 20))
+                    (i32.store16 (i32.add (i32.const 20) (local.get $len)) (i32.const 0x7d7d))
+                    (call $set_output (i32.const 0) (i32.add (local.get $len) (i32.const 22)))))"#,
+        )
+        .unwrap();
+        let result = handler.execute(&input());
+        let HookAction::Modify(value) = result.action else {
+            panic!("expected Modify, got {:?}", result.action);
+        };
+        assert_eq!(value["context"], serde_json::json!({"mood": "calm"}));
+        assert_eq!(value["session_id"], "sess-1");
+    }
+
+    #[test]
+    fn runaway_module_is_stopped_and_fails_open() {
+        let spin = "(loop $spin (br $spin))";
+        let handler = WasmHookHandler::new(module(spin, r#"{"action":"continue"}"#)).unwrap();
+        let result = handler.execute(&input());
+        assert_eq!(result.action, HookAction::Continue);
+        assert!(result.annotations.contains_key("sandbox_error"));
+
+        // Unlimited fuel leaves the wall-clock timeout to stop it.
+        let handler = handler
+            .with_limits(SandboxLimits {
+                fuel: u64::MAX,
+                timeout: Duration::from_millis(20),
+                ..SandboxLimits::default()
+            })
+            .with_fail_closed(true);
+        let result = handler.execute(&input());
+        let HookAction::Abort { reason } = result.action else {
+            panic!("expected Abort, got {:?}", result.action);
+        };
+        assert!(reason.contains("timed out"), "{reason}");
+    }
+
+    #[test]
+    fn module_without_host_api_shape_is_rejected() {
+        for wat in [
+            r#"(module (memory (export "memory") 1))"#,
+            r#"(module (func (export "run")))"#,
+            r#"(module (import "env" "exit" (func)) (memory (export "memory") 1) (func (export "run")))"#,
+        ] {
+            assert!(WasmHookHandler::new(wat).is_err(), "{wat}");
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Runs the repository's shared conformance vectors (`/conformance`).
+//!
+//! Vectors this SDK currently disagrees with are pinned below with the
+//! reason. The test fails both on a new divergence and on a pinned one
+//! that starts passing, so the list only ever shrinks deliberately.
+
+use std::path::Path;
+
+use vcp_core::conformance::run_dir;
+
+const KNOWN_DIVERGENCES: &[&str] = &[
+    // Fixture personal dims use `level` / `cognitive_state`; this SDK
+    // serializes `intensity` / `cognitive`.
+    "adaptation/context_encoding:ctx-003",
+    "adaptation/context_encoding:ctx-004",
+    "adaptation/context_encoding:ctx-006",
+    "adaptation/context_encoding_extended:vep-008",
+    "adaptation/context_encoding_extended:vep-010",
+    // Approach is the penultimate segment here, the second in the fixture.
+    "identity/token_parsing:token-003",
+    // Scopes keep input order rather than being sorted, T/O/R are not
+    // scopes, namespaces follow scopes, and versions must be semver.
+    "semantics/csm1_encoding:enc-001",
+    "semantics/csm1_encoding:enc-005",
+    "semantics/csm1_encoding:enc-007",
+    "semantics/csm1_encoding:enc-008",
+    "semantics/csm1_encoding:enc-010",
+    "semantics/csm1_parsing:csm1-005",
+    "semantics/csm1_parsing:csm1-009",
+    "semantics/csm1_parsing:csm1-010",
+    "semantics/csm1_parsing:csm1-012",
+    "semantics/csm1_parsing:csm1-013",
+    "semantics/csm1_parsing:csm1-014",
+    // Lowercase namespaces are upper-cased, and there is no length limit.
+    "semantics/csm1_parsing:csm1-err-006",
+    "semantics/csm1_parsing:csm1-err-007",
+    // Fixture errors: the hash is of the input without its trailing
+    // newline, and the canonical JSON repeats `outer_a`.
+    "transport/content_hashing:hash-001",
+    "transport/manifest_canonicalization:mc-004",
+];
+
+#[test]
+fn shared_vectors_match_known_divergences() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../conformance");
+    let report = run_dir(&dir).unwrap();
+
+    assert!(report.suites.len() >= 15, "vectors directory not found");
+    assert!(report.passed() >= 100);
+
+    assert_eq!(report.failed_ids(), KNOWN_DIVERGENCES);
+}
@@ -0,0 +1,110 @@
+//! Algebraic properties of CSM-1 code arithmetic.
+//!
+//! [`Csm1Code::merge`] documents itself as a join: commutative,
+//! associative and idempotent on canonical codes, with namespace
+//! conflicts failing in any grouping. [`Csm1Code::restrict_to`] is
+//! documented as idempotent and order-independent. Namespaces are drawn
+//! from a small set so conflicts are common.
+
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use vcp_core::csm1::{Csm1Code, Persona, Scope};
+
+fn scope_set() -> impl Strategy<Value = Vec<Scope>> {
+    vec(select(Scope::all()), 0..5)
+}
+
+fn csm1_code() -> impl Strategy<Value = Csm1Code> {
+    (
+        select(Persona::all()),
+        0u8..=5,
+        scope_set(),
+        option::of(select(&["ORG", "SEC"][..]).prop_map(String::from)),
+        option::of((0u32..3, 0u32..12, 0u32..3).prop_map(|(a, b, c)| format!("{a}.{b}.{c}"))),
+    )
+        .prop_map(
+            |(persona, adherence_level, scopes, namespace, version)| Csm1Code {
+                persona,
+                adherence_level,
+                scopes,
+                namespace,
+                version,
+            },
+        )
+}
+
+/// A code with its scopes in canonical order, as merge results are.
+fn canonical(code: &Csm1Code) -> Csm1Code {
+    code.restrict_to(&[]).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn merge_is_commutative(a in csm1_code(), b in csm1_code()) {
+        prop_assert_eq!(a.merge(&b), b.merge(&a));
+    }
+
+    #[test]
+    fn merge_is_associative(a in csm1_code(), b in csm1_code(), c in csm1_code()) {
+        let left = a.merge(&b).and_then(|ab| ab.merge(&c));
+        let right = b.merge(&c).and_then(|bc| a.merge(&bc));
+        prop_assert_eq!(left.is_ok(), right.is_ok());
+        if let (Ok(left), Ok(right)) = (left, right) {
+            prop_assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn merge_is_idempotent(a in csm1_code()) {
+        let a = canonical(&a);
+        prop_assert_eq!(a.merge(&a).unwrap(), a);
+    }
+
+    #[test]
+    fn merge_is_at_least_as_restrictive(a in csm1_code(), b in csm1_code()) {
+        if let Ok(merged) = a.merge(&b) {
+            prop_assert!(merged.adherence_level >= a.adherence_level.max(b.adherence_level));
+            prop_assert!(merged.persona.restrictiveness() >= a.persona.restrictiveness());
+            prop_assert!(merged.persona.restrictiveness() >= b.persona.restrictiveness());
+            for scope in Scope::all() {
+                if a.applies_to(*scope) || b.applies_to(*scope) {
+                    prop_assert!(merged.applies_to(*scope));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn restrict_to_is_idempotent(a in csm1_code(), scopes in scope_set()) {
+        let once = a.restrict_to(&scopes);
+        let twice = once.as_ref().and_then(|c| c.restrict_to(&scopes));
+        prop_assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn restrict_to_order_does_not_matter(
+        a in csm1_code(),
+        x in scope_set(),
+        y in scope_set(),
+    ) {
+        prop_assert_eq!(
+            a.restrict_to(&x).and_then(|c| c.restrict_to(&y)),
+            a.restrict_to(&y).and_then(|c| c.restrict_to(&x)),
+        );
+    }
+
+    #[test]
+    fn restrict_to_never_widens(a in csm1_code(), scopes in scope_set()) {
+        if let Some(narrowed) = a.restrict_to(&scopes) {
+            for scope in Scope::all() {
+                if narrowed.applies_to(*scope) {
+                    prop_assert!(a.applies_to(*scope));
+                    prop_assert!(scopes.is_empty() || scopes.contains(scope));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Property-based wire round-trips.
+//!
+//! Each generator covers the space of values the encoder can emit and the
+//! Python SDK can read back, so `parse(encode(x)) == x` pins the wire
+//! format for both sides. Strategies stay inside the documented grammar
+//! (e.g. list items never contain `,`) — inputs outside it are the fuzz
+//! targets' job.
+
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use vcp_core::context::FullContext;
+use vcp_core::csm1::{
+    ConstitutionRef, ConstraintFlag, Csm1Code, Csm1Token, GoalContext, Persona, Scope,
+};
+use vcp_core::identity::{SemVer, VcpToken};
+use vcp_core::personal::{PersonalDimension, PersonalDimensionKind, PersonalState};
+use vcp_core::situational::SituationalContext;
+
+// ── Shared pieces ───────────────────────────────────────────
+
+const SCOPES: [Scope; 11] = [
+    Scope::Family,
+    Scope::Work,
+    Scope::Education,
+    Scope::Healthcare,
+    Scope::Finance,
+    Scope::Legal,
+    Scope::Privacy,
+    Scope::Safety,
+    Scope::Accessibility,
+    Scope::Environment,
+    Scope::General,
+];
+
+/// Tag values seen in the wild, including ZWJ sequences and VS16 emoji.
+const EMOJI_TAGS: [&str; 12] = [
+    "\u{1F305}",
+    "\u{1F319}",
+    "\u{1F3E1}",
+    "\u{1F3E2}",
+    "\u{1F476}",
+    "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}",
+    "\u{1F382}",
+    "\u{2614}",
+    "\u{1F327}\u{FE0F}",
+    "\u{1F4F1}",
+    "\u{1F3F3}\u{FE0F}\u{200D}\u{1F308}",
+    "\u{1F9D1}\u{1F3FD}",
+];
+
+fn persona() -> impl Strategy<Value = Persona> {
+    select(Persona::all())
+}
+
+fn namespace() -> impl Strategy<Value = String> {
+    "[A-Z][A-Z0-9]{0,7}"
+}
+
+fn semver() -> impl Strategy<Value = SemVer> {
+    (0u32..100, 0u32..100, 0u32..1000).prop_map(|(major, minor, patch)| SemVer {
+        major,
+        minor,
+        patch,
+    })
+}
+
+/// A comma-free, whitespace-free list item.
+fn list_item() -> impl Strategy<Value = String> {
+    "[a-z0-9][a-z0-9_.-]{0,15}"
+}
+
+// ── CSM-1 code ──────────────────────────────────────────────
+
+fn csm1_code() -> impl Strategy<Value = Csm1Code> {
+    (
+        persona(),
+        0u8..=5,
+        vec(select(&SCOPES[..]), 0..6),
+        option::of(namespace()),
+        option::of(semver().prop_map(|v| v.to_string())),
+    )
+        .prop_map(
+            |(persona, adherence_level, scopes, namespace, version)| Csm1Code {
+                persona,
+                adherence_level,
+                scopes,
+                namespace,
+                version,
+            },
+        )
+}
+
+// ── Personal state ──────────────────────────────────────────
+
+fn personal_dimension(kind: PersonalDimensionKind) -> impl Strategy<Value = PersonalDimension> {
+    let extended = prop_oneof![
+        "[a-z_]{0,12}",
+        select(&EMOJI_TAGS[..]).prop_map(String::from),
+    ];
+    (select(kind.valid_values()), 1u8..=5, option::of(extended)).prop_map(
+        |(value, intensity, extended)| PersonalDimension {
+            value: value.to_string(),
+            intensity,
+            extended,
+            source: None,
+            declared_at: None,
+            confidence: None,
+        },
+    )
+}
+
+fn personal_state() -> impl Strategy<Value = PersonalState> {
+    (
+        option::of(personal_dimension(PersonalDimensionKind::CognitiveState)),
+        option::of(personal_dimension(PersonalDimensionKind::EmotionalTone)),
+        option::of(personal_dimension(PersonalDimensionKind::EnergyLevel)),
+        option::of(personal_dimension(PersonalDimensionKind::PerceivedUrgency)),
+        option::of(personal_dimension(PersonalDimensionKind::BodySignals)),
+    )
+        .prop_map(
+            |(cognitive, emotional, energy, urgency, body)| PersonalState {
+                cognitive,
+                emotional,
+                energy,
+                urgency,
+                body,
+            },
+        )
+}
+
+// ── CSM-1 token ─────────────────────────────────────────────
+
+fn goal_context() -> impl Strategy<Value = GoalContext> {
+    // The style field is last, so it may carry further `:`s.
+    ("[a-z_-]{0,10}", "[a-z_-]{0,10}", "[a-z_:-]{0,10}").prop_map(|(goal, experience, style)| {
+        GoalContext {
+            goal,
+            experience,
+            style,
+        }
+    })
+}
+
+fn csm1_token() -> impl Strategy<Value = Csm1Token> {
+    (
+        ("[0-9]\\.[0-9]", "[a-z0-9][a-z0-9_:-]{0,15}"),
+        ("[a-z0-9][a-z0-9._-]{0,15}", semver()),
+        persona(),
+        1u8..=5,
+        option::of(goal_context()),
+        vec(list_item(), 0..4),
+        vec(list_item(), 0..4),
+        vec(list_item(), 0..4),
+        // An empty `R:` line reads back as absent, so only non-empty states.
+        option::of(personal_state().prop_filter("empty personal state", PersonalState::has_any)),
+    )
+        .prop_map(
+            |(
+                (version, profile_id),
+                (const_id, const_ver),
+                persona,
+                adherence,
+                goal,
+                constraints,
+                flags,
+                private_markers,
+                personal_state,
+            )| Csm1Token {
+                version,
+                profile_id,
+                constitution: ConstitutionRef {
+                    id: const_id,
+                    version: const_ver.to_string(),
+                },
+                persona,
+                adherence,
+                goal,
+                constraints: constraints.into_iter().map(ConstraintFlag).collect(),
+                flags,
+                private_markers,
+                personal_state,
+                extensions: Vec::new(),
+            },
+        )
+}
+
+// ── VCP/I token ─────────────────────────────────────────────
+
+fn vcp_token() -> impl Strategy<Value = VcpToken> {
+    (
+        vec("[a-z][a-z0-9-]{0,31}", 3..=10),
+        option::of(semver()),
+        option::of(namespace()),
+    )
+        .prop_map(|(segments, version, namespace)| VcpToken {
+            segments,
+            version,
+            namespace,
+        })
+        .prop_filter("exceeds max token length", |t| t.full().len() <= 256)
+}
+
+// ── Full context ────────────────────────────────────────────
+
+/// One tag per dimension: the wire format has no tag separator, so a
+/// dimension always reads back as a single concatenated tag.
+fn situational_tag() -> impl Strategy<Value = Vec<String>> {
+    prop_oneof![
+        select(&EMOJI_TAGS[..]).prop_map(String::from),
+        "[a-z_]{1,10}:[a-z_]{1,10}",
+    ]
+    .prop_map(|tag| vec![tag])
+}
+
+fn situational_context() -> impl Strategy<Value = SituationalContext> {
+    (
+        (
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+        ),
+        (
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+            option::of(situational_tag()),
+        ),
+    )
+        .prop_map(
+            |(
+                (time, space, company, culture, occasion, environment, agency),
+                (constraints, system_context, embodiment, proximity, relationship, formality),
+            )| SituationalContext {
+                time,
+                space,
+                company,
+                culture,
+                occasion,
+                environment,
+                agency,
+                constraints,
+                system_context,
+                embodiment,
+                proximity,
+                relationship,
+                formality,
+            },
+        )
+}
+
+fn full_context() -> impl Strategy<Value = FullContext> {
+    (situational_context(), personal_state())
+        .prop_map(|(situational, personal)| FullContext::new(situational, personal))
+}
+
+// ── Properties ──────────────────────────────────────────────
+
+proptest! {
+    #[test]
+    fn csm1_code_roundtrips(code in csm1_code()) {
+        let encoded = code.encode();
+        prop_assert_eq!(&Csm1Code::parse(&encoded).unwrap(), &code);
+        prop_assert_eq!(Csm1Code::from(Csm1Code::parse_borrowed(&encoded).unwrap()), code);
+    }
+
+    #[test]
+    fn csm1_token_roundtrips(token in csm1_token()) {
+        prop_assert_eq!(Csm1Token::parse(&token.encode()).unwrap(), token);
+    }
+
+    #[test]
+    fn vcp_token_roundtrips(token in vcp_token()) {
+        prop_assert_eq!(VcpToken::parse(&token.full()).unwrap(), token);
+    }
+
+    #[test]
+    fn personal_state_roundtrips(state in personal_state()) {
+        prop_assert_eq!(PersonalState::from_wire(&state.to_wire()).unwrap(), state);
+    }
+
+    #[test]
+    fn full_context_roundtrips(ctx in full_context()) {
+        prop_assert_eq!(FullContext::from_wire(&ctx.to_wire()).unwrap(), ctx);
+    }
+
+    #[test]
+    fn full_context_json_roundtrips(ctx in full_context()) {
+        let json = serde_json::to_string(&ctx).unwrap();
+        prop_assert_eq!(serde_json::from_str::<FullContext>(&json).unwrap(), ctx);
+    }
+}
@@ -0,0 +1,20 @@
+//! Build script for vcp-grpc.
+//!
+//! Compiles `proto/vcp/service/v1/service.proto` with tonic-prost-build
+//! using a vendored `protoc`. Messages from `vcp/v1/vcp.proto` are not
+//! regenerated: they map onto `vcp_core::proto::v1`, so the service
+//! shares types and conversions with vcp-core.
+
+fn main() {
+    const SERVICE: &str = "proto/vcp/service/v1/service.proto";
+    const CORE: &str = "../vcp-core/proto";
+    println!("cargo:rerun-if-changed={SERVICE}");
+
+    let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available");
+    let mut config = tonic_prost_build::Config::new();
+    config.protoc_executable(protoc);
+    tonic_prost_build::configure()
+        .extern_path(".vcp.v1", "::vcp_core::proto::v1")
+        .compile_with_config(config, &[SERVICE], &["proto", CORE])
+        .expect("failed to compile service.proto");
+}
@@ -0,0 +1,705 @@
+//! # vcp-grpc
+//!
+//! A gRPC surface for the **Value Context Protocol**, for service meshes
+//! that want typed RPCs rather than JSON over HTTP.
+//!
+//! The service is defined in `proto/vcp/service/v1/service.proto` and
+//! reuses the data-model messages from vcp-core's `vcp/v1/vcp.proto`
+//! (generated into `vcp_core::proto::v1`), so requests and responses
+//! convert to native types with the `From` / `TryFrom` impls there.
+//!
+//! | RPC | Operation |
+//! |-----|-----------|
+//! | `VerifyBundle` | [`Orchestrator::verify_outcome`] |
+//! | `Compose` | [`Composer::compose`] |
+//! | `ParseToken` | [`VcpToken::parse`] |
+//! | `ParseCsm1Token` | [`Csm1Token::parse`] |
+//! | `EncodeContext` | [`FullContext::to_wire_as`] |
+//! | `DecodeContext` | [`FullContext::from_wire`] |
+//! | `MergeContexts` | [`FullContext::merge`] |
+//!
+//! [`VcpGrpcService`] implements the server trait; wrap it with
+//! [`VcpGrpcService::into_server`] to mount it on a tonic router, or call
+//! [`serve`] to run it on its own. [`VcpServiceClient`] is the generated
+//! client.
+//!
+//! Bundles are verified by an [`Orchestrator`] built from the server's
+//! [`TrustConfig`], never from anything in the request. Send the manifest
+//! as `manifest_json`, the exact bytes that were signed; the typed
+//! `manifest` field is accepted too and converted back to JSON.
+//!
+//! ## Errors
+//!
+//! Malformed input fails with `INVALID_ARGUMENT` and a composition with
+//! unresolvable conflicts with `FAILED_PRECONDITION`. A failed
+//! verification is not an error: `VerifyBundle` returns a report whose
+//! code says what went wrong. Errors raised by vcp-core carry their
+//! [`VcpError::code`] in the `vcp-error-code` metadata entry, and parse
+//! errors with a location add `vcp-error-offset` and `vcp-error-length`
+//! (a byte range into the input).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use vcp_grpc::pb::ParseTokenRequest;
+//! use vcp_grpc::VcpServiceClient;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut client = VcpServiceClient::connect("http://127.0.0.1:50051").await?;
+//! let parsed = client
+//!     .parse_token(ParseTokenRequest {
+//!         token: "family.safe.guide@1.2.0".into(),
+//!     })
+//!     .await?
+//!     .into_inner();
+//! assert_eq!(parsed.canonical, "family.safe.guide");
+//! # Ok(())
+//! # }
+//! ```
+
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+#![allow(clippy::must_use_candidate)]
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tonic::metadata::MetadataValue;
+use tonic::{Request, Response, Status};
+
+use vcp_core::composer::{
+    Composer, CompositionMode, CompositionResult, Conflict, Constitution, MergedRule,
+};
+use vcp_core::context::{FullContext, WireFormat};
+use vcp_core::csm1::Csm1Token;
+use vcp_core::error::{VcpError, VerificationCode};
+use vcp_core::identity::VcpToken;
+use vcp_core::orchestrator::{Orchestrator, VerificationOutcome};
+use vcp_core::proto::v1;
+use vcp_core::transport::VerificationResult;
+use vcp_core::trust::TrustConfig;
+
+pub use pb::vcp_service_client::VcpServiceClient;
+pub use pb::vcp_service_server::{VcpService, VcpServiceServer};
+
+/// Generated messages and service stubs for package `vcp.service.v1`.
+#[allow(clippy::all, clippy::pedantic, missing_docs)]
+pub mod pb {
+    include!(concat!(env!("OUT_DIR"), "/vcp.service.v1.rs"));
+}
+
+/// Default address for [`serve`] when none is given.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:50051";
+
+// ── Service ─────────────────────────────────────────────────
+
+/// The vcp-core backed implementation of [`VcpService`].
+///
+/// `VerifyBundle` runs through a shared [`Orchestrator`], so its replay
+/// cache spans every request and clone; the other RPCs are pure
+/// functions of their request.
+#[derive(Clone)]
+pub struct VcpGrpcService {
+    orchestrator: Arc<Orchestrator>,
+}
+
+impl VcpGrpcService {
+    /// Create a service that verifies bundles against `trust_config`.
+    #[must_use]
+    pub fn new(trust_config: TrustConfig) -> Self {
+        Self::with_orchestrator(Orchestrator::new(trust_config))
+    }
+
+    /// Create a service that verifies bundles with `orchestrator`, e.g.
+    /// one following a reloaded trust configuration.
+    #[must_use]
+    pub fn with_orchestrator(orchestrator: Orchestrator) -> Self {
+        Self {
+            orchestrator: Arc::new(orchestrator),
+        }
+    }
+
+    /// The orchestrator behind `VerifyBundle`.
+    pub fn orchestrator(&self) -> &Orchestrator {
+        &self.orchestrator
+    }
+
+    /// Wrap the service for mounting on a tonic server.
+    #[must_use]
+    pub fn into_server(self) -> VcpServiceServer<Self> {
+        VcpServiceServer::new(self)
+    }
+}
+
+impl fmt::Debug for VcpGrpcService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VcpGrpcService")
+            .field("replay", &self.orchestrator.replay_stats())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Serve [`VcpGrpcService`] on `addr`, verifying bundles against
+/// `trust_config`, until the process is stopped.
+///
+/// # Errors
+///
+/// Returns a transport error if `addr` cannot be bound.
+pub async fn serve(
+    addr: SocketAddr,
+    trust_config: TrustConfig,
+) -> Result<(), tonic::transport::Error> {
+    tonic::transport::Server::builder()
+        .add_service(VcpGrpcService::new(trust_config).into_server())
+        .serve(addr)
+        .await
+}
+
+#[tonic::async_trait]
+impl VcpService for VcpGrpcService {
+    async fn verify_bundle(
+        &self,
+        request: Request<pb::VerifyBundleRequest>,
+    ) -> Result<Response<v1::VerificationReport>, Status> {
+        let request = request.into_inner();
+        let manifest_json = match (request.manifest_json.is_empty(), request.manifest) {
+            (false, None) => String::from_utf8(request.manifest_json)
+                .map_err(|_| Status::invalid_argument("manifest_json is not valid UTF-8"))?,
+            (true, Some(manifest)) => serde_json::Value::try_from(&manifest)
+                .map_err(|e| status(&e))?
+                .to_string(),
+            (false, Some(_)) => {
+                return Err(Status::invalid_argument(
+                    "set only one of manifest_json and manifest",
+                ))
+            }
+            (true, None) => return Err(Status::invalid_argument("missing field: manifest_json")),
+        };
+        let ctx = self.orchestrator.verification_context();
+        let outcome = self
+            .orchestrator
+            .verify_outcome(&manifest_json, &request.content, &ctx);
+        Ok(Response::new(v1::VerificationReport::from(&report(
+            &outcome,
+        ))))
+    }
+
+    async fn compose(
+        &self,
+        request: Request<pb::ComposeRequest>,
+    ) -> Result<Response<pb::ComposeResponse>, Status> {
+        let request = request.into_inner();
+        let mode = CompositionMode::from(request.mode());
+        let constitutions: Vec<Constitution> = request
+            .constitutions
+            .into_iter()
+            .map(|c| Constitution::new(c.id, c.rules, c.priority))
+            .collect();
+        let result = Composer::new()
+            .compose(&constitutions, mode)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+        Ok(Response::new(pb::ComposeResponse::from(result)))
+    }
+
+    async fn parse_token(
+        &self,
+        request: Request<pb::ParseTokenRequest>,
+    ) -> Result<Response<pb::ParseTokenResponse>, Status> {
+        let token = VcpToken::parse(&request.into_inner().token).map_err(|e| status(&e))?;
+        Ok(Response::new(pb::ParseTokenResponse {
+            canonical: token.canonical(),
+            full: token.full(),
+            version: token.version.as_ref().map(ToString::to_string),
+            namespace: token.namespace,
+            segments: token.segments,
+        }))
+    }
+
+    async fn parse_csm1_token(
+        &self,
+        request: Request<pb::ParseCsm1TokenRequest>,
+    ) -> Result<Response<v1::Csm1Token>, Status> {
+        let token = Csm1Token::parse(&request.into_inner().token).map_err(|e| status(&e))?;
+        Ok(Response::new(v1::Csm1Token::from(&token)))
+    }
+
+    async fn encode_context(
+        &self,
+        request: Request<pb::EncodeContextRequest>,
+    ) -> Result<Response<pb::EncodeContextResponse>, Status> {
+        let request = request.into_inner();
+        let format = match request.format() {
+            pb::WireFormat::Unspecified | pb::WireFormat::Emoji => WireFormat::Emoji,
+            pb::WireFormat::Ascii => WireFormat::Ascii,
+        };
+        let ctx = match request.context {
+            Some(ctx) => FullContext::try_from(ctx).map_err(|e| status(&e))?,
+            None => FullContext::default(),
+        };
+        Ok(Response::new(pb::EncodeContextResponse {
+            wire: ctx.to_wire_as(format),
+            conformance_level: ctx.conformance_level().label().to_string(),
+        }))
+    }
+
+    async fn decode_context(
+        &self,
+        request: Request<pb::DecodeContextRequest>,
+    ) -> Result<Response<v1::FullContext>, Status> {
+        let ctx = FullContext::from_wire(&request.into_inner().wire).map_err(|e| status(&e))?;
+        Ok(Response::new(v1::FullContext::from(&ctx)))
+    }
+
+    async fn merge_contexts(
+        &self,
+        request: Request<pb::MergeContextsRequest>,
+    ) -> Result<Response<v1::FullContext>, Status> {
+        let mut merged = FullContext::default();
+        for ctx in request.into_inner().contexts {
+            merged.merge(&FullContext::try_from(ctx).map_err(|e| status(&e))?);
+        }
+        Ok(Response::new(v1::FullContext::from(&merged)))
+    }
+}
+
+// ── Conversions ─────────────────────────────────────────────
+
+/// Summarize a pipeline outcome, listing schema violations if any.
+fn report(outcome: &VerificationOutcome) -> VerificationResult {
+    if outcome.code == VerificationCode::Valid {
+        return VerificationResult::valid();
+    }
+    let mut message = format!("verification failed: {}", outcome.code);
+    if !outcome.schema_errors.is_empty() {
+        let errors: Vec<String> = outcome
+            .schema_errors
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        message = format!("{message} ({})", errors.join("; "));
+    }
+    VerificationResult::fail(outcome.code, message)
+}
+
+/// Map a vcp-core error to `INVALID_ARGUMENT`, with its code and span
+/// in the metadata.
+fn status(err: &VcpError) -> Status {
+    let mut status = Status::invalid_argument(err.to_string());
+    let metadata = status.metadata_mut();
+    metadata.insert("vcp-error-code", MetadataValue::from_static(err.code()));
+    if let Some(span) = err.span() {
+        metadata.insert("vcp-error-offset", MetadataValue::from(span.offset));
+        metadata.insert("vcp-error-length", MetadataValue::from(span.len));
+    }
+    status
+}
+
+impl From<pb::CompositionMode> for CompositionMode {
+    fn from(mode: pb::CompositionMode) -> Self {
+        match mode {
+            pb::CompositionMode::Unspecified | pb::CompositionMode::Extend => {
+                CompositionMode::Extend
+            }
+            pb::CompositionMode::Base => CompositionMode::Base,
+            pb::CompositionMode::Override => CompositionMode::Override,
+            pb::CompositionMode::Strict => CompositionMode::Strict,
+        }
+    }
+}
+
+impl From<CompositionMode> for pb::CompositionMode {
+    fn from(mode: CompositionMode) -> Self {
+        match mode {
+            CompositionMode::Base => pb::CompositionMode::Base,
+            CompositionMode::Extend => pb::CompositionMode::Extend,
+            CompositionMode::Override => pb::CompositionMode::Override,
+            CompositionMode::Strict => pb::CompositionMode::Strict,
+        }
+    }
+}
+
+impl From<MergedRule> for pb::MergedRule {
+    fn from(rule: MergedRule) -> Self {
+        Self {
+            text: rule.text,
+            source_id: rule.source_id,
+            original_index: u32::try_from(rule.original_index).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+impl From<Conflict> for pb::Conflict {
+    fn from(conflict: Conflict) -> Self {
+        Self {
+            rule_a: conflict.rule_a,
+            source_a: conflict.source_a,
+            rule_b: conflict.rule_b,
+            source_b: conflict.source_b,
+            conflict_type: conflict.conflict_type,
+            resolution: conflict.resolution,
+        }
+    }
+}
+
+impl From<CompositionResult> for pb::ComposeResponse {
+    fn from(result: CompositionResult) -> Self {
+        Self {
+            merged_rules: result.merged_rules.into_iter().map(Into::into).collect(),
+            conflicts: result.conflicts.into_iter().map(Into::into).collect(),
+            warnings: result.warnings,
+            mode_used: pb::CompositionMode::from(result.mode_used).into(),
+        }
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{Duration, Utc};
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::Code;
+    use vcp_core::keys::KeyPair;
+    use vcp_core::transport::{compute_content_hash, sign_manifest};
+    use vcp_core::trust::{AnchorState, AnchorType, TrustAnchor};
+
+    const CSM1_TOKEN: &str = "\
+VCP:1.0:user-001
+C:family-safe@1.2.0
+P:N:5
+G:protect:guided:gentle
+X:no-profanity
+F:coppa
+S:internal-marker";
+
+    fn issuer_key() -> KeyPair {
+        KeyPair::from_seed(&[7u8; 32])
+    }
+
+    fn anchor(id: &str, key_id: &str, public_key: String, anchor_type: AnchorType) -> TrustAnchor {
+        TrustAnchor {
+            id: id.into(),
+            key_id: key_id.into(),
+            algorithm: "ed25519".into(),
+            public_key,
+            anchor_type,
+            valid_from: Utc::now() - Duration::days(1),
+            valid_until: Utc::now() + Duration::days(365),
+            state: AnchorState::Active,
+        }
+    }
+
+    /// Trusts creed.space under [`issuer_key`] and auditor.example.
+    fn trust_config() -> TrustConfig {
+        let mut config = TrustConfig::new();
+        let key = issuer_key().public_key_base64();
+        config.add_issuer(
+            "creed.space",
+            anchor("creed.space", "k1", key, AnchorType::Issuer),
+        );
+        config.add_auditor(
+            "auditor.example",
+            anchor(
+                "auditor.example",
+                "a1",
+                "base64:AAAA".into(),
+                AnchorType::Auditor,
+            ),
+        );
+        config
+    }
+
+    fn service() -> VcpGrpcService {
+        VcpGrpcService::new(trust_config())
+    }
+
+    /// A manifest for `content` from `issuer`, signed with `key`.
+    fn signed_manifest(content: &str, issuer: &str, key: &KeyPair, jti: &str) -> Value {
+        let hash = compute_content_hash(content).unwrap();
+        let now = Utc::now();
+        let mut manifest = json!({
+            "vcp_version": "1.0",
+            "bundle": {"id": "family.safe.guide", "version": "1.2.0", "content_hash": hash},
+            "issuer": {"id": issuer, "public_key": key.public_key_base64(), "key_id": "k1"},
+            "timestamps": {
+                "iat": now.to_rfc3339(),
+                "nbf": (now - Duration::hours(1)).to_rfc3339(),
+                "exp": (now + Duration::days(30)).to_rfc3339(),
+                "jti": jti
+            },
+            "budget": {"token_count": 10, "tokenizer": "cl100k_base"},
+            "safety_attestation": {
+                "auditor": "auditor.example",
+                "auditor_key_id": "a1",
+                "reviewed_at": now.to_rfc3339(),
+                "attestation_type": "injection-safe",
+                "signature": "base64:AAAA"
+            }
+        });
+        let value = sign_manifest(&manifest, &key.secret_bytes()).unwrap();
+        manifest["signature"] = json!({"algorithm": "ed25519", "value": value});
+        manifest
+    }
+
+    async fn verify(
+        service: &VcpGrpcService,
+        manifest: &Value,
+        content: &str,
+    ) -> v1::VerificationReport {
+        service
+            .verify_bundle(Request::new(pb::VerifyBundleRequest {
+                manifest_json: manifest.to_string().into_bytes(),
+                content: content.into(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    #[tokio::test]
+    async fn verifies_bundles_against_server_trust() {
+        let service = service();
+        let content = "Be kind to everyone.";
+        let key = issuer_key();
+
+        let manifest = signed_manifest(content, "creed.space", &key, "jti-valid");
+        let report = verify(&service, &manifest, content).await;
+        assert_eq!(
+            report.code(),
+            v1::VerificationCode::Valid,
+            "{}",
+            report.message
+        );
+
+        // The same manifest again is a replay.
+        let report = verify(&service, &manifest, content).await;
+        assert_eq!(report.code(), v1::VerificationCode::ReplayDetected);
+
+        let manifest = signed_manifest(content, "creed.space", &key, "jti-hash");
+        let report = verify(&service, &manifest, "Be unkind.").await;
+        assert_eq!(report.code(), v1::VerificationCode::HashMismatch);
+        assert!(
+            report.message.contains("hash_mismatch"),
+            "{}",
+            report.message
+        );
+
+        // The typed manifest carries its canonical JSON, signature intact.
+        let manifest = signed_manifest(content, "creed.space", &key, "jti-typed");
+        let report = service
+            .verify_bundle(Request::new(pb::VerifyBundleRequest {
+                manifest: Some(v1::Manifest::try_from(&manifest).unwrap()),
+                content: content.into(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            report.code(),
+            v1::VerificationCode::Valid,
+            "{}",
+            report.message
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_tampered_and_untrusted_bundles() {
+        let service = service();
+        let content = "Be kind to everyone.";
+        let key = issuer_key();
+
+        // Editing a signed field breaks the signature.
+        let mut tampered = signed_manifest(content, "creed.space", &key, "jti-tampered");
+        tampered["budget"]["token_count"] = json!(5);
+        let report = verify(&service, &tampered, content).await;
+        assert_eq!(report.code(), v1::VerificationCode::InvalidSignature);
+
+        // A well-signed manifest from an issuer the server does not trust.
+        let stranger = KeyPair::from_seed(&[9u8; 32]);
+        let untrusted = signed_manifest(content, "mallory.example", &stranger, "jti-stranger");
+        let report = verify(&service, &untrusted, content).await;
+        assert_eq!(report.code(), v1::VerificationCode::UntrustedIssuer);
+
+        // Nor may an untrusted key sign as a trusted issuer.
+        let forged = signed_manifest(content, "creed.space", &stranger, "jti-forged");
+        let report = verify(&service, &forged, content).await;
+        assert_eq!(report.code(), v1::VerificationCode::InvalidSignature);
+    }
+
+    #[tokio::test]
+    async fn verify_bundle_rejects_malformed_requests() {
+        let service = service();
+        let manifest = signed_manifest("x", "creed.space", &issuer_key(), "jti-both");
+
+        let err = service
+            .verify_bundle(Request::new(pb::VerifyBundleRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        let err = service
+            .verify_bundle(Request::new(pb::VerifyBundleRequest {
+                manifest: Some(v1::Manifest::try_from(&manifest).unwrap()),
+                manifest_json: manifest.to_string().into_bytes(),
+                content: "x".into(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        let err = service
+            .verify_bundle(Request::new(pb::VerifyBundleRequest {
+                manifest_json: vec![0xff, 0xfe],
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn composes_constitutions() {
+        let service = service();
+        let constitution = |id: &str, rule: &str| pb::Constitution {
+            id: id.into(),
+            rules: vec![rule.into()],
+            priority: 0,
+        };
+
+        let response = service
+            .compose(Request::new(pb::ComposeRequest {
+                constitutions: vec![
+                    constitution("a", "Be kind."),
+                    constitution("b", "Cite sources."),
+                ],
+                mode: pb::CompositionMode::Unspecified.into(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.mode_used(), pb::CompositionMode::Extend);
+        let texts: Vec<_> = response.merged_rules.iter().map(|r| &r.text).collect();
+        assert_eq!(texts, ["Be kind.", "Cite sources."]);
+        assert_eq!(response.merged_rules[1].source_id, "b");
+
+        let err = service
+            .compose(Request::new(pb::ComposeRequest {
+                constitutions: vec![
+                    constitution("a", "Always share personal data."),
+                    constitution("b", "Never share personal data."),
+                ],
+                mode: pb::CompositionMode::Strict.into(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn parses_tokens() {
+        let service = service();
+
+        let parsed = service
+            .parse_token(Request::new(pb::ParseTokenRequest {
+                token: "family.safe.guide@1.2.0".into(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(parsed.segments, ["family", "safe", "guide"]);
+        assert_eq!(parsed.version.as_deref(), Some("1.2.0"));
+        assert_eq!(parsed.canonical, "family.safe.guide");
+
+        let token = service
+            .parse_csm1_token(Request::new(pb::ParseCsm1TokenRequest {
+                token: CSM1_TOKEN.into(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(token.profile_id, "user-001");
+        assert_eq!(token.persona(), v1::Persona::Nanny);
+    }
+
+    #[tokio::test]
+    async fn parse_errors_carry_code_and_span() {
+        let err = service()
+            .parse_token(Request::new(pb::ParseTokenRequest {
+                token: "family.Safe.guide".into(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        let metadata = err.metadata();
+        assert!(metadata.get("vcp-error-code").is_some());
+        assert_eq!(metadata.get("vcp-error-offset").unwrap(), "7");
+        assert_eq!(metadata.get("vcp-error-length").unwrap(), "4");
+    }
+
+    #[tokio::test]
+    async fn exchanges_contexts() {
+        let service = service();
+
+        let morning = service
+            .decode_context(Request::new(pb::DecodeContextRequest {
+                wire: "⏰🌅|📍🏡".into(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let ctx = FullContext::try_from(morning.clone()).unwrap();
+        assert_eq!(ctx, FullContext::from_wire("⏰🌅|📍🏡").unwrap());
+
+        let encoded = service
+            .encode_context(Request::new(pb::EncodeContextRequest {
+                context: Some(morning.clone()),
+                format: pb::WireFormat::Ascii.into(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(encoded.wire, ctx.to_ascii_wire());
+
+        let evening = v1::FullContext::from(&FullContext::from_wire("⏰🌙").unwrap());
+        let merged = service
+            .merge_contexts(Request::new(pb::MergeContextsRequest {
+                contexts: vec![morning, evening],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let mut expected = ctx;
+        expected.merge(&FullContext::from_wire("⏰🌙").unwrap());
+        assert_eq!(FullContext::try_from(merged).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn client_round_trip_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service().into_server())
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let mut client = VcpServiceClient::connect(format!("http://{addr}"))
+            .await
+            .unwrap();
+        let parsed = client
+            .parse_token(pb::ParseTokenRequest {
+                token: "work.strict.review".into(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(parsed.full, "work.strict.review");
+    }
+}
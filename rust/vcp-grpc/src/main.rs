@@ -0,0 +1,49 @@
+//! `vcp-grpc` — run the VCP gRPC service.
+//!
+//! ```text
+//! vcp-grpc trust.json                  # listen on 127.0.0.1:50051
+//! vcp-grpc trust.json 0.0.0.0:50051
+//! ```
+//!
+//! `trust.json` is the [`TrustConfig`](vcp_core::trust::TrustConfig)
+//! that `VerifyBundle` checks issuers and auditors against.
+
+use std::net::SocketAddr;
+use std::process;
+
+use vcp_core::trust::TrustConfig;
+
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(trust_path) = args.next() else {
+        eprintln!("usage: vcp-grpc <trust.json> [addr]");
+        process::exit(2);
+    };
+    let trust_config = match std::fs::read_to_string(&trust_path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| TrustConfig::from_json(&json).map_err(|e| e.to_string()))
+    {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: cannot load trust config {trust_path}: {e}");
+            process::exit(1);
+        }
+    };
+    let addr = args
+        .next()
+        .unwrap_or_else(|| vcp_grpc::DEFAULT_ADDR.to_string());
+    let addr: SocketAddr = match addr.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("error: invalid address {addr}: {e}");
+            process::exit(1);
+        }
+    };
+
+    eprintln!("vcp-grpc listening on {addr}");
+    if let Err(e) = vcp_grpc::serve(addr, trust_config).await {
+        eprintln!("error: {e}");
+        process::exit(1);
+    }
+}
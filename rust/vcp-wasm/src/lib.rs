@@ -3,6 +3,12 @@
 //! WebAssembly bindings for the VCP SDK, exposing core parsing
 //! and encoding functions to JavaScript/TypeScript via `wasm-bindgen`.
 //!
+//! The generated `.d.ts` declares an interface for every object passed
+//! across the boundary (`Csm1Code`, `FullContext`, `VerificationResult`,
+//! ...), so TypeScript callers get typed results instead of `any`.
+//! Errors are thrown as `{ code, error_code, message, position?, ... }`
+//! objects (the `VcpError` interface), so front-ends can branch on `code`.
+//!
 //! ## Usage from JS
 //!
 //! ```js
@@ -17,69 +23,420 @@
 //! console.log(wire.personal.cognitive.value); // "focused"
 //! ```
 
+use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
-use vcp_core::context::FullContext;
+use vcp_core::context::{self, FullContext, SealKeyPair};
+use vcp_core::context_schema::ContextSchema;
 use vcp_core::csm1::{Csm1Code, Csm1Token};
+use vcp_core::error::VcpError;
 use vcp_core::identity::VcpToken;
+use vcp_core::keys::{self, KeyPair};
+use vcp_core::orchestrator::{Orchestrator, VerificationContext};
 use vcp_core::transport;
+use vcp_core::trust::TrustConfig;
+
+// ── Errors ──────────────────────────────────────────────────
+
+/// The object every function throws:
+/// `{ code, error_code, message, position?, length?, expected? }`.
+///
+/// `code` is the [`VcpError`] variant name and `error_code` its stable
+/// `VCP-E-NNNN` identifier. `position` and `length` are
+/// the UTF-16 range of the input at fault, and `expected` what would have
+/// been accepted there, where the parser records them.
+#[derive(Serialize)]
+struct JsError {
+    code: &'static str,
+    error_code: String,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    length: Option<usize>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    expected: Vec<String>,
+}
+
+impl JsError {
+    /// Convert `err`, translating its span to UTF-16 offsets in `input`.
+    fn js_value(err: &VcpError, input: Option<&str>) -> JsValue {
+        let utf16 = |end: usize| {
+            input.map(|s| {
+                s.get(..end.min(s.len()))
+                    .unwrap_or(s)
+                    .encode_utf16()
+                    .count()
+            })
+        };
+        let range = err.span().and_then(|span| {
+            let start = utf16(span.offset)?;
+            Some((start, utf16(span.end())? - start))
+        });
+        JsError {
+            code: err.code(),
+            error_code: err.error_code().to_string(),
+            message: err.to_string(),
+            position: range.map(|(start, _)| start),
+            length: range.map(|(_, len)| len),
+            expected: err.expected().to_vec(),
+        }
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or_else(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+fn js_error(err: impl Into<VcpError>) -> JsValue {
+    JsError::js_value(&err.into(), None)
+}
+
+/// A parse error located in `input`.
+fn parse_error(err: &VcpError, input: &str) -> JsValue {
+    JsError::js_value(err, Some(input))
+}
+
+/// A JS value that could not be converted to or from its Rust type.
+#[allow(clippy::needless_pass_by_value)]
+fn bridge_error(err: serde_wasm_bindgen::Error) -> JsValue {
+    JsError::js_value(&VcpError::JsonError(err.to_string()), None)
+}
+
+// ── TypeScript declarations ─────────────────────────────────
+
+// Interfaces for the objects the functions below return and accept.
+// They mirror the serde shapes in `vcp-core`; fields skipped when `None`
+// are optional.
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** Thrown by every function. `code` names the error kind. */
+export interface VcpError {
+  code:
+    | "ParseError" | "InvalidPersona" | "InvalidAdherence" | "InvalidIntensity"
+    | "InvalidScope" | "MalformedToken" | "HashMismatch" | "SignatureError"
+    | "JsonError" | "HookError" | "RevocationError" | "SessionError" | "ConsentError"
+    | "IoError";
+  /** Stable identifier shared across VCP SDKs, e.g. "VCP-E-1001". */
+  error_code: string;
+  message: string;
+  /** UTF-16 offset of the input region at fault, for parse errors. */
+  position?: number;
+  /** UTF-16 length of that region. */
+  length?: number;
+  /** What would have been accepted there. */
+  expected?: string[];
+}
+
+export type Persona =
+  | "Nanny" | "Sentinel" | "Godparent" | "Ambassador" | "Muse" | "Mediator" | "Custom";
+
+export type Scope =
+  | "Family" | "Work" | "Education" | "Healthcare" | "Finance" | "Legal"
+  | "Privacy" | "Safety" | "Accessibility" | "Environment" | "General";
+
+export interface Csm1Code {
+  persona: Persona;
+  /** 0 (disabled) to 5 (maximum). */
+  adherence_level: number;
+  scopes: Scope[];
+  namespace?: string;
+  version?: string;
+}
+
+export interface ConstitutionRef {
+  id: string;
+  version: string;
+}
+
+export interface GoalContext {
+  goal: string;
+  experience: string;
+  style: string;
+}
+
+export interface Csm1Token {
+  version: string;
+  profile_id: string;
+  constitution: ConstitutionRef;
+  persona: Persona;
+  adherence: number;
+  goal?: GoalContext;
+  constraints: string[];
+  flags: string[];
+  private_markers: string[];
+  personal_state?: PersonalState;
+  extensions?: [string, string][];
+}
+
+export interface SituationalContext {
+  time?: string[];
+  space?: string[];
+  company?: string[];
+  culture?: string[];
+  occasion?: string[];
+  environment?: string[];
+  agency?: string[];
+  constraints?: string[];
+  system_context?: string[];
+  embodiment?: string[];
+  proximity?: string[];
+  relationship?: string[];
+  formality?: string[];
+}
+
+export interface PersonalDimension {
+  value: string;
+  /** 1 to 5. */
+  intensity: number;
+  extended?: string;
+  source?: "Declared" | "Inferred" | "InferredLocal" | "Preset" | "Decayed";
+  /** RFC 3339. */
+  declared_at?: string;
+  /** 0 to 1, to two decimal places. */
+  confidence?: number;
+}
+
+export interface PersonalState {
+  cognitive?: PersonalDimension;
+  emotional?: PersonalDimension;
+  energy?: PersonalDimension;
+  urgency?: PersonalDimension;
+  body?: PersonalDimension;
+}
+
+export interface Consent {
+  purposes: string[];
+  /** RFC 3339 timestamp. */
+  expires_at?: string;
+  situational?: string[];
+  personal?: string[];
+  revocation?: string;
+}
+
+export interface FullContext {
+  situational: SituationalContext;
+  personal: PersonalState;
+  redactions?: string[];
+  consent?: Consent;
+}
+
+export interface ValidationIssue {
+  severity: "warning" | "error";
+  code:
+    | "invalid_intensity" | "unknown_category" | "malformed_tag"
+    | "empty_dimension" | "duplicate_tag" | "conflicting_signals";
+  path: string;
+  message: string;
+}
+
+export interface SemVer {
+  major: number;
+  minor: number;
+  patch: number;
+}
+
+export interface VcpToken {
+  segments: string[];
+  version?: SemVer;
+  namespace?: string;
+}
+
+export type VerificationCode =
+  | "valid" | "size_exceeded" | "invalid_schema" | "untrusted_issuer"
+  | "invalid_signature" | "untrusted_auditor" | "invalid_attestation"
+  | "hash_mismatch" | "not_yet_valid" | "expired" | "future_timestamp"
+  | "replay_detected" | "token_mismatch" | "budget_exceeded"
+  | "scope_mismatch" | "revoked" | "fetch_failed";
+
+export interface VerificationResult {
+  code: VerificationCode;
+  message: string;
+}
+
+export interface SchemaViolation {
+  /** JSON Pointer to the offending value; empty for the document root. */
+  pointer: string;
+  keyword: string;
+  message: string;
+}
+
+export interface VerificationOutcome {
+  code: VerificationCode;
+  /** Accepted from cached trust data. */
+  degraded: boolean;
+  /** RFC 3339; when the acceptance should be re-checked. */
+  valid_until?: string;
+  schema_errors?: SchemaViolation[];
+}
+
+export interface TrustAnchorKey {
+  id: string;
+  algorithm: string;
+  public_key: string;
+  state: "active" | "rotating" | "retired" | "compromised";
+  valid_from: string;
+  valid_until: string;
+}
+
+export interface TrustConfig {
+  trust_anchors: Record<string, { type: "issuer" | "auditor" | "organization"; keys: TrustAnchorKey[] }>;
+}
+
+/** X25519 keys for sealed contexts, as `base64:` text. */
+export interface SealKeyPair {
+  public_key: string;
+  secret_key: string;
+}
+
+export interface Capabilities {
+  sdk_version: string;
+  spec_versions: Record<string, string>;
+  hash_algorithms: string[];
+  signature_algorithms: string[];
+  hook_types: (
+    | "pre_inject" | "post_select" | "on_transition"
+    | "on_conflict" | "on_violation" | "periodic"
+  )[];
+  composition_modes: ("base" | "extend" | "override" | "strict")[];
+  features: string[];
+}
+"#;
 
 /// Parse a CSM-1 compact code (e.g. `"N5+F+E"`) and return it as a JS object.
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "Csm1Code")]
 pub fn parse_csm1(code: &str) -> Result<JsValue, JsValue> {
-    let parsed = Csm1Code::parse(code).map_err(|e| JsValue::from_str(&e.to_string()))?;
-    serde_wasm_bindgen::to_value(&parsed).map_err(|e| JsValue::from_str(&e.to_string()))
+    let parsed = Csm1Code::parse(code).map_err(|e| parse_error(&e, code))?;
+    serde_wasm_bindgen::to_value(&parsed).map_err(bridge_error)
 }
 
 /// Encode a CSM-1 compact code from a JS object back to a string.
 ///
 /// Accepts the same shape returned by `parse_csm1`.
 #[wasm_bindgen]
-pub fn encode_csm1(obj: JsValue) -> Result<String, JsValue> {
-    let code: Csm1Code =
-        serde_wasm_bindgen::from_value(obj).map_err(|e| JsValue::from_str(&e.to_string()))?;
+pub fn encode_csm1(
+    #[wasm_bindgen(unchecked_param_type = "Csm1Code")] obj: JsValue,
+) -> Result<String, JsValue> {
+    let code: Csm1Code = serde_wasm_bindgen::from_value(obj).map_err(bridge_error)?;
     Ok(code.encode())
 }
 
 /// Parse a CSM-1 8-line token string and return it as a JS object.
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "Csm1Token")]
 pub fn parse_csm1_token(token: &str) -> Result<JsValue, JsValue> {
-    let parsed = Csm1Token::parse(token).map_err(|e| JsValue::from_str(&e.to_string()))?;
-    serde_wasm_bindgen::to_value(&parsed).map_err(|e| JsValue::from_str(&e.to_string()))
+    let parsed = Csm1Token::parse(token).map_err(|e| parse_error(&e, token))?;
+    serde_wasm_bindgen::to_value(&parsed).map_err(bridge_error)
 }
 
 /// Encode a CSM-1 8-line token from a JS object back to a string.
 #[wasm_bindgen]
-pub fn encode_csm1_token(obj: JsValue) -> Result<String, JsValue> {
-    let token: Csm1Token =
-        serde_wasm_bindgen::from_value(obj).map_err(|e| JsValue::from_str(&e.to_string()))?;
+pub fn encode_csm1_token(
+    #[wasm_bindgen(unchecked_param_type = "Csm1Token")] obj: JsValue,
+) -> Result<String, JsValue> {
+    let token: Csm1Token = serde_wasm_bindgen::from_value(obj).map_err(bridge_error)?;
     Ok(token.encode())
 }
 
-/// Parse the full context wire format (situational + personal, separated by `‖`).
+/// Parse the full context wire format (situational + personal, separated by `‖`),
+/// or the ASCII-safe `ctx1;` form.
 ///
 /// Returns a JS object with `situational` and `personal` fields.
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "FullContext")]
 pub fn parse_context_wire(wire: &str) -> Result<JsValue, JsValue> {
-    let parsed = FullContext::from_wire(wire).map_err(|e| JsValue::from_str(&e.to_string()))?;
-    serde_wasm_bindgen::to_value(&parsed).map_err(|e| JsValue::from_str(&e.to_string()))
+    let parsed = FullContext::from_wire(wire).map_err(|e| parse_error(&e, wire))?;
+    serde_wasm_bindgen::to_value(&parsed).map_err(bridge_error)
 }
 
 /// Encode a full context object to wire format.
 #[wasm_bindgen]
-pub fn encode_context_wire(obj: JsValue) -> Result<String, JsValue> {
-    let ctx: FullContext =
-        serde_wasm_bindgen::from_value(obj).map_err(|e| JsValue::from_str(&e.to_string()))?;
+pub fn encode_context_wire(
+    #[wasm_bindgen(unchecked_param_type = "FullContext")] obj: JsValue,
+) -> Result<String, JsValue> {
+    let ctx: FullContext = serde_wasm_bindgen::from_value(obj).map_err(bridge_error)?;
     Ok(ctx.to_wire())
 }
 
+/// Encode a full context object to the ASCII-safe `ctx1;...` wire format.
+///
+/// `parse_context_wire` accepts either form.
+#[wasm_bindgen]
+pub fn encode_context_wire_ascii(
+    #[wasm_bindgen(unchecked_param_type = "FullContext")] obj: JsValue,
+) -> Result<String, JsValue> {
+    let ctx: FullContext = serde_wasm_bindgen::from_value(obj).map_err(bridge_error)?;
+    Ok(ctx.to_ascii_wire())
+}
+
+/// Fill unset situational dimensions of a full context object from the
+/// browser clock (local time of day and weekday/weekend).
+#[wasm_bindgen(unchecked_return_type = "FullContext")]
+pub fn infer_context_defaults(
+    #[wasm_bindgen(unchecked_param_type = "FullContext")] obj: JsValue,
+) -> Result<JsValue, JsValue> {
+    let mut ctx: FullContext = serde_wasm_bindgen::from_value(obj).map_err(bridge_error)?;
+    ctx.situational = ctx.situational.infer_defaults();
+    serde_wasm_bindgen::to_value(&ctx).map_err(bridge_error)
+}
+
+/// Validate a context wire string against the default context schema.
+///
+/// Returns an array of `{severity, code, path, message}` issues; an empty
+/// array means the context is sensible. Pass `strict` to report unknown
+/// categories as errors.
+#[wasm_bindgen(unchecked_return_type = "ValidationIssue[]")]
+pub fn validate_context(wire: &str, strict: bool) -> Result<JsValue, JsValue> {
+    let ctx = FullContext::from_wire(wire).map_err(|e| parse_error(&e, wire))?;
+    let mut schema = ContextSchema::default();
+    if strict {
+        schema = schema.strict();
+    }
+    serde_wasm_bindgen::to_value(&ctx.validate(&schema)).map_err(bridge_error)
+}
+
+/// Generate an X25519 key pair for receiving sealed contexts.
+///
+/// Publish `public_key`; keep `secret_key` for `open_context`.
+#[wasm_bindgen(unchecked_return_type = "SealKeyPair")]
+pub fn generate_seal_key_pair() -> Result<JsValue, JsValue> {
+    let key = SealKeyPair::generate();
+    serde_json::json!({
+        "public_key": key.public_key_base64(),
+        "secret_key": key.secret_key_base64(),
+    })
+    .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+    .map_err(bridge_error)
+}
+
+/// Encrypt a full context object to a recipient's X25519 public key.
+///
+/// Returns a compact `vcs1.` envelope that only the recipient can open,
+/// safe to pass through untrusted relays.
+#[wasm_bindgen]
+pub fn seal_context(
+    #[wasm_bindgen(unchecked_param_type = "FullContext")] obj: JsValue,
+    recipient_public_key_b64: &str,
+) -> Result<String, JsValue> {
+    let ctx: FullContext = serde_wasm_bindgen::from_value(obj).map_err(bridge_error)?;
+    let recipient = context::import_seal_public_key(recipient_public_key_b64).map_err(js_error)?;
+    context::seal(&ctx, &recipient).map_err(js_error)
+}
+
+/// Decrypt a `vcs1.` envelope with the recipient's X25519 secret key.
+///
+/// Throws a `SignatureError` if the envelope was sealed to another key or
+/// tampered with.
+#[wasm_bindgen(unchecked_return_type = "FullContext")]
+pub fn open_context(envelope: &str, secret_key_b64: &str) -> Result<JsValue, JsValue> {
+    let key = SealKeyPair::import(secret_key_b64).map_err(js_error)?;
+    let ctx = context::open(envelope, &key).map_err(js_error)?;
+    serde_wasm_bindgen::to_value(&ctx).map_err(bridge_error)
+}
+
 /// Validate a VCP/I identity token (e.g. `"family.safe.guide@1.2.0"`).
 ///
 /// Returns the parsed token as a JS object on success.
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "VcpToken")]
 pub fn validate_token(token: &str) -> Result<JsValue, JsValue> {
-    let parsed = VcpToken::parse(token).map_err(|e| JsValue::from_str(&e.to_string()))?;
-    serde_wasm_bindgen::to_value(&parsed).map_err(|e| JsValue::from_str(&e.to_string()))
+    let parsed = VcpToken::parse(token).map_err(|e| parse_error(&e, token))?;
+    serde_wasm_bindgen::to_value(&parsed).map_err(bridge_error)
 }
 
 /// Compute the SHA-256 content hash of constitution text.
@@ -87,7 +444,7 @@ pub fn validate_token(token: &str) -> Result<JsValue, JsValue> {
 /// Returns a string in the format `"sha256:<hex>"`.
 #[wasm_bindgen]
 pub fn hash_content(content: &str) -> Result<String, JsValue> {
-    transport::compute_content_hash(content).map_err(|e| JsValue::from_str(&e.to_string()))
+    transport::compute_content_hash(content).map_err(js_error)
 }
 
 /// Verify that content matches an expected hash.
@@ -95,16 +452,167 @@ pub fn hash_content(content: &str) -> Result<String, JsValue> {
 /// Returns `true` if the hash matches.
 #[wasm_bindgen]
 pub fn verify_hash(content: &str, expected_hash: &str) -> Result<bool, JsValue> {
-    transport::verify_content_hash(content, expected_hash)
-        .map_err(|e| JsValue::from_str(&e.to_string()))
+    transport::verify_content_hash(content, expected_hash).map_err(js_error)
 }
 
 /// Verify a bundle (manifest JSON + content).
 ///
 /// Returns a JS object with `code` and `message` fields.
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "VerificationResult")]
 pub fn verify_bundle(manifest_json: &str, content: &str) -> Result<JsValue, JsValue> {
-    let result = transport::verify_bundle(manifest_json, content)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
-    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+    let result = transport::verify_bundle(manifest_json, content).map_err(js_error)?;
+    serde_wasm_bindgen::to_value(&result).map_err(bridge_error)
+}
+
+/// Incremental content hasher for large constitutions.
+///
+/// Feed `Uint8Array` chunks (e.g. from a `ReadableStream` reader) with
+/// `update`, then call `finalize` for the `"sha256:<hex>"` hash or pass
+/// the hasher to `verify_bundle_streaming`. Chunks may split a UTF-8
+/// character, and the content is never held in memory as a whole.
+///
+/// ```js
+/// const hasher = new ContentHasher();
+/// for await (const chunk of response.body) hasher.update(chunk);
+/// const result = verify_bundle_streaming(manifestJson, hasher);
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct ContentHasher {
+    inner: transport::ContentHasher,
+}
+
+#[wasm_bindgen]
+impl ContentHasher {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next chunk of UTF-8 bytes.
+    pub fn update(&mut self, chunk: &[u8]) -> Result<(), JsValue> {
+        self.inner.update_bytes(chunk).map_err(js_error)
+    }
+
+    /// Finish hashing and return `"sha256:<hex>"`. The hasher cannot be
+    /// used afterwards.
+    pub fn finalize(self) -> Result<String, JsValue> {
+        self.inner.finalize().map_err(js_error)
+    }
+}
+
+/// Verify a bundle whose content was streamed into a `ContentHasher`.
+///
+/// Consumes the hasher. Returns the same `{code, message}` object as
+/// `verify_bundle`.
+#[wasm_bindgen(unchecked_return_type = "VerificationResult")]
+pub fn verify_bundle_streaming(
+    manifest_json: &str,
+    hasher: ContentHasher,
+) -> Result<JsValue, JsValue> {
+    let result =
+        transport::verify_bundle_streaming(manifest_json, hasher.inner).map_err(js_error)?;
+    serde_wasm_bindgen::to_value(&result).map_err(bridge_error)
+}
+
+/// Sign a manifest with an Ed25519 secret key.
+///
+/// `secret_key_b64` is the 32-byte seed in base64, with or without the
+/// `base64:` prefix. Returns the base64 signature over the canonical
+/// manifest, ready for the manifest's `signature.value` field.
+#[wasm_bindgen]
+pub fn sign_manifest(manifest_json: &str, secret_key_b64: &str) -> Result<String, JsValue> {
+    let manifest: serde_json::Value = serde_json::from_str(manifest_json).map_err(js_error)?;
+    let key = KeyPair::import(secret_key_b64.as_bytes()).map_err(js_error)?;
+    transport::sign_manifest(&manifest, &key.secret_bytes()).map_err(js_error)
+}
+
+/// Verify a base64 Ed25519 signature over a manifest.
+///
+/// `public_key_b64` takes the same form as a trust anchor's `public_key`.
+/// Returns `false` for a well-formed signature that does not match.
+#[wasm_bindgen]
+pub fn verify_signature(
+    manifest_json: &str,
+    public_key_b64: &str,
+    sig: &str,
+) -> Result<bool, JsValue> {
+    let manifest: serde_json::Value = serde_json::from_str(manifest_json).map_err(js_error)?;
+    let public_key = keys::import_public_key(public_key_b64.as_bytes()).map_err(js_error)?;
+    transport::verify_manifest_signature(&manifest, &public_key, sig).map_err(js_error)
+}
+
+/// Parse and validate a trust config JSON document.
+///
+/// Returns the normalized `{trust_anchors: {...}}` object, or throws with
+/// the first problem found.
+#[wasm_bindgen(unchecked_return_type = "TrustConfig")]
+pub fn parse_trust_config(json: &str) -> Result<JsValue, JsValue> {
+    let config = TrustConfig::from_json(json).map_err(js_error)?;
+    config
+        .to_dict()
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(bridge_error)
+}
+
+/// The full verification pipeline: trust anchors, signatures, temporal
+/// claims (`nbf`, `exp`, `iat`) and replay detection.
+///
+/// Time is read from the browser's `Date.now()`. The replay cache lives
+/// as long as the object, so keep one verifier per trust config rather
+/// than creating one per bundle.
+///
+/// ```js
+/// const verifier = new Verifier(trustConfigJson);
+/// const outcome = verifier.verify(manifestJson, content);
+/// if (outcome.code === "expired") showRenewalPrompt();
+/// ```
+#[wasm_bindgen]
+pub struct Verifier {
+    orchestrator: Orchestrator,
+    context: VerificationContext,
+}
+
+#[wasm_bindgen]
+impl Verifier {
+    /// Create a verifier trusting the anchors in a trust config JSON
+    /// document. Throws if the config is invalid.
+    #[wasm_bindgen(constructor)]
+    pub fn new(trust_config_json: &str) -> Result<Verifier, JsValue> {
+        let trust = TrustConfig::from_json(trust_config_json).map_err(js_error)?;
+        let orchestrator = Orchestrator::new(trust);
+        let context = orchestrator.verification_context();
+        Ok(Self {
+            orchestrator,
+            context,
+        })
+    }
+
+    /// Verify a manifest and its content, recording the manifest's `jti`
+    /// so a second presentation fails with `replay_detected`.
+    #[wasm_bindgen(unchecked_return_type = "VerificationOutcome")]
+    pub fn verify(&self, manifest_json: &str, content: &str) -> Result<JsValue, JsValue> {
+        self.orchestrator
+            .verify_outcome(manifest_json, content, &self.context)
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .map_err(bridge_error)
+    }
+
+    /// Number of JTIs held in the replay cache.
+    #[wasm_bindgen(getter)]
+    pub fn replay_entries(&self) -> usize {
+        self.orchestrator.replay_stats().entries
+    }
+}
+
+/// Describe what this build supports (spec versions, algorithms, hook
+/// types, composition modes and enabled features).
+///
+/// Returned as a plain JS object so it can be passed straight to
+/// `JSON.stringify` and sent to a peer.
+#[wasm_bindgen(unchecked_return_type = "Capabilities")]
+pub fn capabilities() -> Result<JsValue, JsValue> {
+    vcp_core::capabilities()
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(bridge_error)
 }
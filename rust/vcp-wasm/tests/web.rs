@@ -0,0 +1,98 @@
+//! Orchestrator checks that read the clock, run under wasm-bindgen.
+//!
+//! `SystemTime::now()` panics on `wasm32-unknown-unknown`, so these cover
+//! the paths that need "now": `nbf`, `exp` and the replay cache.
+//!
+//! Run with `wasm-pack test --node vcp-wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use serde_json::{json, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use vcp_wasm::{hash_content, Verifier};
+
+const CONTENT: &str = "Be helpful and honest.";
+
+fn trust_config() -> String {
+    let key = |id: &str| {
+        json!({
+            "id": id,
+            "algorithm": "ed25519",
+            "public_key": "base64:AAAA",
+            "valid_from": "2000-01-01T00:00:00Z",
+            "valid_until": "2999-01-01T00:00:00Z",
+        })
+    };
+    json!({
+        "trust_anchors": {
+            "test-issuer": {"type": "issuer", "keys": [key("key-01")]},
+            "test-auditor": {"type": "auditor", "keys": [key("aud-key-01")]},
+        }
+    })
+    .to_string()
+}
+
+fn manifest(jti: &str, nbf: &str, exp: &str) -> String {
+    json!({
+        "vcp_version": "2.0",
+        "bundle": {
+            "id": "test-bundle",
+            "version": "1.0.0",
+            "content_hash": hash_content(CONTENT).unwrap(),
+        },
+        "issuer": {"id": "test-issuer", "key_id": "key-01"},
+        "safety_attestation": {
+            "auditor": "test-auditor",
+            "auditor_key_id": "aud-key-01",
+            "attestation_type": "injection-safe",
+            "signature": "base64:fake-sig",
+        },
+        "timestamps": {"nbf": nbf, "exp": exp, "jti": jti},
+    })
+    .to_string()
+}
+
+fn code(verifier: &Verifier, manifest: &str) -> String {
+    let outcome: Value =
+        serde_wasm_bindgen::from_value(verifier.verify(manifest, CONTENT).unwrap()).unwrap();
+    outcome["code"].as_str().unwrap().to_string()
+}
+
+#[wasm_bindgen_test]
+fn current_manifest_is_valid() {
+    let verifier = Verifier::new(&trust_config()).unwrap();
+    let current = manifest(
+        "jti-current",
+        "2000-01-01T00:00:00Z",
+        "2999-01-01T00:00:00Z",
+    );
+    assert_eq!(code(&verifier, &current), "valid");
+}
+
+#[wasm_bindgen_test]
+fn past_exp_is_expired() {
+    let verifier = Verifier::new(&trust_config()).unwrap();
+    let expired = manifest(
+        "jti-expired",
+        "2000-01-01T00:00:00Z",
+        "2001-01-01T00:00:00Z",
+    );
+    assert_eq!(code(&verifier, &expired), "expired");
+}
+
+#[wasm_bindgen_test]
+fn future_nbf_is_not_yet_valid() {
+    let verifier = Verifier::new(&trust_config()).unwrap();
+    let early = manifest("jti-early", "2998-01-01T00:00:00Z", "2999-01-01T00:00:00Z");
+    assert_eq!(code(&verifier, &early), "not_yet_valid");
+}
+
+#[wasm_bindgen_test]
+fn second_presentation_is_a_replay() {
+    let verifier = Verifier::new(&trust_config()).unwrap();
+    let bundle = manifest("jti-replay", "2000-01-01T00:00:00Z", "2999-01-01T00:00:00Z");
+    assert_eq!(code(&verifier, &bundle), "valid");
+    assert_eq!(verifier.replay_entries(), 1);
+    assert_eq!(code(&verifier, &bundle), "replay_detected");
+}